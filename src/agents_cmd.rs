@@ -0,0 +1,48 @@
+//! Handler for the `agents` subcommand (main thread vs subagent split).
+//!
+//! Lives in its own module to keep `app.rs` under the module size limit.
+
+use crate::app::{CommandContext, print_json_rows, print_no_data_hint};
+use crate::output::{
+    AgentTableOptions, DataKind, OutputFormat, agent_json_rows, output_agent_csv, print_agent_table,
+};
+use crate::source::{Source, load_agents};
+
+pub(crate) fn handle_agents(source: &dyn Source, ctx: &CommandContext<'_>) {
+    if !source.capabilities().has_subagents {
+        println!(
+            "{} does not mark subagent requests.\nHint: switch with `--source claude` (or alias `--source cc`); for Codex use `--codex-scope subagent`.",
+            source.display_name()
+        );
+        return;
+    }
+
+    let sessions = load_agents(source, ctx.filter, ctx.timezone);
+    if sessions.is_empty() {
+        print_no_data_hint(ctx.locale, source.display_name(), DataKind::Agent);
+        return;
+    }
+
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => print!(
+            "{}",
+            output_agent_csv(&sessions, ctx.pricing_db, ctx.cli.show_cost(), ctx.currency)
+        ),
+        OutputFormat::Json => {
+            let rows =
+                agent_json_rows(&sessions, ctx.pricing_db, ctx.cli.show_cost(), ctx.currency);
+            print_json_rows(rows, true, ctx);
+        }
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => print_agent_table(
+            &sessions,
+            ctx.pricing_db,
+            AgentTableOptions {
+                use_color: ctx.cli.use_color(),
+                show_cost: ctx.cli.show_cost(),
+                source_label: source.display_name(),
+                locale: ctx.locale,
+                currency: ctx.currency,
+            },
+        ),
+    }
+}
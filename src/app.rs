@@ -1,34 +1,77 @@
 use std::fmt::Write as _;
 use std::time::Instant;
 
-use crate::cli::{Cli, SourceCommand, TopDimension};
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::cli::{Cli, SourceCommand, ToolGrouping, TopDimension};
 use crate::core::{
-    BlockStats, DateFilter, LoadResult, ProjectStats, SessionStats, ToolSummary, aggregate_tools,
-    merge_day_stats,
+    BlockStats, DataQuality, DateFilter, LoadResult, ProjectStats, SessionStats, ToolCall,
+    ToolSummary, aggregate_commands, aggregate_files, aggregate_tools, aggregate_tools_by,
+    format_project_name, merge_day_stats, server_and_tool, tool_category,
 };
-use crate::output::NumberFormat;
 use crate::output::{
-    BlockTableOptions, MonthlyBudgetOptions, OutputFormat, Period, PeriodSummaryFooter,
-    ProjectTableOptions, SessionTableOptions, TokenTableOptions, TopRow, TopTableOptions,
-    add_monthly_budget_to_json, append_data_quality_csv_comment, monthly_budget_reports,
-    output_block_csv, output_block_json, output_monthly_budget_csv, output_period_csv_with_quality,
-    output_period_json_with_quality, output_project_csv, output_project_json, output_session_csv,
-    output_session_json, output_tools_csv, output_tools_json, output_top_csv, output_top_json,
-    print_block_table, print_monthly_budget_table, print_period_table, print_project_table,
-    print_session_table, print_statusline, print_statusline_json_with_quality, print_tools_table,
-    print_top_table, rank_by_model, rank_by_model_with_cost_mode, rank_by_project,
+    ActiveBlock, BlockTableOptions, Markup, OutputFormat, Period, ProjectTableOptions,
+    SessionTableOptions, StatuslineOptions, ToolReportOptions, TopRow, TopTableOptions,
+    active_block, block_json_rows, commands_json_report, files_json_report, output_block_csv,
+    output_block_json, output_commands_csv, output_files_csv, output_project_csv,
+    output_project_json, output_session_csv, output_session_json, output_tools_csv, output_top_csv,
+    print_block_table, print_commands_table, print_files_table, print_ndjson, print_project_table,
+    print_session_table, print_statusline, print_tools_table, print_top_table, project_json_rows,
+    rank_by_model, rank_by_model_with_cost_mode, rank_by_project, session_json_rows,
+    statusline_json_report, template_uses, tools_json_report, top_json_report,
 };
+use crate::output::{DataKind, Locale, report_to_string, rows_to_string};
 use crate::pricing::{CostDisplayMode, PricingDb};
 use crate::source::{
-    Capabilities, CodexScope, Source, all_capabilities, all_sources, load_blocks, load_daily,
-    load_projects, load_sessions, load_tool_calls,
+    Capabilities, CodexScope, CodexWeeklyQuota, Source, all_capabilities, all_sources, load_blocks,
+    load_daily, load_projects, load_sessions, load_tool_calls, load_weekly_quota,
 };
 use crate::utils::{Timezone, filter_json};
 
-/// Print JSON output, optionally filtering through jq
-pub(crate) fn print_json(json: &str, jq_filter: Option<&str>) {
-    match jq_filter {
-        Some(filter) => match filter_json(json, filter) {
+/// Print a typed JSON report: one document, or a single line with `--ndjson`
+pub(crate) fn print_json_report<T: Serialize>(report: &T, pretty: bool, ctx: &CommandContext<'_>) {
+    if ctx.cli.ndjson {
+        return print_ndjson(None, |out| out.row(report));
+    }
+    print_json(&report_to_string(report, pretty), ctx);
+}
+
+/// Print typed JSON rows: an array, or one line per row with `--ndjson`,
+/// written as the iterator yields it
+pub(crate) fn print_json_rows<T: Serialize>(
+    rows: impl IntoIterator<Item = T>,
+    pretty: bool,
+    ctx: &CommandContext<'_>,
+) {
+    if ctx.cli.ndjson {
+        return print_ndjson(None, |out| out.rows(rows));
+    }
+    let rows: Vec<T> = rows.into_iter().collect();
+    print_json(&rows_to_string(&rows, pretty), ctx);
+}
+
+/// [`print_json_report`] for a report tagged with the Codex scope it covers
+fn print_codex_json_report<T: Serialize>(
+    report: &T,
+    scope: Option<CodexScope>,
+    ctx: &CommandContext<'_>,
+) {
+    if ctx.cli.ndjson {
+        return print_ndjson(scope.map(|scope| ("codex_scope", scope.as_str())), |out| {
+            out.row(report)
+        });
+    }
+    let json = annotate_json_codex_scope(&report_to_string(report, false), scope);
+    print_json(&json, ctx);
+}
+
+/// Print a rendered JSON document, optionally filtering through jq. Commands
+/// supporting `--ndjson` go through [`print_json_report`] or
+/// [`print_json_rows`] instead.
+pub(crate) fn print_json(json: &str, ctx: &CommandContext<'_>) {
+    match ctx.jq_filter {
+        Some(filter) => match filter_json(json, filter, ctx.cli.jq_fallback) {
             Ok(filtered) => print!("{filtered}"),
             Err(e) => {
                 eprintln!("{e}");
@@ -44,16 +87,23 @@ pub(crate) struct CommandContext<'a> {
     pub(crate) cli: &'a Cli,
     pub(crate) pricing_db: &'a PricingDb,
     pub(crate) timezone: Timezone,
-    pub(crate) number_format: NumberFormat,
+    pub(crate) locale: Locale,
     pub(crate) jq_filter: Option<&'a str>,
     pub(crate) currency: Option<&'a crate::pricing::CurrencyConverter>,
     pub(crate) budget_as_of: chrono::NaiveDate,
 }
 
-pub(crate) fn print_no_data_hint(source_name: &str, category: &str) {
-    println!(
-        "No {source_name} {category} data found in the selected date range.\nHint: widen --since/--until, try `today`, or run `ccstats sources` to pick a different --source."
-    );
+impl CommandContext<'_> {
+    /// Whether costs are converted at daily rates, which needs the per-day
+    /// model stats of sessions, projects and blocks.
+    pub(crate) fn daily_rates(&self) -> bool {
+        self.currency
+            .is_some_and(crate::pricing::CurrencyConverter::is_historical)
+    }
+}
+
+pub(crate) fn print_no_data_hint(locale: Locale, source_name: &str, kind: DataKind) {
+    println!("{}", locale.no_data_hint(source_name, kind));
 }
 
 fn codex_scope_for_source(source: &dyn Source, ctx: &CommandContext<'_>) -> Option<CodexScope> {
@@ -68,7 +118,7 @@ fn source_label(source: &dyn Source, ctx: &CommandContext<'_>) -> String {
     }
 }
 
-fn annotate_json_codex_scope(json: &str, scope: Option<CodexScope>) -> String {
+pub(crate) fn annotate_json_codex_scope(json: &str, scope: Option<CodexScope>) -> String {
     let Some(scope) = scope else {
         return json.to_string();
     };
@@ -99,7 +149,7 @@ fn annotate_json_codex_scope(json: &str, scope: Option<CodexScope>) -> String {
     serde_json::to_string(&value).unwrap_or_else(|_| json.to_string())
 }
 
-fn annotate_csv_codex_scope(mut csv: String, scope: Option<CodexScope>) -> String {
+pub(crate) fn annotate_csv_codex_scope(mut csv: String, scope: Option<CodexScope>) -> String {
     if let Some(scope) = scope {
         if !csv.ends_with('\n') {
             csv.push('\n');
@@ -109,9 +159,9 @@ fn annotate_csv_codex_scope(mut csv: String, scope: Option<CodexScope>) -> Strin
     csv
 }
 
-fn print_codex_scope_note(scope: Option<CodexScope>) {
+pub(crate) fn print_codex_scope_note(scope: Option<CodexScope>, markup: Markup) {
     if let Some(scope) = scope {
-        println!("\n  Codex scope: {}", scope.as_str());
+        markup.line(&format!("\n  Codex scope: {}", scope.as_str()));
     }
 }
 
@@ -125,16 +175,30 @@ fn should_render_empty_structured_result(result: &LoadResult, ctx: &CommandConte
 }
 
 fn handle_session(source: &dyn Source, ctx: &CommandContext<'_>) {
-    let sessions = load_sessions(source, ctx.filter, ctx.timezone, false);
+    let (sessions, data_quality) =
+        load_sessions(source, ctx.filter, ctx.timezone, false, ctx.daily_rates());
     if sessions.is_empty() {
-        print_no_data_hint(&source_label(source, ctx), "session");
+        print_no_data_hint(ctx.locale, &source_label(source, ctx), DataKind::Session);
         return;
     }
 
-    render_session(&sessions, source, ctx);
+    if let Some(threshold) = ctx.cli.session_context_threshold() {
+        return crate::session_cmd::render_session_context(
+            &sessions,
+            threshold,
+            &source_label(source, ctx),
+            ctx,
+        );
+    }
+    render_session(&sessions, data_quality, source, ctx);
 }
 
-fn render_session(sessions: &[SessionStats], source: &dyn Source, ctx: &CommandContext<'_>) {
+fn render_session(
+    sessions: &[SessionStats],
+    data_quality: DataQuality,
+    source: &dyn Source,
+    ctx: &CommandContext<'_>,
+) {
     let scope = codex_scope_for_source(source, ctx);
     match ctx.cli.output_format() {
         OutputFormat::Csv => {
@@ -148,6 +212,20 @@ fn render_session(sessions: &[SessionStats], source: &dyn Source, ctx: &CommandC
             );
             print!("{}", annotate_csv_codex_scope(csv, scope));
         }
+        OutputFormat::Json if ctx.cli.ndjson => {
+            let rows = session_json_rows(
+                sessions,
+                ctx.pricing_db,
+                ctx.cli.sort_order(),
+                ctx.cli.show_cost(),
+                source.capabilities().has_cache_read,
+                ctx.currency,
+            );
+            print_ndjson(scope.map(|scope| ("codex_scope", scope.as_str())), |out| {
+                out.rows(rows)?;
+                out.data_quality(data_quality)
+            });
+        }
         OutputFormat::Json => {
             let json = output_session_json(
                 sessions,
@@ -158,9 +236,9 @@ fn render_session(sessions: &[SessionStats], source: &dyn Source, ctx: &CommandC
                 ctx.currency,
             );
             let json = annotate_json_codex_scope(&json, scope);
-            print_json(&json, ctx.jq_filter);
+            print_json(&json, ctx);
         }
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
             let label = source_label(source, ctx);
             print_session_table(
                 sessions,
@@ -171,10 +249,11 @@ fn render_session(sessions: &[SessionStats], source: &dyn Source, ctx: &CommandC
                     compact: ctx.cli.compact,
                     show_cost: ctx.cli.show_cost(),
                     supports_cache_read: source.capabilities().has_cache_read,
-                    number_format: ctx.number_format,
+                    locale: ctx.locale,
                     source_label: &label,
                     timezone: ctx.timezone,
                     currency: ctx.currency,
+                    markup: ctx.cli.output_format().markup(),
                 },
             );
         }
@@ -182,16 +261,22 @@ fn render_session(sessions: &[SessionStats], source: &dyn Source, ctx: &CommandC
 }
 
 fn handle_project(source: &dyn Source, ctx: &CommandContext<'_>) {
-    let projects = load_projects(source, ctx.filter, ctx.timezone, false);
+    let (projects, data_quality) =
+        load_projects(source, ctx.filter, ctx.timezone, false, ctx.daily_rates());
     if projects.is_empty() {
-        print_no_data_hint(source.display_name(), "project");
+        print_no_data_hint(ctx.locale, source.display_name(), DataKind::Project);
         return;
     }
 
-    render_project(&projects, source, ctx);
+    render_project(&projects, data_quality, source, ctx);
 }
 
-fn render_project(projects: &[ProjectStats], source: &dyn Source, ctx: &CommandContext<'_>) {
+fn render_project(
+    projects: &[ProjectStats],
+    data_quality: DataQuality,
+    source: &dyn Source,
+    ctx: &CommandContext<'_>,
+) {
     match ctx.cli.output_format() {
         OutputFormat::Csv => {
             let csv = output_project_csv(
@@ -204,6 +289,20 @@ fn render_project(projects: &[ProjectStats], source: &dyn Source, ctx: &CommandC
             );
             print!("{csv}");
         }
+        OutputFormat::Json if ctx.cli.ndjson => {
+            let rows = project_json_rows(
+                projects,
+                ctx.pricing_db,
+                ctx.cli.sort_order(),
+                ctx.cli.show_cost(),
+                source.capabilities().has_cache_read,
+                ctx.currency,
+            );
+            print_ndjson(None, |out| {
+                out.rows(rows)?;
+                out.data_quality(data_quality)
+            });
+        }
         OutputFormat::Json => {
             let json = output_project_json(
                 projects,
@@ -213,9 +312,9 @@ fn render_project(projects: &[ProjectStats], source: &dyn Source, ctx: &CommandC
                 source.capabilities().has_cache_read,
                 ctx.currency,
             );
-            print_json(&json, ctx.jq_filter);
+            print_json(&json, ctx);
         }
-        OutputFormat::Table => print_project_table(
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => print_project_table(
             projects,
             ctx.pricing_db,
             ProjectTableOptions {
@@ -225,24 +324,31 @@ fn render_project(projects: &[ProjectStats], source: &dyn Source, ctx: &CommandC
                 show_cost: ctx.cli.show_cost(),
                 supports_cache_read: source.capabilities().has_cache_read,
                 source_label: source.display_name(),
-                number_format: ctx.number_format,
+                locale: ctx.locale,
                 currency: ctx.currency,
+                markup: ctx.cli.output_format().markup(),
             },
         ),
     }
 }
 
 fn handle_blocks(source: &dyn Source, ctx: &CommandContext<'_>) {
-    let blocks = load_blocks(source, ctx.filter, ctx.timezone, false);
+    let (blocks, data_quality) =
+        load_blocks(source, ctx.filter, ctx.timezone, false, ctx.daily_rates());
     if blocks.is_empty() {
-        print_no_data_hint(source.display_name(), "billing block");
+        print_no_data_hint(ctx.locale, source.display_name(), DataKind::BillingBlock);
         return;
     }
 
-    render_blocks(&blocks, source, ctx);
+    render_blocks(&blocks, data_quality, source, ctx);
 }
 
-fn render_blocks(blocks: &[BlockStats], source: &dyn Source, ctx: &CommandContext<'_>) {
+fn render_blocks(
+    blocks: &[BlockStats],
+    data_quality: DataQuality,
+    source: &dyn Source,
+    ctx: &CommandContext<'_>,
+) {
     match ctx.cli.output_format() {
         OutputFormat::Csv => {
             let csv = output_block_csv(
@@ -255,6 +361,20 @@ fn render_blocks(blocks: &[BlockStats], source: &dyn Source, ctx: &CommandContex
             );
             print!("{csv}");
         }
+        OutputFormat::Json if ctx.cli.ndjson => {
+            let rows = block_json_rows(
+                blocks,
+                ctx.pricing_db,
+                ctx.cli.sort_order(),
+                ctx.cli.show_cost(),
+                source.capabilities().has_cache_read,
+                ctx.currency,
+            );
+            print_ndjson(None, |out| {
+                out.rows(rows)?;
+                out.data_quality(data_quality)
+            });
+        }
         OutputFormat::Json => {
             let json = output_block_json(
                 blocks,
@@ -264,9 +384,9 @@ fn render_blocks(blocks: &[BlockStats], source: &dyn Source, ctx: &CommandContex
                 source.capabilities().has_cache_read,
                 ctx.currency,
             );
-            print_json(&json, ctx.jq_filter);
+            print_json(&json, ctx);
         }
-        OutputFormat::Table => print_block_table(
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => print_block_table(
             blocks,
             ctx.pricing_db,
             BlockTableOptions {
@@ -276,8 +396,9 @@ fn render_blocks(blocks: &[BlockStats], source: &dyn Source, ctx: &CommandContex
                 show_cost: ctx.cli.show_cost(),
                 supports_cache_read: source.capabilities().has_cache_read,
                 source_label: source.display_name(),
-                number_format: ctx.number_format,
+                locale: ctx.locale,
                 currency: ctx.currency,
+                markup: ctx.cli.output_format().markup(),
             },
         ),
     }
@@ -299,7 +420,7 @@ fn handle_top(
     ctx: &CommandContext<'_>,
 ) {
     if rows.is_empty() {
-        print_no_data_hint(options.source_label, "usage");
+        print_no_data_hint(ctx.locale, options.source_label, DataKind::Usage);
         return;
     }
 
@@ -316,7 +437,7 @@ fn handle_top(
             print!("{}", annotate_csv_codex_scope(csv, options.codex_scope));
         }
         OutputFormat::Json => {
-            let json = output_top_json(
+            let report = top_json_report(
                 rows,
                 dim,
                 limit,
@@ -324,10 +445,9 @@ fn handle_top(
                 options.supports_cache_read,
                 ctx.currency,
             );
-            let json = annotate_json_codex_scope(&json, options.codex_scope);
-            print_json(&json, ctx.jq_filter);
+            print_codex_json_report(&report, options.codex_scope, ctx);
         }
-        OutputFormat::Table => print_top_table(
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => print_top_table(
             rows,
             TopTableOptions {
                 use_color: ctx.cli.use_color(),
@@ -335,11 +455,12 @@ fn handle_top(
                 show_cost: ctx.cli.show_cost(),
                 supports_cache_read: options.supports_cache_read,
                 source_label: options.source_label,
-                number_format: ctx.number_format,
+                locale: ctx.locale,
                 currency: ctx.currency,
                 dim,
                 limit,
                 cost_mode: options.cost_mode,
+                markup: ctx.cli.output_format().markup(),
             },
         ),
     }
@@ -378,7 +499,8 @@ fn handle_top_for_source(
                 );
                 return;
             }
-            let projects = load_projects(source, ctx.filter, ctx.timezone, false);
+            let (projects, _) =
+                load_projects(source, ctx.filter, ctx.timezone, false, ctx.daily_rates());
             let rows = rank_by_project(&projects, ctx.pricing_db);
             handle_top(
                 &rows,
@@ -406,173 +528,210 @@ fn validate_top_limit(limit: usize) -> Result<usize, String> {
     }
 }
 
-fn handle_tools(source: &dyn Source, ctx: &CommandContext<'_>) {
+fn handle_tools(source: &dyn Source, group_by: ToolGrouping, ctx: &CommandContext<'_>) {
     let calls = load_tool_calls(source, ctx.filter, ctx.timezone);
-    let summary = aggregate_tools(&calls);
+    render_tool_calls(&calls, group_by, false, ctx);
+}
 
-    render_tools(&summary, ctx);
+/// Cross-agent tool report: every source that records tool calls, with a
+/// source column in the default per-tool view.
+fn handle_all_tools(group_by: ToolGrouping, ctx: &CommandContext<'_>) {
+    render_tool_calls(&load_all_tool_calls(ctx), group_by, true, ctx);
 }
 
-fn render_tools(summary: &ToolSummary, ctx: &CommandContext<'_>) {
+fn load_all_tool_calls(ctx: &CommandContext<'_>) -> Vec<ToolCall> {
+    all_sources()
+        .flat_map(|source| load_tool_calls(source, ctx.filter, ctx.timezone))
+        .collect()
+}
+
+fn render_tool_commands(calls: &[ToolCall], limit: usize, ctx: &CommandContext<'_>) {
+    let limit = match validate_top_limit(limit) {
+        Ok(l) => l,
+        Err(msg) => {
+            eprintln!("Error: {msg}");
+            std::process::exit(1);
+        }
+    };
+    let summary = aggregate_commands(calls);
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => print!("{}", output_commands_csv(&summary, limit)),
+        OutputFormat::Json => print_json_report(&commands_json_report(&summary, limit), false, ctx),
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            print_commands_table(&summary, limit, ctx.locale, ctx.cli.use_color());
+        }
+    }
+}
+
+fn render_files(calls: &[ToolCall], limit: usize, ctx: &CommandContext<'_>) {
+    let limit = match validate_top_limit(limit) {
+        Ok(l) => l,
+        Err(msg) => {
+            eprintln!("Error: {msg}");
+            std::process::exit(1);
+        }
+    };
+    let summary = aggregate_files(calls);
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => print!("{}", output_files_csv(&summary, limit)),
+        OutputFormat::Json => print_json_report(&files_json_report(&summary, limit), false, ctx),
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            print_files_table(&summary, limit, ctx.locale, ctx.cli.use_color());
+        }
+    }
+}
+
+fn render_tool_calls(
+    calls: &[ToolCall],
+    group_by: ToolGrouping,
+    by_source: bool,
+    ctx: &CommandContext<'_>,
+) {
+    let (summary, group_label) = match group_by {
+        ToolGrouping::Tool if by_source => (
+            aggregate_tools_by(calls, |call| {
+                (Some(call.source.to_string()), call.name.clone())
+            }),
+            Some("source"),
+        ),
+        ToolGrouping::Tool => (aggregate_tools(calls), None),
+        ToolGrouping::Category => (
+            aggregate_tools_by(calls, |call| {
+                (
+                    Some(tool_category(&call.name).to_string()),
+                    call.name.clone(),
+                )
+            }),
+            Some("category"),
+        ),
+        ToolGrouping::Project => (
+            aggregate_tools_by(calls, |call| {
+                (
+                    Some(format_project_name(&call.project_path)),
+                    call.name.clone(),
+                )
+            }),
+            Some("project"),
+        ),
+        ToolGrouping::Day => (
+            aggregate_tools_by(calls, |call| {
+                (Some(call.date_str.clone()), call.name.clone())
+            }),
+            Some("date"),
+        ),
+        ToolGrouping::Server => (
+            aggregate_tools_by(calls, |call| {
+                let (server, tool) = server_and_tool(call);
+                (Some(server), tool)
+            }),
+            Some("server"),
+        ),
+    };
+
+    render_tools(&summary, group_label, group_by, ctx);
+}
+
+fn render_tools(
+    summary: &ToolSummary,
+    group_label: Option<&'static str>,
+    group_by: ToolGrouping,
+    ctx: &CommandContext<'_>,
+) {
+    let options = ToolReportOptions {
+        group_label,
+        daily_series: group_by == ToolGrouping::Server,
+        show_cost: ctx.cli.show_cost(),
+        locale: ctx.locale,
+        currency: ctx.currency,
+    };
     match ctx.cli.output_format() {
         OutputFormat::Csv => {
-            let csv = output_tools_csv(summary);
+            let csv = output_tools_csv(summary, ctx.pricing_db, options);
             print!("{csv}");
         }
         OutputFormat::Json => {
-            let json = output_tools_json(summary);
-            print_json(&json, ctx.jq_filter);
+            let report = tools_json_report(summary, ctx.pricing_db, options);
+            print_json_report(&report, false, ctx);
         }
-        OutputFormat::Table => print_tools_table(summary, ctx.cli.use_color()),
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => print_tools_table(
+            summary,
+            ctx.pricing_db,
+            options,
+            ctx.cli.use_color(),
+            ctx.cli.output_format().markup(),
+        ),
     }
 }
 
+/// Active block and Codex weekly quota for the statusline, loaded only when the
+/// JSON output or the template shows them.
+fn load_statusline_extras(
+    source: Option<&dyn Source>,
+    ctx: &CommandContext<'_>,
+) -> (Option<ActiveBlock>, Option<CodexWeeklyQuota>) {
+    let template = ctx.cli.template.as_deref();
+    let template_shows = |prefix| template.is_some_and(|template| template_uses(template, prefix));
+    let block = source
+        .filter(|source| {
+            source.capabilities().has_billing_blocks && (ctx.cli.json || template_shows("block_"))
+        })
+        .and_then(|source| {
+            let (blocks, _) =
+                load_blocks(source, ctx.filter, ctx.timezone, true, ctx.daily_rates());
+            active_block(blocks, ctx.timezone.to_fixed_offset(Utc::now()))
+        });
+    let is_codex = source.is_none_or(|source| source.name() == "codex");
+    let quota = ((ctx.cli.json && is_codex) || template_shows("quota_"))
+        .then(load_weekly_quota)
+        .and_then(Result::ok);
+    (block, quota)
+}
+
 /// Statusline keeps its compact single-line semantics outside generic output dispatch.
-fn handle_statusline(source: &dyn Source, ctx: &CommandContext<'_>) {
-    let scope = codex_scope_for_source(source, ctx);
-    let label = source_label(source, ctx);
-    let result = load_daily(source, ctx.filter, ctx.timezone, true, false);
+fn render_statusline_output(
+    result: &LoadResult,
+    source: Option<&dyn Source>,
+    caps: &Capabilities,
+    label: &str,
+    ctx: &CommandContext<'_>,
+) {
+    let (block, quota) = load_statusline_extras(source, ctx);
+    let options = StatuslineOptions {
+        source_label: label,
+        locale: ctx.locale,
+        currency: ctx.currency,
+        supports_cache_read: caps.has_cache_read,
+        cost_mode: if source.is_some() {
+            CostDisplayMode::Total
+        } else {
+            CostDisplayMode::RealOnly
+        },
+        timezone: ctx.timezone,
+        template: ctx.cli.template.as_deref(),
+        block: block.as_ref(),
+        quota: quota.as_ref(),
+    };
     if ctx.cli.json {
-        let json = print_statusline_json_with_quality(
+        let report = statusline_json_report(
             &result.day_stats,
             ctx.pricing_db,
-            &label,
-            ctx.number_format,
-            ctx.currency,
-            source.capabilities().has_cache_read,
+            &options,
             Some(result.data_quality()),
-            CostDisplayMode::Total,
         );
-        let json = annotate_json_codex_scope(&json, scope);
-        print_json(&json, ctx.jq_filter);
+        let scope = source.and_then(|source| codex_scope_for_source(source, ctx));
+        print_codex_json_report(&report, scope, ctx);
     } else {
-        print_statusline(
-            &result.day_stats,
-            ctx.pricing_db,
-            &label,
-            ctx.number_format,
-            ctx.currency,
-            source.capabilities().has_cache_read,
-            CostDisplayMode::Total,
-        );
+        print_statusline(&result.day_stats, ctx.pricing_db, &options);
     }
 }
 
-#[allow(clippy::too_many_lines)]
-fn render_period_result(
-    result: &LoadResult,
-    period: Period,
-    caps: &Capabilities,
-    codex_scope: Option<CodexScope>,
-    ctx: &CommandContext<'_>,
-    cost_mode: CostDisplayMode,
-) {
-    let monthly_budget = (period == Period::Month)
-        .then_some(ctx.cli.monthly_budget)
-        .flatten();
-
-    match ctx.cli.output_format() {
-        OutputFormat::Csv => {
-            let csv = if let Some(budget) = monthly_budget {
-                let mut csv = output_monthly_budget_csv(
-                    &result.day_stats,
-                    ctx.pricing_db,
-                    MonthlyBudgetOptions {
-                        order: ctx.cli.sort_order(),
-                        breakdown: ctx.cli.breakdown,
-                        show_cost: ctx.cli.show_cost(),
-                        supports_cache_read: caps.has_cache_read,
-                        limit: budget,
-                        as_of: ctx.budget_as_of,
-                        currency: ctx.currency,
-                        cost_mode,
-                    },
-                );
-                append_data_quality_csv_comment(&mut csv, Some(result.data_quality()));
-                csv
-            } else {
-                output_period_csv_with_quality(
-                    &result.day_stats,
-                    period,
-                    ctx.pricing_db,
-                    ctx.cli.sort_order(),
-                    ctx.cli.breakdown,
-                    ctx.cli.show_cost(),
-                    caps.has_cache_read,
-                    ctx.currency,
-                    Some(result.data_quality()),
-                    cost_mode,
-                )
-            };
-            print!("{}", annotate_csv_codex_scope(csv, codex_scope));
-        }
-        OutputFormat::Json => {
-            let mut json = output_period_json_with_quality(
-                &result.day_stats,
-                period,
-                ctx.pricing_db,
-                ctx.cli.sort_order(),
-                ctx.cli.breakdown,
-                ctx.cli.show_cost(),
-                caps.has_cache_read,
-                ctx.currency,
-                Some(result.data_quality()),
-                cost_mode,
-            );
-            if let Some(budget) = monthly_budget {
-                let reports = monthly_budget_reports(
-                    &result.day_stats,
-                    ctx.pricing_db,
-                    ctx.cli.sort_order(),
-                    budget,
-                    ctx.budget_as_of,
-                    ctx.currency,
-                    cost_mode,
-                );
-                json = add_monthly_budget_to_json(&json, &reports);
-            }
-            json = annotate_json_codex_scope(&json, codex_scope);
-            print_json(&json, ctx.jq_filter);
-        }
-        OutputFormat::Table => {
-            print_codex_scope_note(codex_scope);
-            print_period_table(
-                &result.day_stats,
-                period,
-                ctx.cli.breakdown,
-                PeriodSummaryFooter {
-                    skipped: result.skipped,
-                    valid: result.valid,
-                    elapsed_ms: Some(result.elapsed_ms),
-                },
-                ctx.pricing_db,
-                TokenTableOptions {
-                    order: ctx.cli.sort_order(),
-                    use_color: ctx.cli.use_color(),
-                    compact: ctx.cli.compact,
-                    show_cost: ctx.cli.show_cost(),
-                    number_format: ctx.number_format,
-                    show_reasoning: caps.has_reasoning_tokens,
-                    show_cache_creation: caps.has_cache_creation,
-                    supports_cache_read: caps.has_cache_read,
-                    currency: ctx.currency,
-                    cost_mode,
-                },
-            );
-            if let Some(budget) = monthly_budget {
-                let reports = monthly_budget_reports(
-                    &result.day_stats,
-                    ctx.pricing_db,
-                    ctx.cli.sort_order(),
-                    budget,
-                    ctx.budget_as_of,
-                    ctx.currency,
-                    cost_mode,
-                );
-                print_monthly_budget_table(&reports, ctx.cli.use_color(), ctx.currency);
-            }
-        }
+fn handle_statusline(source: &dyn Source, ctx: &CommandContext<'_>) {
+    if ctx.cli.claude_hook {
+        return crate::claude_hook_cmd::handle_claude_hook(source, ctx);
     }
+    let label = source_label(source, ctx);
+    let result = load_daily(source, ctx.filter, ctx.timezone, true, false);
+    render_statusline_output(&result, Some(source), &source.capabilities(), &label, ctx);
 }
 
 fn handle_period(
@@ -590,10 +749,10 @@ fn handle_period(
 
     let result = load_daily(source, ctx.filter, ctx.timezone, false, ctx.cli.debug);
     if result.day_stats.is_empty() && !should_render_empty_structured_result(&result, ctx) {
-        print_no_data_hint(&source_label(source, ctx), "usage");
+        print_no_data_hint(ctx.locale, &source_label(source, ctx), DataKind::Usage);
         return;
     }
-    render_period_result(
+    crate::period_cmd::render_period_result(
         &result,
         period,
         caps,
@@ -613,8 +772,12 @@ pub(crate) fn handle_source_command(
 
     match command {
         SourceCommand::Sources => return crate::sources_cmd::handle_sources(ctx),
-        SourceCommand::Quota => return crate::quota_cmd::handle_quota(ctx),
+        SourceCommand::Schema { report } => return crate::schema_cmd::handle_schema(report, ctx),
+        SourceCommand::Quota { history } => return crate::quota_cmd::handle_quota(ctx, history),
+        SourceCommand::Notify => return crate::notify_cmd::handle_notify(Some(source), ctx),
+        SourceCommand::Report => return crate::report_cmd::handle_report(Some(source), ctx),
         SourceCommand::Session => return handle_session(source, ctx),
+        SourceCommand::SessionShow => return crate::session_cmd::handle_session_show(source, ctx),
         SourceCommand::Project => {
             if !caps.has_projects {
                 println!(
@@ -636,15 +799,45 @@ pub(crate) fn handle_source_command(
             return handle_blocks(source, ctx);
         }
         SourceCommand::Endpoints => return crate::endpoints_cmd::handle_endpoints(source, ctx),
+        SourceCommand::Agents => return crate::agents_cmd::handle_agents(source, ctx),
+        SourceCommand::Pricing { .. } => {
+            return crate::pricing_cmd::handle_pricing(Some(source), ctx);
+        }
+        SourceCommand::AuditPricing => {
+            return crate::audit_cmd::handle_audit_pricing(Some(source), ctx);
+        }
         SourceCommand::Statusline => return handle_statusline(source, ctx),
-        SourceCommand::Tools => {
+        SourceCommand::Tools { group_by } => {
             if !caps.has_tool_calls {
                 println!(
-                    "Tool usage analysis is only supported for Claude source.\nHint: switch with `--source claude` (or alias `--source cc`)."
+                    "{} does not record tool calls.\nHint: try `--source all`, or run `ccstats sources` to inspect capabilities.",
+                    source.display_name()
                 );
                 return;
             }
-            return handle_tools(source, ctx);
+            return handle_tools(source, group_by, ctx);
+        }
+        SourceCommand::ToolCommands { limit } => {
+            if !caps.has_tool_calls {
+                println!(
+                    "{} does not record tool calls.\nHint: try `--source all`, or run `ccstats sources` to inspect capabilities.",
+                    source.display_name()
+                );
+                return;
+            }
+            let calls = load_tool_calls(source, ctx.filter, ctx.timezone);
+            return render_tool_commands(&calls, limit, ctx);
+        }
+        SourceCommand::Files { limit } => {
+            if !caps.has_tool_calls {
+                println!(
+                    "{} does not record tool calls.\nHint: try `--source all`, or run `ccstats sources` to inspect capabilities.",
+                    source.display_name()
+                );
+                return;
+            }
+            let calls = load_tool_calls(source, ctx.filter, ctx.timezone);
+            return render_files(&calls, limit, ctx);
         }
         SourceCommand::Top { dim, limit } => {
             let limit = match validate_top_limit(limit) {
@@ -687,35 +880,18 @@ fn load_all_daily(ctx: &CommandContext<'_>, quiet: bool) -> (LoadResult, Capabil
 pub(crate) fn handle_all_sources_command(command: SourceCommand, ctx: &CommandContext<'_>) {
     match command {
         SourceCommand::Sources => return crate::sources_cmd::handle_sources(ctx),
-        SourceCommand::Quota => {
+        SourceCommand::Schema { report } => return crate::schema_cmd::handle_schema(report, ctx),
+        SourceCommand::Quota { .. } => {
             eprintln!("Error: quota analysis only supports the Codex source");
             std::process::exit(1);
         }
+        SourceCommand::Notify => return crate::notify_cmd::handle_notify(None, ctx),
+        SourceCommand::Report => return crate::report_cmd::handle_report(None, ctx),
+        SourceCommand::Pricing { .. } => return crate::pricing_cmd::handle_pricing(None, ctx),
+        SourceCommand::AuditPricing => return crate::audit_cmd::handle_audit_pricing(None, ctx),
         SourceCommand::Statusline => {
             let (result, caps) = load_all_daily(ctx, true);
-            if ctx.cli.json {
-                let json = print_statusline_json_with_quality(
-                    &result.day_stats,
-                    ctx.pricing_db,
-                    "All Sources",
-                    ctx.number_format,
-                    ctx.currency,
-                    caps.has_cache_read,
-                    Some(result.data_quality()),
-                    CostDisplayMode::RealOnly,
-                );
-                print_json(&json, ctx.jq_filter);
-            } else {
-                print_statusline(
-                    &result.day_stats,
-                    ctx.pricing_db,
-                    "All Sources",
-                    ctx.number_format,
-                    ctx.currency,
-                    caps.has_cache_read,
-                    CostDisplayMode::RealOnly,
-                );
-            }
+            render_statusline_output(&result, None, &caps, "All Sources", ctx);
             return;
         }
         SourceCommand::Top { dim, limit } => {
@@ -755,13 +931,21 @@ pub(crate) fn handle_all_sources_command(command: SourceCommand, ctx: &CommandCo
             );
             return;
         }
+        SourceCommand::Tools { group_by } => return handle_all_tools(group_by, ctx),
+        SourceCommand::ToolCommands { limit } => {
+            return render_tool_commands(&load_all_tool_calls(ctx), limit, ctx);
+        }
+        SourceCommand::Files { limit } => {
+            return render_files(&load_all_tool_calls(ctx), limit, ctx);
+        }
         SourceCommand::Session
+        | SourceCommand::SessionShow
         | SourceCommand::Project
         | SourceCommand::Blocks
         | SourceCommand::Endpoints
-        | SourceCommand::Tools => {
+        | SourceCommand::Agents => {
             println!(
-                "`--source all` supports daily, weekly, monthly, today, statusline, and top views.\nHint: use a specific --source for {command:?}."
+                "`--source all` supports daily, weekly, monthly, today, statusline, tools, files, and top views.\nHint: use a specific --source for {command:?}."
             );
             return;
         }
@@ -780,8 +964,15 @@ pub(crate) fn handle_all_sources_command(command: SourceCommand, ctx: &CommandCo
 
     let (result, caps) = load_all_daily(ctx, false);
     if result.day_stats.is_empty() && !should_render_empty_structured_result(&result, ctx) {
-        print_no_data_hint("All Sources", "usage");
+        print_no_data_hint(ctx.locale, "All Sources", DataKind::Usage);
         return;
     }
-    render_period_result(&result, period, &caps, None, ctx, CostDisplayMode::RealOnly);
+    crate::period_cmd::render_period_result(
+        &result,
+        period,
+        &caps,
+        None,
+        ctx,
+        CostDisplayMode::RealOnly,
+    );
 }
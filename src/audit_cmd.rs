@@ -0,0 +1,114 @@
+//! Handler for the `audit pricing` subcommand (unpriced-model report).
+//!
+//! Lives in its own module to keep `app.rs` under the module size limit.
+
+use std::collections::HashMap;
+
+use crate::app::{CommandContext, print_json_report, print_no_data_hint};
+use crate::core::Stats;
+use crate::output::{
+    DataKind, OutputFormat, PricingAudit, output_pricing_audit_csv, pricing_audit_json_report,
+    print_pricing_audit_table,
+};
+use crate::source::{ALL_SOURCES, Source, all_sources, load_daily};
+
+fn validate_fail_above(percent: f64) -> Result<f64, String> {
+    if (0.0..=100.0).contains(&percent) {
+        Ok(percent)
+    } else {
+        Err(format!(
+            "Error: --fail-above must be between 0 and 100, got {percent}"
+        ))
+    }
+}
+
+/// Usage per model over the date range, for one source or every source.
+fn load_model_stats(
+    source: Option<&dyn Source>,
+    ctx: &CommandContext<'_>,
+) -> HashMap<String, Stats> {
+    let sources: Vec<&dyn Source> = match source {
+        Some(source) => vec![source],
+        None => all_sources().collect(),
+    };
+    let mut models: HashMap<String, Stats> = HashMap::new();
+    for source in sources {
+        let result = load_daily(source, ctx.filter, ctx.timezone, true, ctx.cli.debug);
+        for day in result.day_stats.values() {
+            for (model, stats) in &day.models {
+                models.entry(model.clone()).or_default().add(stats);
+            }
+        }
+    }
+    models
+}
+
+pub(crate) fn handle_audit_pricing(source: Option<&dyn Source>, ctx: &CommandContext<'_>) {
+    let Some((fail_above, include_fallback)) = ctx.cli.audit_pricing_args() else {
+        return;
+    };
+    let fail_above = match fail_above.map(validate_fail_above).transpose() {
+        Ok(fail_above) => fail_above,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    };
+    let source_label = source.map_or("All Sources", |source| source.display_name());
+
+    let models = load_model_stats(source, ctx);
+    if models.is_empty() {
+        print_no_data_hint(
+            ctx.locale,
+            source.map_or(ALL_SOURCES, |source| source.display_name()),
+            DataKind::Usage,
+        );
+        return;
+    }
+    let audit = PricingAudit::new(&models, ctx.pricing_db, include_fallback);
+
+    let show_cost = ctx.cli.show_cost();
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => print!(
+            "{}",
+            output_pricing_audit_csv(&audit, show_cost, ctx.currency)
+        ),
+        OutputFormat::Json => {
+            let report = pricing_audit_json_report(&audit, show_cost, ctx.currency);
+            print_json_report(&report, true, ctx);
+        }
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            print_pricing_audit_table(
+                &audit,
+                source_label,
+                ctx.cli.use_color(),
+                show_cost,
+                ctx.locale,
+                ctx.currency,
+            );
+        }
+    }
+
+    if let Some(limit) = fail_above
+        && audit.unpriced_pct() > limit
+    {
+        eprintln!(
+            "Error: unpriced usage is {:.1}% of tokens, above --fail-above {limit}%",
+            audit.unpriced_pct()
+        );
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fail_above_must_be_a_percentage() {
+        assert_eq!(validate_fail_above(0.0), Ok(0.0));
+        assert_eq!(validate_fail_above(100.0), Ok(100.0));
+        assert!(validate_fail_above(-1.0).is_err());
+        assert!(validate_fail_above(f64::NAN).is_err());
+    }
+}
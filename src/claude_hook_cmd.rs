@@ -0,0 +1,387 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::app::{CommandContext, print_json_report};
+use crate::core::{
+    DateFilter, ProjectStats, RawEntry, SessionStats, aggregate_projects, aggregate_sessions,
+    format_project_name,
+};
+use crate::output::{
+    ClaudeHookOptions, ClaudeHookStatus, ContextUsage, active_block, claude_hook_json,
+    render_claude_hook_line,
+};
+use crate::source::{
+    Source, blocks_from_entries, load_entries, load_file_entries, load_session_entries,
+};
+use crate::utils::Timezone;
+
+const DEFAULT_CONTEXT_WINDOW: i64 = 200_000;
+const EXTENDED_CONTEXT_WINDOW: i64 = 1_000_000;
+/// Bytes read per step when scanning a transcript backwards.
+const TAIL_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Session JSON that Claude Code writes to the statusLine command's stdin.
+/// Only the fields ccstats uses are read; everything is optional so older and
+/// newer Claude Code versions both parse.
+#[derive(Debug, Default, Deserialize)]
+struct HookPayload {
+    #[serde(default)]
+    session_id: String,
+    #[serde(default)]
+    transcript_path: Option<PathBuf>,
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    #[serde(default)]
+    model: HookModel,
+    #[serde(default)]
+    workspace: HookWorkspace,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HookModel {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    display_name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HookWorkspace {
+    #[serde(default)]
+    current_dir: Option<PathBuf>,
+    #[serde(default)]
+    project_dir: Option<PathBuf>,
+}
+
+impl HookPayload {
+    /// Directory Claude Code was started in, which names the project.
+    fn project_dir(&self) -> Option<&Path> {
+        self.workspace
+            .project_dir
+            .as_deref()
+            .or(self.cwd.as_deref())
+            .or(self.workspace.current_dir.as_deref())
+    }
+
+    /// Project key as stored by the Claude parser: the transcript's directory
+    /// under `~/.claude/projects`, or the encoded project directory when the
+    /// transcript path is missing.
+    fn project_key(&self) -> Option<String> {
+        self.transcript_path
+            .as_deref()
+            .and_then(Path::parent)
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .map(str::to_string)
+            .or_else(|| self.project_dir().map(encode_project_dir))
+    }
+
+    fn project_label(&self, project_key: Option<&str>) -> String {
+        self.project_dir()
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .map(str::to_string)
+            .or_else(|| project_key.map(format_project_name))
+            .unwrap_or_default()
+    }
+}
+
+fn parse_payload(input: &str) -> Result<HookPayload, String> {
+    if input.trim().is_empty() {
+        return Err("no Claude Code session JSON on stdin".to_string());
+    }
+    serde_json::from_str(input).map_err(|e| format!("invalid Claude Code session JSON: {e}"))
+}
+
+/// Claude Code names a project's log directory after its path with every
+/// non-alphanumeric character replaced by `-`.
+fn encode_project_dir(path: &Path) -> String {
+    path.to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn context_window_tokens(model_id: &str) -> i64 {
+    if model_id.to_ascii_lowercase().contains("[1m]") {
+        EXTENDED_CONTEXT_WINDOW
+    } else {
+        DEFAULT_CONTEXT_WINDOW
+    }
+}
+
+#[derive(Deserialize)]
+struct TranscriptLine {
+    #[serde(rename = "isSidechain", default)]
+    is_sidechain: bool,
+    message: Option<TranscriptMessage>,
+}
+
+#[derive(Deserialize)]
+struct TranscriptMessage {
+    usage: Option<TranscriptUsage>,
+}
+
+#[derive(Deserialize)]
+#[allow(clippy::struct_field_names)] // field names match JSON schema
+struct TranscriptUsage {
+    #[serde(default)]
+    input_tokens: i64,
+    #[serde(default)]
+    cache_creation_input_tokens: i64,
+    #[serde(default)]
+    cache_read_input_tokens: i64,
+}
+
+/// Prompt size of a main-thread assistant turn: everything the model read,
+/// cached or not, is what occupies the context window.
+fn line_context_usage(line: &[u8], model_id: &str) -> Option<ContextUsage> {
+    let line: TranscriptLine = serde_json::from_slice(line).ok()?;
+    if line.is_sidechain {
+        return None;
+    }
+    let usage = line.message?.usage?;
+    Some(ContextUsage {
+        used_tokens: usage.input_tokens.max(0)
+            + usage.cache_creation_input_tokens.max(0)
+            + usage.cache_read_input_tokens.max(0),
+        limit_tokens: context_window_tokens(model_id),
+    })
+}
+
+/// Context usage of the latest main-thread turn, read from the end of the
+/// transcript so long sessions cost one chunk rather than the whole file.
+fn context_usage<R: Read + Seek>(
+    reader: &mut R,
+    model_id: &str,
+    chunk_size: usize,
+) -> io::Result<Option<ContextUsage>> {
+    let mut remaining = reader.seek(SeekFrom::End(0))?;
+    let mut suffix = Vec::new();
+    while remaining > 0 {
+        let chunk_len = remaining.min(chunk_size as u64) as usize;
+        remaining -= chunk_len as u64;
+        reader.seek(SeekFrom::Start(remaining))?;
+        let mut buffer = vec![0_u8; chunk_len];
+        reader.read_exact(&mut buffer)?;
+        buffer.extend_from_slice(&suffix);
+
+        let mut line_end = buffer.len();
+        while let Some(newline) = buffer[..line_end].iter().rposition(|byte| *byte == b'\n') {
+            if let Some(usage) = line_context_usage(&buffer[newline + 1..line_end], model_id) {
+                return Ok(Some(usage));
+            }
+            line_end = newline;
+        }
+        buffer.truncate(line_end);
+        suffix = buffer;
+    }
+    Ok(line_context_usage(&suffix, model_id))
+}
+
+fn transcript_context_usage(path: &Path, model_id: &str) -> Option<ContextUsage> {
+    let mut file = File::open(path).ok()?;
+    context_usage(&mut file, model_id, TAIL_CHUNK_SIZE)
+        .ok()
+        .flatten()
+}
+
+/// Entries of the hook's session across all dates: its transcript file, or
+/// every log of the session id when Claude Code sent no transcript path.
+fn hook_session_entries(
+    source: &dyn Source,
+    payload: &HookPayload,
+    timezone: Timezone,
+) -> Vec<RawEntry> {
+    match payload.transcript_path.as_deref() {
+        Some(path) => load_file_entries(source, path, timezone),
+        None if payload.session_id.is_empty() => Vec::new(),
+        None => {
+            load_session_entries(
+                source,
+                &DateFilter::new(None, None),
+                timezone,
+                &payload.session_id,
+            )
+            .0
+        }
+    }
+}
+
+/// Session totals for the hook's session, matched on its transcript path
+/// (the Claude parser's session key), or on the session id when Claude Code
+/// sent no transcript path.
+fn hook_session(
+    entries: &[RawEntry],
+    payload: &HookPayload,
+    track_days: bool,
+) -> Option<SessionStats> {
+    let transcript_key = payload
+        .transcript_path
+        .as_deref()
+        .map(|path| path.display().to_string());
+    let matches = |entry: &RawEntry| match transcript_key.as_deref() {
+        Some(key) => entry.session_key == key,
+        None => !payload.session_id.is_empty() && entry.session_id == payload.session_id,
+    };
+    let session_entries: Vec<RawEntry> = entries
+        .iter()
+        .filter(|entry| matches(entry))
+        .cloned()
+        .collect();
+    aggregate_sessions(session_entries, track_days).pop()
+}
+
+fn hook_project(entries: &[RawEntry], project_key: &str, track_days: bool) -> Option<ProjectStats> {
+    let project_entries: Vec<RawEntry> = entries
+        .iter()
+        .filter(|entry| entry.project_path == project_key)
+        .cloned()
+        .collect();
+    aggregate_projects(aggregate_sessions(project_entries, track_days)).pop()
+}
+
+/// Render the Claude Code statusLine for the session described on stdin.
+/// Problems are printed on the status line itself, since Claude Code shows
+/// stdout and discards stderr.
+pub(crate) fn handle_claude_hook(source: &dyn Source, ctx: &CommandContext<'_>) {
+    let mut input = String::new();
+    let payload = std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| format!("failed to read stdin: {e}"))
+        .and_then(|_| parse_payload(&input));
+    let payload = match payload {
+        Ok(payload) => payload,
+        Err(error) => {
+            println!("ccstats: {error}");
+            return;
+        }
+    };
+
+    // The session covers its whole transcript; one scan of today's entries
+    // feeds the project and block.
+    let session_entries = hook_session_entries(source, &payload, ctx.timezone);
+    let session = hook_session(&session_entries, &payload, ctx.daily_rates());
+    let entries = load_entries(source, ctx.filter, ctx.timezone);
+    let project_key = session
+        .as_ref()
+        .map(|session| session.project_path.clone())
+        .or_else(|| payload.project_key());
+    let today = project_key
+        .as_deref()
+        .and_then(|key| hook_project(&entries, key, ctx.daily_rates()));
+    let block = active_block(
+        blocks_from_entries(entries, ctx.timezone, ctx.daily_rates()),
+        ctx.timezone.to_fixed_offset(Utc::now()),
+    );
+    let context = payload
+        .transcript_path
+        .as_deref()
+        .and_then(|path| transcript_context_usage(path, &payload.model.id));
+
+    let project = payload.project_label(project_key.as_deref());
+    let status = ClaudeHookStatus {
+        session_id: &payload.session_id,
+        model: &payload.model.display_name,
+        project: &project,
+        session: session.as_ref(),
+        today: today.as_ref(),
+        block: block.as_ref(),
+        context,
+    };
+    let options = ClaudeHookOptions {
+        locale: ctx.locale,
+        currency: ctx.currency,
+    };
+    if ctx.cli.json {
+        print_json_report(
+            &claude_hook_json(&status, ctx.pricing_db, options),
+            false,
+            ctx,
+        );
+    } else {
+        println!(
+            "{}",
+            render_claude_hook_line(&status, ctx.pricing_db, options)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn payload_reads_claude_code_session_fields() {
+        let payload = parse_payload(
+            r#"{
+                "hook_event_name": "Status",
+                "session_id": "abc123",
+                "transcript_path": "/home/me/.claude/projects/-home-me-myapp/abc123.jsonl",
+                "cwd": "/home/me/myapp/src",
+                "model": {"id": "claude-opus-4-1", "display_name": "Opus"},
+                "workspace": {"current_dir": "/home/me/myapp/src", "project_dir": "/home/me/myapp"},
+                "version": "1.0.80"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(payload.session_id, "abc123");
+        assert_eq!(payload.model.display_name, "Opus");
+        assert_eq!(payload.project_key().as_deref(), Some("-home-me-myapp"));
+        assert_eq!(payload.project_label(None), "myapp");
+    }
+
+    #[test]
+    fn payload_without_transcript_encodes_the_project_dir() {
+        let payload = parse_payload(r#"{"cwd": "/home/me/my.app"}"#).unwrap();
+        assert_eq!(payload.project_key().as_deref(), Some("-home-me-my-app"));
+    }
+
+    #[test]
+    fn empty_or_malformed_stdin_is_reported() {
+        assert!(
+            parse_payload("  \n")
+                .unwrap_err()
+                .contains("no Claude Code")
+        );
+        assert!(parse_payload("{").unwrap_err().contains("invalid"));
+    }
+
+    #[test]
+    fn context_usage_uses_the_latest_main_thread_turn() {
+        let transcript = [
+            r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"cache_read_input_tokens":1000}}}"#,
+            r#"{"type":"assistant","message":{"usage":{"input_tokens":5,"cache_creation_input_tokens":2000,"cache_read_input_tokens":88000,"output_tokens":500}}}"#,
+            r#"{"type":"assistant","isSidechain":true,"message":{"usage":{"input_tokens":150000}}}"#,
+            r#"{"type":"user","message":{"role":"user","content":"next"}}"#,
+            "not json",
+        ]
+        .join("\n");
+
+        let usage = |model_id: &str, chunk_size: usize| {
+            context_usage(&mut Cursor::new(&transcript), model_id, chunk_size).unwrap()
+        };
+
+        let latest = usage("claude-sonnet-4", TAIL_CHUNK_SIZE).unwrap();
+        assert_eq!(latest.used_tokens, 90_005);
+        assert_eq!(latest.limit_tokens, DEFAULT_CONTEXT_WINDOW);
+        assert_eq!(usage("claude-sonnet-4", 7), Some(latest));
+        assert_eq!(
+            usage("claude-sonnet-4[1m]", TAIL_CHUNK_SIZE)
+                .unwrap()
+                .limit_tokens,
+            EXTENDED_CONTEXT_WINDOW
+        );
+        assert!(
+            context_usage(&mut Cursor::new(""), "claude-sonnet-4", TAIL_CHUNK_SIZE)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -3,14 +3,16 @@
 //! Global CLI options and configuration merging logic.
 
 use std::io::IsTerminal;
+use std::path::Path;
 
 use clap::{Parser, ValueEnum};
 
 use crate::config::{Config, ConfigColorMode, ConfigCostMode, ConfigSortOrder};
-use crate::output::OutputFormat;
+use crate::output::{Markup, OutputFormat};
+use crate::pricing::{ExchangeRateConfig, PricingProvider};
 use crate::source::CodexScope;
 
-use super::commands::Commands;
+use super::commands::{AuditCommands, Commands, NotifyArgs, PricingCommands, SessionCommands};
 
 #[derive(Debug, Clone, Copy, Default, ValueEnum, PartialEq, Eq)]
 pub(crate) enum SortOrder {
@@ -68,10 +70,18 @@ pub(crate) struct Cli {
     #[arg(short, long, global = true)]
     pub(crate) json: bool,
 
+    /// Output as newline-delimited JSON, one object per row (implies --json)
+    #[arg(long, global = true, conflicts_with = "jq")]
+    pub(crate) ndjson: bool,
+
     /// Output as CSV
     #[arg(long, global = true)]
     pub(crate) csv: bool,
 
+    /// Output format; `markdown` and `html` render the table view for pasting or sharing
+    #[arg(long, global = true, value_enum, conflicts_with_all = ["json", "ndjson", "csv"])]
+    pub(crate) format: Option<OutputFormat>,
+
     /// Use offline cached pricing (skip fetching from `LiteLLM`)
     #[arg(short = 'O', long, global = true)]
     pub(crate) offline: bool,
@@ -108,15 +118,19 @@ pub(crate) struct Cli {
     #[arg(long, global = true)]
     pub(crate) no_cost: bool,
 
-    /// Filter JSON output with jq expression (requires jq installed)
+    /// Filter JSON output with a jq expression (built in, no jq binary needed)
     #[arg(long, global = true, value_name = "FILTER")]
     pub(crate) jq: Option<String>,
 
+    /// Run --jq filters the built-in engine does not support with the jq binary
+    #[arg(long, global = true, requires = "jq")]
+    pub(crate) jq_fallback: bool,
+
     /// Timezone for date display (e.g., `Asia/Shanghai`, `UTC`, `America/New_York`)
     #[arg(long, global = true, value_name = "TZ")]
     pub(crate) timezone: Option<String>,
 
-    /// Locale for number formatting (e.g., "en", "zh", "de")
+    /// Locale for numbers, costs, dates and labels in tables (e.g., "en", "zh", "ja", "de")
     #[arg(long, global = true, value_name = "LOCALE")]
     pub(crate) locale: Option<String>,
 
@@ -128,6 +142,14 @@ pub(crate) struct Cli {
     #[arg(long, global = true, value_name = "AMOUNT")]
     pub(crate) monthly_budget: Option<f64>,
 
+    /// Statusline format with placeholders, e.g. `"{source} {cost} {in}/{out} {block_remaining}"`
+    #[arg(long, global = true, value_name = "TEMPLATE")]
+    pub(crate) template: Option<String>,
+
+    /// Read Claude Code's statusLine session JSON from stdin and show that session and project
+    #[arg(long, global = true)]
+    pub(crate) claude_hook: bool,
+
     /// Data source name or alias (e.g., "claude", "codex", "cursor", "grok", "kimi", "all", "cc", "cx", "cur", "gx", "km")
     #[arg(long, global = true, value_name = "SOURCE")]
     pub(crate) source: Option<String>,
@@ -135,6 +157,14 @@ pub(crate) struct Cli {
     /// Filter Codex sessions by origin
     #[arg(long, global = true, value_enum, default_value_t = CodexScope::All)]
     pub(crate) codex_scope: CodexScope,
+
+    /// Price catalog chain from the `[pricing]` config table (empty: `LiteLLM`)
+    #[arg(skip)]
+    pub(crate) pricing_providers: Vec<PricingProvider>,
+
+    /// Fixed rates and daily rates file from the `[exchange_rates]` config table
+    #[arg(skip)]
+    pub(crate) exchange_rates: ExchangeRateConfig,
 }
 
 impl Cli {
@@ -143,9 +173,11 @@ impl Cli {
     }
 
     pub(crate) fn output_format(&self) -> OutputFormat {
-        if self.csv {
+        if let Some(format) = self.format {
+            format
+        } else if self.csv {
             OutputFormat::Csv
-        } else if self.json {
+        } else if self.json || self.ndjson {
             OutputFormat::Json
         } else {
             OutputFormat::Table
@@ -222,12 +254,89 @@ impl Cli {
         if self.source.is_none() {
             self.source.clone_from(&config.source);
         }
+        if self.template.is_none() {
+            self.template.clone_from(&config.statusline_template);
+        }
+        if self.pricing_providers.is_empty() {
+            self.pricing_providers.clone_from(&config.pricing.providers);
+        }
+        self.exchange_rates.clone_from(&config.exchange_rates);
+        if let Some(Commands::Notify(notify)) = &mut self.command {
+            if notify.hook_command.is_none() {
+                notify.hook_command.clone_from(&config.notify_command);
+            }
+            if notify.webhook.is_none() {
+                notify.webhook.clone_from(&config.notify_webhook);
+            }
+        }
 
         self
     }
 
+    /// Options of the `notify` subcommand, when that is the command being run.
+    pub(crate) fn notify_args(&self) -> Option<&NotifyArgs> {
+        match &self.command {
+            Some(Commands::Notify(notify)) => Some(notify),
+            _ => None,
+        }
+    }
+
+    /// `--html` target of `report`, when that is the command being run.
+    pub(crate) fn report_html_path(&self) -> Option<&Path> {
+        match &self.command {
+            Some(Commands::Report { html }) => Some(html),
+            _ => None,
+        }
+    }
+
+    /// `--fail-above` and `--include-fallback` of `audit pricing`, when that
+    /// is the command being run.
+    pub(crate) fn audit_pricing_args(&self) -> Option<(Option<f64>, bool)> {
+        match &self.command {
+            Some(Commands::Audit {
+                command:
+                    AuditCommands::Pricing {
+                        fail_above,
+                        include_fallback,
+                    },
+            }) => Some((*fail_above, *include_fallback)),
+            _ => None,
+        }
+    }
+
+    /// View of the `pricing` subcommand, when that is the command being run.
+    pub(crate) fn pricing_command(&self) -> Option<&PricingCommands> {
+        match &self.command {
+            Some(Commands::Pricing { command }) => Some(command),
+            _ => None,
+        }
+    }
+
+    /// Session id prefix of `session show`, when that is the command being run.
+    pub(crate) fn session_show_id(&self) -> Option<&str> {
+        match &self.command {
+            Some(Commands::Session {
+                command: Some(SessionCommands::Show { id }),
+                ..
+            }) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Ceiling threshold of `session --context`, when context metrics were requested.
+    pub(crate) fn session_context_threshold(&self) -> Option<i64> {
+        match &self.command {
+            Some(Commands::Session {
+                command: None,
+                context: true,
+                context_threshold,
+            }) => Some(*context_threshold),
+            _ => None,
+        }
+    }
+
     pub(crate) fn use_color(&self) -> bool {
-        if self.no_color {
+        if self.no_color || self.output_format().markup() != Markup::Terminal {
             return false;
         }
         match self.color_mode() {
@@ -531,6 +640,25 @@ mod tests {
         assert_eq!(cli.output_format(), OutputFormat::Csv);
     }
 
+    #[test]
+    fn output_format_ndjson_implies_json() {
+        let cli = Cli::parse_from(["ccstats", "session", "--ndjson"]);
+        assert_eq!(cli.output_format(), OutputFormat::Json);
+        assert!(Cli::try_parse_from(["ccstats", "daily", "--ndjson", "--jq", ".[]"]).is_err());
+    }
+
+    #[test]
+    fn output_format_flag_selects_markdown_and_html_without_color() {
+        let cli = Cli::parse_from([
+            "ccstats", "daily", "--format", "markdown", "--color", "always",
+        ]);
+        assert_eq!(cli.output_format(), OutputFormat::Markdown);
+        assert!(!cli.use_color());
+        let cli = Cli::parse_from(["ccstats", "daily", "--format", "json"]);
+        assert_eq!(cli.output_format(), OutputFormat::Json);
+        assert!(Cli::try_parse_from(["ccstats", "daily", "--format", "html", "--json"]).is_err());
+    }
+
     #[test]
     fn output_format_csv_wins_over_json_flag() {
         let cli = Cli::parse_from(["ccstats", "daily", "--json", "--csv"]);
@@ -549,6 +677,35 @@ mod tests {
         assert_eq!(cli.monthly_budget, Some(25.5));
     }
 
+    #[test]
+    fn cli_template_wins_over_config_statusline_template() {
+        let cli = Cli::parse_from(["ccstats", "statusline", "--template", "{cost}"]);
+        let config = Config {
+            statusline_template: Some("{source}".to_string()),
+            ..Default::default()
+        };
+        let merged = cli.with_config(&config);
+        assert_eq!(merged.template.as_deref(), Some("{cost}"));
+
+        let merged = Cli::parse_from(["ccstats", "statusline"]).with_config(&config);
+        assert_eq!(merged.template.as_deref(), Some("{source}"));
+    }
+
+    #[test]
+    fn config_notify_hooks_fill_unset_notify_flags() {
+        let cli = Cli::parse_from(["ccstats", "notify", "--webhook", "http://localhost/cli"]);
+        let config = Config {
+            notify_command: Some("cat".to_string()),
+            notify_webhook: Some("http://localhost/config".to_string()),
+            ..Default::default()
+        };
+        let merged = cli.with_config(&config);
+        let notify = merged.notify_args().unwrap();
+        assert_eq!(notify.hook_command.as_deref(), Some("cat"));
+        assert_eq!(notify.webhook.as_deref(), Some("http://localhost/cli"));
+        assert_eq!(notify.interval, 300);
+    }
+
     #[test]
     fn empty_config_changes_nothing() {
         let cli = Cli::parse_from(["ccstats", "daily"]);
@@ -2,7 +2,12 @@
 //!
 //! Defines the available commands for each data source.
 
-use clap::{Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand, ValueEnum};
+
+use crate::core::DEFAULT_CONTEXT_THRESHOLD;
+use crate::output::SchemaReport;
 
 /// Dimension to rank in the `top` command
 #[derive(Debug, Clone, Copy, Default, ValueEnum, PartialEq, Eq)]
@@ -14,6 +19,44 @@ pub(crate) enum TopDimension {
     Project,
 }
 
+/// Row grouping for the `tools` command
+#[derive(Debug, Clone, Copy, Default, ValueEnum, PartialEq, Eq)]
+pub(crate) enum ToolGrouping {
+    /// One row per tool (default)
+    #[default]
+    Tool,
+    /// One row per project and tool
+    Project,
+    /// One row per day and tool
+    Day,
+    /// One row per MCP server and tool, with built-in tools and subagent
+    /// launches listed separately
+    Server,
+    /// One row per tool within its kind of work (shell, edit, search, ...),
+    /// normalized across agents
+    Category,
+}
+
+/// Delivery and scheduling options for `ccstats notify`
+#[derive(Debug, Clone, Default, Args)]
+pub(crate) struct NotifyArgs {
+    /// Shell command to run on status changes; receives the JSON payload on stdin
+    #[arg(long = "command", value_name = "CMD")]
+    pub(crate) hook_command: Option<String>,
+    /// URL that receives the JSON payload as an HTTP POST on status changes
+    #[arg(long, value_name = "URL")]
+    pub(crate) webhook: Option<String>,
+    /// Keep running and re-check every --interval seconds
+    #[arg(long)]
+    pub(crate) watch: bool,
+    /// Seconds between checks in --watch mode
+    #[arg(long, value_name = "SECONDS", default_value_t = 300)]
+    pub(crate) interval: u64,
+    /// File that keeps the last-seen statuses (default: ccstats cache directory)
+    #[arg(long, value_name = "PATH")]
+    pub(crate) state_file: Option<PathBuf>,
+}
+
 /// Main CLI commands
 #[derive(Subcommand)]
 pub(crate) enum Commands {
@@ -24,23 +67,57 @@ pub(crate) enum Commands {
     /// Show weekly usage
     Weekly,
     /// Estimate the current Codex weekly quota pace
-    Quota,
+    Quota {
+        /// Reconstruct past and current quota windows from every logged snapshot
+        #[arg(long)]
+        history: bool,
+    },
     /// Show monthly usage
     Monthly,
     /// Show today's usage
     Today,
     /// Show usage by session
-    Session,
+    Session {
+        #[command(subcommand)]
+        command: Option<SessionCommands>,
+        /// Show context-window growth per session: peak, average, compactions
+        #[arg(long)]
+        context: bool,
+        /// Context size in tokens that counts as riding the ceiling with --context
+        #[arg(
+            long,
+            value_name = "TOKENS",
+            default_value_t = DEFAULT_CONTEXT_THRESHOLD,
+            value_parser = clap::value_parser!(i64).range(1..)
+        )]
+        context_threshold: i64,
+    },
     /// Show usage by project
     Project,
     /// Show usage by 5-hour billing blocks
     Blocks,
     /// Show usage split by serving endpoint (native Anthropic vs proxy)
     Endpoints,
+    /// Show how much of each session's usage came from subagents
+    Agents,
     /// Output single line for statusline/tmux integration
     Statusline,
     /// Show tool usage statistics (Read, Bash, Edit, etc.)
-    Tools,
+    Tools {
+        #[command(subcommand)]
+        command: Option<ToolsCommands>,
+        /// Break tool rows down by project, day, MCP server, or category
+        #[arg(long, value_enum, default_value_t = ToolGrouping::Tool)]
+        group_by: ToolGrouping,
+    },
+    /// Show files read and written by agents per project, with hot directories
+    Files {
+        /// Maximum number of files and directories listed per project (1..=1000)
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Run a hook when Codex quota or monthly budget status changes
+    Notify(NotifyArgs),
     /// Show top N consumers ranked by cost (or tokens when cost is unknown)
     Top {
         /// Dimension to rank by
@@ -50,6 +127,28 @@ pub(crate) enum Commands {
         #[arg(long, default_value_t = 10)]
         limit: usize,
     },
+    /// Inspect, search, refresh and diff the model price catalog
+    Pricing {
+        #[command(subcommand)]
+        command: PricingCommands,
+    },
+    /// Audit data quality; `audit pricing` lists how every model was priced
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+    /// Print the JSON Schema of a report's `--json` output
+    Schema {
+        /// Report whose schema to print
+        #[arg(value_enum)]
+        report: SchemaReport,
+    },
+    /// Write an offline HTML dashboard with charts (all sources unless --source is set)
+    Report {
+        /// File to write the self-contained dashboard to
+        #[arg(long, value_name = "FILE")]
+        html: PathBuf,
+    },
     /// `Codex` CLI usage statistics
     Codex {
         #[command(subcommand)]
@@ -67,6 +166,61 @@ pub(crate) enum Commands {
     },
 }
 
+/// Session views
+#[derive(Subcommand)]
+pub(crate) enum SessionCommands {
+    /// Show a turn-by-turn timeline of one session
+    Show {
+        /// Session id or a unique prefix of it
+        id: String,
+    },
+}
+
+/// Price catalog views
+#[derive(Subcommand)]
+pub(crate) enum PricingCommands {
+    /// Show the resolved prices of one model, the matched catalog key and the pricing source
+    Show {
+        /// Model name as it appears in usage logs
+        model: String,
+    },
+    /// List catalog entries, optionally only those whose name contains a pattern
+    List {
+        /// Case-insensitive substring of the model name
+        #[arg(long = "match", value_name = "PATTERN")]
+        pattern: Option<String>,
+    },
+    /// Re-download the `LiteLLM` price list now and report the cache it replaced
+    Refresh,
+    /// Compare the cached price list with a fresh one for models you used
+    Diff,
+}
+
+/// Audit reports
+#[derive(Subcommand)]
+pub(crate) enum AuditCommands {
+    /// List every model in the range with its pricing resolution, token volume and cost share
+    Pricing {
+        /// Exit with status 1 when unpriced usage exceeds this share of all tokens (0-100)
+        #[arg(long, value_name = "PERCENT")]
+        fail_above: Option<f64>,
+        /// Count usage priced with built-in fallback estimates as unpriced
+        #[arg(long)]
+        include_fallback: bool,
+    },
+}
+
+/// Tool usage views
+#[derive(Subcommand)]
+pub(crate) enum ToolsCommands {
+    /// Rank shell commands run by agents per project and flag risky ones
+    Commands {
+        /// Maximum number of commands listed per project (1..=1000)
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+}
+
 /// Codex-specific subcommands
 #[derive(Subcommand)]
 pub(crate) enum CodexCommands {
@@ -75,7 +229,11 @@ pub(crate) enum CodexCommands {
     /// Show weekly Codex usage
     Weekly,
     /// Estimate the current Codex weekly quota pace
-    Quota,
+    Quota {
+        /// Reconstruct past and current quota windows from every logged snapshot
+        #[arg(long)]
+        history: bool,
+    },
     /// Show monthly Codex usage
     Monthly,
     /// Show today's Codex usage
@@ -124,22 +282,49 @@ pub(crate) enum KimiCommands {
     Statusline,
 }
 
+/// Commands [`SourceCommand::supports_markup`] accepts, as the `--format`
+/// error lists them.
+pub(crate) const MARKUP_COMMANDS: &[&str] = &[
+    "daily",
+    "weekly",
+    "monthly",
+    "today",
+    "session",
+    "session show",
+    "session --context",
+    "project",
+    "blocks",
+    "endpoints",
+    "tools",
+    "top",
+    "quota",
+];
+
 /// Normalized command that works across all sources
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum SourceCommand {
     Sources,
     Daily,
     Weekly,
-    Quota,
+    Quota { history: bool },
     Monthly,
     Today,
     Session,
+    SessionShow,
     Project,
     Blocks,
     Endpoints,
+    Agents,
     Statusline,
-    Tools,
+    Tools { group_by: ToolGrouping },
+    ToolCommands { limit: usize },
+    Files { limit: usize },
+    Notify,
     Top { dim: TopDimension, limit: usize },
+    Pricing { fetches: bool },
+    AuditPricing,
+    Schema { report: SchemaReport },
+    Report,
 }
 
 impl SourceCommand {
@@ -148,6 +333,51 @@ impl SourceCommand {
         matches!(self, SourceCommand::Statusline)
     }
 
+    /// Check if this is a quota command (current pace or history)
+    pub(crate) fn is_quota(self) -> bool {
+        matches!(self, SourceCommand::Quota { .. })
+    }
+
+    /// Check if this command renders its table view as Markdown or HTML.
+    /// Keep [`MARKUP_COMMANDS`] in step when adding a command here.
+    pub(crate) fn supports_markup(self) -> bool {
+        matches!(
+            self,
+            SourceCommand::Daily
+                | SourceCommand::Weekly
+                | SourceCommand::Monthly
+                | SourceCommand::Today
+                | SourceCommand::Session
+                | SourceCommand::SessionShow
+                | SourceCommand::Project
+                | SourceCommand::Blocks
+                | SourceCommand::Endpoints
+                | SourceCommand::Tools { .. }
+                | SourceCommand::Top { .. }
+                | SourceCommand::Quota { history: false }
+        )
+    }
+
+    /// Check if this command converts costs at each day's exchange rate
+    /// rather than at the latest one
+    pub(crate) fn uses_daily_rates(self) -> bool {
+        matches!(
+            self,
+            SourceCommand::Daily
+                | SourceCommand::Weekly
+                | SourceCommand::Monthly
+                | SourceCommand::Today
+                | SourceCommand::Session
+                | SourceCommand::SessionShow
+                | SourceCommand::Project
+                | SourceCommand::Blocks
+                | SourceCommand::Statusline
+                | SourceCommand::Notify
+                | SourceCommand::Top { .. }
+                | SourceCommand::Report
+        )
+    }
+
     /// Check if this command needs today's date filter
     pub(crate) fn needs_today_filter(self) -> bool {
         matches!(self, SourceCommand::Today | SourceCommand::Statusline)
@@ -161,19 +391,45 @@ impl From<&Commands> for SourceCommand {
             Commands::Sources => SourceCommand::Sources,
             Commands::Daily => SourceCommand::Daily,
             Commands::Weekly => SourceCommand::Weekly,
-            Commands::Quota => SourceCommand::Quota,
+            Commands::Quota { history } => SourceCommand::Quota { history: *history },
             Commands::Monthly => SourceCommand::Monthly,
             Commands::Today => SourceCommand::Today,
-            Commands::Session => SourceCommand::Session,
+            Commands::Session { command: None, .. } => SourceCommand::Session,
+            Commands::Session {
+                command: Some(SessionCommands::Show { .. }),
+                ..
+            } => SourceCommand::SessionShow,
             Commands::Project => SourceCommand::Project,
             Commands::Blocks => SourceCommand::Blocks,
             Commands::Endpoints => SourceCommand::Endpoints,
+            Commands::Agents => SourceCommand::Agents,
             Commands::Statusline => SourceCommand::Statusline,
-            Commands::Tools => SourceCommand::Tools,
+            Commands::Tools {
+                command: Some(ToolsCommands::Commands { limit }),
+                ..
+            } => SourceCommand::ToolCommands { limit: *limit },
+            Commands::Tools {
+                command: None,
+                group_by,
+            } => SourceCommand::Tools {
+                group_by: *group_by,
+            },
+            Commands::Files { limit } => SourceCommand::Files { limit: *limit },
+            Commands::Notify(_) => SourceCommand::Notify,
             Commands::Top { dim, limit } => SourceCommand::Top {
                 dim: *dim,
                 limit: *limit,
             },
+            // Refresh and diff download the price list themselves instead of
+            // using the loaded catalog.
+            Commands::Pricing { command } => SourceCommand::Pricing {
+                fetches: matches!(command, PricingCommands::Refresh | PricingCommands::Diff),
+            },
+            Commands::Audit {
+                command: AuditCommands::Pricing { .. },
+            } => SourceCommand::AuditPricing,
+            Commands::Schema { report } => SourceCommand::Schema { report: *report },
+            Commands::Report { .. } => SourceCommand::Report,
             Commands::Codex { .. } | Commands::Grok { .. } | Commands::Kimi { .. } => {
                 SourceCommand::Daily
             } // Default, handled separately
@@ -186,7 +442,7 @@ impl From<&Option<CodexCommands>> for SourceCommand {
         match cmd {
             Some(CodexCommands::Daily) | None => SourceCommand::Daily,
             Some(CodexCommands::Weekly) => SourceCommand::Weekly,
-            Some(CodexCommands::Quota) => SourceCommand::Quota,
+            Some(CodexCommands::Quota { history }) => SourceCommand::Quota { history: *history },
             Some(CodexCommands::Monthly) => SourceCommand::Monthly,
             Some(CodexCommands::Today) => SourceCommand::Today,
             Some(CodexCommands::Session) => SourceCommand::Session,
@@ -237,9 +493,9 @@ pub(crate) fn parse_command(cmd: Option<&Commands>) -> ParsedCommand {
             source_hint: Some("codex"),
             command: SourceCommand::from(command),
         },
-        Some(Commands::Quota) => ParsedCommand {
+        Some(Commands::Quota { history }) => ParsedCommand {
             source_hint: Some("codex"),
-            command: SourceCommand::Quota,
+            command: SourceCommand::Quota { history: *history },
         },
         Some(Commands::Grok { command }) => ParsedCommand {
             source_hint: Some("grok"),
@@ -271,6 +527,22 @@ mod tests {
         assert_eq!(parsed.source_hint, None);
     }
 
+    #[test]
+    fn markup_commands_lists_what_supports_markup_accepts() {
+        use clap::Parser;
+
+        for name in MARKUP_COMMANDS {
+            let mut args = vec!["ccstats"];
+            args.extend(name.split_whitespace());
+            if *name == "session show" {
+                args.push("abc");
+            }
+            let cli = crate::cli::Cli::try_parse_from(&args).unwrap();
+            let parsed = parse_command(cli.command.as_ref());
+            assert!(parsed.command.supports_markup(), "{name}");
+        }
+    }
+
     #[test]
     fn parse_command_codex_sets_source_hint() {
         let parsed = parse_command(Some(&Commands::Codex {
@@ -282,14 +554,14 @@ mod tests {
 
     #[test]
     fn parse_command_quota_is_codex_specific() {
-        let top_level = parse_command(Some(&Commands::Quota));
-        assert_eq!(top_level.command, SourceCommand::Quota);
+        let top_level = parse_command(Some(&Commands::Quota { history: false }));
+        assert_eq!(top_level.command, SourceCommand::Quota { history: false });
         assert_eq!(top_level.source_hint, Some("codex"));
 
         let nested = parse_command(Some(&Commands::Codex {
-            command: Some(CodexCommands::Quota),
+            command: Some(CodexCommands::Quota { history: true }),
         }));
-        assert_eq!(nested.command, SourceCommand::Quota);
+        assert_eq!(nested.command, SourceCommand::Quota { history: true });
         assert_eq!(nested.source_hint, Some("codex"));
     }
 
@@ -318,10 +590,60 @@ mod tests {
         assert_eq!(parsed.source_hint, None);
     }
 
+    #[test]
+    fn parse_command_notify_keeps_no_source_hint() {
+        let parsed = parse_command(Some(&Commands::Notify(NotifyArgs::default())));
+        assert_eq!(parsed.command, SourceCommand::Notify);
+        assert_eq!(parsed.source_hint, None);
+    }
+
     #[test]
     fn parse_command_sources_has_no_source_hint() {
         let parsed = parse_command(Some(&Commands::Sources));
         assert_eq!(parsed.command, SourceCommand::Sources);
         assert_eq!(parsed.source_hint, None);
     }
+
+    #[test]
+    fn parse_command_pricing_marks_fetching_views() {
+        let refresh = Commands::Pricing {
+            command: PricingCommands::Refresh,
+        };
+        assert_eq!(
+            parse_command(Some(&refresh)).command,
+            SourceCommand::Pricing { fetches: true }
+        );
+        let show = Commands::Pricing {
+            command: PricingCommands::Show {
+                model: "sonnet-4".to_string(),
+            },
+        };
+        assert_eq!(
+            parse_command(Some(&show)).command,
+            SourceCommand::Pricing { fetches: false }
+        );
+    }
+
+    #[test]
+    fn parse_command_schema_keeps_report() {
+        let parsed = parse_command(Some(&Commands::Schema {
+            report: SchemaReport::Blocks,
+        }));
+        assert_eq!(
+            parsed.command,
+            SourceCommand::Schema {
+                report: SchemaReport::Blocks
+            }
+        );
+        assert_eq!(parsed.source_hint, None);
+    }
+
+    #[test]
+    fn parse_command_report_has_no_source_hint() {
+        let parsed = parse_command(Some(&Commands::Report {
+            html: PathBuf::from("out.html"),
+        }));
+        assert_eq!(parsed.command, SourceCommand::Report);
+        assert_eq!(parsed.source_hint, None);
+    }
 }
@@ -2,4 +2,7 @@ mod args;
 mod commands;
 
 pub(crate) use args::{Cli, SortOrder};
-pub(crate) use commands::{SourceCommand, TopDimension, parse_command};
+pub(crate) use commands::{
+    MARKUP_COMMANDS, NotifyArgs, PricingCommands, SourceCommand, ToolGrouping, TopDimension,
+    parse_command,
+};
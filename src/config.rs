@@ -5,6 +5,8 @@ use std::path::PathBuf;
 
 use thiserror::Error;
 
+use crate::pricing::{ExchangeRateConfig, PricingProvider};
+
 #[derive(Debug, Error)]
 pub(crate) enum ConfigError {
     #[error("failed to read config {}: {source}", path.display())]
@@ -70,6 +72,23 @@ pub(crate) struct Config {
     pub(crate) currency: Option<String>,
     #[serde(default)]
     pub(crate) source: Option<String>,
+    #[serde(default)]
+    pub(crate) statusline_template: Option<String>,
+    #[serde(default)]
+    pub(crate) notify_command: Option<String>,
+    #[serde(default)]
+    pub(crate) notify_webhook: Option<String>,
+    #[serde(default)]
+    pub(crate) pricing: PricingConfig,
+    #[serde(default)]
+    pub(crate) exchange_rates: ExchangeRateConfig,
+}
+
+/// `[pricing]` table: the ordered price catalog chain.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct PricingConfig {
+    #[serde(default)]
+    pub(crate) providers: Vec<PricingProvider>,
 }
 
 impl Config {
@@ -259,6 +278,22 @@ strict_pricing = true
         assert_eq!(config.source.as_deref(), Some("codex"));
     }
 
+    #[test]
+    fn test_deserialize_notify_hooks() {
+        let config: Config = toml::from_str(
+            "notify_command = \"notify-send ccstats\"\nnotify_webhook = \"http://127.0.0.1:9000/hook\"",
+        )
+        .unwrap();
+        assert_eq!(
+            config.notify_command.as_deref(),
+            Some("notify-send ccstats")
+        );
+        assert_eq!(
+            config.notify_webhook.as_deref(),
+            Some("http://127.0.0.1:9000/hook")
+        );
+    }
+
     #[test]
     fn test_deserialize_full_config() {
         let toml_str = r#"
@@ -320,6 +355,46 @@ source = "codex"
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_deserialize_pricing_providers() {
+        let config: Config = toml::from_str(
+            r#"
+[pricing]
+providers = [
+    { kind = "file", path = "/etc/ccstats/prices.toml", schema = "ccstats" },
+    { kind = "litellm" },
+]
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.pricing.providers.len(), 2);
+        assert_eq!(config.pricing.providers[0].label(), "prices.toml");
+        assert!(
+            toml::from_str::<Config>("")
+                .unwrap()
+                .pricing
+                .providers
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_exchange_rates() {
+        let config: Config = toml::from_str(
+            r#"
+[exchange_rates]
+fixed_rates = { CNY = 7.1 }
+rates_file = "/etc/ccstats/fx.csv"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.exchange_rates.fixed_rates.get("CNY"), Some(&7.1));
+        assert_eq!(
+            config.exchange_rates.rates_file,
+            Some(PathBuf::from("/etc/ccstats/fx.csv"))
+        );
+    }
+
     // --- load_from_paths tests ---
 
     fn write_temp_config(content: &str) -> NamedTempFile {
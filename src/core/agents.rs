@@ -0,0 +1,130 @@
+//! Main-thread vs subagent split of each session for `ccstats agents`
+//!
+//! Subagent transcripts kept in their own file are folded into the session
+//! that launched them, so each row shows how much of a session's usage its
+//! subagents were responsible for.
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::types::{RawEntry, Stats};
+
+/// Usage of one agent role (main thread, all subagents, or one subagent type).
+#[derive(Debug, Default, Clone)]
+pub(crate) struct AgentUsage {
+    pub(crate) stats: Stats,
+    pub(crate) models: HashMap<String, Stats>,
+}
+
+impl AgentUsage {
+    fn add(&mut self, model: &str, stats: &Stats) {
+        self.stats.add(stats);
+        self.models.entry(model.to_string()).or_default().add(stats);
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SessionAgentStats {
+    pub(crate) session_id: String,
+    pub(crate) project_path: String,
+    pub(crate) last_timestamp: String,
+    pub(crate) last_timestamp_ms: i64,
+    pub(crate) main: AgentUsage,
+    pub(crate) subagents: AgentUsage,
+    /// Subagent usage per subagent type; `None` collects requests whose
+    /// launching `Task` call is unknown.
+    pub(crate) subagent_types: BTreeMap<Option<String>, AgentUsage>,
+}
+
+/// Split each session's entries into main-thread and subagent usage.
+/// Sessions are returned ordered by project, then session id.
+pub(crate) fn aggregate_agents(entries: Vec<RawEntry>) -> Vec<SessionAgentStats> {
+    let mut sessions: BTreeMap<(String, String), SessionAgentStats> = BTreeMap::new();
+    for entry in entries {
+        let stats = entry.to_stats();
+        let session_id = entry.parent_session_id.unwrap_or(entry.session_id);
+        let session = sessions
+            .entry((entry.project_path.clone(), session_id.clone()))
+            .or_insert_with(|| SessionAgentStats {
+                session_id,
+                project_path: entry.project_path,
+                ..SessionAgentStats::default()
+            });
+        if entry.timestamp_ms >= session.last_timestamp_ms {
+            session.last_timestamp = entry.timestamp;
+            session.last_timestamp_ms = entry.timestamp_ms;
+        }
+        if entry.is_sidechain {
+            session.subagents.add(&entry.model, &stats);
+            session
+                .subagent_types
+                .entry(entry.subagent_type)
+                .or_default()
+                .add(&entry.model, &stats);
+        } else {
+            session.main.add(&entry.model, &stats);
+        }
+    }
+    sessions.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CostKind, Endpoint, ServiceTier};
+
+    fn entry(session: &str, sidechain: bool, subagent_type: Option<&str>, input: i64) -> RawEntry {
+        RawEntry {
+            timestamp: "2026-02-06T12:00:00Z".to_string(),
+            timestamp_ms: 1_770_379_200_000,
+            date_str: "2026-02-06".to_string(),
+            message_id: None,
+            session_key: session.to_string(),
+            session_id: session.to_string(),
+            project_path: "proj".to_string(),
+            model: "sonnet-4".to_string(),
+            input_tokens: input,
+            output_tokens: 0,
+            cache_creation: 0,
+            cache_creation_1h: 0,
+            cache_read: 0,
+            reasoning_tokens: 0,
+            stop_reason: None,
+            cost_kind: CostKind::Real,
+            endpoint: Endpoint::Unknown,
+            call_count: 1,
+            recorded_cost_usd: None,
+            is_sidechain: sidechain,
+            service_tier: ServiceTier::Standard,
+            subagent_type: subagent_type.map(str::to_string),
+            parent_session_id: None,
+        }
+    }
+
+    #[test]
+    fn splits_main_and_subagent_usage_by_type() {
+        let sessions = aggregate_agents(vec![
+            entry("s1", false, None, 100),
+            entry("s1", true, Some("Explore"), 40),
+            entry("s1", true, Some("Explore"), 10),
+            entry("s1", true, None, 5),
+        ]);
+        assert_eq!(sessions.len(), 1);
+        let session = &sessions[0];
+        assert_eq!(session.main.stats.input_tokens, 100);
+        assert_eq!(session.subagents.stats.input_tokens, 55);
+        assert_eq!(session.subagents.stats.count, 3);
+        let explore = &session.subagent_types[&Some("Explore".to_string())];
+        assert_eq!(explore.stats.input_tokens, 50);
+        assert_eq!(session.subagent_types[&None].stats.input_tokens, 5);
+    }
+
+    #[test]
+    fn subagent_transcripts_fold_into_the_parent_session() {
+        let mut agent = entry("agent-a1", true, None, 30);
+        agent.parent_session_id = Some("s1".to_string());
+        let sessions = aggregate_agents(vec![entry("s1", false, None, 100), agent]);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, "s1");
+        assert_eq!(sessions[0].subagents.stats.input_tokens, 30);
+    }
+}
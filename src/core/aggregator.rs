@@ -5,8 +5,10 @@
 use chrono::{DateTime, Duration, FixedOffset, TimeZone, Timelike};
 use std::collections::HashMap;
 
+use crate::core::ContextSample;
 use crate::core::types::{
-    BlockStats, DayStats, Endpoint, EndpointStats, ProjectStats, RawEntry, SessionStats, Stats,
+    BlockStats, DailyModels, DayStats, Endpoint, EndpointStats, ProjectStats, RawEntry,
+    SessionStats, Stats,
 };
 
 /// Aggregate entries by day (consumes entries to avoid cloning)
@@ -64,6 +66,9 @@ struct SessionAccumulator {
     last_timestamp_ms: i64,
     stats: Stats,
     models: HashMap<String, Stats>,
+    /// Per-day model stats, only tracked when per-day exchange rates need them.
+    days: Option<DailyModels>,
+    context_samples: Vec<ContextSample>,
 }
 
 impl SessionAccumulator {
@@ -73,6 +78,7 @@ impl SessionAccumulator {
         project_path: String,
         timestamp: &str,
         timestamp_ms: i64,
+        track_days: bool,
     ) -> Self {
         SessionAccumulator {
             session_key,
@@ -84,6 +90,8 @@ impl SessionAccumulator {
             last_timestamp_ms: timestamp_ms,
             stats: Stats::default(),
             models: HashMap::new(),
+            days: track_days.then(HashMap::new),
+            context_samples: Vec::new(),
         }
     }
 
@@ -93,7 +101,16 @@ impl SessionAccumulator {
         let model = entry.model.clone();
         let stats = entry.to_stats();
         self.stats.add(&stats);
+        if let Some(days) = &mut self.days {
+            add_day_model(days, &entry.date_str, &model, &stats);
+        }
         self.models.entry(model).or_default().add(&stats);
+        if !entry.is_sidechain {
+            self.context_samples.push(ContextSample {
+                timestamp_ms,
+                tokens: stats.input_tokens + stats.cache_creation + stats.cache_read,
+            });
+        }
         self.update_timestamps(timestamp, timestamp_ms);
     }
 
@@ -121,17 +138,33 @@ impl SessionAccumulator {
             last_timestamp: self.last_timestamp,
             stats: self.stats,
             models: self.models,
+            days: self.days.unwrap_or_default(),
+            context_samples: self.context_samples,
         }
     }
 }
 
-/// Aggregate entries by session (consumes entries to avoid cloning)
-pub(crate) fn aggregate_sessions(entries: Vec<RawEntry>) -> Vec<SessionStats> {
-    aggregate_sessions_map(entries).into_values().collect()
+fn add_day_model(days: &mut DailyModels, date: &str, model: &str, stats: &Stats) {
+    days.entry(date.to_string())
+        .or_default()
+        .entry(model.to_string())
+        .or_default()
+        .add(stats);
+}
+
+/// Aggregate entries by session (consumes entries to avoid cloning).
+/// `track_days` keeps per-day model stats for daily exchange rates.
+pub(crate) fn aggregate_sessions(entries: Vec<RawEntry>, track_days: bool) -> Vec<SessionStats> {
+    aggregate_sessions_map(entries, track_days)
+        .into_values()
+        .collect()
 }
 
 /// Aggregate entries by session into a map keyed by stable internal session key.
-pub(crate) fn aggregate_sessions_map(entries: Vec<RawEntry>) -> HashMap<String, SessionStats> {
+pub(crate) fn aggregate_sessions_map(
+    entries: Vec<RawEntry>,
+    track_days: bool,
+) -> HashMap<String, SessionStats> {
     let mut sessions: HashMap<String, SessionAccumulator> = HashMap::with_capacity(entries.len());
 
     for entry in entries {
@@ -147,6 +180,7 @@ pub(crate) fn aggregate_sessions_map(entries: Vec<RawEntry>) -> HashMap<String,
                 entry.project_path.clone(),
                 &entry.timestamp,
                 entry.timestamp_ms,
+                track_days,
             )
         });
         session.add_entry(&entry);
@@ -172,6 +206,7 @@ pub(crate) fn aggregate_projects(sessions: Vec<SessionStats>) -> Vec<ProjectStat
                 session_count: 0,
                 stats: Stats::default(),
                 models: HashMap::new(),
+                days: HashMap::new(),
             });
 
         project.session_count += 1;
@@ -180,6 +215,12 @@ pub(crate) fn aggregate_projects(sessions: Vec<SessionStats>) -> Vec<ProjectStat
         for (model, model_stats) in session.models {
             project.models.entry(model).or_default().add(&model_stats);
         }
+        for (date, models) in session.days {
+            let day = project.days.entry(date).or_default();
+            for (model, model_stats) in models {
+                day.entry(model).or_default().add(&model_stats);
+            }
+        }
     }
 
     let mut projects: Vec<ProjectStats> = project_map.into_values().collect();
@@ -200,10 +241,12 @@ pub(crate) fn format_project_name(path: &str) -> String {
     path.trim_start_matches('-').to_string()
 }
 
-/// Aggregate entries by 5-hour billing blocks (consumes entries to avoid cloning)
+/// Aggregate entries by 5-hour billing blocks (consumes entries to avoid cloning).
+/// `track_days` keeps per-day model stats for daily exchange rates.
 pub(crate) fn aggregate_blocks(
     entries: Vec<RawEntry>,
     local_times: &HashMap<i64, DateTime<FixedOffset>>,
+    track_days: bool,
 ) -> Vec<BlockStats> {
     let mut block_map: HashMap<DateTime<FixedOffset>, BlockStats> =
         HashMap::with_capacity(entries.len() / 2 + 1);
@@ -223,9 +266,13 @@ pub(crate) fn aggregate_blocks(
             block_end: block_end.format("%H:%M").to_string(),
             stats: Stats::default(),
             models: HashMap::new(),
+            days: HashMap::new(),
         });
 
         block.stats.add(&stats);
+        if track_days {
+            add_day_model(&mut block.days, &entry.date_str, &entry.model, &stats);
+        }
         block.models.entry(entry.model).or_default().add(&stats);
     }
 
@@ -251,6 +298,7 @@ fn get_block_start(dt: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ServiceTier;
 
     fn make_entry(
         date: &str,
@@ -281,6 +329,10 @@ mod tests {
             endpoint: Endpoint::Unknown,
             call_count: 1,
             recorded_cost_usd: None,
+            is_sidechain: false,
+            service_tier: ServiceTier::Standard,
+            subagent_type: None,
+            parent_session_id: None,
         }
     }
 
@@ -470,7 +522,7 @@ mod tests {
 
     #[test]
     fn aggregate_sessions_empty() {
-        let result = aggregate_sessions(vec![]);
+        let result = aggregate_sessions(vec![], false);
         assert!(result.is_empty());
     }
 
@@ -480,7 +532,7 @@ mod tests {
             make_entry("2025-01-01", "s1", "/path/proj", "claude", 100, 50, 1000),
             make_entry("2025-01-01", "s1", "/path/proj", "claude", 200, 100, 5000),
         ];
-        let result = aggregate_sessions(entries);
+        let result = aggregate_sessions(entries, false);
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].session_id, "s1");
         assert_eq!(result[0].project_path, "/path/proj");
@@ -511,6 +563,10 @@ mod tests {
                 endpoint: Endpoint::Unknown,
                 call_count: 1,
                 recorded_cost_usd: None,
+                is_sidechain: false,
+                service_tier: ServiceTier::Standard,
+                subagent_type: None,
+                parent_session_id: None,
             },
             RawEntry {
                 timestamp: "2025-01-01T08:00:00Z".to_string(),
@@ -532,6 +588,10 @@ mod tests {
                 endpoint: Endpoint::Unknown,
                 call_count: 1,
                 recorded_cost_usd: None,
+                is_sidechain: false,
+                service_tier: ServiceTier::Standard,
+                subagent_type: None,
+                parent_session_id: None,
             },
             RawEntry {
                 timestamp: "2025-01-01T20:00:00Z".to_string(),
@@ -553,9 +613,13 @@ mod tests {
                 endpoint: Endpoint::Unknown,
                 call_count: 1,
                 recorded_cost_usd: None,
+                is_sidechain: false,
+                service_tier: ServiceTier::Standard,
+                subagent_type: None,
+                parent_session_id: None,
             },
         ];
-        let result = aggregate_sessions(entries);
+        let result = aggregate_sessions(entries, false);
         assert_eq!(result[0].first_timestamp, "2025-01-01T08:00:00Z");
         assert_eq!(result[0].last_timestamp, "2025-01-01T20:00:00Z");
     }
@@ -566,7 +630,7 @@ mod tests {
             make_entry("2025-01-01", "s1", "p1", "claude", 100, 50, 1000),
             make_entry("2025-01-01", "s2", "p2", "gpt-4", 200, 100, 2000),
         ];
-        let result = aggregate_sessions(entries);
+        let result = aggregate_sessions(entries, false);
         assert_eq!(result.len(), 2);
     }
 
@@ -576,12 +640,36 @@ mod tests {
             make_entry("2025-01-01", "s1", "p1", "claude", 100, 50, 1000),
             make_entry("2025-01-01", "s1", "p1", "gpt-4", 200, 100, 2000),
         ];
-        let result = aggregate_sessions(entries);
+        let result = aggregate_sessions(entries, false);
         assert_eq!(result[0].models.len(), 2);
         assert_eq!(result[0].models["claude"].input_tokens, 100);
         assert_eq!(result[0].models["gpt-4"].input_tokens, 200);
     }
 
+    #[test]
+    fn sessions_and_projects_keep_per_day_models() {
+        let entries = vec![
+            make_entry("2025-01-01", "s1", "p1", "claude", 100, 50, 1000),
+            make_entry("2025-01-02", "s1", "p1", "claude", 200, 100, 2000),
+            make_entry("2025-01-02", "s2", "p1", "gpt-4", 300, 0, 3000),
+        ];
+        assert!(
+            aggregate_sessions(entries.clone(), false)
+                .iter()
+                .all(|session| session.days.is_empty())
+        );
+        let sessions = aggregate_sessions(entries, true);
+        let s1 = sessions.iter().find(|s| s.session_id == "s1").unwrap();
+        assert_eq!(s1.days.len(), 2);
+        assert_eq!(s1.days["2025-01-02"]["claude"].input_tokens, 200);
+
+        let projects = aggregate_projects(sessions);
+        let days = &projects[0].days;
+        assert_eq!(days["2025-01-01"]["claude"].input_tokens, 100);
+        assert_eq!(days["2025-01-02"]["claude"].input_tokens, 200);
+        assert_eq!(days["2025-01-02"]["gpt-4"].input_tokens, 300);
+    }
+
     // --- aggregate_projects ---
 
     #[test]
@@ -613,6 +701,7 @@ mod tests {
                     ..Default::default()
                 },
             )]),
+            ..Default::default()
         }];
         let result = aggregate_projects(sessions);
         assert_eq!(result.len(), 1);
@@ -701,7 +790,7 @@ mod tests {
 
     #[test]
     fn aggregate_blocks_empty() {
-        let result = aggregate_blocks(vec![], &HashMap::new());
+        let result = aggregate_blocks(vec![], &HashMap::new(), false);
         assert!(result.is_empty());
     }
 
@@ -709,7 +798,7 @@ mod tests {
     fn aggregate_blocks_skips_missing_timestamps() {
         let entries = vec![make_entry("2025-01-01", "s1", "p1", "claude", 100, 50, 999)];
         // local_times map doesn't contain ts_ms=999
-        let result = aggregate_blocks(entries, &HashMap::new());
+        let result = aggregate_blocks(entries, &HashMap::new(), false);
         assert!(result.is_empty());
     }
 
@@ -729,7 +818,7 @@ mod tests {
             make_entry("2025-01-01", "s1", "p1", "claude", 300, 150, 3000),
         ];
 
-        let result = aggregate_blocks(entries, &local_times);
+        let result = aggregate_blocks(entries, &local_times, false);
         assert_eq!(result.len(), 2);
         // sorted by block_start
         assert!(result[0].block_start.contains("00:00"));
@@ -752,7 +841,7 @@ mod tests {
             make_entry("2025-01-01", "s1", "p1", "claude", 100, 50, 1000),
         ];
 
-        let result = aggregate_blocks(entries, &local_times);
+        let result = aggregate_blocks(entries, &local_times, false);
         assert!(result[0].block_start < result[1].block_start);
     }
 
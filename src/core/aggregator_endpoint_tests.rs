@@ -26,6 +26,10 @@ fn entry(model: &str, endpoint: Endpoint, input: i64) -> RawEntry {
         endpoint,
         call_count: 1,
         recorded_cost_usd: None,
+        is_sidechain: false,
+        service_tier: crate::core::ServiceTier::Standard,
+        subagent_type: None,
+        parent_session_id: None,
     }
 }
 
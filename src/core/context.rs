@@ -0,0 +1,125 @@
+//! Context-window growth per session for `ccstats session --context`
+//!
+//! A request's prompt (fresh input plus cache writes and reads) is the size
+//! of the context at that turn. Main-thread samples are replayed in time
+//! order; subagent requests run in their own context and are left out.
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Default `--context-threshold`: turns at or above it count as riding the
+/// ceiling.
+pub(crate) const DEFAULT_CONTEXT_THRESHOLD: i64 = 150_000;
+
+/// A drop to at most this fraction of the previous turn's context is a
+/// compaction.
+const COMPACTION_DROP_RATIO: f64 = 0.5;
+
+/// Contexts smaller than this are too small for a drop to be a compaction.
+const MIN_COMPACTION_CONTEXT: i64 = 50_000;
+
+/// Context size of one main-thread request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ContextSample {
+    pub(crate) timestamp_ms: i64,
+    pub(crate) tokens: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, JsonSchema)]
+pub(crate) struct ContextStats {
+    pub(crate) turns: usize,
+    pub(crate) peak_tokens: i64,
+    pub(crate) average_tokens: i64,
+    pub(crate) compactions: usize,
+    pub(crate) turns_above_threshold: usize,
+    pub(crate) threshold: i64,
+}
+
+impl ContextStats {
+    pub(crate) fn above_threshold_pct(&self) -> f64 {
+        if self.turns == 0 {
+            0.0
+        } else {
+            self.turns_above_threshold as f64 / self.turns as f64 * 100.0
+        }
+    }
+}
+
+fn is_compaction(previous: i64, current: i64) -> bool {
+    previous >= MIN_COMPACTION_CONTEXT
+        && (current as f64) <= previous as f64 * COMPACTION_DROP_RATIO
+}
+
+/// Summarize context samples (in any order) against `threshold`.
+pub(crate) fn context_stats(samples: &[ContextSample], threshold: i64) -> ContextStats {
+    let mut ordered = samples.to_vec();
+    ordered.sort_by_key(|sample| sample.timestamp_ms);
+    let total: i64 = ordered.iter().map(|sample| sample.tokens).sum();
+    let compactions = ordered
+        .windows(2)
+        .filter(|pair| is_compaction(pair[0].tokens, pair[1].tokens))
+        .count();
+    ContextStats {
+        turns: ordered.len(),
+        peak_tokens: ordered
+            .iter()
+            .map(|sample| sample.tokens)
+            .max()
+            .unwrap_or(0),
+        average_tokens: if ordered.is_empty() {
+            0
+        } else {
+            total / ordered.len() as i64
+        },
+        compactions,
+        turns_above_threshold: ordered
+            .iter()
+            .filter(|sample| sample.tokens >= threshold)
+            .count(),
+        threshold,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(tokens: &[i64]) -> Vec<ContextSample> {
+        tokens
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, &tokens)| ContextSample {
+                timestamp_ms: i as i64,
+                tokens,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn counts_peak_average_compactions_and_ceiling_turns() {
+        let stats = context_stats(
+            &samples(&[20_000, 90_000, 160_000, 170_000, 30_000, 40_000, 30_000]),
+            150_000,
+        );
+        assert_eq!(stats.turns, 7);
+        assert_eq!(stats.peak_tokens, 170_000);
+        assert_eq!(stats.average_tokens, 77_142);
+        assert_eq!(stats.compactions, 1);
+        assert_eq!(stats.turns_above_threshold, 2);
+        assert!((stats.above_threshold_pct() - 28.57).abs() < 0.01);
+    }
+
+    #[test]
+    fn small_contexts_never_count_as_compactions() {
+        let stats = context_stats(&samples(&[40_000, 10_000]), 150_000);
+        assert_eq!(stats.compactions, 0);
+        assert_eq!(
+            context_stats(&[], 150_000),
+            ContextStats {
+                threshold: 150_000,
+                ..ContextStats::default()
+            }
+        );
+    }
+}
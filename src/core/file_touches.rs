@@ -0,0 +1,240 @@
+//! File touch analytics for `ccstats files`
+//!
+//! Tool calls that read or write files carry their target paths. Paths are
+//! aggregated per project into reads, writes and distinct sessions per file,
+//! and rolled up to their parent directories to find hot spots.
+
+use std::collections::{HashMap, HashSet};
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::aggregator::format_project_name;
+use super::tool_types::{ToolCall, ToolCallIdentity};
+
+/// Whether a tool reads or writes the files it targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FileAccess {
+    Read,
+    Write,
+}
+
+impl FileAccess {
+    /// Access of a tool by name; `None` for tools that do not target files.
+    pub(crate) fn of_tool(name: &str) -> Option<Self> {
+        match name {
+            "Read" | "Grep" | "Glob" | "NotebookRead" => Some(Self::Read),
+            "Edit" | "Write" | "MultiEdit" | "NotebookEdit" | "apply_patch" => Some(Self::Write),
+            _ => None,
+        }
+    }
+}
+
+/// `path` relative to the working directory it was used from, when inside it.
+pub(crate) fn relative_path(path: &str, cwd: Option<&str>) -> String {
+    cwd.map(|cwd| cwd.trim_end_matches('/'))
+        .filter(|cwd| !cwd.is_empty())
+        .and_then(|cwd| path.strip_prefix(cwd))
+        .and_then(|rest| rest.strip_prefix('/'))
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(path)
+        .to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub(crate) struct FileStats {
+    pub(crate) project: String,
+    pub(crate) path: String,
+    pub(crate) reads: u64,
+    pub(crate) writes: u64,
+    /// Distinct sessions that touched the file.
+    pub(crate) sessions: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub(crate) struct DirectoryStats {
+    pub(crate) project: String,
+    pub(crate) directory: String,
+    pub(crate) reads: u64,
+    pub(crate) writes: u64,
+    /// Distinct files touched in the directory.
+    pub(crate) files: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct FileSummary {
+    /// Ordered by project, then touches descending.
+    pub(crate) files: Vec<FileStats>,
+    /// Ordered by project, then touches descending.
+    pub(crate) directories: Vec<DirectoryStats>,
+    /// File-targeting tool calls.
+    pub(crate) total: u64,
+}
+
+fn parent_directory(path: &str) -> String {
+    match path.trim_end_matches('/').rsplit_once('/') {
+        Some(("", _)) => "/".to_string(),
+        Some((dir, _)) => dir.to_string(),
+        None => ".".to_string(),
+    }
+}
+
+#[derive(Default)]
+struct FileAccumulator {
+    reads: u64,
+    writes: u64,
+    sessions: HashSet<String>,
+}
+
+impl FileAccumulator {
+    fn add(&mut self, access: FileAccess) {
+        match access {
+            FileAccess::Read => self.reads += 1,
+            FileAccess::Write => self.writes += 1,
+        }
+    }
+}
+
+/// Aggregate file reads and writes per project, file and directory.
+pub(crate) fn aggregate_files(calls: &[ToolCall]) -> FileSummary {
+    let mut seen_identities: HashSet<ToolCallIdentity> = HashSet::new();
+    let mut files: HashMap<(String, String), FileAccumulator> = HashMap::new();
+    let mut total = 0;
+
+    for call in calls {
+        let Some(access) = FileAccess::of_tool(&call.name) else {
+            continue;
+        };
+        if call.file_paths.is_empty() {
+            continue;
+        }
+        if let Some(identity) = &call.identity
+            && !seen_identities.insert(identity.clone())
+        {
+            continue;
+        }
+        total += 1;
+        let project = format_project_name(&call.project_path);
+        for path in &call.file_paths {
+            let file = files.entry((project.clone(), path.clone())).or_default();
+            file.add(access);
+            file.sessions.insert(call.session_key.clone());
+        }
+    }
+
+    let mut directories: HashMap<(String, String), DirectoryStats> = HashMap::new();
+    for ((project, path), file) in &files {
+        let directory = parent_directory(path);
+        let dir = directories
+            .entry((project.clone(), directory.clone()))
+            .or_insert_with(|| DirectoryStats {
+                project: project.clone(),
+                directory,
+                ..DirectoryStats::default()
+            });
+        dir.reads += file.reads;
+        dir.writes += file.writes;
+        dir.files += 1;
+    }
+
+    let mut files: Vec<FileStats> = files
+        .into_iter()
+        .map(|((project, path), file)| FileStats {
+            project,
+            path,
+            reads: file.reads,
+            writes: file.writes,
+            sessions: file.sessions.len(),
+        })
+        .collect();
+    files.sort_by(|a, b| {
+        a.project
+            .cmp(&b.project)
+            .then_with(|| (b.reads + b.writes).cmp(&(a.reads + a.writes)))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    let mut directories: Vec<DirectoryStats> = directories.into_values().collect();
+    directories.sort_by(|a, b| {
+        a.project
+            .cmp(&b.project)
+            .then_with(|| (b.reads + b.writes).cmp(&(a.reads + a.writes)))
+            .then_with(|| a.directory.cmp(&b.directory))
+    });
+
+    FileSummary {
+        files,
+        directories,
+        total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, session: &str, paths: &[&str]) -> ToolCall {
+        ToolCall {
+            name: name.to_string(),
+            project_path: "-work-app".to_string(),
+            session_key: session.to_string(),
+            file_paths: paths.iter().map(|path| (*path).to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn relative_path_strips_the_working_directory() {
+        assert_eq!(
+            relative_path("/work/app/src/main.rs", Some("/work/app")),
+            "src/main.rs"
+        );
+        assert_eq!(
+            relative_path("/work/app/src/main.rs", Some("/work/app/")),
+            "src/main.rs"
+        );
+        assert_eq!(
+            relative_path("/work/application/x.rs", Some("/work/app")),
+            "/work/application/x.rs"
+        );
+        assert_eq!(relative_path("src/lib.rs", None), "src/lib.rs");
+    }
+
+    #[test]
+    fn aggregates_reads_writes_sessions_and_directories() {
+        let mut replayed = call("Read", "s1", &["src/main.rs"]);
+        replayed.identity = Some(ToolCallIdentity::new("s1", "m", "t1"));
+        let calls = vec![
+            call("Read", "s1", &["src/main.rs"]),
+            call("Read", "s2", &["src/main.rs"]),
+            call("Edit", "s2", &["src/main.rs"]),
+            call("apply_patch", "s3", &["src/lib.rs", "README.md"]),
+            replayed.clone(),
+            replayed,
+            call("Bash", "s1", &["ignored.rs"]),
+            call("Read", "s1", &[]),
+        ];
+
+        let summary = aggregate_files(&calls);
+        assert_eq!(summary.total, 5);
+        let files: Vec<_> = summary
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.reads, f.writes, f.sessions))
+            .collect();
+        assert_eq!(
+            files,
+            [
+                ("src/main.rs", 3, 1, 2),
+                ("README.md", 0, 1, 1),
+                ("src/lib.rs", 0, 1, 1)
+            ]
+        );
+        assert_eq!(summary.files[0].project, "work-app");
+        let dirs: Vec<_> = summary
+            .directories
+            .iter()
+            .map(|d| (d.directory.as_str(), d.reads + d.writes, d.files))
+            .collect();
+        assert_eq!(dirs, [("src", 5, 2), (".", 1, 1)]);
+    }
+}
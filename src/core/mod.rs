@@ -1,23 +1,36 @@
 //! Core module - shared types and logic for all data sources
 
+mod agents;
 mod aggregator;
 #[cfg(test)]
 mod aggregator_endpoint_tests;
+mod context;
 mod dedup;
+mod file_touches;
+mod session_timeline;
+mod shell_commands;
 mod tool_aggregator;
 mod tool_types;
 mod types;
 
+pub(crate) use agents::{AgentUsage, SessionAgentStats, aggregate_agents};
 pub(crate) use aggregator::{
     aggregate_blocks, aggregate_by_endpoint, aggregate_daily, aggregate_projects,
     aggregate_sessions, aggregate_sessions_map, format_project_name, merge_day_stats,
 };
+pub(crate) use context::{ContextSample, ContextStats, DEFAULT_CONTEXT_THRESHOLD, context_stats};
 pub(crate) use dedup::{DedupAccumulator, source_wide_message_id};
-pub(crate) use tool_aggregator::aggregate_tools;
-#[cfg(test)]
-pub(crate) use tool_types::ToolStats;
-pub(crate) use tool_types::{ToolCall, ToolCallIdentity, ToolSummary};
+pub(crate) use file_touches::{
+    DirectoryStats, FileStats, FileSummary, aggregate_files, relative_path,
+};
+pub(crate) use session_timeline::{SessionTimeline, TimelineTurn, build_session_timeline};
+pub(crate) use shell_commands::{CommandStats, CommandSummary, RiskyCommand, aggregate_commands};
+pub(crate) use tool_aggregator::{aggregate_tools, aggregate_tools_by};
+pub(crate) use tool_types::{
+    ToolCall, ToolCallIdentity, ToolStats, ToolSummary, server_and_tool, tool_category,
+};
 pub(crate) use types::{
-    BlockStats, CostKind, CostTokens, DataQuality, DateFilter, DayStats, Endpoint, EndpointStats,
-    LoadResult, ProjectStats, RawEntry, SessionStats, Stats,
+    BlockStats, CostKind, CostTokens, DailyModels, DataQuality, DateFilter, DayStats, Endpoint,
+    EndpointStats, LONG_CONTEXT_THRESHOLD, LoadResult, ProjectStats, PromptSizeTokens, RawEntry,
+    RequestTokens, ServiceTier, ServiceTierTokens, SessionStats, Stats, set_prompt_size_floor,
 };
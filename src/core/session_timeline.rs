@@ -0,0 +1,208 @@
+//! Turn-by-turn timeline of a single session for `ccstats session show`
+//!
+//! Built from the deduplicated entries of one session: one turn per model
+//! request in time order, with the main-thread/subagent segments they form
+//! and the idle gaps between them.
+
+use std::collections::HashMap;
+
+use super::types::{RawEntry, Stats};
+
+/// A pause between consecutive requests at least this long is an idle gap.
+pub(crate) const IDLE_GAP_MS: i64 = 5 * 60 * 1000;
+
+/// One model request of the session.
+#[derive(Debug, Clone)]
+pub(crate) struct TimelineTurn {
+    pub(crate) timestamp: String,
+    pub(crate) timestamp_ms: i64,
+    /// Local day of the request (`YYYY-MM-DD`).
+    pub(crate) date: String,
+    pub(crate) model: String,
+    /// Made by a subagent rather than the main thread.
+    pub(crate) sidechain: bool,
+    pub(crate) stats: Stats,
+    pub(crate) stop_reason: Option<String>,
+    /// Tools invoked by the request's response, in call order.
+    pub(crate) tools: Vec<String>,
+}
+
+impl TimelineTurn {
+    /// Prompt size of the request: fresh input plus cache writes and reads.
+    pub(crate) fn context_tokens(&self) -> i64 {
+        self.stats.input_tokens + self.stats.cache_creation + self.stats.cache_read
+    }
+}
+
+/// A run of consecutive turns on the same thread (main or subagent).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TimelineSegment {
+    pub(crate) sidechain: bool,
+    /// Index of the first turn in the segment.
+    pub(crate) start: usize,
+    /// Index of the last turn in the segment.
+    pub(crate) end: usize,
+}
+
+/// A pause of at least [`IDLE_GAP_MS`] before the turn at `before`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IdleGap {
+    pub(crate) before: usize,
+    pub(crate) duration_ms: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SessionTimeline {
+    pub(crate) session_id: String,
+    pub(crate) project_path: String,
+    pub(crate) turns: Vec<TimelineTurn>,
+    pub(crate) segments: Vec<TimelineSegment>,
+    pub(crate) idle_gaps: Vec<IdleGap>,
+}
+
+impl SessionTimeline {
+    /// Idle gap that precedes the turn at `index`, if any.
+    pub(crate) fn gap_before(&self, index: usize) -> Option<&IdleGap> {
+        self.idle_gaps.iter().find(|gap| gap.before == index)
+    }
+
+    pub(crate) fn peak_context_tokens(&self) -> i64 {
+        self.turns
+            .iter()
+            .map(TimelineTurn::context_tokens)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Build the timeline of one session's entries. `tools_by_message` maps an
+/// entry's `message_id` to the tools its response invoked.
+pub(crate) fn build_session_timeline(
+    mut entries: Vec<RawEntry>,
+    tools_by_message: &HashMap<String, Vec<String>>,
+) -> SessionTimeline {
+    entries.sort_by_key(|entry| entry.timestamp_ms);
+    let mut timeline = SessionTimeline::default();
+    if let Some(first) = entries.first() {
+        timeline.session_id.clone_from(&first.session_id);
+        timeline.project_path.clone_from(&first.project_path);
+    }
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        if let Some(previous) = timeline.turns.last() {
+            let duration_ms = entry.timestamp_ms - previous.timestamp_ms;
+            if duration_ms >= IDLE_GAP_MS {
+                timeline.idle_gaps.push(IdleGap {
+                    before: index,
+                    duration_ms,
+                });
+            }
+        }
+        match timeline.segments.last_mut() {
+            Some(segment) if segment.sidechain == entry.is_sidechain => segment.end = index,
+            _ => timeline.segments.push(TimelineSegment {
+                sidechain: entry.is_sidechain,
+                start: index,
+                end: index,
+            }),
+        }
+        let tools = entry
+            .message_id
+            .as_ref()
+            .and_then(|id| tools_by_message.get(id))
+            .cloned()
+            .unwrap_or_default();
+        timeline.turns.push(TimelineTurn {
+            stats: entry.to_stats(),
+            timestamp: entry.timestamp,
+            timestamp_ms: entry.timestamp_ms,
+            date: entry.date_str,
+            model: entry.model,
+            sidechain: entry.is_sidechain,
+            stop_reason: entry.stop_reason,
+            tools,
+        });
+    }
+    timeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CostKind, Endpoint, ServiceTier};
+
+    fn entry(id: &str, minute: i64, sidechain: bool) -> RawEntry {
+        RawEntry {
+            timestamp: format!("2026-02-06T12:{minute:02}:00Z"),
+            timestamp_ms: 1_770_379_200_000 + minute * 60_000,
+            date_str: "2026-02-06".to_string(),
+            message_id: Some(id.to_string()),
+            session_key: "s".to_string(),
+            session_id: "s".to_string(),
+            project_path: "-work-app".to_string(),
+            model: "sonnet-4".to_string(),
+            input_tokens: 10,
+            output_tokens: 5,
+            cache_creation: 100,
+            cache_creation_1h: 0,
+            cache_read: 1_000,
+            reasoning_tokens: 0,
+            stop_reason: Some("tool_use".to_string()),
+            cost_kind: CostKind::Real,
+            endpoint: Endpoint::Unknown,
+            call_count: 1,
+            recorded_cost_usd: None,
+            is_sidechain: sidechain,
+            service_tier: ServiceTier::Standard,
+            subagent_type: None,
+            parent_session_id: None,
+        }
+    }
+
+    #[test]
+    fn orders_turns_and_finds_segments_and_idle_gaps() {
+        let entries = vec![
+            entry("m3", 2, true),
+            entry("m1", 0, false),
+            entry("m2", 1, true),
+            entry("m4", 20, false),
+        ];
+        let tools = HashMap::from([("m1".to_string(), vec!["Task".to_string()])]);
+        let timeline = build_session_timeline(entries, &tools);
+
+        let ids: Vec<i64> = timeline.turns.iter().map(|t| t.timestamp_ms).collect();
+        assert!(ids.is_sorted());
+        assert_eq!(timeline.turns[0].tools, ["Task"]);
+        assert!(timeline.turns[1].tools.is_empty());
+        assert_eq!(timeline.turns[0].context_tokens(), 1_110);
+        assert_eq!(
+            timeline.segments,
+            [
+                TimelineSegment {
+                    sidechain: false,
+                    start: 0,
+                    end: 0
+                },
+                TimelineSegment {
+                    sidechain: true,
+                    start: 1,
+                    end: 2
+                },
+                TimelineSegment {
+                    sidechain: false,
+                    start: 3,
+                    end: 3
+                },
+            ]
+        );
+        assert_eq!(
+            timeline.idle_gaps,
+            [IdleGap {
+                before: 3,
+                duration_ms: 18 * 60_000
+            }]
+        );
+        assert!(timeline.gap_before(3).is_some());
+        assert_eq!(timeline.session_id, "s");
+    }
+}
@@ -0,0 +1,420 @@
+//! Shell command analytics for `tools commands`
+//!
+//! Commands run through shell tools are split on `&&`, `||`, `;` and `|`, and
+//! each segment is normalized to its program name plus subcommand
+//! (`cargo test`, `git commit`, `npm run`). Risky patterns are matched on the
+//! whole command line so pipelines such as `curl ... | sh` are caught.
+
+use std::collections::{HashMap, HashSet};
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::aggregator::format_project_name;
+use super::tool_types::{ToolCall, ToolCallIdentity};
+
+/// Programs whose first non-option argument selects what they do.
+const SUBCOMMAND_PROGRAMS: &[&str] = &[
+    "apt",
+    "apt-get",
+    "brew",
+    "bun",
+    "cargo",
+    "deno",
+    "docker",
+    "gh",
+    "git",
+    "go",
+    "helm",
+    "kubectl",
+    "npm",
+    "pip",
+    "pip3",
+    "pnpm",
+    "poetry",
+    "rustup",
+    "systemctl",
+    "terraform",
+    "uv",
+    "yarn",
+];
+
+/// Prefixes that run the command after them.
+const WRAPPERS: &[&str] = &["command", "env", "exec", "nohup", "sudo", "time"];
+
+const SHELLS: &[&str] = &["bash", "sh", "zsh"];
+
+/// Risky pattern labels, as shown in the report.
+const RM_RF: &str = "rm -rf";
+const FORCE_PUSH: &str = "git push --force";
+const PIPE_TO_SHELL: &str = "curl | sh";
+const HARD_RESET: &str = "git reset --hard";
+const CHMOD_777: &str = "chmod 777";
+
+/// How often one normalized command ran in a project.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct CommandStats {
+    pub(crate) project: String,
+    pub(crate) command: String,
+    pub(crate) runs: u64,
+}
+
+/// A command line that matched a risky pattern.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub(crate) struct RiskyCommand {
+    pub(crate) pattern: &'static str,
+    pub(crate) project: String,
+    pub(crate) command: String,
+    pub(crate) runs: u64,
+    pub(crate) last_seen: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct CommandSummary {
+    /// Ordered by project, then runs descending.
+    pub(crate) commands: Vec<CommandStats>,
+    /// Ordered by runs descending.
+    pub(crate) risky: Vec<RiskyCommand>,
+    /// Shell tool calls that carried a command.
+    pub(crate) total: u64,
+}
+
+impl CommandSummary {
+    /// Normalized command runs per project, the denominator for percentages.
+    pub(crate) fn project_runs(&self) -> HashMap<&str, u64> {
+        let mut runs: HashMap<&str, u64> = HashMap::new();
+        for stats in &self.commands {
+            *runs.entry(stats.project.as_str()).or_default() += stats.runs;
+        }
+        runs
+    }
+}
+
+/// Split a command line into its simple commands on `&&`, `||`, `;`, `|`
+/// and newlines, ignoring operators inside quotes.
+fn split_segments(command: &str) -> Vec<&str> {
+    let bytes = command.as_bytes();
+    let mut segments = Vec::new();
+    let mut quote: Option<u8> = None;
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        match quote {
+            Some(q) if byte == q => quote = None,
+            Some(_) => {}
+            None => match byte {
+                b'\'' | b'"' => quote = Some(byte),
+                b';' | b'\n' | b'|' | b'&' => {
+                    let doubled = matches!(byte, b'|' | b'&') && bytes.get(i + 1) == Some(&byte);
+                    if byte == b'&' && !doubled {
+                        i += 1;
+                        continue;
+                    }
+                    segments.push(&command[start..i]);
+                    i += if doubled { 2 } else { 1 };
+                    start = i;
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        i += 1;
+    }
+    segments.push(&command[start..]);
+    segments
+        .into_iter()
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Whitespace-separated words with surrounding quotes removed.
+fn words(segment: &str) -> Vec<String> {
+    segment
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c| c == '\'' || c == '"').to_string())
+        .collect()
+}
+
+fn program_name(word: &str) -> &str {
+    word.rsplit('/').next().unwrap_or(word)
+}
+
+/// Words of a simple command after leading `VAR=value` assignments and
+/// wrappers such as `sudo` or `env`.
+fn command_words(segment: &str) -> Vec<String> {
+    let words = words(segment);
+    let start = words
+        .iter()
+        .position(|word| {
+            let is_assignment = word.contains('=') && !word.starts_with('=');
+            !is_assignment && !WRAPPERS.contains(&program_name(word))
+        })
+        .unwrap_or(words.len());
+    words[start..].to_vec()
+}
+
+/// Normalize one simple command to `program` or `program subcommand`.
+fn normalize_segment(segment: &str) -> Option<String> {
+    let words = command_words(segment);
+    let program = program_name(words.first()?);
+    if program.is_empty() {
+        return None;
+    }
+    if !SUBCOMMAND_PROGRAMS.contains(&program) {
+        return Some(program.to_string());
+    }
+    let mut args = words[1..].iter();
+    while let Some(arg) = args.next() {
+        if program == "git" && matches!(arg.as_str(), "-C" | "-c") {
+            // `git -C <dir>` / `git -c <key=value>` take a value.
+            args.next();
+        } else if !arg.starts_with('-') && !arg.starts_with('+') {
+            return Some(format!("{program} {arg}"));
+        }
+    }
+    Some(program.to_string())
+}
+
+/// The script a `bash -c`/`bash -lc` invocation runs, if `command` is one.
+fn shell_script(command: &str) -> Option<&str> {
+    let rest = command.trim_start();
+    let (shell, rest) = rest.split_once(char::is_whitespace)?;
+    if !SHELLS.contains(&program_name(shell)) {
+        return None;
+    }
+    let (flag, script) = rest.trim_start().split_once(char::is_whitespace)?;
+    if !(flag.starts_with('-') && flag.ends_with('c')) {
+        return None;
+    }
+    let script = script.trim();
+    Some(
+        script
+            .strip_prefix('\'')
+            .and_then(|s| s.strip_suffix('\''))
+            .or_else(|| script.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+            .unwrap_or(script),
+    )
+}
+
+/// Normalized commands run by one command line, in order.
+pub(crate) fn normalize_command(command: &str) -> Vec<String> {
+    let command = shell_script(command).unwrap_or(command);
+    split_segments(command)
+        .into_iter()
+        .filter_map(normalize_segment)
+        .collect()
+}
+
+fn has_short_flags(words: &[String], flags: &[char]) -> bool {
+    let mut seen = HashSet::new();
+    for word in words {
+        if let Some(short) = word.strip_prefix('-').filter(|w| !w.starts_with('-')) {
+            seen.extend(short.chars().map(|c| c.to_ascii_lowercase()));
+        } else if word == "--recursive" {
+            seen.insert('r');
+        } else if word == "--force" {
+            seen.insert('f');
+        }
+    }
+    flags.iter().all(|flag| seen.contains(flag))
+}
+
+fn segment_risk(words: &[String]) -> Option<&'static str> {
+    let program = program_name(words.first()?);
+    let args = &words[1..];
+    let has = |arg: &str| args.iter().any(|a| a == arg);
+    match program {
+        "rm" if has_short_flags(args, &['r', 'f']) => Some(RM_RF),
+        "git" if has("push") && (has("--force") || has("-f")) => Some(FORCE_PUSH),
+        "git" if has("reset") && has("--hard") => Some(HARD_RESET),
+        "chmod" if args.iter().any(|a| a == "777" || a == "a+rwx") => Some(CHMOD_777),
+        _ => None,
+    }
+}
+
+/// Risky patterns a command line matches, in the order they appear.
+pub(crate) fn risky_patterns(command: &str) -> Vec<&'static str> {
+    let command = shell_script(command).unwrap_or(command);
+    let mut patterns = Vec::new();
+    let mut after_download = false;
+    for segment in split_segments(command) {
+        let words = command_words(segment);
+        let program = words.first().map_or("", |word| program_name(word));
+        if after_download && SHELLS.contains(&program) && !patterns.contains(&PIPE_TO_SHELL) {
+            patterns.push(PIPE_TO_SHELL);
+        }
+        after_download = matches!(program, "curl" | "wget");
+        if let Some(pattern) = segment_risk(&words)
+            && !patterns.contains(&pattern)
+        {
+            patterns.push(pattern);
+        }
+    }
+    patterns
+}
+
+/// Rank normalized shell commands per project and collect risky command lines.
+pub(crate) fn aggregate_commands(calls: &[ToolCall]) -> CommandSummary {
+    let mut seen_identities: HashSet<ToolCallIdentity> = HashSet::new();
+    let mut commands: HashMap<(String, String), u64> = HashMap::new();
+    let mut risky: HashMap<(&'static str, String, String), RiskyCommand> = HashMap::new();
+    let mut total = 0;
+
+    for call in calls {
+        let Some(command) = call.command.as_deref() else {
+            continue;
+        };
+        if let Some(identity) = &call.identity
+            && !seen_identities.insert(identity.clone())
+        {
+            continue;
+        }
+        total += 1;
+        let project = format_project_name(&call.project_path);
+        for normalized in normalize_command(command) {
+            *commands.entry((project.clone(), normalized)).or_default() += 1;
+        }
+        for pattern in risky_patterns(command) {
+            let entry = risky
+                .entry((pattern, project.clone(), command.trim().to_string()))
+                .or_insert_with(|| RiskyCommand {
+                    pattern,
+                    project: project.clone(),
+                    command: command.trim().to_string(),
+                    ..RiskyCommand::default()
+                });
+            entry.runs += 1;
+            if call.date_str > entry.last_seen {
+                entry.last_seen.clone_from(&call.date_str);
+            }
+        }
+    }
+
+    let mut commands: Vec<CommandStats> = commands
+        .into_iter()
+        .map(|((project, command), runs)| CommandStats {
+            project,
+            command,
+            runs,
+        })
+        .collect();
+    commands.sort_by(|a, b| {
+        a.project
+            .cmp(&b.project)
+            .then_with(|| b.runs.cmp(&a.runs))
+            .then_with(|| a.command.cmp(&b.command))
+    });
+    let mut risky: Vec<RiskyCommand> = risky.into_values().collect();
+    risky.sort_by(|a, b| {
+        b.runs
+            .cmp(&a.runs)
+            .then_with(|| b.last_seen.cmp(&a.last_seen))
+            .then_with(|| a.command.cmp(&b.command))
+    });
+
+    CommandSummary {
+        commands,
+        risky,
+        total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(project: &str, command: &str) -> ToolCall {
+        ToolCall {
+            name: "Bash".to_string(),
+            date_str: "2026-02-06".to_string(),
+            project_path: project.to_string(),
+            command: Some(command.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn normalizes_program_and_subcommand() {
+        assert_eq!(normalize_command("cargo test --workspace"), ["cargo test"]);
+        assert_eq!(normalize_command("cargo +nightly fmt"), ["cargo fmt"]);
+        assert_eq!(
+            normalize_command("git -C ../repo commit -m 'fix: a && b'"),
+            ["git commit"]
+        );
+        assert_eq!(normalize_command("npm run build"), ["npm run"]);
+        assert_eq!(
+            normalize_command("RUST_LOG=debug sudo /usr/bin/ls -la"),
+            ["ls"]
+        );
+        assert_eq!(
+            normalize_command("cd crate && cargo build 2>&1 | tail -5; echo done"),
+            ["cd", "cargo build", "tail", "echo"]
+        );
+        assert_eq!(
+            normalize_command("bash -lc 'git status && make'"),
+            ["git status", "make"]
+        );
+        assert!(normalize_command("   ").is_empty());
+    }
+
+    #[test]
+    fn flags_risky_patterns() {
+        assert_eq!(risky_patterns("rm -rf target"), [RM_RF]);
+        assert_eq!(risky_patterns("rm -r -f /tmp/x"), [RM_RF]);
+        assert!(risky_patterns("rm -r target").is_empty());
+        assert_eq!(risky_patterns("git push --force origin main"), [FORCE_PUSH]);
+        assert_eq!(risky_patterns("git push -f"), [FORCE_PUSH]);
+        assert!(risky_patterns("git push --force-with-lease").is_empty());
+        assert_eq!(
+            risky_patterns("curl -fsSL https://example.com/install.sh | sh"),
+            [PIPE_TO_SHELL]
+        );
+        assert_eq!(risky_patterns("wget -qO- x | sudo bash"), [PIPE_TO_SHELL]);
+        assert!(risky_patterns("curl https://example.com | jq .").is_empty());
+        assert_eq!(
+            risky_patterns("git reset --hard && chmod -R 777 ."),
+            [HARD_RESET, CHMOD_777]
+        );
+        assert!(risky_patterns("echo 'rm -rf /'").is_empty());
+    }
+
+    #[test]
+    fn aggregates_per_project_and_deduplicates_calls() {
+        let mut replayed = call("-work-app", "cargo test");
+        replayed.identity = Some(ToolCallIdentity::new("s", "m", "t1"));
+        let calls = vec![
+            call("-work-app", "cargo test"),
+            call("-work-app", "cargo test -p core && git status"),
+            replayed.clone(),
+            replayed,
+            call("-work-lib", "rm -rf dist"),
+            ToolCall {
+                name: "Read".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let summary = aggregate_commands(&calls);
+        assert_eq!(summary.total, 4);
+        let rows: Vec<_> = summary
+            .commands
+            .iter()
+            .map(|c| (c.project.as_str(), c.command.as_str(), c.runs))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("work-app", "cargo test", 3),
+                ("work-app", "git status", 1),
+                ("work-lib", "rm", 1)
+            ]
+        );
+        assert_eq!(summary.project_runs()["work-app"], 4);
+        assert_eq!(summary.risky.len(), 1);
+        assert_eq!(summary.risky[0].pattern, RM_RF);
+        assert_eq!(summary.risky[0].command, "rm -rf dist");
+        assert_eq!(summary.risky[0].last_seen, "2026-02-06");
+    }
+}
@@ -6,7 +6,18 @@ use super::tool_types::{ToolCall, ToolCallIdentity, ToolStats, ToolSummary};
 
 /// Aggregate tool calls into a sorted summary
 pub(crate) fn aggregate_tools(calls: &[ToolCall]) -> ToolSummary {
-    let mut counts: HashMap<String, u64> = HashMap::with_capacity(calls.len());
+    aggregate_tools_by(calls, |call| (None, call.name.clone()))
+}
+
+/// Aggregate tool calls into rows keyed by `row_of`: the group a call belongs
+/// to (project, date, server, ...) and the tool name shown for it. Rows are
+/// ordered by group, then by call count descending.
+pub(crate) fn aggregate_tools_by(
+    calls: &[ToolCall],
+    row_of: impl Fn(&ToolCall) -> (Option<String>, String),
+) -> ToolSummary {
+    let mut rows: HashMap<(Option<String>, String), ToolStats> =
+        HashMap::with_capacity(calls.len());
     let mut seen_identities: HashSet<ToolCallIdentity> = HashSet::new();
     let mut total = 0;
 
@@ -16,17 +27,33 @@ pub(crate) fn aggregate_tools(calls: &[ToolCall]) -> ToolSummary {
         {
             continue;
         }
-        *counts.entry(call.name.clone()).or_default() += 1;
+        let (group, name) = row_of(call);
+        let row = rows
+            .entry((group.clone(), name.clone()))
+            .or_insert_with(|| ToolStats {
+                name,
+                group,
+                ..ToolStats::default()
+            });
+        row.calls += 1;
+        *row.daily.entry(call.date_str.clone()).or_default() += 1;
+        if let Some(bytes) = call.result_bytes {
+            row.results += 1;
+            row.result_bytes += bytes;
+        }
+        for (model, stats) in &call.attributed {
+            row.models.entry(model.clone()).or_default().add(stats);
+        }
         total += 1;
     }
 
-    let mut tools: Vec<ToolStats> = counts
-        .into_iter()
-        .map(|(name, calls)| ToolStats { name, calls })
-        .collect();
-
-    // Sort by call count descending
-    tools.sort_by_key(|tool| std::cmp::Reverse(tool.calls));
+    let mut tools: Vec<ToolStats> = rows.into_values().collect();
+    tools.sort_by(|a, b| {
+        a.group
+            .cmp(&b.group)
+            .then_with(|| b.calls.cmp(&a.calls))
+            .then_with(|| a.name.cmp(&b.name))
+    });
 
     ToolSummary { tools, total }
 }
@@ -34,12 +61,15 @@ pub(crate) fn aggregate_tools(calls: &[ToolCall]) -> ToolSummary {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::Stats;
+    use crate::core::tool_types::server_and_tool;
 
     fn make_call(name: &str) -> ToolCall {
         ToolCall {
             name: name.to_string(),
             date_str: "2025-01-01".to_string(),
             identity: None,
+            ..Default::default()
         }
     }
 
@@ -48,9 +78,25 @@ mod tests {
             name: name.to_string(),
             date_str: "2025-01-01".to_string(),
             identity: Some(ToolCallIdentity::new("session-a", message_id, tool_use_id)),
+            ..Default::default()
         }
     }
 
+    fn make_attributed_call(name: &str, date: &str, result_bytes: u64, input: i64) -> ToolCall {
+        let mut call = make_call(name);
+        call.date_str = date.to_string();
+        call.result_bytes = Some(result_bytes);
+        call.attributed.insert(
+            "sonnet-4".to_string(),
+            Stats {
+                input_tokens: input,
+                output_tokens: 10,
+                ..Default::default()
+            },
+        );
+        call
+    }
+
     #[test]
     fn aggregate_empty() {
         let summary = aggregate_tools(&[]);
@@ -112,4 +158,100 @@ mod tests {
                 .any(|tool| tool.name == "Bash" && tool.calls == 1)
         );
     }
+
+    #[test]
+    fn aggregate_sums_result_sizes_and_attributed_tokens() {
+        let mut calls = vec![
+            make_attributed_call("Read", "2025-01-01", 4_000, 1_000),
+            make_attributed_call("Read", "2025-01-01", 2_000, 500),
+        ];
+        calls.push(make_call("Read"));
+        let summary = aggregate_tools(&calls);
+
+        let read = &summary.tools[0];
+        assert_eq!(read.calls, 3);
+        assert_eq!(read.results, 2);
+        assert_eq!(read.avg_result_bytes(), Some(3_000));
+        assert_eq!(read.input_tokens(), 1_500);
+        assert_eq!(read.output_tokens(), 20);
+    }
+
+    #[test]
+    fn aggregate_by_group_keeps_one_row_per_group_and_tool() {
+        let calls = vec![
+            make_attributed_call("Read", "2025-01-02", 10, 1),
+            make_attributed_call("Bash", "2025-01-01", 10, 1),
+            make_attributed_call("Read", "2025-01-01", 10, 1),
+            make_attributed_call("Read", "2025-01-01", 10, 1),
+        ];
+        let summary = aggregate_tools_by(&calls, |call| {
+            (Some(call.date_str.clone()), call.name.clone())
+        });
+
+        let rows: Vec<_> = summary
+            .tools
+            .iter()
+            .map(|tool| {
+                (
+                    tool.group.as_deref().unwrap(),
+                    tool.name.as_str(),
+                    tool.calls,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("2025-01-01", "Read", 2),
+                ("2025-01-01", "Bash", 1),
+                ("2025-01-02", "Read", 1)
+            ]
+        );
+        assert_eq!(summary.total, 4);
+    }
+
+    #[test]
+    fn aggregate_by_server_rolls_up_mcp_tools_and_subagents() {
+        let mut task = make_call("Task");
+        task.subagent_type = Some("code-reviewer".to_string());
+        let mut later = make_call("mcp__github__create_issue");
+        later.date_str = "2025-01-03".to_string();
+        let calls = vec![
+            make_call("mcp__github__create_issue"),
+            later,
+            make_call("mcp__github__list_prs"),
+            make_call("mcp__linear__search"),
+            make_call("Read"),
+            task,
+        ];
+        let summary = aggregate_tools_by(&calls, |call| {
+            let (server, tool) = server_and_tool(call);
+            (Some(server), tool)
+        });
+
+        let rows: Vec<_> = summary
+            .tools
+            .iter()
+            .map(|tool| {
+                (
+                    tool.group.as_deref().unwrap(),
+                    tool.name.as_str(),
+                    tool.calls,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("built-in", "Read", 1),
+                ("github", "create_issue", 2),
+                ("github", "list_prs", 1),
+                ("linear", "search", 1),
+                ("subagents", "code-reviewer", 1)
+            ]
+        );
+        let create_issue = &summary.tools[1];
+        assert_eq!(create_issue.daily.len(), 2);
+        assert_eq!(create_issue.last_used(), Some("2025-01-03"));
+    }
 }
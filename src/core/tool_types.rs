@@ -1,7 +1,11 @@
 //! Types for tool usage analysis
 
+use std::collections::{BTreeMap, HashMap};
+
 use serde::Serialize;
 
+use super::types::Stats;
+
 /// Stable identity for a tool call in Claude JSONL logs.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct ToolCallIdentity {
@@ -21,18 +25,138 @@ impl ToolCallIdentity {
 }
 
 /// A single tool call extracted from JSONL
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub(crate) struct ToolCall {
+    /// Name of the source the call was logged by.
+    pub(crate) source: &'static str,
     pub(crate) name: String,
     pub(crate) date_str: String,
     pub(crate) identity: Option<ToolCallIdentity>,
+    pub(crate) project_path: String,
+    /// Session the call was made in.
+    pub(crate) session_key: String,
+    /// Files the call read or wrote, relative to the session's working
+    /// directory when inside it.
+    pub(crate) file_paths: Vec<String>,
+    /// Command line run by a shell tool call.
+    pub(crate) command: Option<String>,
+    /// `subagent_type` of a `Task` launch.
+    pub(crate) subagent_type: Option<String>,
+    /// Size of the `tool_result` content, when the result was logged.
+    pub(crate) result_bytes: Option<u64>,
+    /// Tokens attributed to this call, keyed by model: its share of the
+    /// emitting message's output and the input its result added to the next
+    /// request.
+    pub(crate) attributed: HashMap<String, Stats>,
 }
 
 /// Aggregated statistics for a single tool
 #[derive(Debug, Clone, Default, Serialize)]
 pub(crate) struct ToolStats {
     pub(crate) name: String,
+    /// Project or date the row is limited to when tools are grouped.
+    pub(crate) group: Option<String>,
     pub(crate) calls: u64,
+    /// Calls whose result was logged; the denominator for average result size.
+    pub(crate) results: u64,
+    pub(crate) result_bytes: u64,
+    pub(crate) models: HashMap<String, Stats>,
+    /// Calls per date (`YYYY-MM-DD`).
+    pub(crate) daily: BTreeMap<String, u64>,
+}
+
+impl ToolStats {
+    /// Prompt tokens the tool's results added, whether billed as fresh
+    /// input, cache writes or cache reads.
+    pub(crate) fn input_tokens(&self) -> i64 {
+        self.models
+            .values()
+            .map(|stats| stats.input_tokens + stats.cache_creation + stats.cache_read)
+            .sum()
+    }
+
+    pub(crate) fn output_tokens(&self) -> i64 {
+        self.models.values().map(|stats| stats.output_tokens).sum()
+    }
+
+    pub(crate) fn avg_result_bytes(&self) -> Option<u64> {
+        (self.results > 0).then(|| self.result_bytes / self.results)
+    }
+
+    pub(crate) fn last_used(&self) -> Option<&str> {
+        self.daily.keys().next_back().map(String::as_str)
+    }
+}
+
+/// Tool that launches a subagent in Claude Code.
+pub(crate) const SUBAGENT_TOOL: &str = "Task";
+
+/// Where a tool comes from, parsed from Claude Code's tool naming.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ToolOrigin<'a> {
+    BuiltIn,
+    /// `mcp__<server>__<tool>`
+    Mcp {
+        server: &'a str,
+        tool: &'a str,
+    },
+    Subagent,
+}
+
+impl<'a> ToolOrigin<'a> {
+    pub(crate) fn parse(name: &'a str) -> Self {
+        if name == SUBAGENT_TOOL {
+            return Self::Subagent;
+        }
+        match name.strip_prefix("mcp__") {
+            Some(rest) => {
+                let (server, tool) = rest.split_once("__").unwrap_or((rest, ""));
+                Self::Mcp { server, tool }
+            }
+            None => Self::BuiltIn,
+        }
+    }
+}
+
+/// Kind of work a tool does, normalized across agents so Claude's `Bash`,
+/// Codex's `shell`, Kimi's `Shell` and Grok's `execute` all count as `shell`.
+pub(crate) fn tool_category(name: &str) -> &'static str {
+    match ToolOrigin::parse(name) {
+        ToolOrigin::Mcp { .. } => return "mcp",
+        ToolOrigin::Subagent => return "subagent",
+        ToolOrigin::BuiltIn => {}
+    }
+    match name.to_ascii_lowercase().as_str() {
+        "bash" | "bashoutput" | "killshell" | "shell" | "exec_command" | "write_stdin"
+        | "local_shell" | "execute" => "shell",
+        "edit" | "multiedit" | "write" | "notebookedit" | "apply_patch" | "writefile"
+        | "strreplacefile" | "delete" | "move" => "edit",
+        "read" | "readfile" | "view_image" => "read",
+        "grep" | "glob" | "ls" | "list_dir" | "search" => "search",
+        "webfetch" | "websearch" | "web_search" | "fetch" | "fetchurl" | "searchweb" => "web",
+        "agent" => "subagent",
+        "todowrite" | "update_plan" | "settodolist" | "think" | "exitplanmode" => "planning",
+        _ => "other",
+    }
+}
+
+/// Server column and tool name of a call in the per-server breakdown: MCP
+/// tools roll up under their server, built-in tools under `built-in`, and
+/// `Task` launches under `subagents`, named by subagent type.
+pub(crate) fn server_and_tool(call: &ToolCall) -> (String, String) {
+    match ToolOrigin::parse(&call.name) {
+        ToolOrigin::BuiltIn => ("built-in".to_string(), call.name.clone()),
+        ToolOrigin::Mcp { server, tool } => (
+            server.to_string(),
+            if tool.is_empty() { &call.name } else { tool }.to_string(),
+        ),
+        ToolOrigin::Subagent => (
+            "subagents".to_string(),
+            call.subagent_type
+                .clone()
+                .unwrap_or_else(|| SUBAGENT_TOOL.to_string()),
+        ),
+    }
 }
 
 /// Result of tool aggregation
@@ -41,3 +165,42 @@ pub(crate) struct ToolSummary {
     pub(crate) tools: Vec<ToolStats>,
     pub(crate) total: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_origin_parses_claude_code_tool_names() {
+        assert_eq!(ToolOrigin::parse("Read"), ToolOrigin::BuiltIn);
+        assert_eq!(ToolOrigin::parse("Task"), ToolOrigin::Subagent);
+        assert_eq!(
+            ToolOrigin::parse("mcp__claude_ai_Linear__list_issues"),
+            ToolOrigin::Mcp {
+                server: "claude_ai_Linear",
+                tool: "list_issues"
+            }
+        );
+        assert_eq!(
+            ToolOrigin::parse("mcp__ide"),
+            ToolOrigin::Mcp {
+                server: "ide",
+                tool: ""
+            }
+        );
+    }
+
+    #[test]
+    fn tool_category_normalizes_names_across_agents() {
+        for name in ["Bash", "shell", "exec_command", "Shell", "execute"] {
+            assert_eq!(tool_category(name), "shell", "{name}");
+        }
+        for name in ["Edit", "apply_patch", "StrReplaceFile", "edit"] {
+            assert_eq!(tool_category(name), "edit", "{name}");
+        }
+        assert_eq!(tool_category("Grep"), "search");
+        assert_eq!(tool_category("mcp__github__create_issue"), "mcp");
+        assert_eq!(tool_category("Task"), "subagent");
+        assert_eq!(tool_category("Frobnicate"), "other");
+    }
+}
@@ -3,7 +3,14 @@
 //! These types represent the unified data model that all sources convert to.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use super::ContextSample;
+
+/// Prompt size (input plus cache writes and reads) above which the built-in
+/// Claude Sonnet 4 and Grok rates switch to their long-context tier.
+pub(crate) const LONG_CONTEXT_THRESHOLD: i64 = 200_000;
 
 /// Token usage statistics
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -32,6 +39,20 @@ pub(crate) struct Stats {
     /// Tokens that still need local pricing (records without a provider cost).
     #[serde(default)]
     pub(crate) priced_tokens: CostTokens,
+    /// The token totals split by the prompt size of their request, for the
+    /// requests that may reach a long-context tier.
+    #[serde(default)]
+    pub(crate) by_prompt_size: PromptSizeTokens,
+    /// Portion of `by_prompt_size` from `estimated_proxy` records.
+    #[serde(default)]
+    pub(crate) estimated_proxy_by_prompt_size: PromptSizeTokens,
+    /// Portion of `by_prompt_size` from records with a provider-reported cost,
+    /// which are left out of `priced_tokens`.
+    #[serde(default)]
+    pub(crate) recorded_by_prompt_size: PromptSizeTokens,
+    /// Portion of `priced_tokens` billed under a non-standard service tier.
+    #[serde(default)]
+    pub(crate) service_tiers: ServiceTierTokens,
 }
 
 impl Stats {
@@ -53,6 +74,12 @@ impl Stats {
             .recorded_cost_entries
             .saturating_add(other.recorded_cost_entries);
         self.priced_tokens.add(&other.priced_tokens);
+        self.by_prompt_size.add(&other.by_prompt_size);
+        self.estimated_proxy_by_prompt_size
+            .add(&other.estimated_proxy_by_prompt_size);
+        self.recorded_by_prompt_size
+            .add(&other.recorded_by_prompt_size);
+        self.service_tiers.add(&other.service_tiers);
     }
 
     /// Total tokens for display purposes
@@ -165,6 +192,121 @@ impl Endpoint {
     pub(crate) const ORDER: [Endpoint; 3] = [Endpoint::Native, Endpoint::Proxy, Endpoint::Unknown];
 }
 
+/// Provider service tier a request was billed under.
+///
+/// Read from Claude `usage.service_tier` and Codex `turn_context.service_tier`.
+/// Records without a tier, and tiers billed at list price (`default`,
+/// `auto`, `scale`), are `Standard`.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ServiceTier {
+    #[default]
+    Standard,
+    Batch,
+    Flex,
+    Priority,
+}
+
+impl ServiceTier {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ServiceTier::Standard => "standard",
+            ServiceTier::Batch => "batch",
+            ServiceTier::Flex => "flex",
+            ServiceTier::Priority => "priority",
+        }
+    }
+
+    /// Classify a provider's tier label.
+    pub(crate) fn from_label(label: Option<&str>) -> Self {
+        match label.map(str::to_ascii_lowercase).as_deref() {
+            Some("batch" | "batches") => ServiceTier::Batch,
+            Some("flex") => ServiceTier::Flex,
+            Some("priority") => ServiceTier::Priority,
+            _ => ServiceTier::Standard,
+        }
+    }
+
+    /// Tiers priced differently from the list price, in display order.
+    pub(crate) const NON_STANDARD: [ServiceTier; 3] =
+        [ServiceTier::Batch, ServiceTier::Flex, ServiceTier::Priority];
+}
+
+/// Locally priced tokens per non-standard service tier.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ServiceTierTokens {
+    pub(crate) batch: CostTokens,
+    pub(crate) flex: CostTokens,
+    pub(crate) priority: CostTokens,
+    /// Prompt-size split of each tier's tokens, in
+    /// [`ServiceTier::NON_STANDARD`] order.
+    #[serde(default)]
+    by_prompt_size: [PromptSizeTokens; 3],
+}
+
+impl ServiceTierTokens {
+    pub(crate) fn add(&mut self, other: &Self) {
+        self.batch.add(&other.batch);
+        self.flex.add(&other.flex);
+        self.priority.add(&other.priority);
+        for (sizes, other) in self.by_prompt_size.iter_mut().zip(&other.by_prompt_size) {
+            sizes.add(other);
+        }
+    }
+
+    /// Tokens billed under `tier`; standard tokens are not tracked here.
+    pub(crate) fn get(&self, tier: ServiceTier) -> CostTokens {
+        match tier {
+            ServiceTier::Standard => CostTokens::default(),
+            ServiceTier::Batch => self.batch,
+            ServiceTier::Flex => self.flex,
+            ServiceTier::Priority => self.priority,
+        }
+    }
+
+    /// Prompt-size split of the tokens billed under `tier`.
+    pub(crate) fn prompt_sizes(&self, tier: ServiceTier) -> &PromptSizeTokens {
+        match Self::index(tier) {
+            Some(index) => &self.by_prompt_size[index],
+            None => &EMPTY_PROMPT_SIZES,
+        }
+    }
+
+    /// Record one request's tokens and prompt-size split under `tier`.
+    fn record(&mut self, tier: ServiceTier, tokens: CostTokens, sizes: &PromptSizeTokens) {
+        let Some(index) = Self::index(tier) else {
+            return;
+        };
+        self.by_prompt_size[index].clone_from(sizes);
+        if let Some(tier_tokens) = self.get_mut(tier) {
+            *tier_tokens = tokens;
+        }
+    }
+
+    fn get_mut(&mut self, tier: ServiceTier) -> Option<&mut CostTokens> {
+        match tier {
+            ServiceTier::Standard => None,
+            ServiceTier::Batch => Some(&mut self.batch),
+            ServiceTier::Flex => Some(&mut self.flex),
+            ServiceTier::Priority => Some(&mut self.priority),
+        }
+    }
+
+    fn index(tier: ServiceTier) -> Option<usize> {
+        ServiceTier::NON_STANDARD.iter().position(|t| *t == tier)
+    }
+
+    /// Non-standard tiers with usage, in display order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (ServiceTier, CostTokens)> + '_ {
+        ServiceTier::NON_STANDARD
+            .into_iter()
+            .map(|tier| (tier, self.get(tier)))
+            .filter(|(_, tokens)| tokens.has_entries())
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct CostTokens {
     pub(crate) input_tokens: i64,
@@ -191,7 +333,7 @@ impl CostTokens {
         self.count = self.count.saturating_add(other.count);
     }
 
-    pub(crate) fn saturating_sub(self, other: &Self) -> Self {
+    pub(crate) fn saturating_sub(&self, other: &Self) -> Self {
         Self {
             input_tokens: self.input_tokens.saturating_sub(other.input_tokens).max(0),
             output_tokens: self
@@ -215,7 +357,7 @@ impl CostTokens {
         }
     }
 
-    pub(crate) fn has_entries(self) -> bool {
+    pub(crate) fn has_entries(&self) -> bool {
         self.count > 0
             || self.input_tokens > 0
             || self.output_tokens > 0
@@ -225,6 +367,117 @@ impl CostTokens {
     }
 }
 
+/// Smallest long-context threshold of the loaded price list. Requests at or
+/// below it are billed at base rates under every model, so their prompt size
+/// is not kept. Zero, keeping every request, until a price list sets it.
+static PROMPT_SIZE_FLOOR: AtomicI64 = AtomicI64::new(0);
+
+static EMPTY_PROMPT_SIZES: PromptSizeTokens = PromptSizeTokens(BTreeMap::new());
+
+/// Only keep the prompt size of requests above `threshold` tokens, the
+/// smallest long-context threshold of the loaded prices. Smaller requests
+/// are billed at base rates by every model, so their size is never needed.
+pub(crate) fn set_prompt_size_floor(threshold: i64) {
+    PROMPT_SIZE_FLOOR.store(threshold, Ordering::Relaxed);
+}
+
+/// Tokens keyed by the prompt size of the request they came from, in
+/// thousands of tokens rounded up. Long-context tiers apply per request and
+/// their threshold depends on the model, so the prompt size is kept alongside
+/// the totals and compared with the model's threshold when pricing.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct PromptSizeTokens(BTreeMap<i64, RequestTokens>);
+
+impl PromptSizeTokens {
+    /// Tokens of one request with a prompt of `prompt_tokens`; empty unless
+    /// the prompt may reach a long-context tier.
+    pub(crate) fn request(prompt_tokens: i64, tokens: RequestTokens) -> Self {
+        let mut sizes = BTreeMap::new();
+        if prompt_tokens > PROMPT_SIZE_FLOOR.load(Ordering::Relaxed).max(0) {
+            sizes.insert(prompt_tokens.saturating_add(999) / 1000, tokens);
+        }
+        Self(sizes)
+    }
+
+    pub(crate) fn add(&mut self, other: &Self) {
+        for (size, tokens) in &other.0 {
+            self.0.entry(*size).or_default().add(tokens);
+        }
+    }
+
+    /// Tokens of requests whose prompt exceeded `threshold` tokens. Exact for
+    /// thresholds in whole thousands, which is how providers publish them.
+    pub(crate) fn above(&self, threshold: i64) -> RequestTokens {
+        let mut total = RequestTokens::default();
+        for tokens in self.0.range(threshold.max(0) / 1000 + 1..).map(|(_, t)| t) {
+            total.add(tokens);
+        }
+        total
+    }
+}
+
+impl From<RequestTokens> for CostTokens {
+    /// Standalone tokens, for pricing one portion of the requests.
+    fn from(tokens: RequestTokens) -> Self {
+        Self {
+            input_tokens: tokens.input_tokens,
+            output_tokens: tokens.output_tokens,
+            cache_creation: tokens.cache_creation,
+            cache_creation_1h: tokens.cache_creation_1h,
+            cache_read: tokens.cache_read,
+            reasoning_tokens: tokens.reasoning_tokens,
+            count: 0,
+        }
+    }
+}
+
+/// Token counts of the requests in one [`PromptSizeTokens`] bucket.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RequestTokens {
+    pub(crate) input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) cache_creation: i64,
+    pub(crate) cache_creation_1h: i64,
+    pub(crate) cache_read: i64,
+    pub(crate) reasoning_tokens: i64,
+}
+
+impl RequestTokens {
+    pub(crate) fn add(&mut self, other: &Self) {
+        self.input_tokens = self.input_tokens.saturating_add(other.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(other.output_tokens);
+        self.cache_creation = self.cache_creation.saturating_add(other.cache_creation);
+        self.cache_creation_1h = self
+            .cache_creation_1h
+            .saturating_add(other.cache_creation_1h);
+        self.cache_read = self.cache_read.saturating_add(other.cache_read);
+        self.reasoning_tokens = self.reasoning_tokens.saturating_add(other.reasoning_tokens);
+    }
+
+    pub(crate) fn saturating_sub(self, other: &Self) -> Self {
+        Self {
+            input_tokens: self.input_tokens.saturating_sub(other.input_tokens).max(0),
+            output_tokens: self
+                .output_tokens
+                .saturating_sub(other.output_tokens)
+                .max(0),
+            cache_creation: self
+                .cache_creation
+                .saturating_sub(other.cache_creation)
+                .max(0),
+            cache_creation_1h: self
+                .cache_creation_1h
+                .saturating_sub(other.cache_creation_1h)
+                .max(0),
+            cache_read: self.cache_read.saturating_sub(other.cache_read).max(0),
+            reasoning_tokens: self
+                .reasoning_tokens
+                .saturating_sub(other.reasoning_tokens)
+                .max(0),
+        }
+    }
+}
+
 /// Day-level aggregated statistics
 #[derive(Debug, Default, Clone)]
 pub(crate) struct DayStats {
@@ -239,6 +492,11 @@ impl DayStats {
     }
 }
 
+/// Per-model stats of each local day (`YYYY-MM-DD`) a row spans, so its cost
+/// can be converted at each day's exchange rate. Left empty unless the
+/// currency converter carries daily rates.
+pub(crate) type DailyModels = HashMap<String, HashMap<String, Stats>>;
+
 /// Session statistics
 #[derive(Debug, Default, Clone)]
 pub(crate) struct SessionStats {
@@ -249,6 +507,9 @@ pub(crate) struct SessionStats {
     pub(crate) last_timestamp: String,
     pub(crate) stats: Stats,
     pub(crate) models: HashMap<String, Stats>,
+    pub(crate) days: DailyModels,
+    /// Context size of each main-thread request.
+    pub(crate) context_samples: Vec<ContextSample>,
 }
 
 /// Project statistics
@@ -259,6 +520,7 @@ pub(crate) struct ProjectStats {
     pub(crate) session_count: usize,
     pub(crate) stats: Stats,
     pub(crate) models: HashMap<String, Stats>,
+    pub(crate) days: DailyModels,
 }
 
 /// 5-hour billing block statistics
@@ -268,6 +530,7 @@ pub(crate) struct BlockStats {
     pub(crate) block_end: String,
     pub(crate) stats: Stats,
     pub(crate) models: HashMap<String, Stats>,
+    pub(crate) days: DailyModels,
 }
 
 /// Per-endpoint (native vs proxy) statistics
@@ -321,6 +584,20 @@ pub(crate) struct RawEntry {
     /// Provider-reported USD cost for this record, when the source logs one.
     #[serde(default)]
     pub(crate) recorded_cost_usd: Option<f64>,
+    /// Whether the request was made by a subagent (Claude sidechain).
+    #[serde(default)]
+    pub(crate) is_sidechain: bool,
+    /// Service tier the request was billed under.
+    #[serde(default)]
+    pub(crate) service_tier: ServiceTier,
+    /// Subagent type (e.g. `Explore`) of a sidechain request, when the
+    /// launching `Task` call is known.
+    #[serde(default)]
+    pub(crate) subagent_type: Option<String>,
+    /// Session that launched this subagent, for subagent transcripts kept in
+    /// their own file (whose `session_id` is the agent file's).
+    #[serde(default)]
+    pub(crate) parent_session_id: Option<String>,
 }
 
 fn default_call_count() -> i64 {
@@ -328,6 +605,13 @@ fn default_call_count() -> i64 {
 }
 
 impl RawEntry {
+    /// Prompt size of the request: fresh input plus cache writes and reads.
+    pub(crate) fn prompt_tokens(&self) -> i64 {
+        self.input_tokens
+            .saturating_add(self.cache_creation)
+            .saturating_add(self.cache_read)
+    }
+
     pub(crate) fn to_stats(&self) -> Stats {
         // Parsers default real records to one call; a synthetic residual may
         // legitimately carry tokens or cost without representing another call.
@@ -346,15 +630,40 @@ impl RawEntry {
             recorded_cost_usd: 0.0,
             recorded_cost_entries: 0,
             priced_tokens: CostTokens::default(),
+            by_prompt_size: PromptSizeTokens::request(
+                self.prompt_tokens(),
+                RequestTokens {
+                    input_tokens: self.input_tokens,
+                    output_tokens: self.output_tokens,
+                    cache_creation: self.cache_creation,
+                    cache_creation_1h: self.cache_creation_1h,
+                    cache_read: self.cache_read,
+                    reasoning_tokens: self.reasoning_tokens,
+                },
+            ),
+            estimated_proxy_by_prompt_size: PromptSizeTokens::default(),
+            recorded_by_prompt_size: PromptSizeTokens::default(),
+            service_tiers: ServiceTierTokens::default(),
         };
         if self.cost_kind == CostKind::EstimatedProxy {
             stats.estimated_proxy = stats.cost_tokens();
             stats.priced_tokens = stats.cost_tokens();
+            stats
+                .estimated_proxy_by_prompt_size
+                .clone_from(&stats.by_prompt_size);
         } else if let Some(recorded) = self.recorded_cost_usd {
             stats.recorded_cost_usd = recorded.max(0.0);
             stats.recorded_cost_entries = 1;
+            stats
+                .recorded_by_prompt_size
+                .clone_from(&stats.by_prompt_size);
         } else {
             stats.priced_tokens = stats.cost_tokens();
+            stats.service_tiers.record(
+                self.service_tier,
+                stats.priced_tokens,
+                &stats.by_prompt_size,
+            );
         }
         stats
     }
@@ -625,6 +934,10 @@ mod tests {
             endpoint: Endpoint::Unknown,
             call_count: 1,
             recorded_cost_usd: None,
+            is_sidechain: false,
+            service_tier: ServiceTier::Standard,
+            subagent_type: None,
+            parent_session_id: None,
         };
         let s = entry.to_stats();
         assert_eq!(s.input_tokens, 100);
@@ -661,6 +974,10 @@ mod tests {
             endpoint: Endpoint::Unknown,
             call_count: 5,
             recorded_cost_usd: Some(1.25),
+            is_sidechain: false,
+            service_tier: ServiceTier::Standard,
+            subagent_type: None,
+            parent_session_id: None,
         };
         let stats = entry.to_stats();
         assert_eq!(stats.count, 5);
@@ -670,6 +987,58 @@ mod tests {
         assert!(!stats.priced_tokens.has_entries());
     }
 
+    #[test]
+    fn raw_entry_to_stats_marks_long_context_requests() {
+        let mut entry = RawEntry {
+            timestamp: String::new(),
+            timestamp_ms: 0,
+            date_str: String::new(),
+            message_id: None,
+            session_key: String::new(),
+            session_id: String::new(),
+            project_path: String::new(),
+            model: String::new(),
+            input_tokens: 1_000,
+            output_tokens: 500,
+            cache_creation: 9_000,
+            cache_creation_1h: 0,
+            cache_read: 190_000,
+            reasoning_tokens: 0,
+            stop_reason: None,
+            cost_kind: CostKind::Real,
+            endpoint: Endpoint::Unknown,
+            call_count: 1,
+            recorded_cost_usd: None,
+            is_sidechain: false,
+            service_tier: ServiceTier::Standard,
+            subagent_type: None,
+            parent_session_id: None,
+        };
+        // Exactly at the threshold stays in the base tier.
+        let at_threshold = entry.to_stats();
+        assert_eq!(
+            at_threshold.by_prompt_size.above(200_000),
+            RequestTokens::default()
+        );
+        assert_eq!(
+            at_threshold.by_prompt_size.above(128_000).cache_read,
+            190_000
+        );
+
+        entry.input_tokens = 1_001;
+        let stats = entry.to_stats();
+        let above = stats.by_prompt_size.above(200_000);
+        assert_eq!(above.cache_read, 190_000);
+        assert_eq!(above.output_tokens, 500);
+        assert_eq!(
+            stats.by_prompt_size.above(272_000),
+            RequestTokens::default()
+        );
+        let mut total = stats.clone();
+        total.add(&stats);
+        assert_eq!(total.by_prompt_size.above(200_000).input_tokens, 2_002);
+    }
+
     // --- DateFilter ---
 
     fn d(y: i32, m: u32, day: u32) -> NaiveDate {
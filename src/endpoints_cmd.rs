@@ -2,9 +2,9 @@
 //!
 //! Lives in its own module to keep `app.rs` under the module size limit.
 
-use crate::app::{CommandContext, print_json, print_no_data_hint};
+use crate::app::{CommandContext, print_json_rows, print_no_data_hint};
 use crate::output::{
-    EndpointTableOptions, OutputFormat, output_endpoint_json, print_endpoint_table,
+    DataKind, EndpointTableOptions, OutputFormat, endpoint_json_rows, print_endpoint_table,
 };
 use crate::source::{Source, load_endpoints};
 
@@ -19,7 +19,7 @@ pub(crate) fn handle_endpoints(source: &dyn Source, ctx: &CommandContext<'_>) {
 
     let endpoints = load_endpoints(source, ctx.filter, ctx.timezone);
     if endpoints.is_empty() {
-        print_no_data_hint(source.display_name(), "endpoint");
+        print_no_data_hint(ctx.locale, source.display_name(), DataKind::Endpoint);
         return;
     }
 
@@ -28,23 +28,24 @@ pub(crate) fn handle_endpoints(source: &dyn Source, ctx: &CommandContext<'_>) {
             eprintln!("CSV output is not available for the endpoints view; use --json.");
         }
         OutputFormat::Json => {
-            let json = output_endpoint_json(
+            let rows = endpoint_json_rows(
                 &endpoints,
                 ctx.pricing_db,
                 ctx.cli.show_cost(),
                 ctx.currency,
             );
-            print_json(&json, ctx.jq_filter);
+            print_json_rows(rows, true, ctx);
         }
-        OutputFormat::Table => print_endpoint_table(
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => print_endpoint_table(
             &endpoints,
             ctx.pricing_db,
             EndpointTableOptions {
                 use_color: ctx.cli.use_color(),
                 show_cost: ctx.cli.show_cost(),
                 source_label: source.display_name(),
-                number_format: ctx.number_format,
+                locale: ctx.locale,
                 currency: ctx.currency,
+                markup: ctx.cli.output_format().markup(),
             },
         ),
     }
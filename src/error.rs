@@ -19,7 +19,7 @@ pub(crate) enum AppError {
 
 #[derive(Debug, Error)]
 pub(crate) enum JqError {
-    #[error("jq not found. Please install jq to use --jq option.")]
+    #[error("jq not found. Please install jq to use --jq-fallback.")]
     NotFound,
 
     #[error("Failed to run jq: {0}")]
@@ -36,6 +36,11 @@ pub(crate) enum JqError {
 
     #[error("jq error: {0}")]
     Filter(String),
+
+    #[error(
+        "jq error: {0} (not supported by the built-in engine; use --jq-fallback to run it with jq)"
+    )]
+    Unsupported(String),
 }
 
 #[cfg(test)]
@@ -73,7 +78,7 @@ mod tests {
     fn jq_error_not_found() {
         assert_eq!(
             JqError::NotFound.to_string(),
-            "jq not found. Please install jq to use --jq option."
+            "jq not found. Please install jq to use --jq-fallback."
         );
     }
 
@@ -3,7 +3,7 @@
 //!
 //! The public SDK entry points are [`summarize_cost`] and
 //! [`summarize_cost_ranges`] for cost analytics, [`load_codex_weekly_quota`]
-//! for provider-authoritative Codex quota pace,
+//! and [`load_codex_quota_windows`] for provider-authoritative Codex quota pace,
 //! [`estimate_codex_weekly_value`] for an API-equivalent weekly estimate, plus
 //! [`summarize_cost_with_cli_config`] and
 //! [`summarize_cost_ranges_with_cli_config`] for CLI-aligned config defaults.
@@ -18,26 +18,35 @@
     clippy::cast_sign_loss
 )]
 
+mod agents_cmd;
 mod app;
+mod audit_cmd;
+mod claude_hook_cmd;
 mod cli;
 mod config;
 mod consts;
 mod core;
 mod endpoints_cmd;
 mod error;
+mod notify_cmd;
 mod output;
+mod period_cmd;
 mod pricing;
+mod pricing_cmd;
 mod quota_cmd;
+mod report_cmd;
+mod schema_cmd;
 mod sdk;
+mod session_cmd;
 mod source;
 mod sources_cmd;
 mod utils;
 
 pub use sdk::{
-    CodexQuotaError, CodexQuotaStatus, CodexWeeklyQuota, CodexWeeklyValueError,
+    CodexQuotaError, CodexQuotaStatus, CodexQuotaWindow, CodexWeeklyQuota, CodexWeeklyValueError,
     CodexWeeklyValueEstimate, CostSummary, ModelCostSummary, MultiCostSummary, MultiSummaryOptions,
     SdkError, SummaryOptions, TokenBreakdown, UsageRange, UsageSource, estimate_codex_weekly_value,
-    load_codex_weekly_quota, summarize_cost, summarize_cost_ranges,
+    load_codex_quota_windows, load_codex_weekly_quota, summarize_cost, summarize_cost_ranges,
     summarize_cost_ranges_with_cli_config, summarize_cost_with_cli_config,
 };
 
@@ -45,11 +54,11 @@ use chrono::{NaiveDate, Utc};
 use clap::Parser;
 
 use app::{CommandContext, handle_all_sources_command, handle_source_command};
-use cli::{Cli, SourceCommand, parse_command};
+use cli::{Cli, MARKUP_COMMANDS, SourceCommand, parse_command};
 use config::Config;
 use core::DateFilter;
-use output::NumberFormat;
-use pricing::{CurrencyConverter, PricingDb};
+use output::{Locale, OutputFormat, print_html_page_end, print_html_page_start};
+use pricing::{CurrencyConverter, CurrencyError, PricingDb};
 use source::{ALL_SOURCES, CodexScope, CodexSource, get_source, source_choices, suggest_source};
 use utils::{Timezone, parse_date};
 
@@ -96,12 +105,12 @@ fn resolve_timezone(timezone: Option<&str>, cli_timezone_was_set: bool) -> Timez
     }
 }
 
-fn resolve_number_format(locale: Option<&str>) -> NumberFormat {
-    match NumberFormat::from_locale(locale) {
+fn resolve_locale(locale: Option<&str>) -> Locale {
+    match Locale::parse(locale) {
         Ok(format) => format,
         Err(err) => {
             eprintln!("Warning: {err}. Using default locale.");
-            NumberFormat::default()
+            Locale::default()
         }
     }
 }
@@ -133,8 +142,11 @@ fn validate_monthly_budget(cli: &Cli, source_cmd: SourceCommand) {
         eprintln!("Error: --monthly-budget must be a positive number");
         std::process::exit(1);
     }
-    if source_cmd != SourceCommand::Monthly {
-        eprintln!("Error: --monthly-budget only supports the monthly command");
+    if !matches!(
+        source_cmd,
+        SourceCommand::Monthly | SourceCommand::Notify | SourceCommand::Report
+    ) {
+        eprintln!("Error: --monthly-budget only supports the monthly, notify and report commands");
         std::process::exit(1);
     }
     if !cli.show_cost() {
@@ -143,6 +155,72 @@ fn validate_monthly_budget(cli: &Cli, source_cmd: SourceCommand) {
     }
 }
 
+/// Markdown and HTML reuse the table renderers, so commands without one of
+/// the supported tables fail before any output is written.
+fn validate_output_format(cli: &Cli, source_cmd: SourceCommand) {
+    let name = match cli.output_format() {
+        OutputFormat::Markdown => "markdown",
+        OutputFormat::Html => "html",
+        OutputFormat::Table | OutputFormat::Json | OutputFormat::Csv => return,
+    };
+    if !source_cmd.supports_markup() {
+        let (last, rest) = MARKUP_COMMANDS
+            .split_last()
+            .expect("some commands support markup");
+        eprintln!(
+            "Error: --format {name} supports {}, and {last}; use --json or --csv for other commands",
+            rest.join(", ")
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Only the statusline renders `--template`, and a bad template fails before
+/// any logs are read. A template from the config file is ignored elsewhere.
+fn validate_statusline_template(cli: &Cli, source_cmd: SourceCommand, template_was_set: bool) {
+    let Some(template) = cli.template.as_deref() else {
+        return;
+    };
+    if !source_cmd.is_statusline() {
+        if template_was_set {
+            eprintln!("Error: --template only supports the statusline command");
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let Err(error) = output::validate_statusline_template(template) {
+        eprintln!("Error: {error}");
+        std::process::exit(1);
+    }
+}
+
+/// `--claude-hook` reads one Claude Code session, so it only makes sense for the
+/// Claude statusline and renders its own line instead of a template.
+fn validate_claude_hook(
+    cli: &Cli,
+    source_cmd: SourceCommand,
+    source_name: &str,
+    template_was_set: bool,
+) {
+    if !cli.claude_hook {
+        return;
+    }
+    if !source_cmd.is_statusline() {
+        eprintln!("Error: --claude-hook only supports the statusline command");
+        std::process::exit(1);
+    }
+    if template_was_set {
+        eprintln!("Error: --template cannot be combined with --claude-hook");
+        std::process::exit(1);
+    }
+    let is_claude = !source_name.eq_ignore_ascii_case(ALL_SOURCES)
+        && get_source(source_name).is_none_or(|source| source.name() == "claude");
+    if !is_claude {
+        eprintln!("Error: --claude-hook only supports the Claude source");
+        std::process::exit(1);
+    }
+}
+
 fn build_date_filter(
     source_cmd: SourceCommand,
     today: NaiveDate,
@@ -160,9 +238,9 @@ fn load_pricing_db(cli: &Cli, needs_pricing: bool, is_statusline: bool) -> Prici
     if !needs_pricing {
         PricingDb::default()
     } else if is_statusline {
-        PricingDb::load_quiet(cli.offline, cli.strict_pricing)
+        PricingDb::load_quiet(cli.offline, cli.strict_pricing, &cli.pricing_providers)
     } else {
-        PricingDb::load(cli.offline, cli.strict_pricing)
+        PricingDb::load(cli.offline, cli.strict_pricing, &cli.pricing_providers)
     }
 }
 
@@ -182,7 +260,10 @@ fn resolve_source_name<'a>(
     source_override: Option<&'a str>,
     source_cmd: SourceCommand,
 ) -> &'a str {
-    if source_cmd == SourceCommand::Sources {
+    if matches!(
+        source_cmd,
+        SourceCommand::Sources | SourceCommand::Schema { .. }
+    ) {
         return "claude";
     }
 
@@ -190,6 +271,8 @@ fn resolve_source_name<'a>(
         (Some(hint), Some(override_name)) => resolve_overridden_command_source(hint, override_name),
         (Some(hint), None) => hint,
         (None, Some(name)) => name,
+        // The dashboard is a whole-fleet overview unless narrowed down.
+        (None, None) if source_cmd == SourceCommand::Report => ALL_SOURCES,
         (None, None) => "claude",
     }
 }
@@ -217,25 +300,48 @@ fn resolve_overridden_command_source(hint: &'static str, override_name: &str) ->
 fn load_currency_converter(
     cli: &Cli,
     needs_pricing: bool,
-    is_statusline: bool,
+    source_cmd: SourceCommand,
 ) -> Option<CurrencyConverter> {
     if !needs_pricing {
         return None;
     }
 
     cli.currency.as_ref().map(|code| {
-        let Some(converter) = CurrencyConverter::load(code, cli.offline) else {
-            eprintln!(
-                "Error: failed to load exchange rate for '{code}'. Use a supported currency with cached rates, refresh rates without --offline, or omit --currency."
-            );
-            std::process::exit(1);
+        let converter = match CurrencyConverter::load_with(code, cli.offline, &cli.exchange_rates) {
+            Ok(converter) => converter,
+            Err(CurrencyError::Unavailable { .. }) => {
+                eprintln!(
+                    "Error: failed to load exchange rate for '{code}'. Use a supported currency with cached rates, refresh rates without --offline, or omit --currency."
+                );
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
         };
-        if !is_statusline && converter.currency_code() != "USD" {
-            eprintln!(
-                "Converting costs to {} (rate: displayed as {})",
-                converter.currency_code(),
-                converter.format(1.0)
-            );
+        if !source_cmd.is_statusline() && converter.currency_code() != "USD" {
+            if converter.is_historical() && source_cmd.uses_daily_rates() {
+                eprintln!(
+                    "Converting costs to {} at daily rates ({}, latest: {})",
+                    converter.currency_code(),
+                    converter.rate_source().as_str(),
+                    converter.format(1.0)
+                );
+            } else if converter.is_historical() {
+                eprintln!(
+                    "Converting costs to {} at the latest rate ({}: {})",
+                    converter.currency_code(),
+                    converter.rate_source().as_str(),
+                    converter.format(1.0)
+                );
+            } else {
+                eprintln!(
+                    "Converting costs to {} (rate: displayed as {})",
+                    converter.currency_code(),
+                    converter.format(1.0)
+                );
+            }
         }
         converter
     })
@@ -261,7 +367,7 @@ fn validate_codex_scope(scope: CodexScope, source_name: &str) {
 }
 
 fn validate_quota_currency(cli: &Cli, source_cmd: SourceCommand, currency_was_set: bool) {
-    if source_cmd == SourceCommand::Quota
+    if source_cmd == (SourceCommand::Quota { history: false })
         && cli.show_cost()
         && currency_was_set
         && cli
@@ -303,6 +409,7 @@ pub fn run_cli() {
     let raw_cli = Cli::parse();
     let cli_timezone_was_set = raw_cli.timezone.is_some();
     let cli_currency_was_set = raw_cli.currency.is_some();
+    let cli_template_was_set = raw_cli.template.is_some();
     let parsed_command = parse_command(raw_cli.command.as_ref());
     let source_cmd = parsed_command.command;
     let is_statusline = source_cmd.is_statusline();
@@ -311,19 +418,29 @@ pub fn run_cli() {
     let cli = raw_cli.with_config(&config);
     validate_quota_currency(&cli, source_cmd, cli_currency_was_set);
     let timezone = resolve_timezone(cli.timezone.as_deref(), cli_timezone_was_set);
-    let number_format = resolve_number_format(cli.locale.as_deref());
+    let locale = resolve_locale(cli.locale.as_deref());
 
     let jq_filter = cli.jq.as_deref();
     let since = parse_date_flag(cli.since.as_deref(), "--since");
     let until = parse_date_flag(cli.until.as_deref(), "--until");
     validate_date_range(since, until);
     validate_monthly_budget(&cli, source_cmd);
+    validate_output_format(&cli, source_cmd);
+    validate_statusline_template(&cli, source_cmd, cli_template_was_set);
 
     let today = timezone.to_fixed_offset(Utc::now()).date_naive();
     let budget_as_of = until.map_or(today, |end| end.min(today));
     let filter = build_date_filter(source_cmd, today, since, until);
     let show_cost = cli.show_cost();
-    let needs_pricing = is_statusline || show_cost;
+    let needs_pricing = is_statusline
+        || match source_cmd {
+            SourceCommand::Pricing { fetches } => !fetches,
+            SourceCommand::AuditPricing => true,
+            SourceCommand::Schema { .. } => false,
+            SourceCommand::Quota { history } => show_cost && !history,
+            SourceCommand::Notify => show_cost && cli.monthly_budget.is_some(),
+            _ => show_cost,
+        };
     let pricing_db = load_pricing_db(&cli, needs_pricing, is_statusline);
     let source_name = resolve_source_name(
         parsed_command.source_hint,
@@ -331,9 +448,18 @@ pub fn run_cli() {
         source_cmd,
     );
     validate_codex_scope(cli.codex_scope, source_name);
-    let needs_currency = source_cmd != SourceCommand::Quota && needs_pricing;
-    let currency_converter = load_currency_converter(&cli, needs_currency, is_statusline);
-
+    validate_claude_hook(&cli, source_cmd, source_name, cli_template_was_set);
+    let needs_currency = !source_cmd.is_quota()
+        && !matches!(source_cmd, SourceCommand::Pricing { .. })
+        && needs_pricing;
+    let currency_converter = load_currency_converter(&cli, needs_currency, source_cmd);
+
+    // One page for the whole run, even when `--source all` prints a table
+    // per source.
+    let html_page = cli.output_format() == OutputFormat::Html;
+    if html_page {
+        print_html_page_start();
+    }
     dispatch_command(
         source_name,
         source_cmd,
@@ -342,12 +468,15 @@ pub fn run_cli() {
             cli: &cli,
             pricing_db: &pricing_db,
             timezone,
-            number_format,
+            locale,
             jq_filter,
             currency: currency_converter.as_ref(),
             budget_as_of,
         },
     );
+    if html_page {
+        print_html_page_end();
+    }
 
     if cli.debug && needs_pricing {
         for diagnostic in pricing_db.pricing_diagnostics() {
@@ -0,0 +1,545 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{ErrorKind, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{CommandContext, print_json_report};
+use crate::cli::{NotifyArgs, SortOrder};
+use crate::core::{DateFilter, merge_day_stats};
+use crate::output::{
+    BudgetJson, NotifyBudgetJson, NotifyChangeJson, NotifyDetailsJson, NotifyPayloadJson,
+    OutputFormat, SCHEMA_VERSION, monthly_budget_reports, quota_window_json, quota_window_name,
+    report_to_string, timestamp,
+};
+use crate::pricing::{CostDisplayMode, get_cache_path, write_json_atomically};
+use crate::source::{
+    ALL_SOURCES, CodexQuotaError, CodexQuotaStatus, Source, all_sources, load_daily,
+    load_quota_windows,
+};
+
+const STATE_FILE: &str = "notify-state.json";
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Last status reported for one watched item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SeenStatus {
+    status: String,
+    /// Quota reset time or budget month the status belonged to.
+    period: String,
+    updated_at: String,
+}
+
+/// Persisted between runs so cron invocations only report transitions.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct NotifyState {
+    #[serde(default)]
+    statuses: BTreeMap<String, SeenStatus>,
+}
+
+impl NotifyState {
+    fn record(&mut self, observations: &[Observation], now: DateTime<Utc>) {
+        for observation in observations {
+            self.statuses.insert(
+                observation.key.clone(),
+                SeenStatus {
+                    status: observation.status.to_string(),
+                    period: observation.period.clone(),
+                    updated_at: timestamp(now),
+                },
+            );
+        }
+    }
+}
+
+/// Current status of one watched item, e.g. the weekly Codex quota window.
+#[derive(Debug)]
+struct Observation {
+    key: String,
+    kind: &'static str,
+    status: &'static str,
+    period: String,
+    /// Whether a first sighting of this status is worth reporting.
+    alerting: bool,
+    details: NotifyDetailsJson,
+}
+
+#[derive(Debug)]
+struct StatusChange<'a> {
+    observation: &'a Observation,
+    previous: Option<&'a SeenStatus>,
+}
+
+fn default_state_path() -> Option<PathBuf> {
+    get_cache_path(STATE_FILE)
+}
+
+fn quota_observations() -> Vec<Observation> {
+    let windows = match load_quota_windows() {
+        Ok(windows) => windows,
+        Err(CodexQuotaError::SnapshotNotFound | CodexQuotaError::SnapshotExpired { .. }) => {
+            return Vec::new();
+        }
+        Err(error) => {
+            eprintln!("Warning: skipping Codex quota: {error}");
+            return Vec::new();
+        }
+    };
+
+    windows
+        .iter()
+        .map(|window| Observation {
+            key: format!("codex_quota:{}", quota_window_name(window.window_minutes)),
+            kind: "codex_quota",
+            status: window.status.as_str(),
+            period: timestamp(window.resets_at),
+            alerting: window.status != CodexQuotaStatus::OnTrack,
+            details: NotifyDetailsJson::CodexQuota(quota_window_json(window)),
+        })
+        .collect()
+}
+
+fn budget_observation(
+    source: Option<&dyn Source>,
+    limit: f64,
+    now: DateTime<Utc>,
+    ctx: &CommandContext<'_>,
+) -> Option<Observation> {
+    let today = ctx.timezone.to_fixed_offset(now).date_naive();
+    let filter = DateFilter::new(today.with_day(1), Some(today));
+    let (day_stats, source_name, cost_mode) = if let Some(source) = source {
+        (
+            load_daily(source, &filter, ctx.timezone, true, false).day_stats,
+            source.name(),
+            CostDisplayMode::Total,
+        )
+    } else {
+        let mut combined = HashMap::new();
+        for source in all_sources() {
+            let result = load_daily(source, &filter, ctx.timezone, true, false);
+            merge_day_stats(&mut combined, result.day_stats);
+        }
+        (combined, ALL_SOURCES, CostDisplayMode::RealOnly)
+    };
+
+    let month = today.format("%Y-%m").to_string();
+    let report = monthly_budget_reports(
+        &day_stats,
+        ctx.pricing_db,
+        SortOrder::Asc,
+        limit,
+        today,
+        ctx.currency,
+        cost_mode,
+    )
+    .into_iter()
+    .find(|report| report.month == month)?;
+
+    let details = NotifyDetailsJson::MonthlyBudget(NotifyBudgetJson {
+        budget: BudgetJson::from(&report),
+        source: source_name,
+        month: report.month.clone(),
+        currency: ctx
+            .currency
+            .map_or("USD", |conv| conv.currency_code())
+            .to_string(),
+    });
+
+    Some(Observation {
+        key: format!("monthly_budget:{source_name}"),
+        kind: "monthly_budget",
+        status: report.status,
+        period: report.month,
+        alerting: matches!(report.status, "watch" | "over_budget"),
+        details,
+    })
+}
+
+/// A known item changes when its status differs from the stored one, or when
+/// it is still alerting in a new period (the next quota window or budget
+/// month); an item seen for the first time is only reported when it is
+/// already alerting.
+fn detect_changes<'a>(
+    state: &'a NotifyState,
+    observations: &'a [Observation],
+) -> Vec<StatusChange<'a>> {
+    observations
+        .iter()
+        .filter_map(|observation| {
+            let previous = state.statuses.get(&observation.key);
+            let changed = previous.map_or(observation.alerting, |seen| {
+                seen.status != observation.status
+                    || (observation.alerting && seen.period != observation.period)
+            });
+            changed.then_some(StatusChange {
+                observation,
+                previous,
+            })
+        })
+        .collect()
+}
+
+fn notify_payload<'a>(changes: &[StatusChange<'a>], now: DateTime<Utc>) -> NotifyPayloadJson<'a> {
+    NotifyPayloadJson {
+        schema_version: SCHEMA_VERSION,
+        event: "status_changed",
+        generated_at: timestamp(now),
+        changes: changes
+            .iter()
+            .map(|change| NotifyChangeJson {
+                kind: change.observation.kind,
+                key: &change.observation.key,
+                previous_status: change.previous.map(|seen| seen.status.as_str()),
+                status: change.observation.status,
+                previous_period: change.previous.map(|seen| seen.period.as_str()),
+                period: &change.observation.period,
+                details: &change.observation.details,
+            })
+            .collect(),
+    }
+}
+
+fn load_state(path: &Path) -> NotifyState {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|error| {
+            eprintln!(
+                "Warning: ignoring unreadable notify state {}: {error}",
+                path.display()
+            );
+            NotifyState::default()
+        }),
+        Err(error) if error.kind() == ErrorKind::NotFound => NotifyState::default(),
+        Err(error) => {
+            eprintln!(
+                "Warning: ignoring unreadable notify state {}: {error}",
+                path.display()
+            );
+            NotifyState::default()
+        }
+    }
+}
+
+fn save_state(path: &Path, state: &NotifyState) -> Result<(), String> {
+    write_json_atomically(state, path).map_err(|error| error.to_string())
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+fn run_hook_command(command: &str, payload: &str) -> Result<(), String> {
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|error| format!("failed to start notify command: {error}"))?;
+    // A command that exits without reading the payload closes the pipe
+    // early; its exit status decides the outcome, not the broken pipe.
+    let write_result = child
+        .stdin
+        .take()
+        .map_or(Ok(()), |mut stdin| stdin.write_all(payload.as_bytes()));
+    if let Err(error) = write_result
+        && error.kind() != ErrorKind::BrokenPipe
+    {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(format!("failed to write notify payload: {error}"));
+    }
+    let status = child
+        .wait()
+        .map_err(|error| format!("failed to wait for notify command: {error}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("notify command exited with {status}"))
+    }
+}
+
+fn post_webhook(url: &str, payload: &str) -> Result<(), String> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(WEBHOOK_TIMEOUT))
+        .build()
+        .into();
+    agent
+        .post(url)
+        .header("Content-Type", "application/json")
+        .send(payload.as_bytes())
+        .map(|_| ())
+        .map_err(|error| format!("notify webhook {url} failed: {error}"))
+}
+
+/// Try every configured hook so one broken target does not mute the other.
+fn deliver(args: &NotifyArgs, payload: &str) -> Result<(), String> {
+    let mut errors = Vec::new();
+    if let Some(command) = args.hook_command.as_deref() {
+        errors.extend(run_hook_command(command, payload).err());
+    }
+    if let Some(url) = args.webhook.as_deref() {
+        errors.extend(post_webhook(url, payload).err());
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn print_changes(changes: &[StatusChange<'_>], checked: usize, watch: bool) {
+    if changes.is_empty() {
+        if watch {
+            return;
+        }
+        if checked == 0 {
+            println!(
+                "Nothing to watch: no current Codex quota snapshot and no --monthly-budget.\nHint: start a Codex CLI session, or pass --monthly-budget AMOUNT."
+            );
+        } else {
+            println!("No status changes ({checked} checked).");
+        }
+        return;
+    }
+    for change in changes {
+        let previous = change.previous.map_or("new", |seen| seen.status.as_str());
+        println!(
+            "{}: {previous} → {}",
+            change.observation.key, change.observation.status
+        );
+    }
+}
+
+/// One check: observe, report transitions, then persist. State is only saved
+/// after every hook succeeded so a failed delivery is retried on the next run.
+fn check_once(
+    source: Option<&dyn Source>,
+    args: &NotifyArgs,
+    state_path: &Path,
+    ctx: &CommandContext<'_>,
+) -> Result<(), String> {
+    let now = Utc::now();
+    let mut observations = quota_observations();
+    if let Some(limit) = ctx.cli.monthly_budget {
+        observations.extend(budget_observation(source, limit, now, ctx));
+    }
+
+    let mut state = load_state(state_path);
+    let changes = detect_changes(&state, &observations);
+    let payload = notify_payload(&changes, now);
+    match ctx.cli.output_format() {
+        // Watching prints one report per change, not one per interval.
+        OutputFormat::Json if args.watch && changes.is_empty() => {}
+        OutputFormat::Json => print_json_report(&payload, false, ctx),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Markdown | OutputFormat::Html => {
+            print_changes(&changes, observations.len(), args.watch);
+        }
+    }
+    if !changes.is_empty() {
+        deliver(args, &report_to_string(&payload, false))?;
+    }
+
+    state.record(&observations, now);
+    save_state(state_path, &state)
+}
+
+pub(crate) fn handle_notify(source: Option<&dyn Source>, ctx: &CommandContext<'_>) {
+    let Some(args) = ctx.cli.notify_args() else {
+        return;
+    };
+    if args.watch && args.interval == 0 {
+        eprintln!("Error: --interval must be at least 1 second");
+        std::process::exit(1);
+    }
+    let Some(state_path) = args.state_file.clone().or_else(default_state_path) else {
+        eprintln!("Error: no cache directory is available for notify state; pass --state-file");
+        std::process::exit(1);
+    };
+
+    loop {
+        let result = check_once(source, args, &state_path, ctx);
+        if let Err(error) = &result {
+            eprintln!("Error: {error}");
+        }
+        if !args.watch {
+            if result.is_err() {
+                std::process::exit(1);
+            }
+            return;
+        }
+        std::thread::sleep(Duration::from_secs(args.interval));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::CodexQuotaWindow;
+
+    fn observation(key: &str, status: &'static str, alerting: bool) -> Observation {
+        let resets_at: DateTime<Utc> = "2026-08-08T00:00:00Z".parse().unwrap();
+        let window = CodexQuotaWindow {
+            observed_at: resets_at,
+            resets_at,
+            estimated_depletion_at: None,
+            window_minutes: 10_080,
+            used_pct: 50.0,
+            remaining_pct: 50.0,
+            projected_pct_at_reset: 90.0,
+            status: CodexQuotaStatus::Watch,
+        };
+        Observation {
+            key: key.to_string(),
+            kind: "codex_quota",
+            status,
+            period: timestamp(resets_at),
+            alerting,
+            details: NotifyDetailsJson::CodexQuota(quota_window_json(&window)),
+        }
+    }
+
+    fn seen(status: &str) -> SeenStatus {
+        SeenStatus {
+            status: status.to_string(),
+            period: "2026-08-08T00:00:00Z".to_string(),
+            updated_at: "2026-08-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn first_sighting_reports_only_alerting_statuses() {
+        let state = NotifyState::default();
+        let observations = vec![
+            observation("codex_quota:weekly", "watch", true),
+            observation("codex_quota:5h", "on_track", false),
+        ];
+
+        let changes = detect_changes(&state, &observations);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].observation.key, "codex_quota:weekly");
+        assert!(changes[0].previous.is_none());
+    }
+
+    #[test]
+    fn known_items_report_any_status_difference() {
+        let mut state = NotifyState::default();
+        state
+            .statuses
+            .insert("codex_quota:weekly".to_string(), seen("exhausted"));
+        state
+            .statuses
+            .insert("monthly_budget:claude".to_string(), seen("watch"));
+        let observations = vec![
+            observation("codex_quota:weekly", "on_track", false),
+            observation("monthly_budget:claude", "watch", true),
+        ];
+
+        let changes = detect_changes(&state, &observations);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].observation.status, "on_track");
+        assert_eq!(changes[0].previous.unwrap().status, "exhausted");
+    }
+
+    #[test]
+    fn alerting_items_report_a_new_period_with_the_same_status() {
+        let mut state = NotifyState::default();
+        for (key, status) in [
+            ("codex_quota:weekly", "watch"),
+            ("codex_quota:5h", "on_track"),
+        ] {
+            let mut last_window = seen(status);
+            last_window.period = "2026-08-01T00:00:00Z".to_string();
+            state.statuses.insert(key.to_string(), last_window);
+        }
+        let observations = vec![
+            observation("codex_quota:weekly", "watch", true),
+            observation("codex_quota:5h", "on_track", false),
+        ];
+
+        let changes = detect_changes(&state, &observations);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].observation.key, "codex_quota:weekly");
+        assert_eq!(changes[0].previous.unwrap().period, "2026-08-01T00:00:00Z");
+    }
+
+    #[test]
+    fn payload_describes_previous_and_current_status() {
+        let mut state = NotifyState::default();
+        state
+            .statuses
+            .insert("codex_quota:weekly".to_string(), seen("on_track"));
+        let observations = vec![observation("codex_quota:weekly", "likely_exhausted", true)];
+        let changes = detect_changes(&state, &observations);
+
+        let payload = serde_json::to_value(notify_payload(
+            &changes,
+            "2026-08-02T00:00:00Z".parse().unwrap(),
+        ))
+        .unwrap();
+
+        let schema = crate::output::SchemaReport::Notify.json_schema();
+        assert!(jsonschema::is_valid(&schema, &payload));
+        assert_eq!(payload["schema_version"], SCHEMA_VERSION);
+        assert_eq!(payload["event"], "status_changed");
+        assert_eq!(payload["generated_at"], "2026-08-02T00:00:00Z");
+        assert_eq!(payload["changes"][0]["previous_status"], "on_track");
+        assert_eq!(payload["changes"][0]["status"], "likely_exhausted");
+        assert_eq!(payload["changes"][0]["kind"], "codex_quota");
+    }
+
+    #[test]
+    fn state_round_trips_and_keeps_unobserved_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(STATE_FILE);
+        let mut state = NotifyState::default();
+        state
+            .statuses
+            .insert("monthly_budget:claude".to_string(), seen("watch"));
+        state.record(
+            &[observation("codex_quota:weekly", "watch", true)],
+            "2026-08-02T00:00:00Z".parse().unwrap(),
+        );
+
+        save_state(&path, &state).unwrap();
+        let loaded = load_state(&path);
+
+        assert_eq!(loaded.statuses.len(), 2);
+        assert_eq!(loaded.statuses["codex_quota:weekly"].status, "watch");
+        assert_eq!(loaded.statuses["monthly_budget:claude"].status, "watch");
+    }
+
+    #[test]
+    fn corrupt_state_starts_fresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE);
+        fs::write(&path, "{not json").unwrap();
+
+        assert!(load_state(&path).statuses.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hook_command_receives_payload_and_reports_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("payload.json");
+
+        run_hook_command(&format!("cat > '{}'", out.display()), "{\"ok\":true}").unwrap();
+        assert_eq!(fs::read_to_string(&out).unwrap(), "{\"ok\":true}");
+
+        let error = run_hook_command("exit 3", "{}").unwrap_err();
+        assert!(error.contains("exited"), "{error}");
+    }
+}
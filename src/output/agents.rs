@@ -0,0 +1,387 @@
+//! Output for the main-thread vs subagent split of each session
+
+use std::fmt::Write;
+
+use comfy_table::{Cell, Color};
+
+use crate::core::{AgentUsage, SessionAgentStats, format_project_name};
+use crate::pricing::{CurrencyConverter, PricingDb, sum_model_costs};
+
+use super::command_json::{AgentSessionJson, AgentUsageJson, SubagentTypeJson};
+use super::csv::csv_cost;
+use super::format::{
+    Locale, compare_cost, create_styled_table, csv_escape, format_cost, format_number, header_cell,
+    right_cell,
+};
+use super::locale::Msg;
+use super::schema::{SCHEMA_VERSION, cost_amount};
+
+/// Label for subagent requests whose launching `Task` call is unknown.
+const UNKNOWN_TYPE: &str = "unknown";
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AgentTableOptions<'a> {
+    pub(crate) use_color: bool,
+    pub(crate) show_cost: bool,
+    pub(crate) source_label: &'a str,
+    pub(crate) locale: Locale,
+    pub(crate) currency: Option<&'a CurrencyConverter>,
+}
+
+/// A session with its main-thread and subagent costs.
+struct AgentRow<'a> {
+    session: &'a SessionAgentStats,
+    main_cost: f64,
+    subagent_cost: f64,
+}
+
+impl AgentRow<'_> {
+    /// Subagent share of the session: by cost when priced, else by tokens.
+    fn subagent_share_pct(&self, by_cost: bool) -> f64 {
+        let (subagent, total) = if by_cost {
+            (self.subagent_cost, self.main_cost + self.subagent_cost)
+        } else {
+            let subagent = self.session.subagents.stats.total_tokens();
+            (
+                subagent as f64,
+                (self.session.main.stats.total_tokens() + subagent) as f64,
+            )
+        };
+        if total > 0.0 {
+            subagent / total * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Sessions with their costs, heaviest subagent spend first.
+fn agent_rows<'a>(sessions: &'a [SessionAgentStats], pricing_db: &PricingDb) -> Vec<AgentRow<'a>> {
+    let mut rows: Vec<_> = sessions
+        .iter()
+        .map(|session| AgentRow {
+            session,
+            main_cost: sum_model_costs(&session.main.models, pricing_db),
+            subagent_cost: sum_model_costs(&session.subagents.models, pricing_db),
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        compare_cost(b.subagent_cost, a.subagent_cost)
+            .then_with(|| {
+                b.session
+                    .subagents
+                    .stats
+                    .total_tokens()
+                    .cmp(&a.session.subagents.stats.total_tokens())
+            })
+            .then_with(|| a.session.session_id.cmp(&b.session.session_id))
+    });
+    rows
+}
+
+fn type_label(subagent_type: Option<&String>) -> &str {
+    subagent_type.map_or(UNKNOWN_TYPE, String::as_str)
+}
+
+/// Subagent types of a session by call count, e.g. `Explore ×3, unknown ×1`.
+fn subagent_types_summary(session: &SessionAgentStats) -> String {
+    let mut types: Vec<_> = session.subagent_types.iter().collect();
+    types.sort_by(|(a_type, a), (b_type, b)| {
+        b.stats
+            .count
+            .cmp(&a.stats.count)
+            .then_with(|| a_type.cmp(b_type))
+    });
+    types
+        .iter()
+        .map(|(subagent_type, usage)| {
+            format!(
+                "{} ×{}",
+                type_label(subagent_type.as_ref()),
+                usage.stats.count
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Numeric cells of a row: calls, tokens, costs and subagent share.
+fn split_cells(row: &AgentRow<'_>, bold: bool, options: AgentTableOptions<'_>) -> Vec<Cell> {
+    let session = row.session;
+    let number = |n: i64| right_cell(&format_number(n, options.locale), None, bold);
+    let mut cells = vec![
+        number(session.main.stats.count),
+        number(session.subagents.stats.count),
+        number(session.main.stats.total_tokens()),
+        number(session.subagents.stats.total_tokens()),
+    ];
+    if options.show_cost {
+        let cost_color = options.use_color.then_some(Color::Green);
+        for cost in [row.main_cost, row.subagent_cost] {
+            cells.push(right_cell(
+                &format_cost(cost, options.currency, options.locale),
+                cost_color,
+                bold,
+            ));
+        }
+    }
+    cells.push(right_cell(
+        &format!("{:.1}%", row.subagent_share_pct(options.show_cost)),
+        None,
+        bold,
+    ));
+    cells
+}
+
+/// Print one row per session with its main-thread vs subagent split
+pub(crate) fn print_agent_table(
+    sessions: &[SessionAgentStats],
+    pricing_db: &PricingDb,
+    options: AgentTableOptions<'_>,
+) {
+    let locale = options.locale;
+    let h_cell = |msg| header_cell(locale.text(msg), options.use_color);
+    let mut table = create_styled_table();
+    let mut header = vec![
+        h_cell(Msg::Session),
+        h_cell(Msg::Project),
+        h_cell(Msg::MainCalls),
+        h_cell(Msg::SubagentCalls),
+        h_cell(Msg::MainTokens),
+        h_cell(Msg::SubagentTokens),
+    ];
+    if options.show_cost {
+        header.push(h_cell(Msg::MainCost));
+        header.push(h_cell(Msg::SubagentCost));
+    }
+    header.push(h_cell(Msg::SubagentPct));
+    header.push(h_cell(Msg::SubagentTypes));
+    table.set_header(header);
+
+    let mut total_session = SessionAgentStats::default();
+    let (mut main_cost, mut subagent_cost) = (0.0, 0.0);
+    for row in agent_rows(sessions, pricing_db) {
+        let session = row.session;
+        total_session.main.stats.add(&session.main.stats);
+        total_session.subagents.stats.add(&session.subagents.stats);
+        main_cost += row.main_cost;
+        subagent_cost += row.subagent_cost;
+
+        let mut cells = vec![
+            Cell::new(&session.session_id),
+            Cell::new(format_project_name(&session.project_path)),
+        ];
+        cells.extend(split_cells(&row, false, options));
+        cells.push(Cell::new(subagent_types_summary(session)));
+        table.add_row(cells);
+    }
+
+    let totals = AgentRow {
+        session: &total_session,
+        main_cost,
+        subagent_cost,
+    };
+    let mut total_row = vec![Cell::new(locale.text(Msg::TotalRow)), Cell::new("")];
+    total_row.extend(split_cells(&totals, true, options));
+    total_row.push(Cell::new(""));
+    table.add_row(total_row);
+
+    println!(
+        "\n  {} {}\n",
+        options.source_label,
+        locale.text(Msg::UsageByAgent)
+    );
+    println!("{table}");
+    println!("\n  {}\n", locale.subagent_share_note(options.show_cost));
+}
+
+fn agent_usage_json(
+    usage: &AgentUsage,
+    cost: f64,
+    show_cost: bool,
+    currency: Option<&CurrencyConverter>,
+) -> AgentUsageJson {
+    AgentUsageJson {
+        calls: usage.stats.count,
+        total_tokens: usage.stats.total_tokens(),
+        cost: show_cost.then(|| cost_amount(cost, currency)),
+    }
+}
+
+/// Output the per-session main-thread vs subagent split as JSON
+pub(crate) fn agent_json_rows(
+    sessions: &[SessionAgentStats],
+    pricing_db: &PricingDb,
+    show_cost: bool,
+    currency: Option<&CurrencyConverter>,
+) -> Vec<AgentSessionJson> {
+    let rows: Vec<AgentSessionJson> = agent_rows(sessions, pricing_db)
+        .iter()
+        .map(|row| {
+            let session = row.session;
+            let subagent_types = session
+                .subagent_types
+                .iter()
+                .map(|(subagent_type, usage)| SubagentTypeJson {
+                    subagent_type: subagent_type.clone(),
+                    usage: agent_usage_json(
+                        usage,
+                        sum_model_costs(&usage.models, pricing_db),
+                        show_cost,
+                        currency,
+                    ),
+                })
+                .collect();
+            AgentSessionJson {
+                schema_version: SCHEMA_VERSION,
+                session_id: session.session_id.clone(),
+                project: format_project_name(&session.project_path),
+                project_path: session.project_path.clone(),
+                last_timestamp: session.last_timestamp.clone(),
+                main: agent_usage_json(&session.main, row.main_cost, show_cost, currency),
+                subagents: agent_usage_json(
+                    &session.subagents,
+                    row.subagent_cost,
+                    show_cost,
+                    currency,
+                ),
+                subagent_share_pct: (row.subagent_share_pct(show_cost) * 10.0).round() / 10.0,
+                subagent_types,
+            }
+        })
+        .collect();
+
+    rows
+}
+
+/// Output the split as CSV: one `main` row per session plus one `subagent`
+/// row per subagent type.
+pub(crate) fn output_agent_csv(
+    sessions: &[SessionAgentStats],
+    pricing_db: &PricingDb,
+    show_cost: bool,
+    currency: Option<&CurrencyConverter>,
+) -> String {
+    let mut out = String::from("session_id,project,role,subagent_type,calls,total_tokens");
+    if show_cost {
+        out.push_str(",cost");
+    }
+    out.push('\n');
+    for row in agent_rows(sessions, pricing_db) {
+        let session = row.session;
+        let lines =
+            std::iter::once(("main", "", &session.main)).chain(session.subagent_types.iter().map(
+                |(subagent_type, usage)| ("subagent", type_label(subagent_type.as_ref()), usage),
+            ));
+        for (role, subagent_type, usage) in lines {
+            if usage.stats.count == 0 && role == "main" {
+                continue;
+            }
+            let _ = write!(
+                out,
+                "{},{},{role},{},{},{}",
+                csv_escape(&session.session_id),
+                csv_escape(&format_project_name(&session.project_path)),
+                csv_escape(subagent_type),
+                usage.stats.count,
+                usage.stats.total_tokens(),
+            );
+            if show_cost {
+                let cost = sum_model_costs(&usage.models, pricing_db);
+                let _ = write!(out, ",{}", csv_cost(cost, currency));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Stats;
+    use serde_json::Value;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn usage(model: &str, input: i64, count: i64) -> AgentUsage {
+        let stats = Stats {
+            input_tokens: input,
+            count,
+            ..Default::default()
+        };
+        AgentUsage {
+            stats: stats.clone(),
+            models: HashMap::from([(model.to_string(), stats)]),
+        }
+    }
+
+    fn session(id: &str, main: i64, explore: i64, unknown: i64) -> SessionAgentStats {
+        let mut subagents = AgentUsage::default();
+        let mut subagent_types = BTreeMap::new();
+        for (subagent_type, input) in [(Some("Explore".to_string()), explore), (None, unknown)] {
+            if input > 0 {
+                let typed = usage("sonnet-4", input, 1);
+                subagents.stats.add(&typed.stats);
+                subagents
+                    .models
+                    .entry("sonnet-4".to_string())
+                    .or_default()
+                    .add(&typed.stats);
+                subagent_types.insert(subagent_type, typed);
+            }
+        }
+        SessionAgentStats {
+            session_id: id.to_string(),
+            project_path: "-work-app".to_string(),
+            main: usage("sonnet-4", main, 1),
+            subagents,
+            subagent_types,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn json_sorts_by_subagent_cost_and_reports_share() {
+        let db = PricingDb::default();
+        let sessions = vec![
+            session("quiet", 1_000_000, 0, 0),
+            session("busy", 1_000_000, 2_000_000, 1_000_000),
+        ];
+        let json: Value =
+            serde_json::to_value(agent_json_rows(&sessions, &db, true, None)).expect("valid json");
+        assert_eq!(json[0]["session_id"], "busy");
+        assert_eq!(json[0]["subagents"]["calls"], 2);
+        assert_eq!(json[0]["subagent_share_pct"], 75.0);
+        assert_eq!(json[0]["subagent_types"][0]["subagent_type"], Value::Null);
+        assert_eq!(json[0]["subagent_types"][1]["subagent_type"], "Explore");
+        assert_eq!(json[1]["session_id"], "quiet");
+        assert_eq!(json[1]["subagent_share_pct"], 0.0);
+    }
+
+    #[test]
+    fn csv_has_main_row_and_one_row_per_subagent_type() {
+        let db = PricingDb::default();
+        let csv = output_agent_csv(&[session("s1", 100, 40, 5)], &db, false, None);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "session_id,project,role,subagent_type,calls,total_tokens",
+                "s1,work-app,main,,1,100",
+                "s1,work-app,subagent,unknown,1,5",
+                "s1,work-app,subagent,Explore,1,40",
+            ]
+        );
+    }
+
+    #[test]
+    fn types_summary_orders_by_call_count() {
+        let mut s = session("s1", 100, 40, 5);
+        s.subagent_types
+            .get_mut(&Some("Explore".to_string()))
+            .unwrap()
+            .stats
+            .count = 3;
+        assert_eq!(subagent_types_summary(&s), "Explore ×3, unknown ×1");
+    }
+}
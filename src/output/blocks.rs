@@ -3,13 +3,18 @@ use comfy_table::{Cell, Color};
 use crate::cli::SortOrder;
 use crate::core::{BlockStats, Stats};
 use crate::output::format::{
-    NumberFormat, cache_hit_rate_json_value, cost_json_value, create_styled_table,
-    format_cache_hit_rate, format_compact, format_cost, format_number, header_cell, right_cell,
-    styled_cell,
+    Locale, create_styled_table, format_cache_hit_rate, format_compact, format_cost, format_number,
+    header_cell, right_cell, styled_cell,
 };
+use crate::output::locale::Msg;
+use crate::output::markup::Markup;
+use crate::output::period::span_currency;
 use crate::output::pricing_meta;
+use crate::output::schema::{
+    BlockJson, CostJson, ExchangeRateJson, SCHEMA_VERSION, TokensJson, model_names, rows_to_string,
+};
 use crate::pricing::{
-    CurrencyConverter, PricingDb, model_cost_kind, sum_estimated_proxy_model_costs, sum_model_costs,
+    CostDisplayMode, CurrencyConverter, PricingDb, sum_estimated_proxy_model_costs, sum_model_costs,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -21,8 +26,9 @@ pub(crate) struct BlockTableOptions<'a> {
     pub(crate) show_cost: bool,
     pub(crate) supports_cache_read: bool,
     pub(crate) source_label: &'a str,
-    pub(crate) number_format: NumberFormat,
+    pub(crate) locale: Locale,
     pub(crate) currency: Option<&'a CurrencyConverter>,
+    pub(crate) markup: Markup,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -36,7 +42,7 @@ pub(crate) fn print_block_table(
     let compact = options.compact;
     let show_cost = options.show_cost;
     let source_label = options.source_label;
-    let number_format = options.number_format;
+    let locale = options.locale;
 
     let mut sorted_blocks: Vec<_> = blocks.iter().collect();
 
@@ -45,28 +51,29 @@ pub(crate) fn print_block_table(
         SortOrder::Desc => sorted_blocks.sort_by(|a, b| b.block_start.cmp(&a.block_start)),
     }
 
+    let h_cell = |msg| header_cell(locale.text(msg), use_color);
     let mut table = create_styled_table();
 
     if compact {
-        let mut header = vec![header_cell("Block", use_color)];
-        header.push(header_cell("Cache Hit", use_color));
-        header.push(header_cell("Total", use_color));
+        let mut header = vec![h_cell(Msg::Block)];
+        header.push(h_cell(Msg::CacheHit));
+        header.push(h_cell(Msg::Total));
         if show_cost {
-            header.push(header_cell("Cost", use_color));
+            header.push(h_cell(Msg::Cost));
         }
         table.set_header(header);
     } else {
         let mut header = vec![
-            header_cell("Block", use_color),
-            header_cell("Input", use_color),
-            header_cell("Output", use_color),
-            header_cell("Cache Create", use_color),
-            header_cell("Cache Read", use_color),
+            h_cell(Msg::Block),
+            h_cell(Msg::Input),
+            h_cell(Msg::Output),
+            h_cell(Msg::CacheCreate),
+            h_cell(Msg::CacheRead),
         ];
-        header.push(header_cell("Cache Hit", use_color));
-        header.push(header_cell("Total", use_color));
+        header.push(h_cell(Msg::CacheHit));
+        header.push(h_cell(Msg::Total));
         if show_cost {
-            header.push(header_cell("Cost", use_color));
+            header.push(h_cell(Msg::Cost));
         }
         table.set_header(header);
     }
@@ -77,9 +84,21 @@ pub(crate) fn print_block_table(
     let mut total_cost = 0.0;
     let mut total_estimated_cost = 0.0;
     let mut has_estimated_cost = false;
+    let total_currency = span_currency(
+        options.currency,
+        sorted_blocks.iter().flat_map(|block| &block.days),
+        pricing_db,
+        CostDisplayMode::Total,
+    );
 
     for block in &sorted_blocks {
         let block_cost = sum_model_costs(&block.models, pricing_db);
+        let row_currency = span_currency(
+            options.currency,
+            &block.days,
+            pricing_db,
+            CostDisplayMode::Total,
+        );
         total_cost += block_cost;
         let estimated_cost = sum_estimated_proxy_model_costs(&block.models, pricing_db);
         if estimated_cost > 0.0 {
@@ -98,13 +117,13 @@ pub(crate) fn print_block_table(
                 false,
             ));
             row.push(right_cell(
-                &format_compact(block.stats.total_tokens(), number_format),
+                &format_compact(block.stats.total_tokens(), locale),
                 None,
                 false,
             ));
             if show_cost {
                 row.push(right_cell(
-                    &format_cost(block_cost, options.currency),
+                    &format_cost(block_cost, row_currency.as_deref(), options.locale),
                     cost_color,
                     false,
                 ));
@@ -114,25 +133,21 @@ pub(crate) fn print_block_table(
             let mut row = vec![
                 Cell::new(&block_label),
                 right_cell(
-                    &format_number(block.stats.input_tokens, number_format),
-                    None,
-                    false,
-                ),
-                right_cell(
-                    &format_number(block.stats.output_tokens, number_format),
+                    &format_number(block.stats.input_tokens, locale),
                     None,
                     false,
                 ),
                 right_cell(
-                    &format_number(block.stats.cache_creation, number_format),
+                    &format_number(block.stats.output_tokens, locale),
                     None,
                     false,
                 ),
                 right_cell(
-                    &format_number(block.stats.cache_read, number_format),
+                    &format_number(block.stats.cache_creation, locale),
                     None,
                     false,
                 ),
+                right_cell(&format_number(block.stats.cache_read, locale), None, false),
             ];
             row.push(right_cell(
                 &format_cache_hit_rate(block.stats.cache_hit_rate(options.supports_cache_read)),
@@ -140,13 +155,13 @@ pub(crate) fn print_block_table(
                 false,
             ));
             row.push(right_cell(
-                &format_number(block.stats.total_tokens(), number_format),
+                &format_number(block.stats.total_tokens(), locale),
                 None,
                 false,
             ));
             if show_cost {
                 row.push(right_cell(
-                    &format_cost(block_cost, options.currency),
+                    &format_cost(block_cost, row_currency.as_deref(), options.locale),
                     cost_color,
                     false,
                 ));
@@ -160,20 +175,20 @@ pub(crate) fn print_block_table(
 
     // Add total row
     if compact {
-        let mut row = vec![styled_cell("TOTAL", cyan, true)];
+        let mut row = vec![styled_cell(locale.text(Msg::TotalRow), cyan, true)];
         row.push(right_cell(
             &format_cache_hit_rate(total_stats.cache_hit_rate(options.supports_cache_read)),
             cyan,
             true,
         ));
         row.push(right_cell(
-            &format_compact(total_stats.total_tokens(), number_format),
+            &format_compact(total_stats.total_tokens(), locale),
             cyan,
             true,
         ));
         if show_cost {
             row.push(right_cell(
-                &format_cost(total_cost, options.currency),
+                &format_cost(total_cost, total_currency.as_deref(), options.locale),
                 green,
                 true,
             ));
@@ -181,27 +196,19 @@ pub(crate) fn print_block_table(
         table.add_row(row);
     } else {
         let mut row = vec![
-            styled_cell("TOTAL", cyan, true),
+            styled_cell(locale.text(Msg::TotalRow), cyan, true),
+            right_cell(&format_number(total_stats.input_tokens, locale), cyan, true),
             right_cell(
-                &format_number(total_stats.input_tokens, number_format),
+                &format_number(total_stats.output_tokens, locale),
                 cyan,
                 true,
             ),
             right_cell(
-                &format_number(total_stats.output_tokens, number_format),
-                cyan,
-                true,
-            ),
-            right_cell(
-                &format_number(total_stats.cache_creation, number_format),
-                cyan,
-                true,
-            ),
-            right_cell(
-                &format_number(total_stats.cache_read, number_format),
+                &format_number(total_stats.cache_creation, locale),
                 cyan,
                 true,
             ),
+            right_cell(&format_number(total_stats.cache_read, locale), cyan, true),
         ];
         row.push(right_cell(
             &format_cache_hit_rate(total_stats.cache_hit_rate(options.supports_cache_read)),
@@ -209,13 +216,13 @@ pub(crate) fn print_block_table(
             true,
         ));
         row.push(right_cell(
-            &format_number(total_stats.total_tokens(), number_format),
+            &format_number(total_stats.total_tokens(), locale),
             cyan,
             true,
         ));
         if show_cost {
             row.push(right_cell(
-                &format_cost(total_cost, options.currency),
+                &format_cost(total_cost, total_currency.as_deref(), options.locale),
                 green,
                 true,
             ));
@@ -223,34 +230,42 @@ pub(crate) fn print_block_table(
         table.add_row(row);
     }
 
-    println!("\n  {source_label} 5-Hour Billing Blocks\n");
-    println!("{table}");
+    options.markup.title(&format!(
+        "{source_label} {}",
+        locale.text(Msg::BillingBlocks)
+    ));
+    options.markup.table(&table, 1, locale);
     if show_cost && has_estimated_cost {
-        println!(
-            "\n  Cost includes estimated proxy values: {}",
-            format_cost(total_estimated_cost, options.currency)
-        );
+        options.markup.line(&format!(
+            "\n  {}: {}",
+            locale.text(Msg::EstimatedProxyIncluded),
+            format_cost(
+                total_estimated_cost,
+                total_currency.as_deref(),
+                options.locale
+            )
+        ));
     }
     if show_cost
         && let Some(note) =
             pricing_meta::note_for_maps(sorted_blocks.iter().map(|block| &block.models), pricing_db)
     {
-        println!("\n  {note}");
+        options.markup.line(&format!("\n  {note}"));
     }
-    println!(
-        "\n  {} blocks\n",
-        format_number(sorted_blocks.len() as i64, number_format)
-    );
+    options.markup.line(&format!(
+        "\n  {}\n",
+        locale.block_count(&format_number(sorted_blocks.len() as i64, locale))
+    ));
 }
 
-pub(crate) fn output_block_json(
-    blocks: &[BlockStats],
-    pricing_db: &PricingDb,
+pub(crate) fn block_json_rows<'a>(
+    blocks: &'a [BlockStats],
+    pricing_db: &'a PricingDb,
     order: SortOrder,
     show_cost: bool,
     supports_cache_read: bool,
-    currency: Option<&CurrencyConverter>,
-) -> String {
+    currency: Option<&'a CurrencyConverter>,
+) -> impl Iterator<Item = BlockJson> + 'a {
     let mut sorted_blocks: Vec<_> = blocks.iter().collect();
 
     match order {
@@ -258,45 +273,49 @@ pub(crate) fn output_block_json(
         SortOrder::Desc => sorted_blocks.sort_by(|a, b| b.block_start.cmp(&a.block_start)),
     }
 
-    let output: Vec<serde_json::Value> = sorted_blocks
-        .iter()
-        .map(|block| {
-            let block_cost = sum_model_costs(&block.models, pricing_db);
-
-            let mut models: Vec<_> = block.models.keys().cloned().collect();
-            models.sort();
-            let mut obj = serde_json::json!({
-                "block_start": block.block_start,
-                "block_end": block.block_end,
-                "input_tokens": block.stats.input_tokens,
-                "output_tokens": block.stats.output_tokens,
-                "cache_creation_tokens": block.stats.cache_creation,
-                "cache_read_tokens": block.stats.cache_read,
-                "cache_hit_rate": cache_hit_rate_json_value(
-                    block.stats.cache_hit_rate(supports_cache_read)
-                ),
-                "total_tokens": block.stats.total_tokens(),
-                "models": models,
-            });
-            if show_cost {
-                obj["cost"] = cost_json_value(block_cost, currency);
-                pricing_meta::add_json(&mut obj, &block.models, pricing_db);
-                let estimated_cost = sum_estimated_proxy_model_costs(&block.models, pricing_db);
-                if estimated_cost > 0.0 {
-                    obj["cost_kind"] = serde_json::json!(model_cost_kind(&block.models).as_str());
-                    obj["estimated_cost"] = cost_json_value(estimated_cost, currency);
-                }
-            }
-            obj
-        })
-        .collect();
-
-    serde_json::to_string_pretty(&output).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize JSON output: {e}");
-        "[]".to_string()
+    sorted_blocks.into_iter().map(move |block| {
+        let currency = show_cost
+            .then(|| span_currency(currency, &block.days, pricing_db, CostDisplayMode::Total))
+            .flatten();
+        BlockJson {
+            schema_version: SCHEMA_VERSION,
+            block_start: block.block_start.clone(),
+            block_end: block.block_end.clone(),
+            tokens: TokensJson::new(&block.stats, supports_cache_read),
+            models: model_names(&block.models),
+            cost: show_cost.then(|| {
+                CostJson::for_models(
+                    &block.models,
+                    sum_model_costs(&block.models, pricing_db),
+                    pricing_db,
+                    currency.as_deref(),
+                )
+            }),
+            exchange_rate: currency.as_deref().map(ExchangeRateJson::new),
+        }
     })
 }
 
+pub(crate) fn output_block_json(
+    blocks: &[BlockStats],
+    pricing_db: &PricingDb,
+    order: SortOrder,
+    show_cost: bool,
+    supports_cache_read: bool,
+    currency: Option<&CurrencyConverter>,
+) -> String {
+    let rows: Vec<_> = block_json_rows(
+        blocks,
+        pricing_db,
+        order,
+        show_cost,
+        supports_cache_read,
+        currency,
+    )
+    .collect();
+    rows_to_string(&rows, true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -312,6 +331,7 @@ mod tests {
                 ..Default::default()
             },
             models: HashMap::new(),
+            days: HashMap::new(),
         }
     }
 
@@ -334,6 +354,7 @@ mod tests {
                 ..Default::default()
             },
             models: HashMap::new(),
+            days: HashMap::new(),
         }
     }
 
@@ -441,6 +462,7 @@ mod tests {
             block_end: "2026-02-12 15:00".to_string(),
             stats: Stats::default(),
             models,
+            days: HashMap::new(),
         }];
         let json_str = output_block_json(&blocks, &db, SortOrder::Asc, false, true, None);
         let parsed: Vec<serde_json::Value> = serde_json::from_str(&json_str).unwrap();
@@ -2,12 +2,14 @@ use std::collections::HashMap;
 
 use chrono::{Datelike, NaiveDate};
 use comfy_table::{Cell, Color};
-use serde_json::{Map, Value};
 
 use crate::cli::SortOrder;
 use crate::core::DayStats;
-use crate::output::format::{create_styled_table, header_cell, right_cell, styled_cell};
-use crate::output::period::{Period, aggregate_day_stats_by_period};
+use crate::output::format::{Locale, create_styled_table, header_cell, right_cell, styled_cell};
+use crate::output::locale::Msg;
+use crate::output::markup::Markup;
+use crate::output::period::{Period, PeriodRates, aggregate_day_stats_by_period, row_currency};
+use crate::output::schema::{BudgetJson, PeriodJsonRow, PeriodKeyJson, PricingJson};
 use crate::pricing::{
     CostDisplayMode, CurrencyConverter, PricingDb, PricingSource, pricing_source_for_models,
     sum_display_model_costs,
@@ -147,6 +149,7 @@ pub(crate) fn monthly_budget_reports(
     cost_mode: CostDisplayMode,
 ) -> Vec<MonthlyBudgetReport> {
     let monthly = aggregate_day_stats_by_period(day_stats, Period::Month);
+    let rates = PeriodRates::new(day_stats, Period::Month, currency, pricing_db, cost_mode);
     let mut months: Vec<_> = monthly.keys().collect();
     match order {
         SortOrder::Asc => months.sort(),
@@ -159,7 +162,7 @@ pub(crate) fn monthly_budget_reports(
             monthly.get(month).map(|stats| {
                 let spent = display_cost(
                     sum_display_model_costs(&stats.models, pricing_db, cost_mode),
-                    currency,
+                    row_currency(rates.as_ref(), month, currency),
                 );
                 let pricing_source = pricing_source_for_models(&stats.models, pricing_db);
                 budget_report(
@@ -175,78 +178,58 @@ pub(crate) fn monthly_budget_reports(
         .collect()
 }
 
-fn json_number(value: f64) -> Value {
-    if value.is_nan() {
-        Value::Null
-    } else {
-        serde_json::json!(value)
-    }
+fn json_number(value: f64) -> Option<f64> {
+    (!value.is_nan()).then_some(value)
 }
 
-fn report_json(report: &MonthlyBudgetReport) -> Value {
-    let mut obj = Map::new();
-    obj.insert("limit".to_string(), json_number(report.limit));
-    obj.insert("spent".to_string(), json_number(report.spent));
-    obj.insert("projected".to_string(), json_number(report.projected));
-    obj.insert("remaining".to_string(), json_number(report.remaining));
-    obj.insert("used_pct".to_string(), json_number(report.used_pct));
-    obj.insert(
-        "projected_pct".to_string(),
-        json_number(report.projected_pct),
-    );
-    obj.insert(
-        "days_elapsed".to_string(),
-        serde_json::json!(report.days_elapsed),
-    );
-    obj.insert(
-        "days_in_month".to_string(),
-        serde_json::json!(report.days_in_month),
-    );
-    obj.insert("status".to_string(), serde_json::json!(report.status));
-    obj.insert(
-        "pricing_source".to_string(),
-        serde_json::json!(report.pricing_source.as_str()),
-    );
-    if matches!(
-        report.pricing_source,
-        PricingSource::Cache | PricingSource::CacheStale | PricingSource::Mixed
-    ) {
-        if let Some(age) = report.pricing_cache_age_seconds {
-            obj.insert(
-                "pricing_cache_age_seconds".to_string(),
-                serde_json::json!(age),
-            );
-        }
-        if let Some(mtime) = report.pricing_cache_mtime_epoch_seconds {
-            obj.insert(
-                "pricing_cache_mtime_epoch_seconds".to_string(),
-                serde_json::json!(mtime),
-            );
+impl From<&MonthlyBudgetReport> for BudgetJson {
+    fn from(report: &MonthlyBudgetReport) -> Self {
+        let cache_backed = matches!(
+            report.pricing_source,
+            PricingSource::Cache | PricingSource::CacheStale | PricingSource::Mixed
+        );
+        BudgetJson {
+            limit: json_number(report.limit),
+            spent: json_number(report.spent),
+            projected: json_number(report.projected),
+            remaining: json_number(report.remaining),
+            used_pct: json_number(report.used_pct),
+            projected_pct: json_number(report.projected_pct),
+            days_elapsed: report.days_elapsed,
+            days_in_month: report.days_in_month,
+            status: report.status,
+            pricing: PricingJson {
+                pricing_source: report.pricing_source.as_str(),
+                pricing_cache_age_seconds: report
+                    .pricing_cache_age_seconds
+                    .filter(|_| cache_backed),
+                pricing_cache_mtime_epoch_seconds: report
+                    .pricing_cache_mtime_epoch_seconds
+                    .filter(|_| cache_backed),
+                pricing_catalog: None,
+            },
         }
     }
-    Value::Object(obj)
 }
 
-pub(crate) fn add_monthly_budget_to_json(json: &str, reports: &[MonthlyBudgetReport]) -> String {
-    let mut rows: Vec<Value> = serde_json::from_str(json).unwrap_or_default();
+/// Set the `budget` of each monthly row that has a report.
+pub(crate) fn attach_monthly_budgets(rows: &mut [PeriodJsonRow], reports: &[MonthlyBudgetReport]) {
     let by_month: HashMap<&str, &MonthlyBudgetReport> = reports
         .iter()
         .map(|report| (report.month.as_str(), report))
         .collect();
 
-    for row in &mut rows {
-        let Some(obj) = row.as_object_mut() else {
+    for row in rows {
+        let PeriodJsonRow::Period(entry) = row else {
             continue;
         };
-        let Some(month) = obj.get("month").and_then(Value::as_str) else {
+        let PeriodKeyJson::Month(month) = &entry.period else {
             continue;
         };
-        if let Some(report) = by_month.get(month) {
-            obj.insert("budget".to_string(), report_json(report));
+        if let Some(report) = by_month.get(month.as_str()) {
+            entry.budget = Some(BudgetJson::from(*report));
         }
     }
-
-    serde_json::to_string(&rows).unwrap_or_else(|_| "[]".to_string())
 }
 
 fn format_amount(value: f64, currency: Option<&CurrencyConverter>) -> String {
@@ -284,26 +267,29 @@ pub(crate) fn print_monthly_budget_table(
     reports: &[MonthlyBudgetReport],
     use_color: bool,
     currency: Option<&CurrencyConverter>,
+    locale: Locale,
+    markup: Markup,
 ) {
     if reports.is_empty() {
         return;
     }
 
+    let h_cell = |msg| header_cell(locale.text(msg), use_color);
     let mut table = create_styled_table();
     table.set_header(vec![
-        header_cell("Month", use_color),
-        header_cell("Budget", use_color),
-        header_cell("Spent", use_color),
-        header_cell("Projected", use_color),
-        header_cell("Remaining", use_color),
-        header_cell("Used", use_color),
-        header_cell("Projected", use_color),
-        header_cell("Status", use_color),
+        h_cell(Msg::Month),
+        h_cell(Msg::Budget),
+        h_cell(Msg::Spent),
+        h_cell(Msg::Projected),
+        h_cell(Msg::Remaining),
+        h_cell(Msg::Used),
+        h_cell(Msg::Projected),
+        h_cell(Msg::Status),
     ]);
 
     for report in reports {
         table.add_row(vec![
-            Cell::new(&report.month),
+            Cell::new(locale.period_label(&report.month, Period::Month)),
             right_cell(&format_amount(report.limit, currency), None, false),
             right_cell(&format_amount(report.spent, currency), None, false),
             right_cell(&format_amount(report.projected, currency), None, false),
@@ -314,10 +300,10 @@ pub(crate) fn print_monthly_budget_table(
         ]);
     }
 
-    println!("\n  Monthly Budget Forecast\n");
-    println!("{table}");
+    markup.title(locale.text(Msg::MonthlyBudgetForecast));
+    markup.table(&table, 0, locale);
     if let Some(note) = budget_pricing_note(reports) {
-        println!("\n  {note}");
+        markup.line(&format!("\n  {note}"));
     }
 }
 
@@ -337,6 +323,7 @@ fn budget_pricing_note(reports: &[MonthlyBudgetReport]) -> Option<String> {
             report_cache_suffix(reports)
         )),
         PricingSource::Fallback => Some("Pricing source: fallback estimates.".to_string()),
+        PricingSource::Catalog => Some("Pricing source: local price catalog.".to_string()),
         PricingSource::Recorded => Some("Pricing source: recorded provider cost.".to_string()),
         PricingSource::Unknown => Some("Pricing source: unknown unpriced models.".to_string()),
         PricingSource::Mixed => Some(format!(
@@ -368,6 +355,7 @@ fn report_cache_suffix(reports: &[MonthlyBudgetReport]) -> String {
 mod tests {
     use super::*;
     use crate::core::Stats;
+    use crate::output::json::period_json_rows;
 
     fn make_day_stats() -> HashMap<String, DayStats> {
         let mut stats = HashMap::new();
@@ -408,7 +396,7 @@ mod tests {
     }
 
     #[test]
-    fn add_monthly_budget_to_json_attaches_budget_object() {
+    fn attach_monthly_budgets_sets_budget_of_matching_month() {
         let report = budget_report(
             "2026-02".to_string(),
             4.5,
@@ -417,11 +405,26 @@ mod tests {
             PricingSource::Fallback,
             &PricingDb::default(),
         );
-        let json = r#"[{"month":"2026-02","cost":4.5}]"#;
-        let output = add_monthly_budget_to_json(json, &[report]);
-        let parsed: Value = serde_json::from_str(&output).unwrap();
+        let mut day_stats = HashMap::new();
+        day_stats.insert("2026-02-03".to_string(), DayStats::default());
+        day_stats.insert("2026-03-03".to_string(), DayStats::default());
+        let mut rows = period_json_rows(
+            &day_stats,
+            Period::Month,
+            &PricingDb::default(),
+            SortOrder::Asc,
+            false,
+            true,
+            true,
+            None,
+            None,
+            CostDisplayMode::Total,
+        );
+        attach_monthly_budgets(&mut rows, &[report]);
+        let parsed = serde_json::to_value(&rows).unwrap();
 
         assert_eq!(parsed[0]["budget"]["status"], "over_budget");
         assert_eq!(parsed[0]["budget"]["days_elapsed"], 10);
+        assert!(parsed[1].get("budget").is_none());
     }
 }
@@ -0,0 +1,281 @@
+use std::borrow::Cow;
+
+use crate::core::{DailyModels, ProjectStats, SessionStats};
+use crate::output::command_json::{ClaudeHookContextJson, ClaudeHookJson, ClaudeHookUsageJson};
+use crate::output::format::{Locale, format_compact, format_cost};
+use crate::output::period::span_currency;
+use crate::output::schema::{SCHEMA_VERSION, cost_amount};
+use crate::pricing::{CostDisplayMode, CurrencyConverter, PricingDb, sum_display_model_costs};
+
+use super::statusline::{
+    ActiveBlock, block_cost, block_currency, block_json, format_duration_minutes,
+};
+
+/// Tokens occupying the model's context window after the latest assistant turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ContextUsage {
+    pub(crate) used_tokens: i64,
+    pub(crate) limit_tokens: i64,
+}
+
+impl ContextUsage {
+    pub(crate) fn used_pct(self) -> f64 {
+        if self.limit_tokens <= 0 {
+            return 0.0;
+        }
+        self.used_tokens as f64 / self.limit_tokens as f64 * 100.0
+    }
+}
+
+/// Everything the Claude Code statusLine hook shows for the running session.
+pub(crate) struct ClaudeHookStatus<'a> {
+    pub(crate) session_id: &'a str,
+    pub(crate) model: &'a str,
+    pub(crate) project: &'a str,
+    /// The current session's whole transcript, including days before today;
+    /// `None` before its first response.
+    pub(crate) session: Option<&'a SessionStats>,
+    /// The current project, limited to today.
+    pub(crate) today: Option<&'a ProjectStats>,
+    pub(crate) block: Option<&'a ActiveBlock>,
+    pub(crate) context: Option<ContextUsage>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ClaudeHookOptions<'a> {
+    pub(crate) locale: Locale,
+    pub(crate) currency: Option<&'a CurrencyConverter>,
+}
+
+const COST_MODE: CostDisplayMode = CostDisplayMode::Total;
+
+/// Converter for the session or today's cost at the rates of their days.
+fn days_currency<'a>(
+    days: Option<&DailyModels>,
+    pricing_db: &PricingDb,
+    currency: Option<&'a CurrencyConverter>,
+) -> Option<Cow<'a, CurrencyConverter>> {
+    span_currency(currency, days.into_iter().flatten(), pricing_db, COST_MODE)
+}
+
+fn session_cost(status: &ClaudeHookStatus<'_>, pricing_db: &PricingDb) -> f64 {
+    status.session.map_or(0.0, |session| {
+        sum_display_model_costs(&session.models, pricing_db, COST_MODE)
+    })
+}
+
+fn today_cost(status: &ClaudeHookStatus<'_>, pricing_db: &PricingDb) -> f64 {
+    status.today.map_or(0.0, |project| {
+        sum_display_model_costs(&project.models, pricing_db, COST_MODE)
+    })
+}
+
+/// Render `"Opus | myapp | Session: $X | Today: $Y | Block: $Z (2h13m left) | Ctx: 45% (90.0K/200.0K)"`.
+/// The block and context parts are left out when unknown.
+pub(crate) fn render_claude_hook_line(
+    status: &ClaudeHookStatus<'_>,
+    pricing_db: &PricingDb,
+    options: ClaudeHookOptions<'_>,
+) -> String {
+    let currency = options.currency;
+    let session_currency = days_currency(
+        status.session.map(|session| &session.days),
+        pricing_db,
+        currency,
+    );
+    let today_currency = days_currency(
+        status.today.map(|project| &project.days),
+        pricing_db,
+        currency,
+    );
+    let mut parts: Vec<String> = [status.model, status.project]
+        .into_iter()
+        .filter(|label| !label.is_empty())
+        .map(str::to_string)
+        .collect();
+    parts.push(format!(
+        "Session: {}",
+        format_cost(
+            session_cost(status, pricing_db),
+            session_currency.as_deref(),
+            options.locale
+        )
+    ));
+    parts.push(format!(
+        "Today: {}",
+        format_cost(
+            today_cost(status, pricing_db),
+            today_currency.as_deref(),
+            options.locale
+        )
+    ));
+    if let Some(block) = status.block {
+        parts.push(format!(
+            "Block: {} ({} left)",
+            format_cost(
+                block_cost(block, pricing_db, COST_MODE),
+                block_currency(block, pricing_db, COST_MODE, currency).as_deref(),
+                options.locale
+            ),
+            format_duration_minutes(block.remaining_minutes)
+        ));
+    }
+    if let Some(context) = status.context {
+        parts.push(format!(
+            "Ctx: {:.0}% ({}/{})",
+            context.used_pct(),
+            format_compact(context.used_tokens, options.locale),
+            format_compact(context.limit_tokens, options.locale)
+        ));
+    }
+    parts.join(" | ")
+}
+
+pub(crate) fn claude_hook_json(
+    status: &ClaudeHookStatus<'_>,
+    pricing_db: &PricingDb,
+    options: ClaudeHookOptions<'_>,
+) -> ClaudeHookJson {
+    let currency = options.currency;
+    ClaudeHookJson {
+        schema_version: SCHEMA_VERSION,
+        source: "claude",
+        session_id: status.session_id.to_string(),
+        model: status.model.to_string(),
+        project: status.project.to_string(),
+        session: ClaudeHookUsageJson {
+            total_tokens: status
+                .session
+                .map_or(0, |session| session.stats.total_tokens()),
+            cost: cost_amount(
+                session_cost(status, pricing_db),
+                days_currency(
+                    status.session.map(|session| &session.days),
+                    pricing_db,
+                    currency,
+                )
+                .as_deref(),
+            ),
+        },
+        today: ClaudeHookUsageJson {
+            total_tokens: status
+                .today
+                .map_or(0, |project| project.stats.total_tokens()),
+            cost: cost_amount(
+                today_cost(status, pricing_db),
+                days_currency(
+                    status.today.map(|project| &project.days),
+                    pricing_db,
+                    currency,
+                )
+                .as_deref(),
+            ),
+        },
+        block: status
+            .block
+            .map(|block| block_json(block, pricing_db, COST_MODE, currency)),
+        context: status.context.map(|context| ClaudeHookContextJson {
+            used_tokens: context.used_tokens,
+            limit_tokens: context.limit_tokens,
+            used_pct: (context.used_pct() * 100.0).round() / 100.0,
+        }),
+        line: render_claude_hook_line(status, pricing_db, options),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::core::{BlockStats, Stats};
+
+    // `sonnet-4` resolves via the built-in fallback even with an empty PricingDb.
+    fn pricing_db() -> PricingDb {
+        PricingDb::default()
+    }
+
+    fn models(input_tokens: i64) -> HashMap<String, Stats> {
+        let mut models = HashMap::new();
+        models.insert(
+            "sonnet-4".to_string(),
+            Stats {
+                input_tokens,
+                count: 1,
+                ..Default::default()
+            },
+        );
+        models
+    }
+
+    fn options() -> ClaudeHookOptions<'static> {
+        ClaudeHookOptions {
+            locale: Locale::default(),
+            currency: None,
+        }
+    }
+
+    #[test]
+    fn line_shows_session_today_block_and_context() {
+        let session = SessionStats {
+            session_id: "abc".to_string(),
+            models: models(1_000_000),
+            ..Default::default()
+        };
+        let today = ProjectStats {
+            project_path: "-work-myapp".to_string(),
+            project_name: "work-myapp".to_string(),
+            session_count: 2,
+            stats: Stats::default(),
+            models: models(2_000_000),
+            days: HashMap::new(),
+        };
+        let block = ActiveBlock {
+            block: BlockStats {
+                models: models(3_000_000),
+                ..Default::default()
+            },
+            remaining_minutes: 133,
+        };
+        let status = ClaudeHookStatus {
+            session_id: "abc",
+            model: "Sonnet 4",
+            project: "myapp",
+            session: Some(&session),
+            today: Some(&today),
+            block: Some(&block),
+            context: Some(ContextUsage {
+                used_tokens: 90_000,
+                limit_tokens: 200_000,
+            }),
+        };
+
+        assert_eq!(
+            render_claude_hook_line(&status, &pricing_db(), options()),
+            "Sonnet 4 | myapp | Session: $3.00 | Today: $6.00 | Block: $9.00 (2h13m left) | Ctx: 45% (90.0K/200.0K)"
+        );
+    }
+
+    #[test]
+    fn line_and_json_tolerate_a_session_without_usage_yet() {
+        let status = ClaudeHookStatus {
+            session_id: "new",
+            model: "",
+            project: "myapp",
+            session: None,
+            today: None,
+            block: None,
+            context: None,
+        };
+
+        assert_eq!(
+            render_claude_hook_line(&status, &pricing_db(), options()),
+            "myapp | Session: $0.00 | Today: $0.00"
+        );
+        let value =
+            serde_json::to_value(claude_hook_json(&status, &pricing_db(), options())).unwrap();
+        assert_eq!(value["session"]["cost"], 0.0);
+        assert!(value["block"].is_null());
+        assert!(value["context"].is_null());
+    }
+}
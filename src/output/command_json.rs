@@ -0,0 +1,638 @@
+//! Typed JSON of the commands outside the usage reports: `tools`, `top`,
+//! `quota`, `statusline`, `endpoints`, `agents`, `files`, `audit pricing`,
+//! `pricing`, `session show`, `session --context`, `notify`, `sources`, and
+//! `statusline --claude-hook`.
+//!
+//! Like the usage rows in [`super::schema`], each report carries
+//! `schema_version` (on the object, or on every row of an array) and its
+//! JSON Schema is printed by `ccstats schema <report>`.
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::core::{ContextStats, DirectoryStats, FileStats, RiskyCommand};
+use crate::pricing::PriceRates;
+
+use super::schema::{BudgetJson, CostJson, DataQualityJson, PricingJson, TokensJson};
+
+/// Column a `tools --group-by` row is grouped by, keyed by the grouping.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ToolGroupJson {
+    /// Source name (`tools` across every source).
+    Source(String),
+    /// Tool category (`--group-by category`).
+    Category(String),
+    /// Project name (`--group-by project`).
+    Project(String),
+    /// `YYYY-MM-DD` (`--group-by day`).
+    Date(String),
+    /// MCP server, or `builtin` (`--group-by server`).
+    Server(String),
+}
+
+impl ToolGroupJson {
+    /// Group value under the JSON key `label`.
+    pub(crate) fn new(label: &str, value: String) -> Self {
+        match label {
+            "source" => Self::Source(value),
+            "category" => Self::Category(value),
+            "project" => Self::Project(value),
+            "server" => Self::Server(value),
+            _ => Self::Date(value),
+        }
+    }
+}
+
+/// `tools --json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ToolsJson {
+    pub(crate) schema_version: u32,
+    pub(crate) tools: Vec<ToolJson>,
+    /// Tool calls in the range.
+    pub(crate) total: u64,
+    /// `tool`, `source`, `category`, `project`, `date`, or `server`.
+    pub(crate) group_by: &'static str,
+    pub(crate) input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)] // Absent without cost, `null` when unpriced.
+    pub(crate) cost: Option<Option<f64>>,
+}
+
+/// One tool, or one tool within a group.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ToolJson {
+    pub(crate) name: String,
+    #[serde(flatten)]
+    pub(crate) group: Option<ToolGroupJson>,
+    pub(crate) calls: u64,
+    /// Share of all tool calls, in percent.
+    pub(crate) percentage: f64,
+    /// Calls whose result was logged.
+    pub(crate) results: u64,
+    pub(crate) avg_result_bytes: Option<u64>,
+    pub(crate) input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    /// `YYYY-MM-DD` of the last call (`--group-by server`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) last_used: Option<Option<String>>,
+    /// Calls per day (`--group-by server`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) daily: Option<Vec<ToolDayJson>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) cost: Option<Option<f64>>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ToolDayJson {
+    pub(crate) date: String,
+    pub(crate) calls: u64,
+}
+
+/// `tools commands --json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ToolCommandsJson {
+    pub(crate) schema_version: u32,
+    pub(crate) commands: Vec<ToolCommandJson>,
+    pub(crate) risky: Vec<RiskyCommand>,
+    /// Shell tool calls that carried a command.
+    pub(crate) total: u64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ToolCommandJson {
+    pub(crate) project: String,
+    pub(crate) command: String,
+    pub(crate) runs: u64,
+    /// Share of the project's command runs, in percent.
+    pub(crate) percentage: f64,
+}
+
+/// `top --json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct TopJson {
+    pub(crate) schema_version: u32,
+    /// `model` or `project`.
+    pub(crate) dimension: &'static str,
+    pub(crate) limit: usize,
+    pub(crate) displayed: usize,
+    pub(crate) total_rows: usize,
+    /// `cost`, or `tokens` when no row has a cost.
+    pub(crate) share_basis: &'static str,
+    pub(crate) entries: Vec<TopEntryJson>,
+}
+
+/// One ranked model or project.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct TopEntryJson {
+    pub(crate) rank: usize,
+    pub(crate) name: String,
+    /// Calls for models, sessions for projects.
+    pub(crate) count: i64,
+    pub(crate) input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) cache_creation: i64,
+    pub(crate) cache_read: i64,
+    pub(crate) cache_hit_rate: Option<f64>,
+    pub(crate) reasoning_tokens: i64,
+    pub(crate) total_tokens: i64,
+    pub(crate) share_percent: f64,
+    /// Cost in USD; `null` when unpriced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) cost_usd: Option<Option<f64>>,
+    #[serde(flatten)]
+    pub(crate) pricing: Option<PricingJson>,
+    /// Cost formatted in `--currency`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) cost_local: Option<String>,
+    /// `real`, `estimated_proxy`, or `mixed`, when any row has an estimate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) cost_kind: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) estimated_cost_usd: Option<Option<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) estimated_cost_local: Option<String>,
+}
+
+/// A Codex quota window as observed in the latest rate-limit snapshot.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct QuotaWindowJson {
+    /// `weekly`, or the window length such as `5h`.
+    pub(crate) window: String,
+    pub(crate) window_minutes: i64,
+    pub(crate) used_pct: f64,
+    pub(crate) remaining_pct: f64,
+    pub(crate) projected_pct_at_reset: f64,
+    /// `on_track`, `watch`, `likely_exhausted`, or `exhausted`.
+    pub(crate) status: &'static str,
+    pub(crate) observed_at: String,
+    pub(crate) resets_at: String,
+    pub(crate) estimated_depletion_at: Option<String>,
+}
+
+/// `quota --json`: the weekly window at the top level, and every current
+/// window under `windows`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct QuotaJson {
+    pub(crate) schema_version: u32,
+    /// Always `codex`.
+    pub(crate) source: &'static str,
+    #[serde(flatten)]
+    pub(crate) weekly: QuotaWindowJson,
+    pub(crate) windows: Vec<QuotaWindowJson>,
+    /// API-equivalent value of the weekly window; `null` with
+    /// `value_estimate_error` when it could not be computed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) value_estimate: Option<Option<QuotaValueJson>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) value_estimate_error: Option<Option<String>>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct QuotaValueJson {
+    /// Always `api_equivalent`.
+    pub(crate) kind: &'static str,
+    pub(crate) observed_cost_usd: f64,
+    pub(crate) estimated_weekly_value_usd: f64,
+    pub(crate) observed_tokens: i64,
+    pub(crate) estimated_weekly_tokens: f64,
+    pub(crate) window_started_at: String,
+    pub(crate) valid_entries: i64,
+    pub(crate) dedup_skipped_entries: i64,
+}
+
+/// `quota --history --json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct QuotaHistoryJson {
+    pub(crate) schema_version: u32,
+    /// Always `codex`.
+    pub(crate) source: &'static str,
+    pub(crate) windows: Vec<QuotaHistoryWindowJson>,
+    /// Rate-limit snapshots that could not be read.
+    pub(crate) invalid_records: usize,
+}
+
+/// One past or active quota window reconstructed from its snapshots.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct QuotaHistoryWindowJson {
+    pub(crate) window: String,
+    pub(crate) window_minutes: i64,
+    pub(crate) started_at: String,
+    pub(crate) resets_at: String,
+    pub(crate) active: bool,
+    pub(crate) peak_pct: f64,
+    pub(crate) last_pct: f64,
+    pub(crate) exhausted: bool,
+    pub(crate) exhausted_at: Option<String>,
+    pub(crate) series: Vec<QuotaSampleJson>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct QuotaSampleJson {
+    pub(crate) observed_at: String,
+    pub(crate) used_pct: f64,
+}
+
+/// `statusline --json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct StatuslineJson {
+    pub(crate) schema_version: u32,
+    pub(crate) source: String,
+    #[serde(flatten)]
+    pub(crate) tokens: TokensJson,
+    #[serde(flatten)]
+    pub(crate) cost: CostJson,
+    /// Active 5-hour billing block; `null` when none was loaded.
+    pub(crate) block: Option<StatuslineBlockJson>,
+    /// Codex weekly quota; `null` when none was loaded.
+    pub(crate) quota: Option<QuotaWindowJson>,
+    /// The rendered statusline.
+    pub(crate) line: String,
+    pub(crate) formatted: StatuslineFormattedJson,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) data_quality: Option<DataQualityJson>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct StatuslineBlockJson {
+    pub(crate) block_start: String,
+    pub(crate) block_end: String,
+    pub(crate) remaining_minutes: i64,
+    pub(crate) total_tokens: i64,
+    pub(crate) cost: Option<f64>,
+}
+
+/// `statusline --claude-hook --json`: the status of the running Claude Code
+/// session.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ClaudeHookJson {
+    pub(crate) schema_version: u32,
+    /// Always `claude`.
+    pub(crate) source: &'static str,
+    pub(crate) session_id: String,
+    pub(crate) model: String,
+    pub(crate) project: String,
+    /// The current session across all dates.
+    pub(crate) session: ClaudeHookUsageJson,
+    /// The current project, limited to today.
+    pub(crate) today: ClaudeHookUsageJson,
+    /// Active 5-hour billing block; `null` when there is none.
+    pub(crate) block: Option<StatuslineBlockJson>,
+    /// Context window use of the latest main-thread turn; `null` before the
+    /// first one.
+    pub(crate) context: Option<ClaudeHookContextJson>,
+    /// The rendered status line.
+    pub(crate) line: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ClaudeHookUsageJson {
+    pub(crate) total_tokens: i64,
+    pub(crate) cost: Option<f64>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ClaudeHookContextJson {
+    pub(crate) used_tokens: i64,
+    pub(crate) limit_tokens: i64,
+    pub(crate) used_pct: f64,
+}
+
+/// Totals as the statusline shows them.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct StatuslineFormattedJson {
+    pub(crate) cost: String,
+    pub(crate) input: String,
+    pub(crate) output: String,
+    pub(crate) reasoning: String,
+}
+
+/// Row of `sources` output, one per source plus the `all` pseudo-source.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct SourceJson {
+    pub(crate) schema_version: u32,
+    pub(crate) name: &'static str,
+    pub(crate) display_name: &'static str,
+    pub(crate) aliases: &'static [&'static str],
+    pub(crate) capabilities: CapabilitiesJson,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct CapabilitiesJson {
+    pub(crate) has_projects: bool,
+    pub(crate) has_billing_blocks: bool,
+    pub(crate) has_reasoning_tokens: bool,
+    pub(crate) has_cache_creation: bool,
+    pub(crate) has_cache_read: bool,
+    pub(crate) needs_dedup: bool,
+}
+
+/// Row of `endpoints` output.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct EndpointJson {
+    pub(crate) schema_version: u32,
+    /// `native` or `proxy`.
+    pub(crate) endpoint: &'static str,
+    pub(crate) calls: i64,
+    pub(crate) input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) cache_creation_tokens: i64,
+    pub(crate) cache_read_tokens: i64,
+    pub(crate) total_tokens: i64,
+    pub(crate) avg_input_per_call: i64,
+    pub(crate) models: Vec<String>,
+    #[serde(flatten)]
+    pub(crate) cost: Option<CostJson>,
+}
+
+/// Row of `agents` output: one session's main-thread and subagent usage.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct AgentSessionJson {
+    pub(crate) schema_version: u32,
+    pub(crate) session_id: String,
+    pub(crate) project: String,
+    pub(crate) project_path: String,
+    pub(crate) last_timestamp: String,
+    pub(crate) main: AgentUsageJson,
+    pub(crate) subagents: AgentUsageJson,
+    /// Subagents' share of the session's cost, or of its tokens without cost.
+    pub(crate) subagent_share_pct: f64,
+    pub(crate) subagent_types: Vec<SubagentTypeJson>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct AgentUsageJson {
+    pub(crate) calls: i64,
+    pub(crate) total_tokens: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) cost: Option<Option<f64>>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct SubagentTypeJson {
+    /// `null` for subagents whose type was not logged.
+    pub(crate) subagent_type: Option<String>,
+    #[serde(flatten)]
+    pub(crate) usage: AgentUsageJson,
+}
+
+/// `files --json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct FilesJson {
+    pub(crate) schema_version: u32,
+    pub(crate) files: Vec<FileStats>,
+    pub(crate) directories: Vec<DirectoryStats>,
+    /// File-targeting tool calls.
+    pub(crate) total: u64,
+}
+
+/// `audit pricing --json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PricingAuditJson {
+    pub(crate) schema_version: u32,
+    pub(crate) models: Vec<PricingAuditModelJson>,
+    pub(crate) total_tokens: i64,
+    pub(crate) unpriced_tokens: i64,
+    pub(crate) unpriced_pct: f64,
+    pub(crate) fallback_counts_as_unpriced: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PricingAuditModelJson {
+    pub(crate) model: String,
+    /// How the model was priced, as in `pricing_source`.
+    pub(crate) resolution: &'static str,
+    pub(crate) matched_key: Option<String>,
+    pub(crate) fallback_available: bool,
+    pub(crate) unpriced: bool,
+    pub(crate) calls: i64,
+    pub(crate) total_tokens: i64,
+    pub(crate) token_share_pct: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) cost: Option<Option<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) cost_share_pct: Option<Option<f64>>,
+}
+
+/// Prices in USD per million tokens.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct RatesJson {
+    pub(crate) input: f64,
+    pub(crate) output: f64,
+    pub(crate) reasoning_output: f64,
+    pub(crate) cache_read: f64,
+    pub(crate) cache_write: f64,
+    pub(crate) cache_write_1h: f64,
+}
+
+impl From<&PriceRates> for RatesJson {
+    fn from(rates: &PriceRates) -> Self {
+        Self {
+            input: rates.input,
+            output: rates.output,
+            reasoning_output: rates.reasoning_output,
+            cache_read: rates.cache_read,
+            cache_write: rates.cache_write,
+            cache_write_1h: rates.cache_write_1h,
+        }
+    }
+}
+
+/// `pricing show --json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PriceQuoteJson {
+    pub(crate) schema_version: u32,
+    pub(crate) model: String,
+    pub(crate) matched_key: Option<String>,
+    pub(crate) pricing_source: &'static str,
+    pub(crate) pricing_catalog: Option<String>,
+    /// `null` when no catalog prices the model.
+    pub(crate) rates_usd_per_million: Option<RatesJson>,
+    pub(crate) long_context_threshold_tokens: Option<i64>,
+    pub(crate) long_context_rates_usd_per_million: Option<RatesJson>,
+}
+
+/// Row of `pricing list` output.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct CatalogEntryJson {
+    pub(crate) schema_version: u32,
+    pub(crate) model: String,
+    pub(crate) pricing_catalog: String,
+    pub(crate) pricing_source: &'static str,
+    pub(crate) rates_usd_per_million: RatesJson,
+    pub(crate) long_context_threshold_tokens: Option<i64>,
+    pub(crate) long_context_rates_usd_per_million: Option<RatesJson>,
+}
+
+/// Row of `pricing refresh` output, one per `LiteLLM` provider.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PricingRefreshJson {
+    pub(crate) schema_version: u32,
+    pub(crate) pricing_catalog: String,
+    /// Models in the downloaded price list.
+    pub(crate) models: usize,
+    pub(crate) previous_cache_age_seconds: Option<u64>,
+    pub(crate) cache_path: Option<String>,
+    /// Why the cache could not be written, if it could not.
+    pub(crate) cache_error: Option<String>,
+}
+
+/// Row of `pricing diff` output, one per `LiteLLM` provider.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PricingDiffJson {
+    pub(crate) schema_version: u32,
+    pub(crate) pricing_catalog: String,
+    pub(crate) cache_age_seconds: Option<u64>,
+    pub(crate) models_compared: usize,
+    pub(crate) changes: Vec<PriceChangeJson>,
+}
+
+/// A price that differs between the cached and the fresh price list.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PriceChangeJson {
+    pub(crate) model: String,
+    pub(crate) field: &'static str,
+    /// `null` when the model is missing from the cache.
+    pub(crate) cached_usd_per_million: Option<f64>,
+    /// `null` when the model is missing from the fresh list.
+    pub(crate) fresh_usd_per_million: Option<f64>,
+}
+
+/// `session show --json`: one session's requests in time order.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct SessionTimelineJson {
+    pub(crate) schema_version: u32,
+    pub(crate) session_id: String,
+    pub(crate) project: String,
+    pub(crate) project_path: String,
+    pub(crate) requests: usize,
+    pub(crate) peak_context_tokens: i64,
+    pub(crate) turns: Vec<TimelineTurnJson>,
+    pub(crate) segments: Vec<TimelineSegmentJson>,
+    pub(crate) idle_gaps: Vec<IdleGapJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) cost: Option<Option<f64>>,
+}
+
+/// One request of `session show`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct TimelineTurnJson {
+    pub(crate) timestamp: String,
+    pub(crate) model: String,
+    /// `main` or `subagent`.
+    pub(crate) thread: &'static str,
+    pub(crate) input_tokens: i64,
+    pub(crate) cache_creation_tokens: i64,
+    pub(crate) cache_read_tokens: i64,
+    pub(crate) output_tokens: i64,
+    /// Prompt size: input plus cache writes and reads.
+    pub(crate) context_tokens: i64,
+    pub(crate) stop_reason: Option<String>,
+    pub(crate) tools: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) cost: Option<Option<f64>>,
+    /// Cost of the session up to and including this request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)]
+    pub(crate) cumulative_cost: Option<Option<f64>>,
+}
+
+/// Line of `session show --ndjson`: a request with its session.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct SessionEntryJson {
+    pub(crate) schema_version: u32,
+    pub(crate) session_id: String,
+    #[serde(flatten)]
+    pub(crate) turn: TimelineTurnJson,
+}
+
+/// Consecutive requests made by the same thread.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct TimelineSegmentJson {
+    /// `main` or `subagent`.
+    pub(crate) thread: &'static str,
+    pub(crate) start: String,
+    pub(crate) end: String,
+    pub(crate) requests: usize,
+}
+
+/// Pause between two requests of `session show`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct IdleGapJson {
+    pub(crate) start: String,
+    pub(crate) end: String,
+    pub(crate) minutes: i64,
+}
+
+/// Row of `session --context` output.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct SessionContextJson {
+    pub(crate) schema_version: u32,
+    pub(crate) session_id: String,
+    pub(crate) project: String,
+    pub(crate) project_path: String,
+    pub(crate) first_timestamp: String,
+    pub(crate) last_timestamp: String,
+    pub(crate) context: ContextStats,
+    /// Share of turns at or above `context.threshold`, in percent.
+    pub(crate) above_threshold_pct: f64,
+}
+
+/// `notify --json`, also the payload sent to `--hook-command` and
+/// `--webhook`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct NotifyPayloadJson<'a> {
+    pub(crate) schema_version: u32,
+    /// Always `status_changed`.
+    pub(crate) event: &'static str,
+    pub(crate) generated_at: String,
+    pub(crate) changes: Vec<NotifyChangeJson<'a>>,
+}
+
+/// A watched item whose status changed since the last check.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct NotifyChangeJson<'a> {
+    /// `codex_quota` or `monthly_budget`.
+    pub(crate) kind: &'static str,
+    /// `codex_quota:<window>` or `monthly_budget:<source>`.
+    pub(crate) key: &'a str,
+    /// `null` the first time the item is seen.
+    pub(crate) previous_status: Option<&'a str>,
+    pub(crate) status: &'static str,
+    pub(crate) previous_period: Option<&'a str>,
+    /// Quota reset time or budget month the status belongs to.
+    pub(crate) period: &'a str,
+    pub(crate) details: &'a NotifyDetailsJson,
+}
+
+/// Current state of the watched item, shaped by its `kind`.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub(crate) enum NotifyDetailsJson {
+    CodexQuota(QuotaWindowJson),
+    MonthlyBudget(NotifyBudgetJson),
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct NotifyBudgetJson {
+    #[serde(flatten)]
+    pub(crate) budget: BudgetJson,
+    /// Source name, or `all` across every source.
+    pub(crate) source: &'static str,
+    /// `YYYY-MM`.
+    pub(crate) month: String,
+    pub(crate) currency: String,
+}
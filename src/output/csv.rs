@@ -6,7 +6,9 @@ use crate::cli::SortOrder;
 use crate::core::{BlockStats, DataQuality, DayStats, ProjectStats, SessionStats};
 use crate::output::budget::{MonthlyBudgetOptions, MonthlyBudgetReport, monthly_budget_reports};
 use crate::output::format::{cache_hit_rate_csv_value, compare_cost, csv_escape};
-use crate::output::period::{Period, aggregate_day_stats_by_period};
+use crate::output::period::{
+    Period, PeriodRates, aggregate_day_stats_by_period, row_currency, span_currency,
+};
 use crate::output::pricing_meta;
 use crate::pricing::{
     CostDisplayMode, CurrencyConverter, PricingDb, PricingSource, calculate_display_cost,
@@ -66,12 +68,14 @@ pub(crate) fn output_period_csv_with_quality(
     }
     let label = period.label();
     let mut out = String::new();
+    let rates = PeriodRates::new(day_stats, period, currency, pricing_db, cost_mode);
     let ctx = PeriodCsvContext {
         label,
         pricing_db,
         show_cost,
         supports_cache_read,
         currency,
+        rates: rates.as_ref(),
         include_cost_kind: period_csv_includes_cost_kind(&rows, breakdown, show_cost),
         pricing_source: pricing_source_for_model_maps(
             rows.iter().map(|(_, stats)| &stats.models),
@@ -95,11 +99,19 @@ struct PeriodCsvContext<'a> {
     show_cost: bool,
     supports_cache_read: bool,
     currency: Option<&'a CurrencyConverter>,
+    /// Per-row converters when the currency has daily rates.
+    rates: Option<&'a PeriodRates>,
     include_cost_kind: bool,
     pricing_source: crate::pricing::PricingSource,
     cost_mode: CostDisplayMode,
 }
 
+impl PeriodCsvContext<'_> {
+    fn row_currency(&self, key: &str) -> Option<&CurrencyConverter> {
+        row_currency(self.rates, key, self.currency)
+    }
+}
+
 fn period_csv_includes_cost_kind(
     rows: &[(&String, &DayStats)],
     breakdown: bool,
@@ -144,6 +156,7 @@ fn write_period_csv_breakdown(
     for &(key, stats) in rows {
         let mut models: Vec<_> = stats.models.iter().collect();
         models.sort_by_key(|(name, _)| name.as_str());
+        let currency = ctx.row_currency(key);
         for (model, model_stats) in &models {
             let _ = write!(
                 out,
@@ -161,7 +174,7 @@ fn write_period_csv_breakdown(
             if ctx.show_cost {
                 let cost =
                     calculate_display_cost(model_stats, model, ctx.pricing_db, ctx.cost_mode);
-                let _ = write!(out, ",{}", csv_cost(cost, ctx.currency));
+                let _ = write!(out, ",{}", csv_cost(cost, currency));
                 if ctx.include_cost_kind {
                     let estimated_cost =
                         calculate_estimated_proxy_cost(model_stats, model, ctx.pricing_db);
@@ -169,7 +182,7 @@ fn write_period_csv_breakdown(
                         out,
                         ",{},{}",
                         model_stats.cost_kind().as_str(),
-                        csv_cost(estimated_cost, ctx.currency)
+                        csv_cost(estimated_cost, currency)
                     );
                 }
                 pricing_meta::append_model_csv_fields(
@@ -198,6 +211,7 @@ fn write_period_csv_standard(
     write_period_cost_header(out, ctx);
 
     for &(key, stats) in rows {
+        let currency = ctx.row_currency(key);
         let _ = write!(
             out,
             "{},{},{},{},{},{},{},{}",
@@ -212,14 +226,14 @@ fn write_period_csv_standard(
         );
         if ctx.show_cost {
             let cost = sum_display_model_costs(&stats.models, ctx.pricing_db, ctx.cost_mode);
-            let _ = write!(out, ",{}", csv_cost(cost, ctx.currency));
+            let _ = write!(out, ",{}", csv_cost(cost, currency));
             if ctx.include_cost_kind {
                 let estimated_cost = sum_estimated_proxy_model_costs(&stats.models, ctx.pricing_db);
                 let _ = write!(
                     out,
                     ",{},{}",
                     model_cost_kind(&stats.models).as_str(),
-                    csv_cost(estimated_cost, ctx.currency)
+                    csv_cost(estimated_cost, currency)
                 );
             }
             pricing_meta::append_csv_fields(
@@ -260,7 +274,7 @@ fn csv_float(value: f64) -> String {
     }
 }
 
-fn csv_cost(usd: f64, currency: Option<&CurrencyConverter>) -> String {
+pub(super) fn csv_cost(usd: f64, currency: Option<&CurrencyConverter>) -> String {
     let amount = currency.map_or(usd, |conv| conv.convert(usd));
     csv_float(amount)
 }
@@ -470,6 +484,8 @@ pub(crate) fn output_session_csv(
         );
         if show_cost {
             let cost = sum_model_costs(&s.models, pricing_db);
+            let row_currency = span_currency(currency, &s.days, pricing_db, CostDisplayMode::Total);
+            let currency = row_currency.as_deref();
             let _ = write!(out, ",{}", csv_cost(cost, currency));
             if include_cost_kind {
                 let estimated_cost = sum_estimated_proxy_model_costs(&s.models, pricing_db);
@@ -552,6 +568,8 @@ pub(crate) fn output_project_csv(
         );
         if show_cost {
             let cost = sum_model_costs(&p.models, pricing_db);
+            let row_currency = span_currency(currency, &p.days, pricing_db, CostDisplayMode::Total);
+            let currency = row_currency.as_deref();
             let _ = write!(out, ",{}", csv_cost(cost, currency));
             if include_cost_kind {
                 let estimated_cost = sum_estimated_proxy_model_costs(&p.models, pricing_db);
@@ -626,6 +644,8 @@ pub(crate) fn output_block_csv(
         );
         if show_cost {
             let cost = sum_model_costs(&b.models, pricing_db);
+            let row_currency = span_currency(currency, &b.days, pricing_db, CostDisplayMode::Total);
+            let currency = row_currency.as_deref();
             let _ = write!(out, ",{}", csv_cost(cost, currency));
             if include_cost_kind {
                 let estimated_cost = sum_estimated_proxy_model_costs(&b.models, pricing_db);
@@ -112,6 +112,50 @@ fn period_csv_converts_cost_when_currency_is_set() {
     );
 }
 
+#[test]
+fn period_csv_converts_each_day_at_its_own_rate() {
+    let mut day_stats = HashMap::new();
+    day_stats.insert(
+        "2025-01-01".to_string(),
+        make_day_stats(&[("sonnet", 1_000_000)]),
+    );
+    day_stats.insert(
+        "2025-01-02".to_string(),
+        make_day_stats(&[("sonnet", 1_000_000)]),
+    );
+    let converter = CurrencyConverter::from_history_for_test(
+        "CNY",
+        &[("2025-01-01", 7.0), ("2025-01-02", 8.0)],
+    );
+
+    let db = PricingDb::default();
+    let daily = output_period_csv(
+        &day_stats,
+        Period::Day,
+        &db,
+        SortOrder::Asc,
+        false,
+        true,
+        Some(&converter),
+    );
+    let lines: Vec<&str> = daily.lines().collect();
+    assert!(lines[1].contains(",73.500000,"), "{}", lines[1]);
+    assert!(lines[2].contains(",84.000000,"), "{}", lines[2]);
+
+    let weekly = output_period_csv(
+        &day_stats,
+        Period::Week,
+        &db,
+        SortOrder::Asc,
+        true,
+        true,
+        Some(&converter),
+    );
+    let lines: Vec<&str> = weekly.lines().collect();
+    assert!(lines[1].starts_with("2024-12-30,sonnet,"), "{}", lines[1]);
+    assert!(lines[1].contains(",157.500000,"), "{}", lines[1]);
+}
+
 #[test]
 fn period_csv_sort_desc() {
     let mut day_stats = HashMap::new();
@@ -149,6 +193,7 @@ fn session_csv_structure() {
             ..Default::default()
         },
         models: HashMap::new(),
+        ..Default::default()
     }];
 
     let db = PricingDb::default();
@@ -162,6 +207,38 @@ fn session_csv_structure() {
     assert!(lines[1].starts_with("abc-123,/home/user/project,"));
 }
 
+#[test]
+fn session_csv_converts_each_day_at_its_own_rate() {
+    let first = make_day_stats(&[("sonnet", 1_000_000)]);
+    let second = make_day_stats(&[("sonnet", 1_000_000)]);
+    let mut stats = first.stats.clone();
+    stats.add(&second.stats);
+    let sessions = vec![SessionStats {
+        session_key: "overnight".to_string(),
+        session_id: "overnight".to_string(),
+        first_timestamp: "2025-01-01T23:00:00Z".to_string(),
+        last_timestamp: "2025-01-02T01:00:00Z".to_string(),
+        models: HashMap::from([("sonnet".to_string(), stats.clone())]),
+        stats,
+        days: HashMap::from([
+            ("2025-01-01".to_string(), first.models),
+            ("2025-01-02".to_string(), second.models),
+        ]),
+        ..Default::default()
+    }];
+    let converter = CurrencyConverter::from_history_for_test(
+        "CNY",
+        &[("2025-01-01", 7.0), ("2025-01-02", 8.0)],
+    );
+
+    let db = PricingDb::default();
+    let csv = output_session_csv(&sessions, &db, SortOrder::Asc, true, true, Some(&converter));
+    let lines: Vec<&str> = csv.lines().collect();
+
+    // $10.50 on each day: 73.50 at 7.0 plus 84.00 at 8.0.
+    assert!(lines[1].contains(",157.500000,"), "{}", lines[1]);
+}
+
 #[test]
 fn session_csv_includes_reasoning_and_cache_tokens() {
     let sessions = vec![SessionStats {
@@ -180,6 +257,7 @@ fn session_csv_includes_reasoning_and_cache_tokens() {
             ..Default::default()
         },
         models: HashMap::new(),
+        ..Default::default()
     }];
 
     let db = PricingDb::default();
@@ -205,6 +283,7 @@ fn project_csv_structure() {
             ..Default::default()
         },
         models: HashMap::new(),
+        days: HashMap::new(),
     }];
 
     let db = PricingDb::default();
@@ -229,6 +308,7 @@ fn block_csv_structure() {
             ..Default::default()
         },
         models: HashMap::new(),
+        days: HashMap::new(),
     }];
 
     let db = PricingDb::default();
@@ -0,0 +1,843 @@
+//! Self-contained HTML dashboard for `ccstats report --html`.
+//!
+//! The report data is embedded as JSON and drawn by a small inline SVG chart
+//! script, so the page needs no network access when it is opened.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+
+use crate::consts::DATE_FORMAT;
+use crate::core::{BlockStats, DayStats, ProjectStats, Stats, ToolCall, aggregate_tools};
+use crate::output::markup::escape_html;
+use crate::output::period::{blended_currency, span_currency};
+use crate::pricing::{
+    CostDisplayMode, CurrencyConverter, PricingDb, calculate_cost, sum_model_costs,
+};
+
+/// Projects and tools beyond these ranks are left out of the charts.
+const TOP_PROJECTS: usize = 10;
+const TOP_TOOLS: usize = 15;
+
+/// Usage loaded from one source.
+pub(crate) struct DashboardSource {
+    pub(crate) label: &'static str,
+    pub(crate) day_stats: HashMap<String, DayStats>,
+    pub(crate) projects: Vec<ProjectStats>,
+    pub(crate) blocks: Vec<BlockStats>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DashboardOptions<'a> {
+    pub(crate) title: &'a str,
+    pub(crate) generated: &'a str,
+    pub(crate) show_cost: bool,
+    pub(crate) supports_cache_read: bool,
+    pub(crate) monthly_budget: Option<f64>,
+    pub(crate) as_of: NaiveDate,
+    pub(crate) currency: Option<&'a CurrencyConverter>,
+}
+
+#[derive(Debug, Serialize)]
+struct DashboardData {
+    title: String,
+    generated: String,
+    /// `"cost"` or `"tokens"`: the value every chart plots.
+    metric: &'static str,
+    currency_symbol: String,
+    /// Models ordered by their overall share, which fixes their colors.
+    models: Vec<String>,
+    days: Vec<DayPoint>,
+    month: MonthToDate,
+    sources: Vec<Slice>,
+    projects: Vec<ProjectSlice>,
+    blocks: Vec<BlockPoint>,
+    tools: Vec<ToolSlice>,
+}
+
+#[derive(Debug, Serialize)]
+struct Slice {
+    label: String,
+    cost: f64,
+    tokens: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct DayPoint {
+    date: String,
+    cost: f64,
+    tokens: i64,
+    cache_hit_rate: Option<f64>,
+    models: Vec<Slice>,
+}
+
+#[derive(Debug, Serialize)]
+struct MonthToDate {
+    month: String,
+    days_in_month: u32,
+    budget: Option<f64>,
+    /// Running totals for each day of the month up to the report date.
+    cumulative: Vec<Slice>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectSlice {
+    label: String,
+    source: &'static str,
+    sessions: usize,
+    cost: f64,
+    tokens: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct BlockPoint {
+    source: &'static str,
+    start: String,
+    end: String,
+    cost: f64,
+    tokens: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolSlice {
+    label: String,
+    calls: u64,
+}
+
+/// Render the dashboard page.
+pub(crate) fn render_dashboard(
+    sources: &[DashboardSource],
+    tool_calls: &[ToolCall],
+    pricing_db: &PricingDb,
+    options: DashboardOptions<'_>,
+) -> String {
+    let data = dashboard_data(sources, tool_calls, pricing_db, options);
+    let json = serde_json::to_string(&data).unwrap_or_else(|_| "{}".to_string());
+    let metric = if options.show_cost { "Cost" } else { "Tokens" };
+    let title = escape_html(options.title);
+
+    let mut page = String::with_capacity(json.len() + DASHBOARD_CSS.len() + CHART_JS.len() + 4096);
+    let _ = write!(
+        page,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>ccstats report: {title}</title>\n<style>\n{DASHBOARD_CSS}</style>\n</head>\n<body>\n\
+         <h1>ccstats report: {title}</h1>\n<p class=\"meta\" id=\"meta\">Generated {}</p>\n\
+         <div class=\"cards\" id=\"cards\"></div>\n",
+        escape_html(options.generated),
+    );
+    let month_heading = if options.monthly_budget.is_some() {
+        format!("{metric} month to date vs budget")
+    } else {
+        format!("{metric} month to date")
+    };
+    let sections = [
+        ("daily", format!("Daily {} by model", metric.to_lowercase())),
+        ("month", month_heading),
+        ("sources", "Source split".to_string()),
+        ("projects", "Top projects".to_string()),
+        ("cache", "Cache-hit rate".to_string()),
+        ("blocks", "5-hour blocks".to_string()),
+        ("tools", "Tool usage".to_string()),
+    ];
+    for (id, heading) in sections {
+        let _ = writeln!(
+            page,
+            "<section id=\"section-{id}\"><h2>{heading}</h2><div class=\"chart\" id=\"chart-{id}\"></div></section>"
+        );
+    }
+    // Inside a <script> element only `</` could end the block early, and
+    // `<` only appears inside JSON strings, where the escape is equivalent.
+    let _ = write!(
+        page,
+        "<script type=\"application/json\" id=\"ccstats-data\">{}</script>\n<script>\n{CHART_JS}</script>\n</body>\n</html>\n",
+        json.replace('<', "\\u003c"),
+    );
+    page
+}
+
+fn dashboard_data(
+    sources: &[DashboardSource],
+    tool_calls: &[ToolCall],
+    pricing_db: &PricingDb,
+    options: DashboardOptions<'_>,
+) -> DashboardData {
+    let mut merged: BTreeMap<&str, DayStats> = BTreeMap::new();
+    let mut source_slices = Vec::new();
+    for source in sources {
+        let mut total = DayStats::default();
+        for (date, day) in &source.day_stats {
+            for (model, stats) in &day.models {
+                total.add_stats(model.clone(), stats);
+                merged
+                    .entry(date)
+                    .or_default()
+                    .add_stats(model.clone(), stats);
+            }
+        }
+        if total.stats.total_tokens() > 0 {
+            source_slices.push(Slice {
+                label: source.label.to_string(),
+                cost: span_cost(
+                    &total.models,
+                    source
+                        .day_stats
+                        .iter()
+                        .map(|(date, day)| (date, &day.models)),
+                    pricing_db,
+                    options.currency,
+                ),
+                tokens: total.stats.total_tokens(),
+            });
+        }
+    }
+    sort_by_metric(&mut source_slices, options.show_cost);
+
+    let (days, models) = day_points(&merged, pricing_db, options);
+
+    let mut projects: Vec<ProjectSlice> = sources
+        .iter()
+        .flat_map(|source| {
+            source.projects.iter().map(|project| ProjectSlice {
+                label: project.project_name.clone(),
+                source: source.label,
+                sessions: project.session_count,
+                cost: span_cost(&project.models, &project.days, pricing_db, options.currency),
+                tokens: project.stats.total_tokens(),
+            })
+        })
+        .collect();
+    sort_by_metric(&mut projects, options.show_cost);
+    projects.truncate(TOP_PROJECTS);
+
+    let mut blocks: Vec<BlockPoint> = sources
+        .iter()
+        .flat_map(|source| {
+            source.blocks.iter().map(|block| BlockPoint {
+                source: source.label,
+                start: block.block_start.clone(),
+                end: block_end(block),
+                cost: span_cost(&block.models, &block.days, pricing_db, options.currency),
+                tokens: block.stats.total_tokens(),
+            })
+        })
+        .collect();
+    blocks.sort_by(|a, b| a.start.cmp(&b.start));
+
+    let mut tools: Vec<ToolSlice> = aggregate_tools(tool_calls)
+        .tools
+        .into_iter()
+        .map(|tool| ToolSlice {
+            label: tool.name,
+            calls: tool.calls,
+        })
+        .collect();
+    tools.truncate(TOP_TOOLS);
+
+    DashboardData {
+        title: options.title.to_string(),
+        generated: options.generated.to_string(),
+        metric: if options.show_cost { "cost" } else { "tokens" },
+        currency_symbol: options
+            .currency
+            .map_or_else(|| "$".to_string(), |conv| conv.symbol().to_string()),
+        models,
+        month: month_to_date(&days, options),
+        days,
+        sources: source_slices,
+        projects,
+        blocks,
+        tools,
+    }
+}
+
+/// One point per day with its per-model split, plus every model ordered by
+/// its share of the whole range.
+fn day_points(
+    merged: &BTreeMap<&str, DayStats>,
+    pricing_db: &PricingDb,
+    options: DashboardOptions<'_>,
+) -> (Vec<DayPoint>, Vec<String>) {
+    let mut model_totals: HashMap<String, Slice> = HashMap::new();
+    let days: Vec<DayPoint> = merged
+        .iter()
+        .map(|(date, day)| {
+            let currency = day_currency(options.currency, date);
+            let currency = currency.as_deref();
+            let mut models: Vec<Slice> = day
+                .models
+                .iter()
+                .map(|(model, stats)| Slice {
+                    label: model.clone(),
+                    cost: display_cost(calculate_cost(stats, model, pricing_db), currency),
+                    tokens: stats.total_tokens(),
+                })
+                .collect();
+            models.sort_by(|a, b| a.label.cmp(&b.label));
+            for slice in &models {
+                let total = model_totals
+                    .entry(slice.label.clone())
+                    .or_insert_with(|| Slice {
+                        label: slice.label.clone(),
+                        cost: 0.0,
+                        tokens: 0,
+                    });
+                total.cost += slice.cost;
+                total.tokens += slice.tokens;
+            }
+            DayPoint {
+                date: (*date).to_string(),
+                cost: display_cost(sum_model_costs(&day.models, pricing_db), currency),
+                tokens: day.stats.total_tokens(),
+                cache_hit_rate: day
+                    .stats
+                    .cache_hit_rate(options.supports_cache_read)
+                    .map(round2),
+                models,
+            }
+        })
+        .collect();
+    let mut model_totals: Vec<Slice> = model_totals.into_values().collect();
+    sort_by_metric(&mut model_totals, options.show_cost);
+    let models = model_totals.into_iter().map(|slice| slice.label).collect();
+    (days, models)
+}
+
+/// Running totals for the month that contains the report date.
+fn month_to_date(days: &[DayPoint], options: DashboardOptions<'_>) -> MonthToDate {
+    let as_of = options.as_of;
+    let first = as_of.with_day(1).unwrap_or(as_of);
+    let next_month = first
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(first);
+    let by_date: HashMap<&str, &DayPoint> =
+        days.iter().map(|day| (day.date.as_str(), day)).collect();
+
+    let mut cost = 0.0;
+    let mut tokens = 0;
+    let cumulative = first
+        .iter_days()
+        .take_while(|date| *date <= as_of)
+        .map(|date| {
+            let label = date.format("%Y-%m-%d").to_string();
+            if let Some(day) = by_date.get(label.as_str()) {
+                cost += day.cost;
+                tokens += day.tokens;
+            }
+            Slice {
+                label,
+                cost: round4(cost),
+                tokens,
+            }
+        })
+        .collect();
+
+    MonthToDate {
+        month: first.format("%Y-%m").to_string(),
+        days_in_month: (next_month - first).num_days().max(1) as u32,
+        budget: options.monthly_budget,
+        cumulative,
+    }
+}
+
+/// `block_end` only carries the time of day; the timeline needs the date too.
+fn block_end(block: &BlockStats) -> String {
+    NaiveDateTime::parse_from_str(&block.block_start, "%Y-%m-%d %H:%M").map_or_else(
+        |_| block.block_end.clone(),
+        |start| {
+            (start + Duration::hours(5))
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        },
+    )
+}
+
+/// Cost of usage spread over several days, converted at each day's rate
+/// weighted by its cost.
+fn span_cost<'d>(
+    models: &HashMap<String, Stats>,
+    days: impl IntoIterator<Item = (&'d String, &'d HashMap<String, Stats>)>,
+    pricing_db: &PricingDb,
+    currency: Option<&CurrencyConverter>,
+) -> f64 {
+    let currency = span_currency(currency, days, pricing_db, CostDisplayMode::Total);
+    display_cost(sum_model_costs(models, pricing_db), currency.as_deref())
+}
+
+/// Converter for one day's costs: that day's rate when `currency` carries
+/// daily rates.
+fn day_currency<'a>(
+    currency: Option<&'a CurrencyConverter>,
+    date: &str,
+) -> Option<Cow<'a, CurrencyConverter>> {
+    match NaiveDate::parse_from_str(date, DATE_FORMAT) {
+        Ok(day) => blended_currency(currency, &[(day, 0.0)]),
+        Err(_) => currency.map(Cow::Borrowed),
+    }
+}
+
+/// Unpriced models count as zero so one unknown model does not blank a chart.
+fn display_cost(usd: f64, currency: Option<&CurrencyConverter>) -> f64 {
+    let cost = currency.map_or(usd, |conv| conv.convert(usd));
+    if cost.is_finite() { round4(cost) } else { 0.0 }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn round4(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+trait Ranked {
+    fn rank(&self) -> (f64, i64, &str);
+}
+
+impl Ranked for Slice {
+    fn rank(&self) -> (f64, i64, &str) {
+        (self.cost, self.tokens, &self.label)
+    }
+}
+
+impl Ranked for ProjectSlice {
+    fn rank(&self) -> (f64, i64, &str) {
+        (self.cost, self.tokens, &self.label)
+    }
+}
+
+/// Largest first by the plotted metric, then by label for a stable order.
+fn sort_by_metric<T: Ranked>(items: &mut [T], show_cost: bool) {
+    items.sort_by(|a, b| {
+        let (a_cost, a_tokens, a_label) = a.rank();
+        let (b_cost, b_tokens, b_label) = b.rank();
+        let cost_order = if show_cost {
+            b_cost.total_cmp(&a_cost)
+        } else {
+            std::cmp::Ordering::Equal
+        };
+        cost_order
+            .then(b_tokens.cmp(&a_tokens))
+            .then_with(|| a_label.cmp(b_label))
+    });
+}
+
+const DASHBOARD_CSS: &str = r#"body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem auto; max-width: 960px; padding: 0 1rem; color: #1f2328; }
+h1 { font-size: 1.5rem; margin-bottom: 0.25rem; }
+h2 { font-size: 1.1rem; margin: 2rem 0 0.5rem; }
+p.meta, p.empty { color: #59636e; font-size: 0.85rem; }
+.cards { display: flex; flex-wrap: wrap; gap: 0.75rem; margin: 1.25rem 0; }
+.card { border: 1px solid #d0d7de; border-radius: 6px; padding: 0.6rem 0.9rem; min-width: 8rem; }
+.card span { display: block; color: #59636e; font-size: 0.75rem; }
+.card strong { font-size: 1.15rem; font-variant-numeric: tabular-nums; }
+.chart svg { width: 100%; height: auto; display: block; }
+.chart text { font-size: 11px; fill: #59636e; }
+.chart line.grid { stroke: #eaeef2; }
+.legend { list-style: none; display: flex; flex-wrap: wrap; gap: 0.35rem 1rem; padding: 0; margin: 0.5rem 0 0; font-size: 0.8rem; }
+.legend span { display: inline-block; width: 0.75rem; height: 0.75rem; margin-right: 0.35rem; border-radius: 2px; vertical-align: -1px; }
+"#;
+
+/// Dependency-free SVG charts: stacked bars, lines, horizontal bars and a
+/// block timeline. Values carry native `<title>` tooltips.
+const CHART_JS: &str = r"(() => {
+  const data = JSON.parse(document.getElementById('ccstats-data').textContent);
+  const NS = 'http://www.w3.org/2000/svg';
+  const W = 860;
+  const H = 260;
+  const PAD = { top: 12, right: 16, bottom: 28, left: 64 };
+  const PALETTE = ['#4e79a7', '#f28e2b', '#e15759', '#76b7b2', '#59a14f', '#edc948', '#b07aa1', '#ff9da7', '#9c755f', '#bab0ac'];
+  const color = (i) => PALETTE[i % PALETTE.length];
+  const isCost = data.metric === 'cost';
+  const value = (d) => (isCost ? d.cost : d.tokens);
+  const sum = (items, f) => items.reduce((total, item) => total + f(item), 0);
+  const compact = (n) => {
+    const abs = Math.abs(n);
+    if (abs >= 1e9) return (n / 1e9).toFixed(1) + 'B';
+    if (abs >= 1e6) return (n / 1e6).toFixed(1) + 'M';
+    if (abs >= 1e3) return (n / 1e3).toFixed(1) + 'K';
+    return String(Math.round(n));
+  };
+  const money = (n) => data.currency_symbol + n.toFixed(n === 0 || Math.abs(n) >= 1 ? 2 : 4);
+  const fmt = (n) => (isCost ? money(n) : compact(n));
+  const percent = (n) => n.toFixed(1) + '%';
+  const niceMax = (v) => {
+    if (!(v > 0)) return 1;
+    const step = 10 ** Math.floor(Math.log10(v));
+    return (Math.ceil((v / step) * 2) / 2) * step;
+  };
+
+  function node(tag, attrs, parent, text) {
+    const el = document.createElementNS(NS, tag);
+    for (const [key, val] of Object.entries(attrs)) el.setAttribute(key, val);
+    if (text !== undefined) el.textContent = text;
+    if (parent) parent.appendChild(el);
+    return el;
+  }
+  const tip = (el, text) => node('title', {}, el, text);
+  const host = (id) => document.getElementById('chart-' + id);
+  const svg = (id, height) => node('svg', { viewBox: `0 0 ${W} ${height}`, role: 'img' }, host(id));
+  function empty(id, text) {
+    const p = document.createElement('p');
+    p.className = 'empty';
+    p.textContent = text;
+    host(id).appendChild(p);
+  }
+  function legend(id, names) {
+    const list = document.createElement('ul');
+    list.className = 'legend';
+    names.forEach((name, i) => {
+      const item = document.createElement('li');
+      const swatch = document.createElement('span');
+      swatch.style.background = color(i);
+      item.append(swatch, name);
+      list.appendChild(item);
+    });
+    host(id).appendChild(list);
+  }
+  function yAxis(s, max, format) {
+    const plotH = H - PAD.top - PAD.bottom;
+    for (let i = 0; i <= 4; i++) {
+      const y = PAD.top + plotH - (plotH * i) / 4;
+      node('line', { x1: PAD.left, x2: W - PAD.right, y1: y, y2: y, class: 'grid' }, s);
+      node('text', { x: PAD.left - 6, y: y + 4, 'text-anchor': 'end' }, s, format((max * i) / 4));
+    }
+    return (v) => PAD.top + plotH - (plotH * v) / max;
+  }
+  function xLabels(s, labels, xOf) {
+    const step = Math.max(1, Math.ceil(labels.length / 10));
+    labels.forEach((label, i) => {
+      if (i % step === 0) node('text', { x: xOf(i), y: H - 8, 'text-anchor': 'middle' }, s, label);
+    });
+  }
+
+  function stackedBars(id, labels, series) {
+    const s = svg(id, H);
+    const totals = labels.map((_, i) => sum(series, (ser) => ser.values[i]));
+    const y = yAxis(s, niceMax(Math.max(0, ...totals)), fmt);
+    const band = (W - PAD.left - PAD.right) / Math.max(1, labels.length);
+    labels.forEach((label, i) => {
+      let base = 0;
+      series.forEach((ser, k) => {
+        const v = ser.values[i];
+        if (!v) return;
+        const rect = node('rect', {
+          x: PAD.left + band * i + band * 0.1,
+          width: Math.max(1, band * 0.8),
+          y: y(base + v),
+          height: Math.max(0, y(base) - y(base + v)),
+          fill: color(k),
+        }, s);
+        tip(rect, `${label} ${ser.name}: ${fmt(v)}`);
+        base += v;
+      });
+    });
+    xLabels(s, labels, (i) => PAD.left + band * i + band / 2);
+  }
+
+  function lines(id, labels, series, format, max) {
+    const s = svg(id, H);
+    const values = series.flatMap((ser) => ser.values).filter((v) => v !== null);
+    const y = yAxis(s, max || niceMax(Math.max(0, ...values)), format);
+    const plotW = W - PAD.left - PAD.right;
+    const xOf = (i) => PAD.left + (labels.length > 1 ? (plotW * i) / (labels.length - 1) : plotW / 2);
+    series.forEach((ser) => {
+      let d = '';
+      let pen = 'M';
+      ser.values.forEach((v, i) => {
+        if (v === null) {
+          pen = 'M';
+          return;
+        }
+        d += `${pen}${xOf(i).toFixed(1)},${y(v).toFixed(1)} `;
+        pen = 'L';
+      });
+      const path = node('path', { d, fill: 'none', stroke: ser.color, 'stroke-width': 2 }, s);
+      if (ser.dashed) path.setAttribute('stroke-dasharray', '6 4');
+      tip(path, ser.name);
+      if (!ser.points) return;
+      ser.values.forEach((v, i) => {
+        if (v === null) return;
+        tip(node('circle', { cx: xOf(i), cy: y(v), r: 2.5, fill: ser.color }, s), `${labels[i]}: ${format(v)}`);
+      });
+    });
+    xLabels(s, labels, xOf);
+  }
+
+  function hbars(id, items, format) {
+    const row = 24;
+    const labelW = 220;
+    const s = svg(id, items.length * row + 8);
+    const max = Math.max(0, ...items.map((item) => item.value)) || 1;
+    items.forEach((item, i) => {
+      const y = 4 + i * row;
+      const label = item.label.length > 34 ? item.label.slice(0, 33) + '…' : item.label;
+      node('text', { x: labelW - 8, y: y + 15, 'text-anchor': 'end' }, s, label);
+      const width = ((W - labelW - 90) * item.value) / max;
+      const rect = node('rect', { x: labelW, y: y + 3, width: Math.max(1, width), height: row - 8, fill: item.color || color(0) }, s);
+      tip(rect, `${item.label}: ${format(item.value)}`);
+      node('text', { x: labelW + width + 6, y: y + 15 }, s, format(item.value));
+    });
+  }
+
+  function timeline(id, blocks) {
+    const s = svg(id, H);
+    const time = (text) => new Date(text.replace(' ', 'T') + ':00').getTime();
+    const start = Math.min(...blocks.map((b) => time(b.start)));
+    const end = Math.max(...blocks.map((b) => time(b.end)));
+    const y = yAxis(s, niceMax(Math.max(0, ...blocks.map(value))), fmt);
+    const plotW = W - PAD.left - PAD.right;
+    const xOf = (t) => PAD.left + (plotW * (t - start)) / Math.max(1, end - start);
+    const sources = [...new Set(blocks.map((b) => b.source))];
+    blocks.forEach((b) => {
+      const x0 = xOf(time(b.start));
+      const v = value(b);
+      const rect = node('rect', {
+        x: x0,
+        width: Math.max(2, xOf(time(b.end)) - x0 - 1),
+        y: y(v),
+        height: Math.max(0, y(0) - y(v)),
+        fill: color(sources.indexOf(b.source)),
+      }, s);
+      tip(rect, `${b.start} to ${b.end.slice(11)} (${b.source}): ${fmt(v)}`);
+    });
+    const short = end - start < 3 * 864e5;
+    for (let i = 0; i <= 5; i++) {
+      const t = start + ((end - start) * i) / 5;
+      const when = new Date(t).toLocaleString(undefined, short
+        ? { month: 'short', day: 'numeric', hour: '2-digit', minute: '2-digit' }
+        : { month: 'short', day: 'numeric' });
+      node('text', { x: xOf(t), y: H - 8, 'text-anchor': i === 0 ? 'start' : i === 5 ? 'end' : 'middle' }, s, when);
+    }
+    if (sources.length > 1) legend(id, sources);
+  }
+
+  const days = data.days;
+  const total = sum(days, value);
+  document.getElementById('meta').textContent +=
+    days.length ? ` · ${days[0].date} to ${days[days.length - 1].date}` : '';
+  const cards = isCost ? [['Total cost', fmt(total)]] : [];
+  cards.push(
+    ['Total tokens', compact(sum(days, (d) => d.tokens))],
+    ['Active days', String(days.length)],
+    ['Daily average', fmt(days.length ? total / days.length : 0)],
+    ['Top model', data.models[0] || '—'],
+  );
+  cards.forEach(([label, text]) => {
+    const card = document.createElement('div');
+    card.className = 'card';
+    const name = document.createElement('span');
+    name.textContent = label;
+    const amount = document.createElement('strong');
+    amount.textContent = text;
+    card.append(name, amount);
+    document.getElementById('cards').appendChild(card);
+  });
+
+  stackedBars('daily', days.map((d) => d.date), data.models.map((model) => ({
+    name: model,
+    values: days.map((d) => {
+      const slice = d.models.find((m) => m.label === model);
+      return slice ? value(slice) : 0;
+    }),
+  })));
+  legend('daily', data.models);
+
+  const month = data.month;
+  const dayLabels = Array.from({ length: month.days_in_month }, (_, i) => String(i + 1));
+  const spent = month.cumulative.map(value);
+  const last = spent.length - 1;
+  const monthSeries = [{
+    name: 'Month to date',
+    color: color(0),
+    points: true,
+    values: dayLabels.map((_, i) => (i <= last ? spent[i] : null)),
+  }];
+  if (last >= 0 && last < dayLabels.length - 1) {
+    const rate = spent[last] / (last + 1);
+    monthSeries.push({
+      name: 'Projected at the current pace',
+      color: color(0),
+      dashed: true,
+      values: dayLabels.map((_, i) => (i < last ? null : spent[last] + rate * (i - last))),
+    });
+  }
+  if (month.budget !== null) {
+    monthSeries.push({
+      name: `Budget ${fmt(month.budget)}`,
+      color: color(2),
+      dashed: true,
+      values: dayLabels.map(() => month.budget),
+    });
+    monthSeries.push({
+      name: 'Even pace to budget',
+      color: '#8c959f',
+      dashed: true,
+      values: dayLabels.map((_, i) => (month.budget * (i + 1)) / month.days_in_month),
+    });
+  }
+  const monthMax = niceMax(Math.max(0, ...monthSeries.flatMap((ser) => ser.values).filter((v) => v !== null)));
+  lines('month', dayLabels, monthSeries, fmt, monthMax);
+  legend('month', monthSeries.map((ser) => ser.name));
+  host('month').querySelectorAll('.legend span').forEach((swatch, i) => {
+    swatch.style.background = monthSeries[i].color;
+  });
+
+  hbars('sources', data.sources.map((src, i) => ({ label: src.label, value: value(src), color: color(i) })), fmt);
+
+  if (data.projects.length) {
+    const multi = data.sources.length > 1;
+    hbars('projects', data.projects.map((p) => ({
+      label: multi ? `${p.label} (${p.source})` : p.label,
+      value: value(p),
+    })), fmt);
+  } else {
+    empty('projects', 'No project data for the selected sources.');
+  }
+
+  if (days.some((d) => d.cache_hit_rate !== null)) {
+    lines('cache', days.map((d) => d.date), [{
+      name: 'Cache-hit rate',
+      color: color(3),
+      points: true,
+      values: days.map((d) => d.cache_hit_rate),
+    }], percent, 100);
+  } else {
+    empty('cache', 'The selected sources do not log prompt-cache reads.');
+  }
+
+  if (data.blocks.length) {
+    timeline('blocks', data.blocks);
+  } else {
+    empty('blocks', 'No 5-hour block data for the selected sources.');
+  }
+
+  if (data.tools.length) {
+    hbars('tools', data.tools.map((tool) => ({ label: tool.label, value: tool.calls })), (n) => `${n} calls`);
+  } else {
+    empty('tools', 'No tool calls were logged by the selected sources.');
+  }
+})();
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(model: &str, input_tokens: i64) -> DayStats {
+        let mut day = DayStats::default();
+        day.add_stats(
+            model.to_string(),
+            &Stats {
+                input_tokens,
+                count: 1,
+                ..Stats::default()
+            },
+        );
+        day
+    }
+
+    fn options(as_of: NaiveDate) -> DashboardOptions<'static> {
+        DashboardOptions {
+            title: "All Sources",
+            generated: "2026-02-10 09:00",
+            show_cost: false,
+            supports_cache_read: false,
+            monthly_budget: Some(20.0),
+            as_of,
+            currency: None,
+        }
+    }
+
+    #[test]
+    fn merges_sources_by_day_and_runs_month_to_date() {
+        let sources = [
+            DashboardSource {
+                label: "Claude Code",
+                day_stats: HashMap::from([
+                    ("2026-01-31".to_string(), day("opus", 50)),
+                    ("2026-02-02".to_string(), day("opus", 100)),
+                ]),
+                projects: Vec::new(),
+                blocks: Vec::new(),
+            },
+            DashboardSource {
+                label: "OpenAI Codex",
+                day_stats: HashMap::from([("2026-02-02".to_string(), day("gpt-5", 300))]),
+                projects: Vec::new(),
+                blocks: Vec::new(),
+            },
+        ];
+        let as_of = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let data = dashboard_data(&sources, &[], &PricingDb::default(), options(as_of));
+
+        assert_eq!(data.days.len(), 2);
+        assert_eq!(data.days[1].tokens, 400);
+        assert_eq!(data.days[1].models.len(), 2);
+        assert_eq!(data.models, ["gpt-5", "opus"]);
+        assert_eq!(data.sources[0].label, "OpenAI Codex");
+
+        assert_eq!(data.month.month, "2026-02");
+        assert_eq!(data.month.days_in_month, 28);
+        assert_eq!(data.month.budget, Some(20.0));
+        let running: Vec<i64> = data.month.cumulative.iter().map(|s| s.tokens).collect();
+        assert_eq!(running, [0, 400, 400]);
+    }
+
+    #[test]
+    fn converts_each_day_at_its_own_rate() {
+        // `sonnet-4` resolves via the built-in fallback at $3 per million input tokens.
+        let conv = CurrencyConverter::from_history_for_test(
+            "EUR",
+            &[("2026-02-01", 0.5), ("2026-02-02", 2.0)],
+        );
+        let sources = [DashboardSource {
+            label: "Claude Code",
+            day_stats: HashMap::from([
+                ("2026-02-01".to_string(), day("sonnet-4", 1_000_000)),
+                ("2026-02-02".to_string(), day("sonnet-4", 1_000_000)),
+            ]),
+            projects: Vec::new(),
+            blocks: Vec::new(),
+        }];
+        let as_of = NaiveDate::from_ymd_opt(2026, 2, 2).unwrap();
+        let options = DashboardOptions {
+            currency: Some(&conv),
+            ..options(as_of)
+        };
+        let data = dashboard_data(&sources, &[], &PricingDb::default(), options);
+
+        let days: Vec<f64> = data.days.iter().map(|day| day.cost).collect();
+        assert_eq!(days, [1.5, 6.0]);
+        let running: Vec<f64> = data.month.cumulative.iter().map(|s| s.cost).collect();
+        assert_eq!(running, [1.5, 7.5]);
+        let split = vec![data.days[1].models[0].cost, data.sources[0].cost];
+        assert_eq!(split, [6.0, 7.5]);
+    }
+
+    #[test]
+    fn embedded_data_cannot_close_the_script_block() {
+        let sources = [DashboardSource {
+            label: "Claude Code",
+            day_stats: HashMap::from([("2026-02-02".to_string(), day("opus", 100))]),
+            projects: vec![ProjectStats {
+                project_name: "</script><b>x</b>".to_string(),
+                ..ProjectStats::default()
+            }],
+            blocks: vec![BlockStats {
+                block_start: "2026-02-02 22:00".to_string(),
+                block_end: "03:00".to_string(),
+                ..BlockStats::default()
+            }],
+        }];
+        let as_of = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let page = render_dashboard(&sources, &[], &PricingDb::default(), options(as_of));
+
+        assert_eq!(page.matches("</script>").count(), 2);
+        assert!(page.contains(r#""label":"\u003c/script>\u003cb>x\u003c/b>""#));
+        assert!(page.contains(r#""end":"2026-02-03 03:00""#));
+        assert!(page.contains("<h2>Tokens month to date vs budget</h2>"));
+    }
+}
@@ -3,11 +3,13 @@
 use comfy_table::{Cell, Color};
 
 use crate::core::{EndpointStats, Stats};
+use crate::output::command_json::EndpointJson;
 use crate::output::format::{
-    NumberFormat, cost_json_value, create_styled_table, format_cost, format_number, header_cell,
-    right_cell,
+    Locale, create_styled_table, format_cost, format_number, header_cell, right_cell,
 };
+use crate::output::markup::Markup;
 use crate::output::pricing_meta;
+use crate::output::schema::{CostJson, SCHEMA_VERSION, model_names};
 use crate::pricing::{CurrencyConverter, PricingDb, sum_model_costs};
 
 #[derive(Debug, Clone, Copy)]
@@ -15,8 +17,9 @@ pub(crate) struct EndpointTableOptions<'a> {
     pub(crate) use_color: bool,
     pub(crate) show_cost: bool,
     pub(crate) source_label: &'a str,
-    pub(crate) number_format: NumberFormat,
+    pub(crate) locale: Locale,
     pub(crate) currency: Option<&'a CurrencyConverter>,
+    pub(crate) markup: Markup,
 }
 
 /// Average input tokens per call — the key signal that distinguishes native
@@ -37,7 +40,7 @@ pub(crate) fn print_endpoint_table(
 ) {
     let use_color = options.use_color;
     let show_cost = options.show_cost;
-    let number_format = options.number_format;
+    let locale = options.locale;
 
     let mut table = create_styled_table();
     let mut header = vec![
@@ -66,41 +69,21 @@ pub(crate) fn print_endpoint_table(
 
         let mut row = vec![
             Cell::new(ep.endpoint.as_str()),
-            right_cell(&format_number(ep.stats.count, number_format), None, false),
+            right_cell(&format_number(ep.stats.count, locale), None, false),
+            right_cell(&format_number(ep.stats.input_tokens, locale), None, false),
+            right_cell(&format_number(ep.stats.output_tokens, locale), None, false),
+            right_cell(&format_number(ep.stats.cache_creation, locale), None, false),
+            right_cell(&format_number(ep.stats.cache_read, locale), None, false),
+            right_cell(&format_number(ep.stats.total_tokens(), locale), None, false),
             right_cell(
-                &format_number(ep.stats.input_tokens, number_format),
-                None,
-                false,
-            ),
-            right_cell(
-                &format_number(ep.stats.output_tokens, number_format),
-                None,
-                false,
-            ),
-            right_cell(
-                &format_number(ep.stats.cache_creation, number_format),
-                None,
-                false,
-            ),
-            right_cell(
-                &format_number(ep.stats.cache_read, number_format),
-                None,
-                false,
-            ),
-            right_cell(
-                &format_number(ep.stats.total_tokens(), number_format),
-                None,
-                false,
-            ),
-            right_cell(
-                &format_number(avg_input_per_call(&ep.stats), number_format),
+                &format_number(avg_input_per_call(&ep.stats), locale),
                 None,
                 false,
             ),
         ];
         if show_cost {
             row.push(right_cell(
-                &format_cost(ep_cost, options.currency),
+                &format_cost(ep_cost, options.currency, options.locale),
                 cost_color,
                 false,
             ));
@@ -111,95 +94,85 @@ pub(crate) fn print_endpoint_table(
     // Total row
     let mut total_row = vec![
         Cell::new("TOTAL"),
-        right_cell(&format_number(total_stats.count, number_format), None, true),
-        right_cell(
-            &format_number(total_stats.input_tokens, number_format),
-            None,
-            true,
-        ),
-        right_cell(
-            &format_number(total_stats.output_tokens, number_format),
-            None,
-            true,
-        ),
+        right_cell(&format_number(total_stats.count, locale), None, true),
+        right_cell(&format_number(total_stats.input_tokens, locale), None, true),
         right_cell(
-            &format_number(total_stats.cache_creation, number_format),
+            &format_number(total_stats.output_tokens, locale),
             None,
             true,
         ),
         right_cell(
-            &format_number(total_stats.cache_read, number_format),
+            &format_number(total_stats.cache_creation, locale),
             None,
             true,
         ),
+        right_cell(&format_number(total_stats.cache_read, locale), None, true),
         right_cell(
-            &format_number(total_stats.total_tokens(), number_format),
+            &format_number(total_stats.total_tokens(), locale),
             None,
             true,
         ),
         right_cell(
-            &format_number(avg_input_per_call(&total_stats), number_format),
+            &format_number(avg_input_per_call(&total_stats), locale),
             None,
             true,
         ),
     ];
     if show_cost {
         total_row.push(right_cell(
-            &format_cost(total_cost, options.currency),
+            &format_cost(total_cost, options.currency, options.locale),
             cost_color,
             true,
         ));
     }
     table.add_row(total_row);
 
-    println!("\n  {} Usage by Endpoint\n", options.source_label);
-    println!("{table}");
+    options
+        .markup
+        .title(&format!("{} Usage by Endpoint", options.source_label));
+    options.markup.table(&table, 1, options.locale);
     if show_cost
         && let Some(note) =
             pricing_meta::note_for_maps(endpoints.iter().map(|ep| &ep.models), pricing_db)
     {
-        println!("\n  {note}");
+        options.markup.line(&format!("\n  {note}"));
     }
-    println!(
-        "\n  proxy = third-party gateway (does not report cache creation; bills full context as input)\n"
+    options.markup.line(
+        "\n  proxy = third-party gateway (does not report cache creation; bills full context as input)\n",
     );
 }
 
-pub(crate) fn output_endpoint_json(
+pub(crate) fn endpoint_json_rows(
     endpoints: &[EndpointStats],
     pricing_db: &PricingDb,
     show_cost: bool,
     currency: Option<&CurrencyConverter>,
-) -> String {
-    let output: Vec<serde_json::Value> = endpoints
+) -> Vec<EndpointJson> {
+    let rows: Vec<EndpointJson> = endpoints
         .iter()
-        .map(|ep| {
-            let ep_cost = sum_model_costs(&ep.models, pricing_db);
-            let mut models: Vec<_> = ep.models.keys().cloned().collect();
-            models.sort();
-            let mut obj = serde_json::json!({
-                "endpoint": ep.endpoint.as_str(),
-                "calls": ep.stats.count,
-                "input_tokens": ep.stats.input_tokens,
-                "output_tokens": ep.stats.output_tokens,
-                "cache_creation_tokens": ep.stats.cache_creation,
-                "cache_read_tokens": ep.stats.cache_read,
-                "total_tokens": ep.stats.total_tokens(),
-                "avg_input_per_call": avg_input_per_call(&ep.stats),
-                "models": models,
-            });
-            if show_cost {
-                obj["cost"] = cost_json_value(ep_cost, currency);
-                pricing_meta::add_json(&mut obj, &ep.models, pricing_db);
-            }
-            obj
+        .map(|ep| EndpointJson {
+            schema_version: SCHEMA_VERSION,
+            endpoint: ep.endpoint.as_str(),
+            calls: ep.stats.count,
+            input_tokens: ep.stats.input_tokens,
+            output_tokens: ep.stats.output_tokens,
+            cache_creation_tokens: ep.stats.cache_creation,
+            cache_read_tokens: ep.stats.cache_read,
+            total_tokens: ep.stats.total_tokens(),
+            avg_input_per_call: avg_input_per_call(&ep.stats),
+            models: model_names(&ep.models),
+            cost: show_cost.then(|| {
+                CostJson::for_models(
+                    &ep.models,
+                    sum_model_costs(&ep.models, pricing_db),
+                    pricing_db,
+                    currency,
+                )
+            }),
         })
         .collect();
 
-    serde_json::to_string_pretty(&output).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize JSON output: {e}");
-        "[]".to_string()
-    })
+    rows
 }
 
 #[cfg(test)]
@@ -247,10 +220,11 @@ mod tests {
             make_endpoint(Endpoint::Native, 1000, 10),
             make_endpoint(Endpoint::Proxy, 5000, 5),
         ];
-        let json = output_endpoint_json(&endpoints, &db, false, None);
-        assert!(json.contains("\"endpoint\": \"native\""));
-        assert!(json.contains("\"endpoint\": \"proxy\""));
-        assert!(json.contains("\"avg_input_per_call\": 100"));
-        assert!(json.contains("\"avg_input_per_call\": 1000"));
+        let rows: Vec<(&str, i64)> = endpoint_json_rows(&endpoints, &db, false, None)
+            .iter()
+            .map(|row| (row.endpoint, row.avg_input_per_call))
+            .collect();
+        assert!(rows.contains(&("native", 100)));
+        assert!(rows.contains(&("proxy", 1000)));
     }
 }
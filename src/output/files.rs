@@ -0,0 +1,175 @@
+//! Output formatters for file touch analytics
+
+use std::fmt::Write;
+
+use comfy_table::Cell;
+
+use crate::core::FileSummary;
+
+use super::command_json::FilesJson;
+use super::format::{Locale, create_styled_table, csv_escape, header_cell, right_cell};
+use super::locale::Msg;
+use super::schema::SCHEMA_VERSION;
+use super::tools::format_calls;
+
+/// The first `limit` rows of each project, in summary order.
+fn top_per_project<T>(rows: &[T], project: impl Fn(&T) -> &str, limit: usize) -> Vec<&T> {
+    let mut top = Vec::new();
+    let mut current: Option<&str> = None;
+    let mut listed = 0;
+    for row in rows {
+        if current != Some(project(row)) {
+            current = Some(project(row));
+            listed = 0;
+        }
+        if listed < limit {
+            top.push(row);
+            listed += 1;
+        }
+    }
+    top
+}
+
+/// Print the most touched files per project, then hot directories
+pub(crate) fn print_files_table(
+    summary: &FileSummary,
+    limit: usize,
+    locale: Locale,
+    use_color: bool,
+) {
+    if summary.files.is_empty() {
+        println!("{}", locale.text(Msg::NoFileTouches));
+        return;
+    }
+
+    let h_cell = |msg| header_cell(locale.text(msg), use_color);
+    let mut table = create_styled_table();
+    table.set_header(vec![
+        h_cell(Msg::Project),
+        h_cell(Msg::File),
+        h_cell(Msg::Reads),
+        h_cell(Msg::Writes),
+        h_cell(Msg::Sessions),
+    ]);
+    for file in top_per_project(&summary.files, |f| &f.project, limit) {
+        table.add_row(vec![
+            Cell::new(&file.project),
+            Cell::new(&file.path),
+            right_cell(&format_calls(file.reads), None, false),
+            right_cell(&format_calls(file.writes), None, false),
+            right_cell(&file.sessions.to_string(), None, false),
+        ]);
+    }
+    println!("{table}");
+
+    let mut dirs = create_styled_table();
+    dirs.set_header(vec![
+        h_cell(Msg::Project),
+        h_cell(Msg::Directory),
+        h_cell(Msg::Reads),
+        h_cell(Msg::Writes),
+        h_cell(Msg::Files),
+    ]);
+    for dir in top_per_project(&summary.directories, |d| &d.project, limit) {
+        dirs.add_row(vec![
+            Cell::new(&dir.project),
+            Cell::new(&dir.directory),
+            right_cell(&format_calls(dir.reads), None, false),
+            right_cell(&format_calls(dir.writes), None, false),
+            right_cell(&dir.files.to_string(), None, false),
+        ]);
+    }
+    println!();
+    println!("{}", locale.text(Msg::HotDirectories));
+    println!("{dirs}");
+}
+
+/// Output the most touched files and directories per project as JSON
+pub(crate) fn files_json_report(summary: &FileSummary, limit: usize) -> FilesJson {
+    FilesJson {
+        schema_version: SCHEMA_VERSION,
+        files: top_per_project(&summary.files, |f| &f.project, limit)
+            .into_iter()
+            .cloned()
+            .collect(),
+        directories: top_per_project(&summary.directories, |d| &d.project, limit)
+            .into_iter()
+            .cloned()
+            .collect(),
+        total: summary.total,
+    }
+}
+
+/// Output files and directories as CSV, one `kind` per section
+pub(crate) fn output_files_csv(summary: &FileSummary, limit: usize) -> String {
+    let mut out = String::from("kind,project,path,reads,writes,sessions,files\n");
+    for file in top_per_project(&summary.files, |f| &f.project, limit) {
+        let _ = writeln!(
+            out,
+            "file,{},{},{},{},{},",
+            csv_escape(&file.project),
+            csv_escape(&file.path),
+            file.reads,
+            file.writes,
+            file.sessions,
+        );
+    }
+    for dir in top_per_project(&summary.directories, |d| &d.project, limit) {
+        let _ = writeln!(
+            out,
+            "directory,{},{},{},{},,{}",
+            csv_escape(&dir.project),
+            csv_escape(&dir.directory),
+            dir.reads,
+            dir.writes,
+            dir.files,
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ToolCall, aggregate_files};
+    use serde_json::Value;
+
+    fn sample_summary() -> FileSummary {
+        let call = |project: &str, name: &str, session: &str, path: &str| ToolCall {
+            name: name.to_string(),
+            project_path: project.to_string(),
+            session_key: session.to_string(),
+            file_paths: vec![path.to_string()],
+            ..Default::default()
+        };
+        aggregate_files(&[
+            call("app", "Read", "s1", "src/main.rs"),
+            call("app", "Edit", "s2", "src/main.rs"),
+            call("app", "Read", "s1", "Cargo.toml"),
+            call("lib", "Write", "s3", "src/lib.rs"),
+        ])
+    }
+
+    #[test]
+    fn json_limits_rows_per_project() {
+        let val: Value = serde_json::to_value(files_json_report(&sample_summary(), 1)).unwrap();
+        let files = val["files"].as_array().unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0]["path"], "src/main.rs");
+        assert_eq!(files[0]["reads"], 1);
+        assert_eq!(files[0]["writes"], 1);
+        assert_eq!(files[0]["sessions"], 2);
+        assert_eq!(val["directories"][0]["directory"], "src");
+        assert_eq!(val["total"], 4);
+    }
+
+    #[test]
+    fn csv_has_one_section_per_kind() {
+        let csv = output_files_csv(&sample_summary(), 10);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "kind,project,path,reads,writes,sessions,files");
+        assert_eq!(lines[1], "file,app,src/main.rs,1,1,2,");
+        assert!(lines.contains(&"directory,app,src,1,1,,1"));
+        assert!(lines.contains(&"directory,lib,src,0,1,,1"));
+    }
+}
@@ -3,61 +3,11 @@ use comfy_table::{
     modifiers::UTF8_SOLID_INNER_BORDERS, presets::UTF8_FULL,
 };
 
-use crate::error::AppError;
 use crate::pricing::CurrencyConverter;
 
-#[derive(Debug, Clone, Copy)]
-pub(crate) struct NumberFormat {
-    group_sep: char,
-    decimal_sep: char,
-}
-
-impl Default for NumberFormat {
-    fn default() -> Self {
-        NumberFormat {
-            group_sep: ',',
-            decimal_sep: '.',
-        }
-    }
-}
-
-impl NumberFormat {
-    pub(crate) fn from_locale(locale: Option<&str>) -> Result<Self, AppError> {
-        let Some(raw) = locale else {
-            return Ok(NumberFormat::default());
-        };
-        let trimmed = raw.trim();
-        if trimmed.is_empty() {
-            return Ok(NumberFormat::default());
-        }
-        let base = trimmed
-            .split(['-', '_'])
-            .next()
-            .unwrap_or(trimmed)
-            .to_ascii_lowercase();
-
-        let format = match base.as_str() {
-            "de" => NumberFormat {
-                group_sep: '.',
-                decimal_sep: ',',
-            },
-            "fr" | "ru" => NumberFormat {
-                group_sep: ' ',
-                decimal_sep: ',',
-            },
-            "en" | "zh" => NumberFormat::default(),
-            _ => {
-                return Err(AppError::UnsupportedLocale {
-                    input: trimmed.to_string(),
-                });
-            }
-        };
+pub(crate) use super::locale::Locale;
 
-        Ok(format)
-    }
-}
-
-pub(super) fn format_number(n: i64, format: NumberFormat) -> String {
+pub(super) fn format_number(n: i64, format: Locale) -> String {
     let (sign, digits) = if n < 0 {
         ("-", (-n).to_string())
     } else {
@@ -75,7 +25,7 @@ pub(super) fn format_number(n: i64, format: NumberFormat) -> String {
 }
 
 /// Format number in compact form (K, M, B suffixes)
-pub(super) fn format_compact(n: i64, format: NumberFormat) -> String {
+pub(super) fn format_compact(n: i64, format: Locale) -> String {
     let (sign, value) = if n < 0 { ("-", -n) } else { ("", n) };
     let (scaled, suffix) = if value >= 1_000_000_000 {
         (value as f64 / 1_000_000_000.0, "B")
@@ -106,56 +56,29 @@ pub(super) fn compare_cost(a: f64, b: f64) -> std::cmp::Ordering {
     }
 }
 
-pub(super) fn format_cost(cost: f64, currency: Option<&CurrencyConverter>) -> String {
-    match currency {
-        Some(conv) => conv.format(cost),
-        None => {
-            if cost.is_nan() {
-                "N/A".to_string()
-            } else {
-                format!("${cost:.2}")
-            }
-        }
+pub(super) fn format_cost(
+    cost: f64,
+    currency: Option<&CurrencyConverter>,
+    locale: Locale,
+) -> String {
+    let (amount, symbol, decimals) = match currency {
+        Some(conv) => (conv.convert(cost), conv.symbol(), conv.decimals()),
+        None => (cost, "$", 2),
+    };
+    if amount.is_nan() {
+        return "N/A".to_string();
     }
+    locale.format_money(amount, symbol, decimals)
 }
 
 pub(super) fn format_cache_hit_rate(rate: Option<f64>) -> String {
     rate.map_or_else(|| "N/A".to_string(), |value| format!("{value:.1}%"))
 }
 
-pub(super) fn cache_hit_rate_json_value(rate: Option<f64>) -> serde_json::Value {
-    rate.map_or(serde_json::Value::Null, |value| {
-        serde_json::json!((value * 100.0).round() / 100.0)
-    })
-}
-
 pub(super) fn cache_hit_rate_csv_value(rate: Option<f64>) -> String {
     rate.map_or_else(String::new, |value| format!("{value:.2}"))
 }
 
-pub(super) fn cost_json_value(
-    cost: f64,
-    currency: Option<&CurrencyConverter>,
-) -> serde_json::Value {
-    match currency {
-        Some(conv) => {
-            let converted = conv.convert(cost);
-            if converted.is_nan() {
-                serde_json::Value::Null
-            } else {
-                serde_json::json!(converted)
-            }
-        }
-        None => {
-            if cost.is_nan() {
-                serde_json::Value::Null
-            } else {
-                serde_json::json!(cost)
-            }
-        }
-    }
-}
-
 pub(super) fn styled_cell(text: &str, color: Option<Color>, bold: bool) -> Cell {
     let mut cell = Cell::new(text);
     if let Some(c) = color {
@@ -220,14 +143,16 @@ pub(super) fn csv_escape(s: &str) -> String {
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod tests {
+    use crate::output::schema::cache_hit_rate_amount;
+
     use super::{
-        NumberFormat, cache_hit_rate_csv_value, cache_hit_rate_json_value, compare_cost,
-        format_cache_hit_rate, format_compact, format_cost, format_number,
+        Locale, cache_hit_rate_csv_value, compare_cost, format_cache_hit_rate, format_compact,
+        format_cost, format_number,
     };
 
     #[test]
     fn format_number_with_commas() {
-        let fmt = NumberFormat::default();
+        let fmt = Locale::default();
         assert_eq!(format_number(0, fmt), "0");
         assert_eq!(format_number(999, fmt), "999");
         assert_eq!(format_number(1000, fmt), "1,000");
@@ -236,7 +161,7 @@ mod tests {
 
     #[test]
     fn format_compact_units() {
-        let fmt = NumberFormat::default();
+        let fmt = Locale::default();
         assert_eq!(format_compact(0, fmt), "0");
         assert_eq!(format_compact(999, fmt), "999");
         assert_eq!(format_compact(1_000, fmt), "1.0K");
@@ -248,81 +173,64 @@ mod tests {
 
     #[test]
     fn format_cost_handles_nan() {
-        assert_eq!(format_cost(f64::NAN, None), "N/A");
-        assert_eq!(format_cost(1.234, None), "$1.23");
+        assert_eq!(format_cost(f64::NAN, None, Locale::default()), "N/A");
+        assert_eq!(format_cost(1.234, None, Locale::default()), "$1.23");
     }
 
     #[test]
     fn cache_hit_rate_formats_for_table_json_and_csv() {
         assert_eq!(format_cache_hit_rate(Some(12.345)), "12.3%");
         assert_eq!(format_cache_hit_rate(None), "N/A");
-        assert_eq!(
-            cache_hit_rate_json_value(Some(12.345)),
-            serde_json::json!(12.35)
-        );
-        assert_eq!(cache_hit_rate_json_value(None), serde_json::Value::Null);
+        assert_eq!(cache_hit_rate_amount(Some(12.345)), Some(12.35));
+        assert_eq!(cache_hit_rate_amount(None), None);
         assert_eq!(cache_hit_rate_csv_value(Some(12.345)), "12.35");
         assert_eq!(cache_hit_rate_csv_value(None), "");
     }
 
-    #[test]
-    fn cost_json_value_nan_is_null() {
-        assert_eq!(
-            super::cost_json_value(f64::NAN, None),
-            serde_json::Value::Null
-        );
-    }
-
-    #[test]
-    fn cost_json_value_normal() {
-        let val = super::cost_json_value(1.5, None);
-        assert_eq!(val.as_f64().unwrap(), 1.5);
-    }
-
     #[test]
     fn from_locale_none_returns_default() {
-        let fmt = NumberFormat::from_locale(None).unwrap();
+        let fmt = Locale::parse(None).unwrap();
         assert_eq!(format_number(1000, fmt), "1,000");
     }
 
     #[test]
     fn from_locale_empty_returns_default() {
-        let fmt = NumberFormat::from_locale(Some("")).unwrap();
+        let fmt = Locale::parse(Some("")).unwrap();
         assert_eq!(format_number(1000, fmt), "1,000");
     }
 
     #[test]
     fn from_locale_de_uses_dot_separator() {
-        let fmt = NumberFormat::from_locale(Some("de")).unwrap();
+        let fmt = Locale::parse(Some("de")).unwrap();
         assert_eq!(format_number(1000, fmt), "1.000");
     }
 
     #[test]
     fn from_locale_fr_uses_space_separator() {
-        let fmt = NumberFormat::from_locale(Some("fr")).unwrap();
+        let fmt = Locale::parse(Some("fr")).unwrap();
         assert_eq!(format_number(1000, fmt), "1 000");
     }
 
     #[test]
     fn from_locale_with_region_suffix() {
-        let fmt = NumberFormat::from_locale(Some("de-DE")).unwrap();
+        let fmt = Locale::parse(Some("de-DE")).unwrap();
         assert_eq!(format_number(1000, fmt), "1.000");
     }
 
     #[test]
     fn from_locale_unsupported_returns_error() {
-        assert!(NumberFormat::from_locale(Some("ja")).is_err());
+        assert!(Locale::parse(Some("xx")).is_err());
     }
 
     #[test]
     fn format_compact_with_de_locale() {
-        let fmt = NumberFormat::from_locale(Some("de")).unwrap();
+        let fmt = Locale::parse(Some("de")).unwrap();
         assert_eq!(format_compact(1500, fmt), "1,5K");
     }
 
     #[test]
     fn format_number_negative() {
-        let fmt = NumberFormat::default();
+        let fmt = Locale::default();
         assert_eq!(format_number(-1234, fmt), "-1,234");
     }
 
@@ -2,164 +2,160 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use crate::cli::SortOrder;
-use crate::core::{DataQuality, DayStats};
-use crate::output::format::{cache_hit_rate_json_value, cost_json_value};
-use crate::output::period::{Period, aggregate_day_stats_by_period};
-use crate::output::pricing_meta;
+use crate::core::{DataQuality, DayStats, Stats};
+use crate::output::period::{Period, PeriodRates, aggregate_day_stats_by_period, row_currency};
+use crate::output::schema::{
+    CostJson, DataQualityJson, ExchangeRateJson, ModelBreakdownJson, PeriodJson, PeriodJsonRow,
+    PeriodKeyJson, SCHEMA_VERSION, ServiceTierJson, TokensJson, cost_amount, model_names,
+};
 use crate::pricing::{
     CostDisplayMode, CurrencyConverter, PricingDb, calculate_display_cost,
-    calculate_estimated_proxy_cost, model_cost_kind, sum_display_model_costs,
-    sum_estimated_proxy_model_costs,
+    calculate_service_tier_cost, sum_display_model_costs,
 };
 
-fn sort_output(output: &mut [serde_json::Value], key: &str, order: SortOrder) {
+fn sorted_keys<V>(stats: &HashMap<String, V>, order: SortOrder) -> Vec<&String> {
+    let mut keys: Vec<_> = stats.keys().collect();
     match order {
-        SortOrder::Asc => output.sort_by(|a, b| {
-            a.get(key)
-                .and_then(|v| v.as_str())
-                .cmp(&b.get(key).and_then(|v| v.as_str()))
-        }),
-        SortOrder::Desc => output.sort_by(|a, b| {
-            b.get(key)
-                .and_then(|v| v.as_str())
-                .cmp(&a.get(key).and_then(|v| v.as_str()))
-        }),
+        SortOrder::Asc => keys.sort(),
+        SortOrder::Desc => keys.sort_by(|a, b| b.cmp(a)),
     }
+    keys
 }
 
-fn sort_models_breakdown(breakdown: &mut [serde_json::Value], show_cost: bool) {
+fn breakdown_cost(row: &ModelBreakdownJson) -> Option<f64> {
+    row.cost.as_ref().and_then(|cost| cost.cost)
+}
+
+fn sort_models_breakdown(breakdown: &mut [ModelBreakdownJson], show_cost: bool) {
     if show_cost {
         breakdown.sort_by(|a, b| {
-            let cost_a = a.get("cost").and_then(serde_json::Value::as_f64);
-            let cost_b = b.get("cost").and_then(serde_json::Value::as_f64);
-            match cost_b.partial_cmp(&cost_a).unwrap_or(Ordering::Equal) {
-                Ordering::Equal => a
-                    .get("model")
-                    .and_then(|v| v.as_str())
-                    .cmp(&b.get("model").and_then(|v| v.as_str())),
+            match breakdown_cost(b)
+                .partial_cmp(&breakdown_cost(a))
+                .unwrap_or(Ordering::Equal)
+            {
+                Ordering::Equal => a.model.cmp(&b.model),
                 other => other,
             }
         });
     } else {
-        breakdown.sort_by(|a, b| {
-            a.get("model")
-                .and_then(|v| v.as_str())
-                .cmp(&b.get("model").and_then(|v| v.as_str()))
-        });
+        breakdown.sort_by(|a, b| a.model.cmp(&b.model));
+    }
+}
+
+/// Usage a model billed under non-standard service tiers (batch, flex,
+/// priority); the remainder of the model's usage is standard.
+fn service_tiers_json(
+    model: &str,
+    stats: &Stats,
+    options: &PeriodJsonOptions<'_>,
+) -> Vec<ServiceTierJson> {
+    stats
+        .service_tiers
+        .iter()
+        .map(|(tier, tokens)| ServiceTierJson {
+            tier: tier.as_str(),
+            calls: tokens.count,
+            input_tokens: tokens.input_tokens,
+            output_tokens: tokens.output_tokens,
+            reasoning_tokens: tokens.reasoning_tokens,
+            cache_creation_tokens: tokens.cache_creation,
+            cache_read_tokens: tokens.cache_read,
+            cost: options.show_cost.then(|| {
+                cost_amount(
+                    calculate_service_tier_cost(stats, tier, model, options.pricing_db),
+                    options.currency,
+                )
+            }),
+        })
+        .collect()
+}
+
+/// One model of a `--breakdown` period, with its display cost.
+fn model_breakdown_json(
+    model: &str,
+    model_stats: &Stats,
+    options: &PeriodJsonOptions<'_>,
+) -> (ModelBreakdownJson, f64) {
+    let cost = if options.show_cost {
+        calculate_display_cost(model_stats, model, options.pricing_db, options.cost_mode)
+    } else {
+        0.0
+    };
+    let tiers = service_tiers_json(model, model_stats, options);
+    let row = ModelBreakdownJson {
+        model: model.to_string(),
+        tokens: TokensJson::new(model_stats, options.supports_cache_read),
+        cost: options.show_cost.then(|| {
+            CostJson::for_model(
+                model,
+                model_stats,
+                cost,
+                options.pricing_db,
+                options.currency,
+            )
+        }),
+        service_tiers: (!tiers.is_empty()).then_some(tiers),
+    };
+    (row, cost)
+}
+
+fn period_key_json(period: Period, key: &str) -> PeriodKeyJson {
+    match period {
+        Period::Day => PeriodKeyJson::Date(key.to_string()),
+        Period::Week => PeriodKeyJson::Week(key.to_string()),
+        Period::Month => PeriodKeyJson::Month(key.to_string()),
     }
 }
 
 fn build_period_entry(
-    label: &str,
+    period: Period,
     key: &str,
     stats: &DayStats,
     options: &PeriodJsonOptions<'_>,
-) -> serde_json::Value {
-    if options.breakdown {
-        let mut models_breakdown: Vec<serde_json::Value> = Vec::new();
+) -> PeriodJson {
+    let (models, breakdown, period_cost) = if options.breakdown {
+        let mut breakdown = Vec::with_capacity(stats.models.len());
         let mut period_cost = 0.0;
-
         for (model, model_stats) in &stats.models {
-            let mut model_obj = serde_json::json!({
-                "model": model,
-                "input_tokens": model_stats.input_tokens,
-                "output_tokens": model_stats.output_tokens,
-                "reasoning_tokens": model_stats.reasoning_tokens,
-                "cache_creation_tokens": model_stats.cache_creation,
-                "cache_read_tokens": model_stats.cache_read,
-                "cache_hit_rate": cache_hit_rate_json_value(
-                    model_stats.cache_hit_rate(options.supports_cache_read)
-                ),
-                "total_tokens": model_stats.total_tokens(),
-            });
-            if options.show_cost {
-                let cost = calculate_display_cost(
-                    model_stats,
-                    model,
-                    options.pricing_db,
-                    options.cost_mode,
-                );
-                period_cost += cost;
-                model_obj["cost"] = cost_json_value(cost, options.currency);
-                pricing_meta::add_model_json(
-                    &mut model_obj,
-                    model,
-                    model_stats,
-                    options.pricing_db,
-                );
-                let estimated_cost =
-                    calculate_estimated_proxy_cost(model_stats, model, options.pricing_db);
-                if estimated_cost > 0.0 {
-                    model_obj["cost_kind"] = serde_json::json!(model_stats.cost_kind().as_str());
-                    model_obj["estimated_cost"] = cost_json_value(estimated_cost, options.currency);
-                }
-            }
-            models_breakdown.push(model_obj);
+            let (row, cost) = model_breakdown_json(model, model_stats, options);
+            period_cost += cost;
+            breakdown.push(row);
         }
-
-        sort_models_breakdown(&mut models_breakdown, options.show_cost);
-
-        let mut obj = serde_json::json!({
-            (label): key,
-            "input_tokens": stats.stats.input_tokens,
-            "output_tokens": stats.stats.output_tokens,
-            "reasoning_tokens": stats.stats.reasoning_tokens,
-            "cache_creation_tokens": stats.stats.cache_creation,
-            "cache_read_tokens": stats.stats.cache_read,
-            "cache_hit_rate": cache_hit_rate_json_value(
-                stats.stats.cache_hit_rate(options.supports_cache_read)
-            ),
-            "total_tokens": stats.stats.total_tokens(),
-            "breakdown": models_breakdown,
-        });
-        if options.show_cost {
-            obj["cost"] = cost_json_value(period_cost, options.currency);
-            pricing_meta::add_json(&mut obj, &stats.models, options.pricing_db);
-            let estimated_cost = sum_estimated_proxy_model_costs(&stats.models, options.pricing_db);
-            if estimated_cost > 0.0 {
-                obj["cost_kind"] = serde_json::json!(model_cost_kind(&stats.models).as_str());
-                obj["estimated_cost"] = cost_json_value(estimated_cost, options.currency);
-            }
-        }
-        obj
+        sort_models_breakdown(&mut breakdown, options.show_cost);
+        (None, Some(breakdown), period_cost)
     } else {
         let period_cost = if options.show_cost {
-            Some(sum_display_model_costs(
-                &stats.models,
-                options.pricing_db,
-                options.cost_mode,
-            ))
+            sum_display_model_costs(&stats.models, options.pricing_db, options.cost_mode)
         } else {
-            None
+            0.0
         };
-        let mut models: Vec<_> = stats.models.keys().cloned().collect();
-        models.sort();
-        let mut obj = serde_json::json!({
-            (label): key,
-            "input_tokens": stats.stats.input_tokens,
-            "output_tokens": stats.stats.output_tokens,
-            "reasoning_tokens": stats.stats.reasoning_tokens,
-            "cache_creation_tokens": stats.stats.cache_creation,
-            "cache_read_tokens": stats.stats.cache_read,
-            "cache_hit_rate": cache_hit_rate_json_value(
-                stats.stats.cache_hit_rate(options.supports_cache_read)
-            ),
-            "total_tokens": stats.stats.total_tokens(),
-            "models": models,
-        });
-        if options.show_cost {
-            obj["cost"] = cost_json_value(period_cost.unwrap_or(0.0), options.currency);
-            pricing_meta::add_json(&mut obj, &stats.models, options.pricing_db);
-            let estimated_cost = sum_estimated_proxy_model_costs(&stats.models, options.pricing_db);
-            if estimated_cost > 0.0 {
-                obj["cost_kind"] = serde_json::json!(model_cost_kind(&stats.models).as_str());
-                obj["estimated_cost"] = cost_json_value(estimated_cost, options.currency);
-            }
-        }
-        obj
+        (Some(model_names(&stats.models)), None, period_cost)
+    };
+
+    PeriodJson {
+        schema_version: SCHEMA_VERSION,
+        period: period_key_json(period, key),
+        tokens: TokensJson::new(&stats.stats, options.supports_cache_read),
+        models,
+        breakdown,
+        cost: options.show_cost.then(|| {
+            CostJson::for_models(
+                &stats.models,
+                period_cost,
+                options.pricing_db,
+                options.currency,
+            )
+        }),
+        exchange_rate: options
+            .currency
+            .filter(|_| options.show_cost)
+            .map(ExchangeRateJson::new),
+        budget: None,
+        data_quality: None,
     }
 }
 
+#[derive(Clone, Copy)]
 struct PeriodJsonOptions<'a> {
     pricing_db: &'a PricingDb,
     breakdown: bool,
@@ -169,14 +165,6 @@ struct PeriodJsonOptions<'a> {
     cost_mode: CostDisplayMode,
 }
 
-fn data_quality_json(data_quality: DataQuality) -> serde_json::Value {
-    serde_json::json!({
-        "valid_entries": data_quality.valid_entries,
-        "dedup_skipped_entries": data_quality.dedup_skipped_entries,
-        "parse_errors": data_quality.parse_errors,
-    })
-}
-
 #[cfg(test)]
 pub(crate) fn output_period_json(
     day_stats: &HashMap<String, DayStats>,
@@ -187,7 +175,7 @@ pub(crate) fn output_period_json(
     show_cost: bool,
     currency: Option<&CurrencyConverter>,
 ) -> String {
-    output_period_json_with_quality(
+    let rows = period_json_rows(
         day_stats,
         period,
         pricing_db,
@@ -198,11 +186,13 @@ pub(crate) fn output_period_json(
         currency,
         None,
         CostDisplayMode::Total,
-    )
+    );
+    crate::output::schema::rows_to_string(&rows, false)
 }
 
+/// Typed rows of a `daily`, `weekly`, or `monthly` report.
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn output_period_json_with_quality(
+pub(crate) fn period_json_rows(
     day_stats: &HashMap<String, DayStats>,
     period: Period,
     pricing_db: &PricingDb,
@@ -213,8 +203,7 @@ pub(crate) fn output_period_json_with_quality(
     currency: Option<&CurrencyConverter>,
     data_quality: Option<DataQuality>,
     cost_mode: CostDisplayMode,
-) -> String {
-    let label = period.label();
+) -> Vec<PeriodJsonRow> {
     let aggregated;
     let stats_ref = if period == Period::Day {
         day_stats
@@ -223,7 +212,7 @@ pub(crate) fn output_period_json_with_quality(
         &aggregated
     };
 
-    let data_quality = data_quality.map(data_quality_json);
+    let data_quality = data_quality.map(DataQualityJson::from);
     let options = PeriodJsonOptions {
         pricing_db,
         breakdown,
@@ -232,34 +221,35 @@ pub(crate) fn output_period_json_with_quality(
         currency,
         cost_mode,
     };
-    let mut output: Vec<serde_json::Value> = stats_ref
-        .iter()
-        .map(|(key, stats)| {
-            let mut entry = build_period_entry(label, key, stats, &options);
-            if let Some(data_quality) = &data_quality {
-                entry["data_quality"] = data_quality.clone();
-            }
-            entry
+    let rates = PeriodRates::new(day_stats, period, currency, pricing_db, cost_mode);
+    let mut output: Vec<PeriodJsonRow> = sorted_keys(stats_ref, order)
+        .into_iter()
+        .map(|key| {
+            let row_options = PeriodJsonOptions {
+                currency: row_currency(rates.as_ref(), key, currency),
+                ..options
+            };
+            let mut entry = build_period_entry(period, key, &stats_ref[key], &row_options);
+            entry.data_quality = data_quality;
+            PeriodJsonRow::Period(Box::new(entry))
         })
         .collect();
 
     if output.is_empty()
         && let Some(data_quality) = data_quality
     {
-        output.push(serde_json::json!({ "data_quality": data_quality }));
+        output.push(PeriodJsonRow::DataQualityOnly {
+            schema_version: SCHEMA_VERSION,
+            data_quality,
+        });
     }
-
-    sort_output(&mut output, label, order);
-    serde_json::to_string(&output).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize JSON output: {e}");
-        "[]".to_string()
-    })
+    output
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::Stats;
+    use crate::output::schema::PricingJson;
 
     fn make_day_stats(models: &[(&str, i64)]) -> DayStats {
         let mut ds = DayStats::default();
@@ -275,6 +265,53 @@ mod tests {
         ds
     }
 
+    #[test]
+    fn period_json_reports_exchange_rate_per_row() {
+        let mut day_stats = HashMap::new();
+        day_stats.insert(
+            "2025-01-01".to_string(),
+            make_day_stats(&[("sonnet", 1_000_000)]),
+        );
+        day_stats.insert(
+            "2025-01-02".to_string(),
+            make_day_stats(&[("sonnet", 1_000_000)]),
+        );
+        let converter = CurrencyConverter::from_history_for_test(
+            "EUR",
+            &[("2025-01-01", 0.5), ("2025-01-02", 1.0)],
+        );
+        let db = PricingDb::default();
+
+        let json = output_period_json(
+            &day_stats,
+            Period::Day,
+            &db,
+            SortOrder::Asc,
+            false,
+            true,
+            Some(&converter),
+        );
+        let rows: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(rows[0]["currency"], "EUR");
+        assert_eq!(rows[0]["exchange_rate_source"], "file");
+        assert!((rows[0]["exchange_rate"].as_f64().unwrap() - 0.5).abs() < 1e-9);
+        assert!((rows[0]["cost"].as_f64().unwrap() - 5.25).abs() < 1e-9);
+        assert!((rows[1]["cost"].as_f64().unwrap() - 10.5).abs() < 1e-9);
+
+        let json = output_period_json(
+            &day_stats,
+            Period::Month,
+            &db,
+            SortOrder::Asc,
+            false,
+            true,
+            Some(&converter),
+        );
+        let rows: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert!((rows[0]["cost"].as_f64().unwrap() - 15.75).abs() < 1e-9);
+        assert!((rows[0]["exchange_rate"].as_f64().unwrap() - 0.75).abs() < 1e-9);
+    }
+
     #[test]
     fn period_label_mapping() {
         assert_eq!(Period::Day.label(), "date");
@@ -283,54 +320,65 @@ mod tests {
     }
 
     #[test]
-    fn sort_output_asc() {
-        let mut vals = vec![
-            serde_json::json!({"date": "2025-01-15"}),
-            serde_json::json!({"date": "2025-01-01"}),
-            serde_json::json!({"date": "2025-01-10"}),
-        ];
-        sort_output(&mut vals, "date", SortOrder::Asc);
-        let dates: Vec<&str> = vals.iter().map(|v| v["date"].as_str().unwrap()).collect();
-        assert_eq!(dates, vec!["2025-01-01", "2025-01-10", "2025-01-15"]);
+    fn sorted_keys_asc() {
+        let stats: HashMap<String, u8> = ["2025-01-15", "2025-01-01", "2025-01-10"]
+            .into_iter()
+            .map(|key| (key.to_string(), 0))
+            .collect();
+        let keys = sorted_keys(&stats, SortOrder::Asc);
+        assert_eq!(keys, vec!["2025-01-01", "2025-01-10", "2025-01-15"]);
     }
 
     #[test]
-    fn sort_output_desc() {
-        let mut vals = vec![
-            serde_json::json!({"date": "2025-01-01"}),
-            serde_json::json!({"date": "2025-01-15"}),
-        ];
-        sort_output(&mut vals, "date", SortOrder::Desc);
-        assert_eq!(vals[0]["date"].as_str().unwrap(), "2025-01-15");
+    fn sorted_keys_desc() {
+        let stats: HashMap<String, u8> = ["2025-01-01", "2025-01-15"]
+            .into_iter()
+            .map(|key| (key.to_string(), 0))
+            .collect();
+        let keys = sorted_keys(&stats, SortOrder::Desc);
+        assert_eq!(keys[0], "2025-01-15");
+    }
+
+    fn breakdown_row(model: &str, cost: Option<f64>) -> ModelBreakdownJson {
+        ModelBreakdownJson {
+            model: model.to_string(),
+            tokens: TokensJson::new(&Stats::default(), true),
+            cost: cost.map(|cost| CostJson {
+                cost: Some(cost),
+                pricing: PricingJson {
+                    pricing_source: "builtin",
+                    pricing_cache_age_seconds: None,
+                    pricing_cache_mtime_epoch_seconds: None,
+                    pricing_catalog: None,
+                },
+                cost_kind: None,
+                estimated_cost: None,
+            }),
+            service_tiers: None,
+        }
     }
 
     #[test]
     fn sort_models_breakdown_by_cost_desc() {
         let mut models = vec![
-            serde_json::json!({"model": "opus", "cost": 1.0}),
-            serde_json::json!({"model": "sonnet", "cost": 5.0}),
-            serde_json::json!({"model": "haiku", "cost": 0.5}),
+            breakdown_row("opus", Some(1.0)),
+            breakdown_row("sonnet", Some(5.0)),
+            breakdown_row("haiku", Some(0.5)),
         ];
         sort_models_breakdown(&mut models, true);
-        let names: Vec<&str> = models
-            .iter()
-            .map(|v| v["model"].as_str().unwrap())
-            .collect();
+        let names: Vec<&str> = models.iter().map(|row| row.model.as_str()).collect();
         assert_eq!(names, vec!["sonnet", "opus", "haiku"]);
     }
 
     #[test]
     fn sort_models_breakdown_by_name_when_no_cost() {
         let mut models = vec![
-            serde_json::json!({"model": "sonnet"}),
-            serde_json::json!({"model": "haiku"}),
-            serde_json::json!({"model": "opus"}),
+            breakdown_row("sonnet", None),
+            breakdown_row("haiku", None),
+            breakdown_row("opus", None),
         ];
         sort_models_breakdown(&mut models, false);
-        let names: Vec<&str> = models
-            .iter()
-            .map(|v| v["model"].as_str().unwrap())
-            .collect();
+        let names: Vec<&str> = models.iter().map(|row| row.model.as_str()).collect();
         assert_eq!(names, vec!["haiku", "opus", "sonnet"]);
     }
 
@@ -0,0 +1,952 @@
+//! Locale-aware presentation for table and text output: digit grouping,
+//! currency placement, period label dates, and translated labels.
+//!
+//! JSON and CSV output never go through a locale, so scripts see the same
+//! keys, dates, and numbers whatever `--locale` is set to.
+
+use std::fmt::Write as _;
+
+use chrono::NaiveDate;
+
+use crate::consts::DATE_FORMAT;
+use crate::error::AppError;
+use crate::output::period::Period;
+
+/// Language of translated labels. Locales without a translation use English.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    En,
+    Zh,
+    Ja,
+    Ko,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateStyle {
+    /// `2025-01-15`, the raw period key.
+    Iso,
+    /// `15.01.2025`
+    DayMonthYearDot,
+    /// `15/01/2025`
+    DayMonthYearSlash,
+    /// `15-01-2025`
+    DayMonthYearDash,
+    /// `2025年1月15日`
+    Cjk,
+    /// `2025. 1. 15.`
+    Korean,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Locale {
+    pub(super) group_sep: char,
+    pub(super) decimal_sep: char,
+    /// Currency symbol after the amount (`12,50 €`) rather than before.
+    symbol_after: bool,
+    date_style: DateStyle,
+    language: Language,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale {
+            group_sep: ',',
+            decimal_sep: '.',
+            symbol_after: false,
+            date_style: DateStyle::Iso,
+            language: Language::En,
+        }
+    }
+}
+
+impl Locale {
+    /// Parse a locale tag such as `ja`, `zh-CN`, or `de_DE`; only the
+    /// language part is used.
+    pub(crate) fn parse(tag: Option<&str>) -> Result<Self, AppError> {
+        let Some(raw) = tag else {
+            return Ok(Locale::default());
+        };
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Ok(Locale::default());
+        }
+        let base = trimmed
+            .split(['-', '_'])
+            .next()
+            .unwrap_or(trimmed)
+            .to_ascii_lowercase();
+
+        let european = |group_sep, symbol_after, date_style| Locale {
+            group_sep,
+            decimal_sep: ',',
+            symbol_after,
+            date_style,
+            language: Language::En,
+        };
+        let locale = match base.as_str() {
+            "en" => Locale::default(),
+            "zh" => Locale {
+                date_style: DateStyle::Cjk,
+                language: Language::Zh,
+                ..Locale::default()
+            },
+            "ja" => Locale {
+                date_style: DateStyle::Cjk,
+                language: Language::Ja,
+                ..Locale::default()
+            },
+            "ko" => Locale {
+                date_style: DateStyle::Korean,
+                language: Language::Ko,
+                ..Locale::default()
+            },
+            "de" => european('.', true, DateStyle::DayMonthYearDot),
+            "es" | "it" | "pt" => european('.', true, DateStyle::DayMonthYearSlash),
+            "nl" => european('.', false, DateStyle::DayMonthYearDash),
+            "fr" => european(' ', true, DateStyle::DayMonthYearSlash),
+            "ru" => european(' ', true, DateStyle::DayMonthYearDot),
+            _ => {
+                return Err(AppError::UnsupportedLocale {
+                    input: trimmed.to_string(),
+                });
+            }
+        };
+
+        Ok(locale)
+    }
+
+    /// Format a money amount with `decimals` places and the currency symbol
+    /// on the locale's side. Amounts are not digit-grouped.
+    pub(super) fn format_money(self, amount: f64, symbol: &str, decimals: usize) -> String {
+        let mut number = format!("{amount:.decimals$}");
+        if self.decimal_sep != '.' {
+            number = number.replace('.', &self.decimal_sep.to_string());
+        }
+        if self.symbol_after {
+            format!("{number} {}", symbol.trim())
+        } else {
+            format!("{symbol}{number}")
+        }
+    }
+
+    /// Display label for a period key (`2025-01-15`, or `2025-01` for
+    /// months). Keys that do not parse are shown unchanged.
+    pub(super) fn period_label(self, key: &str, period: Period) -> String {
+        if self.date_style == DateStyle::Iso {
+            return key.to_string();
+        }
+        let (date, with_day) = if period == Period::Month {
+            (
+                NaiveDate::parse_from_str(&format!("{key}-01"), DATE_FORMAT),
+                false,
+            )
+        } else {
+            (NaiveDate::parse_from_str(key, DATE_FORMAT), true)
+        };
+        let Ok(date) = date else {
+            return key.to_string();
+        };
+        let pattern = match (self.date_style, with_day) {
+            (DateStyle::Cjk, true) => "%Y年%-m月%-d日",
+            (DateStyle::Cjk, false) => "%Y年%-m月",
+            (DateStyle::Korean, true) => "%Y. %-m. %-d.",
+            (DateStyle::Korean, false) => "%Y. %-m.",
+            (DateStyle::DayMonthYearDot, true) => "%d.%m.%Y",
+            (DateStyle::DayMonthYearDot, false) => "%m.%Y",
+            (DateStyle::DayMonthYearSlash, true) => "%d/%m/%Y",
+            (DateStyle::DayMonthYearSlash, false) => "%m/%Y",
+            (DateStyle::DayMonthYearDash, true) => "%d-%m-%Y",
+            (DateStyle::DayMonthYearDash, false) => "%m-%Y",
+            (DateStyle::Iso, _) => return key.to_string(),
+        };
+        date.format(pattern).to_string()
+    }
+
+    /// Pick the text for the locale's language from English, Chinese,
+    /// Japanese, and Korean variants.
+    fn pick<T>(self, [en, zh, ja, ko]: [T; 4]) -> T {
+        match self.language {
+            Language::En => en,
+            Language::Zh => zh,
+            Language::Ja => ja,
+            Language::Ko => ko,
+        }
+    }
+
+    /// Translated label.
+    pub(super) fn text(self, msg: Msg) -> &'static str {
+        self.pick(msg.texts())
+    }
+
+    /// Footer line counting the records a report read.
+    pub(super) fn usage_records(self, valid: &str, skipped: &str) -> String {
+        match self.language {
+            Language::En => {
+                format!("{valid} usage records ({skipped} streaming records deduplicated)")
+            }
+            Language::Zh => format!("{valid} 条使用记录（已去重 {skipped} 条流式记录）"),
+            Language::Ja => {
+                format!("使用記録 {valid} 件（重複排除したストリーミング記録 {skipped} 件）")
+            }
+            Language::Ko => {
+                format!("사용 기록 {valid}건 (중복 제거된 스트리밍 기록 {skipped}건)")
+            }
+        }
+    }
+
+    /// Footer line of the session table.
+    pub(super) fn session_count(self, sessions: &str) -> String {
+        match self.language {
+            Language::En => format!("{sessions} sessions"),
+            Language::Zh => format!("{sessions} 个会话"),
+            Language::Ja => format!("セッション {sessions} 件"),
+            Language::Ko => format!("세션 {sessions}개"),
+        }
+    }
+
+    /// Footer line of the project table.
+    pub(super) fn project_count(self, projects: &str, sessions: &str) -> String {
+        match self.language {
+            Language::En => format!("{projects} projects, {sessions} sessions"),
+            Language::Zh => format!("{projects} 个项目，{sessions} 个会话"),
+            Language::Ja => format!("プロジェクト {projects} 件、セッション {sessions} 件"),
+            Language::Ko => format!("프로젝트 {projects}개, 세션 {sessions}개"),
+        }
+    }
+
+    /// Footer line of the billing block table.
+    pub(super) fn block_count(self, blocks: &str) -> String {
+        match self.language {
+            Language::En => format!("{blocks} blocks"),
+            Language::Zh => format!("{blocks} 个时段"),
+            Language::Ja => format!("ブロック {blocks} 件"),
+            Language::Ko => format!("블록 {blocks}개"),
+        }
+    }
+
+    /// Heading of the `top` leaderboard; `of` is the number of ranked rows
+    /// when the table shows only some of them.
+    pub(super) fn top_heading(
+        self,
+        source: &str,
+        shown: usize,
+        of: Option<usize>,
+        dim: Msg,
+        by_cost: bool,
+    ) -> String {
+        let noun = self.text(dim);
+        match self.language {
+            Language::En => {
+                let of = of.map(|of| format!(" of {of}")).unwrap_or_default();
+                let basis = if by_cost { "cost" } else { "tokens" };
+                format!(
+                    "{source} top {shown}{of} {}(s) — by {basis}",
+                    noun.to_lowercase()
+                )
+            }
+            Language::Zh => {
+                let of = of.map(|of| format!("（共 {of} 个）")).unwrap_or_default();
+                let basis = if by_cost { "按费用" } else { "按 Token" };
+                format!("{source} 前 {shown} 个{noun}{of} — {basis}")
+            }
+            Language::Ja => {
+                let of = of.map(|of| format!("（全 {of} 件）")).unwrap_or_default();
+                let basis = if by_cost {
+                    "コスト順"
+                } else {
+                    "トークン順"
+                };
+                format!("{source} 上位 {shown} {noun}{of} — {basis}")
+            }
+            Language::Ko => {
+                let of = of.map(|of| format!(" (전체 {of}개)")).unwrap_or_default();
+                let basis = if by_cost { "비용순" } else { "토큰순" };
+                format!("{source} 상위 {shown}개 {noun}{of} — {basis}")
+            }
+        }
+    }
+
+    /// Line shown instead of an empty `top` leaderboard.
+    pub(super) fn nothing_to_rank(self, dim: Msg, source: &str) -> String {
+        let noun = self.text(dim);
+        match self.language {
+            Language::En => format!("No {} usage to rank for {source}.", noun.to_lowercase()),
+            Language::Zh => format!("{source} 没有可排名的{noun}用量。"),
+            Language::Ja => format!("{source} にランク付けできる{noun}の使用量はありません。"),
+            Language::Ko => format!("{source}에 순위를 매길 {noun} 사용량이 없습니다."),
+        }
+    }
+
+    /// Message printed when a command finds no data in the date range.
+    pub(crate) fn no_data_hint(self, source: &str, kind: DataKind) -> String {
+        let noun = self.pick(kind.nouns());
+        match self.language {
+            Language::En => format!(
+                "No {source} {noun} data found in the selected date range.\nHint: widen --since/--until, try `today`, or run `ccstats sources` to pick a different --source."
+            ),
+            Language::Zh => format!(
+                "所选日期范围内没有 {source} 的{noun}数据。\n提示：放宽 --since/--until，试试 `today`，或运行 `ccstats sources` 选择其他 --source。"
+            ),
+            Language::Ja => format!(
+                "選択した期間に {source} の{noun}データが見つかりません。\nヒント: --since/--until を広げるか、`today` を試すか、`ccstats sources` で別の --source を選んでください。"
+            ),
+            Language::Ko => format!(
+                "선택한 기간에 {source} {noun} 데이터가 없습니다.\n힌트: --since/--until 범위를 넓히거나, `today`를 시도하거나, `ccstats sources`를 실행해 다른 --source를 고르세요."
+            ),
+        }
+    }
+
+    /// Summary line of `audit pricing`. `tokens` is already formatted.
+    pub(super) fn unpriced_usage(
+        self,
+        tokens: &str,
+        pct: f64,
+        unpriced_models: usize,
+        models: usize,
+        include_fallback: bool,
+    ) -> String {
+        match self.language {
+            Language::En => format!(
+                "Unpriced usage: {tokens} tokens ({pct:.1}%) across {unpriced_models} of {models} models{}.",
+                if include_fallback {
+                    ", counting fallback estimates as unpriced"
+                } else {
+                    ""
+                }
+            ),
+            Language::Zh => format!(
+                "未定价用量：{tokens} 个 Token（{pct:.1}%），涉及 {models} 个模型中的 {unpriced_models} 个{}。",
+                if include_fallback {
+                    "，回退估算计为未定价"
+                } else {
+                    ""
+                }
+            ),
+            Language::Ja => format!(
+                "価格未設定の使用量: {tokens} トークン（{pct:.1}%）、{models} モデル中 {unpriced_models} モデル{}。",
+                if include_fallback {
+                    "、フォールバック推定を未設定として計上"
+                } else {
+                    ""
+                }
+            ),
+            Language::Ko => format!(
+                "가격 미설정 사용량: {tokens} 토큰 ({pct:.1}%), 모델 {models}개 중 {unpriced_models}개{}.",
+                if include_fallback {
+                    ", 대체 추정치를 미설정으로 계산"
+                } else {
+                    ""
+                }
+            ),
+        }
+    }
+
+    /// Note under the `agents` table; `by_cost` when the share is of cost.
+    pub(super) fn subagent_share_note(self, by_cost: bool) -> String {
+        match self.language {
+            Language::En => format!(
+                "Subagent % is the share of each session's {} spent by subagents.",
+                if by_cost { "cost" } else { "tokens" }
+            ),
+            Language::Zh => format!(
+                "子代理占比为每个会话的{}中由子代理消耗的比例。",
+                if by_cost { "费用" } else { "Token" }
+            ),
+            Language::Ja => format!(
+                "サブエージェント % は各セッションの{}のうちサブエージェントが使った割合です。",
+                if by_cost { "コスト" } else { "トークン" }
+            ),
+            Language::Ko => format!(
+                "서브에이전트 %는 각 세션의 {} 중 서브에이전트가 사용한 비율입니다.",
+                if by_cost { "비용" } else { "토큰" }
+            ),
+        }
+    }
+
+    /// First line of `session show`. `requests` is already formatted.
+    pub(super) fn timeline_heading(
+        self,
+        session: &str,
+        project: &str,
+        span: (&str, &str),
+        requests: &str,
+    ) -> String {
+        let (start, end) = span;
+        match self.language {
+            Language::En => {
+                format!("Session {session} ({project}) · {start} → {end} · {requests} requests")
+            }
+            Language::Zh => {
+                format!("会话 {session}（{project}）· {start} → {end} · {requests} 次请求")
+            }
+            Language::Ja => format!(
+                "セッション {session}（{project}）· {start} → {end} · リクエスト {requests} 件"
+            ),
+            Language::Ko => {
+                format!("세션 {session} ({project}) · {start} → {end} · 요청 {requests}건")
+            }
+        }
+    }
+
+    /// Separator row of `session show` for an idle gap of `duration`.
+    pub(super) fn idle_gap(self, duration: &str) -> String {
+        let idle = self.pick(["idle", "空闲", "アイドル", "유휴"]);
+        format!("{idle} {duration}")
+    }
+
+    /// Summary line of `session show`. Numbers and `total_cost` are already
+    /// formatted.
+    pub(super) fn timeline_summary(
+        self,
+        peak: &str,
+        subagent_segments: usize,
+        (gaps, idle): (usize, &str),
+        total_cost: Option<&str>,
+    ) -> String {
+        let mut summary = match self.language {
+            Language::En => format!(
+                "Peak context: {peak} tokens · Subagent segments: {subagent_segments} · Idle gaps: {gaps} ({idle})"
+            ),
+            Language::Zh => format!(
+                "峰值上下文：{peak} Token · 子代理片段：{subagent_segments} · 空闲间隔：{gaps}（{idle}）"
+            ),
+            Language::Ja => format!(
+                "最大コンテキスト: {peak} トークン · サブエージェント区間: {subagent_segments} · アイドル: {gaps} 回（{idle}）"
+            ),
+            Language::Ko => format!(
+                "최대 컨텍스트: {peak} 토큰 · 서브에이전트 구간: {subagent_segments} · 유휴 구간: {gaps}개 ({idle})"
+            ),
+        };
+        if let Some(cost) = total_cost {
+            let label = self.pick(["Total cost: ", "总费用：", "合計コスト: ", "총 비용: "]);
+            let _ = write!(summary, " · {label}{cost}");
+        }
+        summary
+    }
+
+    /// Header of the `session --context` column counting turns at or above
+    /// the threshold. `threshold` is already formatted.
+    pub(super) fn turns_at_or_above(self, threshold: &str) -> String {
+        let turns = self.pick(["Turns", "轮次", "ターン", "턴"]);
+        format!("≥{threshold} {turns}")
+    }
+
+    /// Summary line of `quota --history`.
+    pub(super) fn quota_history_summary(self, windows: usize, exhausted: usize) -> String {
+        match self.language {
+            Language::En => format!(
+                "{windows} windows, {exhausted} reached 100% · Percentages are provider-reported snapshots."
+            ),
+            Language::Zh => {
+                format!("{windows} 个窗口，{exhausted} 个达到 100% · 百分比为服务商报告的快照。")
+            }
+            Language::Ja => format!(
+                "ウィンドウ {windows} 件、100% 到達 {exhausted} 件 · 割合はプロバイダー報告のスナップショットです。"
+            ),
+            Language::Ko => format!(
+                "기간 {windows}개, 100% 도달 {exhausted}개 · 비율은 제공자가 보고한 스냅샷입니다."
+            ),
+        }
+    }
+
+    /// Line counting the rate-limit records `quota --history` skipped.
+    pub(super) fn skipped_quota_records(self, records: usize) -> String {
+        match self.language {
+            Language::En => format!("Skipped {records} malformed rate-limit records."),
+            Language::Zh => format!("已跳过 {records} 条格式错误的速率限制记录。"),
+            Language::Ja => {
+                format!("不正な形式のレート制限記録 {records} 件をスキップしました。")
+            }
+            Language::Ko => {
+                format!("형식이 잘못된 속도 제한 기록 {records}건을 건너뛰었습니다.")
+            }
+        }
+    }
+}
+
+/// Kind of data a command looked for, named in [`Locale::no_data_hint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DataKind {
+    Usage,
+    Session,
+    Project,
+    BillingBlock,
+    Endpoint,
+    Agent,
+}
+
+impl DataKind {
+    /// English, Chinese, Japanese, and Korean nouns.
+    fn nouns(self) -> [&'static str; 4] {
+        match self {
+            DataKind::Usage => ["usage", "用量", "使用量", "사용량"],
+            DataKind::Session => ["session", "会话", "セッション", "세션"],
+            DataKind::Project => ["project", "项目", "プロジェクト", "프로젝트"],
+            DataKind::BillingBlock => ["billing block", "计费时段", "課金ブロック", "과금 블록"],
+            DataKind::Endpoint => ["endpoint", "端点", "エンドポイント", "엔드포인트"],
+            DataKind::Agent => ["agent", "代理", "エージェント", "에이전트"],
+        }
+    }
+}
+
+/// Labels shown in translated tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Msg {
+    Date,
+    Week,
+    Month,
+    TokenUsage,
+    WeeklyTokenUsage,
+    MonthlyTokenUsage,
+    SessionUsage,
+    ProjectUsage,
+    BillingBlocks,
+    MonthlyBudgetForecast,
+    Model,
+    Models,
+    Session,
+    Sessions,
+    Project,
+    Block,
+    Source,
+    Category,
+    Server,
+    Tool,
+    Calls,
+    In,
+    Out,
+    Input,
+    Output,
+    Reason,
+    CacheCreation,
+    CacheCreate,
+    CacheRead,
+    CacheHit,
+    Total,
+    Share,
+    Cost,
+    TotalRow,
+    LastUsed,
+    AvgResult,
+    Window,
+    Budget,
+    Spent,
+    Used,
+    Remaining,
+    Projected,
+    Status,
+    Resets,
+    EstDepletion,
+    EstimatedProxyExcluded,
+    EstimatedProxyIncluded,
+    EstimatedProxyExcludedFromRanking,
+    NoToolUsage,
+    ToolTokenLegend,
+    UsageByAgent,
+    MainCalls,
+    SubagentCalls,
+    MainTokens,
+    SubagentTokens,
+    MainCost,
+    SubagentCost,
+    SubagentPct,
+    SubagentTypes,
+    File,
+    Files,
+    Directory,
+    Reads,
+    Writes,
+    HotDirectories,
+    NoFileTouches,
+    Command,
+    Runs,
+    PctOfProject,
+    Risk,
+    LastSeen,
+    RiskyCommands,
+    NoShellCommands,
+    NoRiskyCommands,
+    Time,
+    Thread,
+    CacheW,
+    CacheR,
+    Context,
+    Cumulative,
+    Stop,
+    Tools,
+    SessionContext,
+    NoSessionContext,
+    Turns,
+    Peak,
+    Average,
+    Compactions,
+    PctOfTurns,
+    PricingAudit,
+    Resolution,
+    MatchedKey,
+    Tokens,
+    TokenPct,
+    CostPct,
+    Started,
+    Last,
+    Hit100,
+    Trend,
+    Active,
+}
+
+impl Msg {
+    /// English, Chinese, Japanese, and Korean text.
+    #[allow(clippy::too_many_lines)]
+    fn texts(self) -> [&'static str; 4] {
+        match self {
+            Msg::Date => ["Date", "日期", "日付", "날짜"],
+            Msg::Week => ["Week", "周", "週", "주"],
+            Msg::Month => ["Month", "月份", "月", "월"],
+            Msg::TokenUsage => ["Token Usage", "Token 用量", "トークン使用量", "토큰 사용량"],
+            Msg::WeeklyTokenUsage => [
+                "Weekly Token Usage",
+                "每周 Token 用量",
+                "週間トークン使用量",
+                "주간 토큰 사용량",
+            ],
+            Msg::MonthlyTokenUsage => [
+                "Monthly Token Usage",
+                "每月 Token 用量",
+                "月間トークン使用量",
+                "월간 토큰 사용량",
+            ],
+            Msg::SessionUsage => [
+                "Session Usage",
+                "会话用量",
+                "セッション使用量",
+                "세션 사용량",
+            ],
+            Msg::ProjectUsage => [
+                "Project Usage",
+                "项目用量",
+                "プロジェクト使用量",
+                "프로젝트 사용량",
+            ],
+            Msg::BillingBlocks => [
+                "5-Hour Billing Blocks",
+                "5 小时计费时段",
+                "5 時間課金ブロック",
+                "5시간 과금 블록",
+            ],
+            Msg::MonthlyBudgetForecast => [
+                "Monthly Budget Forecast",
+                "每月预算预测",
+                "月間予算予測",
+                "월간 예산 예측",
+            ],
+            Msg::Model => ["Model", "模型", "モデル", "모델"],
+            Msg::Models => ["Models", "模型", "モデル", "모델"],
+            Msg::Session => ["Session", "会话", "セッション", "세션"],
+            Msg::Sessions => ["Sessions", "会话数", "セッション数", "세션 수"],
+            Msg::Project => ["Project", "项目", "プロジェクト", "프로젝트"],
+            Msg::Block => ["Block", "时段", "ブロック", "블록"],
+            Msg::Source => ["Source", "来源", "ソース", "소스"],
+            Msg::Category => ["Category", "类别", "カテゴリ", "분류"],
+            Msg::Server => ["Server", "服务器", "サーバー", "서버"],
+            Msg::Tool => ["Tool", "工具", "ツール", "도구"],
+            Msg::Calls => ["Calls", "调用", "呼び出し", "호출"],
+            Msg::In => ["In", "输入", "入力", "입력"],
+            Msg::Out => ["Out", "输出", "出力", "출력"],
+            Msg::Input => ["Input", "输入", "入力", "입력"],
+            Msg::Output => ["Output", "输出", "出力", "출력"],
+            Msg::Reason => ["Reason", "推理", "推論", "추론"],
+            Msg::CacheCreation => ["Cache Creation", "缓存写入", "キャッシュ作成", "캐시 생성"],
+            Msg::CacheCreate => ["Cache Create", "缓存写入", "キャッシュ作成", "캐시 생성"],
+            Msg::CacheRead => ["Cache Read", "缓存读取", "キャッシュ読込", "캐시 읽기"],
+            Msg::CacheHit => ["Cache Hit", "缓存命中", "キャッシュヒット", "캐시 적중"],
+            Msg::Total => ["Total", "合计", "合計", "합계"],
+            Msg::Share => ["Share", "占比", "割合", "비중"],
+            Msg::Cost => ["Cost", "费用", "コスト", "비용"],
+            Msg::TotalRow => ["TOTAL", "总计", "総計", "총계"],
+            Msg::LastUsed => ["Last Used", "最近使用", "最終使用", "최근 사용"],
+            Msg::AvgResult => ["Avg Result", "平均结果", "平均結果", "평균 결과"],
+            Msg::Window => ["Window", "窗口", "ウィンドウ", "기간"],
+            Msg::Budget => ["Budget", "预算", "予算", "예산"],
+            Msg::Spent => ["Spent", "已花费", "支出", "지출"],
+            Msg::Used => ["Used", "已用", "使用済み", "사용"],
+            Msg::Remaining => ["Remaining", "剩余", "残り", "남음"],
+            Msg::Projected => ["Projected", "预计", "予測", "예상"],
+            Msg::Status => ["Status", "状态", "状態", "상태"],
+            Msg::Resets => ["Resets", "重置", "リセット", "초기화"],
+            Msg::EstDepletion => ["Est. depletion", "预计耗尽", "枯渇予測", "예상 소진"],
+            Msg::EstimatedProxyExcluded => [
+                "Estimated proxy cost excluded from Cost total",
+                "费用合计未包含的估算代理费用",
+                "コスト合計に含まれない推定プロキシ費用",
+                "비용 합계에서 제외된 추정 프록시 비용",
+            ],
+            Msg::EstimatedProxyIncluded => [
+                "Cost includes estimated proxy values",
+                "费用包含的估算代理费用",
+                "コストに含まれる推定プロキシ費用",
+                "비용에 포함된 추정 프록시 비용",
+            ],
+            Msg::EstimatedProxyExcludedFromRanking => [
+                "Estimated proxy cost excluded from Cost ranking",
+                "费用排名未包含的估算代理费用",
+                "コスト順位に含まれない推定プロキシ費用",
+                "비용 순위에서 제외된 추정 프록시 비용",
+            ],
+            Msg::NoToolUsage => [
+                "No tool usage data found.",
+                "未找到工具使用数据。",
+                "ツールの使用データが見つかりません。",
+                "도구 사용 데이터가 없습니다.",
+            ],
+            Msg::ToolTokenLegend => [
+                "Input: tokens tool results added to the next request · Output: each message's output split across its tool calls",
+                "输入：工具结果加入下一次请求的 Token · 输出：每条消息的输出按其工具调用分摊",
+                "入力: ツール結果が次のリクエストに加えたトークン · 出力: 各メッセージの出力をそのツール呼び出しに按分",
+                "입력: 도구 결과가 다음 요청에 더한 토큰 · 출력: 각 메시지의 출력을 해당 도구 호출에 나눈 값",
+            ],
+            Msg::UsageByAgent => [
+                "Usage by Agent",
+                "按代理划分的用量",
+                "エージェント別使用量",
+                "에이전트별 사용량",
+            ],
+            Msg::MainCalls => ["Main Calls", "主线程调用", "メイン呼び出し", "메인 호출"],
+            Msg::SubagentCalls => [
+                "Subagent Calls",
+                "子代理调用",
+                "サブエージェント呼び出し",
+                "서브에이전트 호출",
+            ],
+            Msg::MainTokens => ["Main Tokens", "主线程 Token", "メイントークン", "메인 토큰"],
+            Msg::SubagentTokens => [
+                "Subagent Tokens",
+                "子代理 Token",
+                "サブエージェントトークン",
+                "서브에이전트 토큰",
+            ],
+            Msg::MainCost => ["Main Cost", "主线程费用", "メインコスト", "메인 비용"],
+            Msg::SubagentCost => [
+                "Subagent Cost",
+                "子代理费用",
+                "サブエージェントコスト",
+                "서브에이전트 비용",
+            ],
+            Msg::SubagentPct => [
+                "Subagent %",
+                "子代理占比",
+                "サブエージェント %",
+                "서브에이전트 %",
+            ],
+            Msg::SubagentTypes => [
+                "Subagent Types",
+                "子代理类型",
+                "サブエージェント種別",
+                "서브에이전트 유형",
+            ],
+            Msg::File => ["File", "文件", "ファイル", "파일"],
+            Msg::Files => ["Files", "文件数", "ファイル数", "파일 수"],
+            Msg::Directory => ["Directory", "目录", "ディレクトリ", "디렉터리"],
+            Msg::Reads => ["Reads", "读取", "読み取り", "읽기"],
+            Msg::Writes => ["Writes", "写入", "書き込み", "쓰기"],
+            Msg::HotDirectories => [
+                "Hot directories",
+                "热点目录",
+                "よく使うディレクトリ",
+                "자주 쓰는 디렉터리",
+            ],
+            Msg::NoFileTouches => [
+                "No file reads or writes found.",
+                "未找到文件读写。",
+                "ファイルの読み書きが見つかりません。",
+                "파일 읽기/쓰기가 없습니다.",
+            ],
+            Msg::Command => ["Command", "命令", "コマンド", "명령"],
+            Msg::Runs => ["Runs", "次数", "実行回数", "실행 횟수"],
+            Msg::PctOfProject => [
+                "% of Project",
+                "项目占比",
+                "プロジェクト内 %",
+                "프로젝트 내 %",
+            ],
+            Msg::Risk => ["Risk", "风险", "リスク", "위험"],
+            Msg::LastSeen => ["Last Seen", "最近出现", "最終実行", "최근 실행"],
+            Msg::RiskyCommands => [
+                "Risky commands",
+                "高风险命令",
+                "危険なコマンド",
+                "위험한 명령",
+            ],
+            Msg::NoShellCommands => [
+                "No shell commands found.",
+                "未找到 Shell 命令。",
+                "シェルコマンドが見つかりません。",
+                "셸 명령이 없습니다.",
+            ],
+            Msg::NoRiskyCommands => [
+                "No risky commands found.",
+                "未找到高风险命令。",
+                "危険なコマンドは見つかりません。",
+                "위험한 명령이 없습니다.",
+            ],
+            Msg::Time => ["Time", "时间", "時刻", "시간"],
+            Msg::Thread => ["Thread", "线程", "スレッド", "스레드"],
+            Msg::CacheW => ["Cache W", "缓存写", "キャッシュ書込", "캐시 쓰기"],
+            Msg::CacheR => ["Cache R", "缓存读", "キャッシュ読込", "캐시 읽기"],
+            Msg::Context => ["Context", "上下文", "コンテキスト", "컨텍스트"],
+            Msg::Cumulative => ["Cumulative", "累计", "累計", "누적"],
+            Msg::Stop => ["Stop", "停止原因", "停止理由", "중단 사유"],
+            Msg::Tools => ["Tools", "工具", "ツール", "도구"],
+            Msg::SessionContext => [
+                "Session Context",
+                "会话上下文",
+                "セッションコンテキスト",
+                "세션 컨텍스트",
+            ],
+            Msg::NoSessionContext => [
+                "No session context data found.",
+                "未找到会话上下文数据。",
+                "セッションのコンテキストデータが見つかりません。",
+                "세션 컨텍스트 데이터가 없습니다.",
+            ],
+            Msg::Turns => ["Turns", "轮次", "ターン", "턴"],
+            Msg::Peak => ["Peak", "峰值", "最大", "최대"],
+            Msg::Average => ["Average", "平均", "平均", "평균"],
+            Msg::Compactions => ["Compactions", "压缩", "圧縮", "압축"],
+            Msg::PctOfTurns => ["% of Turns", "轮次占比", "ターン比率", "턴 비율"],
+            Msg::PricingAudit => ["Pricing Audit", "定价审计", "価格監査", "가격 감사"],
+            Msg::Resolution => ["Resolution", "解析方式", "解決方法", "해석 방식"],
+            Msg::MatchedKey => ["Matched Key", "匹配键", "一致キー", "일치 키"],
+            Msg::Tokens => ["Tokens", "Token", "トークン", "토큰"],
+            Msg::TokenPct => ["Token %", "Token 占比", "トークン %", "토큰 %"],
+            Msg::CostPct => ["Cost %", "费用占比", "コスト %", "비용 %"],
+            Msg::Started => ["Started", "开始", "開始", "시작"],
+            Msg::Last => ["Last", "最新", "最新", "최근"],
+            Msg::Hit100 => ["Hit 100%", "达到 100%", "100% 到達", "100% 도달"],
+            Msg::Trend => ["Trend", "趋势", "推移", "추세"],
+            Msg::Active => ["active", "进行中", "進行中", "진행 중"],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_covers_grouping_for_more_locales() {
+        for tag in ["ja", "ko", "zh-CN", "en_US"] {
+            assert_eq!(Locale::parse(Some(tag)).unwrap().group_sep, ',', "{tag}");
+        }
+        for tag in ["es", "it", "pt-BR", "nl", "de"] {
+            let locale = Locale::parse(Some(tag)).unwrap();
+            assert_eq!((locale.group_sep, locale.decimal_sep), ('.', ','), "{tag}");
+        }
+        assert!(Locale::parse(Some("xx")).is_err());
+    }
+
+    #[test]
+    fn format_money_places_symbol_by_locale() {
+        let en = Locale::default();
+        assert_eq!(en.format_money(12.5, "$", 2), "$12.50");
+        assert_eq!(en.format_money(-1.234, "$", 2), "$-1.23");
+        let de = Locale::parse(Some("de")).unwrap();
+        assert_eq!(de.format_money(12.5, "€", 2), "12,50 €");
+        assert_eq!(de.format_money(3.0, "XYZ ", 2), "3,00 XYZ");
+        let ja = Locale::parse(Some("ja")).unwrap();
+        assert_eq!(ja.format_money(1234.4, "¥", 0), "¥1234");
+    }
+
+    #[test]
+    fn period_label_follows_date_style() {
+        let zh = Locale::parse(Some("zh")).unwrap();
+        assert_eq!(zh.period_label("2025-01-05", Period::Day), "2025年1月5日");
+        assert_eq!(zh.period_label("2025-01", Period::Month), "2025年1月");
+        let de = Locale::parse(Some("de")).unwrap();
+        assert_eq!(de.period_label("2025-01-05", Period::Week), "05.01.2025");
+        assert_eq!(de.period_label("2025-01", Period::Month), "01.2025");
+        assert_eq!(
+            Locale::default().period_label("2025-01-05", Period::Day),
+            "2025-01-05"
+        );
+        assert_eq!(zh.period_label("unknown", Period::Day), "unknown");
+    }
+
+    #[test]
+    fn text_translates_for_zh_and_ja() {
+        let en = Locale::default();
+        let zh = Locale::parse(Some("zh")).unwrap();
+        let ja = Locale::parse(Some("ja")).unwrap();
+        let fr = Locale::parse(Some("fr")).unwrap();
+        assert_eq!(en.text(Msg::Cost), "Cost");
+        assert_eq!(zh.text(Msg::Cost), "费用");
+        assert_eq!(ja.text(Msg::Cost), "コスト");
+        assert_eq!(fr.text(Msg::Cost), "Cost");
+        assert_eq!(
+            zh.usage_records("10", "2"),
+            "10 条使用记录（已去重 2 条流式记录）"
+        );
+    }
+
+    #[test]
+    fn korean_is_its_own_locale() {
+        let ko = Locale::parse(Some("ko-KR")).unwrap();
+        assert_eq!(ko.language, Language::Ko);
+        assert_eq!(ko.text(Msg::Cost), "비용");
+        assert_eq!(ko.text(Msg::SessionUsage), "세션 사용량");
+        assert_eq!(ko.period_label("2025-01-05", Period::Day), "2025. 1. 5.");
+        assert_eq!(ko.period_label("2025-01", Period::Month), "2025. 1.");
+        assert_eq!(ko.format_money(1234.5, "₩", 0), "₩1234");
+        assert_eq!(ko.session_count("3"), "세션 3개");
+        assert!(
+            ko.no_data_hint("Codex", DataKind::Session)
+                .starts_with("선택한 기간에 Codex 세션 데이터가 없습니다.")
+        );
+        assert_eq!(ko.turns_at_or_above("150.0K"), "≥150.0K 턴");
+        assert_eq!(
+            ko.quota_history_summary(3, 1),
+            "기간 3개, 100% 도달 1개 · 비율은 제공자가 보고한 스냅샷입니다."
+        );
+    }
+
+    #[test]
+    fn timeline_lines_translate_for_ja() {
+        let ja = Locale::parse(Some("ja")).unwrap();
+        assert_eq!(
+            ja.timeline_heading("abc", "myapp", ("2026-02-06 12:00", "12:30"), "3"),
+            "セッション abc（myapp）· 2026-02-06 12:00 → 12:30 · リクエスト 3 件"
+        );
+        assert_eq!(
+            ja.timeline_summary("2,000", 1, (1, "29m"), Some("$1.00")),
+            "最大コンテキスト: 2,000 トークン · サブエージェント区間: 1 · アイドル: 1 回（29m） · 合計コスト: $1.00"
+        );
+        assert_eq!(
+            Locale::default().timeline_summary("2,000", 1, (1, "29m"), None),
+            "Peak context: 2,000 tokens · Subagent segments: 1 · Idle gaps: 1 (29m)"
+        );
+    }
+
+    #[test]
+    fn hints_and_footers_keep_english_wording() {
+        let en = Locale::default();
+        assert_eq!(
+            en.no_data_hint("Codex", DataKind::BillingBlock)
+                .lines()
+                .next(),
+            Some("No Codex billing block data found in the selected date range.")
+        );
+        assert_eq!(en.project_count("2", "5"), "2 projects, 5 sessions");
+        assert_eq!(
+            en.top_heading("Codex", 3, Some(7), Msg::Model, true),
+            "Codex top 3 of 7 model(s) — by cost"
+        );
+        assert_eq!(
+            en.unpriced_usage("1,000", 12.5, 1, 4, true),
+            "Unpriced usage: 1,000 tokens (12.5%) across 1 of 4 models, counting fallback estimates as unpriced."
+        );
+        let ja = Locale::parse(Some("ja")).unwrap();
+        assert_eq!(
+            ja.top_heading("Codex", 3, None, Msg::Project, false),
+            "Codex 上位 3 プロジェクト — トークン順"
+        );
+    }
+}
@@ -0,0 +1,407 @@
+//! Markdown and HTML rendering of table views (`--format markdown|html`).
+//!
+//! Table renderers build the same comfy-table `Table` whatever the markup and
+//! hand it, with their title and notes, to [`Markup`]. Terminal output is
+//! printed unchanged; Markdown becomes GitHub tables, and HTML fragments are
+//! wrapped in one standalone page with inline CSS and sortable columns.
+
+use std::fmt::Write as _;
+
+use comfy_table::{Cell, Row, Table};
+
+use super::format::Locale;
+
+/// How a table view is written out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Markup {
+    /// Box-drawn tables for the terminal.
+    #[default]
+    Terminal,
+    /// GitHub-flavored Markdown tables.
+    Markdown,
+    /// HTML fragments inside the page from [`print_html_page_start`].
+    Html,
+}
+
+impl Markup {
+    /// Report title, shown as `\n  {title}\n` in the terminal.
+    pub(crate) fn title(self, title: &str) {
+        print!("{}", self.render_title(title));
+    }
+
+    /// Print a table. The last `footer_rows` rows (totals) stay below the
+    /// sortable body in HTML; `locale` is needed to sort numbers.
+    pub(crate) fn table(self, table: &Table, footer_rows: usize, locale: Locale) {
+        print!("{}", self.render_table(table, footer_rows, locale));
+    }
+
+    /// Print a note or summary line. `text` is the terminal form; Markdown and
+    /// HTML drop its blank lines and indentation.
+    pub(crate) fn line(self, text: &str) {
+        print!("{}", self.render_line(text));
+    }
+
+    fn render_title(self, title: &str) -> String {
+        match self {
+            Markup::Terminal => format!("\n  {title}\n\n"),
+            Markup::Markdown => format!("## {title}\n\n"),
+            Markup::Html => format!("<h2>{}</h2>\n", escape_html(title)),
+        }
+    }
+
+    fn render_line(self, text: &str) -> String {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        match self {
+            Markup::Terminal => format!("{text}\n"),
+            _ if lines.is_empty() => String::new(),
+            Markup::Markdown => format!("{}\n\n", lines.join("  \n")),
+            Markup::Html => {
+                let lines: Vec<String> = lines.into_iter().map(escape_html).collect();
+                format!("<p>{}</p>\n", lines.join("<br>"))
+            }
+        }
+    }
+
+    fn render_table(self, table: &Table, footer_rows: usize, locale: Locale) -> String {
+        let header: Vec<String> = table
+            .header()
+            .map(|row| row.cell_iter().map(Cell::content).collect())
+            .unwrap_or_default();
+        let rows: Vec<Vec<String>> = table
+            .row_iter()
+            .map(|row: &Row| row.cell_iter().map(Cell::content).collect())
+            .collect();
+        match self {
+            Markup::Terminal => format!("{table}\n"),
+            Markup::Markdown => markdown_table(&header, &rows, locale),
+            Markup::Html => html_table(&header, &rows, footer_rows, locale),
+        }
+    }
+}
+
+fn markdown_table(header: &[String], rows: &[Vec<String>], locale: Locale) -> String {
+    let columns = rows
+        .iter()
+        .map(Vec::len)
+        .chain([header.len()])
+        .max()
+        .unwrap_or(0);
+    let cell = |row: &[String], index: usize| {
+        row.get(index)
+            .map_or_else(String::new, |text| escape_markdown(text))
+    };
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+
+    let mut out = line((0..columns).map(|index| cell(header, index)).collect());
+    out.push_str(&line(
+        (0..columns)
+            .map(|index| {
+                if index > 0 && is_numeric_column(rows, index, locale) {
+                    "---:".to_string()
+                } else {
+                    "---".to_string()
+                }
+            })
+            .collect(),
+    ));
+    for row in rows {
+        out.push_str(&line((0..columns).map(|index| cell(row, index)).collect()));
+    }
+    out.push('\n');
+    out
+}
+
+/// Right-align columns whose cells are all numbers, apart from placeholders
+/// such as `N/A` or `—`.
+fn is_numeric_column(rows: &[Vec<String>], index: usize, locale: Locale) -> bool {
+    let mut values = rows
+        .iter()
+        .filter_map(|row| row.get(index))
+        .filter(|text| text.contains(|c: char| c.is_ascii_digit()))
+        .peekable();
+    values.peek().is_some() && values.all(|text| sort_value(text, locale).is_some())
+}
+
+fn html_table(
+    header: &[String],
+    rows: &[Vec<String>],
+    footer_rows: usize,
+    locale: Locale,
+) -> String {
+    let mut out = String::from("<table class=\"sortable\">\n<thead><tr>");
+    for text in header {
+        let _ = write!(out, "<th>{}</th>", escape_html(text));
+    }
+    out.push_str("</tr></thead>\n<tbody>\n");
+    let body_rows = rows.len().saturating_sub(footer_rows);
+    for (index, row) in rows.iter().enumerate() {
+        if index == body_rows {
+            out.push_str("</tbody>\n<tfoot>\n");
+        }
+        out.push_str("<tr>");
+        for text in row {
+            match sort_value(text, locale) {
+                Some(value) => {
+                    let _ = write!(
+                        out,
+                        "<td class=\"num\" data-value=\"{value}\">{}</td>",
+                        escape_html(text)
+                    );
+                }
+                None => {
+                    let _ = write!(out, "<td>{}</td>", escape_html(text));
+                }
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str(if body_rows < rows.len() {
+        "</tfoot>\n</table>\n"
+    } else {
+        "</tbody>\n</table>\n"
+    });
+    out
+}
+
+/// Numeric value of a formatted cell such as `1,234`, `$12.50`, `12,50 €`,
+/// `45.2%`, `1.5M`, or `4.2 KB`. Dates, names, and `N/A` have none.
+fn sort_value(text: &str, locale: Locale) -> Option<f64> {
+    const SCALES: [(&str, f64); 7] = [
+        ("%", 1.0),
+        (" KB", 1e3),
+        (" MB", 1e6),
+        (" B", 1.0),
+        ("K", 1e3),
+        ("M", 1e6),
+        ("B", 1e9),
+    ];
+    // Currency symbols and codes around the amount.
+    let is_affix = |c: char| !c.is_ascii_digit() && c != '-' && !c.is_lowercase();
+
+    let text = text.trim();
+    let (number, scale, percent) = SCALES
+        .iter()
+        .find_map(|(suffix, scale)| {
+            text.strip_suffix(suffix)
+                .filter(|rest| rest.ends_with(|c: char| c.is_ascii_digit()))
+                .map(|rest| (rest, *scale, *suffix == "%"))
+        })
+        .unwrap_or((text, 1.0, false));
+    let number = number.trim_matches(is_affix);
+    // Percentages are always written with a `.` decimal point.
+    let (group_sep, decimal_sep) = if percent {
+        (',', '.')
+    } else {
+        (locale.group_sep, locale.decimal_sep)
+    };
+
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number),
+    };
+    if !digits.starts_with(|c: char| c.is_ascii_digit())
+        || !digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == group_sep || c == decimal_sep)
+    {
+        return None;
+    }
+    let normalized: String = digits
+        .chars()
+        .filter(|&c| c != group_sep)
+        .map(|c| if c == decimal_sep { '.' } else { c })
+        .collect();
+    format!("{sign}{normalized}")
+        .parse::<f64>()
+        .ok()
+        .map(|value| value * scale)
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+pub(super) fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\n' => out.push_str("<br>"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const HTML_PAGE_START: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>ccstats report</title>
+<style>
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem; color: #1f2328; }
+h2 { font-size: 1.2rem; margin: 2rem 0 0.75rem; }
+table { border-collapse: collapse; font-size: 0.9rem; }
+th, td { border: 1px solid #d0d7de; padding: 0.3rem 0.6rem; text-align: left; }
+th { background: #f6f8fa; cursor: pointer; user-select: none; white-space: nowrap; }
+th[data-order="asc"]::after { content: " \25B2"; }
+th[data-order="desc"]::after { content: " \25BC"; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+tbody tr:nth-child(even) { background: #f6f8fa; }
+tfoot td { font-weight: 600; border-top: 2px solid #8c959f; }
+p { color: #59636e; font-size: 0.85rem; }
+</style>
+</head>
+<body>
+"#;
+
+const HTML_PAGE_END: &str = r"<script>
+document.querySelectorAll('table.sortable th').forEach((th) => {
+  th.addEventListener('click', () => {
+    const column = th.cellIndex;
+    const body = th.closest('table').tBodies[0];
+    const asc = th.dataset.order !== 'asc';
+    th.parentElement.querySelectorAll('th').forEach((other) => delete other.dataset.order);
+    th.dataset.order = asc ? 'asc' : 'desc';
+    const key = (row) => {
+      const cell = row.cells[column];
+      if (!cell) return '';
+      return cell.dataset.value !== undefined ? Number(cell.dataset.value) : cell.textContent;
+    };
+    const rows = Array.from(body.rows).sort((a, b) => {
+      const x = key(a);
+      const y = key(b);
+      const order = typeof x === 'number' && typeof y === 'number'
+        ? x - y
+        : String(x).localeCompare(String(y));
+      return asc ? order : -order;
+    });
+    body.append(...rows);
+  });
+});
+</script>
+</body>
+</html>
+";
+
+/// Open the standalone page that `--format html` fragments are written into.
+pub(crate) fn print_html_page_start() {
+    print!("{HTML_PAGE_START}");
+}
+
+pub(crate) fn print_html_page_end() {
+    print!("{HTML_PAGE_END}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::format::{create_styled_table, right_cell};
+
+    fn sample_table() -> Table {
+        let mut table = create_styled_table();
+        table.set_header(vec!["Date", "Model", "Total", "Cost"]);
+        table.add_row(vec![
+            Cell::new("2026-01-02"),
+            Cell::new("opus|4"),
+            right_cell("1,234", None, false),
+            right_cell("$1.50", None, false),
+        ]);
+        table.add_row(vec![
+            Cell::new(""),
+            Cell::new("sonnet"),
+            right_cell("56", None, false),
+            right_cell("N/A", None, false),
+        ]);
+        table.add_row(vec![
+            Cell::new("TOTAL"),
+            Cell::new(""),
+            right_cell("1,290", None, true),
+            right_cell("$1.50", None, true),
+        ]);
+        table
+    }
+
+    #[test]
+    fn markdown_table_keeps_rows_and_right_aligns_numbers() {
+        let out = Markup::Markdown.render_table(&sample_table(), 1, Locale::default());
+        assert_eq!(
+            out,
+            "| Date | Model | Total | Cost |\n\
+             | --- | --- | ---: | ---: |\n\
+             | 2026-01-02 | opus\\|4 | 1,234 | $1.50 |\n\
+             |  | sonnet | 56 | N/A |\n\
+             | TOTAL |  | 1,290 | $1.50 |\n\n"
+        );
+    }
+
+    #[test]
+    fn html_table_moves_totals_to_footer_and_tags_numbers() {
+        let out = Markup::Html.render_table(&sample_table(), 1, Locale::default());
+        assert!(out.starts_with("<table class=\"sortable\">\n<thead><tr><th>Date</th>"));
+        assert!(out.contains("<td class=\"num\" data-value=\"1234\">1,234</td>"));
+        assert!(out.contains("<td>N/A</td>"));
+        let footer = out.split("<tfoot>").nth(1).unwrap();
+        assert!(footer.contains("<td>TOTAL</td>"));
+        assert!(!footer.contains("sonnet"));
+
+        let no_footer = Markup::Html.render_table(&sample_table(), 0, Locale::default());
+        assert!(!no_footer.contains("<tfoot>"));
+        assert!(no_footer.ends_with("</tbody>\n</table>\n"));
+    }
+
+    #[test]
+    fn lines_and_titles_drop_terminal_padding() {
+        assert_eq!(
+            Markup::Markdown.render_title("Token Usage"),
+            "## Token Usage\n\n"
+        );
+        assert_eq!(
+            Markup::Terminal.render_title("Token Usage"),
+            "\n  Token Usage\n\n"
+        );
+        assert_eq!(
+            Markup::Markdown.render_line("\n  2 records\n  <1ms\n"),
+            "2 records  \n<1ms\n\n"
+        );
+        assert_eq!(
+            Markup::Html.render_line("\n  a < b & c\n"),
+            "<p>a &lt; b &amp; c</p>\n"
+        );
+        assert_eq!(Markup::Html.render_line("\n"), "");
+        assert_eq!(Markup::Terminal.render_line("\n  note"), "\n  note\n");
+    }
+
+    #[test]
+    fn sort_value_reads_formatted_numbers() {
+        let en = Locale::default();
+        let de = Locale::parse(Some("de")).unwrap();
+        assert_eq!(sort_value("1,234,567", en), Some(1_234_567.0));
+        assert_eq!(sort_value("$-1.25", en), Some(-1.25));
+        assert_eq!(sort_value("12,50 €", de), Some(12.5));
+        assert_eq!(sort_value("1.234", de), Some(1234.0));
+        assert_eq!(sort_value("45.2%", de), Some(45.2));
+        assert_eq!(sort_value("1.5M", en), Some(1_500_000.0));
+        assert_eq!(sort_value("4.2 KB", en), Some(4200.0));
+        assert_eq!(sort_value("812 B", en), Some(812.0));
+        for text in [
+            "2026-01-02",
+            "N/A",
+            "—",
+            "sonnet 4",
+            "claude-3-5",
+            "on_track",
+            "",
+        ] {
+            assert_eq!(sort_value(text, en), None, "{text}");
+        }
+    }
+}
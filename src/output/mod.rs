@@ -1,16 +1,31 @@
+mod agents;
 mod blocks;
 mod budget;
+mod claude_hook;
+mod command_json;
 mod csv;
+mod dashboard;
 mod endpoints;
+mod files;
 mod format;
 mod json;
+mod locale;
+mod markup;
+mod ndjson;
 mod period;
+mod pricing_audit;
+mod pricing_catalog;
 mod pricing_meta;
 mod project;
 mod quota;
+mod quota_history;
+mod schema;
 mod session;
+mod session_context;
+mod session_timeline;
 mod statusline;
 mod table;
+mod tool_commands;
 mod tools;
 mod top;
 mod top_structured;
@@ -18,38 +33,97 @@ mod top_structured;
 /// Central selector for supported CLI output modes.
 ///
 /// Add a real new format by adding a variant and updating the `render_*`
-/// matches. Trait/plugin dispatch is intentionally deferred until a fourth
-/// format exists.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// matches. Markdown and HTML are not separate renderers: they reuse the
+/// table renderers, which write through [`Markup`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub(crate) enum OutputFormat {
+    /// Terminal tables (default)
     Table,
+    /// JSON, same as --json
     Json,
+    /// CSV, same as --csv
     Csv,
+    /// GitHub-flavored Markdown tables
+    Markdown,
+    /// Standalone HTML page with sortable tables
+    Html,
 }
 
-pub(crate) use blocks::{BlockTableOptions, output_block_json, print_block_table};
+impl OutputFormat {
+    /// Markup for the table renderers.
+    pub(crate) fn markup(self) -> Markup {
+        match self {
+            OutputFormat::Markdown => Markup::Markdown,
+            OutputFormat::Html => Markup::Html,
+            OutputFormat::Table | OutputFormat::Json | OutputFormat::Csv => Markup::Terminal,
+        }
+    }
+}
+
+pub(crate) use agents::{AgentTableOptions, agent_json_rows, output_agent_csv, print_agent_table};
+pub(crate) use blocks::{BlockTableOptions, block_json_rows, output_block_json, print_block_table};
 pub(crate) use budget::{
-    MonthlyBudgetOptions, add_monthly_budget_to_json, monthly_budget_reports,
+    MonthlyBudgetOptions, attach_monthly_budgets, monthly_budget_reports,
     print_monthly_budget_table,
 };
+pub(crate) use claude_hook::{
+    ClaudeHookOptions, ClaudeHookStatus, ContextUsage, claude_hook_json, render_claude_hook_line,
+};
+pub(crate) use command_json::{
+    CapabilitiesJson, CatalogEntryJson, NotifyBudgetJson, NotifyChangeJson, NotifyDetailsJson,
+    NotifyPayloadJson, PriceChangeJson, PriceQuoteJson, PricingDiffJson, PricingRefreshJson,
+    RatesJson, SourceJson,
+};
 pub(crate) use csv::{
     append_data_quality_csv_comment, output_block_csv, output_monthly_budget_csv,
     output_period_csv_with_quality, output_project_csv, output_session_csv,
 };
-pub(crate) use endpoints::{EndpointTableOptions, output_endpoint_json, print_endpoint_table};
-pub(crate) use format::NumberFormat;
-pub(crate) use json::output_period_json_with_quality;
-pub(crate) use period::Period;
-pub(crate) use project::{ProjectTableOptions, output_project_json, print_project_table};
+pub(crate) use dashboard::{DashboardOptions, DashboardSource, render_dashboard};
+pub(crate) use endpoints::{EndpointTableOptions, endpoint_json_rows, print_endpoint_table};
+pub(crate) use files::{files_json_report, output_files_csv, print_files_table};
+pub(crate) use format::Locale;
+pub(crate) use json::period_json_rows;
+pub(crate) use locale::DataKind;
+pub(crate) use markup::{Markup, print_html_page_end, print_html_page_start};
+pub(crate) use ndjson::print_ndjson;
+pub(crate) use period::{Period, blended_currency, span_currency};
+pub(crate) use pricing_audit::{
+    PricingAudit, output_pricing_audit_csv, pricing_audit_json_report, print_pricing_audit_table,
+};
+pub(crate) use pricing_catalog::{format_rate, print_catalog_table};
+pub(crate) use project::{
+    ProjectTableOptions, output_project_json, print_project_table, project_json_rows,
+};
 pub(crate) use quota::{
-    QuotaValueEstimate, output_quota_csv, output_quota_json, print_quota_table,
+    QuotaValueEstimate, output_quota_csv, print_quota_table, quota_json_report, quota_window_json,
+    quota_window_name, timestamp,
+};
+pub(crate) use quota_history::{
+    output_quota_history_csv, print_quota_history_table, quota_history_json_report,
+};
+pub(crate) use schema::{
+    BudgetJson, SCHEMA_VERSION, SchemaReport, report_to_string, rows_to_string,
+};
+pub(crate) use session::{
+    SessionTableOptions, output_session_json, print_session_table, session_json_rows,
+};
+pub(crate) use session_context::{
+    SessionContextTableOptions, output_session_context_csv, print_session_context_table,
+    session_context_json_rows,
+};
+pub(crate) use session_timeline::{
+    SessionTimelineOptions, output_session_timeline_csv, print_session_timeline,
+    session_entry_json_rows, session_timeline_json_report,
+};
+pub(crate) use statusline::{
+    ActiveBlock, StatuslineOptions, active_block, print_statusline, statusline_json_report,
+    template_uses, validate_statusline_template,
 };
-pub(crate) use session::{SessionTableOptions, output_session_json, print_session_table};
-pub(crate) use statusline::{print_statusline, print_statusline_json_with_quality};
 pub(crate) use table::{PeriodSummaryFooter, TokenTableOptions, print_period_table};
-pub(crate) use tools::{output_tools_csv, output_tools_json, print_tools_table};
+pub(crate) use tool_commands::{commands_json_report, output_commands_csv, print_commands_table};
+pub(crate) use tools::{ToolReportOptions, output_tools_csv, print_tools_table, tools_json_report};
 pub(crate) use top::{
     TopRow, TopTableOptions, print_top_table, rank_by_model, rank_by_model_with_cost_mode,
     rank_by_project,
 };
-pub(crate) use top_structured::{output_top_csv, output_top_json};
+pub(crate) use top_structured::{output_top_csv, top_json_report};
@@ -0,0 +1,142 @@
+//! Newline-delimited JSON (`--ndjson`): one compact object per line.
+//!
+//! Typed reports write each row as soon as it is serialized instead of
+//! building one document first, so consumers such as `jq -c` or log shippers
+//! can process them incrementally. Reports that track data quality end with
+//! a `{"schema_version":1,"data_quality":{...}}` line.
+
+use std::io::{self, BufWriter, StdoutLock, Write};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::DataQuality;
+use crate::output::schema::{DataQualityJson, PeriodJsonRow, SCHEMA_VERSION};
+
+pub(crate) type StdoutNdjson = NdjsonWriter<BufWriter<StdoutLock<'static>>>;
+
+/// Write NDJSON to stdout. A closed pipe (`| head`) ends the output quietly.
+pub(crate) fn print_ndjson(
+    extra_field: Option<(&'static str, &'static str)>,
+    write: impl FnOnce(&mut StdoutNdjson) -> io::Result<()>,
+) {
+    let mut writer = NdjsonWriter::stdout().with_field(extra_field);
+    let result = write(&mut writer).and_then(|()| writer.finish().map(drop));
+    match result {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+            eprintln!("Failed to write NDJSON output: {e}");
+            std::process::exit(1);
+        }
+        _ => {}
+    }
+}
+
+pub(crate) struct NdjsonWriter<W: Write> {
+    out: W,
+    /// Field added to every line, such as `codex_scope`.
+    extra_field: Option<(&'static str, &'static str)>,
+}
+
+impl StdoutNdjson {
+    pub(crate) fn stdout() -> Self {
+        Self::new(BufWriter::new(io::stdout().lock()))
+    }
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    pub(crate) fn new(out: W) -> Self {
+        Self {
+            out,
+            extra_field: None,
+        }
+    }
+
+    pub(crate) fn with_field(mut self, field: Option<(&'static str, &'static str)>) -> Self {
+        self.extra_field = field;
+        self
+    }
+
+    pub(crate) fn row<T: Serialize>(&mut self, row: &T) -> io::Result<()> {
+        match self.extra_field {
+            None => serde_json::to_writer(&mut self.out, row)?,
+            Some((key, value)) => {
+                let mut row = serde_json::to_value(row)?;
+                if let Value::Object(object) = &mut row {
+                    object.insert(key.to_string(), Value::from(value));
+                }
+                serde_json::to_writer(&mut self.out, &row)?;
+            }
+        }
+        self.out.write_all(b"\n")
+    }
+
+    /// Write each row as the iterator yields it, borrowed or owned.
+    pub(crate) fn rows<T: Serialize>(
+        &mut self,
+        rows: impl IntoIterator<Item = T>,
+    ) -> io::Result<()> {
+        rows.into_iter().try_for_each(|row| self.row(&row))
+    }
+
+    /// Trailing line with the record counts behind the report.
+    pub(crate) fn data_quality(&mut self, data_quality: DataQuality) -> io::Result<()> {
+        self.row(&PeriodJsonRow::DataQualityOnly {
+            schema_version: SCHEMA_VERSION,
+            data_quality: DataQualityJson::from(data_quality),
+        })
+    }
+
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(bytes: Vec<u8>) -> Vec<Value> {
+        String::from_utf8(bytes)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn rows_accepts_borrowed_and_owned_rows() {
+        let mut writer = NdjsonWriter::new(Vec::new());
+        let borrowed = [serde_json::json!({"date": "2026-01-01"})];
+        writer.rows(&borrowed).unwrap();
+        writer
+            .rows((2..=3).map(|day| serde_json::json!({"date": format!("2026-01-0{day}")})))
+            .unwrap();
+        let rows = lines(writer.finish().unwrap());
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0]["date"], "2026-01-01");
+        assert_eq!(rows[2]["date"], "2026-01-03");
+    }
+
+    #[test]
+    fn extra_field_and_data_quality_trailer() {
+        let mut writer = NdjsonWriter::new(Vec::new()).with_field(Some(("codex_scope", "exec")));
+        writer
+            .row(&serde_json::json!({"date": "2026-01-01"}))
+            .unwrap();
+        writer
+            .data_quality(DataQuality {
+                valid_entries: 4,
+                dedup_skipped_entries: 1,
+                parse_errors: 0,
+            })
+            .unwrap();
+        let rows = lines(writer.finish().unwrap());
+
+        assert_eq!(rows[0]["codex_scope"], "exec");
+        assert_eq!(rows[1]["schema_version"], SCHEMA_VERSION);
+        assert_eq!(rows[1]["data_quality"]["valid_entries"], 4);
+        assert_eq!(rows[1]["data_quality"]["dedup_skipped_entries"], 1);
+    }
+}
@@ -1,8 +1,10 @@
 use chrono::{Datelike, NaiveDate};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use crate::consts::DATE_FORMAT;
-use crate::core::DayStats;
+use crate::core::{DayStats, Stats};
+use crate::pricing::{CostDisplayMode, CurrencyConverter, PricingDb, sum_display_model_costs};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Period {
@@ -64,6 +66,106 @@ pub(crate) fn aggregate_day_stats_by_period(
     aggregated
 }
 
+/// Per-row converters for a currency with daily rates: each period converts
+/// at its days' rates weighted by their cost, and so does the total.
+pub(crate) struct PeriodRates {
+    by_key: HashMap<String, CurrencyConverter>,
+    total: CurrencyConverter,
+}
+
+impl PeriodRates {
+    /// `None` unless the converter carries daily rates.
+    pub(crate) fn new(
+        day_stats: &HashMap<String, DayStats>,
+        period: Period,
+        currency: Option<&CurrencyConverter>,
+        pricing_db: &PricingDb,
+        cost_mode: CostDisplayMode,
+    ) -> Option<Self> {
+        let currency = currency.filter(|conv| conv.is_historical())?;
+        let mut days_by_key: HashMap<String, Vec<(NaiveDate, f64)>> = HashMap::new();
+        let mut all_days = Vec::with_capacity(day_stats.len());
+        for (date, stats) in day_stats {
+            let Ok(day) = NaiveDate::parse_from_str(date, DATE_FORMAT) else {
+                continue;
+            };
+            let cost = sum_display_model_costs(&stats.models, pricing_db, cost_mode);
+            days_by_key
+                .entry(period_key(date, period))
+                .or_default()
+                .push((day, cost));
+            all_days.push((day, cost));
+        }
+        Some(Self {
+            by_key: days_by_key
+                .into_iter()
+                .map(|(key, days)| (key, currency.blended(&days)))
+                .collect(),
+            total: currency.blended(&all_days),
+        })
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&CurrencyConverter> {
+        self.by_key.get(key).or(Some(&self.total))
+    }
+
+    pub(crate) fn total(&self) -> &CurrencyConverter {
+        &self.total
+    }
+}
+
+/// USD cost of each day in `days` (date, per-model stats), for weighting
+/// daily exchange rates.
+pub(crate) fn day_costs<'d>(
+    days: impl IntoIterator<Item = (&'d String, &'d HashMap<String, Stats>)>,
+    pricing_db: &PricingDb,
+    cost_mode: CostDisplayMode,
+) -> Vec<(NaiveDate, f64)> {
+    days.into_iter()
+        .filter_map(|(date, models)| {
+            let day = NaiveDate::parse_from_str(date, DATE_FORMAT).ok()?;
+            Some((day, sum_display_model_costs(models, pricing_db, cost_mode)))
+        })
+        .collect()
+}
+
+/// Converter for usage spread over several days, such as a session, project
+/// or block: each day's rate weighted by its cost. Borrows `currency` unless
+/// it carries daily rates.
+pub(crate) fn blended_currency<'a>(
+    currency: Option<&'a CurrencyConverter>,
+    day_costs: &[(NaiveDate, f64)],
+) -> Option<Cow<'a, CurrencyConverter>> {
+    let currency = currency?;
+    Some(if currency.is_historical() {
+        Cow::Owned(currency.blended(day_costs))
+    } else {
+        Cow::Borrowed(currency)
+    })
+}
+
+/// [`blended_currency`] over the per-model stats of each day in `days`.
+pub(crate) fn span_currency<'a, 'd>(
+    currency: Option<&'a CurrencyConverter>,
+    days: impl IntoIterator<Item = (&'d String, &'d HashMap<String, Stats>)>,
+    pricing_db: &PricingDb,
+    cost_mode: CostDisplayMode,
+) -> Option<Cow<'a, CurrencyConverter>> {
+    if !currency.is_some_and(CurrencyConverter::is_historical) {
+        return currency.map(Cow::Borrowed);
+    }
+    blended_currency(currency, &day_costs(days, pricing_db, cost_mode))
+}
+
+/// Converter for one period row: its own rate when `rates` is set.
+pub(crate) fn row_currency<'a>(
+    rates: Option<&'a PeriodRates>,
+    key: &str,
+    currency: Option<&'a CurrencyConverter>,
+) -> Option<&'a CurrencyConverter> {
+    rates.map_or(currency, |rates| rates.get(key))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,380 @@
+//! Output for `ccstats audit pricing`: how every model seen in the range was
+//! priced and how much usage went unpriced.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use comfy_table::{Cell, Color};
+
+use crate::core::Stats;
+use crate::output::command_json::{PricingAuditJson, PricingAuditModelJson};
+use crate::output::csv::csv_cost;
+use crate::output::format::{
+    Locale, create_styled_table, csv_escape, format_cost, format_number, header_cell, right_cell,
+    styled_cell,
+};
+use crate::output::locale::Msg;
+use crate::output::schema::{SCHEMA_VERSION, cost_amount};
+use crate::pricing::{
+    CurrencyConverter, PricingDb, PricingSource, calculate_cost, has_fallback_pricing,
+    pricing_source_for_model_stats,
+};
+
+/// Pricing resolution and usage of one model.
+#[derive(Debug, Clone)]
+pub(crate) struct PricingAuditRow {
+    pub(crate) model: String,
+    /// `live`, `cache`, `fallback`, `catalog`, `recorded`, `unknown` or `mixed`.
+    pub(crate) resolution: PricingSource,
+    /// Catalog key the model matched (`built-in fallback` for fallback prices).
+    pub(crate) matched_key: Option<String>,
+    /// Whether `fallback_pricing` knows the model, even when strict pricing
+    /// keeps it from being applied.
+    pub(crate) fallback_available: bool,
+    pub(crate) calls: i64,
+    pub(crate) tokens: i64,
+    /// Cost in USD; NaN when the model has no price.
+    pub(crate) cost: f64,
+}
+
+/// Audit of every model in the range, sorted by token volume.
+#[derive(Debug, Clone)]
+pub(crate) struct PricingAudit {
+    pub(crate) rows: Vec<PricingAuditRow>,
+    /// Whether fallback estimates count as unpriced usage.
+    pub(crate) include_fallback: bool,
+}
+
+impl PricingAudit {
+    pub(crate) fn new(
+        models: &HashMap<String, Stats>,
+        pricing_db: &PricingDb,
+        include_fallback: bool,
+    ) -> Self {
+        let mut rows: Vec<PricingAuditRow> = models
+            .iter()
+            .map(|(model, stats)| PricingAuditRow {
+                model: model.clone(),
+                resolution: pricing_source_for_model_stats(model, stats, pricing_db),
+                matched_key: pricing_db.quote(model).matched_key,
+                fallback_available: has_fallback_pricing(model),
+                calls: stats.count,
+                tokens: stats.total_tokens(),
+                cost: calculate_cost(stats, model, pricing_db),
+            })
+            .collect();
+        rows.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.model.cmp(&b.model)));
+        Self {
+            rows,
+            include_fallback,
+        }
+    }
+
+    pub(crate) fn is_unpriced(&self, row: &PricingAuditRow) -> bool {
+        match row.resolution {
+            PricingSource::Unknown => true,
+            PricingSource::Fallback => self.include_fallback,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn total_tokens(&self) -> i64 {
+        self.rows.iter().map(|row| row.tokens).sum()
+    }
+
+    pub(crate) fn unpriced_tokens(&self) -> i64 {
+        self.rows
+            .iter()
+            .filter(|row| self.is_unpriced(row))
+            .map(|row| row.tokens)
+            .sum()
+    }
+
+    /// Unpriced share of all tokens, in percent.
+    pub(crate) fn unpriced_pct(&self) -> f64 {
+        share_pct(self.unpriced_tokens() as f64, self.total_tokens() as f64)
+    }
+
+    fn total_cost(&self) -> f64 {
+        self.rows
+            .iter()
+            .map(|row| row.cost)
+            .filter(|cost| !cost.is_nan())
+            .sum()
+    }
+
+    fn token_share_pct(&self, row: &PricingAuditRow) -> f64 {
+        share_pct(row.tokens as f64, self.total_tokens() as f64)
+    }
+
+    /// Share of the priced cost; `None` for models without a price.
+    fn cost_share_pct(&self, row: &PricingAuditRow) -> Option<f64> {
+        (!row.cost.is_nan()).then(|| share_pct(row.cost, self.total_cost()))
+    }
+}
+
+fn share_pct(part: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        part / total * 100.0
+    } else {
+        0.0
+    }
+}
+
+fn round_pct(pct: f64) -> f64 {
+    (pct * 10.0).round() / 10.0
+}
+
+fn resolution_label(row: &PricingAuditRow) -> String {
+    if row.resolution == PricingSource::Unknown && row.fallback_available {
+        "unknown (fallback off)".to_string()
+    } else {
+        row.resolution.as_str().to_string()
+    }
+}
+
+pub(crate) fn print_pricing_audit_table(
+    audit: &PricingAudit,
+    source_label: &str,
+    use_color: bool,
+    show_cost: bool,
+    locale: Locale,
+    currency: Option<&CurrencyConverter>,
+) {
+    let h_cell = |msg| header_cell(locale.text(msg), use_color);
+    let mut table = create_styled_table();
+    let mut header = vec![
+        h_cell(Msg::Model),
+        h_cell(Msg::Resolution),
+        h_cell(Msg::MatchedKey),
+        h_cell(Msg::Calls),
+        h_cell(Msg::Tokens),
+        h_cell(Msg::TokenPct),
+    ];
+    if show_cost {
+        header.push(h_cell(Msg::Cost));
+        header.push(h_cell(Msg::CostPct));
+    }
+    table.set_header(header);
+
+    let unpriced_color = use_color.then_some(Color::Red);
+    for row in &audit.rows {
+        let color = if audit.is_unpriced(row) {
+            unpriced_color
+        } else {
+            None
+        };
+        let mut cells = vec![
+            Cell::new(&row.model),
+            styled_cell(&resolution_label(row), color, false),
+            Cell::new(row.matched_key.as_deref().unwrap_or("-")),
+            right_cell(&format_number(row.calls, locale), None, false),
+            right_cell(&format_number(row.tokens, locale), None, false),
+            right_cell(&format!("{:.1}%", audit.token_share_pct(row)), None, false),
+        ];
+        if show_cost {
+            cells.push(right_cell(
+                &format_cost(row.cost, currency, locale),
+                None,
+                false,
+            ));
+            cells.push(right_cell(
+                &audit
+                    .cost_share_pct(row)
+                    .map_or_else(|| "-".to_string(), |pct| format!("{pct:.1}%")),
+                None,
+                false,
+            ));
+        }
+        table.add_row(cells);
+    }
+
+    println!("\n  {source_label} {}\n", locale.text(Msg::PricingAudit));
+    println!("{table}");
+    println!("\n  {}\n", unpriced_summary(audit, locale));
+}
+
+fn unpriced_summary(audit: &PricingAudit, locale: Locale) -> String {
+    let unpriced_models = audit
+        .rows
+        .iter()
+        .filter(|row| audit.is_unpriced(row))
+        .count();
+    locale.unpriced_usage(
+        &format_number(audit.unpriced_tokens(), locale),
+        audit.unpriced_pct(),
+        unpriced_models,
+        audit.rows.len(),
+        audit.include_fallback,
+    )
+}
+
+pub(crate) fn pricing_audit_json_report(
+    audit: &PricingAudit,
+    show_cost: bool,
+    currency: Option<&CurrencyConverter>,
+) -> PricingAuditJson {
+    let models = audit
+        .rows
+        .iter()
+        .map(|row| PricingAuditModelJson {
+            model: row.model.clone(),
+            resolution: row.resolution.as_str(),
+            matched_key: row.matched_key.clone(),
+            fallback_available: row.fallback_available,
+            unpriced: audit.is_unpriced(row),
+            calls: row.calls,
+            total_tokens: row.tokens,
+            token_share_pct: round_pct(audit.token_share_pct(row)),
+            cost: show_cost.then(|| cost_amount(row.cost, currency)),
+            cost_share_pct: show_cost.then(|| audit.cost_share_pct(row).map(round_pct)),
+        })
+        .collect();
+    PricingAuditJson {
+        schema_version: SCHEMA_VERSION,
+        models,
+        total_tokens: audit.total_tokens(),
+        unpriced_tokens: audit.unpriced_tokens(),
+        unpriced_pct: round_pct(audit.unpriced_pct()),
+        fallback_counts_as_unpriced: audit.include_fallback,
+    }
+}
+
+pub(crate) fn output_pricing_audit_csv(
+    audit: &PricingAudit,
+    show_cost: bool,
+    currency: Option<&CurrencyConverter>,
+) -> String {
+    let mut out = String::from(
+        "model,resolution,matched_key,fallback_available,unpriced,calls,total_tokens,token_share_pct",
+    );
+    if show_cost {
+        out.push_str(",cost,cost_share_pct");
+    }
+    out.push('\n');
+    for row in &audit.rows {
+        let _ = write!(
+            out,
+            "{},{},{},{},{},{},{},{:.1}",
+            csv_escape(&row.model),
+            row.resolution.as_str(),
+            csv_escape(row.matched_key.as_deref().unwrap_or_default()),
+            row.fallback_available,
+            audit.is_unpriced(row),
+            row.calls,
+            row.tokens,
+            audit.token_share_pct(row)
+        );
+        if show_cost {
+            let _ = write!(
+                out,
+                ",{},{}",
+                csv_cost(row.cost, currency),
+                audit
+                    .cost_share_pct(row)
+                    .map(|pct| format!("{pct:.1}"))
+                    .unwrap_or_default()
+            );
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(input: i64, count: i64) -> Stats {
+        Stats {
+            input_tokens: input,
+            count,
+            ..Stats::default()
+        }
+    }
+
+    fn row(model: &str, resolution: PricingSource, tokens: i64, cost: f64) -> PricingAuditRow {
+        PricingAuditRow {
+            model: model.to_string(),
+            resolution,
+            matched_key: None,
+            fallback_available: false,
+            calls: 1,
+            tokens,
+            cost,
+        }
+    }
+
+    #[test]
+    fn unpriced_share_counts_unknown_and_optionally_fallback_tokens() {
+        let mut audit = PricingAudit {
+            rows: vec![
+                row("priced", PricingSource::Live, 600, 1.0),
+                row("estimated", PricingSource::Fallback, 300, 0.5),
+                row("mystery", PricingSource::Unknown, 100, f64::NAN),
+            ],
+            include_fallback: false,
+        };
+        assert_eq!(audit.unpriced_tokens(), 100);
+        assert!((audit.unpriced_pct() - 10.0).abs() < 1e-9);
+        assert_eq!(audit.cost_share_pct(&audit.rows[2]), None);
+        let share = audit.cost_share_pct(&audit.rows[0]).unwrap();
+        assert!((share - 66.666).abs() < 0.01);
+
+        audit.include_fallback = true;
+        assert_eq!(audit.unpriced_tokens(), 400);
+        assert!((audit.unpriced_pct() - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn audit_resolves_each_model_and_sorts_by_tokens() {
+        let pricing_db = PricingDb::default();
+        let models = HashMap::from([
+            ("claude-sonnet-4".to_string(), stats(100, 2)),
+            ("qwen-unknown".to_string(), stats(500, 1)),
+        ]);
+        let audit = PricingAudit::new(&models, &pricing_db, false);
+        assert_eq!(audit.rows[0].model, "qwen-unknown");
+        assert_eq!(audit.rows[0].resolution, PricingSource::Unknown);
+        assert!(audit.rows[0].cost.is_nan());
+        assert_eq!(audit.rows[1].resolution, PricingSource::Fallback);
+        assert_eq!(
+            audit.rows[1].matched_key.as_deref(),
+            Some("built-in fallback")
+        );
+        assert!(audit.rows[1].fallback_available);
+
+        let json: serde_json::Value =
+            serde_json::to_value(pricing_audit_json_report(&audit, true, None)).unwrap();
+        assert_eq!(json["unpriced_tokens"], 500);
+        assert_eq!(json["models"][0]["unpriced"], true);
+        assert_eq!(json["models"][0]["cost"], serde_json::Value::Null);
+        assert_eq!(json["models"][1]["cost_share_pct"], 100.0);
+    }
+
+    #[test]
+    fn csv_lists_resolution_and_shares() {
+        let audit = PricingAudit {
+            rows: vec![
+                row("priced", PricingSource::Cache, 900, 2.0),
+                PricingAuditRow {
+                    fallback_available: true,
+                    ..row("strict", PricingSource::Unknown, 100, f64::NAN)
+                },
+            ],
+            include_fallback: false,
+        };
+        let csv = output_pricing_audit_csv(&audit, true, None);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "model,resolution,matched_key,fallback_available,unpriced,calls,total_tokens,token_share_pct,cost,cost_share_pct"
+        );
+        assert_eq!(
+            lines[1],
+            "priced,cache,,false,false,1,900,90.0,2.000000,100.0"
+        );
+        assert_eq!(lines[2], "strict,unknown,,true,true,1,100,10.0,N/A,");
+        assert_eq!(resolution_label(&audit.rows[1]), "unknown (fallback off)");
+    }
+}
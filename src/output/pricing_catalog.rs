@@ -0,0 +1,101 @@
+//! Table output for `ccstats pricing list`: catalog entries and their rates.
+
+use comfy_table::{Cell, Table};
+
+use crate::output::format::{Locale, create_styled_table, header_cell, right_cell};
+use crate::output::markup::Markup;
+use crate::pricing::CatalogEntry;
+
+/// USD per million tokens with at least two and at most six decimals.
+pub(crate) fn format_rate(rate: f64) -> String {
+    let mut text = format!("{rate:.6}");
+    while text.ends_with('0') && text.len() - text.find('.').unwrap_or(0) > 3 {
+        text.pop();
+    }
+    format!("${text}/M")
+}
+
+fn catalog_table(entries: &[CatalogEntry], use_color: bool) -> Table {
+    let mut table = create_styled_table();
+    table.set_header(vec![
+        header_cell("Model", use_color),
+        header_cell("Input", use_color),
+        header_cell("Output", use_color),
+        header_cell("Cache Read", use_color),
+        header_cell("Cache Write", use_color),
+        header_cell("Catalog", use_color),
+    ]);
+    for entry in entries {
+        table.add_row(vec![
+            Cell::new(&entry.key),
+            right_cell(&format_rate(entry.rates.input), None, false),
+            right_cell(&format_rate(entry.rates.output), None, false),
+            right_cell(&format_rate(entry.rates.cache_read), None, false),
+            right_cell(&format_rate(entry.rates.cache_write), None, false),
+            Cell::new(format!("{} ({})", entry.catalog, entry.source.as_str())),
+        ]);
+    }
+    table
+}
+
+/// Print catalog entries with their base rates, one row per listed model
+pub(crate) fn print_catalog_table(
+    entries: &[CatalogEntry],
+    locale: Locale,
+    use_color: bool,
+    markup: Markup,
+) {
+    if entries.is_empty() {
+        markup.line("No matching catalog entries.");
+        return;
+    }
+    markup.table(&catalog_table(entries, use_color), 0, locale);
+    markup.line(&format!("\n{} models", entries.len()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::{PriceRates, PricingSource};
+
+    #[test]
+    fn rates_keep_two_to_six_decimals() {
+        assert_eq!(format_rate(3.0), "$3.00/M");
+        assert_eq!(format_rate(0.075), "$0.075/M");
+        assert_eq!(format_rate(0.000_125), "$0.000125/M");
+    }
+
+    #[test]
+    fn catalog_table_has_one_row_per_entry() {
+        let entry = CatalogEntry {
+            key: "claude-sonnet-4".to_string(),
+            catalog: "litellm".to_string(),
+            source: PricingSource::Cache,
+            rates: PriceRates {
+                input: 3.0,
+                output: 15.0,
+                reasoning_output: 15.0,
+                cache_read: 0.3,
+                cache_write: 3.75,
+                cache_write_1h: 6.0,
+            },
+            long_context: None,
+        };
+        let table = catalog_table(&[entry], false);
+        let rows: Vec<Vec<String>> = table
+            .row_iter()
+            .map(|row| row.cell_iter().map(Cell::content).collect())
+            .collect();
+        assert_eq!(
+            rows,
+            vec![vec![
+                "claude-sonnet-4",
+                "$3.00/M",
+                "$15.00/M",
+                "$0.30/M",
+                "$3.75/M",
+                "litellm (cache)",
+            ]]
+        );
+    }
+}
@@ -6,63 +6,13 @@ use crate::pricing::{
     pricing_source_for_models,
 };
 
-fn needs_cache_fields(source: PricingSource, pricing_db: &PricingDb) -> bool {
+pub(super) fn needs_cache_fields(source: PricingSource, pricing_db: &PricingDb) -> bool {
     matches!(
         source,
         PricingSource::Cache | PricingSource::CacheStale | PricingSource::Mixed
     ) && pricing_db.cache_age_seconds().is_some()
 }
 
-pub(super) fn add_json(
-    obj: &mut serde_json::Value,
-    models: &HashMap<String, Stats>,
-    pricing_db: &PricingDb,
-) {
-    add_source_json(
-        obj,
-        pricing_source_for_models(models, pricing_db),
-        pricing_db,
-    );
-}
-
-pub(super) fn add_model_json(
-    obj: &mut serde_json::Value,
-    model: &str,
-    stats: &Stats,
-    pricing_db: &PricingDb,
-) {
-    let source = pricing_source_for_model_stats(model, stats, pricing_db);
-    add_source_json(obj, source, pricing_db);
-}
-
-pub(super) fn add_json_for_maps<'a>(
-    obj: &mut serde_json::Value,
-    maps: impl IntoIterator<Item = &'a HashMap<String, Stats>>,
-    pricing_db: &PricingDb,
-) {
-    add_source_json(
-        obj,
-        pricing_source_for_model_maps(maps, pricing_db),
-        pricing_db,
-    );
-}
-
-pub(super) fn add_source_json(
-    obj: &mut serde_json::Value,
-    source: PricingSource,
-    pricing_db: &PricingDb,
-) {
-    obj["pricing_source"] = serde_json::json!(source.as_str());
-    if needs_cache_fields(source, pricing_db) {
-        if let Some(age) = pricing_db.cache_age_seconds() {
-            obj["pricing_cache_age_seconds"] = serde_json::json!(age);
-        }
-        if let Some(mtime) = pricing_db.cache_modified_epoch_seconds() {
-            obj["pricing_cache_mtime_epoch_seconds"] = serde_json::json!(mtime);
-        }
-    }
-}
-
 pub(super) fn csv_has_cache_fields(source: PricingSource, pricing_db: &PricingDb) -> bool {
     needs_cache_fields(source, pricing_db)
 }
@@ -137,6 +87,7 @@ pub(super) fn note(source: PricingSource, pricing_db: &PricingDb) -> Option<Stri
             cache_age_suffix(pricing_db)
         )),
         PricingSource::Fallback => Some("Pricing source: fallback estimates.".to_string()),
+        PricingSource::Catalog => Some("Pricing source: local price catalog.".to_string()),
         PricingSource::Recorded => Some("Pricing source: recorded provider cost.".to_string()),
         PricingSource::Unknown => Some("Pricing source: unknown unpriced models.".to_string()),
         PricingSource::Mixed => Some(format!(
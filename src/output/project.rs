@@ -3,13 +3,19 @@ use comfy_table::{Cell, Color};
 use crate::cli::SortOrder;
 use crate::core::{ProjectStats, Stats};
 use crate::output::format::{
-    NumberFormat, cache_hit_rate_json_value, compare_cost, cost_json_value, create_styled_table,
-    format_cache_hit_rate, format_compact, format_cost, format_number, header_cell, right_cell,
-    styled_cell,
+    Locale, compare_cost, create_styled_table, format_cache_hit_rate, format_compact, format_cost,
+    format_number, header_cell, right_cell, styled_cell,
 };
+use crate::output::locale::Msg;
+use crate::output::markup::Markup;
+use crate::output::period::span_currency;
 use crate::output::pricing_meta;
+use crate::output::schema::{
+    CostJson, ExchangeRateJson, ProjectJson, SCHEMA_VERSION, TokensJson, model_names,
+    rows_to_string,
+};
 use crate::pricing::{
-    CurrencyConverter, PricingDb, attach_costs, model_cost_kind, sum_estimated_proxy_model_costs,
+    CostDisplayMode, CurrencyConverter, PricingDb, attach_costs, sum_estimated_proxy_model_costs,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -21,8 +27,9 @@ pub(crate) struct ProjectTableOptions<'a> {
     pub(crate) show_cost: bool,
     pub(crate) supports_cache_read: bool,
     pub(crate) source_label: &'a str,
-    pub(crate) number_format: NumberFormat,
+    pub(crate) locale: Locale,
     pub(crate) currency: Option<&'a CurrencyConverter>,
+    pub(crate) markup: Markup,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -36,7 +43,7 @@ pub(crate) fn print_project_table(
     let compact = options.compact;
     let show_cost = options.show_cost;
     let source_label = options.source_label;
-    let number_format = options.number_format;
+    let locale = options.locale;
 
     let mut sorted_projects = attach_costs(projects, |p| &p.models, pricing_db);
 
@@ -46,30 +53,28 @@ pub(crate) fn print_project_table(
         SortOrder::Desc => sorted_projects.sort_by(|a, b| compare_cost(b.cost, a.cost)),
     }
 
+    let h_cell = |msg| header_cell(locale.text(msg), use_color);
     let mut table = create_styled_table();
 
     if compact {
-        let mut header = vec![
-            header_cell("Project", use_color),
-            header_cell("Sessions", use_color),
-        ];
-        header.push(header_cell("Cache Hit", use_color));
-        header.push(header_cell("Total", use_color));
+        let mut header = vec![h_cell(Msg::Project), h_cell(Msg::Sessions)];
+        header.push(h_cell(Msg::CacheHit));
+        header.push(h_cell(Msg::Total));
         if show_cost {
-            header.push(header_cell("Cost", use_color));
+            header.push(h_cell(Msg::Cost));
         }
         table.set_header(header);
     } else {
         let mut header = vec![
-            header_cell("Project", use_color),
-            header_cell("Sessions", use_color),
-            header_cell("Input", use_color),
-            header_cell("Output", use_color),
+            h_cell(Msg::Project),
+            h_cell(Msg::Sessions),
+            h_cell(Msg::Input),
+            h_cell(Msg::Output),
         ];
-        header.push(header_cell("Cache Hit", use_color));
-        header.push(header_cell("Total", use_color));
+        header.push(h_cell(Msg::CacheHit));
+        header.push(h_cell(Msg::Total));
         if show_cost {
-            header.push(header_cell("Cost", use_color));
+            header.push(h_cell(Msg::Cost));
         }
         table.set_header(header);
     }
@@ -81,10 +86,22 @@ pub(crate) fn print_project_table(
     let mut total_estimated_cost = 0.0;
     let mut has_estimated_cost = false;
     let mut total_sessions = 0usize;
+    let total_currency = span_currency(
+        options.currency,
+        sorted_projects.iter().flat_map(|costed| &costed.item.days),
+        pricing_db,
+        CostDisplayMode::Total,
+    );
 
     for costed in &sorted_projects {
         let project = costed.item;
         let project_cost = costed.cost;
+        let row_currency = span_currency(
+            options.currency,
+            &project.days,
+            pricing_db,
+            CostDisplayMode::Total,
+        );
         total_cost += project_cost;
         let estimated_cost = sum_estimated_proxy_model_costs(&project.models, pricing_db);
         if estimated_cost > 0.0 {
@@ -98,7 +115,7 @@ pub(crate) fn print_project_table(
             let mut row = vec![
                 Cell::new(&project.project_name),
                 right_cell(
-                    &format_number(project.session_count as i64, number_format),
+                    &format_number(project.session_count as i64, locale),
                     None,
                     false,
                 ),
@@ -109,13 +126,13 @@ pub(crate) fn print_project_table(
                 false,
             ));
             row.push(right_cell(
-                &format_compact(project.stats.total_tokens(), number_format),
+                &format_compact(project.stats.total_tokens(), locale),
                 None,
                 false,
             ));
             if show_cost {
                 row.push(right_cell(
-                    &format_cost(project_cost, options.currency),
+                    &format_cost(project_cost, row_currency.as_deref(), options.locale),
                     cost_color,
                     false,
                 ));
@@ -125,17 +142,17 @@ pub(crate) fn print_project_table(
             let mut row = vec![
                 Cell::new(&project.project_name),
                 right_cell(
-                    &format_number(project.session_count as i64, number_format),
+                    &format_number(project.session_count as i64, locale),
                     None,
                     false,
                 ),
                 right_cell(
-                    &format_number(project.stats.input_tokens, number_format),
+                    &format_number(project.stats.input_tokens, locale),
                     None,
                     false,
                 ),
                 right_cell(
-                    &format_number(project.stats.output_tokens, number_format),
+                    &format_number(project.stats.output_tokens, locale),
                     None,
                     false,
                 ),
@@ -146,13 +163,13 @@ pub(crate) fn print_project_table(
                 false,
             ));
             row.push(right_cell(
-                &format_number(project.stats.total_tokens(), number_format),
+                &format_number(project.stats.total_tokens(), locale),
                 None,
                 false,
             ));
             if show_cost {
                 row.push(right_cell(
-                    &format_cost(project_cost, options.currency),
+                    &format_cost(project_cost, row_currency.as_deref(), options.locale),
                     cost_color,
                     false,
                 ));
@@ -167,12 +184,8 @@ pub(crate) fn print_project_table(
     // Add total row
     if compact {
         let mut row = vec![
-            styled_cell("TOTAL", cyan, true),
-            right_cell(
-                &format_number(total_sessions as i64, number_format),
-                cyan,
-                true,
-            ),
+            styled_cell(locale.text(Msg::TotalRow), cyan, true),
+            right_cell(&format_number(total_sessions as i64, locale), cyan, true),
         ];
         row.push(right_cell(
             &format_cache_hit_rate(total_stats.cache_hit_rate(options.supports_cache_read)),
@@ -180,13 +193,13 @@ pub(crate) fn print_project_table(
             true,
         ));
         row.push(right_cell(
-            &format_compact(total_stats.total_tokens(), number_format),
+            &format_compact(total_stats.total_tokens(), locale),
             cyan,
             true,
         ));
         if show_cost {
             row.push(right_cell(
-                &format_cost(total_cost, options.currency),
+                &format_cost(total_cost, total_currency.as_deref(), options.locale),
                 green,
                 true,
             ));
@@ -194,19 +207,11 @@ pub(crate) fn print_project_table(
         table.add_row(row);
     } else {
         let mut row = vec![
-            styled_cell("TOTAL", cyan, true),
+            styled_cell(locale.text(Msg::TotalRow), cyan, true),
+            right_cell(&format_number(total_sessions as i64, locale), cyan, true),
+            right_cell(&format_number(total_stats.input_tokens, locale), cyan, true),
             right_cell(
-                &format_number(total_sessions as i64, number_format),
-                cyan,
-                true,
-            ),
-            right_cell(
-                &format_number(total_stats.input_tokens, number_format),
-                cyan,
-                true,
-            ),
-            right_cell(
-                &format_number(total_stats.output_tokens, number_format),
+                &format_number(total_stats.output_tokens, locale),
                 cyan,
                 true,
             ),
@@ -217,13 +222,13 @@ pub(crate) fn print_project_table(
             true,
         ));
         row.push(right_cell(
-            &format_number(total_stats.total_tokens(), number_format),
+            &format_number(total_stats.total_tokens(), locale),
             cyan,
             true,
         ));
         if show_cost {
             row.push(right_cell(
-                &format_cost(total_cost, options.currency),
+                &format_cost(total_cost, total_currency.as_deref(), options.locale),
                 green,
                 true,
             ));
@@ -231,13 +236,21 @@ pub(crate) fn print_project_table(
         table.add_row(row);
     }
 
-    println!("\n  {source_label} Project Usage\n");
-    println!("{table}");
+    options.markup.title(&format!(
+        "{source_label} {}",
+        locale.text(Msg::ProjectUsage)
+    ));
+    options.markup.table(&table, 1, locale);
     if show_cost && has_estimated_cost {
-        println!(
-            "\n  Cost includes estimated proxy values: {}",
-            format_cost(total_estimated_cost, options.currency)
-        );
+        options.markup.line(&format!(
+            "\n  {}: {}",
+            locale.text(Msg::EstimatedProxyIncluded),
+            format_cost(
+                total_estimated_cost,
+                total_currency.as_deref(),
+                options.locale
+            )
+        ));
     }
     if show_cost
         && let Some(note) = pricing_meta::note_for_maps(
@@ -245,23 +258,25 @@ pub(crate) fn print_project_table(
             pricing_db,
         )
     {
-        println!("\n  {note}");
+        options.markup.line(&format!("\n  {note}"));
     }
-    println!(
-        "\n  {} projects, {} sessions\n",
-        format_number(sorted_projects.len() as i64, number_format),
-        format_number(total_sessions as i64, number_format)
-    );
+    options.markup.line(&format!(
+        "\n  {}\n",
+        locale.project_count(
+            &format_number(sorted_projects.len() as i64, locale),
+            &format_number(total_sessions as i64, locale)
+        )
+    ));
 }
 
-pub(crate) fn output_project_json(
-    projects: &[ProjectStats],
-    pricing_db: &PricingDb,
+pub(crate) fn project_json_rows<'a>(
+    projects: &'a [ProjectStats],
+    pricing_db: &'a PricingDb,
     order: SortOrder,
     show_cost: bool,
     supports_cache_read: bool,
-    currency: Option<&CurrencyConverter>,
-) -> String {
+    currency: Option<&'a CurrencyConverter>,
+) -> impl Iterator<Item = ProjectJson> + 'a {
     let mut sorted_projects = attach_costs(projects, |p| &p.models, pricing_db);
 
     match order {
@@ -269,47 +284,51 @@ pub(crate) fn output_project_json(
         SortOrder::Desc => sorted_projects.sort_by(|a, b| compare_cost(b.cost, a.cost)),
     }
 
-    let output: Vec<serde_json::Value> = sorted_projects
-        .iter()
-        .map(|costed| {
-            let project = costed.item;
-            let project_cost = costed.cost;
-
-            let mut models: Vec<_> = project.models.keys().cloned().collect();
-            models.sort();
-            let mut obj = serde_json::json!({
-                "project": project.project_name,
-                "project_path": project.project_path,
-                "session_count": project.session_count,
-                "input_tokens": project.stats.input_tokens,
-                "output_tokens": project.stats.output_tokens,
-                "cache_creation_tokens": project.stats.cache_creation,
-                "cache_read_tokens": project.stats.cache_read,
-                "cache_hit_rate": cache_hit_rate_json_value(
-                    project.stats.cache_hit_rate(supports_cache_read)
-                ),
-                "total_tokens": project.stats.total_tokens(),
-                "models": models,
-            });
-            if show_cost {
-                obj["cost"] = cost_json_value(project_cost, currency);
-                pricing_meta::add_json(&mut obj, &project.models, pricing_db);
-                let estimated_cost = sum_estimated_proxy_model_costs(&project.models, pricing_db);
-                if estimated_cost > 0.0 {
-                    obj["cost_kind"] = serde_json::json!(model_cost_kind(&project.models).as_str());
-                    obj["estimated_cost"] = cost_json_value(estimated_cost, currency);
-                }
-            }
-            obj
-        })
-        .collect();
-
-    serde_json::to_string_pretty(&output).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize JSON output: {e}");
-        "[]".to_string()
+    sorted_projects.into_iter().map(move |costed| {
+        let project = costed.item;
+        let currency = show_cost
+            .then(|| span_currency(currency, &project.days, pricing_db, CostDisplayMode::Total))
+            .flatten();
+        ProjectJson {
+            schema_version: SCHEMA_VERSION,
+            project: project.project_name.clone(),
+            project_path: project.project_path.clone(),
+            session_count: project.session_count,
+            tokens: TokensJson::new(&project.stats, supports_cache_read),
+            models: model_names(&project.models),
+            cost: show_cost.then(|| {
+                CostJson::for_models(
+                    &project.models,
+                    costed.cost,
+                    pricing_db,
+                    currency.as_deref(),
+                )
+            }),
+            exchange_rate: currency.as_deref().map(ExchangeRateJson::new),
+        }
     })
 }
 
+pub(crate) fn output_project_json(
+    projects: &[ProjectStats],
+    pricing_db: &PricingDb,
+    order: SortOrder,
+    show_cost: bool,
+    supports_cache_read: bool,
+    currency: Option<&CurrencyConverter>,
+) -> String {
+    let rows: Vec<_> = project_json_rows(
+        projects,
+        pricing_db,
+        order,
+        show_cost,
+        supports_cache_read,
+        currency,
+    )
+    .collect();
+    rows_to_string(&rows, true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -341,6 +360,7 @@ mod tests {
                     ..Default::default()
                 },
             )]),
+            days: HashMap::new(),
         }
     }
 
@@ -406,6 +426,7 @@ mod tests {
                     },
                 ),
             ]),
+            days: HashMap::new(),
         }];
         let result = output_project_json(&projects, &db, SortOrder::Desc, false, true, None);
         let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
@@ -432,4 +453,35 @@ mod tests {
         let parsed_asc: Vec<serde_json::Value> = serde_json::from_str(&asc).unwrap();
         assert_eq!(parsed_asc.len(), 2);
     }
+
+    #[test]
+    fn output_project_json_reports_exchange_rate() {
+        let db = PricingDb::default();
+        let projects = vec![make_project("app", "/app", 1, 100, 50)];
+        let converter = CurrencyConverter::from_rate_for_test("EUR", 0.9, "€");
+
+        let result = output_project_json(
+            &projects,
+            &db,
+            SortOrder::Desc,
+            true,
+            true,
+            Some(&converter),
+        );
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed[0]["currency"], "EUR");
+        assert_eq!(parsed[0]["exchange_rate"], 0.9);
+        assert_eq!(parsed[0]["exchange_rate_source"], "latest");
+
+        let result = output_project_json(
+            &projects,
+            &db,
+            SortOrder::Desc,
+            false,
+            true,
+            Some(&converter),
+        );
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+        assert!(parsed[0].get("currency").is_none());
+    }
 }
@@ -1,15 +1,20 @@
+use std::fmt::Write as _;
+
 use comfy_table::{Cell, Color};
-use serde_json::json;
 
 use crate::sdk::{CodexWeeklyValueError, CodexWeeklyValueEstimate};
-use crate::source::{CodexQuotaStatus, CodexWeeklyQuota};
+use crate::source::{CodexQuotaStatus, CodexQuotaWindow, CodexWeeklyQuota};
 use crate::utils::Timezone;
 
+use super::command_json::{QuotaJson, QuotaValueJson, QuotaWindowJson};
 use super::format::{
-    NumberFormat, create_styled_table, format_compact, header_cell, right_cell, styled_cell,
+    Locale, create_styled_table, format_compact, header_cell, right_cell, styled_cell,
 };
+use super::locale::Msg;
+use super::markup::Markup;
+use super::schema::SCHEMA_VERSION;
 
-fn rounded_pct(value: f64) -> f64 {
+pub(super) fn rounded_pct(value: f64) -> f64 {
     (value * 100.0).round() / 100.0
 }
 
@@ -17,52 +22,97 @@ fn rounded_cost(value: f64) -> f64 {
     (value * 1_000_000.0).round() / 1_000_000.0
 }
 
-fn timestamp(value: chrono::DateTime<chrono::Utc>) -> String {
+/// RFC 3339 UTC timestamp with whole seconds, as used in JSON output.
+pub(crate) fn timestamp(value: chrono::DateTime<chrono::Utc>) -> String {
     value.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
 }
 
+/// Stable machine name for a window duration (`weekly` keeps the `quota` name).
+pub(crate) fn quota_window_name(window_minutes: i64) -> String {
+    match window_minutes {
+        10_080 => "weekly".to_string(),
+        minutes if minutes % 60 == 0 => format!("{}h", minutes / 60),
+        minutes => format!("{minutes}m"),
+    }
+}
+
+/// Human label for a window duration, e.g. `7 days` or `5 hours`.
+pub(super) fn window_label(window_minutes: i64) -> String {
+    let plural = |count: i64, unit: &str| {
+        if count == 1 {
+            format!("1 {unit}")
+        } else {
+            format!("{count} {unit}s")
+        }
+    };
+    if window_minutes % 1_440 == 0 {
+        plural(window_minutes / 1_440, "day")
+    } else if window_minutes % 60 == 0 {
+        plural(window_minutes / 60, "hour")
+    } else {
+        plural(window_minutes, "minute")
+    }
+}
+
 pub(crate) type QuotaValueEstimate<'a> =
     Option<Result<&'a CodexWeeklyValueEstimate, &'a CodexWeeklyValueError>>;
 
-pub(crate) fn output_quota_json(
+pub(crate) fn quota_window_json(window: &CodexQuotaWindow) -> QuotaWindowJson {
+    QuotaWindowJson {
+        window: quota_window_name(window.window_minutes),
+        window_minutes: window.window_minutes,
+        used_pct: rounded_pct(window.used_pct),
+        remaining_pct: rounded_pct(window.remaining_pct),
+        projected_pct_at_reset: rounded_pct(window.projected_pct_at_reset),
+        status: window.status.as_str(),
+        observed_at: timestamp(window.observed_at),
+        resets_at: timestamp(window.resets_at),
+        estimated_depletion_at: window.estimated_depletion_at.map(timestamp),
+    }
+}
+
+/// Render the weekly report at the top level (the original schema) plus every
+/// current window, including the weekly one, under `windows`.
+pub(crate) fn quota_json_report(
     report: &CodexWeeklyQuota,
+    windows: &[CodexQuotaWindow],
     value_estimate: QuotaValueEstimate<'_>,
-) -> String {
-    let mut output = json!({
-        "source": "codex",
-        "window": "weekly",
-        "window_minutes": report.window_minutes,
-        "used_pct": rounded_pct(report.used_pct),
-        "remaining_pct": rounded_pct(report.remaining_pct),
-        "projected_pct_at_reset": rounded_pct(report.projected_pct_at_reset),
-        "status": report.status.as_str(),
-        "observed_at": timestamp(report.observed_at),
-        "resets_at": timestamp(report.resets_at),
-        "estimated_depletion_at": report.estimated_depletion_at.map(timestamp),
-    });
-
-    match value_estimate {
-        Some(Ok(estimate)) => {
-            output["value_estimate"] = json!({
-                "kind": "api_equivalent",
-                "observed_cost_usd": rounded_cost(estimate.observed_cost_usd),
-                "estimated_weekly_value_usd": rounded_cost(estimate.estimated_weekly_value_usd),
-                "observed_tokens": estimate.observed_tokens,
-                "estimated_weekly_tokens": estimate.estimated_weekly_tokens.round(),
-                "window_started_at": timestamp(estimate.window_started_at),
-                "valid_entries": estimate.valid_entries,
-                "dedup_skipped_entries": estimate.dedup_skipped_entries,
-            });
-            output["value_estimate_error"] = serde_json::Value::Null;
-        }
-        Some(Err(error)) => {
-            output["value_estimate"] = serde_json::Value::Null;
-            output["value_estimate_error"] = json!(error.to_string());
-        }
-        None => {}
+) -> QuotaJson {
+    let (value_estimate, value_estimate_error) = match value_estimate {
+        Some(Ok(estimate)) => (
+            Some(Some(QuotaValueJson {
+                kind: "api_equivalent",
+                observed_cost_usd: rounded_cost(estimate.observed_cost_usd),
+                estimated_weekly_value_usd: rounded_cost(estimate.estimated_weekly_value_usd),
+                observed_tokens: estimate.observed_tokens,
+                estimated_weekly_tokens: estimate.estimated_weekly_tokens.round(),
+                window_started_at: timestamp(estimate.window_started_at),
+                valid_entries: estimate.valid_entries,
+                dedup_skipped_entries: estimate.dedup_skipped_entries,
+            })),
+            Some(None),
+        ),
+        Some(Err(error)) => (Some(None), Some(Some(error.to_string()))),
+        None => (None, None),
+    };
+    QuotaJson {
+        schema_version: SCHEMA_VERSION,
+        source: "codex",
+        weekly: QuotaWindowJson {
+            window: "weekly".to_string(),
+            window_minutes: report.window_minutes,
+            used_pct: rounded_pct(report.used_pct),
+            remaining_pct: rounded_pct(report.remaining_pct),
+            projected_pct_at_reset: rounded_pct(report.projected_pct_at_reset),
+            status: report.status.as_str(),
+            observed_at: timestamp(report.observed_at),
+            resets_at: timestamp(report.resets_at),
+            estimated_depletion_at: report.estimated_depletion_at.map(timestamp),
+        },
+        windows: windows.iter().map(quota_window_json).collect(),
+        value_estimate,
+        value_estimate_error,
     }
-
-    output.to_string()
 }
 
 fn csv_field(value: &str) -> String {
@@ -73,27 +123,41 @@ fn csv_field(value: &str) -> String {
     }
 }
 
+fn window_csv_columns(window: &CodexQuotaWindow) -> String {
+    format!(
+        "codex,{},{},{:.2},{:.2},{:.2},{},{},{},{}",
+        quota_window_name(window.window_minutes),
+        window.window_minutes,
+        window.used_pct,
+        window.remaining_pct,
+        window.projected_pct_at_reset,
+        window.status.as_str(),
+        timestamp(window.observed_at),
+        timestamp(window.resets_at),
+        window
+            .estimated_depletion_at
+            .map(timestamp)
+            .unwrap_or_default(),
+    )
+}
+
+/// The weekly row comes first; other windows follow with empty value columns.
 pub(crate) fn output_quota_csv(
     report: &CodexWeeklyQuota,
+    windows: &[CodexQuotaWindow],
     value_estimate: QuotaValueEstimate<'_>,
 ) -> String {
-    let depletion = report
-        .estimated_depletion_at
-        .map(timestamp)
-        .unwrap_or_default();
+    let other_windows = windows
+        .iter()
+        .filter(|window| window.window_minutes != report.window_minutes);
     let Some(value_estimate) = value_estimate else {
-        return format!(
-            "source,window,window_minutes,used_pct,remaining_pct,projected_pct_at_reset,status,observed_at,resets_at,estimated_depletion_at\n\
-codex,weekly,{},{:.2},{:.2},{:.2},{},{},{},{}\n",
-            report.window_minutes,
-            report.used_pct,
-            report.remaining_pct,
-            report.projected_pct_at_reset,
-            report.status.as_str(),
-            timestamp(report.observed_at),
-            timestamp(report.resets_at),
-            depletion,
+        let mut csv = String::from(
+            "source,window,window_minutes,used_pct,remaining_pct,projected_pct_at_reset,status,observed_at,resets_at,estimated_depletion_at\n",
         );
+        for window in std::iter::once(report).chain(other_windows) {
+            let _ = writeln!(csv, "{}", window_csv_columns(window));
+        }
+        return csv;
     };
     let (observed_cost, weekly_value, observed_tokens, weekly_tokens, window_start, error) =
         match value_estimate {
@@ -114,114 +178,113 @@ codex,weekly,{},{:.2},{:.2},{:.2},{},{},{},{}\n",
                 csv_field(&error.to_string()),
             ),
         };
-    format!(
+    let mut csv = format!(
         "source,window,window_minutes,used_pct,remaining_pct,projected_pct_at_reset,status,observed_at,resets_at,estimated_depletion_at,observed_cost_usd,estimated_weekly_value_usd,observed_tokens,estimated_weekly_tokens,value_window_started_at,value_estimate_error\n\
-codex,weekly,{},{:.2},{:.2},{:.2},{},{},{},{},{},{},{},{},{},{}\n",
-        report.window_minutes,
-        report.used_pct,
-        report.remaining_pct,
-        report.projected_pct_at_reset,
-        report.status.as_str(),
-        timestamp(report.observed_at),
-        timestamp(report.resets_at),
-        depletion,
+{},{},{},{},{},{},{}\n",
+        window_csv_columns(report),
         observed_cost,
         weekly_value,
         observed_tokens,
         weekly_tokens,
         window_start,
         error,
-    )
+    );
+    for window in other_windows {
+        let _ = writeln!(csv, "{},,,,,,", window_csv_columns(window));
+    }
+    csv
+}
+
+fn status_color(status: CodexQuotaStatus, use_color: bool) -> Option<Color> {
+    if !use_color {
+        return None;
+    }
+    match status {
+        CodexQuotaStatus::OnTrack => Some(Color::Green),
+        CodexQuotaStatus::Watch => Some(Color::Yellow),
+        CodexQuotaStatus::LikelyExhausted | CodexQuotaStatus::Exhausted => Some(Color::Red),
+    }
 }
 
 pub(crate) fn print_quota_table(
     report: &CodexWeeklyQuota,
+    windows: &[CodexQuotaWindow],
     value_estimate: QuotaValueEstimate<'_>,
     timezone: Timezone,
-    number_format: NumberFormat,
+    locale: Locale,
     use_color: bool,
+    markup: Markup,
 ) {
+    let h_cell = |msg| header_cell(locale.text(msg), use_color);
     let mut table = create_styled_table();
     table.set_header(vec![
-        header_cell("Window", use_color),
-        header_cell("Used", use_color),
-        header_cell("Remaining", use_color),
-        header_cell("Projected", use_color),
-        header_cell("Status", use_color),
-        header_cell("Resets", use_color),
-        header_cell("Est. depletion", use_color),
+        h_cell(Msg::Window),
+        h_cell(Msg::Used),
+        h_cell(Msg::Remaining),
+        h_cell(Msg::Projected),
+        h_cell(Msg::Status),
+        h_cell(Msg::Resets),
+        h_cell(Msg::EstDepletion),
     ]);
 
-    let status_color = if use_color {
-        match report.status {
-            CodexQuotaStatus::OnTrack => Some(Color::Green),
-            CodexQuotaStatus::Watch => Some(Color::Yellow),
-            CodexQuotaStatus::LikelyExhausted | CodexQuotaStatus::Exhausted => Some(Color::Red),
-        }
-    } else {
-        None
+    let local = |value: chrono::DateTime<chrono::Utc>| {
+        timezone
+            .to_fixed_offset(value)
+            .format("%Y-%m-%d %H:%M %:z")
+            .to_string()
     };
-    let depletion = report.estimated_depletion_at.map_or_else(
-        || "—".to_string(),
-        |value| {
-            timezone
-                .to_fixed_offset(value)
-                .format("%Y-%m-%d %H:%M %:z")
-                .to_string()
-        },
-    );
-
-    table.add_row(vec![
-        Cell::new("7 days"),
-        right_cell(&format!("{:.1}%", report.used_pct), status_color, false),
-        right_cell(&format!("{:.1}%", report.remaining_pct), None, false),
-        right_cell(
-            &format!("{:.1}%", report.projected_pct_at_reset),
-            status_color,
-            false,
-        ),
-        styled_cell(report.status.as_str(), status_color, true),
-        Cell::new(
-            timezone
-                .to_fixed_offset(report.resets_at)
-                .format("%Y-%m-%d %H:%M %:z")
-                .to_string(),
-        ),
-        Cell::new(depletion),
-    ]);
+    for window in windows {
+        let status_color = status_color(window.status, use_color);
+        table.add_row(vec![
+            Cell::new(window_label(window.window_minutes)),
+            right_cell(&format!("{:.1}%", window.used_pct), status_color, false),
+            right_cell(&format!("{:.1}%", window.remaining_pct), None, false),
+            right_cell(
+                &format!("{:.1}%", window.projected_pct_at_reset),
+                status_color,
+                false,
+            ),
+            styled_cell(window.status.as_str(), status_color, true),
+            Cell::new(local(window.resets_at)),
+            Cell::new(
+                window
+                    .estimated_depletion_at
+                    .map_or_else(|| "—".to_string(), local),
+            ),
+        ]);
+    }
 
-    println!("{table}");
-    println!(
+    markup.table(&table, 0, locale);
+    markup.line(&format!(
         "Observed: {} · Projection is a pace estimate, not a token allowance.",
         timezone
             .to_fixed_offset(report.observed_at)
             .format("%Y-%m-%d %H:%M:%S %:z")
-    );
+    ));
     match value_estimate {
         Some(Ok(estimate)) => {
-            println!(
+            markup.line(&format!(
                 "API-equivalent weekly value: ≈${:.2} (observed ${:.2} at {:.1}% used)",
                 estimate.estimated_weekly_value_usd, estimate.observed_cost_usd, estimate.used_pct,
-            );
-            println!(
+            ));
+            markup.line(&format!(
                 "Estimated weekly tokens at the current model/cache mix: ≈{} (observed {})",
-                format_compact(
-                    estimate.estimated_weekly_tokens.round() as i64,
-                    number_format
-                ),
-                format_compact(estimate.observed_tokens, number_format),
-            );
-            println!(
+                format_compact(estimate.estimated_weekly_tokens.round() as i64, locale),
+                format_compact(estimate.observed_tokens, locale),
+            ));
+            markup.line(&format!(
                 "Value window: {} to the quota observation above.",
                 timezone
                     .to_fixed_offset(estimate.window_started_at)
                     .format("%Y-%m-%d %H:%M:%S %:z")
-            );
-            println!(
-                "Dollar and token values are local API-pricing estimates, not official provider allowances."
+            ));
+            markup.line(
+                "Dollar and token values are local API-pricing estimates, not official provider allowances.",
             );
         }
-        Some(Err(error)) => println!("API-equivalent weekly value unavailable: {error}"),
+        Some(Err(error)) => {
+            markup.line(&format!("API-equivalent weekly value unavailable: {error}"));
+        }
         None => {}
     }
 }
@@ -262,8 +325,12 @@ mod tests {
     #[test]
     fn json_contains_machine_readable_quota_fields() {
         let estimate = value_estimate();
-        let value: serde_json::Value =
-            serde_json::from_str(&output_quota_json(&report(), Some(Ok(&estimate)))).unwrap();
+        let value: serde_json::Value = serde_json::to_value(quota_json_report(
+            &report(),
+            &[report()],
+            Some(Ok(&estimate)),
+        ))
+        .unwrap();
 
         assert_eq!(value["window"], "weekly");
         assert_eq!(value["used_pct"], 25.0);
@@ -283,7 +350,7 @@ mod tests {
     #[test]
     fn csv_has_stable_header_and_values() {
         let estimate = value_estimate();
-        let csv = output_quota_csv(&report(), Some(Ok(&estimate)));
+        let csv = output_quota_csv(&report(), &[report()], Some(Ok(&estimate)));
         let mut lines = csv.lines();
 
         assert_eq!(
@@ -301,7 +368,8 @@ mod tests {
     fn json_exposes_value_estimate_error_without_hiding_quota() {
         let error = CodexWeeklyValueError::ZeroUsagePercentage;
         let value: serde_json::Value =
-            serde_json::from_str(&output_quota_json(&report(), Some(Err(&error)))).unwrap();
+            serde_json::to_value(quota_json_report(&report(), &[report()], Some(Err(&error))))
+                .unwrap();
 
         assert_eq!(value["used_pct"], 25.0);
         assert!(value["value_estimate"].is_null());
@@ -312,4 +380,58 @@ mod tests {
                 .contains("used percentage is zero")
         );
     }
+
+    fn primary_window() -> CodexQuotaWindow {
+        CodexQuotaWindow {
+            observed_at: "2026-08-22T00:00:00Z".parse::<DateTime<_>>().unwrap(),
+            resets_at: "2026-08-22T03:00:00Z".parse::<DateTime<_>>().unwrap(),
+            estimated_depletion_at: None,
+            window_minutes: 300,
+            used_pct: 80.0,
+            remaining_pct: 20.0,
+            projected_pct_at_reset: 200.0,
+            status: CodexQuotaStatus::LikelyExhausted,
+        }
+    }
+
+    #[test]
+    fn json_lists_every_window() {
+        let windows = [primary_window(), report()];
+        let value: serde_json::Value =
+            serde_json::to_value(quota_json_report(&report(), &windows, None)).unwrap();
+
+        assert_eq!(value["window"], "weekly");
+        assert_eq!(value["windows"][0]["window"], "5h");
+        assert_eq!(value["windows"][0]["used_pct"], 80.0);
+        assert_eq!(value["windows"][0]["status"], "likely_exhausted");
+        assert_eq!(value["windows"][1]["window_minutes"], 10_080);
+    }
+
+    #[test]
+    fn csv_puts_weekly_row_first_and_pads_other_windows() {
+        let estimate = value_estimate();
+        let windows = [primary_window(), report()];
+        let csv = output_quota_csv(&report(), &windows, Some(Ok(&estimate)));
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("codex,weekly,10080,"));
+        assert!(lines[2].starts_with("codex,5h,300,80.00,20.00,200.00,likely_exhausted"));
+        assert_eq!(
+            lines[2].split(',').count(),
+            lines[0].split(',').count(),
+            "{}",
+            lines[2]
+        );
+    }
+
+    #[test]
+    fn window_names_and_labels_cover_common_durations() {
+        assert_eq!(quota_window_name(10_080), "weekly");
+        assert_eq!(quota_window_name(300), "5h");
+        assert_eq!(quota_window_name(45), "45m");
+        assert_eq!(window_label(10_080), "7 days");
+        assert_eq!(window_label(300), "5 hours");
+        assert_eq!(window_label(60), "1 hour");
+    }
 }
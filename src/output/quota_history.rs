@@ -0,0 +1,256 @@
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Utc};
+use comfy_table::{Cell, Color};
+
+use crate::source::{CodexQuotaHistory, CodexQuotaWindowHistory};
+use crate::utils::Timezone;
+
+use super::command_json::{QuotaHistoryJson, QuotaHistoryWindowJson, QuotaSampleJson};
+use super::format::{Locale, create_styled_table, header_cell, right_cell, styled_cell};
+use super::locale::Msg;
+use super::quota::{quota_window_name, rounded_pct, timestamp, window_label};
+use super::schema::SCHEMA_VERSION;
+
+const SPARKLINE_WIDTH: usize = 24;
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Render the window's usage curve as fixed-width block characters.
+///
+/// Each slot shows the latest used percentage observed by the end of its
+/// slice of the window; in the active window, slots after the latest sample
+/// stay blank.
+fn sparkline(window: &CodexQuotaWindowHistory) -> String {
+    let Some(last) = window.samples.last() else {
+        return String::new();
+    };
+    let span = (window.resets_at - window.started_at).num_seconds().max(1);
+    let mut line = String::with_capacity(SPARKLINE_WIDTH * 3);
+    let mut samples = window.samples.iter().peekable();
+    let mut current: Option<f64> = None;
+    for slot in 1..=SPARKLINE_WIDTH {
+        let slot_end = window.started_at
+            + chrono::Duration::seconds(span * slot as i64 / SPARKLINE_WIDTH as i64);
+        while let Some(sample) = samples.next_if(|sample| sample.observed_at <= slot_end) {
+            current = Some(sample.used_pct);
+        }
+        let slot_start = window.started_at
+            + chrono::Duration::seconds(span * (slot as i64 - 1) / SPARKLINE_WIDTH as i64);
+        match current {
+            _ if window.active && slot_start > last.observed_at => line.push(' '),
+            Some(pct) => {
+                let level = (pct.clamp(0.0, 100.0) / 100.0 * 7.0).round() as usize;
+                line.push(SPARKLINE_LEVELS[level.min(7)]);
+            }
+            None => line.push(SPARKLINE_LEVELS[0]),
+        }
+    }
+    line.trim_end().to_string()
+}
+
+fn window_json(window: &CodexQuotaWindowHistory) -> QuotaHistoryWindowJson {
+    QuotaHistoryWindowJson {
+        window: quota_window_name(window.window_minutes),
+        window_minutes: window.window_minutes,
+        started_at: timestamp(window.started_at),
+        resets_at: timestamp(window.resets_at),
+        active: window.active,
+        peak_pct: rounded_pct(window.peak_pct),
+        last_pct: rounded_pct(window.last_pct()),
+        exhausted: window.exhausted_at.is_some(),
+        exhausted_at: window.exhausted_at.map(timestamp),
+        series: window
+            .samples
+            .iter()
+            .map(|sample| QuotaSampleJson {
+                observed_at: timestamp(sample.observed_at),
+                used_pct: rounded_pct(sample.used_pct),
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn quota_history_json_report(history: &CodexQuotaHistory) -> QuotaHistoryJson {
+    QuotaHistoryJson {
+        schema_version: SCHEMA_VERSION,
+        source: "codex",
+        windows: history.windows.iter().map(window_json).collect(),
+        invalid_records: history.invalid_records,
+    }
+}
+
+/// One CSV row per series point so spreadsheets can plot the curve directly.
+pub(crate) fn output_quota_history_csv(history: &CodexQuotaHistory) -> String {
+    let mut csv = String::from(
+        "source,window,window_minutes,window_started_at,resets_at,observed_at,used_pct,peak_pct,exhausted_at\n",
+    );
+    for window in &history.windows {
+        let exhausted_at = window.exhausted_at.map(timestamp).unwrap_or_default();
+        for sample in &window.samples {
+            let _ = writeln!(
+                csv,
+                "codex,{},{},{},{},{},{:.2},{:.2},{}",
+                quota_window_name(window.window_minutes),
+                window.window_minutes,
+                timestamp(window.started_at),
+                timestamp(window.resets_at),
+                timestamp(sample.observed_at),
+                sample.used_pct,
+                window.peak_pct,
+                exhausted_at,
+            );
+        }
+    }
+    if history.invalid_records > 0 {
+        let _ = writeln!(csv, "# invalid_records,{}", history.invalid_records);
+    }
+    csv
+}
+
+fn peak_color(pct: f64, use_color: bool) -> Option<Color> {
+    if !use_color {
+        None
+    } else if pct >= 100.0 {
+        Some(Color::Red)
+    } else if pct >= 90.0 {
+        Some(Color::Yellow)
+    } else {
+        Some(Color::Green)
+    }
+}
+
+pub(crate) fn print_quota_history_table(
+    history: &CodexQuotaHistory,
+    timezone: Timezone,
+    locale: Locale,
+    use_color: bool,
+) {
+    let local = |value: DateTime<Utc>| {
+        timezone
+            .to_fixed_offset(value)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    };
+
+    let h_cell = |msg| header_cell(locale.text(msg), use_color);
+    let mut table = create_styled_table();
+    table.set_header(vec![
+        h_cell(Msg::Window),
+        h_cell(Msg::Started),
+        h_cell(Msg::Resets),
+        h_cell(Msg::Peak),
+        h_cell(Msg::Last),
+        h_cell(Msg::Hit100),
+        h_cell(Msg::Trend),
+    ]);
+
+    for window in &history.windows {
+        let color = peak_color(window.peak_pct, use_color);
+        let label = if window.active {
+            format!(
+                "{} ({})",
+                window_label(window.window_minutes),
+                locale.text(Msg::Active)
+            )
+        } else {
+            window_label(window.window_minutes)
+        };
+        table.add_row(vec![
+            styled_cell(&label, None, window.active),
+            Cell::new(local(window.started_at)),
+            Cell::new(local(window.resets_at)),
+            right_cell(&format!("{:.1}%", window.peak_pct), color, false),
+            right_cell(&format!("{:.1}%", window.last_pct()), None, false),
+            Cell::new(window.exhausted_at.map_or_else(|| "—".to_string(), local)),
+            Cell::new(sparkline(window)),
+        ]);
+    }
+
+    println!("{table}");
+    let exhausted = history
+        .windows
+        .iter()
+        .filter(|window| window.exhausted_at.is_some())
+        .count();
+    println!(
+        "{}",
+        locale.quota_history_summary(history.windows.len(), exhausted)
+    );
+    if history.invalid_records > 0 {
+        println!("{}", locale.skipped_quota_records(history.invalid_records));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::CodexQuotaSample;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn history() -> CodexQuotaHistory {
+        CodexQuotaHistory {
+            windows: vec![CodexQuotaWindowHistory {
+                window_minutes: 10_080,
+                started_at: utc("2026-08-01T00:00:00Z"),
+                resets_at: utc("2026-08-08T00:00:00Z"),
+                samples: vec![
+                    CodexQuotaSample {
+                        observed_at: utc("2026-08-01T12:00:00Z"),
+                        used_pct: 10.0,
+                    },
+                    CodexQuotaSample {
+                        observed_at: utc("2026-08-05T00:00:00Z"),
+                        used_pct: 100.0,
+                    },
+                ],
+                peak_pct: 100.0,
+                exhausted_at: Some(utc("2026-08-05T00:00:00Z")),
+                active: false,
+            }],
+            invalid_records: 2,
+        }
+    }
+
+    #[test]
+    fn json_exposes_window_summary_and_series() {
+        let value: serde_json::Value =
+            serde_json::to_value(quota_history_json_report(&history())).unwrap();
+        let window = &value["windows"][0];
+
+        assert_eq!(window["window"], "weekly");
+        assert_eq!(window["peak_pct"], 100.0);
+        assert_eq!(window["exhausted"], true);
+        assert_eq!(window["exhausted_at"], "2026-08-05T00:00:00Z");
+        assert_eq!(window["series"].as_array().unwrap().len(), 2);
+        assert_eq!(window["series"][1]["used_pct"], 100.0);
+        assert_eq!(value["invalid_records"], 2);
+    }
+
+    #[test]
+    fn csv_emits_one_row_per_series_point() {
+        let csv = output_quota_history_csv(&history());
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "source,window,window_minutes,window_started_at,resets_at,observed_at,used_pct,peak_pct,exhausted_at"
+        );
+        assert_eq!(
+            lines[2],
+            "codex,weekly,10080,2026-08-01T00:00:00Z,2026-08-08T00:00:00Z,2026-08-05T00:00:00Z,100.00,100.00,2026-08-05T00:00:00Z"
+        );
+        assert_eq!(lines[3], "# invalid_records,2");
+    }
+
+    #[test]
+    fn sparkline_carries_latest_value_forward() {
+        let line = sparkline(&history().windows[0]);
+
+        assert_eq!(line.chars().count(), SPARKLINE_WIDTH);
+        assert!(line.starts_with('▁'));
+        assert!(line.ends_with('█'));
+    }
+}
@@ -0,0 +1,650 @@
+//! Typed JSON reports for the usage commands and their published schema.
+//!
+//! `daily`, `weekly`, `monthly`, `session`, `project`, and `blocks` build
+//! these structs instead of hand-assembled `serde_json::Value`s, so the JSON
+//! they print and the schema `ccstats schema <report>` prints come from the
+//! same definition; the other commands' reports live in
+//! [`super::command_json`]. Every report carries [`SCHEMA_VERSION`]; see the
+//! README's JSON compatibility policy for when it changes.
+
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::core::{DataQuality, Stats};
+use crate::output::command_json::{
+    AgentSessionJson, CatalogEntryJson, ClaudeHookJson, EndpointJson, FilesJson, NotifyPayloadJson,
+    PriceQuoteJson, PricingAuditJson, PricingDiffJson, PricingRefreshJson, QuotaHistoryJson,
+    QuotaJson, SessionContextJson, SessionTimelineJson, SourceJson, StatuslineJson,
+    ToolCommandsJson, ToolsJson, TopJson,
+};
+use crate::output::pricing_meta;
+use crate::pricing::{
+    CurrencyConverter, PricingDb, PricingSource, calculate_estimated_proxy_cost, model_cost_kind,
+    pricing_source_for_model_stats, pricing_source_for_models, sum_estimated_proxy_model_costs,
+};
+
+/// Version of the typed JSON reports. Bumped only for breaking changes:
+/// removing or renaming a field, or changing its type or meaning.
+pub(crate) const SCHEMA_VERSION: u32 = 1;
+
+/// Reports with a published JSON Schema.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub(crate) enum SchemaReport {
+    /// `daily --json`
+    Daily,
+    /// `weekly --json`
+    Weekly,
+    /// `monthly --json`
+    Monthly,
+    /// `session --json`
+    Session,
+    /// `session show --json`
+    SessionShow,
+    /// `session --context --json`
+    SessionContext,
+    /// `project --json`
+    Project,
+    /// `blocks --json`
+    Blocks,
+    /// `tools --json`
+    Tools,
+    /// `tools commands --json`
+    ToolsCommands,
+    /// `top --json`
+    Top,
+    /// `quota --json`
+    Quota,
+    /// `quota --history --json`
+    QuotaHistory,
+    /// `statusline --json`
+    Statusline,
+    /// `endpoints --json`
+    Endpoints,
+    /// `agents --json`
+    Agents,
+    /// `files --json`
+    Files,
+    /// `audit pricing --json`
+    AuditPricing,
+    /// `pricing show --json`
+    PricingShow,
+    /// `pricing list --json`
+    PricingList,
+    /// `pricing refresh --json`
+    PricingRefresh,
+    /// `pricing diff --json`
+    PricingDiff,
+    /// `notify --json` and the payload sent to notify hooks
+    Notify,
+    /// `sources --json`
+    Sources,
+    /// `statusline --claude-hook --json`
+    ClaudeHook,
+}
+
+impl SchemaReport {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+            Self::Session => "session",
+            Self::SessionShow => "session-show",
+            Self::SessionContext => "session-context",
+            Self::Project => "project",
+            Self::Blocks => "blocks",
+            Self::Tools => "tools",
+            Self::ToolsCommands => "tools-commands",
+            Self::Top => "top",
+            Self::Quota => "quota",
+            Self::QuotaHistory => "quota-history",
+            Self::Statusline => "statusline",
+            Self::Endpoints => "endpoints",
+            Self::Agents => "agents",
+            Self::Files => "files",
+            Self::AuditPricing => "audit-pricing",
+            Self::PricingShow => "pricing-show",
+            Self::PricingList => "pricing-list",
+            Self::PricingRefresh => "pricing-refresh",
+            Self::PricingDiff => "pricing-diff",
+            Self::Notify => "notify",
+            Self::Sources => "sources",
+            Self::ClaudeHook => "claude-hook",
+        }
+    }
+
+    /// Subcommand that prints the report, as typed on the command line.
+    fn command(self) -> &'static str {
+        match self {
+            Self::SessionShow => "session show",
+            Self::SessionContext => "session --context",
+            Self::ToolsCommands => "tools commands",
+            Self::QuotaHistory => "quota --history",
+            Self::AuditPricing => "audit pricing",
+            Self::PricingShow => "pricing show",
+            Self::PricingList => "pricing list",
+            Self::PricingRefresh => "pricing refresh",
+            Self::PricingDiff => "pricing diff",
+            Self::ClaudeHook => "statusline --claude-hook",
+            _ => self.name(),
+        }
+    }
+
+    /// JSON Schema of the report's `--json` output: an array of rows, or
+    /// one object for reports such as `tools` and `top`.
+    pub(crate) fn json_schema(self) -> serde_json::Value {
+        let mut schema = match self {
+            Self::Daily | Self::Weekly | Self::Monthly => schemars::schema_for!(Vec<PeriodJsonRow>),
+            Self::Session => schemars::schema_for!(Vec<SessionJson>),
+            Self::SessionShow => schemars::schema_for!(SessionTimelineJson),
+            Self::SessionContext => schemars::schema_for!(Vec<SessionContextJson>),
+            Self::Project => schemars::schema_for!(Vec<ProjectJson>),
+            Self::Blocks => schemars::schema_for!(Vec<BlockJson>),
+            Self::Tools => schemars::schema_for!(ToolsJson),
+            Self::ToolsCommands => schemars::schema_for!(ToolCommandsJson),
+            Self::Top => schemars::schema_for!(TopJson),
+            Self::Quota => schemars::schema_for!(QuotaJson),
+            Self::QuotaHistory => schemars::schema_for!(QuotaHistoryJson),
+            Self::Statusline => schemars::schema_for!(StatuslineJson),
+            Self::Endpoints => schemars::schema_for!(Vec<EndpointJson>),
+            Self::Agents => schemars::schema_for!(Vec<AgentSessionJson>),
+            Self::Files => schemars::schema_for!(FilesJson),
+            Self::AuditPricing => schemars::schema_for!(PricingAuditJson),
+            Self::PricingShow => schemars::schema_for!(PriceQuoteJson),
+            Self::PricingList => schemars::schema_for!(Vec<CatalogEntryJson>),
+            Self::PricingRefresh => schemars::schema_for!(Vec<PricingRefreshJson>),
+            Self::PricingDiff => schemars::schema_for!(Vec<PricingDiffJson>),
+            Self::Notify => schemars::schema_for!(NotifyPayloadJson<'static>),
+            Self::Sources => schemars::schema_for!(Vec<SourceJson>),
+            Self::ClaudeHook => schemars::schema_for!(ClaudeHookJson),
+        }
+        .to_value();
+        if let Some(obj) = schema.as_object_mut() {
+            obj.insert(
+                "$id".to_string(),
+                serde_json::json!(format!(
+                    "https://github.com/majiayu000/ccstats/schema/v{SCHEMA_VERSION}/{}.json",
+                    self.name()
+                )),
+            );
+            obj.insert(
+                "title".to_string(),
+                serde_json::json!(format!("ccstats {} --json", self.command())),
+            );
+        }
+        schema
+    }
+}
+
+/// Cost in the display currency; `None` (JSON `null`) when unpriced.
+pub(crate) fn cost_amount(cost: f64, currency: Option<&CurrencyConverter>) -> Option<f64> {
+    let amount = currency.map_or(cost, |conv| conv.convert(cost));
+    (!amount.is_nan()).then_some(amount)
+}
+
+/// Cache hit rate in percent, rounded to two decimals.
+pub(crate) fn cache_hit_rate_amount(rate: Option<f64>) -> Option<f64> {
+    rate.map(|value| (value * 100.0).round() / 100.0)
+}
+
+/// Token counters shared by every usage row.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct TokensJson {
+    pub(crate) input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) reasoning_tokens: i64,
+    pub(crate) cache_creation_tokens: i64,
+    pub(crate) cache_read_tokens: i64,
+    /// Cache reads as a percentage of all prompt tokens; `null` when the
+    /// source does not report cache reads.
+    pub(crate) cache_hit_rate: Option<f64>,
+    pub(crate) total_tokens: i64,
+}
+
+impl TokensJson {
+    pub(crate) fn new(stats: &Stats, supports_cache_read: bool) -> Self {
+        Self {
+            input_tokens: stats.input_tokens,
+            output_tokens: stats.output_tokens,
+            reasoning_tokens: stats.reasoning_tokens,
+            cache_creation_tokens: stats.cache_creation,
+            cache_read_tokens: stats.cache_read,
+            cache_hit_rate: cache_hit_rate_amount(stats.cache_hit_rate(supports_cache_read)),
+            total_tokens: stats.total_tokens(),
+        }
+    }
+}
+
+/// Where the prices behind a cost came from.
+#[derive(Debug, Serialize, JsonSchema)]
+#[allow(clippy::struct_field_names)] // Flattened into rows, where the prefix is the key.
+pub(crate) struct PricingJson {
+    /// `live`, `cache`, `cache_stale`, `fallback`, `catalog`, `recorded`,
+    /// `mixed`, or `unknown`.
+    pub(crate) pricing_source: &'static str,
+    /// Age of the cached price list, for cache-backed sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) pricing_cache_age_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) pricing_cache_mtime_epoch_seconds: Option<u64>,
+    /// Price catalog that priced the model (per-model rows only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) pricing_catalog: Option<String>,
+}
+
+impl PricingJson {
+    pub(crate) fn new(source: PricingSource, pricing_db: &PricingDb) -> Self {
+        let cache_backed = pricing_meta::needs_cache_fields(source, pricing_db);
+        Self {
+            pricing_source: source.as_str(),
+            pricing_cache_age_seconds: cache_backed
+                .then(|| pricing_db.cache_age_seconds())
+                .flatten(),
+            pricing_cache_mtime_epoch_seconds: cache_backed
+                .then(|| pricing_db.cache_modified_epoch_seconds())
+                .flatten(),
+            pricing_catalog: None,
+        }
+    }
+}
+
+/// Cost fields, present unless costs are hidden.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct CostJson {
+    /// Cost in USD, or in `--currency`; `null` when a model has no price.
+    pub(crate) cost: Option<f64>,
+    #[serde(flatten)]
+    pub(crate) pricing: PricingJson,
+    /// `real`, `estimated_proxy`, or `mixed`; present with `estimated_cost`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) cost_kind: Option<&'static str>,
+    /// Estimated proxy cost, present when part of the usage has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)] // Absent, or `null` when unpriced.
+    pub(crate) estimated_cost: Option<Option<f64>>,
+}
+
+impl CostJson {
+    /// Cost fields of a row aggregating `models`, given its display cost.
+    pub(crate) fn for_models(
+        models: &HashMap<String, Stats>,
+        cost: f64,
+        pricing_db: &PricingDb,
+        currency: Option<&CurrencyConverter>,
+    ) -> Self {
+        let estimated_cost = sum_estimated_proxy_model_costs(models, pricing_db);
+        Self {
+            cost: cost_amount(cost, currency),
+            pricing: PricingJson::new(pricing_source_for_models(models, pricing_db), pricing_db),
+            cost_kind: (estimated_cost > 0.0).then(|| model_cost_kind(models).as_str()),
+            estimated_cost: (estimated_cost > 0.0).then(|| cost_amount(estimated_cost, currency)),
+        }
+    }
+
+    /// Cost fields of a single model's row, given its display cost.
+    pub(crate) fn for_model(
+        model: &str,
+        stats: &Stats,
+        cost: f64,
+        pricing_db: &PricingDb,
+        currency: Option<&CurrencyConverter>,
+    ) -> Self {
+        let source = pricing_source_for_model_stats(model, stats, pricing_db);
+        let mut pricing = PricingJson::new(source, pricing_db);
+        if source != PricingSource::Recorded {
+            pricing.pricing_catalog = pricing_db.catalog_for_model(model).map(str::to_string);
+        }
+        let estimated_cost = calculate_estimated_proxy_cost(stats, model, pricing_db);
+        Self {
+            cost: cost_amount(cost, currency),
+            pricing,
+            cost_kind: (estimated_cost > 0.0).then(|| stats.cost_kind().as_str()),
+            estimated_cost: (estimated_cost > 0.0).then(|| cost_amount(estimated_cost, currency)),
+        }
+    }
+}
+
+/// The currency a row's costs were converted to, present with `--currency`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ExchangeRateJson {
+    pub(crate) currency: String,
+    /// Units of `currency` per USD applied to this row.
+    pub(crate) exchange_rate: f64,
+    /// `identity`, `fixed`, `file`, `snapshots`, or `latest`.
+    pub(crate) exchange_rate_source: &'static str,
+}
+
+impl ExchangeRateJson {
+    pub(crate) fn new(currency: &CurrencyConverter) -> Self {
+        Self {
+            currency: currency.currency_code().to_string(),
+            exchange_rate: currency.rate(),
+            exchange_rate_source: currency.rate_source().as_str(),
+        }
+    }
+}
+
+/// Record counts behind the report.
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema)]
+pub(crate) struct DataQualityJson {
+    pub(crate) valid_entries: i64,
+    pub(crate) dedup_skipped_entries: i64,
+    pub(crate) parse_errors: usize,
+}
+
+impl From<DataQuality> for DataQualityJson {
+    fn from(data_quality: DataQuality) -> Self {
+        Self {
+            valid_entries: data_quality.valid_entries,
+            dedup_skipped_entries: data_quality.dedup_skipped_entries,
+            parse_errors: data_quality.parse_errors,
+        }
+    }
+}
+
+/// The period a row covers; the key is named after the report.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PeriodKeyJson {
+    /// `YYYY-MM-DD` (daily).
+    Date(String),
+    /// `YYYY-MM-DD` of the week's Monday (weekly).
+    Week(String),
+    /// `YYYY-MM` (monthly).
+    Month(String),
+}
+
+/// Row of `daily`, `weekly`, or `monthly` output.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub(crate) enum PeriodJsonRow {
+    Period(Box<PeriodJson>),
+    /// Lone row carrying `data_quality` when no usage matched.
+    #[schemars(extend("additionalProperties" = false))]
+    DataQualityOnly {
+        schema_version: u32,
+        data_quality: DataQualityJson,
+    },
+}
+
+/// Usage of one day, week, or month.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct PeriodJson {
+    pub(crate) schema_version: u32,
+    #[serde(flatten)]
+    pub(crate) period: PeriodKeyJson,
+    #[serde(flatten)]
+    pub(crate) tokens: TokensJson,
+    /// Models used, without `--breakdown`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) models: Option<Vec<String>>,
+    /// Per-model rows, with `--breakdown`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) breakdown: Option<Vec<ModelBreakdownJson>>,
+    #[serde(flatten)]
+    pub(crate) cost: Option<CostJson>,
+    #[serde(flatten)]
+    pub(crate) exchange_rate: Option<ExchangeRateJson>,
+    /// Monthly budget status, for `monthly --monthly-budget`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) budget: Option<BudgetJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) data_quality: Option<DataQualityJson>,
+}
+
+/// One model's usage within a period.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ModelBreakdownJson {
+    pub(crate) model: String,
+    #[serde(flatten)]
+    pub(crate) tokens: TokensJson,
+    #[serde(flatten)]
+    pub(crate) cost: Option<CostJson>,
+    /// Usage billed on non-standard service tiers; the rest is standard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) service_tiers: Option<Vec<ServiceTierJson>>,
+}
+
+/// A model's usage on one non-standard service tier.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ServiceTierJson {
+    /// `batch`, `flex`, or `priority`.
+    pub(crate) tier: &'static str,
+    pub(crate) calls: i64,
+    pub(crate) input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) reasoning_tokens: i64,
+    pub(crate) cache_creation_tokens: i64,
+    pub(crate) cache_read_tokens: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::option_option)] // Absent, or `null` when unpriced.
+    pub(crate) cost: Option<Option<f64>>,
+}
+
+/// Spend and forecast against the monthly budget.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct BudgetJson {
+    pub(crate) limit: Option<f64>,
+    pub(crate) spent: Option<f64>,
+    pub(crate) projected: Option<f64>,
+    pub(crate) remaining: Option<f64>,
+    pub(crate) used_pct: Option<f64>,
+    pub(crate) projected_pct: Option<f64>,
+    pub(crate) days_elapsed: u32,
+    pub(crate) days_in_month: u32,
+    /// `on_track`, `watch`, `over_budget`, or `unknown`.
+    pub(crate) status: &'static str,
+    #[serde(flatten)]
+    pub(crate) pricing: PricingJson,
+}
+
+/// Row of `session` output.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct SessionJson {
+    pub(crate) schema_version: u32,
+    pub(crate) session_id: String,
+    pub(crate) project: String,
+    pub(crate) project_path: String,
+    pub(crate) first_timestamp: String,
+    pub(crate) last_timestamp: String,
+    #[serde(flatten)]
+    pub(crate) tokens: TokensJson,
+    pub(crate) models: Vec<String>,
+    #[serde(flatten)]
+    pub(crate) cost: Option<CostJson>,
+    #[serde(flatten)]
+    pub(crate) exchange_rate: Option<ExchangeRateJson>,
+}
+
+/// Row of `project` output.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ProjectJson {
+    pub(crate) schema_version: u32,
+    pub(crate) project: String,
+    pub(crate) project_path: String,
+    pub(crate) session_count: usize,
+    #[serde(flatten)]
+    pub(crate) tokens: TokensJson,
+    pub(crate) models: Vec<String>,
+    #[serde(flatten)]
+    pub(crate) cost: Option<CostJson>,
+    #[serde(flatten)]
+    pub(crate) exchange_rate: Option<ExchangeRateJson>,
+}
+
+/// Row of `blocks` output.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct BlockJson {
+    pub(crate) schema_version: u32,
+    pub(crate) block_start: String,
+    pub(crate) block_end: String,
+    #[serde(flatten)]
+    pub(crate) tokens: TokensJson,
+    pub(crate) models: Vec<String>,
+    #[serde(flatten)]
+    pub(crate) cost: Option<CostJson>,
+    #[serde(flatten)]
+    pub(crate) exchange_rate: Option<ExchangeRateJson>,
+}
+
+/// Sorted model names of a usage map.
+pub(crate) fn model_names<V>(models: &HashMap<String, V>) -> Vec<String> {
+    let mut names: Vec<_> = models.keys().cloned().collect();
+    names.sort();
+    names
+}
+
+/// Serialize rows, falling back to an empty array.
+pub(crate) fn rows_to_string<T: Serialize>(rows: &[T], pretty: bool) -> String {
+    let result = if pretty {
+        serde_json::to_string_pretty(rows)
+    } else {
+        serde_json::to_string(rows)
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("Failed to serialize JSON output: {e}");
+        "[]".to_string()
+    })
+}
+
+/// Serialize a report object, falling back to an empty object.
+pub(crate) fn report_to_string<T: Serialize>(report: &T, pretty: bool) -> String {
+    let result = if pretty {
+        serde_json::to_string_pretty(report)
+    } else {
+        serde_json::to_string(report)
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("Failed to serialize JSON output: {e}");
+        "{}".to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(report: SchemaReport) -> jsonschema::Validator {
+        jsonschema::validator_for(&report.json_schema()).expect("valid JSON Schema")
+    }
+
+    /// The schemas under `schemas/` are what `ccstats schema <report>`
+    /// prints. Regenerate them with
+    /// `CCSTATS_UPDATE_SCHEMAS=1 cargo test checked_in_schemas`.
+    #[test]
+    fn checked_in_schemas_match_the_code() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas");
+        let update = std::env::var_os("CCSTATS_UPDATE_SCHEMAS").is_some();
+        let mut expected_files = Vec::new();
+        for report in SchemaReport::value_variants() {
+            let file = format!("{}.json", report.name());
+            let path = dir.join(&file);
+            let schema = report_to_string(&report.json_schema(), true) + "\n";
+            if update {
+                std::fs::create_dir_all(&dir).unwrap();
+                std::fs::write(&path, &schema).unwrap();
+            }
+            let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+            assert!(
+                checked_in == schema,
+                "schemas/{file} is out of date; run CCSTATS_UPDATE_SCHEMAS=1 cargo test checked_in_schemas"
+            );
+            expected_files.push(file);
+        }
+
+        let mut files: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        expected_files.sort();
+        assert_eq!(
+            files, expected_files,
+            "schemas/ has files for removed reports"
+        );
+    }
+
+    #[test]
+    fn every_report_has_versioned_id() {
+        for report in SchemaReport::value_variants() {
+            let schema = report.json_schema();
+            assert_eq!(
+                schema["$id"],
+                format!(
+                    "https://github.com/majiayu000/ccstats/schema/v1/{}.json",
+                    report.name()
+                )
+            );
+            let rows = matches!(
+                report,
+                SchemaReport::Daily
+                    | SchemaReport::Weekly
+                    | SchemaReport::Monthly
+                    | SchemaReport::Session
+                    | SchemaReport::SessionContext
+                    | SchemaReport::Project
+                    | SchemaReport::Blocks
+                    | SchemaReport::Endpoints
+                    | SchemaReport::Agents
+                    | SchemaReport::PricingList
+                    | SchemaReport::PricingRefresh
+                    | SchemaReport::PricingDiff
+                    | SchemaReport::Sources
+            );
+            assert_eq!(schema["type"], if rows { "array" } else { "object" });
+        }
+    }
+
+    #[test]
+    fn cost_amount_is_null_when_unpriced() {
+        assert_eq!(cost_amount(f64::NAN, None), None);
+        assert_eq!(cost_amount(1.5, None), Some(1.5));
+    }
+
+    #[test]
+    fn data_quality_only_row_validates_alone() {
+        let data_quality = DataQualityJson {
+            valid_entries: 0,
+            dedup_skipped_entries: 0,
+            parse_errors: 2,
+        };
+        let row = PeriodJsonRow::DataQualityOnly {
+            schema_version: SCHEMA_VERSION,
+            data_quality,
+        };
+        let validator = validator(SchemaReport::Daily);
+        assert!(validator.is_valid(&serde_json::to_value([row]).unwrap()));
+
+        let mismatched = serde_json::json!([{
+            "schema_version": SCHEMA_VERSION,
+            "data_quality": data_quality,
+            "input_tokens": "10",
+        }]);
+        assert!(!validator.is_valid(&mismatched));
+    }
+
+    #[test]
+    fn period_row_requires_period_key() {
+        let row = PeriodJson {
+            schema_version: SCHEMA_VERSION,
+            period: PeriodKeyJson::Week("2026-02-02".to_string()),
+            tokens: TokensJson::new(&Stats::default(), true),
+            models: Some(Vec::new()),
+            breakdown: None,
+            cost: None,
+            exchange_rate: None,
+            budget: None,
+            data_quality: None,
+        };
+        let mut value = serde_json::to_value([PeriodJsonRow::Period(Box::new(row))]).unwrap();
+        assert_eq!(value[0]["week"], "2026-02-02");
+        let validator = validator(SchemaReport::Weekly);
+        assert!(validator.is_valid(&value));
+
+        value[0].as_object_mut().unwrap().remove("week");
+        assert!(!validator.is_valid(&value));
+    }
+}
@@ -6,13 +6,19 @@ use crate::cli::SortOrder;
 use crate::consts::DATE_FORMAT;
 use crate::core::{SessionStats, Stats, format_project_name};
 use crate::output::format::{
-    NumberFormat, cache_hit_rate_json_value, cost_json_value, create_styled_table,
-    format_cache_hit_rate, format_compact, format_cost, format_number, header_cell, right_cell,
-    styled_cell,
+    Locale, create_styled_table, format_cache_hit_rate, format_compact, format_cost, format_number,
+    header_cell, right_cell, styled_cell,
 };
+use crate::output::locale::Msg;
+use crate::output::markup::Markup;
+use crate::output::period::span_currency;
 use crate::output::pricing_meta;
+use crate::output::schema::{
+    CostJson, ExchangeRateJson, SCHEMA_VERSION, SessionJson, TokensJson, model_names,
+    rows_to_string,
+};
 use crate::pricing::{
-    CurrencyConverter, PricingDb, model_cost_kind, sum_estimated_proxy_model_costs, sum_model_costs,
+    CostDisplayMode, CurrencyConverter, PricingDb, sum_estimated_proxy_model_costs, sum_model_costs,
 };
 use crate::utils::Timezone;
 
@@ -65,10 +71,11 @@ pub(crate) struct SessionTableOptions<'a> {
     pub(crate) compact: bool,
     pub(crate) show_cost: bool,
     pub(crate) supports_cache_read: bool,
-    pub(crate) number_format: NumberFormat,
+    pub(crate) locale: Locale,
     pub(crate) source_label: &'a str,
     pub(crate) timezone: Timezone,
     pub(crate) currency: Option<&'a CurrencyConverter>,
+    pub(crate) markup: Markup,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -81,7 +88,7 @@ pub(crate) fn print_session_table(
     let use_color = options.use_color;
     let compact = options.compact;
     let show_cost = options.show_cost;
-    let number_format = options.number_format;
+    let locale = options.locale;
     let source_label = options.source_label;
     let timezone = options.timezone;
 
@@ -93,32 +100,33 @@ pub(crate) fn print_session_table(
         SortOrder::Desc => sorted_sessions.sort_by(|a, b| compare_session_last_timestamp(b, a)),
     }
 
+    let h_cell = |msg| header_cell(locale.text(msg), use_color);
     let mut table = create_styled_table();
 
     if compact {
         let mut header = vec![
-            header_cell("Session", use_color),
-            header_cell("Project", use_color),
-            header_cell("Date", use_color),
+            h_cell(Msg::Session),
+            h_cell(Msg::Project),
+            h_cell(Msg::Date),
         ];
-        header.push(header_cell("Cache Hit", use_color));
-        header.push(header_cell("Total", use_color));
+        header.push(h_cell(Msg::CacheHit));
+        header.push(h_cell(Msg::Total));
         if show_cost {
-            header.push(header_cell("Cost", use_color));
+            header.push(h_cell(Msg::Cost));
         }
         table.set_header(header);
     } else {
         let mut header = vec![
-            header_cell("Session", use_color),
-            header_cell("Project", use_color),
-            header_cell("Date", use_color),
-            header_cell("Input", use_color),
-            header_cell("Output", use_color),
+            h_cell(Msg::Session),
+            h_cell(Msg::Project),
+            h_cell(Msg::Date),
+            h_cell(Msg::Input),
+            h_cell(Msg::Output),
         ];
-        header.push(header_cell("Cache Hit", use_color));
-        header.push(header_cell("Total", use_color));
+        header.push(h_cell(Msg::CacheHit));
+        header.push(h_cell(Msg::Total));
         if show_cost {
-            header.push(header_cell("Cost", use_color));
+            header.push(h_cell(Msg::Cost));
         }
         table.set_header(header);
     }
@@ -130,7 +138,20 @@ pub(crate) fn print_session_table(
     let mut total_estimated_cost = 0.0;
     let mut has_estimated_cost = false;
 
+    let total_currency = span_currency(
+        options.currency,
+        sorted_sessions.iter().flat_map(|session| &session.days),
+        pricing_db,
+        CostDisplayMode::Total,
+    );
+
     for session in &sorted_sessions {
+        let row_currency = span_currency(
+            options.currency,
+            &session.days,
+            pricing_db,
+            CostDisplayMode::Total,
+        );
         let session_cost = if show_cost {
             let cost = sum_model_costs(&session.models, pricing_db);
             total_cost += cost;
@@ -161,13 +182,17 @@ pub(crate) fn print_session_table(
                 false,
             ));
             row.push(right_cell(
-                &format_compact(session.stats.total_tokens(), number_format),
+                &format_compact(session.stats.total_tokens(), locale),
                 None,
                 false,
             ));
             if show_cost {
                 row.push(right_cell(
-                    &format_cost(session_cost.unwrap_or(0.0), options.currency),
+                    &format_cost(
+                        session_cost.unwrap_or(0.0),
+                        row_currency.as_deref(),
+                        options.locale,
+                    ),
                     cost_color,
                     false,
                 ));
@@ -179,12 +204,12 @@ pub(crate) fn print_session_table(
                 Cell::new(&project),
                 Cell::new(&date),
                 right_cell(
-                    &format_number(session.stats.input_tokens, number_format),
+                    &format_number(session.stats.input_tokens, locale),
                     None,
                     false,
                 ),
                 right_cell(
-                    &format_number(session.stats.output_tokens, number_format),
+                    &format_number(session.stats.output_tokens, locale),
                     None,
                     false,
                 ),
@@ -195,13 +220,17 @@ pub(crate) fn print_session_table(
                 false,
             ));
             row.push(right_cell(
-                &format_number(session.stats.total_tokens(), number_format),
+                &format_number(session.stats.total_tokens(), locale),
                 None,
                 false,
             ));
             if show_cost {
                 row.push(right_cell(
-                    &format_cost(session_cost.unwrap_or(0.0), options.currency),
+                    &format_cost(
+                        session_cost.unwrap_or(0.0),
+                        row_currency.as_deref(),
+                        options.locale,
+                    ),
                     cost_color,
                     false,
                 ));
@@ -216,7 +245,7 @@ pub(crate) fn print_session_table(
     // Add total row
     if compact {
         let mut row = vec![
-            styled_cell("TOTAL", cyan, true),
+            styled_cell(locale.text(Msg::TotalRow), cyan, true),
             Cell::new(""),
             Cell::new(""),
         ];
@@ -226,13 +255,13 @@ pub(crate) fn print_session_table(
             true,
         ));
         row.push(right_cell(
-            &format_compact(total_stats.total_tokens(), number_format),
+            &format_compact(total_stats.total_tokens(), locale),
             cyan,
             true,
         ));
         if show_cost {
             row.push(right_cell(
-                &format_cost(total_cost, options.currency),
+                &format_cost(total_cost, total_currency.as_deref(), options.locale),
                 green,
                 true,
             ));
@@ -240,16 +269,12 @@ pub(crate) fn print_session_table(
         table.add_row(row);
     } else {
         let mut row = vec![
-            styled_cell("TOTAL", cyan, true),
+            styled_cell(locale.text(Msg::TotalRow), cyan, true),
             Cell::new(""),
             Cell::new(""),
+            right_cell(&format_number(total_stats.input_tokens, locale), cyan, true),
             right_cell(
-                &format_number(total_stats.input_tokens, number_format),
-                cyan,
-                true,
-            ),
-            right_cell(
-                &format_number(total_stats.output_tokens, number_format),
+                &format_number(total_stats.output_tokens, locale),
                 cyan,
                 true,
             ),
@@ -260,13 +285,13 @@ pub(crate) fn print_session_table(
             true,
         ));
         row.push(right_cell(
-            &format_number(total_stats.total_tokens(), number_format),
+            &format_number(total_stats.total_tokens(), locale),
             cyan,
             true,
         ));
         if show_cost {
             row.push(right_cell(
-                &format_cost(total_cost, options.currency),
+                &format_cost(total_cost, total_currency.as_deref(), options.locale),
                 green,
                 true,
             ));
@@ -274,13 +299,21 @@ pub(crate) fn print_session_table(
         table.add_row(row);
     }
 
-    println!("\n  {source_label} Session Usage\n");
-    println!("{table}");
+    options.markup.title(&format!(
+        "{source_label} {}",
+        locale.text(Msg::SessionUsage)
+    ));
+    options.markup.table(&table, 1, locale);
     if show_cost && has_estimated_cost {
-        println!(
-            "\n  Cost includes estimated proxy values: {}",
-            format_cost(total_estimated_cost, options.currency)
-        );
+        options.markup.line(&format!(
+            "\n  {}: {}",
+            locale.text(Msg::EstimatedProxyIncluded),
+            format_cost(
+                total_estimated_cost,
+                total_currency.as_deref(),
+                options.locale
+            )
+        ));
     }
     if show_cost
         && let Some(note) = pricing_meta::note_for_maps(
@@ -288,22 +321,22 @@ pub(crate) fn print_session_table(
             pricing_db,
         )
     {
-        println!("\n  {note}");
+        options.markup.line(&format!("\n  {note}"));
     }
-    println!(
-        "\n  {} sessions\n",
-        format_number(sorted_sessions.len() as i64, number_format)
-    );
+    options.markup.line(&format!(
+        "\n  {}\n",
+        locale.session_count(&format_number(sorted_sessions.len() as i64, locale))
+    ));
 }
 
-pub(crate) fn output_session_json(
-    sessions: &[SessionStats],
-    pricing_db: &PricingDb,
+pub(crate) fn session_json_rows<'a>(
+    sessions: &'a [SessionStats],
+    pricing_db: &'a PricingDb,
     order: SortOrder,
     show_cost: bool,
     supports_cache_read: bool,
-    currency: Option<&CurrencyConverter>,
-) -> String {
+    currency: Option<&'a CurrencyConverter>,
+) -> impl Iterator<Item = SessionJson> + 'a {
     let mut sorted_sessions: Vec<_> = sessions.iter().collect();
 
     match order {
@@ -311,53 +344,52 @@ pub(crate) fn output_session_json(
         SortOrder::Desc => sorted_sessions.sort_by(|a, b| compare_session_last_timestamp(b, a)),
     }
 
-    let output: Vec<serde_json::Value> = sorted_sessions
-        .iter()
-        .map(|session| {
-            let session_cost = if show_cost {
-                Some(sum_model_costs(&session.models, pricing_db))
-            } else {
-                None
-            };
-
-            let mut models: Vec<_> = session.models.keys().cloned().collect();
-            models.sort();
-            let mut obj = serde_json::json!({
-                "session_id": session.session_id,
-                "project": format_project_name(&session.project_path),
-                "project_path": session.project_path,
-                "first_timestamp": session.first_timestamp,
-                "last_timestamp": session.last_timestamp,
-                "input_tokens": session.stats.input_tokens,
-                "output_tokens": session.stats.output_tokens,
-                "reasoning_tokens": session.stats.reasoning_tokens,
-                "cache_creation_tokens": session.stats.cache_creation,
-                "cache_read_tokens": session.stats.cache_read,
-                "cache_hit_rate": cache_hit_rate_json_value(
-                    session.stats.cache_hit_rate(supports_cache_read)
-                ),
-                "total_tokens": session.stats.total_tokens(),
-                "models": models,
-            });
-            if show_cost {
-                obj["cost"] = cost_json_value(session_cost.unwrap_or(0.0), currency);
-                pricing_meta::add_json(&mut obj, &session.models, pricing_db);
-                let estimated_cost = sum_estimated_proxy_model_costs(&session.models, pricing_db);
-                if estimated_cost > 0.0 {
-                    obj["cost_kind"] = serde_json::json!(model_cost_kind(&session.models).as_str());
-                    obj["estimated_cost"] = cost_json_value(estimated_cost, currency);
-                }
-            }
-            obj
-        })
-        .collect();
-
-    serde_json::to_string(&output).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize JSON output: {e}");
-        "[]".to_string()
+    sorted_sessions.into_iter().map(move |session| {
+        let currency = show_cost
+            .then(|| span_currency(currency, &session.days, pricing_db, CostDisplayMode::Total))
+            .flatten();
+        SessionJson {
+            schema_version: SCHEMA_VERSION,
+            session_id: session.session_id.clone(),
+            project: format_project_name(&session.project_path),
+            project_path: session.project_path.clone(),
+            first_timestamp: session.first_timestamp.clone(),
+            last_timestamp: session.last_timestamp.clone(),
+            tokens: TokensJson::new(&session.stats, supports_cache_read),
+            models: model_names(&session.models),
+            cost: show_cost.then(|| {
+                CostJson::for_models(
+                    &session.models,
+                    sum_model_costs(&session.models, pricing_db),
+                    pricing_db,
+                    currency.as_deref(),
+                )
+            }),
+            exchange_rate: currency.as_deref().map(ExchangeRateJson::new),
+        }
     })
 }
 
+pub(crate) fn output_session_json(
+    sessions: &[SessionStats],
+    pricing_db: &PricingDb,
+    order: SortOrder,
+    show_cost: bool,
+    supports_cache_read: bool,
+    currency: Option<&CurrencyConverter>,
+) -> String {
+    let rows: Vec<_> = session_json_rows(
+        sessions,
+        pricing_db,
+        order,
+        show_cost,
+        supports_cache_read,
+        currency,
+    )
+    .collect();
+    rows_to_string(&rows, false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -510,6 +542,7 @@ mod tests {
                 ..Default::default()
             },
             models: HashMap::new(),
+            ..Default::default()
         }
     }
 
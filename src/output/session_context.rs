@@ -0,0 +1,189 @@
+//! Output formatters for per-session context-window metrics
+
+use std::fmt::Write;
+
+use comfy_table::{Cell, Color};
+
+use crate::core::{ContextStats, SessionStats, context_stats, format_project_name};
+
+use super::command_json::SessionContextJson;
+use super::format::{
+    Locale, create_styled_table, csv_escape, format_compact, format_number, header_cell, right_cell,
+};
+use super::locale::Msg;
+use super::markup::Markup;
+use super::schema::SCHEMA_VERSION;
+
+/// Sessions with main-thread requests and their context metrics, most time
+/// spent at the ceiling first, then by peak.
+fn context_rows(sessions: &[SessionStats], threshold: i64) -> Vec<(&SessionStats, ContextStats)> {
+    let mut rows: Vec<_> = sessions
+        .iter()
+        .map(|session| (session, context_stats(&session.context_samples, threshold)))
+        .filter(|(_, stats)| stats.turns > 0)
+        .collect();
+    rows.sort_by(|(a_session, a), (b_session, b)| {
+        b.turns_above_threshold
+            .cmp(&a.turns_above_threshold)
+            .then_with(|| b.peak_tokens.cmp(&a.peak_tokens))
+            .then_with(|| a_session.session_id.cmp(&b_session.session_id))
+    });
+    rows
+}
+
+/// Options for rendering the session context table
+#[derive(Clone, Copy)]
+pub(crate) struct SessionContextTableOptions<'a> {
+    pub(crate) threshold: i64,
+    pub(crate) source_label: &'a str,
+    pub(crate) locale: Locale,
+    pub(crate) use_color: bool,
+    pub(crate) markup: Markup,
+}
+
+/// Print peak and average context, compactions and ceiling turns per session
+pub(crate) fn print_session_context_table(
+    sessions: &[SessionStats],
+    options: SessionContextTableOptions<'_>,
+) {
+    let SessionContextTableOptions {
+        threshold,
+        source_label,
+        locale,
+        use_color,
+        markup,
+    } = options;
+    let rows = context_rows(sessions, threshold);
+    if rows.is_empty() {
+        markup.line(locale.text(Msg::NoSessionContext));
+        return;
+    }
+
+    let h_cell = |msg| header_cell(locale.text(msg), use_color);
+    let above = locale.turns_at_or_above(&format_compact(threshold, locale));
+    let mut table = create_styled_table();
+    table.set_header(vec![
+        h_cell(Msg::Session),
+        h_cell(Msg::Project),
+        h_cell(Msg::Turns),
+        h_cell(Msg::Peak),
+        h_cell(Msg::Average),
+        h_cell(Msg::Compactions),
+        header_cell(&above, use_color),
+        h_cell(Msg::PctOfTurns),
+    ]);
+    let hot = use_color.then_some(Color::Red);
+    for (session, stats) in &rows {
+        let ceiling_color = (stats.turns_above_threshold > 0).then_some(hot).flatten();
+        table.add_row(vec![
+            Cell::new(&session.session_id),
+            Cell::new(format_project_name(&session.project_path)),
+            right_cell(&format_number(stats.turns as i64, locale), None, false),
+            right_cell(&format_compact(stats.peak_tokens, locale), None, false),
+            right_cell(&format_compact(stats.average_tokens, locale), None, false),
+            right_cell(&stats.compactions.to_string(), None, false),
+            right_cell(
+                &format_number(stats.turns_above_threshold as i64, locale),
+                ceiling_color,
+                false,
+            ),
+            right_cell(&format!("{:.1}%", stats.above_threshold_pct()), None, false),
+        ]);
+    }
+    markup.title(&format!(
+        "{source_label} {}",
+        locale.text(Msg::SessionContext)
+    ));
+    markup.table(&table, 0, locale);
+}
+
+/// Per-session context metrics as typed JSON rows
+pub(crate) fn session_context_json_rows(
+    sessions: &[SessionStats],
+    threshold: i64,
+) -> Vec<SessionContextJson> {
+    context_rows(sessions, threshold)
+        .into_iter()
+        .map(|(session, stats)| SessionContextJson {
+            schema_version: SCHEMA_VERSION,
+            session_id: session.session_id.clone(),
+            project: format_project_name(&session.project_path),
+            project_path: session.project_path.clone(),
+            first_timestamp: session.first_timestamp.clone(),
+            last_timestamp: session.last_timestamp.clone(),
+            context: stats,
+            above_threshold_pct: (stats.above_threshold_pct() * 10.0).round() / 10.0,
+        })
+        .collect()
+}
+
+/// Output per-session context metrics as CSV
+pub(crate) fn output_session_context_csv(sessions: &[SessionStats], threshold: i64) -> String {
+    let mut out = String::from(
+        "session_id,project,last_timestamp,turns,peak_context_tokens,average_context_tokens,compactions,turns_above_threshold,threshold\n",
+    );
+    for (session, stats) in context_rows(sessions, threshold) {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{}",
+            csv_escape(&session.session_id),
+            csv_escape(&format_project_name(&session.project_path)),
+            session.last_timestamp,
+            stats.turns,
+            stats.peak_tokens,
+            stats.average_tokens,
+            stats.compactions,
+            stats.turns_above_threshold,
+            stats.threshold,
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ContextSample;
+
+    fn session(id: &str, tokens: &[i64]) -> SessionStats {
+        SessionStats {
+            session_id: id.to_string(),
+            project_path: "-work-app".to_string(),
+            context_samples: tokens
+                .iter()
+                .enumerate()
+                .map(|(i, &tokens)| ContextSample {
+                    timestamp_ms: i as i64,
+                    tokens,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn json_ranks_sessions_by_turns_at_the_ceiling() {
+        let sessions = [
+            session("calm", &[10_000, 20_000]),
+            session("hot", &[100_000, 160_000, 180_000, 20_000]),
+            session("empty", &[]),
+        ];
+        let val = serde_json::to_value(session_context_json_rows(&sessions, 150_000)).unwrap();
+        let rows = val.as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["session_id"], "hot");
+        assert_eq!(rows[0]["context"]["peak_tokens"], 180_000);
+        assert_eq!(rows[0]["context"]["compactions"], 1);
+        assert_eq!(rows[0]["context"]["turns_above_threshold"], 2);
+        assert_eq!(rows[0]["above_threshold_pct"], 50.0);
+        assert_eq!(rows[1]["context"]["average_tokens"], 15_000);
+    }
+
+    #[test]
+    fn csv_has_one_row_per_session() {
+        let csv = output_session_context_csv(&[session("s", &[200_000])], 150_000);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1], "s,work-app,,1,200000,200000,0,1,150000");
+    }
+}
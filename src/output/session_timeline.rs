@@ -0,0 +1,475 @@
+//! Output formatters for the `session show` timeline
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use chrono::{DateTime, NaiveDate};
+use comfy_table::{Cell, Color};
+
+use crate::consts::DATE_FORMAT;
+use crate::core::{SessionTimeline, TimelineTurn, format_project_name};
+use crate::pricing::{CurrencyConverter, PricingDb, calculate_cost};
+use crate::utils::Timezone;
+
+use super::command_json::{
+    IdleGapJson, SessionEntryJson, SessionTimelineJson, TimelineSegmentJson, TimelineTurnJson,
+};
+use super::csv::csv_cost;
+use super::format::{
+    Locale, create_styled_table, csv_escape, format_compact, format_cost, format_number,
+    header_cell, right_cell, styled_cell,
+};
+use super::locale::Msg;
+use super::markup::Markup;
+use super::period::blended_currency;
+use super::schema::{SCHEMA_VERSION, cost_amount};
+use super::statusline::format_duration_minutes;
+
+/// Options for the `session show` table.
+pub(crate) struct SessionTimelineOptions<'a> {
+    pub(crate) use_color: bool,
+    pub(crate) show_cost: bool,
+    pub(crate) locale: Locale,
+    pub(crate) timezone: Timezone,
+    pub(crate) currency: Option<&'a CurrencyConverter>,
+    pub(crate) markup: Markup,
+}
+
+/// Cost of a turn and the running total after it, each with the converter
+/// that shows it.
+struct TurnCost<'a> {
+    cost: f64,
+    cumulative: f64,
+    currency: Option<Cow<'a, CurrencyConverter>>,
+    cumulative_currency: Option<Cow<'a, CurrencyConverter>>,
+}
+
+/// Cost of each turn and the running total after it. With daily rates a turn
+/// is converted at its day's rate and the running total at the rates of the
+/// days so far, weighted by their cost.
+fn turn_costs<'a>(
+    timeline: &SessionTimeline,
+    pricing_db: &PricingDb,
+    currency: Option<&'a CurrencyConverter>,
+) -> Vec<TurnCost<'a>> {
+    let mut cumulative = 0.0;
+    let mut days: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+    timeline
+        .turns
+        .iter()
+        .map(|turn| {
+            let cost = calculate_cost(&turn.stats, &turn.model, pricing_db);
+            cumulative += cost;
+            let day = NaiveDate::parse_from_str(&turn.date, DATE_FORMAT).ok();
+            if let Some(day) = day {
+                *days.entry(day).or_default() += cost;
+            }
+            let days_so_far: Vec<_> = days.iter().map(|(&day, &usd)| (day, usd)).collect();
+            TurnCost {
+                cost,
+                cumulative,
+                currency: blended_currency(currency, day.map(|day| (day, cost)).as_slice()),
+                cumulative_currency: blended_currency(currency, &days_so_far),
+            }
+        })
+        .collect()
+}
+
+fn local_time(timestamp_ms: i64, timezone: Timezone, format: &str) -> String {
+    DateTime::from_timestamp_millis(timestamp_ms).map_or_else(String::new, |utc_dt| {
+        timezone.to_fixed_offset(utc_dt).format(format).to_string()
+    })
+}
+
+fn gap_label(duration_ms: i64, locale: Locale) -> String {
+    locale.idle_gap(&format_duration_minutes(duration_ms / 60_000))
+}
+
+fn thread_label(sidechain: bool) -> &'static str {
+    if sidechain { "subagent" } else { "main" }
+}
+
+/// Table row of one request, its time shown with `time_format`.
+fn turn_row(
+    turn: &TimelineTurn,
+    cost: &TurnCost<'_>,
+    time_format: &str,
+    options: &SessionTimelineOptions<'_>,
+) -> Vec<Cell> {
+    let fmt = options.locale;
+    let cost_color = options.use_color.then_some(Color::Green);
+    let sub_color = options.use_color.then_some(Color::Magenta);
+    let mut row = vec![
+        Cell::new(local_time(turn.timestamp_ms, options.timezone, time_format)),
+        styled_cell(
+            thread_label(turn.sidechain),
+            turn.sidechain.then_some(sub_color).flatten(),
+            false,
+        ),
+        Cell::new(&turn.model),
+        right_cell(&format_number(turn.stats.input_tokens, fmt), None, false),
+        right_cell(&format_number(turn.stats.cache_creation, fmt), None, false),
+        right_cell(&format_number(turn.stats.cache_read, fmt), None, false),
+        right_cell(&format_number(turn.stats.output_tokens, fmt), None, false),
+        right_cell(&format_compact(turn.context_tokens(), fmt), None, false),
+    ];
+    if options.show_cost {
+        row.push(right_cell(
+            &format_cost(cost.cost, cost.currency.as_deref(), options.locale),
+            cost_color,
+            false,
+        ));
+        row.push(right_cell(
+            &format_cost(
+                cost.cumulative,
+                cost.cumulative_currency.as_deref(),
+                options.locale,
+            ),
+            None,
+            false,
+        ));
+    }
+    row.push(Cell::new(turn.stop_reason.as_deref().unwrap_or("")));
+    row.push(Cell::new(turn.tools.join(", ")));
+    row
+}
+
+/// Print the timeline: one row per request, idle gaps as separator rows, then
+/// a summary of segments, gaps and peak context.
+pub(crate) fn print_session_timeline(
+    timeline: &SessionTimeline,
+    pricing_db: &PricingDb,
+    options: &SessionTimelineOptions<'_>,
+) {
+    let (Some(first), Some(last)) = (timeline.turns.first(), timeline.turns.last()) else {
+        return;
+    };
+    let fmt = options.locale;
+    let costs = turn_costs(timeline, pricing_db, options.currency);
+    let markup = options.markup;
+    let end_format = if first.date == last.date {
+        "%H:%M"
+    } else {
+        "%Y-%m-%d %H:%M"
+    };
+    markup.line(&fmt.timeline_heading(
+        &timeline.session_id,
+        &format_project_name(&timeline.project_path),
+        (
+            &local_time(first.timestamp_ms, options.timezone, "%Y-%m-%d %H:%M"),
+            &local_time(last.timestamp_ms, options.timezone, end_format),
+        ),
+        &format_number(timeline.turns.len() as i64, fmt),
+    ));
+
+    let mut headers = vec![
+        Msg::Time,
+        Msg::Thread,
+        Msg::Model,
+        Msg::Input,
+        Msg::CacheW,
+        Msg::CacheR,
+        Msg::Output,
+        Msg::Context,
+    ];
+    if options.show_cost {
+        headers.extend([Msg::Cost, Msg::Cumulative]);
+    }
+    headers.extend([Msg::Stop, Msg::Tools]);
+    let mut table = create_styled_table();
+    table.set_header(
+        headers
+            .into_iter()
+            .map(|msg| header_cell(fmt.text(msg), options.use_color))
+            .collect::<Vec<_>>(),
+    );
+
+    let dim = options.use_color.then_some(Color::DarkGrey);
+    for (index, (turn, cost)) in timeline.turns.iter().zip(&costs).enumerate() {
+        if let Some(gap) = timeline.gap_before(index) {
+            table.add_row(vec![styled_cell(
+                &gap_label(gap.duration_ms, fmt),
+                dim,
+                false,
+            )]);
+        }
+        // The heading dates the first row; later rows are dated when the
+        // day changes.
+        let new_day = index > 0 && timeline.turns[index - 1].date != turn.date;
+        let time_format = if new_day {
+            "%Y-%m-%d %H:%M:%S"
+        } else {
+            "%H:%M:%S"
+        };
+        table.add_row(turn_row(turn, cost, time_format, options));
+    }
+    markup.table(&table, 0, options.locale);
+
+    let peak = timeline.peak_context_tokens();
+    let subagent_segments = timeline.segments.iter().filter(|s| s.sidechain).count();
+    let idle_minutes: i64 = timeline
+        .idle_gaps
+        .iter()
+        .map(|gap| gap.duration_ms / 60_000)
+        .sum();
+    let total_cost = costs.last().filter(|_| options.show_cost).map(|total| {
+        format_cost(
+            total.cumulative,
+            total.cumulative_currency.as_deref(),
+            options.locale,
+        )
+    });
+    let summary = fmt.timeline_summary(
+        &format_number(peak, fmt),
+        subagent_segments,
+        (
+            timeline.idle_gaps.len(),
+            &format_duration_minutes(idle_minutes),
+        ),
+        total_cost.as_deref(),
+    );
+    markup.line(&summary);
+}
+
+/// One request of the timeline as JSON, with its cost and the running total.
+fn turn_json(turn: &TimelineTurn, cost: &TurnCost<'_>, show_cost: bool) -> TimelineTurnJson {
+    TimelineTurnJson {
+        timestamp: turn.timestamp.clone(),
+        model: turn.model.clone(),
+        thread: thread_label(turn.sidechain),
+        input_tokens: turn.stats.input_tokens,
+        cache_creation_tokens: turn.stats.cache_creation,
+        cache_read_tokens: turn.stats.cache_read,
+        output_tokens: turn.stats.output_tokens,
+        context_tokens: turn.context_tokens(),
+        stop_reason: turn.stop_reason.clone(),
+        tools: turn.tools.clone(),
+        cost: show_cost.then(|| cost_amount(cost.cost, cost.currency.as_deref())),
+        cumulative_cost: show_cost
+            .then(|| cost_amount(cost.cumulative, cost.cumulative_currency.as_deref())),
+    }
+}
+
+/// Entry-level rows for `session show --ndjson`: one object per request,
+/// shaped like the `turns` of the JSON timeline plus the session id, yielded
+/// as they are serialized.
+pub(crate) fn session_entry_json_rows<'a>(
+    timeline: &'a SessionTimeline,
+    pricing_db: &PricingDb,
+    show_cost: bool,
+    currency: Option<&'a CurrencyConverter>,
+) -> impl Iterator<Item = SessionEntryJson> + 'a {
+    let costs = turn_costs(timeline, pricing_db, currency);
+    timeline
+        .turns
+        .iter()
+        .zip(costs)
+        .map(move |(turn, cost)| SessionEntryJson {
+            schema_version: SCHEMA_VERSION,
+            session_id: timeline.session_id.clone(),
+            turn: turn_json(turn, &cost, show_cost),
+        })
+}
+
+/// Build the timeline JSON: per-turn rows with cumulative cost and context
+/// size, plus thread segments and idle gaps.
+pub(crate) fn session_timeline_json_report(
+    timeline: &SessionTimeline,
+    pricing_db: &PricingDb,
+    show_cost: bool,
+    currency: Option<&CurrencyConverter>,
+) -> SessionTimelineJson {
+    let costs = turn_costs(timeline, pricing_db, currency);
+    let turns = timeline
+        .turns
+        .iter()
+        .zip(&costs)
+        .map(|(turn, cost)| turn_json(turn, cost, show_cost))
+        .collect();
+    let segments = timeline
+        .segments
+        .iter()
+        .map(|segment| TimelineSegmentJson {
+            thread: thread_label(segment.sidechain),
+            start: timeline.turns[segment.start].timestamp.clone(),
+            end: timeline.turns[segment.end].timestamp.clone(),
+            requests: segment.end - segment.start + 1,
+        })
+        .collect();
+    let idle_gaps = timeline
+        .idle_gaps
+        .iter()
+        .map(|gap| IdleGapJson {
+            start: timeline.turns[gap.before - 1].timestamp.clone(),
+            end: timeline.turns[gap.before].timestamp.clone(),
+            minutes: gap.duration_ms / 60_000,
+        })
+        .collect();
+    let cost = show_cost.then(|| {
+        costs.last().map_or(Some(0.0), |total| {
+            cost_amount(total.cumulative, total.cumulative_currency.as_deref())
+        })
+    });
+    SessionTimelineJson {
+        schema_version: SCHEMA_VERSION,
+        session_id: timeline.session_id.clone(),
+        project: format_project_name(&timeline.project_path),
+        project_path: timeline.project_path.clone(),
+        requests: timeline.turns.len(),
+        peak_context_tokens: timeline.peak_context_tokens(),
+        turns,
+        segments,
+        idle_gaps,
+        cost,
+    }
+}
+
+/// Output the timeline as CSV, one row per request
+pub(crate) fn output_session_timeline_csv(
+    timeline: &SessionTimeline,
+    pricing_db: &PricingDb,
+    show_cost: bool,
+    currency: Option<&CurrencyConverter>,
+) -> String {
+    let costs = turn_costs(timeline, pricing_db, currency);
+    let mut out = String::from(
+        "timestamp,thread,model,input_tokens,cache_creation_tokens,cache_read_tokens,output_tokens,context_tokens",
+    );
+    if show_cost {
+        out.push_str(",cost,cumulative_cost");
+    }
+    out.push_str(",stop_reason,tools\n");
+    for (turn, cost) in timeline.turns.iter().zip(&costs) {
+        let _ = write!(
+            out,
+            "{},{},{},{},{},{},{},{}",
+            turn.timestamp,
+            thread_label(turn.sidechain),
+            csv_escape(&turn.model),
+            turn.stats.input_tokens,
+            turn.stats.cache_creation,
+            turn.stats.cache_read,
+            turn.stats.output_tokens,
+            turn.context_tokens(),
+        );
+        if show_cost {
+            let _ = write!(
+                out,
+                ",{},{}",
+                csv_cost(cost.cost, cost.currency.as_deref()),
+                csv_cost(cost.cumulative, cost.cumulative_currency.as_deref())
+            );
+        }
+        let _ = writeln!(
+            out,
+            ",{},{}",
+            csv_escape(turn.stop_reason.as_deref().unwrap_or("")),
+            csv_escape(&turn.tools.join(";")),
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CostKind, Endpoint, RawEntry, ServiceTier, build_session_timeline};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn entry(id: &str, minute: i64, input: i64, sidechain: bool) -> RawEntry {
+        RawEntry {
+            timestamp: format!("2026-02-06T12:{minute:02}:00Z"),
+            timestamp_ms: 1_770_379_200_000 + minute * 60_000,
+            date_str: "2026-02-06".to_string(),
+            message_id: Some(id.to_string()),
+            session_key: "s".to_string(),
+            session_id: "abc".to_string(),
+            project_path: "-work-app".to_string(),
+            model: "sonnet-4".to_string(),
+            input_tokens: input,
+            output_tokens: 1_000_000,
+            cache_creation: 0,
+            cache_creation_1h: 0,
+            cache_read: 0,
+            reasoning_tokens: 0,
+            stop_reason: Some("end_turn".to_string()),
+            cost_kind: CostKind::Real,
+            endpoint: Endpoint::Unknown,
+            call_count: 1,
+            recorded_cost_usd: None,
+            is_sidechain: sidechain,
+            service_tier: ServiceTier::Standard,
+            subagent_type: None,
+            parent_session_id: None,
+        }
+    }
+
+    fn sample_timeline() -> SessionTimeline {
+        let tools = HashMap::from([(
+            "m1".to_string(),
+            vec!["Read".to_string(), "Bash".to_string()],
+        )]);
+        build_session_timeline(
+            vec![
+                entry("m1", 0, 1_000_000, false),
+                entry("m2", 1, 2_000_000, true),
+                entry("m3", 30, 500, false),
+            ],
+            &tools,
+        )
+    }
+
+    #[test]
+    fn json_has_cumulative_cost_segments_and_idle_gaps() {
+        let report =
+            session_timeline_json_report(&sample_timeline(), &PricingDb::default(), true, None);
+        let val = serde_json::to_value(report).unwrap();
+        let turns = val["turns"].as_array().unwrap();
+        assert_eq!(turns.len(), 3);
+        assert_eq!(turns[0]["tools"], json!(["Read", "Bash"]));
+        // Both requests are above 200K prompt tokens: long-context rates.
+        assert_eq!(turns[0]["cost"], 28.5);
+        assert_eq!(turns[1]["cumulative_cost"], 63.0);
+        assert_eq!(turns[1]["thread"], "subagent");
+        assert_eq!(val["peak_context_tokens"], 2_000_000);
+        assert_eq!(val["segments"].as_array().unwrap().len(), 3);
+        assert_eq!(val["idle_gaps"][0]["minutes"], 29);
+        assert_eq!(val["idle_gaps"][0]["start"], "2026-02-06T12:01:00Z");
+    }
+
+    #[test]
+    fn csv_lists_one_row_per_request() {
+        let csv =
+            output_session_timeline_csv(&sample_timeline(), &PricingDb::default(), true, None);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].ends_with(",cost,cumulative_cost,stop_reason,tools"));
+        assert!(lines[1].ends_with(",end_turn,Read;Bash"), "{}", lines[1]);
+        assert!(lines[2].starts_with("2026-02-06T12:01:00Z,subagent,sonnet-4,"));
+    }
+
+    #[test]
+    fn turns_are_converted_at_their_own_day_rate() {
+        let mut late = entry("m2", 0, 500, false);
+        late.timestamp = "2026-02-07T12:00:00Z".to_string();
+        late.timestamp_ms += 86_400_000;
+        late.date_str = "2026-02-07".to_string();
+        let timeline =
+            build_session_timeline(vec![entry("m1", 0, 500, false), late], &HashMap::new());
+        let currency = CurrencyConverter::from_history_for_test(
+            "EUR",
+            &[("2026-02-06", 1.0), ("2026-02-07", 2.0)],
+        );
+
+        let report =
+            session_timeline_json_report(&timeline, &PricingDb::default(), true, Some(&currency));
+        let val = serde_json::to_value(report).unwrap();
+        // 500 input and 1M output tokens at base rates: $15.0015 a request.
+        assert_eq!(val["turns"][0]["cost"], 15.0015);
+        assert_eq!(val["turns"][1]["cost"], 30.003);
+        assert_eq!(val["turns"][1]["cumulative_cost"], 45.0045);
+        assert_eq!(val["cost"], 45.0045);
+    }
+}
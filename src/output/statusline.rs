@@ -1,20 +1,96 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-use crate::core::{DataQuality, DayStats, Stats};
-use crate::output::format::{
-    NumberFormat, cache_hit_rate_json_value, cost_json_value, format_cache_hit_rate,
-    format_compact, format_cost,
-};
-use crate::output::pricing_meta;
+use chrono::{DateTime, FixedOffset, NaiveDate, Timelike};
+
+use crate::consts::DATE_FORMAT;
+use crate::core::{BlockStats, DataQuality, DayStats, Stats};
+use crate::output::format::{Locale, format_cache_hit_rate, format_compact, format_cost};
+use crate::output::period::{blended_currency, span_currency};
 use crate::pricing::{
-    CostDisplayMode, CurrencyConverter, PricingDb, sum_display_model_costs,
-    sum_estimated_proxy_model_costs,
+    CostDisplayMode, CurrencyConverter, PricingDb, pricing_source_for_model_maps,
+    sum_display_model_costs, sum_estimated_proxy_model_costs,
 };
+use crate::source::CodexWeeklyQuota;
+use crate::utils::Timezone;
+
+use super::command_json::{StatuslineBlockJson, StatuslineFormattedJson, StatuslineJson};
+use super::quota::quota_window_json;
+use super::schema::{
+    CostJson, DataQualityJson, PricingJson, SCHEMA_VERSION, TokensJson, cost_amount,
+};
+
+/// Placeholders accepted by `statusline --template`, in documentation order.
+pub(crate) const STATUSLINE_PLACEHOLDERS: &[&str] = &[
+    "source",
+    "cost",
+    "in",
+    "out",
+    "reasoning",
+    "total",
+    "cache_hit",
+    "block_cost",
+    "block_tokens",
+    "block_end",
+    "block_remaining",
+    "quota_pct",
+    "quota_remaining",
+    "quota_status",
+    "quota_reset",
+];
+
+/// The 5-hour billing block that contains the current time.
+#[derive(Debug, Clone)]
+pub(crate) struct ActiveBlock {
+    pub(crate) block: BlockStats,
+    pub(crate) remaining_minutes: i64,
+}
+
+/// Find the block covering `now`, using the same wall-clock 5-hour buckets as
+/// `ccstats blocks`.
+pub(crate) fn active_block(
+    blocks: Vec<BlockStats>,
+    now: DateTime<FixedOffset>,
+) -> Option<ActiveBlock> {
+    let start_hour = now.hour() / 5 * 5;
+    let start = now.date_naive().and_hms_opt(start_hour, 0, 0)?;
+    let key = start.format("%Y-%m-%d %H:%M").to_string();
+    let block = blocks.into_iter().find(|block| block.block_start == key)?;
+    let remaining_minutes = (start + chrono::Duration::hours(5) - now.naive_local()).num_minutes();
+    Some(ActiveBlock {
+        block,
+        remaining_minutes: remaining_minutes.max(0),
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StatuslineOptions<'a> {
+    pub(crate) source_label: &'a str,
+    pub(crate) locale: Locale,
+    pub(crate) currency: Option<&'a CurrencyConverter>,
+    pub(crate) supports_cache_read: bool,
+    pub(crate) cost_mode: CostDisplayMode,
+    pub(crate) timezone: Timezone,
+    pub(crate) template: Option<&'a str>,
+    pub(crate) block: Option<&'a ActiveBlock>,
+    pub(crate) quota: Option<&'a CodexWeeklyQuota>,
+}
 
 struct Totals {
     stats: Stats,
     cost: f64,
     estimated_proxy_cost: f64,
+    day_costs: Vec<(NaiveDate, f64)>,
+}
+
+impl Totals {
+    /// Converter for the totals at the rates of the days they cover.
+    fn currency<'a>(
+        &self,
+        currency: Option<&'a CurrencyConverter>,
+    ) -> Option<Cow<'a, CurrencyConverter>> {
+        blended_currency(currency, &self.day_costs)
+    }
 }
 
 fn aggregate_totals(
@@ -25,50 +101,258 @@ fn aggregate_totals(
     let mut stats = Stats::default();
     let mut cost = 0.0;
     let mut estimated_proxy_cost = 0.0;
-    for day in day_stats.values() {
+    let mut day_costs = Vec::with_capacity(day_stats.len());
+    for (date, day) in day_stats {
         stats.add(&day.stats);
-        cost += sum_display_model_costs(&day.models, pricing_db, cost_mode);
+        let day_cost = sum_display_model_costs(&day.models, pricing_db, cost_mode);
+        cost += day_cost;
         estimated_proxy_cost += sum_estimated_proxy_model_costs(&day.models, pricing_db);
+        if let Ok(date) = NaiveDate::parse_from_str(date, DATE_FORMAT) {
+            day_costs.push((date, day_cost));
+        }
     }
     Totals {
         stats,
         cost,
         estimated_proxy_cost,
+        day_costs,
     }
 }
 
-/// Output a single line suitable for statusline/tmux integration
-/// Format: "CC: $X.XX | In: XM Out: XK | Today"
-pub(crate) fn print_statusline(
-    day_stats: &HashMap<String, DayStats>,
+pub(super) fn block_cost(
+    block: &ActiveBlock,
     pricing_db: &PricingDb,
-    source_label: &str,
-    number_format: NumberFormat,
-    currency: Option<&CurrencyConverter>,
-    supports_cache_read: bool,
     cost_mode: CostDisplayMode,
-) {
-    let t = aggregate_totals(day_stats, pricing_db, cost_mode);
+) -> f64 {
+    sum_display_model_costs(&block.block.models, pricing_db, cost_mode)
+}
+
+/// Converter for the block's cost at the rates of the days it spans.
+pub(super) fn block_currency<'a>(
+    block: &ActiveBlock,
+    pricing_db: &PricingDb,
+    cost_mode: CostDisplayMode,
+    currency: Option<&'a CurrencyConverter>,
+) -> Option<Cow<'a, CurrencyConverter>> {
+    span_currency(currency, &block.block.days, pricing_db, cost_mode)
+}
+
+pub(super) fn format_duration_minutes(minutes: i64) -> String {
+    if minutes >= 60 {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+enum TemplatePart<'t> {
+    Literal(&'t str),
+    Placeholder(&'t str),
+}
+
+/// Split a template into literal text and `{name}` placeholders; `{{` and
+/// `}}` produce literal braces.
+fn parse_template(template: &str) -> Result<Vec<TemplatePart<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '}']) {
+        if index > 0 {
+            parts.push(TemplatePart::Literal(&rest[..index]));
+        }
+        let tail = &rest[index..];
+        if let Some(after) = tail.strip_prefix("{{") {
+            parts.push(TemplatePart::Literal("{"));
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("}}") {
+            parts.push(TemplatePart::Literal("}"));
+            rest = after;
+        } else if tail.starts_with('}') {
+            return Err(
+                "unmatched `}` in statusline template; use `}}` for a literal brace".to_string(),
+            );
+        } else {
+            let Some(end) = tail.find('}') else {
+                return Err("unclosed `{` in statusline template".to_string());
+            };
+            let name = &tail[1..end];
+            if !STATUSLINE_PLACEHOLDERS.contains(&name) {
+                return Err(format!(
+                    "unknown statusline placeholder `{{{name}}}`; available: {}",
+                    STATUSLINE_PLACEHOLDERS
+                        .iter()
+                        .map(|name| format!("{{{name}}}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            parts.push(TemplatePart::Placeholder(name));
+            rest = &tail[end + 1..];
+        }
+    }
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Literal(rest));
+    }
+    Ok(parts)
+}
+
+/// Check a `--template` value before any data is loaded.
+pub(crate) fn validate_statusline_template(template: &str) -> Result<(), String> {
+    parse_template(template).map(|_| ())
+}
+
+/// Whether a valid template references any placeholder starting with `prefix`,
+/// so callers only load block or quota data when it is shown.
+pub(crate) fn template_uses(template: &str, prefix: &str) -> bool {
+    parse_template(template).is_ok_and(|parts| {
+        parts
+            .iter()
+            .any(|part| matches!(part, TemplatePart::Placeholder(name) if name.starts_with(prefix)))
+    })
+}
+
+/// Value of one placeholder; unavailable block or quota data renders empty.
+fn placeholder_value(
+    name: &str,
+    totals: &Totals,
+    pricing_db: &PricingDb,
+    options: &StatuslineOptions<'_>,
+) -> String {
+    let nf = options.locale;
+    let block = options.block;
+    let quota = options.quota;
+    match name {
+        "source" => options.source_label.to_string(),
+        "cost" => format_cost(
+            totals.cost,
+            totals.currency(options.currency).as_deref(),
+            options.locale,
+        ),
+        "in" => format_compact(totals.stats.input_tokens, nf),
+        "out" => format_compact(totals.stats.output_tokens, nf),
+        "reasoning" => format_compact(totals.stats.reasoning_tokens, nf),
+        "total" => format_compact(totals.stats.total_tokens(), nf),
+        "cache_hit" => {
+            format_cache_hit_rate(totals.stats.cache_hit_rate(options.supports_cache_read))
+        }
+        "block_cost" => block.map_or_else(String::new, |block| {
+            format_cost(
+                block_cost(block, pricing_db, options.cost_mode),
+                block_currency(block, pricing_db, options.cost_mode, options.currency).as_deref(),
+                options.locale,
+            )
+        }),
+        "block_tokens" => block.map_or_else(String::new, |block| {
+            format_compact(block.block.stats.total_tokens(), nf)
+        }),
+        "block_end" => block.map_or_else(String::new, |block| block.block.block_end.clone()),
+        "block_remaining" => block.map_or_else(String::new, |block| {
+            format_duration_minutes(block.remaining_minutes)
+        }),
+        "quota_pct" => quota.map_or_else(String::new, |quota| format!("{:.0}%", quota.used_pct)),
+        "quota_remaining" => {
+            quota.map_or_else(String::new, |quota| format!("{:.0}%", quota.remaining_pct))
+        }
+        "quota_status" => quota.map_or_else(String::new, |quota| quota.status.as_str().to_string()),
+        "quota_reset" => quota.map_or_else(String::new, |quota| {
+            options
+                .timezone
+                .to_fixed_offset(quota.resets_at)
+                .format("%a %H:%M")
+                .to_string()
+        }),
+        _ => String::new(),
+    }
+}
+
+fn render_template(
+    template: &str,
+    totals: &Totals,
+    pricing_db: &PricingDb,
+    options: &StatuslineOptions<'_>,
+) -> Result<String, String> {
+    let mut line = String::new();
+    for part in parse_template(template)? {
+        match part {
+            TemplatePart::Literal(text) => line.push_str(text),
+            TemplatePart::Placeholder(name) => {
+                line.push_str(&placeholder_value(name, totals, pricing_db, options));
+            }
+        }
+    }
+    Ok(line)
+}
 
+fn default_line(totals: &Totals, options: &StatuslineOptions<'_>) -> String {
+    let nf = options.locale;
     let mut parts = vec![
-        format!("{}: {}", source_label, format_cost(t.cost, currency)),
+        format!(
+            "{}: {}",
+            options.source_label,
+            format_cost(
+                totals.cost,
+                totals.currency(options.currency).as_deref(),
+                options.locale
+            )
+        ),
         format!(
             "In: {} Out: {}",
-            format_compact(t.stats.input_tokens, number_format),
-            format_compact(t.stats.output_tokens, number_format)
+            format_compact(totals.stats.input_tokens, nf),
+            format_compact(totals.stats.output_tokens, nf)
         ),
     ];
-    if t.stats.reasoning_tokens > 0 {
+    if totals.stats.reasoning_tokens > 0 {
         parts.push(format!(
             "Reason: {}",
-            format_compact(t.stats.reasoning_tokens, number_format)
+            format_compact(totals.stats.reasoning_tokens, nf)
         ));
     }
     parts.push(format!(
         "Cache Hit: {}",
-        format_cache_hit_rate(t.stats.cache_hit_rate(supports_cache_read))
+        format_cache_hit_rate(totals.stats.cache_hit_rate(options.supports_cache_read))
     ));
-    println!("{}", parts.join(" | "));
+    parts.join(" | ")
+}
+
+/// Render the single statusline/tmux line: the `--template` when one is set,
+/// otherwise `"CC: $X.XX | In: XM Out: XK | Cache Hit: X%"`.
+pub(crate) fn render_statusline(
+    day_stats: &HashMap<String, DayStats>,
+    pricing_db: &PricingDb,
+    options: &StatuslineOptions<'_>,
+) -> String {
+    let totals = aggregate_totals(day_stats, pricing_db, options.cost_mode);
+    match options.template {
+        Some(template) => render_template(template, &totals, pricing_db, options)
+            .unwrap_or_else(|error| format!("ccstats: {error}")),
+        None => default_line(&totals, options),
+    }
+}
+
+/// Output a single line suitable for statusline/tmux integration
+pub(crate) fn print_statusline(
+    day_stats: &HashMap<String, DayStats>,
+    pricing_db: &PricingDb,
+    options: &StatuslineOptions<'_>,
+) {
+    println!("{}", render_statusline(day_stats, pricing_db, options));
+}
+
+pub(super) fn block_json(
+    block: &ActiveBlock,
+    pricing_db: &PricingDb,
+    cost_mode: CostDisplayMode,
+    currency: Option<&CurrencyConverter>,
+) -> StatuslineBlockJson {
+    StatuslineBlockJson {
+        block_start: block.block.block_start.clone(),
+        block_end: block.block.block_end.clone(),
+        remaining_minutes: block.remaining_minutes,
+        total_tokens: block.block.stats.total_tokens(),
+        cost: cost_amount(
+            block_cost(block, pricing_db, cost_mode),
+            block_currency(block, pricing_db, cost_mode, currency).as_deref(),
+        ),
+    }
 }
 
 /// Output statusline as JSON for programmatic consumption
@@ -77,74 +361,77 @@ pub(crate) fn print_statusline_json(
     day_stats: &HashMap<String, DayStats>,
     pricing_db: &PricingDb,
     source_label: &str,
-    number_format: NumberFormat,
+    locale: Locale,
     currency: Option<&CurrencyConverter>,
 ) -> String {
-    print_statusline_json_with_quality(
+    let report = statusline_json_report(
         day_stats,
         pricing_db,
-        source_label,
-        number_format,
-        currency,
-        true,
+        &StatuslineOptions {
+            source_label,
+            locale,
+            currency,
+            supports_cache_read: true,
+            cost_mode: CostDisplayMode::Total,
+            timezone: Timezone::Named(chrono_tz::UTC),
+            template: None,
+            block: None,
+            quota: None,
+        },
         None,
-        CostDisplayMode::Total,
-    )
+    );
+    super::schema::report_to_string(&report, false)
 }
 
-#[allow(clippy::too_many_arguments)]
-pub(crate) fn print_statusline_json_with_quality(
+/// Statusline totals as one JSON object, including the active block and the
+/// Codex weekly quota when they were loaded (`null` otherwise) and the
+/// rendered `line`, so hooks can use either the data or the text.
+pub(crate) fn statusline_json_report(
     day_stats: &HashMap<String, DayStats>,
     pricing_db: &PricingDb,
-    source_label: &str,
-    number_format: NumberFormat,
-    currency: Option<&CurrencyConverter>,
-    supports_cache_read: bool,
+    options: &StatuslineOptions<'_>,
     data_quality: Option<DataQuality>,
-    cost_mode: CostDisplayMode,
-) -> String {
-    let t = aggregate_totals(day_stats, pricing_db, cost_mode);
-
-    let mut output = serde_json::json!({
-        "source": source_label,
-        "input_tokens": t.stats.input_tokens,
-        "output_tokens": t.stats.output_tokens,
-        "reasoning_tokens": t.stats.reasoning_tokens,
-        "cache_creation_tokens": t.stats.cache_creation,
-        "cache_read_tokens": t.stats.cache_read,
-        "cache_hit_rate": cache_hit_rate_json_value(
-            t.stats.cache_hit_rate(supports_cache_read)
-        ),
-        "total_tokens": t.stats.total_tokens(),
-        "cost": cost_json_value(t.cost, currency),
-        "formatted": {
-            "cost": format_cost(t.cost, currency),
-            "input": format_compact(t.stats.input_tokens, number_format),
-            "output": format_compact(t.stats.output_tokens, number_format),
-            "reasoning": format_compact(t.stats.reasoning_tokens, number_format),
-        }
-    });
-    if let Some(data_quality) = data_quality {
-        output["data_quality"] = serde_json::json!({
-            "valid_entries": data_quality.valid_entries,
-            "dedup_skipped_entries": data_quality.dedup_skipped_entries,
-            "parse_errors": data_quality.parse_errors,
-        });
-    }
-    pricing_meta::add_json_for_maps(
-        &mut output,
-        day_stats.values().map(|day| &day.models),
-        pricing_db,
-    );
-    if t.estimated_proxy_cost > 0.0 {
-        output["cost_kind"] = serde_json::json!(t.stats.cost_kind().as_str());
-        output["estimated_cost"] = cost_json_value(t.estimated_proxy_cost, currency);
-    }
+) -> StatuslineJson {
+    let t = aggregate_totals(day_stats, pricing_db, options.cost_mode);
+    let totals_currency = t.currency(options.currency);
+    let currency = totals_currency.as_deref();
+    let locale = options.locale;
+    let line = match options.template {
+        Some(template) => render_template(template, &t, pricing_db, options)
+            .unwrap_or_else(|error| format!("ccstats: {error}")),
+        None => default_line(&t, options),
+    };
 
-    serde_json::to_string(&output).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize JSON output: {e}");
-        "{}".to_string()
-    })
+    let has_estimate = t.estimated_proxy_cost > 0.0;
+    StatuslineJson {
+        schema_version: SCHEMA_VERSION,
+        source: options.source_label.to_string(),
+        tokens: TokensJson::new(&t.stats, options.supports_cache_read),
+        cost: CostJson {
+            cost: cost_amount(t.cost, currency),
+            pricing: PricingJson::new(
+                pricing_source_for_model_maps(
+                    day_stats.values().map(|day| &day.models),
+                    pricing_db,
+                ),
+                pricing_db,
+            ),
+            cost_kind: has_estimate.then(|| t.stats.cost_kind().as_str()),
+            estimated_cost: has_estimate.then(|| cost_amount(t.estimated_proxy_cost, currency)),
+        },
+        block: options
+            .block
+            .map(|block| block_json(block, pricing_db, options.cost_mode, options.currency)),
+        quota: options.quota.map(quota_window_json),
+        line,
+        formatted: StatuslineFormattedJson {
+            cost: format_cost(t.cost, currency, locale),
+            input: format_compact(t.stats.input_tokens, locale),
+            output: format_compact(t.stats.output_tokens, locale),
+            reasoning: format_compact(t.stats.reasoning_tokens, locale),
+        },
+        data_quality: data_quality.map(DataQualityJson::from),
+    }
 }
 
 #[cfg(test)]
@@ -200,7 +487,7 @@ mod tests {
             &day_stats,
             &PricingDb::default(),
             "OpenAI Codex",
-            NumberFormat::default(),
+            Locale::default(),
             None,
         );
         let value: serde_json::Value = serde_json::from_str(&json).unwrap();
@@ -216,7 +503,7 @@ mod tests {
             &day_stats,
             &PricingDb::default(),
             "Claude Code",
-            NumberFormat::default(),
+            Locale::default(),
             None,
         );
         let v: serde_json::Value = serde_json::from_str(&json).unwrap();
@@ -239,7 +526,7 @@ mod tests {
             &day_stats,
             &PricingDb::default(),
             "CC",
-            NumberFormat::default(),
+            Locale::default(),
             None,
         );
         let v: serde_json::Value = serde_json::from_str(&json).unwrap();
@@ -261,7 +548,7 @@ mod tests {
             &day_stats,
             &PricingDb::default(),
             "CC",
-            NumberFormat::default(),
+            Locale::default(),
             None,
         );
         let v: serde_json::Value = serde_json::from_str(&json).unwrap();
@@ -281,7 +568,7 @@ mod tests {
             &day_stats,
             &PricingDb::default(),
             "CC",
-            NumberFormat::default(),
+            Locale::default(),
             None,
         );
         let v: serde_json::Value = serde_json::from_str(&json).unwrap();
@@ -296,7 +583,7 @@ mod tests {
             &day_stats,
             &PricingDb::default(),
             "CC",
-            NumberFormat::default(),
+            Locale::default(),
             None,
         );
         let v: serde_json::Value = serde_json::from_str(&json).unwrap();
@@ -317,7 +604,7 @@ mod tests {
             &day_stats,
             &PricingDb::default(),
             "CC",
-            NumberFormat::default(),
+            Locale::default(),
             Some(&converter),
         );
 
@@ -325,4 +612,161 @@ mod tests {
         assert_eq!(v["cost"].as_f64(), Some(31.5));
         assert_eq!(v["formatted"]["cost"].as_str(), Some("CNY 31.50"));
     }
+
+    fn weekly_quota() -> CodexWeeklyQuota {
+        CodexWeeklyQuota {
+            observed_at: "2026-08-22T00:00:00Z".parse().unwrap(),
+            resets_at: "2026-08-24T09:30:00Z".parse().unwrap(),
+            estimated_depletion_at: None,
+            window_minutes: 10_080,
+            used_pct: 42.4,
+            remaining_pct: 57.6,
+            projected_pct_at_reset: 80.0,
+            status: crate::source::CodexQuotaStatus::OnTrack,
+        }
+    }
+
+    fn block_at(start: &str, end: &str, day: &DayStats) -> BlockStats {
+        BlockStats {
+            block_start: start.to_string(),
+            block_end: end.to_string(),
+            stats: day.stats.clone(),
+            models: day.models.clone(),
+            days: HashMap::new(),
+        }
+    }
+
+    fn options<'a>(
+        template: Option<&'a str>,
+        block: Option<&'a ActiveBlock>,
+        quota: Option<&'a CodexWeeklyQuota>,
+    ) -> StatuslineOptions<'a> {
+        StatuslineOptions {
+            source_label: "CC",
+            locale: Locale::default(),
+            currency: None,
+            supports_cache_read: true,
+            cost_mode: CostDisplayMode::Total,
+            timezone: Timezone::Named(chrono_tz::UTC),
+            template,
+            block,
+            quota,
+        }
+    }
+
+    #[test]
+    fn template_renders_placeholders_and_escaped_braces() {
+        let day = make_day(1_500_000, 250_000, 0, 0, 0);
+        let mut day_stats = HashMap::new();
+        day_stats.insert("2026-08-22".to_string(), day.clone());
+        let block = ActiveBlock {
+            block: block_at("2026-08-22 10:00", "15:00", &day),
+            remaining_minutes: 133,
+        };
+        let quota = weekly_quota();
+
+        let line = render_statusline(
+            &day_stats,
+            &PricingDb::default(),
+            &options(
+                Some("{{{source}}} {cost} {in}/{out} {block_remaining} {quota_pct} {quota_reset}"),
+                Some(&block),
+                Some(&quota),
+            ),
+        );
+
+        assert_eq!(line, "{CC} $8.25 1.5M/250.0K 2h13m 42% Mon 09:30");
+    }
+
+    #[test]
+    fn template_leaves_unavailable_values_empty() {
+        let line = render_statusline(
+            &HashMap::new(),
+            &PricingDb::default(),
+            &options(Some("[{block_cost}][{quota_status}]"), None, None),
+        );
+
+        assert_eq!(line, "[][]");
+    }
+
+    #[test]
+    fn template_validation_rejects_unknown_and_unbalanced_placeholders() {
+        let error = validate_statusline_template("{cost} {tokens}").unwrap_err();
+        assert!(error.contains("`{tokens}`"), "{error}");
+        assert!(error.contains("{block_remaining}"), "{error}");
+        assert!(validate_statusline_template("{cost").is_err());
+        assert!(validate_statusline_template("cost}").is_err());
+        assert!(validate_statusline_template("{{literal}}").is_ok());
+    }
+
+    #[test]
+    fn template_uses_matches_placeholder_prefixes() {
+        assert!(template_uses("{cost} {block_end}", "block_"));
+        assert!(!template_uses("{cost} {{block_end}}", "block_"));
+        assert!(!template_uses("{cost}", "quota_"));
+    }
+
+    #[test]
+    fn active_block_finds_the_current_bucket() {
+        let day = make_day(100, 50, 0, 0, 0);
+        let blocks = vec![
+            block_at("2026-08-22 05:00", "10:00", &day),
+            block_at("2026-08-22 10:00", "15:00", &day),
+        ];
+        let now = "2026-08-22T12:47:00+00:00".parse().unwrap();
+
+        let active = active_block(blocks.clone(), now).unwrap();
+        assert_eq!(active.block.block_start, "2026-08-22 10:00");
+        assert_eq!(active.remaining_minutes, 133);
+
+        let later = "2026-08-22T16:00:00+00:00".parse().unwrap();
+        assert!(active_block(blocks, later).is_none());
+    }
+
+    #[test]
+    fn statusline_json_includes_block_quota_and_line() {
+        let day = make_day(1_000, 2_000, 0, 0, 0);
+        let mut day_stats = HashMap::new();
+        day_stats.insert("2026-08-22".to_string(), day.clone());
+        let block = ActiveBlock {
+            block: block_at("2026-08-22 10:00", "15:00", &day),
+            remaining_minutes: 30,
+        };
+        let quota = weekly_quota();
+
+        let v: serde_json::Value = serde_json::to_value(statusline_json_report(
+            &day_stats,
+            &PricingDb::default(),
+            &options(
+                Some("{source} {block_remaining}"),
+                Some(&block),
+                Some(&quota),
+            ),
+            None,
+        ))
+        .unwrap();
+
+        assert_eq!(v["line"], "CC 30m");
+        assert_eq!(v["block"]["block_start"], "2026-08-22 10:00");
+        assert_eq!(v["block"]["remaining_minutes"], 30);
+        assert_eq!(v["block"]["total_tokens"], 3_000);
+        assert_eq!(v["quota"]["window"], "weekly");
+        assert_eq!(v["quota"]["used_pct"], 42.4);
+    }
+
+    #[test]
+    fn statusline_json_reports_missing_block_and_quota_as_null() {
+        let json = print_statusline_json(
+            &HashMap::new(),
+            &PricingDb::default(),
+            "CC",
+            Locale::default(),
+            None,
+        );
+        let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert!(v["block"].is_null());
+        assert!(v["quota"].is_null());
+        assert_eq!(v["line"], "CC: $0.00 | In: 0 Out: 0 | Cache Hit: N/A");
+    }
 }
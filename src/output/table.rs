@@ -4,14 +4,17 @@ use std::collections::HashMap;
 use crate::cli::SortOrder;
 use crate::core::{DayStats, Stats};
 use crate::output::format::{
-    NumberFormat, create_styled_table, format_cache_hit_rate, format_compact, format_cost,
-    format_number, header_cell, right_cell, styled_cell,
+    Locale, create_styled_table, format_cache_hit_rate, format_compact, format_cost, format_number,
+    header_cell, right_cell, styled_cell,
 };
-use crate::output::period::{Period, aggregate_day_stats_by_period};
+use crate::output::locale::Msg;
+use crate::output::markup::Markup;
+use crate::output::period::{Period, PeriodRates, aggregate_day_stats_by_period, row_currency};
 use crate::output::pricing_meta;
 use crate::pricing::{
-    CostDisplayMode, CurrencyConverter, PricingDb, calculate_display_cost, model_cost_kind,
-    sum_display_model_costs, sum_estimated_proxy_model_costs,
+    CostDisplayMode, CurrencyConverter, PricingDb, calculate_display_cost,
+    calculate_service_tier_cost, model_cost_kind, sum_display_model_costs,
+    sum_estimated_proxy_model_costs,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -21,12 +24,13 @@ pub(crate) struct TokenTableOptions<'a> {
     pub(crate) use_color: bool,
     pub(crate) compact: bool,
     pub(crate) show_cost: bool,
-    pub(crate) number_format: NumberFormat,
+    pub(crate) locale: Locale,
     pub(crate) show_reasoning: bool,
     pub(crate) show_cache_creation: bool,
     pub(crate) supports_cache_read: bool,
     pub(crate) currency: Option<&'a CurrencyConverter>,
     pub(crate) cost_mode: CostDisplayMode,
+    pub(crate) markup: Markup,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -40,24 +44,24 @@ pub(crate) struct PeriodSummaryFooter {
 pub(crate) fn print_summary_line(
     valid: i64,
     skipped: i64,
-    number_format: NumberFormat,
+    locale: Locale,
     elapsed_ms: Option<f64>,
     use_color: bool,
+    markup: Markup,
 ) {
-    let stats_text = format!(
-        "{} usage records ({} streaming records deduplicated)",
-        format_number(valid, number_format),
-        format_number(skipped, number_format)
+    let stats_text = locale.usage_records(
+        &format_number(valid, locale),
+        &format_number(skipped, locale),
     );
 
     if let Some(ms) = elapsed_ms {
         if use_color {
-            println!("\n  {stats_text} | \x1b[36m{ms:.0}ms\x1b[0m\n");
+            markup.line(&format!("\n  {stats_text} | \x1b[36m{ms:.0}ms\x1b[0m\n"));
         } else {
-            println!("\n  {stats_text} | {ms:.0}ms\n");
+            markup.line(&format!("\n  {stats_text} | {ms:.0}ms\n"));
         }
     } else {
-        println!("\n  {stats_text}\n");
+        markup.line(&format!("\n  {stats_text}\n"));
     }
 }
 
@@ -69,26 +73,26 @@ fn sort_keys(keys: &mut Vec<&String>, order: SortOrder) {
 }
 
 struct PeriodConfig {
-    label: &'static str,
-    title: &'static str,
+    label: Msg,
+    title: Msg,
     show_calls: bool,
 }
 
 fn period_config(period: Period) -> PeriodConfig {
     match period {
         Period::Day => PeriodConfig {
-            label: "Date",
-            title: "Token Usage",
+            label: Msg::Date,
+            title: Msg::TokenUsage,
             show_calls: true,
         },
         Period::Week => PeriodConfig {
-            label: "Week",
-            title: "Weekly Token Usage",
+            label: Msg::Week,
+            title: Msg::WeeklyTokenUsage,
             show_calls: false,
         },
         Period::Month => PeriodConfig {
-            label: "Month",
-            title: "Monthly Token Usage",
+            label: Msg::Month,
+            title: Msg::MonthlyTokenUsage,
             show_calls: false,
         },
     }
@@ -96,53 +100,54 @@ fn period_config(period: Period) -> PeriodConfig {
 
 fn build_header(cfg: &PeriodConfig, breakdown: bool, opts: &TokenTableOptions<'_>) -> Vec<Cell> {
     let c = opts.use_color;
+    let h_cell = |msg| header_cell(opts.locale.text(msg), c);
     if opts.compact {
-        let mut h = vec![header_cell(cfg.label, c)];
+        let mut h = vec![h_cell(cfg.label)];
         if cfg.show_calls {
-            h.push(header_cell("Calls", c));
+            h.push(h_cell(Msg::Calls));
         }
-        h.extend([header_cell("In", c), header_cell("Out", c)]);
-        h.push(header_cell("Cache Hit", c));
-        h.push(header_cell("Total", c));
+        h.extend([h_cell(Msg::In), h_cell(Msg::Out)]);
+        h.push(h_cell(Msg::CacheHit));
+        h.push(h_cell(Msg::Total));
         if opts.show_cost {
-            h.push(header_cell("Cost", c));
+            h.push(h_cell(Msg::Cost));
         }
         h
     } else if breakdown {
-        let mut h = vec![header_cell(cfg.label, c), header_cell("Model", c)];
+        let mut h = vec![h_cell(cfg.label), h_cell(Msg::Model)];
         if cfg.show_calls {
-            h.push(header_cell("Calls", c));
+            h.push(h_cell(Msg::Calls));
         }
-        h.extend([header_cell("Input", c), header_cell("Output", c)]);
+        h.extend([h_cell(Msg::Input), h_cell(Msg::Output)]);
         if opts.show_reasoning {
-            h.push(header_cell("Reason", c));
+            h.push(h_cell(Msg::Reason));
         }
         if opts.show_cache_creation {
-            h.push(header_cell("Cache Creation", c));
+            h.push(h_cell(Msg::CacheCreation));
         }
-        h.push(header_cell("Cache Read", c));
-        h.push(header_cell("Cache Hit", c));
+        h.push(h_cell(Msg::CacheRead));
+        h.push(h_cell(Msg::CacheHit));
         if opts.show_cost {
-            h.push(header_cell("Cost", c));
+            h.push(h_cell(Msg::Cost));
         }
         h
     } else {
-        let mut h = vec![header_cell(cfg.label, c), header_cell("Models", c)];
+        let mut h = vec![h_cell(cfg.label), h_cell(Msg::Models)];
         if cfg.show_calls {
-            h.push(header_cell("Calls", c));
+            h.push(h_cell(Msg::Calls));
         }
-        h.extend([header_cell("Input", c), header_cell("Output", c)]);
+        h.extend([h_cell(Msg::Input), h_cell(Msg::Output)]);
         if opts.show_reasoning {
-            h.push(header_cell("Reason", c));
+            h.push(h_cell(Msg::Reason));
         }
         if opts.show_cache_creation {
-            h.push(header_cell("Cache Creation", c));
+            h.push(h_cell(Msg::CacheCreation));
         }
-        h.push(header_cell("Cache Read", c));
-        h.push(header_cell("Cache Hit", c));
-        h.push(header_cell("Total", c));
+        h.push(h_cell(Msg::CacheRead));
+        h.push(h_cell(Msg::CacheHit));
+        h.push(h_cell(Msg::Total));
         if opts.show_cost {
-            h.push(header_cell("Cost", c));
+            h.push(h_cell(Msg::Cost));
         }
         h
     }
@@ -158,7 +163,7 @@ fn add_compact_rows(
     pricing_db: &PricingDb,
 ) -> f64 {
     let cost = sum_display_model_costs(&data.models, pricing_db, opts.cost_mode);
-    let nf = opts.number_format;
+    let nf = opts.locale;
     let mut row = vec![Cell::new(key)];
     if cfg.show_calls {
         row.push(right_cell(
@@ -183,7 +188,7 @@ fn add_compact_rows(
     ));
     if opts.show_cost {
         row.push(right_cell(
-            &format_cost(cost, opts.currency),
+            &format_cost(cost, opts.currency, opts.locale),
             cost_color,
             false,
         ));
@@ -203,7 +208,7 @@ fn add_breakdown_rows(
 ) -> f64 {
     let mut models: Vec<_> = data.models.keys().collect();
     models.sort();
-    let nf = opts.number_format;
+    let nf = opts.locale;
     let mut period_cost = 0.0;
 
     for (i, model) in models.iter().enumerate() {
@@ -245,16 +250,76 @@ fn add_breakdown_rows(
         ));
         if opts.show_cost {
             row.push(right_cell(
-                &format_cost(cost, opts.currency),
+                &format_cost(cost, opts.currency, opts.locale),
                 cost_color,
                 false,
             ));
         }
         table.add_row(row);
+        add_service_tier_rows(table, model, stats, cfg, opts, pricing_db);
     }
     period_cost
 }
 
+/// Sub-rows under a breakdown model row for the calls it made on a
+/// non-standard service tier (batch, flex, priority).
+fn add_service_tier_rows(
+    table: &mut Table,
+    model: &str,
+    stats: &Stats,
+    cfg: &PeriodConfig,
+    opts: &TokenTableOptions<'_>,
+    pricing_db: &PricingDb,
+) {
+    let nf = opts.locale;
+    let dim = opts.use_color.then_some(Color::DarkGrey);
+    for (tier, tokens) in stats.service_tiers.iter() {
+        let mut row = vec![
+            Cell::new(""),
+            styled_cell(&format!("  └ {}", tier.as_str()), dim, false),
+        ];
+        if cfg.show_calls {
+            row.push(right_cell(&format_number(tokens.count, nf), dim, false));
+        }
+        row.extend([
+            right_cell(&format_number(tokens.input_tokens, nf), dim, false),
+            right_cell(&format_number(tokens.output_tokens, nf), dim, false),
+        ]);
+        if opts.show_reasoning {
+            row.push(right_cell(
+                &format_number(tokens.reasoning_tokens, nf),
+                dim,
+                false,
+            ));
+        }
+        if opts.show_cache_creation {
+            row.push(right_cell(
+                &format_number(tokens.cache_creation, nf),
+                dim,
+                false,
+            ));
+        }
+        row.push(right_cell(
+            &format_number(tokens.cache_read, nf),
+            dim,
+            false,
+        ));
+        row.push(Cell::new(""));
+        if opts.show_cost {
+            row.push(right_cell(
+                &format_cost(
+                    calculate_service_tier_cost(stats, tier, model, pricing_db),
+                    opts.currency,
+                    opts.locale,
+                ),
+                dim,
+                false,
+            ));
+        }
+        table.add_row(row);
+    }
+}
+
 fn add_standard_rows(
     table: &mut Table,
     key: &str,
@@ -272,7 +337,7 @@ fn add_standard_rows(
         .collect::<Vec<_>>()
         .join(", ");
     let cost = sum_display_model_costs(&data.models, pricing_db, opts.cost_mode);
-    let nf = opts.number_format;
+    let nf = opts.locale;
 
     let mut row = vec![Cell::new(key), Cell::new(&models_str)];
     if cfg.show_calls {
@@ -317,7 +382,7 @@ fn add_standard_rows(
     ));
     if opts.show_cost {
         row.push(right_cell(
-            &format_cost(cost, opts.currency),
+            &format_cost(cost, opts.currency, opts.locale),
             cost_color,
             false,
         ));
@@ -326,6 +391,26 @@ fn add_standard_rows(
     cost
 }
 
+#[allow(clippy::too_many_arguments)]
+fn add_period_rows(
+    table: &mut Table,
+    key: &str,
+    data: &DayStats,
+    cfg: &PeriodConfig,
+    breakdown: bool,
+    opts: &TokenTableOptions<'_>,
+    cost_color: Option<Color>,
+    pricing_db: &PricingDb,
+) -> f64 {
+    if opts.compact {
+        add_compact_rows(table, key, data, cfg, opts, cost_color, pricing_db)
+    } else if breakdown {
+        add_breakdown_rows(table, key, data, cfg, opts, cost_color, pricing_db)
+    } else {
+        add_standard_rows(table, key, data, cfg, opts, cost_color, pricing_db)
+    }
+}
+
 fn add_total_row(
     table: &mut Table,
     total_stats: &Stats,
@@ -344,10 +429,10 @@ fn add_total_row(
     } else {
         None
     };
-    let nf = opts.number_format;
+    let nf = opts.locale;
 
     if opts.compact {
-        let mut row = vec![styled_cell("TOTAL", cyan, true)];
+        let mut row = vec![styled_cell(opts.locale.text(Msg::TotalRow), cyan, true)];
         if cfg.show_calls {
             row.push(right_cell(
                 &format_compact(total_stats.count, nf),
@@ -371,14 +456,17 @@ fn add_total_row(
         ));
         if opts.show_cost {
             row.push(right_cell(
-                &format_cost(total_cost, opts.currency),
+                &format_cost(total_cost, opts.currency, opts.locale),
                 green,
                 true,
             ));
         }
         table.add_row(row);
     } else {
-        let mut row = vec![styled_cell("TOTAL", cyan, true), Cell::new("")];
+        let mut row = vec![
+            styled_cell(opts.locale.text(Msg::TotalRow), cyan, true),
+            Cell::new(""),
+        ];
         if cfg.show_calls {
             row.push(right_cell(
                 &format_number(total_stats.count, nf),
@@ -423,7 +511,7 @@ fn add_total_row(
         }
         if opts.show_cost {
             row.push(right_cell(
-                &format_cost(total_cost, opts.currency),
+                &format_cost(total_cost, opts.currency, opts.locale),
                 green,
                 true,
             ));
@@ -451,6 +539,17 @@ pub(crate) fn print_period_table(
 
     let mut keys: Vec<_> = stats_ref.keys().collect();
     sort_keys(&mut keys, options.order);
+    let rates = PeriodRates::new(
+        day_stats,
+        period,
+        options.currency,
+        pricing_db,
+        options.cost_mode,
+    );
+    let total_options = TokenTableOptions {
+        currency: rates.as_ref().map_or(options.currency, |r| Some(r.total())),
+        ..options
+    };
 
     let mut table = create_styled_table();
     table.set_header(build_header(&cfg, breakdown, &options));
@@ -475,19 +574,21 @@ pub(crate) fn print_period_table(
         if model_cost_kind(&data.models).as_str() != "real" {
             has_estimated_proxy = true;
         }
-        let cost = if options.compact {
-            add_compact_rows(
-                &mut table, key, data, &cfg, &options, cost_color, pricing_db,
-            )
-        } else if breakdown {
-            add_breakdown_rows(
-                &mut table, key, data, &cfg, &options, cost_color, pricing_db,
-            )
-        } else {
-            add_standard_rows(
-                &mut table, key, data, &cfg, &options, cost_color, pricing_db,
-            )
+        let row_options = TokenTableOptions {
+            currency: row_currency(rates.as_ref(), key, options.currency),
+            ..options
         };
+        let label = options.locale.period_label(key, period);
+        let cost = add_period_rows(
+            &mut table,
+            &label,
+            data,
+            &cfg,
+            breakdown,
+            &row_options,
+            cost_color,
+            pricing_db,
+        );
         total_cost += cost;
         total_stats.add(&data.stats);
     }
@@ -498,35 +599,36 @@ pub(crate) fn print_period_table(
         total_cost,
         &cfg,
         breakdown,
-        &options,
+        &total_options,
     );
 
-    println!("\n  {}\n", cfg.title);
-    println!("{table}");
+    let markup = options.markup;
+    markup.title(options.locale.text(cfg.title));
+    markup.table(&table, 1, options.locale);
     if options.show_cost && has_estimated_proxy {
-        match options.cost_mode {
-            CostDisplayMode::RealOnly => println!(
-                "\n  Estimated proxy cost excluded from Cost total: {}",
-                format_cost(estimated_proxy_cost, options.currency)
-            ),
-            CostDisplayMode::Total => println!(
-                "\n  Cost includes estimated proxy values: {}",
-                format_cost(estimated_proxy_cost, options.currency)
-            ),
-        }
+        let message = match options.cost_mode {
+            CostDisplayMode::RealOnly => Msg::EstimatedProxyExcluded,
+            CostDisplayMode::Total => Msg::EstimatedProxyIncluded,
+        };
+        markup.line(&format!(
+            "\n  {}: {}",
+            options.locale.text(message),
+            format_cost(estimated_proxy_cost, total_options.currency, options.locale)
+        ));
     }
     if options.show_cost
         && let Some(note) =
             pricing_meta::note_for_maps(stats_ref.values().map(|data| &data.models), pricing_db)
     {
-        println!("\n  {note}");
+        markup.line(&format!("\n  {note}"));
     }
     print_summary_line(
         summary.valid,
         summary.skipped,
-        options.number_format,
+        options.locale,
         summary.elapsed_ms,
         options.use_color,
+        markup,
     );
 }
 
@@ -1,6 +1,6 @@
 use super::*;
 use crate::cli::SortOrder;
-use crate::output::format::NumberFormat;
+use crate::output::format::Locale;
 use crate::output::period::Period;
 use crate::pricing::CostDisplayMode;
 
@@ -10,12 +10,13 @@ fn default_opts() -> TokenTableOptions<'static> {
         use_color: false,
         compact: false,
         show_cost: false,
-        number_format: NumberFormat::default(),
+        locale: Locale::default(),
         show_reasoning: false,
         show_cache_creation: false,
         supports_cache_read: false,
         currency: None,
         cost_mode: CostDisplayMode::Total,
+        markup: Markup::Terminal,
     }
 }
 
@@ -125,21 +126,21 @@ fn sort_keys_desc() {
 #[test]
 fn period_config_day() {
     let cfg = period_config(Period::Day);
-    assert_eq!(cfg.label, "Date");
+    assert_eq!(Locale::default().text(cfg.label), "Date");
     assert!(cfg.show_calls);
 }
 
 #[test]
 fn period_config_week() {
     let cfg = period_config(Period::Week);
-    assert_eq!(cfg.label, "Week");
+    assert_eq!(Locale::default().text(cfg.label), "Week");
     assert!(!cfg.show_calls);
 }
 
 #[test]
 fn period_config_month() {
     let cfg = period_config(Period::Month);
-    assert_eq!(cfg.label, "Month");
+    assert_eq!(Locale::default().text(cfg.label), "Month");
     assert!(!cfg.show_calls);
 }
 
@@ -352,3 +353,21 @@ fn add_total_row_standard_mode() {
     add_total_row(&mut table, &stats, 3.25, &cfg, false, &opts);
     assert_eq!(table.row_count(), 1);
 }
+
+#[test]
+fn build_header_translates_for_locale() {
+    let opts = TokenTableOptions {
+        compact: true,
+        show_cost: true,
+        locale: Locale::parse(Some("ja")).unwrap(),
+        ..default_opts()
+    };
+    let header: Vec<String> = build_header(&period_config(Period::Month), false, &opts)
+        .iter()
+        .map(Cell::content)
+        .collect();
+    assert_eq!(
+        header,
+        ["月", "入力", "出力", "キャッシュヒット", "合計", "コスト"]
+    );
+}
@@ -2,93 +2,347 @@
 
 use std::fmt::Write;
 
-use comfy_table::CellAlignment;
+use comfy_table::{Cell, CellAlignment};
 
-use crate::core::ToolSummary;
+use crate::core::{ToolStats, ToolSummary};
+use crate::pricing::{CostDisplayMode, CurrencyConverter, PricingDb, sum_display_model_costs};
 
-use super::format::{create_styled_table, header_cell, right_cell};
+use super::command_json::{ToolDayJson, ToolGroupJson, ToolJson, ToolsJson};
+use super::csv::csv_cost;
+use super::format::{
+    Locale, create_styled_table, csv_escape, format_cost, format_number, header_cell, right_cell,
+};
+use super::locale::Msg;
+use super::markup::Markup;
+use super::schema::{SCHEMA_VERSION, cost_amount};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ToolReportOptions<'a> {
+    /// Name of the grouping column (`project`, `date`), when rows are grouped.
+    pub(crate) group_label: Option<&'static str>,
+    /// Add each row's last-used date to the table, a per-day call series to
+    /// JSON, and emit CSV as one row per group, tool, and date.
+    pub(crate) daily_series: bool,
+    pub(crate) show_cost: bool,
+    pub(crate) locale: Locale,
+    pub(crate) currency: Option<&'a CurrencyConverter>,
+}
+
+fn tool_cost(tool: &ToolStats, pricing_db: &PricingDb) -> f64 {
+    sum_display_model_costs(&tool.models, pricing_db, CostDisplayMode::Total)
+}
+
+fn call_pct(calls: u64, total: u64) -> f64 {
+    if total > 0 {
+        (calls as f64 / total as f64) * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Human-readable byte size, e.g. `812 B`, `4.2 KB`, `1.3 MB`.
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1_000_000 {
+        format!("{:.1} MB", bytes as f64 / 1_000_000.0)
+    } else if bytes >= 1_000 {
+        format!("{:.1} KB", bytes as f64 / 1_000.0)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+struct ToolTotals {
+    input_tokens: i64,
+    output_tokens: i64,
+    cost: f64,
+}
+
+fn summary_totals(summary: &ToolSummary, pricing_db: &PricingDb) -> ToolTotals {
+    let mut totals = ToolTotals {
+        input_tokens: 0,
+        output_tokens: 0,
+        cost: 0.0,
+    };
+    for tool in &summary.tools {
+        totals.input_tokens += tool.input_tokens();
+        totals.output_tokens += tool.output_tokens();
+        let cost = tool_cost(tool, pricing_db);
+        if !cost.is_nan() {
+            totals.cost += cost;
+        }
+    }
+    totals
+}
+
+/// Header label of a grouping column; `group_label` doubles as the JSON key.
+fn group_msg(label: &str) -> Msg {
+    match label {
+        "source" => Msg::Source,
+        "category" => Msg::Category,
+        "project" => Msg::Project,
+        "server" => Msg::Server,
+        _ => Msg::Date,
+    }
+}
+
+fn table_header(options: ToolReportOptions<'_>, use_color: bool) -> Vec<Cell> {
+    let h_cell = |msg| header_cell(options.locale.text(msg), use_color);
+    let mut header = Vec::new();
+    if let Some(label) = options.group_label {
+        header.push(h_cell(group_msg(label)));
+    }
+    header.extend([
+        h_cell(Msg::Tool),
+        h_cell(Msg::Calls),
+        header_cell("%", use_color),
+    ]);
+    if options.daily_series {
+        header.push(h_cell(Msg::LastUsed));
+    }
+    header.extend([
+        h_cell(Msg::AvgResult),
+        h_cell(Msg::Input),
+        h_cell(Msg::Output),
+    ]);
+    if options.show_cost {
+        header.push(h_cell(Msg::Cost));
+    }
+    header
+}
 
 /// Print tool usage as a table
-pub(crate) fn print_tools_table(summary: &ToolSummary, use_color: bool) {
+pub(crate) fn print_tools_table(
+    summary: &ToolSummary,
+    pricing_db: &PricingDb,
+    options: ToolReportOptions<'_>,
+    use_color: bool,
+    markup: Markup,
+) {
     if summary.tools.is_empty() {
-        println!("No tool usage data found.");
+        markup.line(options.locale.text(Msg::NoToolUsage));
         return;
     }
+    let nf = options.locale;
 
     let mut table = create_styled_table();
-    table.set_header(vec![
-        header_cell("Tool", use_color),
-        header_cell("Calls", use_color),
-        header_cell("%", use_color),
-    ]);
+    table.set_header(table_header(options, use_color));
 
     for tool in &summary.tools {
-        let pct = if summary.total > 0 {
-            (tool.calls as f64 / summary.total as f64) * 100.0
-        } else {
-            0.0
-        };
-        table.add_row(vec![
-            comfy_table::Cell::new(&tool.name),
+        let mut row = Vec::new();
+        if options.group_label.is_some() {
+            row.push(Cell::new(tool.group.as_deref().unwrap_or_default()));
+        }
+        row.extend([
+            Cell::new(&tool.name),
             right_cell(&format_calls(tool.calls), None, false),
-            right_cell(&format!("{pct:.1}%"), None, false),
+            right_cell(
+                &format!("{:.1}%", call_pct(tool.calls, summary.total)),
+                None,
+                false,
+            ),
+        ]);
+        if options.daily_series {
+            row.push(Cell::new(tool.last_used().unwrap_or_default()));
+        }
+        row.extend([
+            right_cell(
+                &tool
+                    .avg_result_bytes()
+                    .map_or_else(|| "—".to_string(), format_bytes),
+                None,
+                false,
+            ),
+            right_cell(&format_number(tool.input_tokens(), nf), None, false),
+            right_cell(&format_number(tool.output_tokens(), nf), None, false),
         ]);
+        if options.show_cost {
+            row.push(right_cell(
+                &format_cost(
+                    tool_cost(tool, pricing_db),
+                    options.currency,
+                    options.locale,
+                ),
+                None,
+                false,
+            ));
+        }
+        table.add_row(row);
     }
 
     // Total row
-    table.add_row(vec![
-        comfy_table::Cell::new("Total")
+    let totals = summary_totals(summary, pricing_db);
+    let mut row = vec![
+        Cell::new(options.locale.text(Msg::Total))
             .add_attribute(comfy_table::Attribute::Bold)
             .set_alignment(CellAlignment::Left),
+    ];
+    if options.group_label.is_some() {
+        row.push(Cell::new(""));
+    }
+    row.extend([
         right_cell(&format_calls(summary.total), None, true),
         right_cell("100.0%", None, true),
     ]);
+    if options.daily_series {
+        row.push(Cell::new(""));
+    }
+    row.extend([
+        right_cell("", None, true),
+        right_cell(&format_number(totals.input_tokens, nf), None, true),
+        right_cell(&format_number(totals.output_tokens, nf), None, true),
+    ]);
+    if options.show_cost {
+        row.push(right_cell(
+            &format_cost(totals.cost, options.currency, options.locale),
+            None,
+            true,
+        ));
+    }
+    table.add_row(row);
 
-    println!("{table}");
+    markup.table(&table, 1, options.locale);
+    markup.line(options.locale.text(Msg::ToolTokenLegend));
 }
 
 /// Output tool usage as JSON
-pub(crate) fn output_tools_json(summary: &ToolSummary) -> String {
-    let tools: Vec<serde_json::Value> = summary
+pub(crate) fn tools_json_report(
+    summary: &ToolSummary,
+    pricing_db: &PricingDb,
+    options: ToolReportOptions<'_>,
+) -> ToolsJson {
+    let tools = summary
         .tools
         .iter()
         .map(|t| {
-            let pct = if summary.total > 0 {
-                (t.calls as f64 / summary.total as f64) * 100.0
-            } else {
-                0.0
-            };
-            serde_json::json!({
-                "name": t.name,
-                "calls": t.calls,
-                "percentage": (pct * 10.0).round() / 10.0,
-            })
+            let pct = call_pct(t.calls, summary.total);
+            ToolJson {
+                name: t.name.clone(),
+                group: options
+                    .group_label
+                    .map(|label| ToolGroupJson::new(label, t.group.clone().unwrap_or_default())),
+                calls: t.calls,
+                percentage: (pct * 10.0).round() / 10.0,
+                results: t.results,
+                avg_result_bytes: t.avg_result_bytes(),
+                input_tokens: t.input_tokens(),
+                output_tokens: t.output_tokens(),
+                last_used: options
+                    .daily_series
+                    .then(|| t.last_used().map(str::to_string)),
+                daily: options.daily_series.then(|| {
+                    t.daily
+                        .iter()
+                        .map(|(date, calls)| ToolDayJson {
+                            date: date.clone(),
+                            calls: *calls,
+                        })
+                        .collect()
+                }),
+                cost: options
+                    .show_cost
+                    .then(|| cost_amount(tool_cost(t, pricing_db), options.currency)),
+            }
         })
         .collect();
 
-    serde_json::json!({
-        "tools": tools,
-        "total": summary.total,
-    })
-    .to_string()
+    let totals = summary_totals(summary, pricing_db);
+    ToolsJson {
+        schema_version: SCHEMA_VERSION,
+        tools,
+        total: summary.total,
+        group_by: options.group_label.unwrap_or("tool"),
+        input_tokens: totals.input_tokens,
+        output_tokens: totals.output_tokens,
+        cost: options
+            .show_cost
+            .then(|| cost_amount(totals.cost, options.currency)),
+    }
 }
 
 /// Output tool usage as CSV
-pub(crate) fn output_tools_csv(summary: &ToolSummary) -> String {
-    let mut out = String::from("tool,calls,percentage\n");
+pub(crate) fn output_tools_csv(
+    summary: &ToolSummary,
+    pricing_db: &PricingDb,
+    options: ToolReportOptions<'_>,
+) -> String {
+    if options.daily_series {
+        return output_tools_daily_csv(summary, options);
+    }
+    let mut out = String::new();
+    if let Some(label) = options.group_label {
+        let _ = write!(out, "{label},");
+    }
+    out.push_str("tool,calls,percentage,avg_result_bytes,input_tokens,output_tokens");
+    if options.show_cost {
+        out.push_str(",cost");
+    }
+    out.push('\n');
     for tool in &summary.tools {
-        let pct = if summary.total > 0 {
-            (tool.calls as f64 / summary.total as f64) * 100.0
-        } else {
-            0.0
-        };
-        let _ = writeln!(out, "{},{},{pct:.1}", tool.name, tool.calls);
+        if options.group_label.is_some() {
+            let _ = write!(
+                out,
+                "{},",
+                csv_escape(tool.group.as_deref().unwrap_or_default())
+            );
+        }
+        let _ = write!(
+            out,
+            "{},{},{:.1},{},{},{}",
+            csv_escape(&tool.name),
+            tool.calls,
+            call_pct(tool.calls, summary.total),
+            tool.avg_result_bytes()
+                .map_or_else(String::new, |bytes| bytes.to_string()),
+            tool.input_tokens(),
+            tool.output_tokens(),
+        );
+        if options.show_cost {
+            let _ = write!(
+                out,
+                ",{}",
+                csv_cost(tool_cost(tool, pricing_db), options.currency)
+            );
+        }
+        out.push('\n');
+    }
+    let totals = summary_totals(summary, pricing_db);
+    if options.group_label.is_some() {
+        out.push_str("Total,");
+        let _ = write!(out, ",{},100.0,", summary.total);
+    } else {
+        let _ = write!(out, "Total,{},100.0,", summary.total);
+    }
+    let _ = write!(out, ",{},{}", totals.input_tokens, totals.output_tokens);
+    if options.show_cost {
+        let _ = write!(out, ",{}", csv_cost(totals.cost, options.currency));
     }
-    let _ = writeln!(out, "Total,{},100.0", summary.total);
+    out.push('\n');
     out
 }
 
-fn format_calls(n: u64) -> String {
+/// Per-day call series: one row per group, tool, and date.
+fn output_tools_daily_csv(summary: &ToolSummary, options: ToolReportOptions<'_>) -> String {
+    let mut out = String::new();
+    if let Some(label) = options.group_label {
+        let _ = write!(out, "{label},");
+    }
+    out.push_str("tool,date,calls\n");
+    for tool in &summary.tools {
+        for (date, calls) in &tool.daily {
+            if options.group_label.is_some() {
+                let _ = write!(
+                    out,
+                    "{},",
+                    csv_escape(tool.group.as_deref().unwrap_or_default())
+                );
+            }
+            let _ = writeln!(out, "{},{date},{calls}", csv_escape(&tool.name));
+        }
+    }
+    out
+}
+
+pub(super) fn format_calls(n: u64) -> String {
     let s = n.to_string();
     let mut result = String::new();
     for (i, c) in s.chars().rev().enumerate() {
@@ -103,48 +357,147 @@ fn format_calls(n: u64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{ToolStats, ToolSummary};
+    use crate::core::{Stats, ToolStats, ToolSummary};
 
     fn sample_summary() -> ToolSummary {
+        let mut read = ToolStats {
+            name: "Read".to_string(),
+            calls: 100,
+            results: 100,
+            result_bytes: 420_000,
+            ..Default::default()
+        };
+        read.models.insert(
+            "sonnet-4".to_string(),
+            Stats {
+                input_tokens: 1_000_000,
+                output_tokens: 2_000,
+                ..Default::default()
+            },
+        );
         ToolSummary {
             tools: vec![
-                ToolStats {
-                    name: "Read".to_string(),
-                    calls: 100,
-                },
+                read,
                 ToolStats {
                     name: "Bash".to_string(),
                     calls: 50,
+                    ..Default::default()
                 },
                 ToolStats {
                     name: "Edit".to_string(),
                     calls: 25,
+                    ..Default::default()
                 },
             ],
             total: 175,
         }
     }
 
+    fn options(group_label: Option<&'static str>) -> ToolReportOptions<'static> {
+        ToolReportOptions {
+            group_label,
+            daily_series: false,
+            show_cost: true,
+            locale: Locale::default(),
+            currency: None,
+        }
+    }
+
     #[test]
     fn json_output_structure() {
-        let json = output_tools_json(&sample_summary());
-        let val: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let val: serde_json::Value = serde_json::to_value(tools_json_report(
+            &sample_summary(),
+            &PricingDb::default(),
+            options(None),
+        ))
+        .unwrap();
         assert_eq!(val["total"], 175);
         let tools = val["tools"].as_array().unwrap();
         assert_eq!(tools.len(), 3);
         assert_eq!(tools[0]["name"], "Read");
         assert_eq!(tools[0]["calls"], 100);
+        assert_eq!(tools[0]["avg_result_bytes"], 4_200);
+        assert_eq!(tools[0]["input_tokens"], 1_000_000);
+        assert_eq!(tools[0]["output_tokens"], 2_000);
+        assert!((tools[0]["cost"].as_f64().unwrap() - 3.03).abs() < 1e-9);
+        assert!(tools[1]["avg_result_bytes"].is_null());
+        assert_eq!(val["group_by"], "tool");
+        assert!((val["cost"].as_f64().unwrap() - 3.03).abs() < 1e-9);
+    }
+
+    #[test]
+    fn grouped_json_and_csv_name_the_group_column() {
+        let mut summary = sample_summary();
+        summary.tools[0].group = Some("2026-02-06".to_string());
+        let val: serde_json::Value = serde_json::to_value(tools_json_report(
+            &summary,
+            &PricingDb::default(),
+            options(Some("date")),
+        ))
+        .unwrap();
+        assert_eq!(val["group_by"], "date");
+        assert_eq!(val["tools"][0]["date"], "2026-02-06");
+
+        let csv = output_tools_csv(&summary, &PricingDb::default(), options(Some("date")));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "date,tool,calls,percentage,avg_result_bytes,input_tokens,output_tokens,cost"
+        );
+        assert!(lines[1].starts_with("2026-02-06,Read,100,57.1,4200,1000000,2000,"));
+        assert!(lines[4].starts_with("Total,,175,100.0,,1000000,2000,"));
+    }
+
+    #[test]
+    fn server_breakdown_adds_per_day_series() {
+        let mut summary = sample_summary();
+        summary.tools[0].name = "create_issue".to_string();
+        summary.tools[0].group = Some("github".to_string());
+        summary.tools[0].daily.extend([
+            ("2026-02-05".to_string(), 40),
+            ("2026-02-06".to_string(), 60),
+        ]);
+        let options = ToolReportOptions {
+            daily_series: true,
+            ..options(Some("server"))
+        };
+
+        let val: serde_json::Value =
+            serde_json::to_value(tools_json_report(&summary, &PricingDb::default(), options))
+                .unwrap();
+        let row = &val["tools"][0];
+        assert_eq!(row["server"], "github");
+        assert_eq!(row["last_used"], "2026-02-06");
+        assert_eq!(row["daily"][0]["date"], "2026-02-05");
+        assert_eq!(row["daily"][1]["calls"], 60);
+
+        let csv = output_tools_csv(&summary, &PricingDb::default(), options);
+        assert_eq!(
+            csv,
+            "server,tool,date,calls\ngithub,create_issue,2026-02-05,40\ngithub,create_issue,2026-02-06,60\n"
+        );
     }
 
     #[test]
     fn csv_output_format() {
-        let csv = output_tools_csv(&sample_summary());
+        let csv = output_tools_csv(&sample_summary(), &PricingDb::default(), options(None));
         let lines: Vec<&str> = csv.lines().collect();
-        assert_eq!(lines[0], "tool,calls,percentage");
+        assert_eq!(
+            lines[0],
+            "tool,calls,percentage,avg_result_bytes,input_tokens,output_tokens,cost"
+        );
         assert!(lines[1].starts_with("Read,100,"));
+        assert_eq!(lines[2], "Bash,50,28.6,,0,0,0.000000");
         assert!(lines[4].starts_with("Total,175,"));
     }
 
+    #[test]
+    fn format_bytes_scales_units() {
+        assert_eq!(format_bytes(812), "812 B");
+        assert_eq!(format_bytes(4_200), "4.2 KB");
+        assert_eq!(format_bytes(1_300_000), "1.3 MB");
+    }
+
     #[test]
     fn format_calls_with_commas() {
         assert_eq!(format_calls(0), "0");
@@ -156,8 +509,12 @@ mod tests {
     #[test]
     fn empty_summary() {
         let summary = ToolSummary::default();
-        let json = output_tools_json(&summary);
-        let val: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let val: serde_json::Value = serde_json::to_value(tools_json_report(
+            &summary,
+            &PricingDb::default(),
+            options(None),
+        ))
+        .unwrap();
         assert_eq!(val["total"], 0);
         assert!(val["tools"].as_array().unwrap().is_empty());
     }
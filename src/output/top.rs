@@ -5,16 +5,22 @@
 //! reports the share of the overall total so callers can see which
 //! consumer is dominating spend or token volume.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
+use chrono::NaiveDate;
 use comfy_table::{Attribute, Cell, CellAlignment, Color};
 
 use crate::cli::TopDimension;
+use crate::consts::DATE_FORMAT;
 use crate::core::{CostKind, DayStats, ProjectStats, Stats};
 use crate::output::format::{
-    NumberFormat, create_styled_table, format_cache_hit_rate, format_compact, format_cost,
-    format_number, header_cell, right_cell, styled_cell,
+    Locale, create_styled_table, format_cache_hit_rate, format_compact, format_cost, format_number,
+    header_cell, right_cell, styled_cell,
 };
+use crate::output::locale::Msg;
+use crate::output::markup::Markup;
+use crate::output::period::{blended_currency, day_costs};
 use crate::pricing::{
     CostDisplayMode, CurrencyConverter, PricingDb, calculate_display_cost, model_cost_kind,
     pricing_source_for_model_stats, pricing_source_for_models, sum_display_model_costs,
@@ -33,6 +39,18 @@ pub(crate) struct TopRow {
     pub(crate) pricing_source: crate::pricing::PricingSource,
     pub(crate) pricing_cache_age_seconds: Option<u64>,
     pub(crate) pricing_cache_mtime_epoch_seconds: Option<u64>,
+    /// USD cost by local day, for converting at daily exchange rates.
+    pub(crate) day_costs: Vec<(NaiveDate, f64)>,
+}
+
+impl TopRow {
+    /// Converter for this row's cost at the rates of the days it was spent.
+    pub(crate) fn currency<'a>(
+        &self,
+        currency: Option<&'a CurrencyConverter>,
+    ) -> Option<Cow<'a, CurrencyConverter>> {
+        blended_currency(currency, &self.day_costs)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -43,11 +61,12 @@ pub(crate) struct TopTableOptions<'a> {
     pub(crate) show_cost: bool,
     pub(crate) supports_cache_read: bool,
     pub(crate) source_label: &'a str,
-    pub(crate) number_format: NumberFormat,
+    pub(crate) locale: Locale,
     pub(crate) currency: Option<&'a CurrencyConverter>,
     pub(crate) dim: TopDimension,
     pub(crate) limit: usize,
     pub(crate) cost_mode: CostDisplayMode,
+    pub(crate) markup: Markup,
 }
 
 /// Aggregate per-model rows from a daily-stats map.
@@ -64,9 +83,18 @@ pub(crate) fn rank_by_model_with_cost_mode(
     cost_mode: CostDisplayMode,
 ) -> Vec<TopRow> {
     let mut totals: HashMap<String, Stats> = HashMap::new();
-    for day in day_stats.values() {
+    let mut day_costs: HashMap<String, Vec<(NaiveDate, f64)>> = HashMap::new();
+    for (date, day) in day_stats {
+        let date = NaiveDate::parse_from_str(date, DATE_FORMAT).ok();
         for (model, stats) in &day.models {
             totals.entry(model.clone()).or_default().add(stats);
+            if let Some(date) = date {
+                let cost = calculate_display_cost(stats, model, pricing_db, cost_mode);
+                day_costs
+                    .entry(model.clone())
+                    .or_default()
+                    .push((date, cost));
+            }
         }
     }
 
@@ -82,6 +110,7 @@ pub(crate) fn rank_by_model_with_cost_mode(
             let (pricing_cache_age_seconds, pricing_cache_mtime_epoch_seconds) =
                 top_cache_metadata(pricing_source, pricing_db);
             TopRow {
+                day_costs: day_costs.remove(&model).unwrap_or_default(),
                 name: model,
                 count: stats.count,
                 stats,
@@ -124,6 +153,7 @@ pub(crate) fn rank_by_project(projects: &[ProjectStats], pricing_db: &PricingDb)
                 pricing_source,
                 pricing_cache_age_seconds,
                 pricing_cache_mtime_epoch_seconds,
+                day_costs: day_costs(&project.days, pricing_db, CostDisplayMode::Total),
             }
         })
         .collect();
@@ -200,17 +230,17 @@ pub(super) fn share_of(row: &TopRow, total_cost: f64, total_tokens: i64, basis:
     }
 }
 
-fn dim_label(dim: TopDimension) -> &'static str {
+fn dim_label(dim: TopDimension) -> Msg {
     match dim {
-        TopDimension::Model => "Model",
-        TopDimension::Project => "Project",
+        TopDimension::Model => Msg::Model,
+        TopDimension::Project => Msg::Project,
     }
 }
 
-fn count_label(dim: TopDimension) -> &'static str {
+fn count_label(dim: TopDimension) -> Msg {
     match dim {
-        TopDimension::Model => "Calls",
-        TopDimension::Project => "Sessions",
+        TopDimension::Model => Msg::Calls,
+        TopDimension::Project => Msg::Sessions,
     }
 }
 
@@ -220,10 +250,10 @@ pub(crate) fn print_top_table(rows: &[TopRow], options: TopTableOptions<'_>) {
     let limited = take_top(rows, options.limit);
 
     if limited.is_empty() {
-        println!(
-            "No {} usage to rank for {}.",
-            dim_label(options.dim).to_lowercase(),
-            options.source_label
+        options.markup.line(
+            &options
+                .locale
+                .nothing_to_rank(dim_label(options.dim), options.source_label),
         );
         return;
     }
@@ -231,6 +261,7 @@ pub(crate) fn print_top_table(rows: &[TopRow], options: TopTableOptions<'_>) {
     let total_cost = sum_cost(&limited);
     let total_tokens = sum_tokens(&limited);
     let basis = share_basis(&limited);
+    let total_currency = total_currency(&limited, options.currency);
     let cost_color = if options.use_color {
         Some(Color::Green)
     } else {
@@ -242,21 +273,22 @@ pub(crate) fn print_top_table(rows: &[TopRow], options: TopTableOptions<'_>) {
         None
     };
 
+    let h_cell = |msg| header_cell(options.locale.text(msg), options.use_color);
     let mut table = create_styled_table();
     let mut header = vec![
         header_cell("#", options.use_color),
-        header_cell(dim_label(options.dim), options.use_color),
-        header_cell(count_label(options.dim), options.use_color),
+        h_cell(dim_label(options.dim)),
+        h_cell(count_label(options.dim)),
     ];
     if !options.compact {
-        header.push(header_cell("Input", options.use_color));
-        header.push(header_cell("Output", options.use_color));
+        header.push(h_cell(Msg::Input));
+        header.push(h_cell(Msg::Output));
     }
-    header.push(header_cell("Cache Hit", options.use_color));
-    header.push(header_cell("Total", options.use_color));
-    header.push(header_cell("Share", options.use_color));
+    header.push(h_cell(Msg::CacheHit));
+    header.push(h_cell(Msg::Total));
+    header.push(h_cell(Msg::Share));
     if options.show_cost {
-        header.push(header_cell("Cost", options.use_color));
+        header.push(h_cell(Msg::Cost));
     }
     table.set_header(header);
 
@@ -265,20 +297,16 @@ pub(crate) fn print_top_table(rows: &[TopRow], options: TopTableOptions<'_>) {
         let mut cells = vec![
             right_cell(&format!("{}", idx + 1), None, false),
             Cell::new(&row.name),
-            right_cell(
-                &format_number(row.count, options.number_format),
-                None,
-                false,
-            ),
+            right_cell(&format_number(row.count, options.locale), None, false),
         ];
         if !options.compact {
             cells.push(right_cell(
-                &format_number(row.stats.input_tokens, options.number_format),
+                &format_number(row.stats.input_tokens, options.locale),
                 None,
                 false,
             ));
             cells.push(right_cell(
-                &format_number(row.stats.output_tokens, options.number_format),
+                &format_number(row.stats.output_tokens, options.locale),
                 None,
                 false,
             ));
@@ -289,14 +317,18 @@ pub(crate) fn print_top_table(rows: &[TopRow], options: TopTableOptions<'_>) {
             false,
         ));
         cells.push(right_cell(
-            &format_compact(row.stats.total_tokens(), options.number_format),
+            &format_compact(row.stats.total_tokens(), options.locale),
             None,
             false,
         ));
         cells.push(right_cell(&format!("{share:.1}%"), None, false));
         if options.show_cost {
             cells.push(right_cell(
-                &format_cost(row.cost, options.currency),
+                &format_cost(
+                    row.cost,
+                    row.currency(options.currency).as_deref(),
+                    options.locale,
+                ),
                 cost_color,
                 false,
             ));
@@ -324,21 +356,21 @@ pub(crate) fn print_top_table(rows: &[TopRow], options: TopTableOptions<'_>) {
     }
     let mut total_row = vec![
         styled_cell("", bold_cyan, true),
-        styled_cell("TOTAL", bold_cyan, true),
+        styled_cell(options.locale.text(Msg::TotalRow), bold_cyan, true),
         right_cell(
-            &format_number(displayed_total_count, options.number_format),
+            &format_number(displayed_total_count, options.locale),
             bold_cyan,
             true,
         ),
     ];
     if !options.compact {
         total_row.push(right_cell(
-            &format_number(displayed_total_input, options.number_format),
+            &format_number(displayed_total_input, options.locale),
             bold_cyan,
             true,
         ));
         total_row.push(right_cell(
-            &format_number(displayed_total_output, options.number_format),
+            &format_number(displayed_total_output, options.locale),
             bold_cyan,
             true,
         ));
@@ -349,7 +381,7 @@ pub(crate) fn print_top_table(rows: &[TopRow], options: TopTableOptions<'_>) {
         true,
     ));
     total_row.push(right_cell(
-        &format_compact(displayed_total_tokens, options.number_format),
+        &format_compact(displayed_total_tokens, options.locale),
         bold_cyan,
         true,
     ));
@@ -360,7 +392,7 @@ pub(crate) fn print_top_table(rows: &[TopRow], options: TopTableOptions<'_>) {
     );
     if options.show_cost {
         total_row.push(right_cell(
-            &format_cost(total_cost, options.currency),
+            &format_cost(total_cost, total_currency.as_deref(), options.locale),
             cost_color,
             true,
         ));
@@ -372,49 +404,40 @@ pub(crate) fn print_top_table(rows: &[TopRow], options: TopTableOptions<'_>) {
         .map(|row| row.estimated_cost)
         .sum();
 
-    if rows.len() > limited.len() {
-        println!(
-            "{} top {} of {} {}(s) — by {}",
-            options.source_label,
-            limited.len(),
-            rows.len(),
-            dim_label(options.dim).to_lowercase(),
-            if basis == ShareBasis::Cost {
-                "cost"
-            } else {
-                "tokens"
-            }
-        );
-    } else {
-        println!(
-            "{} top {} {}(s) — by {}",
-            options.source_label,
-            limited.len(),
-            dim_label(options.dim).to_lowercase(),
-            if basis == ShareBasis::Cost {
-                "cost"
-            } else {
-                "tokens"
-            }
-        );
-    }
-    println!("{table}");
+    options.markup.line(&options.locale.top_heading(
+        options.source_label,
+        limited.len(),
+        (rows.len() > limited.len()).then_some(rows.len()),
+        dim_label(options.dim),
+        basis == ShareBasis::Cost,
+    ));
+    options.markup.table(&table, 1, options.locale);
     if options.show_cost && estimated_proxy_cost > 0.0 {
         match options.cost_mode {
-            CostDisplayMode::RealOnly => println!(
-                "\nEstimated proxy cost excluded from Cost ranking: {}",
-                format_cost(estimated_proxy_cost, options.currency)
-            ),
-            CostDisplayMode::Total => println!(
-                "\nCost includes estimated proxy values: {}",
-                format_cost(estimated_proxy_cost, options.currency)
-            ),
+            CostDisplayMode::RealOnly => options.markup.line(&format!(
+                "\n{}: {}",
+                options.locale.text(Msg::EstimatedProxyExcludedFromRanking),
+                format_cost(
+                    estimated_proxy_cost,
+                    total_currency.as_deref(),
+                    options.locale
+                )
+            )),
+            CostDisplayMode::Total => options.markup.line(&format!(
+                "\n{}: {}",
+                options.locale.text(Msg::EstimatedProxyIncluded),
+                format_cost(
+                    estimated_proxy_cost,
+                    total_currency.as_deref(),
+                    options.locale
+                )
+            )),
         }
     }
     if options.show_cost
         && let Some(note) = top_pricing_note(&limited)
     {
-        println!("\n{note}");
+        options.markup.line(&format!("\n{note}"));
     }
 }
 
@@ -435,6 +458,9 @@ fn top_pricing_note(rows: &[TopRow]) -> Option<String> {
         crate::pricing::PricingSource::Fallback => {
             Some("Pricing source: fallback estimates.".to_string())
         }
+        crate::pricing::PricingSource::Catalog => {
+            Some("Pricing source: local price catalog.".to_string())
+        }
         crate::pricing::PricingSource::Recorded => {
             Some("Pricing source: recorded provider cost.".to_string())
         }
@@ -477,6 +503,18 @@ pub(super) fn sum_cost(rows: &[TopRow]) -> f64 {
     total
 }
 
+/// Converter for the total of `rows`, at the rates of every row's days.
+pub(super) fn total_currency<'a>(
+    rows: &[TopRow],
+    currency: Option<&'a CurrencyConverter>,
+) -> Option<Cow<'a, CurrencyConverter>> {
+    let days: Vec<(NaiveDate, f64)> = rows
+        .iter()
+        .flat_map(|row| row.day_costs.iter().copied())
+        .collect();
+    blended_currency(currency, &days)
+}
+
 pub(super) fn sum_tokens(rows: &[TopRow]) -> i64 {
     rows.iter().fold(0_i64, |total, row| {
         total.saturating_add(row.stats.total_tokens())
@@ -486,7 +524,7 @@ pub(super) fn sum_tokens(rows: &[TopRow]) -> i64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::output::{output_top_csv, output_top_json};
+    use crate::output::{output_top_csv, top_json_report};
     use crate::pricing::{PricingDb, PricingSource};
 
     fn stats_of(input: i64, output: i64, count: i64) -> Stats {
@@ -560,6 +598,7 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             },
             TopRow {
                 name: "b".into(),
@@ -571,6 +610,7 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             },
         ];
         assert_eq!(share_basis(&rows), ShareBasis::Tokens);
@@ -591,6 +631,7 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             },
             TopRow {
                 name: "b".into(),
@@ -602,6 +643,7 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             },
         ];
         assert_eq!(share_basis(&rows), ShareBasis::Cost);
@@ -623,6 +665,7 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             },
             TopRow {
                 name: "high-cost".into(),
@@ -634,6 +677,7 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             },
             TopRow {
                 name: "low-cost".into(),
@@ -645,6 +689,7 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             },
         ];
         sort_rows(&mut rows);
@@ -666,6 +711,7 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             })
             .collect();
         let csv = output_top_csv(&rows, TopDimension::Model, 5, false, true, None);
@@ -685,9 +731,17 @@ mod tests {
             pricing_source: PricingSource::Fallback,
             pricing_cache_age_seconds: None,
             pricing_cache_mtime_epoch_seconds: None,
+            day_costs: Vec::new(),
         }];
-        let json = output_top_json(&rows, TopDimension::Model, 10, true, true, None);
-        let val: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let val: serde_json::Value = serde_json::to_value(top_json_report(
+            &rows,
+            TopDimension::Model,
+            10,
+            true,
+            true,
+            None,
+        ))
+        .unwrap();
         assert_eq!(val["dimension"], "model");
         assert_eq!(val["share_basis"], "cost");
         assert_eq!(val["entries"][0]["name"], "m");
@@ -710,6 +764,7 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             },
             TopRow {
                 name: "b".into(),
@@ -721,10 +776,18 @@ mod tests {
                 pricing_source: PricingSource::Fallback,
                 pricing_cache_age_seconds: None,
                 pricing_cache_mtime_epoch_seconds: None,
+                day_costs: Vec::new(),
             },
         ];
-        let json = output_top_json(&rows, TopDimension::Model, 10, true, true, None);
-        let val: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let val: serde_json::Value = serde_json::to_value(top_json_report(
+            &rows,
+            TopDimension::Model,
+            10,
+            true,
+            true,
+            None,
+        ))
+        .unwrap();
         assert_eq!(val["share_basis"], "tokens");
         // cost_usd should be JSON null for NaN
         assert!(val["entries"][0]["cost_usd"].is_null());
@@ -742,6 +805,7 @@ mod tests {
             pricing_source: PricingSource::Fallback,
             pricing_cache_age_seconds: None,
             pricing_cache_mtime_epoch_seconds: None,
+            day_costs: Vec::new(),
         }];
         let csv = output_top_csv(&rows, TopDimension::Project, 1, false, true, None);
         let lines: Vec<&str> = csv.lines().collect();
@@ -760,6 +824,7 @@ mod tests {
             pricing_source: PricingSource::Fallback,
             pricing_cache_age_seconds: None,
             pricing_cache_mtime_epoch_seconds: None,
+            day_costs: Vec::new(),
         }];
         let converter = CurrencyConverter::from_rate_for_test("CNY", 7.0, "CNY ");
 
@@ -1,9 +1,9 @@
 use std::fmt::Write;
 
-use serde_json::json;
-
 use crate::cli::TopDimension;
-use crate::output::format::{cache_hit_rate_csv_value, cache_hit_rate_json_value, csv_escape};
+use crate::output::command_json::{TopEntryJson, TopJson};
+use crate::output::format::{cache_hit_rate_csv_value, csv_escape};
+use crate::output::schema::{PricingJson, SCHEMA_VERSION, cache_hit_rate_amount};
 use crate::output::top::{
     ShareBasis, TopRow, share_basis, share_of, sum_cost, sum_tokens, take_top,
 };
@@ -11,85 +11,82 @@ use crate::pricing::{CurrencyConverter, PricingSource};
 
 /// JSON output. Always includes share, basis, and full stats so downstream
 /// tooling does not have to recompute them.
-pub(crate) fn output_top_json(
+pub(crate) fn top_json_report(
     rows: &[TopRow],
     dim: TopDimension,
     limit: usize,
     show_cost: bool,
     supports_cache_read: bool,
     currency: Option<&CurrencyConverter>,
-) -> String {
+) -> TopJson {
     let limited = take_top(rows, limit);
     let total_cost = sum_cost(&limited);
     let total_tokens = sum_tokens(&limited);
     let basis = share_basis(&limited);
     let include_estimated = show_cost && limited.iter().any(|row| row.estimated_cost > 0.0);
 
-    let entries: Vec<serde_json::Value> = limited
+    let entries = limited
         .iter()
         .enumerate()
         .map(|(idx, row)| {
             let share = share_of(row, total_cost, total_tokens, basis);
-            let mut obj = json!({
-                "rank": idx + 1,
-                "name": row.name,
-                "count": row.count,
-                "input_tokens": row.stats.input_tokens,
-                "output_tokens": row.stats.output_tokens,
-                "cache_creation": row.stats.cache_creation,
-                "cache_read": row.stats.cache_read,
-                "cache_hit_rate": cache_hit_rate_json_value(
-                    row.stats.cache_hit_rate(supports_cache_read)
+            let mut entry = TopEntryJson {
+                rank: idx + 1,
+                name: row.name.clone(),
+                count: row.count,
+                input_tokens: row.stats.input_tokens,
+                output_tokens: row.stats.output_tokens,
+                cache_creation: row.stats.cache_creation,
+                cache_read: row.stats.cache_read,
+                cache_hit_rate: cache_hit_rate_amount(
+                    row.stats.cache_hit_rate(supports_cache_read),
                 ),
-                "reasoning_tokens": row.stats.reasoning_tokens,
-                "total_tokens": row.stats.total_tokens(),
-                "share_percent": (share * 100.0).round() / 100.0,
-            });
+                reasoning_tokens: row.stats.reasoning_tokens,
+                total_tokens: row.stats.total_tokens(),
+                share_percent: (share * 100.0).round() / 100.0,
+                cost_usd: None,
+                pricing: None,
+                cost_local: None,
+                cost_kind: None,
+                estimated_cost_usd: None,
+                estimated_cost_local: None,
+            };
             if show_cost {
-                obj["cost_usd"] = if row.cost.is_nan() {
-                    serde_json::Value::Null
-                } else {
-                    json!((row.cost * 100_000.0).round() / 100_000.0)
+                let row_currency = row.currency(currency);
+                let local = |cost: f64| {
+                    row_currency
+                        .as_deref()
+                        .filter(|_| !cost.is_nan())
+                        .map(|conv| conv.format(cost))
                 };
-                add_pricing_source_json(&mut obj, row);
-                if let Some(conv) = currency
-                    && !row.cost.is_nan()
-                {
-                    obj["cost_local"] = json!(conv.format(row.cost));
-                }
+                entry.cost_usd = Some(rounded_usd(row.cost));
+                entry.pricing = Some(pricing_json(row));
+                entry.cost_local = local(row.cost);
                 if include_estimated {
-                    obj["cost_kind"] = json!(row.cost_kind.as_str());
-                    obj["estimated_cost_usd"] = if row.estimated_cost.is_nan() {
-                        serde_json::Value::Null
-                    } else {
-                        json!((row.estimated_cost * 100_000.0).round() / 100_000.0)
-                    };
-                    if let Some(conv) = currency
-                        && !row.estimated_cost.is_nan()
-                    {
-                        obj["estimated_cost_local"] = json!(conv.format(row.estimated_cost));
-                    }
+                    entry.cost_kind = Some(row.cost_kind.as_str());
+                    entry.estimated_cost_usd = Some(rounded_usd(row.estimated_cost));
+                    entry.estimated_cost_local = local(row.estimated_cost);
                 }
             }
-            obj
+            entry
         })
         .collect();
 
-    json!({
-        "dimension": match dim {
+    TopJson {
+        schema_version: SCHEMA_VERSION,
+        dimension: match dim {
             TopDimension::Model => "model",
             TopDimension::Project => "project",
         },
-        "limit": limit,
-        "displayed": limited.len(),
-        "total_rows": rows.len(),
-        "share_basis": match basis {
+        limit,
+        displayed: limited.len(),
+        total_rows: rows.len(),
+        share_basis: match basis {
             ShareBasis::Cost => "cost",
             ShareBasis::Tokens => "tokens",
         },
-        "entries": entries,
-    })
-    .to_string()
+        entries,
+    }
 }
 
 /// CSV output. Header columns mirror the JSON keys.
@@ -149,6 +146,8 @@ pub(crate) fn output_top_csv(
             share,
         );
         if show_cost {
+            let row_currency = row.currency(currency);
+            let currency = row_currency.as_deref();
             if row.cost.is_nan() {
                 out.push(',');
                 if currency.is_some() {
@@ -182,15 +181,20 @@ pub(crate) fn output_top_csv(
     out
 }
 
-fn add_pricing_source_json(obj: &mut serde_json::Value, row: &TopRow) {
-    obj["pricing_source"] = json!(row.pricing_source.as_str());
-    if needs_cache_fields(row) {
-        if let Some(age) = row.pricing_cache_age_seconds {
-            obj["pricing_cache_age_seconds"] = json!(age);
-        }
-        if let Some(mtime) = row.pricing_cache_mtime_epoch_seconds {
-            obj["pricing_cache_mtime_epoch_seconds"] = json!(mtime);
-        }
+/// USD rounded to five decimals; `None` (JSON `null`) when unpriced.
+fn rounded_usd(cost: f64) -> Option<f64> {
+    (!cost.is_nan()).then(|| (cost * 100_000.0).round() / 100_000.0)
+}
+
+fn pricing_json(row: &TopRow) -> PricingJson {
+    let cache_backed = needs_cache_fields(row);
+    PricingJson {
+        pricing_source: row.pricing_source.as_str(),
+        pricing_cache_age_seconds: row.pricing_cache_age_seconds.filter(|_| cache_backed),
+        pricing_cache_mtime_epoch_seconds: row
+            .pricing_cache_mtime_epoch_seconds
+            .filter(|_| cache_backed),
+        pricing_catalog: None,
     }
 }
 
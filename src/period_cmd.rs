@@ -0,0 +1,152 @@
+//! Rendering of the `daily`, `weekly`, `monthly`, and `today` reports.
+//!
+//! Lives in its own module to keep `app.rs` under the module size limit.
+
+use crate::app::{
+    CommandContext, annotate_csv_codex_scope, annotate_json_codex_scope, print_codex_scope_note,
+    print_json,
+};
+use crate::core::LoadResult;
+use crate::output::{
+    MonthlyBudgetOptions, OutputFormat, Period, PeriodSummaryFooter, TokenTableOptions,
+    append_data_quality_csv_comment, attach_monthly_budgets, monthly_budget_reports,
+    output_monthly_budget_csv, output_period_csv_with_quality, period_json_rows,
+    print_monthly_budget_table, print_ndjson, print_period_table, rows_to_string,
+};
+use crate::pricing::CostDisplayMode;
+use crate::source::{Capabilities, CodexScope};
+
+#[allow(clippy::too_many_lines)]
+pub(crate) fn render_period_result(
+    result: &LoadResult,
+    period: Period,
+    caps: &Capabilities,
+    codex_scope: Option<CodexScope>,
+    ctx: &CommandContext<'_>,
+    cost_mode: CostDisplayMode,
+) {
+    let monthly_budget = (period == Period::Month)
+        .then_some(ctx.cli.monthly_budget)
+        .flatten();
+
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => {
+            let csv = if let Some(budget) = monthly_budget {
+                let mut csv = output_monthly_budget_csv(
+                    &result.day_stats,
+                    ctx.pricing_db,
+                    MonthlyBudgetOptions {
+                        order: ctx.cli.sort_order(),
+                        breakdown: ctx.cli.breakdown,
+                        show_cost: ctx.cli.show_cost(),
+                        supports_cache_read: caps.has_cache_read,
+                        limit: budget,
+                        as_of: ctx.budget_as_of,
+                        currency: ctx.currency,
+                        cost_mode,
+                    },
+                );
+                append_data_quality_csv_comment(&mut csv, Some(result.data_quality()));
+                csv
+            } else {
+                output_period_csv_with_quality(
+                    &result.day_stats,
+                    period,
+                    ctx.pricing_db,
+                    ctx.cli.sort_order(),
+                    ctx.cli.breakdown,
+                    ctx.cli.show_cost(),
+                    caps.has_cache_read,
+                    ctx.currency,
+                    Some(result.data_quality()),
+                    cost_mode,
+                )
+            };
+            print!("{}", annotate_csv_codex_scope(csv, codex_scope));
+        }
+        OutputFormat::Json => {
+            let data_quality = result.data_quality();
+            let mut rows = period_json_rows(
+                &result.day_stats,
+                period,
+                ctx.pricing_db,
+                ctx.cli.sort_order(),
+                ctx.cli.breakdown,
+                ctx.cli.show_cost(),
+                caps.has_cache_read,
+                ctx.currency,
+                // NDJSON reports data quality once, on the last line.
+                (!ctx.cli.ndjson).then_some(data_quality),
+                cost_mode,
+            );
+            if let Some(budget) = monthly_budget {
+                let reports = monthly_budget_reports(
+                    &result.day_stats,
+                    ctx.pricing_db,
+                    ctx.cli.sort_order(),
+                    budget,
+                    ctx.budget_as_of,
+                    ctx.currency,
+                    cost_mode,
+                );
+                attach_monthly_budgets(&mut rows, &reports);
+            }
+            if ctx.cli.ndjson {
+                let scope = codex_scope.map(|scope| ("codex_scope", scope.as_str()));
+                print_ndjson(scope, |out| {
+                    out.rows(&rows)?;
+                    out.data_quality(data_quality)
+                });
+            } else {
+                let json = annotate_json_codex_scope(&rows_to_string(&rows, false), codex_scope);
+                print_json(&json, ctx);
+            }
+        }
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            let markup = ctx.cli.output_format().markup();
+            print_codex_scope_note(codex_scope, markup);
+            print_period_table(
+                &result.day_stats,
+                period,
+                ctx.cli.breakdown,
+                PeriodSummaryFooter {
+                    skipped: result.skipped,
+                    valid: result.valid,
+                    elapsed_ms: Some(result.elapsed_ms),
+                },
+                ctx.pricing_db,
+                TokenTableOptions {
+                    order: ctx.cli.sort_order(),
+                    use_color: ctx.cli.use_color(),
+                    compact: ctx.cli.compact,
+                    show_cost: ctx.cli.show_cost(),
+                    locale: ctx.locale,
+                    show_reasoning: caps.has_reasoning_tokens,
+                    show_cache_creation: caps.has_cache_creation,
+                    supports_cache_read: caps.has_cache_read,
+                    currency: ctx.currency,
+                    cost_mode,
+                    markup,
+                },
+            );
+            if let Some(budget) = monthly_budget {
+                let reports = monthly_budget_reports(
+                    &result.day_stats,
+                    ctx.pricing_db,
+                    ctx.cli.sort_order(),
+                    budget,
+                    ctx.budget_as_of,
+                    ctx.currency,
+                    cost_mode,
+                );
+                print_monthly_budget_table(
+                    &reports,
+                    ctx.cli.use_color(),
+                    ctx.currency,
+                    ctx.locale,
+                    markup,
+                );
+            }
+        }
+    }
+}
@@ -4,6 +4,8 @@ use std::io::{BufWriter, ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use serde::Serialize;
+
 use super::source::CacheMetadata;
 
 const APP_CACHE_DIR: &str = "ccstats";
@@ -25,20 +27,22 @@ struct CachePaths {
     read_paths: Vec<PathBuf>,
 }
 
-fn pricing_cache_file(root: &Path) -> PathBuf {
-    root.join(APP_CACHE_DIR).join(PRICING_CACHE_FILE)
+fn pricing_cache_file(root: &Path, file_name: &str) -> PathBuf {
+    root.join(APP_CACHE_DIR).join(file_name)
 }
 
-fn legacy_cache_file(home_dir: &Path) -> PathBuf {
-    home_dir
-        .join(".cache")
-        .join(APP_CACHE_DIR)
-        .join(PRICING_CACHE_FILE)
+fn legacy_cache_file(home_dir: &Path, file_name: &str) -> PathBuf {
+    home_dir.join(".cache").join(APP_CACHE_DIR).join(file_name)
 }
 
-fn select_cache_paths(platform_cache_dir: Option<&Path>, home_dir: Option<&Path>) -> CachePaths {
-    let preferred_path = platform_cache_dir.map(pricing_cache_file);
-    let legacy_path = home_dir.map(legacy_cache_file);
+/// Read and write locations of the cache file `file_name`.
+fn select_cache_paths(
+    platform_cache_dir: Option<&Path>,
+    home_dir: Option<&Path>,
+    file_name: &str,
+) -> CachePaths {
+    let preferred_path = platform_cache_dir.map(|root| pricing_cache_file(root, file_name));
+    let legacy_path = home_dir.map(|home| legacy_cache_file(home, file_name));
     let write_path = preferred_path.clone().or_else(|| legacy_path.clone());
 
     let mut read_paths = Vec::new();
@@ -57,10 +61,14 @@ fn select_cache_paths(platform_cache_dir: Option<&Path>, home_dir: Option<&Path>
     }
 }
 
-fn cache_paths() -> CachePaths {
+fn cache_paths(file_name: &str) -> CachePaths {
     let platform_cache_dir = dirs::cache_dir();
     let home_dir = dirs::home_dir();
-    select_cache_paths(platform_cache_dir.as_deref(), home_dir.as_deref())
+    select_cache_paths(
+        platform_cache_dir.as_deref(),
+        home_dir.as_deref(),
+        file_name,
+    )
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -92,38 +100,38 @@ pub(crate) enum CacheReadError {
 }
 
 #[derive(Debug, thiserror::Error)]
-pub(super) enum CacheWriteError {
-    #[error("failed to create pricing cache directory {path:?}: {source}")]
+pub(crate) enum CacheWriteError {
+    #[error("failed to create cache directory {path:?}: {source}")]
     CreateDirectory {
         path: PathBuf,
         #[source]
         source: std::io::Error,
     },
-    #[error("failed to create temporary pricing cache {path:?}: {source}")]
+    #[error("failed to create temporary cache file {path:?}: {source}")]
     CreateTemp {
         path: PathBuf,
         #[source]
         source: std::io::Error,
     },
-    #[error("failed to serialize pricing cache to {path:?}: {source}")]
+    #[error("failed to serialize cache file {path:?}: {source}")]
     Serialize {
         path: PathBuf,
         #[source]
         source: serde_json::Error,
     },
-    #[error("failed to flush temporary pricing cache {path:?}: {source}")]
+    #[error("failed to flush temporary cache file {path:?}: {source}")]
     Flush {
         path: PathBuf,
         #[source]
         source: std::io::Error,
     },
-    #[error("failed to sync temporary pricing cache {path:?}: {source}")]
+    #[error("failed to sync temporary cache file {path:?}: {source}")]
     Sync {
         path: PathBuf,
         #[source]
         source: std::io::Error,
     },
-    #[error("failed to replace pricing cache {target:?} with {temp:?}: {source}")]
+    #[error("failed to replace cache file {target:?} with {temp:?}: {source}")]
     Rename {
         temp: PathBuf,
         target: PathBuf,
@@ -132,8 +140,9 @@ pub(super) enum CacheWriteError {
     },
 }
 
-pub(super) fn get_cache_path() -> Option<PathBuf> {
-    cache_paths().write_path
+/// Where the ccstats cache file `file_name` is written.
+pub(crate) fn get_cache_path(file_name: &str) -> Option<PathBuf> {
+    cache_paths(file_name).write_path
 }
 
 #[cfg(test)]
@@ -218,20 +227,24 @@ pub(super) fn load_raw_cache_if_fresh_from_paths(
     Ok(None)
 }
 
-pub(super) fn load_raw_cache_snapshot() -> CacheReadResult<RawPricingCacheSnapshot> {
-    let paths = cache_paths();
+pub(super) fn load_raw_cache_snapshot(file_name: &str) -> CacheReadResult<RawPricingCacheSnapshot> {
+    let paths = cache_paths(file_name);
     load_raw_cache_snapshot_from_paths(&paths.read_paths)
 }
 
-pub(super) fn load_raw_cache_if_fresh(ttl: Duration) -> CacheReadResult<FreshRawPricingCache> {
-    let paths = cache_paths();
+pub(super) fn load_raw_cache_if_fresh(
+    file_name: &str,
+    ttl: Duration,
+) -> CacheReadResult<FreshRawPricingCache> {
+    let paths = cache_paths(file_name);
     load_raw_cache_if_fresh_from_paths(&paths.read_paths, ttl)
 }
 
 pub(super) fn save_raw_cache(
+    file_name: &str,
     raw_data: &HashMap<String, serde_json::Value>,
 ) -> Result<(), CacheWriteError> {
-    let Some(path) = get_cache_path() else {
+    let Some(path) = get_cache_path(file_name) else {
         return Ok(());
     };
     save_raw_cache_to_path(raw_data, &path)
@@ -240,6 +253,15 @@ pub(super) fn save_raw_cache(
 pub(super) fn save_raw_cache_to_path(
     raw_data: &HashMap<String, serde_json::Value>,
     path: &Path,
+) -> Result<(), CacheWriteError> {
+    write_json_atomically(raw_data, path)
+}
+
+/// Write `value` as JSON to `path` through a synced temporary file and a
+/// rename, so readers never observe a partially written file.
+pub(crate) fn write_json_atomically<T: Serialize + ?Sized>(
+    value: &T,
+    path: &Path,
 ) -> Result<(), CacheWriteError> {
     let parent = path
         .parent()
@@ -250,8 +272,11 @@ pub(super) fn save_raw_cache_to_path(
         source,
     })?;
 
-    let (temp_path, temp_file) = create_temp_file(parent)?;
-    let write_result = write_cache_file(raw_data, &temp_path, temp_file);
+    let file_name = path
+        .file_name()
+        .map_or_else(|| PRICING_CACHE_FILE.into(), |name| name.to_string_lossy());
+    let (temp_path, temp_file) = create_temp_file(parent, &file_name)?;
+    let write_result = write_json_file(value, &temp_path, temp_file);
     if let Err(error) = write_result {
         let _ = fs::remove_file(&temp_path);
         return Err(error);
@@ -268,7 +293,7 @@ pub(super) fn save_raw_cache_to_path(
     })
 }
 
-fn create_temp_file(parent: &Path) -> Result<(PathBuf, File), CacheWriteError> {
+fn create_temp_file(parent: &Path, file_name: &str) -> Result<(PathBuf, File), CacheWriteError> {
     let nanos = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or(Duration::ZERO)
@@ -276,9 +301,7 @@ fn create_temp_file(parent: &Path) -> Result<(PathBuf, File), CacheWriteError> {
     let process_id = std::process::id();
 
     for attempt in 0..32 {
-        let temp_path = parent.join(format!(
-            ".{PRICING_CACHE_FILE}.{process_id}.{nanos}.{attempt}.tmp"
-        ));
+        let temp_path = parent.join(format!(".{file_name}.{process_id}.{nanos}.{attempt}.tmp"));
         match OpenOptions::new()
             .write(true)
             .create_new(true)
@@ -295,23 +318,23 @@ fn create_temp_file(parent: &Path) -> Result<(PathBuf, File), CacheWriteError> {
         }
     }
 
-    let temp_path = parent.join(format!(".{PRICING_CACHE_FILE}.{process_id}.{nanos}.tmp"));
+    let temp_path = parent.join(format!(".{file_name}.{process_id}.{nanos}.tmp"));
     Err(CacheWriteError::CreateTemp {
         path: temp_path,
         source: std::io::Error::new(
             ErrorKind::AlreadyExists,
-            "unable to allocate unique pricing cache temporary file",
+            "unable to allocate unique cache temporary file",
         ),
     })
 }
 
-fn write_cache_file(
-    raw_data: &HashMap<String, serde_json::Value>,
+fn write_json_file<T: Serialize + ?Sized>(
+    value: &T,
     temp_path: &Path,
     temp_file: File,
 ) -> Result<(), CacheWriteError> {
     let mut writer = BufWriter::new(temp_file);
-    serde_json::to_writer(&mut writer, raw_data).map_err(|source| CacheWriteError::Serialize {
+    serde_json::to_writer(&mut writer, value).map_err(|source| CacheWriteError::Serialize {
         path: temp_path.to_path_buf(),
         source,
     })?;
@@ -343,7 +366,11 @@ mod tests {
     fn cache_paths_prefer_platform_cache_dir() {
         let platform = PathBuf::from("platform-cache");
         let home = PathBuf::from("home-dir");
-        let paths = select_cache_paths(Some(platform.as_path()), Some(home.as_path()));
+        let paths = select_cache_paths(
+            Some(platform.as_path()),
+            Some(home.as_path()),
+            PRICING_CACHE_FILE,
+        );
 
         assert_eq!(
             paths.write_path,
@@ -366,7 +393,7 @@ mod tests {
     #[test]
     fn cache_paths_use_legacy_as_explicit_fallback() {
         let home = PathBuf::from("home-dir");
-        let paths = select_cache_paths(None, Some(home.as_path()));
+        let paths = select_cache_paths(None, Some(home.as_path()), PRICING_CACHE_FILE);
         let legacy_path = home
             .join(".cache")
             .join(APP_CACHE_DIR)
@@ -380,8 +407,12 @@ mod tests {
     fn load_raw_cache_reads_legacy_when_preferred_absent() {
         let platform_root = TempDir::new().unwrap();
         let home_root = TempDir::new().unwrap();
-        let paths = select_cache_paths(Some(platform_root.path()), Some(home_root.path()));
-        let legacy_path = legacy_cache_file(home_root.path());
+        let paths = select_cache_paths(
+            Some(platform_root.path()),
+            Some(home_root.path()),
+            PRICING_CACHE_FILE,
+        );
+        let legacy_path = legacy_cache_file(home_root.path(), PRICING_CACHE_FILE);
         save_raw_cache_to_path(&sample_raw_data("legacy-model"), &legacy_path).unwrap();
 
         let data = load_raw_cache_from_paths(&paths.read_paths)
@@ -395,9 +426,13 @@ mod tests {
     fn load_raw_cache_prefers_current_when_both_exist() {
         let platform_root = TempDir::new().unwrap();
         let home_root = TempDir::new().unwrap();
-        let paths = select_cache_paths(Some(platform_root.path()), Some(home_root.path()));
+        let paths = select_cache_paths(
+            Some(platform_root.path()),
+            Some(home_root.path()),
+            PRICING_CACHE_FILE,
+        );
         let current_path = paths.write_path.as_ref().unwrap();
-        let legacy_path = legacy_cache_file(home_root.path());
+        let legacy_path = legacy_cache_file(home_root.path(), PRICING_CACHE_FILE);
         save_raw_cache_to_path(&sample_raw_data("current-model"), current_path).unwrap();
         save_raw_cache_to_path(&sample_raw_data("legacy-model"), &legacy_path).unwrap();
 
@@ -413,8 +448,12 @@ mod tests {
     fn load_raw_cache_if_fresh_reads_legacy_when_preferred_absent() {
         let platform_root = TempDir::new().unwrap();
         let home_root = TempDir::new().unwrap();
-        let paths = select_cache_paths(Some(platform_root.path()), Some(home_root.path()));
-        let legacy_path = legacy_cache_file(home_root.path());
+        let paths = select_cache_paths(
+            Some(platform_root.path()),
+            Some(home_root.path()),
+            PRICING_CACHE_FILE,
+        );
+        let legacy_path = legacy_cache_file(home_root.path(), PRICING_CACHE_FILE);
         save_raw_cache_to_path(&sample_raw_data("legacy-model"), &legacy_path).unwrap();
 
         let snapshot =
@@ -429,9 +468,13 @@ mod tests {
     fn save_raw_cache_writes_current_path_not_legacy() {
         let platform_root = TempDir::new().unwrap();
         let home_root = TempDir::new().unwrap();
-        let paths = select_cache_paths(Some(platform_root.path()), Some(home_root.path()));
+        let paths = select_cache_paths(
+            Some(platform_root.path()),
+            Some(home_root.path()),
+            PRICING_CACHE_FILE,
+        );
         let current_path = paths.write_path.as_ref().unwrap();
-        let legacy_path = legacy_cache_file(home_root.path());
+        let legacy_path = legacy_cache_file(home_root.path(), PRICING_CACHE_FILE);
 
         save_raw_cache_to_path(&sample_raw_data("current-model"), current_path).unwrap();
 
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
-use crate::core::{CostKind, CostTokens, Stats};
+use crate::core::{CostKind, CostTokens, PromptSizeTokens, ServiceTier, ServiceTierTokens, Stats};
 
 use super::db::PricingDb;
 use super::source::PricingSource;
+use super::types::ModelPricing;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum CostDisplayMode {
@@ -11,26 +12,121 @@ pub(crate) enum CostDisplayMode {
     RealOnly,
 }
 
-fn calculate_token_cost(tokens: CostTokens, model: &str, pricing_db: &PricingDb) -> f64 {
+fn rated_cost(tokens: &CostTokens, pricing: &ModelPricing) -> f64 {
+    let long_ttl_tokens = tokens.cache_creation_1h.min(tokens.cache_creation);
+    let short_ttl_tokens = tokens.cache_creation - long_ttl_tokens;
+    tokens.input_tokens as f64 * pricing.input
+        + tokens.output_tokens as f64 * pricing.output
+        + tokens.reasoning_tokens as f64 * pricing.reasoning_output
+        + short_ttl_tokens as f64 * pricing.cache_create
+        + long_ttl_tokens as f64 * pricing.cache_create_1h
+        + tokens.cache_read as f64 * pricing.cache_read
+}
+
+/// Prompt-size split of a token subset, borrowed from its [`Stats`]: the
+/// requests of `sizes` without those of `excluded`.
+#[derive(Clone, Copy)]
+struct PromptSizes<'a> {
+    sizes: &'a PromptSizeTokens,
+    excluded: &'a [&'a PromptSizeTokens],
+}
+
+impl<'a> PromptSizes<'a> {
+    fn all(sizes: &'a PromptSizeTokens) -> Self {
+        Self {
+            sizes,
+            excluded: &[],
+        }
+    }
+
+    /// Tokens of the subset's requests whose prompt exceeded `threshold`.
+    fn above(self, threshold: i64) -> CostTokens {
+        let tokens = self
+            .excluded
+            .iter()
+            .fold(self.sizes.above(threshold), |tokens, excluded| {
+                tokens.saturating_sub(&excluded.above(threshold))
+            });
+        CostTokens::from(tokens)
+    }
+}
+
+/// Tiers apply per request: tokens of requests above the model's
+/// long-context threshold are billed at the tier rates.
+fn priced_cost(
+    tokens: &CostTokens,
+    long_context: impl Fn(i64) -> CostTokens,
+    pricing: &ModelPricing,
+) -> f64 {
+    let Some(tier) = pricing.long_context.as_deref() else {
+        return rated_cost(tokens, pricing);
+    };
+    let long_context = long_context(tier.threshold);
+    rated_cost(&tokens.saturating_sub(&long_context), pricing)
+        + rated_cost(&long_context, &tier.rates)
+}
+
+/// Cost of `tokens`, of which `service_tiers` were billed under non-standard
+/// service tiers and the rest at standard rates.
+fn calculate_tiered_token_cost(
+    tokens: &CostTokens,
+    sizes: PromptSizes<'_>,
+    service_tiers: &ServiceTierTokens,
+    model: &str,
+    pricing_db: &PricingDb,
+) -> f64 {
     if !tokens.has_entries() {
         return 0.0;
     }
     match pricing_db.get_pricing(model) {
         Some(pricing) => {
-            let long_ttl_tokens = tokens.cache_creation_1h.min(tokens.cache_creation);
-            let short_ttl_tokens = tokens.cache_creation - long_ttl_tokens;
-            tokens.input_tokens as f64 * pricing.input
-                + tokens.output_tokens as f64 * pricing.output
-                + tokens.reasoning_tokens as f64 * pricing.reasoning_output
-                + short_ttl_tokens as f64 * pricing.cache_create
-                + long_ttl_tokens as f64 * pricing.cache_create_1h
-                + tokens.cache_read as f64 * pricing.cache_read
+            let mut standard = *tokens;
+            let mut cost = 0.0;
+            for (tier, tier_tokens) in service_tiers.iter() {
+                standard = standard.saturating_sub(&tier_tokens);
+                let tier_sizes = PromptSizes::all(service_tiers.prompt_sizes(tier));
+                cost += priced_cost(
+                    &tier_tokens,
+                    |threshold| tier_sizes.above(threshold),
+                    &pricing.for_service_tier(tier),
+                );
+            }
+            let standard_long_context = |threshold| {
+                service_tiers
+                    .iter()
+                    .fold(sizes.above(threshold), |tokens, (tier, _)| {
+                        let tier_sizes = PromptSizes::all(service_tiers.prompt_sizes(tier));
+                        tokens.saturating_sub(&tier_sizes.above(threshold))
+                    })
+            };
+            cost + priced_cost(&standard, standard_long_context, &pricing)
         }
         None => f64::NAN,
     }
 }
 
-fn combine_recorded_and_priced(recorded_usd: f64, priced: f64, priced_tokens: CostTokens) -> f64 {
+/// Cost of the tokens a model billed under one service tier, for breakdowns.
+pub(crate) fn calculate_service_tier_cost(
+    stats: &Stats,
+    tier: ServiceTier,
+    model: &str,
+    pricing_db: &PricingDb,
+) -> f64 {
+    let tokens = stats.service_tiers.get(tier);
+    if !tokens.has_entries() {
+        return 0.0;
+    }
+    let sizes = PromptSizes::all(stats.service_tiers.prompt_sizes(tier));
+    pricing_db.get_pricing(model).map_or(f64::NAN, |pricing| {
+        priced_cost(
+            &tokens,
+            |threshold| sizes.above(threshold),
+            &pricing.for_service_tier(tier),
+        )
+    })
+}
+
+fn combine_recorded_and_priced(recorded_usd: f64, priced: f64, priced_tokens: &CostTokens) -> f64 {
     if priced.is_nan() {
         if priced_tokens.has_entries() && recorded_usd == 0.0 {
             f64::NAN
@@ -44,26 +140,65 @@ fn combine_recorded_and_priced(recorded_usd: f64, priced: f64, priced_tokens: Co
 
 pub(crate) fn calculate_cost(stats: &Stats, model: &str, pricing_db: &PricingDb) -> f64 {
     if stats.recorded_cost_entries > 0 {
+        let sizes = PromptSizes {
+            sizes: &stats.by_prompt_size,
+            excluded: &[&stats.recorded_by_prompt_size],
+        };
         combine_recorded_and_priced(
             stats.recorded_cost_usd,
-            calculate_token_cost(stats.priced_tokens, model, pricing_db),
-            stats.priced_tokens,
+            calculate_tiered_token_cost(
+                &stats.priced_tokens,
+                sizes,
+                &stats.service_tiers,
+                model,
+                pricing_db,
+            ),
+            &stats.priced_tokens,
         )
     } else {
-        calculate_token_cost(stats.cost_tokens(), model, pricing_db)
+        calculate_tiered_token_cost(
+            &stats.cost_tokens(),
+            PromptSizes::all(&stats.by_prompt_size),
+            &stats.service_tiers,
+            model,
+            pricing_db,
+        )
     }
 }
 
 pub(crate) fn calculate_real_cost(stats: &Stats, model: &str, pricing_db: &PricingDb) -> f64 {
     if stats.recorded_cost_entries > 0 {
         let priced_real = stats.priced_tokens.saturating_sub(&stats.estimated_proxy);
+        let sizes = PromptSizes {
+            sizes: &stats.by_prompt_size,
+            excluded: &[
+                &stats.recorded_by_prompt_size,
+                &stats.estimated_proxy_by_prompt_size,
+            ],
+        };
         combine_recorded_and_priced(
             stats.recorded_cost_usd,
-            calculate_token_cost(priced_real, model, pricing_db),
-            priced_real,
+            calculate_tiered_token_cost(
+                &priced_real,
+                sizes,
+                &stats.service_tiers,
+                model,
+                pricing_db,
+            ),
+            &priced_real,
         )
     } else {
-        calculate_token_cost(stats.real_cost_tokens(), model, pricing_db)
+        let sizes = PromptSizes {
+            sizes: &stats.by_prompt_size,
+            excluded: &[&stats.estimated_proxy_by_prompt_size],
+        };
+        calculate_tiered_token_cost(
+            &stats.real_cost_tokens(),
+            sizes,
+            &stats.service_tiers,
+            model,
+            pricing_db,
+        )
     }
 }
 
@@ -72,7 +207,13 @@ pub(crate) fn calculate_estimated_proxy_cost(
     model: &str,
     pricing_db: &PricingDb,
 ) -> f64 {
-    calculate_token_cost(stats.estimated_proxy, model, pricing_db)
+    calculate_tiered_token_cost(
+        &stats.estimated_proxy,
+        PromptSizes::all(&stats.estimated_proxy_by_prompt_size),
+        &ServiceTierTokens::default(),
+        model,
+        pricing_db,
+    )
 }
 
 pub(crate) fn calculate_display_cost(
@@ -105,7 +246,12 @@ pub(crate) fn sum_estimated_proxy_model_costs(
     models: &HashMap<String, Stats>,
     pricing_db: &PricingDb,
 ) -> f64 {
-    sum_token_costs_by(models, pricing_db, |stats| stats.estimated_proxy)
+    sum_token_costs_by(
+        models,
+        pricing_db,
+        |stats| &stats.estimated_proxy,
+        calculate_estimated_proxy_cost,
+    )
 }
 
 pub(crate) fn sum_display_model_costs(
@@ -160,7 +306,8 @@ fn sum_model_costs_by(
 fn sum_token_costs_by(
     models: &HashMap<String, Stats>,
     pricing_db: &PricingDb,
-    tokens_of: impl Fn(&Stats) -> CostTokens,
+    tokens_of: impl Fn(&Stats) -> &CostTokens,
+    cost_of: impl Fn(&Stats, &str, &PricingDb) -> f64,
 ) -> f64 {
     if models.is_empty() {
         return 0.0;
@@ -174,7 +321,7 @@ fn sum_token_costs_by(
             continue;
         }
         any_entries = true;
-        let cost = calculate_token_cost(tokens, model, pricing_db);
+        let cost = cost_of(stats, model, pricing_db);
         if cost.is_nan() {
             continue;
         }
@@ -299,7 +446,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::CostTokens;
+    use crate::core::{CostTokens, PromptSizeTokens, RequestTokens};
 
     fn pricing_db_with(model: &str, pricing: super::super::types::ModelPricing) -> PricingDb {
         let mut db = PricingDb::default();
@@ -315,9 +462,77 @@ mod tests {
             cache_create: 1.25e-5,
             cache_create_1h: 2e-5,
             cache_read: 1e-6,
+            long_context: None,
+            service_tiers: Vec::new(),
         }
     }
 
+    #[test]
+    fn long_context_requests_are_billed_at_tier_rates() {
+        let mut pricing = fable_pricing();
+        pricing.long_context = Some(Box::new(super::super::types::LongContextTier {
+            threshold: 272_000,
+            rates: super::super::types::ModelPricing {
+                input: 2e-5,
+                output: 1e-4,
+                ..fable_pricing()
+            },
+        }));
+        let db = pricing_db_with("fable-5", pricing);
+        let request = |input_tokens, output_tokens| Stats {
+            input_tokens,
+            output_tokens,
+            count: 1,
+            by_prompt_size: PromptSizeTokens::request(
+                input_tokens,
+                RequestTokens {
+                    input_tokens,
+                    output_tokens,
+                    ..Default::default()
+                },
+            ),
+            ..Default::default()
+        };
+        let mut stats = request(100_000, 10_000);
+        stats.add(&request(300_000, 10_000));
+        // Above 200K but within this model's 272K threshold.
+        let mid = request(250_000, 0);
+
+        // Short request: 100K * $10/M + 10K * $50/M = $1.5
+        // Long request: 300K * $20/M + 10K * $100/M = $7
+        assert!((calculate_cost(&stats, "fable-5", &db) - 8.5).abs() < 1e-9);
+        assert!((calculate_cost(&mid, "fable-5", &db) - 2.5).abs() < 1e-9);
+        // Without a tier every token is billed at the base rates.
+        let flat = pricing_db_with("fable-5", fable_pricing());
+        assert!((calculate_cost(&stats, "fable-5", &flat) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn service_tier_tokens_are_billed_at_tier_rates() {
+        let mut pricing = fable_pricing();
+        pricing.service_tiers = vec![(
+            ServiceTier::Priority,
+            super::super::types::ModelPricing {
+                input: 2e-5,
+                ..fable_pricing()
+            },
+        )];
+        let db = pricing_db_with("fable-5", pricing);
+        let mut stats = Stats {
+            input_tokens: 300_000,
+            count: 3,
+            ..Default::default()
+        };
+        stats.service_tiers.batch.input_tokens = 100_000;
+        stats.service_tiers.priority.input_tokens = 100_000;
+
+        // Standard 100K * $10/M + batch 100K * $5/M + priority 100K * $20/M
+        assert!((calculate_cost(&stats, "fable-5", &db) - 3.5).abs() < 1e-9);
+        assert!((calculate_real_cost(&stats, "fable-5", &db) - 3.5).abs() < 1e-9);
+        let batch = calculate_service_tier_cost(&stats, ServiceTier::Batch, "fable-5", &db);
+        assert!((batch - 0.5).abs() < 1e-9);
+    }
+
     #[test]
     fn calculate_cost_with_1h_cache_creation() {
         let db = pricing_db_with("fable-5", fable_pricing());
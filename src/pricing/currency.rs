@@ -1,50 +1,179 @@
 //! Currency conversion with exchange rate caching
 //!
 //! Fetches rates from open.er-api.com (free, no API key required).
-//! Caches to `~/.cache/ccstats/exchange_rates.json` for 24h.
+//! Caches to `~/.cache/ccstats/exchange_rates.json` for 24h, and keeps one
+//! snapshot per day in `exchange_rate_history.json` in the ccstats cache
+//! directory so past days convert at the rate of their own day. The
+//! `[exchange_rates]` config table can pin a fixed rate or point at a local
+//! daily rates file instead.
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
+use super::cache::{CacheWriteError, get_cache_path, write_json_atomically};
+use crate::consts::DATE_FORMAT;
+
 const EXCHANGE_RATE_URL: &str = "https://open.er-api.com/v6/latest/USD";
 const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const HISTORY_FILE: &str = "exchange_rate_history.json";
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ExchangeRateResponse {
     rates: HashMap<String, f64>,
 }
 
+/// Daily rates keyed by date, then by currency code.
+type DailyRates = BTreeMap<String, HashMap<String, f64>>;
+
+/// `[exchange_rates]` config table.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct ExchangeRateConfig {
+    /// Fixed rates (units per USD) by currency code, applied to every day.
+    #[serde(default)]
+    pub(crate) fixed_rates: HashMap<String, f64>,
+    /// Daily rates file: CSV `date,currency,rate` rows or JSON
+    /// `{ "YYYY-MM-DD": { "EUR": 0.92 } }`.
+    #[serde(default)]
+    pub(crate) rates_file: Option<PathBuf>,
+}
+
+/// Where a converter's exchange rates came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RateSource {
+    /// USD to USD; no rate involved.
+    Identity,
+    /// A fixed rate from `[exchange_rates] fixed_rates`.
+    Fixed,
+    /// Daily rates from `[exchange_rates] rates_file`.
+    File,
+    /// Daily snapshots recorded each time rates were fetched.
+    Snapshots,
+    /// The latest fetched (or cached) rate, applied to every day.
+    Latest,
+}
+
+impl RateSource {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Identity => "identity",
+            Self::Fixed => "fixed",
+            Self::File => "file",
+            Self::Snapshots => "snapshots",
+            Self::Latest => "latest",
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum CurrencyError {
+    #[error("failed to load exchange rate for '{code}'")]
+    Unavailable { code: String },
+    #[error("fixed exchange rate for '{code}' must be a positive number, got {rate}")]
+    InvalidFixedRate { code: String, rate: f64 },
+    #[error("failed to read exchange rates file {path:?}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("exchange rates file {path:?} is malformed: {message}")]
+    Malformed { path: PathBuf, message: String },
+    #[error("exchange rates file {path:?} has no rates for '{code}'")]
+    MissingCurrency { path: PathBuf, code: String },
+}
+
 /// Currency converter with cached exchange rates
 #[derive(Debug, Clone)]
 pub(crate) struct CurrencyConverter {
     currency: String,
+    /// Rate for views that are not split by day: the latest known rate.
     rate: f64,
     symbol: String,
+    source: RateSource,
+    /// Daily rates for per-day conversion; `None` applies `rate` everywhere.
+    history: Option<Arc<BTreeMap<NaiveDate, f64>>>,
 }
 
 impl CurrencyConverter {
     /// Load converter for the given currency code (e.g., "CNY", "EUR")
     pub(crate) fn load(currency: &str, offline: bool) -> Option<Self> {
+        Self::load_with(currency, offline, &ExchangeRateConfig::default()).ok()
+    }
+
+    /// Load converter honoring the `[exchange_rates]` config: a fixed rate
+    /// wins over a rates file, which wins over fetched rates.
+    pub(crate) fn load_with(
+        currency: &str,
+        offline: bool,
+        config: &ExchangeRateConfig,
+    ) -> Result<Self, CurrencyError> {
         let upper = currency.to_uppercase();
         if upper == "USD" {
-            return Some(Self {
-                currency: "USD".to_string(),
-                rate: 1.0,
-                symbol: "$".to_string(),
+            return Ok(Self::fixed("USD", 1.0, RateSource::Identity));
+        }
+
+        let fixed = config
+            .fixed_rates
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(&upper));
+        if let Some((_, &rate)) = fixed {
+            if !(rate.is_finite() && rate > 0.0) {
+                return Err(CurrencyError::InvalidFixedRate { code: upper, rate });
+            }
+            return Ok(Self::fixed(&upper, rate, RateSource::Fixed));
+        }
+
+        if let Some(path) = &config.rates_file {
+            let history = rates_for_currency(&read_rates_file(path)?, &upper);
+            return Self::historical(&upper, history, RateSource::File).ok_or_else(|| {
+                CurrencyError::MissingCurrency {
+                    path: path.clone(),
+                    code: upper,
+                }
             });
         }
 
-        let rates = load_rates(offline)?;
-        let rate = rates.get(&upper)?;
-        let symbol = currency_symbol(&upper);
+        let unavailable = || CurrencyError::Unavailable {
+            code: upper.clone(),
+        };
+        let rates = load_rates(offline).ok_or_else(unavailable)?;
+        let rate = *rates.get(&upper).ok_or_else(unavailable)?;
+        let snapshots =
+            load_snapshots().map_or_else(BTreeMap::new, |daily| rates_for_currency(&daily, &upper));
+        Ok(
+            Self::historical(&upper, snapshots, RateSource::Snapshots).map_or_else(
+                || Self::fixed(&upper, rate, RateSource::Latest),
+                |converter| Self { rate, ..converter },
+            ),
+        )
+    }
 
+    fn fixed(currency: &str, rate: f64, source: RateSource) -> Self {
+        Self {
+            currency: currency.to_string(),
+            rate,
+            symbol: currency_symbol(currency),
+            source,
+            history: None,
+        }
+    }
+
+    /// Converter over daily rates, using the latest day as its plain rate.
+    /// `None` when the table is empty.
+    fn historical(
+        currency: &str,
+        history: BTreeMap<NaiveDate, f64>,
+        source: RateSource,
+    ) -> Option<Self> {
+        let (_, &rate) = history.last_key_value()?;
         Some(Self {
-            currency: upper,
-            rate: *rate,
-            symbol,
+            history: Some(Arc::new(history)),
+            ..Self::fixed(currency, rate, source)
         })
     }
 
@@ -62,21 +191,97 @@ impl CurrencyConverter {
         if converted.is_nan() {
             return "N/A".to_string();
         }
-        format!("{}{converted:.2}", self.symbol)
+        format!("{}{converted:.*}", self.symbol, self.decimals())
+    }
+
+    pub(crate) fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Decimal places amounts in this currency are shown with.
+    pub(crate) fn decimals(&self) -> usize {
+        match self.currency.as_str() {
+            "JPY" | "KRW" | "VND" | "CLP" | "ISK" => 0,
+            _ => 2,
+        }
     }
 
     pub(crate) fn currency_code(&self) -> &str {
         &self.currency
     }
 
+    pub(crate) fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub(crate) fn rate_source(&self) -> RateSource {
+        self.source
+    }
+
+    /// Whether costs should be converted day by day.
+    pub(crate) fn is_historical(&self) -> bool {
+        self.history.is_some()
+    }
+
+    /// Rate for a day: the closest table entry on or before it, else the
+    /// earliest entry.
+    pub(crate) fn rate_on(&self, date: NaiveDate) -> f64 {
+        let Some(history) = &self.history else {
+            return self.rate;
+        };
+        history
+            .range(..=date)
+            .next_back()
+            .or_else(|| history.first_key_value())
+            .map_or(self.rate, |(_, &rate)| rate)
+    }
+
+    /// Converter with one rate for a span of days: each day's rate weighted
+    /// by that day's USD cost, so converting the span total equals the sum of
+    /// the converted days.
+    pub(crate) fn blended(&self, days: &[(NaiveDate, f64)]) -> Self {
+        let mut weighted = 0.0;
+        let mut total = 0.0;
+        for &(date, usd) in days {
+            if usd.is_finite() {
+                weighted += usd * self.rate_on(date);
+                total += usd;
+            }
+        }
+        let rate = if total > 0.0 {
+            weighted / total
+        } else {
+            days.iter()
+                .map(|&(date, _)| date)
+                .max()
+                .map_or(self.rate, |date| self.rate_on(date))
+        };
+        Self {
+            rate,
+            history: None,
+            ..self.clone()
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn from_rate_for_test(currency: &str, rate: f64, symbol: &str) -> Self {
         Self {
             currency: currency.to_string(),
             rate,
             symbol: symbol.to_string(),
+            source: RateSource::Latest,
+            history: None,
         }
     }
+
+    #[cfg(test)]
+    pub(crate) fn from_history_for_test(currency: &str, rates: &[(&str, f64)]) -> Self {
+        let history = rates
+            .iter()
+            .map(|&(date, rate)| (NaiveDate::parse_from_str(date, DATE_FORMAT).unwrap(), rate))
+            .collect();
+        Self::historical(currency, history, RateSource::File).unwrap()
+    }
 }
 
 fn currency_symbol(code: &str) -> String {
@@ -139,6 +344,123 @@ fn save_cached_rates(rates: &HashMap<String, f64>) {
     }
 }
 
+fn history_path() -> Option<PathBuf> {
+    get_cache_path(HISTORY_FILE)
+}
+
+fn load_snapshots() -> Option<DailyRates> {
+    load_snapshots_from(&history_path()?)
+}
+
+fn load_snapshots_from(path: &Path) -> Option<DailyRates> {
+    let file = File::open(path).ok()?;
+    serde_json::from_reader(file).ok()
+}
+
+/// Record today's fetched rates as that day's snapshot.
+fn save_snapshot(rates: &HashMap<String, f64>) {
+    let Some(path) = history_path() else {
+        return;
+    };
+    let today = chrono::Local::now().date_naive();
+    if let Err(e) = save_snapshot_to(&path, today, rates) {
+        eprintln!("Warning: failed to write exchange rate history: {e}");
+    }
+}
+
+fn save_snapshot_to(
+    path: &Path,
+    day: NaiveDate,
+    rates: &HashMap<String, f64>,
+) -> Result<(), CacheWriteError> {
+    let mut snapshots = load_snapshots_from(path).unwrap_or_default();
+    snapshots.insert(day.format(DATE_FORMAT).to_string(), rates.clone());
+    dedupe_snapshots(&mut snapshots);
+    write_json_atomically(&snapshots, path)
+}
+
+/// Drop each rate that repeats the currency's rate on its previous recorded
+/// day, and the days left empty. A day converts at the closest rate on or
+/// before it, so the history still converts every day the same way.
+fn dedupe_snapshots(snapshots: &mut DailyRates) {
+    let mut latest: HashMap<String, f64> = HashMap::new();
+    snapshots.retain(|_, rates| {
+        rates.retain(|code, &mut rate| latest.insert(code.clone(), rate) != Some(rate));
+        !rates.is_empty()
+    });
+}
+
+fn read_rates_file(path: &Path) -> Result<DailyRates, CurrencyError> {
+    let content = std::fs::read_to_string(path).map_err(|source| CurrencyError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let parsed = if is_json {
+        serde_json::from_str(&content).map_err(|e| e.to_string())
+    } else {
+        parse_rates_csv(&content)
+    };
+    let parsed = parsed.and_then(|daily| {
+        match daily
+            .keys()
+            .find(|date| NaiveDate::parse_from_str(date, DATE_FORMAT).is_err())
+        {
+            Some(date) => Err(format!("invalid date '{date}', expected YYYY-MM-DD")),
+            None => Ok(daily),
+        }
+    });
+    parsed.map_err(|message| CurrencyError::Malformed {
+        path: path.to_path_buf(),
+        message,
+    })
+}
+
+/// Parse `date,currency,rate` rows. Blank lines, `#` comments and header
+/// rows (a first field of `date`) are skipped.
+fn parse_rates_csv(content: &str) -> Result<DailyRates, String> {
+    let mut daily = DailyRates::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields[0].eq_ignore_ascii_case("date") {
+            continue;
+        }
+        let [date, code, rate] = fields[..] else {
+            return Err(format!("line {}: expected date,currency,rate", index + 1));
+        };
+        let rate: f64 = rate
+            .parse()
+            .map_err(|_| format!("line {}: invalid rate '{rate}'", index + 1))?;
+        daily
+            .entry(date.to_string())
+            .or_default()
+            .insert(code.to_uppercase(), rate);
+    }
+    Ok(daily)
+}
+
+/// One currency's rates by day. Entries with an unparsable date (only
+/// possible in snapshots) or a non-positive rate are dropped.
+fn rates_for_currency(daily: &DailyRates, code: &str) -> BTreeMap<NaiveDate, f64> {
+    daily
+        .iter()
+        .filter_map(|(date, rates)| {
+            let date = NaiveDate::parse_from_str(date, DATE_FORMAT).ok()?;
+            let rate = rates
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(code))
+                .map(|(_, &rate)| rate)?;
+            (rate.is_finite() && rate > 0.0).then_some((date, rate))
+        })
+        .collect()
+}
+
 fn fetch_rates() -> Option<HashMap<String, f64>> {
     let agent: ureq::Agent = ureq::Agent::config_builder()
         .timeout_global(Some(Duration::from_secs(5)))
@@ -163,6 +485,7 @@ fn load_rates(offline: bool) -> Option<HashMap<String, f64>> {
     // Fetch fresh rates
     if let Some(rates) = fetch_rates() {
         save_cached_rates(&rates);
+        save_snapshot(&rates);
         return Some(rates);
     }
 
@@ -203,11 +526,7 @@ mod tests {
 
     #[test]
     fn converter_format_with_rate() {
-        let conv = CurrencyConverter {
-            currency: "CNY".to_string(),
-            rate: 7.25,
-            symbol: "¥".to_string(),
-        };
+        let conv = CurrencyConverter::from_rate_for_test("CNY", 7.25, "¥");
         assert_eq!(conv.format(1.0), "¥7.25");
         assert_eq!(conv.format(10.0), "¥72.50");
     }
@@ -217,4 +536,155 @@ mod tests {
         let conv = CurrencyConverter::load("USD", true).unwrap();
         assert_eq!(conv.currency_code(), "USD");
     }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn rate_on_uses_closest_earlier_day() {
+        let conv = CurrencyConverter::from_history_for_test(
+            "EUR",
+            &[("2026-01-01", 0.90), ("2026-01-10", 0.95)],
+        );
+        assert!(conv.is_historical());
+        assert!((conv.rate() - 0.95).abs() < 1e-9);
+        assert!((conv.rate_on(date("2025-12-31")) - 0.90).abs() < 1e-9);
+        assert!((conv.rate_on(date("2026-01-05")) - 0.90).abs() < 1e-9);
+        assert!((conv.rate_on(date("2026-01-10")) - 0.95).abs() < 1e-9);
+        assert!((conv.rate_on(date("2026-02-01")) - 0.95).abs() < 1e-9);
+    }
+
+    #[test]
+    fn blended_rate_weights_days_by_cost() {
+        let conv = CurrencyConverter::from_history_for_test(
+            "EUR",
+            &[("2026-01-01", 0.80), ("2026-01-02", 1.00)],
+        );
+        let span = conv.blended(&[
+            (date("2026-01-01"), 3.0),
+            (date("2026-01-02"), 1.0),
+            (date("2026-01-02"), f64::NAN),
+        ]);
+        assert!(!span.is_historical());
+        assert!((span.convert(4.0) - (3.0 * 0.80 + 1.0)).abs() < 1e-9);
+
+        let free = conv.blended(&[(date("2026-01-01"), 0.0)]);
+        assert!((free.rate() - 0.80).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_rates_csv_reads_rows_and_skips_header() {
+        let daily = parse_rates_csv(
+            "# exported rates\nDate,Currency,Rate\n2026-01-01,eur,0.9\n2026-01-01,CNY,7.1\n\n2026-01-02,EUR,0.91\n",
+        )
+        .unwrap();
+        let eur = rates_for_currency(&daily, "EUR");
+        assert_eq!(eur.len(), 2);
+        assert!((eur[&date("2026-01-02")] - 0.91).abs() < 1e-9);
+        assert_eq!(rates_for_currency(&daily, "GBP").len(), 0);
+
+        let err = parse_rates_csv("2026-01-01,EUR").unwrap_err();
+        assert!(err.contains("line 1"), "{err}");
+        let err = parse_rates_csv("2026-01-01,EUR,abc").unwrap_err();
+        assert!(err.contains("invalid rate"), "{err}");
+    }
+
+    #[test]
+    fn load_with_prefers_fixed_rate_then_rates_file() {
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            br#"{"2026-01-01": {"EUR": 0.9}, "2026-01-03": {"EUR": 0.92, "GBP": 0.8}}"#,
+        )
+        .unwrap();
+        let mut config = ExchangeRateConfig {
+            fixed_rates: HashMap::new(),
+            rates_file: Some(file.path().to_path_buf()),
+        };
+
+        let conv = CurrencyConverter::load_with("eur", true, &config).unwrap();
+        assert_eq!(conv.rate_source(), RateSource::File);
+        assert!((conv.rate_on(date("2026-01-02")) - 0.9).abs() < 1e-9);
+        assert!(matches!(
+            CurrencyConverter::load_with("JPY", true, &config),
+            Err(CurrencyError::MissingCurrency { .. })
+        ));
+
+        config.fixed_rates.insert("eur".to_string(), 0.85);
+        let conv = CurrencyConverter::load_with("EUR", true, &config).unwrap();
+        assert_eq!(conv.rate_source(), RateSource::Fixed);
+        assert!(!conv.is_historical());
+        assert_eq!(conv.format(10.0), "€8.50");
+
+        config.fixed_rates.insert("EUR".to_string(), 0.0);
+        config.fixed_rates.remove("eur");
+        assert!(matches!(
+            CurrencyConverter::load_with("EUR", true, &config),
+            Err(CurrencyError::InvalidFixedRate { .. })
+        ));
+    }
+
+    #[test]
+    fn load_with_reports_malformed_rates_file() {
+        let mut file = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+        std::io::Write::write_all(&mut file, b"01/02/2026,EUR,0.9\n").unwrap();
+        let config = ExchangeRateConfig {
+            fixed_rates: HashMap::new(),
+            rates_file: Some(file.path().to_path_buf()),
+        };
+        let err = CurrencyConverter::load_with("EUR", true, &config).unwrap_err();
+        assert!(
+            err.to_string().contains("invalid date '01/02/2026'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn save_snapshot_keeps_earlier_days() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("nested").join(HISTORY_FILE);
+        let eur = |rate: f64| HashMap::from([("EUR".to_string(), rate)]);
+
+        save_snapshot_to(&path, date("2026-01-01"), &eur(0.9)).unwrap();
+        save_snapshot_to(&path, date("2026-01-02"), &eur(0.91)).unwrap();
+        save_snapshot_to(&path, date("2026-01-02"), &eur(0.92)).unwrap();
+
+        let daily = load_snapshots_from(&path).unwrap();
+        let rates = rates_for_currency(&daily, "EUR");
+        assert_eq!(rates.len(), 2);
+        assert!((rates[&date("2026-01-01")] - 0.9).abs() < 1e-9);
+        assert!((rates[&date("2026-01-02")] - 0.92).abs() < 1e-9);
+        let leftovers = std::fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(leftovers, 1);
+    }
+
+    #[test]
+    fn save_snapshot_only_stores_rates_that_changed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(HISTORY_FILE);
+        let rates = |eur: f64, cny: f64| {
+            HashMap::from([("EUR".to_string(), eur), ("CNY".to_string(), cny)])
+        };
+
+        save_snapshot_to(&path, date("2026-01-01"), &rates(0.9, 7.1)).unwrap();
+        save_snapshot_to(&path, date("2026-01-02"), &rates(0.9, 7.2)).unwrap();
+        save_snapshot_to(&path, date("2026-01-03"), &rates(0.9, 7.2)).unwrap();
+
+        let daily = load_snapshots_from(&path).unwrap();
+        assert_eq!(
+            daily.keys().collect::<Vec<_>>(),
+            ["2026-01-01", "2026-01-02"]
+        );
+        assert_eq!(daily["2026-01-02"].len(), 1);
+        let eur = CurrencyConverter::historical(
+            "EUR",
+            rates_for_currency(&daily, "EUR"),
+            RateSource::Snapshots,
+        )
+        .unwrap();
+        assert!((eur.rate_on(date("2026-01-03")) - 0.9).abs() < 1e-9);
+        let cny = rates_for_currency(&daily, "CNY");
+        assert!((cny[&date("2026-01-02")] - 7.2).abs() < 1e-9);
+    }
 }
@@ -1,21 +1,30 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
 use super::cache::{
-    CacheReadError, CacheWriteError, load_raw_cache_if_fresh, load_raw_cache_snapshot,
-    save_raw_cache,
+    CacheReadError, CacheWriteError, RawPricingCache, load_raw_cache_if_fresh,
+    load_raw_cache_snapshot, save_raw_cache,
+};
+use super::provider::{
+    CatalogError, LITELLM_PRICING_URL, PricingProvider, fetch_litellm_raw, litellm_cache_file,
+    load_catalog_file,
+};
+use super::resolver::{
+    fallback_pricing, parse_catalog_data, parse_litellm_data, resolve_pricing_exact,
+    resolve_pricing_known,
 };
-use super::provider::fetch_litellm_raw;
-use super::resolver::{fallback_pricing, parse_litellm_data, resolve_pricing_known};
 use super::source::{CacheMetadata, PricingSource};
 use super::types::ModelPricing;
+use crate::core::{LONG_CONTEXT_THRESHOLD, set_prompt_size_floor};
 
 #[derive(Debug, Clone)]
 pub(super) enum ResolvedPricing {
     Known {
         pricing: ModelPricing,
         source: PricingSource,
+        /// Index of the catalog that priced the model (`None` for fallback).
+        catalog: Option<usize>,
     },
     Unknown,
 }
@@ -24,15 +33,54 @@ pub(super) enum ResolvedPricing {
 pub(crate) enum PricingLoadError {
     #[error("failed to load pricing cache: {0}")]
     Cache(#[from] CacheReadError),
+    #[error("failed to load pricing catalog: {0}")]
+    Catalog(#[from] CatalogError),
 }
 
-/// Pricing database loaded from `LiteLLM` or cache
+/// Prices from one provider of the pricing chain.
 #[derive(Debug)]
-pub(crate) struct PricingDb {
+pub(super) struct PriceCatalog {
+    pub(super) label: String,
+    pub(super) source: PricingSource,
     pub(super) models: HashMap<String, ModelPricing>,
+    /// Keys of `models` derived from a listed name, such as `sonnet-4` for
+    /// `claude-sonnet-4`; they match usage but are not entries of their own.
+    pub(super) aliases: HashSet<String>,
+}
+
+impl PriceCatalog {
+    fn parse(
+        label: String,
+        source: PricingSource,
+        data: RawPricingCache,
+        parse: fn(RawPricingCache) -> HashMap<String, ModelPricing>,
+    ) -> Self {
+        let listed: HashSet<String> = data.keys().cloned().collect();
+        let models = parse(data);
+        let aliases = models
+            .keys()
+            .filter(|key| !listed.contains(*key))
+            .cloned()
+            .collect();
+        Self {
+            label,
+            source,
+            models,
+            aliases,
+        }
+    }
+}
+
+/// Pricing database loaded from the configured catalogs (`LiteLLM` or cache
+/// by default)
+#[derive(Debug)]
+pub(crate) struct PricingDb {
+    /// Catalogs in chain order; the first one that prices a model wins.
+    pub(super) catalogs: Vec<PriceCatalog>,
     pub(super) resolved: RefCell<HashMap<String, ResolvedPricing>>,
     strict_unknown: bool,
-    pub(super) source: PricingSource,
+    /// Whether the chain differs from the default single `LiteLLM` list.
+    custom_chain: bool,
     cache_metadata: Option<CacheMetadata>,
 }
 
@@ -41,11 +89,8 @@ const PRICING_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
 impl PricingDb {
     fn empty(strict_unknown: bool) -> Self {
         Self {
-            models: HashMap::new(),
-            resolved: RefCell::new(HashMap::new()),
             strict_unknown,
-            source: PricingSource::Fallback,
-            cache_metadata: None,
+            ..Self::default()
         }
     }
 
@@ -56,16 +101,23 @@ impl PricingDb {
         cache_metadata: Option<CacheMetadata>,
     ) -> Self {
         Self {
-            models: parse_litellm_data(data),
-            resolved: RefCell::new(HashMap::new()),
+            catalogs: vec![PriceCatalog::parse(
+                PricingProvider::default().label(),
+                source,
+                data,
+                parse_litellm_data,
+            )],
             strict_unknown,
-            source,
             cache_metadata,
+            ..Self::default()
         }
     }
 
-    fn load_from_cache(strict_unknown: bool) -> Result<Option<Self>, CacheReadError> {
-        Ok(load_raw_cache_snapshot()?.map(|snapshot| {
+    fn load_from_cache(
+        cache_file: &str,
+        strict_unknown: bool,
+    ) -> Result<Option<Self>, CacheReadError> {
+        Ok(load_raw_cache_snapshot(cache_file)?.map(|snapshot| {
             let source = if snapshot.metadata.age > PRICING_CACHE_TTL {
                 PricingSource::CacheStale
             } else {
@@ -81,10 +133,11 @@ impl PricingDb {
     }
 
     fn load_from_cache_if_fresh(
+        cache_file: &str,
         ttl: Duration,
         strict_unknown: bool,
     ) -> Result<Option<(Self, Duration)>, CacheReadError> {
-        Ok(load_raw_cache_if_fresh(ttl)?.map(|snapshot| {
+        Ok(load_raw_cache_if_fresh(cache_file, ttl)?.map(|snapshot| {
             (
                 Self::from_raw_data(
                     snapshot.data,
@@ -97,48 +150,132 @@ impl PricingDb {
         }))
     }
 
-    pub(crate) fn load(offline: bool, strict_unknown: bool) -> Self {
-        Self::try_load(offline, strict_unknown).unwrap_or_else(|error| {
+    pub(crate) fn load(offline: bool, strict_unknown: bool, providers: &[PricingProvider]) -> Self {
+        Self::try_load(offline, strict_unknown, providers).unwrap_or_else(|error| {
             eprintln!("Error: {error}");
             std::process::exit(1);
         })
     }
 
-    pub(crate) fn load_quiet(offline: bool, strict_unknown: bool) -> Self {
-        Self::try_load_quiet(offline, strict_unknown).unwrap_or_else(|error| {
+    pub(crate) fn load_quiet(
+        offline: bool,
+        strict_unknown: bool,
+        providers: &[PricingProvider],
+    ) -> Self {
+        Self::try_load_quiet(offline, strict_unknown, providers).unwrap_or_else(|error| {
             eprintln!("Error: {error}");
             std::process::exit(1);
         })
     }
 
-    pub(crate) fn try_load(offline: bool, strict_unknown: bool) -> Result<Self, PricingLoadError> {
-        Self::load_internal(offline, strict_unknown, false)
+    /// Load the pricing chain. An empty `providers` list means the default
+    /// chain: the public `LiteLLM` price list.
+    pub(crate) fn try_load(
+        offline: bool,
+        strict_unknown: bool,
+        providers: &[PricingProvider],
+    ) -> Result<Self, PricingLoadError> {
+        Self::load_chain(offline, strict_unknown, false, providers)
     }
 
     pub(crate) fn try_load_quiet(
         offline: bool,
         strict_unknown: bool,
+        providers: &[PricingProvider],
     ) -> Result<Self, PricingLoadError> {
-        Self::load_internal(offline, strict_unknown, true)
+        Self::load_chain(offline, strict_unknown, true, providers)
     }
 
+    fn load_chain(
+        offline: bool,
+        strict_unknown: bool,
+        quiet: bool,
+        providers: &[PricingProvider],
+    ) -> Result<Self, PricingLoadError> {
+        let default_chain = [PricingProvider::default()];
+        let custom_chain = !providers.is_empty() && providers != default_chain;
+        let providers = if providers.is_empty() {
+            &default_chain[..]
+        } else {
+            providers
+        };
+
+        let mut db = Self {
+            strict_unknown,
+            custom_chain,
+            ..Self::default()
+        };
+        for provider in providers {
+            let catalog = match provider {
+                PricingProvider::Litellm { url } => {
+                    let url = url.as_deref().unwrap_or(LITELLM_PRICING_URL);
+                    let loaded = Self::load_internal(url, offline, strict_unknown, quiet)?;
+                    db.cache_metadata = db.cache_metadata.or(loaded.cache_metadata);
+                    let Some(mut catalog) = loaded.catalogs.into_iter().next() else {
+                        continue;
+                    };
+                    catalog.label = provider.label();
+                    catalog
+                }
+                PricingProvider::File { path, schema, .. } => {
+                    let raw = load_catalog_file(path, *schema)?;
+                    let catalog = PriceCatalog::parse(
+                        provider.label(),
+                        PricingSource::Catalog,
+                        raw,
+                        parse_catalog_data,
+                    );
+                    if !quiet {
+                        eprintln!(
+                            "Loaded {} models from price catalog {}",
+                            catalog.models.len() - catalog.aliases.len(),
+                            path.display()
+                        );
+                    }
+                    catalog
+                }
+            };
+            db.catalogs.push(catalog);
+        }
+        set_prompt_size_floor(db.min_long_context_threshold());
+        Ok(db)
+    }
+
+    /// Smallest long-context threshold any model can be priced with,
+    /// including the built-in fallback rates.
+    fn min_long_context_threshold(&self) -> i64 {
+        self.catalogs
+            .iter()
+            .flat_map(|catalog| catalog.models.values())
+            .flat_map(|pricing| {
+                std::iter::once(pricing).chain(pricing.service_tiers.iter().map(|(_, tier)| tier))
+            })
+            .filter_map(|pricing| pricing.long_context.as_deref())
+            .map(|tier| tier.threshold)
+            .fold(LONG_CONTEXT_THRESHOLD, i64::min)
+    }
+
+    /// Load the `LiteLLM` list from `url`: fresh cache, then network, then
+    /// any cache, then no catalog at all.
     fn load_internal(
+        url: &str,
         offline: bool,
         strict_unknown: bool,
         quiet: bool,
     ) -> Result<Self, PricingLoadError> {
         let start = Instant::now();
+        let cache_file = litellm_cache_file(url);
 
         if offline {
             return Self::finish_offline_cache_load(
-                Self::load_from_cache(strict_unknown),
+                Self::load_from_cache(&cache_file, strict_unknown),
                 strict_unknown,
                 quiet,
                 start,
             );
         }
 
-        match Self::load_from_cache_if_fresh(PRICING_CACHE_TTL, strict_unknown) {
+        match Self::load_from_cache_if_fresh(&cache_file, PRICING_CACHE_TTL, strict_unknown) {
             Ok(Some((db, age))) => {
                 if !quiet {
                     eprintln!(
@@ -157,14 +294,14 @@ impl PricingDb {
         if !quiet {
             eprint!("Fetching pricing from LiteLLM...");
         }
-        if let Some(raw_data) = fetch_litellm_raw() {
+        if let Some(raw_data) = fetch_litellm_raw(url) {
             let fetch_time = start.elapsed();
-            let save_result = save_raw_cache(&raw_data);
+            let save_result = save_raw_cache(&cache_file, &raw_data);
             let db = Self::from_raw_data(raw_data, strict_unknown, PricingSource::Live, None);
             if !quiet {
                 eprintln!(
                     " {} models ({:.2}ms)",
-                    db.models.len(),
+                    db.catalogs[0].models.len(),
                     fetch_time.as_secs_f64() * 1000.0
                 );
             }
@@ -175,7 +312,7 @@ impl PricingDb {
         if !quiet {
             eprintln!(" failed, trying cache...");
         }
-        match Self::load_from_cache(strict_unknown) {
+        match Self::load_from_cache(&cache_file, strict_unknown) {
             Ok(Some(db)) => {
                 if !quiet {
                     eprintln!(
@@ -235,8 +372,37 @@ impl PricingDb {
         self.resolve_pricing(model).map(|(_, source)| source)
     }
 
+    /// Label of the catalog that priced `model`, when the pricing chain is
+    /// configured (the default `LiteLLM`-only chain reports none).
+    pub(crate) fn catalog_for_model(&self, model: &str) -> Option<&str> {
+        if !self.custom_chain {
+            return None;
+        }
+        self.resolve_pricing(model)?;
+        match self.resolved.borrow().get(model) {
+            Some(ResolvedPricing::Known {
+                catalog: Some(index),
+                ..
+            }) => self
+                .catalogs
+                .get(*index)
+                .map(|catalog| catalog.label.as_str()),
+            _ => None,
+        }
+    }
+
+    pub(super) fn custom_chain(&self) -> bool {
+        self.custom_chain
+    }
+
+    /// Source of the loaded prices: the catalogs' common source, `mixed`
+    /// when they differ, or `fallback` when none loaded.
     pub(crate) fn source(&self) -> PricingSource {
-        self.source
+        self.catalogs
+            .iter()
+            .map(|catalog| catalog.source)
+            .reduce(PricingSource::combine)
+            .unwrap_or(PricingSource::Fallback)
     }
 
     pub(crate) fn cache_age_seconds(&self) -> Option<u64> {
@@ -248,36 +414,62 @@ impl PricingDb {
             .map(CacheMetadata::modified_epoch_seconds)
     }
 
+    /// Insert a model into the first catalog, created with the empty
+    /// database's `fallback` source when missing.
     #[cfg(test)]
     pub(super) fn insert_model_for_tests(&mut self, name: String, pricing: ModelPricing) {
-        self.models.insert(name, pricing);
+        if self.catalogs.is_empty() {
+            self.catalogs.push(PriceCatalog {
+                label: PricingProvider::default().label(),
+                source: PricingSource::Fallback,
+                models: HashMap::new(),
+                aliases: HashSet::new(),
+            });
+        }
+        self.catalogs[0].models.insert(name, pricing);
     }
 
-    fn resolve_pricing(&self, model: &str) -> Option<(ModelPricing, PricingSource)> {
+    pub(super) fn resolve_pricing(&self, model: &str) -> Option<(ModelPricing, PricingSource)> {
         if let Some(cached) = self.resolved.borrow().get(model) {
             return match cached {
-                ResolvedPricing::Known { pricing, source } => Some((pricing.clone(), *source)),
+                ResolvedPricing::Known {
+                    pricing, source, ..
+                } => Some((pricing.clone(), *source)),
                 ResolvedPricing::Unknown => None,
             };
         }
 
-        let pricing = if let Some(pricing) = resolve_pricing_known(model, &self.models) {
-            Some((pricing, self.source))
+        // An exact key anywhere in the chain beats a fuzzy match in an earlier
+        // catalog; the fuzzy stages then run in chain order.
+        let in_chain =
+            |resolve: fn(&str, &HashMap<String, ModelPricing>) -> Option<ModelPricing>| {
+                self.catalogs
+                    .iter()
+                    .enumerate()
+                    .find_map(|(index, catalog)| {
+                        resolve(model, &catalog.models)
+                            .map(|pricing| (pricing, catalog.source, Some(index)))
+                    })
+            };
+        let known = in_chain(resolve_pricing_exact).or_else(|| in_chain(resolve_pricing_known));
+        let pricing = if known.is_some() {
+            known
         } else if self.strict_unknown {
             None
         } else {
-            fallback_pricing(model).map(|pricing| (pricing, PricingSource::Fallback))
+            fallback_pricing(model).map(|pricing| (pricing, PricingSource::Fallback, None))
         };
 
         let cached = match &pricing {
-            Some((pricing, source)) => ResolvedPricing::Known {
+            Some((pricing, source, catalog)) => ResolvedPricing::Known {
                 pricing: pricing.clone(),
                 source: *source,
+                catalog: *catalog,
             },
             None => ResolvedPricing::Unknown,
         };
         self.resolved.borrow_mut().insert(model.to_string(), cached);
-        pricing
+        pricing.map(|(pricing, source, _)| (pricing, source))
     }
 }
 
@@ -290,10 +482,10 @@ fn warn_cache_write_error(result: Result<(), CacheWriteError>) {
 impl Default for PricingDb {
     fn default() -> Self {
         Self {
-            models: HashMap::new(),
+            catalogs: Vec::new(),
             resolved: RefCell::new(HashMap::new()),
             strict_unknown: false,
-            source: PricingSource::Fallback,
+            custom_chain: false,
             cache_metadata: None,
         }
     }
@@ -303,7 +495,8 @@ impl Default for PricingDb {
 #[allow(clippy::float_cmp)]
 mod tests {
     use super::*;
-    use crate::core::Stats;
+    use crate::core::{ServiceTier, Stats};
+    use crate::pricing::types::LongContextTier;
     use crate::pricing::{
         attach_costs, calculate_cost, pricing_source_for_models, sum_model_costs,
     };
@@ -337,7 +530,7 @@ mod tests {
         let db = PricingDb::finish_offline_cache_load(Ok(None), true, true, Instant::now())
             .expect("missing cache should use defaults");
 
-        assert!(db.models.is_empty());
+        assert!(db.catalogs.is_empty());
         assert!(db.strict_unknown);
     }
 
@@ -389,7 +582,7 @@ mod tests {
     #[test]
     fn calculate_cost_basic() {
         let mut db = PricingDb::default();
-        db.models.insert(
+        db.insert_model_for_tests(
             "sonnet-4".to_string(),
             ModelPricing {
                 input: 3e-6,
@@ -398,6 +591,8 @@ mod tests {
                 cache_create: 3.75e-6,
                 cache_create_1h: 3.75e-6,
                 cache_read: 0.3e-6,
+                long_context: None,
+                service_tiers: Vec::new(),
             },
         );
 
@@ -422,7 +617,7 @@ mod tests {
     #[test]
     fn calculate_cost_with_cache() {
         let mut db = PricingDb::default();
-        db.models.insert(
+        db.insert_model_for_tests(
             "sonnet-4".to_string(),
             ModelPricing {
                 input: 3e-6,
@@ -431,6 +626,8 @@ mod tests {
                 cache_create: 3.75e-6,
                 cache_create_1h: 3.75e-6,
                 cache_read: 0.3e-6,
+                long_context: None,
+                service_tiers: Vec::new(),
             },
         );
 
@@ -455,7 +652,7 @@ mod tests {
     #[test]
     fn calculate_cost_zero_tokens() {
         let mut db = PricingDb::default();
-        db.models.insert(
+        db.insert_model_for_tests(
             "sonnet-4".to_string(),
             ModelPricing {
                 input: 3e-6,
@@ -494,7 +691,7 @@ mod tests {
     #[test]
     fn calculate_cost_with_reasoning_tokens() {
         let mut db = PricingDb::default();
-        db.models.insert(
+        db.insert_model_for_tests(
             "opus-4".to_string(),
             ModelPricing {
                 input: 15e-6,
@@ -503,6 +700,8 @@ mod tests {
                 cache_create: 0.0,
                 cache_create_1h: 0.0,
                 cache_read: 0.0,
+                long_context: None,
+                service_tiers: Vec::new(),
             },
         );
 
@@ -521,7 +720,7 @@ mod tests {
     #[test]
     fn sum_model_costs_multiple_models() {
         let mut db = PricingDb::default();
-        db.models.insert(
+        db.insert_model_for_tests(
             "sonnet-4".to_string(),
             ModelPricing {
                 input: 3e-6,
@@ -529,7 +728,7 @@ mod tests {
                 ..Default::default()
             },
         );
-        db.models.insert(
+        db.insert_model_for_tests(
             "haiku-3.5".to_string(),
             ModelPricing {
                 input: 0.8e-6,
@@ -593,7 +792,7 @@ mod tests {
     #[test]
     fn sum_model_costs_skips_unknown_keeps_known() {
         let mut db = PricingDb::default();
-        db.models.insert(
+        db.insert_model_for_tests(
             "sonnet-4".to_string(),
             ModelPricing {
                 input: 3e-6,
@@ -628,7 +827,7 @@ mod tests {
     #[test]
     fn attach_costs_computes_per_item() {
         let mut db = PricingDb::default();
-        db.models.insert(
+        db.insert_model_for_tests(
             "sonnet-4".to_string(),
             ModelPricing {
                 input: 3e-6,
@@ -684,7 +883,7 @@ mod tests {
     #[test]
     fn get_pricing_caches_resolved_result() {
         let mut db = PricingDb::default();
-        db.models.insert(
+        db.insert_model_for_tests(
             "sonnet-4".to_string(),
             ModelPricing {
                 input: 3e-6,
@@ -788,10 +987,87 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn exact_key_in_a_later_catalog_beats_a_fuzzy_match_in_an_earlier_one() {
+        let catalog = |label: &str, key: &str, input: f64| PriceCatalog {
+            label: label.to_string(),
+            source: PricingSource::Live,
+            models: HashMap::from([(
+                key.to_string(),
+                ModelPricing {
+                    input,
+                    ..ModelPricing::default()
+                },
+            )]),
+            aliases: HashSet::new(),
+        };
+        let db = PricingDb {
+            catalogs: vec![
+                catalog("first", "gpt-5", 1e-6),
+                catalog("second", "gpt-5-2025-08-07", 2e-6),
+            ],
+            custom_chain: true,
+            ..PricingDb::default()
+        };
+
+        assert_eq!(db.get_pricing("gpt-5-2025-08-07").unwrap().input, 2e-6);
+        assert_eq!(db.catalog_for_model("gpt-5-2025-08-07"), Some("second"));
+        // Without an exact key anywhere, the first catalog's fuzzy match wins.
+        assert_eq!(db.get_pricing("gpt-5-2025-10-01").unwrap().input, 1e-6);
+    }
+
+    #[test]
+    fn min_long_context_threshold_covers_catalogs_tiers_and_fallback() {
+        let tiered = |threshold: i64| ModelPricing {
+            long_context: Some(Box::new(LongContextTier {
+                threshold,
+                rates: ModelPricing::default(),
+            })),
+            ..ModelPricing::default()
+        };
+        let mut db = PricingDb::default();
+        assert_eq!(db.min_long_context_threshold(), LONG_CONTEXT_THRESHOLD);
+
+        db.insert_model_for_tests("gpt-5".to_string(), tiered(272_000));
+        db.insert_model_for_tests(
+            "gemini-2.5-pro".to_string(),
+            ModelPricing {
+                service_tiers: vec![(ServiceTier::Priority, tiered(128_000))],
+                ..ModelPricing::default()
+            },
+        );
+        assert_eq!(db.min_long_context_threshold(), 128_000);
+    }
+
+    #[test]
+    fn catalog_entries_list_each_model_once_without_aliases() {
+        let data = HashMap::from([
+            (
+                "claude-opus-4-1".to_string(),
+                serde_json::json!({"input_cost_per_token": 15e-6, "output_cost_per_token": 75e-6}),
+            ),
+            (
+                "openai/gpt-5".to_string(),
+                serde_json::json!({"input_cost_per_token": 1.25e-6, "output_cost_per_token": 10e-6}),
+            ),
+        ]);
+        let db = PricingDb::from_raw_data(data, false, PricingSource::Cache, None);
+
+        let keys: Vec<_> = db
+            .catalog_entries(None)
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect();
+        assert_eq!(keys, vec!["claude-opus-4-1", "openai/gpt-5"]);
+        // Aliases still resolve usage.
+        assert_eq!(db.get_pricing("opus-4-1").unwrap().input, 15e-6);
+        assert_eq!(db.get_pricing("gpt-5").unwrap().input, 1.25e-6);
+    }
+
     #[test]
     fn default_pricing_db_has_empty_models() {
         let db = PricingDb::default();
-        assert!(db.models.is_empty());
+        assert!(db.catalogs.is_empty());
         assert!(db.resolved.borrow().is_empty());
         assert!(!db.strict_unknown);
     }
@@ -8,14 +8,22 @@ impl PricingDb {
         let mut lines: Vec<_> = resolved
             .iter()
             .map(|(model, resolution)| match resolution {
-                ResolvedPricing::Known { source, .. } => {
-                    let matched_key = if *source == PricingSource::Fallback {
-                        Some("built-in fallback".to_string())
-                    } else {
-                        resolve_pricing_known_with_key(model, &self.models)
-                            .map(|matched| matched.matched_key)
+                ResolvedPricing::Known {
+                    source, catalog, ..
+                } => {
+                    let catalog = catalog.and_then(|index| self.catalogs.get(index));
+                    let matched_key = match catalog {
+                        Some(catalog) => resolve_pricing_known_with_key(model, &catalog.models)
+                            .map(|matched| matched.matched_key),
+                        None => Some("built-in fallback".to_string()),
                     };
-                    diagnostic_line(model, matched_key.as_deref(), *source)
+                    let line = diagnostic_line(model, matched_key.as_deref(), *source);
+                    match catalog {
+                        Some(catalog) if self.custom_chain() => {
+                            format!("{line} [{}]", catalog.label)
+                        }
+                        _ => line,
+                    }
                 }
                 ResolvedPricing::Unknown => diagnostic_line(model, None, PricingSource::Unknown),
             })
@@ -61,8 +69,8 @@ mod tests {
     #[test]
     fn pricing_db_diagnostics_are_sorted_and_include_unknown_models() {
         let mut db = PricingDb::default();
-        db.source = PricingSource::Cache;
         db.insert_model_for_tests("claude-sonnet-4".to_string(), ModelPricing::default());
+        db.catalogs[0].source = PricingSource::Cache;
 
         assert!(db.get_pricing("sonnet-4").is_some());
         assert!(db.get_pricing("qwen-unknown").is_none());
@@ -0,0 +1,453 @@
+//! Catalog inspection behind `ccstats pricing`: how a model resolves, what a
+//! catalog contains, forced `LiteLLM` refreshes and cached-vs-fresh diffs.
+
+use std::collections::HashMap;
+
+use super::cache::{get_cache_path, load_raw_cache_snapshot, save_raw_cache};
+use super::db::{PricingDb, ResolvedPricing};
+use super::provider::{
+    LITELLM_PRICING_URL, PricingProvider, fetch_litellm_raw, litellm_cache_file,
+};
+use super::resolver::{
+    fallback_pricing, parse_litellm_data, resolve_pricing_known, resolve_pricing_known_with_key,
+};
+use super::source::PricingSource;
+use super::types::{LongContextTier, ModelPricing};
+
+const PER_MILLION: f64 = 1_000_000.0;
+
+/// Rates of one model in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PriceRates {
+    pub(crate) input: f64,
+    pub(crate) output: f64,
+    pub(crate) reasoning_output: f64,
+    pub(crate) cache_read: f64,
+    pub(crate) cache_write: f64,
+    pub(crate) cache_write_1h: f64,
+}
+
+impl PriceRates {
+    fn from_pricing(pricing: &ModelPricing) -> Self {
+        Self {
+            input: pricing.input * PER_MILLION,
+            output: pricing.output * PER_MILLION,
+            reasoning_output: pricing.reasoning_output * PER_MILLION,
+            cache_read: pricing.cache_read * PER_MILLION,
+            cache_write: pricing.cache_create * PER_MILLION,
+            cache_write_1h: pricing.cache_create_1h * PER_MILLION,
+        }
+    }
+
+    /// Field names and values in display order.
+    pub(crate) fn fields(&self) -> [(&'static str, f64); 6] {
+        [
+            ("input", self.input),
+            ("output", self.output),
+            ("reasoning_output", self.reasoning_output),
+            ("cache_read", self.cache_read),
+            ("cache_write", self.cache_write),
+            ("cache_write_1h", self.cache_write_1h),
+        ]
+    }
+}
+
+/// Long-context tier of a model: rates in USD per million tokens for
+/// requests whose prompt exceeds `threshold` tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LongContextRates {
+    pub(crate) threshold: i64,
+    pub(crate) rates: PriceRates,
+}
+
+impl LongContextRates {
+    fn from_tier(tier: &LongContextTier) -> Self {
+        Self {
+            threshold: tier.threshold,
+            rates: PriceRates::from_pricing(&tier.rates),
+        }
+    }
+}
+
+/// How one model name resolves against the pricing chain.
+#[derive(Debug, Clone)]
+pub(crate) struct PriceQuote {
+    pub(crate) model: String,
+    /// Catalog key the model matched (`built-in fallback` for fallback prices).
+    pub(crate) matched_key: Option<String>,
+    pub(crate) source: PricingSource,
+    /// Label of the catalog that priced the model.
+    pub(crate) catalog: Option<String>,
+    pub(crate) rates: Option<PriceRates>,
+    /// Long-context tier, when the model is priced in tiers.
+    pub(crate) long_context: Option<LongContextRates>,
+}
+
+/// One catalog entry listed by `ccstats pricing list`.
+#[derive(Debug, Clone)]
+pub(crate) struct CatalogEntry {
+    pub(crate) key: String,
+    pub(crate) catalog: String,
+    pub(crate) source: PricingSource,
+    pub(crate) rates: PriceRates,
+    pub(crate) long_context: Option<LongContextRates>,
+}
+
+impl PricingDb {
+    /// Resolve `model` and describe the match: catalog key, source, catalog
+    /// and rates.
+    pub(crate) fn quote(&self, model: &str) -> PriceQuote {
+        self.resolve_pricing(model);
+        let resolved = self.resolved.borrow().get(model).cloned();
+        match resolved {
+            Some(ResolvedPricing::Known {
+                pricing,
+                source,
+                catalog,
+            }) => {
+                let catalog = catalog.and_then(|index| self.catalogs.get(index));
+                let matched_key = match catalog {
+                    Some(catalog) => resolve_pricing_known_with_key(model, &catalog.models)
+                        .map(|matched| matched.matched_key),
+                    None => Some("built-in fallback".to_string()),
+                };
+                PriceQuote {
+                    model: model.to_string(),
+                    matched_key,
+                    source,
+                    catalog: catalog.map(|catalog| catalog.label.clone()),
+                    rates: Some(PriceRates::from_pricing(&pricing)),
+                    long_context: pricing
+                        .long_context
+                        .as_deref()
+                        .map(LongContextRates::from_tier),
+                }
+            }
+            Some(ResolvedPricing::Unknown) | None => PriceQuote {
+                model: model.to_string(),
+                matched_key: None,
+                source: PricingSource::Unknown,
+                catalog: None,
+                rates: None,
+                long_context: None,
+            },
+        }
+    }
+
+    /// Entries of every loaded catalog whose key contains `pattern`
+    /// (case-insensitive), in chain order and then by key.
+    pub(crate) fn catalog_entries(&self, pattern: Option<&str>) -> Vec<CatalogEntry> {
+        let pattern = pattern.map(str::to_lowercase);
+        let mut entries = Vec::new();
+        for catalog in &self.catalogs {
+            let mut keys: Vec<&String> = catalog
+                .models
+                .keys()
+                .filter(|key| !catalog.aliases.contains(*key))
+                .filter(|key| {
+                    pattern
+                        .as_deref()
+                        .is_none_or(|pattern| key.to_lowercase().contains(pattern))
+                })
+                .collect();
+            keys.sort_unstable();
+            entries.extend(keys.into_iter().map(|key| {
+                let pricing = &catalog.models[key];
+                CatalogEntry {
+                    key: key.clone(),
+                    catalog: catalog.label.clone(),
+                    source: catalog.source,
+                    rates: PriceRates::from_pricing(pricing),
+                    long_context: pricing
+                        .long_context
+                        .as_deref()
+                        .map(LongContextRates::from_tier),
+                }
+            }));
+        }
+        entries
+    }
+}
+
+/// Whether the built-in fallback table prices `model`, regardless of
+/// whether strict pricing lets it apply.
+pub(crate) fn has_fallback_pricing(model: &str) -> bool {
+    fallback_pricing(model).is_some()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum CatalogFetchError {
+    #[error("failed to fetch {label} price list")]
+    Fetch { label: String },
+    #[error("failed to read cached {label} price list: {message}")]
+    Cache { label: String, message: String },
+}
+
+/// Outcome of force-fetching one `LiteLLM` price list.
+#[derive(Debug)]
+pub(crate) struct RefreshReport {
+    pub(crate) label: String,
+    pub(crate) models: usize,
+    /// Age of the cache the refresh replaced, if there was one.
+    pub(crate) previous_age_seconds: Option<u64>,
+    pub(crate) cache_path: Option<String>,
+    /// Why the fetched list could not be cached, if it could not.
+    pub(crate) save_error: Option<String>,
+}
+
+/// A rate that differs between the cached and the fresh catalog. A side is
+/// `None` when that catalog does not price the model at all.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PriceChange {
+    pub(crate) model: String,
+    pub(crate) field: &'static str,
+    pub(crate) cached: Option<f64>,
+    pub(crate) fresh: Option<f64>,
+}
+
+/// Cached-vs-fresh comparison of one `LiteLLM` price list.
+#[derive(Debug)]
+pub(crate) struct CatalogDiff {
+    pub(crate) label: String,
+    pub(crate) cache_age_seconds: Option<u64>,
+    pub(crate) changes: Vec<PriceChange>,
+}
+
+/// `LiteLLM` providers of the chain as `(label, url)` pairs; an empty chain
+/// is the default `LiteLLM` list. Local catalogs are read on every run and
+/// have nothing to refresh.
+pub(crate) fn litellm_providers(providers: &[PricingProvider]) -> Vec<(String, String)> {
+    let default_chain = [PricingProvider::default()];
+    let providers = if providers.is_empty() {
+        &default_chain[..]
+    } else {
+        providers
+    };
+    providers
+        .iter()
+        .filter_map(|provider| match provider {
+            PricingProvider::Litellm { url } => Some((
+                provider.label(),
+                url.as_deref().unwrap_or(LITELLM_PRICING_URL).to_string(),
+            )),
+            PricingProvider::File { .. } => None,
+        })
+        .collect()
+}
+
+/// Fetch the list at `url` regardless of cache age and replace its cache.
+pub(crate) fn refresh_litellm(label: &str, url: &str) -> Result<RefreshReport, CatalogFetchError> {
+    let cache_file = litellm_cache_file(url);
+    let previous_age_seconds = load_raw_cache_snapshot(&cache_file)
+        .ok()
+        .flatten()
+        .map(|snapshot| snapshot.metadata.age_seconds());
+    let raw = fetch_litellm_raw(url).ok_or_else(|| CatalogFetchError::Fetch {
+        label: label.to_string(),
+    })?;
+    let save_error = save_raw_cache(&cache_file, &raw)
+        .err()
+        .map(|error| error.to_string());
+    Ok(RefreshReport {
+        label: label.to_string(),
+        models: parse_litellm_data(raw).len(),
+        previous_age_seconds,
+        cache_path: get_cache_path(&cache_file).map(|path| path.display().to_string()),
+        save_error,
+    })
+}
+
+/// Compare the cached list at `url` with a fresh download, for `models`
+/// only. The cache is left untouched.
+pub(crate) fn diff_litellm(
+    label: &str,
+    url: &str,
+    models: &[String],
+) -> Result<CatalogDiff, CatalogFetchError> {
+    let cache_file = litellm_cache_file(url);
+    let snapshot =
+        load_raw_cache_snapshot(&cache_file).map_err(|error| CatalogFetchError::Cache {
+            label: label.to_string(),
+            message: error.to_string(),
+        })?;
+    let cache_age_seconds = snapshot
+        .as_ref()
+        .map(|snapshot| snapshot.metadata.age_seconds());
+    let cached = snapshot.map_or_else(HashMap::new, |snapshot| parse_litellm_data(snapshot.data));
+    let fresh = fetch_litellm_raw(url)
+        .map(parse_litellm_data)
+        .ok_or_else(|| CatalogFetchError::Fetch {
+            label: label.to_string(),
+        })?;
+    Ok(CatalogDiff {
+        label: label.to_string(),
+        cache_age_seconds,
+        changes: diff_models(models, &cached, &fresh),
+    })
+}
+
+/// Rates of `models` that differ between two catalogs, by model then field.
+pub(super) fn diff_models(
+    models: &[String],
+    cached: &HashMap<String, ModelPricing>,
+    fresh: &HashMap<String, ModelPricing>,
+) -> Vec<PriceChange> {
+    let mut models: Vec<&String> = models.iter().collect();
+    models.sort_unstable();
+    models.dedup();
+
+    let mut changes = Vec::new();
+    for model in models {
+        let fields = |catalog| {
+            resolve_pricing_known(model, catalog)
+                .map(|pricing| PriceRates::from_pricing(&pricing).fields())
+        };
+        let (cached_fields, fresh_fields) = (fields(cached), fields(fresh));
+        let Some(names) = cached_fields.or(fresh_fields) else {
+            continue;
+        };
+        for (index, (field, _)) in names.into_iter().enumerate() {
+            let cached_rate = cached_fields.map(|fields| fields[index].1);
+            let fresh_rate = fresh_fields.map(|fields| fields[index].1);
+            let differs = match (cached_rate, fresh_rate) {
+                (Some(cached_rate), Some(fresh_rate)) => rate_changed(cached_rate, fresh_rate),
+                (Some(rate), None) | (None, Some(rate)) => rate != 0.0,
+                (None, None) => false,
+            };
+            if differs {
+                changes.push(PriceChange {
+                    model: model.clone(),
+                    field,
+                    cached: cached_rate,
+                    fresh: fresh_rate,
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Whether two per-million rates differ beyond float noise from the
+/// per-token conversion.
+fn rate_changed(old: f64, new: f64) -> bool {
+    (old - new).abs() > 1e-9 * old.abs().max(new.abs()).max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pricing(input: f64, output: f64) -> ModelPricing {
+        ModelPricing {
+            input: input / PER_MILLION,
+            output: output / PER_MILLION,
+            ..ModelPricing::default()
+        }
+    }
+
+    #[test]
+    fn diff_lists_changed_added_and_removed_rates_of_used_models_only() {
+        let cached = HashMap::from([
+            ("claude-sonnet-4".to_string(), pricing(3.0, 15.0)),
+            ("gpt-5".to_string(), pricing(1.25, 10.0)),
+            ("retired-model".to_string(), pricing(1.0, 2.0)),
+        ]);
+        let fresh = HashMap::from([
+            ("claude-sonnet-4".to_string(), pricing(3.0, 12.0)),
+            ("gpt-5".to_string(), pricing(1.25, 10.0)),
+            ("new-model".to_string(), pricing(0.5, 0.0)),
+        ]);
+        let models = [
+            "gpt-5",
+            "claude-sonnet-4",
+            "new-model",
+            "retired-model",
+            "claude-sonnet-4",
+        ]
+        .map(str::to_string);
+
+        let changes = diff_models(&models, &cached, &fresh);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|change| {
+                (
+                    change.model.as_str(),
+                    change.field,
+                    change.cached,
+                    change.fresh,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("claude-sonnet-4", "output", Some(15.0), Some(12.0)),
+                ("new-model", "input", None, Some(0.5)),
+                ("retired-model", "input", Some(1.0), None),
+                ("retired-model", "output", Some(2.0), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn quote_reports_matched_key_catalog_and_rates() {
+        let mut db = PricingDb::default();
+        db.insert_model_for_tests("claude-sonnet-4-20250514".to_string(), pricing(3.0, 15.0));
+        db.catalogs[0].source = PricingSource::Cache;
+
+        let quote = db.quote("claude-sonnet-4-20250514");
+        assert_eq!(
+            quote.matched_key.as_deref(),
+            Some("claude-sonnet-4-20250514")
+        );
+        assert_eq!(quote.source, PricingSource::Cache);
+        assert_eq!(quote.catalog.as_deref(), Some("litellm"));
+        let rates = quote.rates.unwrap();
+        assert!((rates.input - 3.0).abs() < 1e-9 && (rates.output - 15.0).abs() < 1e-9);
+
+        let unknown = PricingDb::default();
+        let quote = unknown.quote("qwen-unknown");
+        assert_eq!(quote.source, PricingSource::Unknown);
+        assert!(quote.rates.is_none());
+    }
+
+    #[test]
+    fn catalog_entries_filter_case_insensitively_and_sort_by_key() {
+        let mut db = PricingDb::default();
+        for key in ["gpt-5", "claude-sonnet-4", "Claude-Sonnet-3.7"] {
+            db.insert_model_for_tests(key.to_string(), pricing(1.0, 1.0));
+        }
+        let keys: Vec<_> = db
+            .catalog_entries(Some("SONNET"))
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect();
+        assert_eq!(keys, vec!["Claude-Sonnet-3.7", "claude-sonnet-4"]);
+        assert_eq!(db.catalog_entries(None).len(), 3);
+    }
+
+    #[test]
+    fn litellm_providers_skip_local_catalogs_and_default_to_litellm() {
+        assert_eq!(
+            litellm_providers(&[]),
+            vec![("litellm".to_string(), LITELLM_PRICING_URL.to_string())]
+        );
+        let chain = [
+            PricingProvider::File {
+                path: "/etc/prices.toml".into(),
+                schema: super::super::provider::CatalogSchema::Ccstats,
+                name: None,
+            },
+            PricingProvider::Litellm {
+                url: Some("https://mirror.example/prices.json".to_string()),
+            },
+        ];
+        assert_eq!(
+            litellm_providers(&chain),
+            vec![(
+                "litellm (https://mirror.example/prices.json)".to_string(),
+                "https://mirror.example/prices.json".to_string()
+            )]
+        );
+    }
+}
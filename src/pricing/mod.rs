@@ -3,17 +3,24 @@ mod cost;
 pub(crate) mod currency;
 mod db;
 mod diagnostics;
+mod inspect;
 mod provider;
 mod resolver;
 mod source;
 mod types;
 
+pub(crate) use cache::{get_cache_path, write_json_atomically};
 pub(crate) use cost::{
     CostDisplayMode, attach_costs, calculate_cost, calculate_display_cost,
-    calculate_estimated_proxy_cost, model_cost_kind, pricing_source_for_model_maps,
-    pricing_source_for_model_stats, pricing_source_for_models, sum_display_model_costs,
-    sum_estimated_proxy_model_costs, sum_model_costs,
+    calculate_estimated_proxy_cost, calculate_service_tier_cost, model_cost_kind,
+    pricing_source_for_model_maps, pricing_source_for_model_stats, pricing_source_for_models,
+    sum_display_model_costs, sum_estimated_proxy_model_costs, sum_model_costs,
 };
-pub(crate) use currency::CurrencyConverter;
+pub(crate) use currency::{CurrencyConverter, CurrencyError, ExchangeRateConfig};
 pub(crate) use db::PricingDb;
+pub(crate) use inspect::{
+    CatalogDiff, CatalogEntry, PriceChange, PriceQuote, PriceRates, RefreshReport, diff_litellm,
+    has_fallback_pricing, litellm_providers, refresh_litellm,
+};
+pub(crate) use provider::PricingProvider;
 pub(crate) use source::PricingSource;
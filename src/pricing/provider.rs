@@ -1,20 +1,108 @@
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-const LITELLM_PRICING_URL: &str =
+use serde::Deserialize;
+
+use super::cache::RawPricingCache;
+
+pub(crate) const LITELLM_PRICING_URL: &str =
     "https://raw.githubusercontent.com/BerriAI/litellm/main/model_prices_and_context_window.json";
 const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
 const FETCH_RETRIES: usize = 3;
 const RETRY_BACKOFF_MS: u64 = 250;
+const DEFAULT_CACHE_FILE: &str = "pricing.json";
+
+/// One price catalog in the `[pricing] providers` chain. Earlier providers
+/// win when several catalogs price the same model.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase", deny_unknown_fields)]
+pub(crate) enum PricingProvider {
+    /// `LiteLLM` price list fetched over HTTP and cached for 24 hours.
+    Litellm {
+        /// Mirror of the `LiteLLM` price list (defaults to GitHub).
+        #[serde(default)]
+        url: Option<String>,
+    },
+    /// Local JSON or TOML price catalog, read on every run.
+    File {
+        path: PathBuf,
+        #[serde(default)]
+        schema: CatalogSchema,
+        /// Label shown as the pricing provenance (defaults to the file name).
+        #[serde(default)]
+        name: Option<String>,
+    },
+}
+
+impl Default for PricingProvider {
+    fn default() -> Self {
+        Self::Litellm { url: None }
+    }
+}
 
-pub(super) fn fetch_litellm_raw() -> Option<HashMap<String, serde_json::Value>> {
+/// Layout of a local price catalog.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CatalogSchema {
+    /// `LiteLLM` layout: model name to `*_cost_per_token` fields.
+    #[default]
+    Litellm,
+    /// ccstats layout: a `models` table of USD prices per million tokens.
+    Ccstats,
+}
+
+impl PricingProvider {
+    /// Label identifying the catalog in pricing provenance.
+    pub(crate) fn label(&self) -> String {
+        match self {
+            Self::Litellm { url: None } => "litellm".to_string(),
+            Self::Litellm { url: Some(url) } => format!("litellm ({url})"),
+            Self::File {
+                name: Some(name), ..
+            } => name.clone(),
+            Self::File { path, .. } => path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum CatalogError {
+    #[error("failed to read price catalog {path:?}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("price catalog {path:?} is malformed: {message}")]
+    Malformed { path: PathBuf, message: String },
+}
+
+/// Cache file of a `LiteLLM` mirror. Mirrors other than the default URL get
+/// their own file so switching URLs never serves another catalog's prices.
+pub(super) fn litellm_cache_file(url: &str) -> String {
+    if url == LITELLM_PRICING_URL {
+        return DEFAULT_CACHE_FILE.to_string();
+    }
+    // FNV-1a: stable across builds, unlike `DefaultHasher`.
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("pricing-{hash:016x}.json")
+}
+
+pub(super) fn fetch_litellm_raw(url: &str) -> Option<RawPricingCache> {
     let agent: ureq::Agent = ureq::Agent::config_builder()
         .timeout_global(Some(FETCH_TIMEOUT))
         .build()
         .into();
 
     for attempt in 0..FETCH_RETRIES {
-        if let Ok(response) = agent.get(LITELLM_PRICING_URL).call() {
+        if let Ok(response) = agent.get(url).call() {
             let mut body = response.into_body();
             if let Ok(parsed) = serde_json::from_reader(body.as_reader()) {
                 return Some(parsed);
@@ -30,3 +118,280 @@ pub(super) fn fetch_litellm_raw() -> Option<HashMap<String, serde_json::Value>>
 
     None
 }
+
+/// Read a local catalog into the `LiteLLM` layout. Files ending in `.toml`
+/// are TOML; anything else is JSON.
+pub(super) fn load_catalog_file(
+    path: &Path,
+    schema: CatalogSchema,
+) -> Result<RawPricingCache, CatalogError> {
+    let content = fs::read_to_string(path).map_err(|source| CatalogError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let malformed = |message: String| CatalogError::Malformed {
+        path: path.to_path_buf(),
+        message,
+    };
+    let is_toml = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+    let value: serde_json::Value = if is_toml {
+        toml::from_str(&content).map_err(|error| malformed(error.to_string()))?
+    } else {
+        serde_json::from_str(&content).map_err(|error| malformed(error.to_string()))?
+    };
+    match schema {
+        CatalogSchema::Litellm => {
+            serde_json::from_value(value).map_err(|error| malformed(error.to_string()))
+        }
+        CatalogSchema::Ccstats => {
+            let catalog: CcstatsCatalog =
+                serde_json::from_value(value).map_err(|error| malformed(error.to_string()))?;
+            catalog.into_litellm().map_err(malformed)
+        }
+    }
+}
+
+/// ccstats catalog: USD per million tokens, keyed by model name.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CcstatsCatalog {
+    models: HashMap<String, CcstatsPrices>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CcstatsPrices {
+    input: f64,
+    output: f64,
+    #[serde(default)]
+    cache_read: Option<f64>,
+    #[serde(default)]
+    cache_write: Option<f64>,
+    #[serde(default)]
+    cache_write_1h: Option<f64>,
+    #[serde(default)]
+    reasoning_output: Option<f64>,
+    /// Rates for requests whose prompt exceeds `threshold` tokens.
+    #[serde(default)]
+    long_context: Option<CcstatsLongContext>,
+    #[serde(default)]
+    service_tiers: CcstatsServiceTiers,
+}
+
+/// Long-context tier; rates it omits keep the model's base rate.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CcstatsLongContext {
+    /// Prompt tokens above which the tier applies, in whole thousands.
+    threshold: i64,
+    #[serde(default)]
+    input: Option<f64>,
+    #[serde(default)]
+    output: Option<f64>,
+    #[serde(default)]
+    cache_read: Option<f64>,
+    #[serde(default)]
+    cache_write: Option<f64>,
+    #[serde(default)]
+    cache_write_1h: Option<f64>,
+    #[serde(default)]
+    reasoning_output: Option<f64>,
+}
+
+/// Published rates of the non-standard service tiers.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CcstatsServiceTiers {
+    #[serde(default)]
+    batch: Option<CcstatsTierPrices>,
+    #[serde(default)]
+    flex: Option<CcstatsTierPrices>,
+    #[serde(default)]
+    priority: Option<CcstatsTierPrices>,
+}
+
+/// One service tier; rates it omits are the base rates scaled by the tier's
+/// default multiplier.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CcstatsTierPrices {
+    #[serde(default)]
+    input: Option<f64>,
+    #[serde(default)]
+    output: Option<f64>,
+    #[serde(default)]
+    cache_read: Option<f64>,
+    #[serde(default)]
+    cache_write: Option<f64>,
+}
+
+const PER_TOKEN: f64 = 1e-6;
+
+/// Insert `{field}{suffix}` per-token rates for the prices that are set.
+fn insert_rates(
+    entry: &mut serde_json::Map<String, serde_json::Value>,
+    suffix: &str,
+    fields: &[(&str, Option<f64>)],
+) {
+    for &(field, per_million) in fields {
+        if let Some(per_million) = per_million {
+            entry.insert(format!("{field}{suffix}"), (per_million * PER_TOKEN).into());
+        }
+    }
+}
+
+impl CcstatsCatalog {
+    /// Convert to the `LiteLLM` layout, including its `*_above_<N>k_tokens`
+    /// long-context and `*_batches`/`_flex`/`_priority` service-tier keys.
+    fn into_litellm(self) -> Result<RawPricingCache, String> {
+        self.models
+            .into_iter()
+            .map(|(model, prices)| {
+                let mut entry = serde_json::Map::new();
+                insert_rates(
+                    &mut entry,
+                    "",
+                    &[
+                        ("input_cost_per_token", Some(prices.input)),
+                        ("output_cost_per_token", Some(prices.output)),
+                        ("cache_read_input_token_cost", prices.cache_read),
+                        ("cache_creation_input_token_cost", prices.cache_write),
+                        (
+                            "cache_creation_input_token_cost_above_1hr",
+                            prices.cache_write_1h,
+                        ),
+                        ("reasoning_output_cost_per_token", prices.reasoning_output),
+                    ],
+                );
+                if let Some(tier) = prices.long_context {
+                    if tier.threshold <= 0 || tier.threshold % 1000 != 0 {
+                        return Err(format!(
+                            "long_context threshold of '{model}' must be a positive multiple of 1000, got {}",
+                            tier.threshold
+                        ));
+                    }
+                    if tier.input.is_none() && tier.output.is_none() {
+                        return Err(format!(
+                            "long_context of '{model}' needs an input or output price"
+                        ));
+                    }
+                    insert_rates(
+                        &mut entry,
+                        &format!("_above_{}k_tokens", tier.threshold / 1000),
+                        &[
+                            ("input_cost_per_token", tier.input),
+                            ("output_cost_per_token", tier.output),
+                            ("cache_read_input_token_cost", tier.cache_read),
+                            ("cache_creation_input_token_cost", tier.cache_write),
+                            (
+                                "cache_creation_input_token_cost_above_1hr",
+                                tier.cache_write_1h,
+                            ),
+                            ("reasoning_output_cost_per_token", tier.reasoning_output),
+                        ],
+                    );
+                }
+                let tiers = prices.service_tiers;
+                for (name, suffix, tier) in [
+                    ("batch", "_batches", tiers.batch),
+                    ("flex", "_flex", tiers.flex),
+                    ("priority", "_priority", tiers.priority),
+                ] {
+                    let Some(tier) = tier else {
+                        continue;
+                    };
+                    if tier.input.is_none() && tier.output.is_none() {
+                        return Err(format!(
+                            "service_tiers.{name} of '{model}' needs an input or output price"
+                        ));
+                    }
+                    insert_rates(
+                        &mut entry,
+                        suffix,
+                        &[
+                            ("input_cost_per_token", tier.input),
+                            ("output_cost_per_token", tier.output),
+                            ("cache_read_input_token_cost", tier.cache_read),
+                            ("cache_creation_input_token_cost", tier.cache_write),
+                        ],
+                    );
+                }
+                Ok((model, serde_json::Value::Object(entry)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn providers_deserialize_from_config_tables() {
+        #[derive(Deserialize)]
+        struct Chain {
+            providers: Vec<PricingProvider>,
+        }
+        let chain: Chain = toml::from_str(
+            r#"providers = [
+                { kind = "file", path = "/etc/prices.toml", schema = "ccstats", name = "internal" },
+                { kind = "litellm", url = "https://mirror.example/prices.json" },
+                { kind = "litellm" },
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(chain.providers[0].label(), "internal");
+        assert_eq!(
+            chain.providers[1].label(),
+            "litellm (https://mirror.example/prices.json)"
+        );
+        assert_eq!(chain.providers[2], PricingProvider::default());
+        assert!(toml::from_str::<Chain>(r#"providers = [{ kind = "ftp" }]"#).is_err());
+    }
+
+    #[test]
+    fn mirror_urls_get_their_own_cache_file() {
+        assert_eq!(litellm_cache_file(LITELLM_PRICING_URL), "pricing.json");
+        let mirror = litellm_cache_file("https://mirror.example/prices.json");
+        assert!(mirror.starts_with("pricing-"));
+        assert_eq!(mirror.len(), "pricing-.json".len() + 16);
+        assert_ne!(
+            mirror,
+            litellm_cache_file("https://other.example/prices.json")
+        );
+    }
+
+    #[test]
+    fn ccstats_toml_catalog_converts_per_million_prices() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("prices.toml");
+        fs::write(
+            &path,
+            "[models.internal-llm]\ninput = 2.0\noutput = 8.0\ncache_read = 0.5\n",
+        )
+        .unwrap();
+        let raw = load_catalog_file(&path, CatalogSchema::Ccstats).unwrap();
+        let entry = &raw["internal-llm"];
+        assert!((entry["input_cost_per_token"].as_f64().unwrap() - 2e-6).abs() < 1e-15);
+        assert!((entry["output_cost_per_token"].as_f64().unwrap() - 8e-6).abs() < 1e-15);
+        assert!((entry["cache_read_input_token_cost"].as_f64().unwrap() - 5e-7).abs() < 1e-15);
+        assert!(entry.get("cache_creation_input_token_cost").is_none());
+    }
+
+    #[test]
+    fn litellm_json_catalog_loads_as_is_and_errors_name_the_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("prices.json");
+        fs::write(&path, r#"{"internal-llm":{"input_cost_per_token":1e-6}}"#).unwrap();
+        let raw = load_catalog_file(&path, CatalogSchema::Litellm).unwrap();
+        assert!(raw.contains_key("internal-llm"));
+
+        let error = load_catalog_file(&path, CatalogSchema::Ccstats).unwrap_err();
+        assert!(error.to_string().contains("prices.json"), "{error}");
+        let missing = load_catalog_file(&dir.path().join("nope.json"), CatalogSchema::Litellm);
+        assert!(matches!(missing, Err(CatalogError::Read { .. })));
+    }
+}
@@ -1,13 +1,22 @@
-use super::super::types::ModelPricing;
+use super::super::types::{LongContextTier, ModelPricing};
+use crate::core::{LONG_CONTEXT_THRESHOLD, ServiceTier};
 
 fn openai_pricing(input: f64, output: f64, cache_read: f64) -> ModelPricing {
-    ModelPricing {
+    let standard = ModelPricing {
         input,
         output,
         reasoning_output: output,
         cache_create: 0.0,
         cache_create_1h: 0.0,
         cache_read,
+        long_context: None,
+        service_tiers: Vec::new(),
+    };
+    // OpenAI bills the priority tier at twice the standard rates.
+    let priority = standard.scaled(2.0);
+    ModelPricing {
+        service_tiers: vec![(ServiceTier::Priority, priority)],
+        ..standard
     }
 }
 
@@ -19,6 +28,8 @@ fn xai_pricing(input: f64, output: f64, cache_read: f64) -> ModelPricing {
         cache_create: 0.0,
         cache_create_1h: 0.0,
         cache_read,
+        long_context: None,
+        service_tiers: Vec::new(),
     }
 }
 
@@ -30,6 +41,8 @@ fn moonshot_pricing(input: f64, output: f64, cache_read: f64) -> ModelPricing {
         cache_create: 0.0,
         cache_create_1h: 0.0,
         cache_read,
+        long_context: None,
+        service_tiers: Vec::new(),
     }
 }
 
@@ -44,6 +57,8 @@ pub(crate) fn fallback_pricing(model: &str) -> Option<ModelPricing> {
                 cache_create: 6.25e-6,  // $6.25/M
                 cache_create_1h: 10e-6, // $10/M (2x input)
                 cache_read: 0.5e-6,     // $0.5/M
+                long_context: None,
+                service_tiers: Vec::new(),
             }
         } else if model_lower.contains("opus") {
             ModelPricing {
@@ -53,6 +68,8 @@ pub(crate) fn fallback_pricing(model: &str) -> Option<ModelPricing> {
                 cache_create: 18.75e-6,
                 cache_create_1h: 30e-6,
                 cache_read: 1.5e-6,
+                long_context: None,
+                service_tiers: Vec::new(),
             }
         } else if model_lower.contains("sonnet") {
             ModelPricing {
@@ -62,6 +79,24 @@ pub(crate) fn fallback_pricing(model: &str) -> Option<ModelPricing> {
                 cache_create: 3.75e-6,
                 cache_create_1h: 6e-6,
                 cache_read: 0.3e-6,
+                // Sonnet 4 models bill 1M-context requests above 200K
+                // prompt tokens at a premium.
+                long_context: model_lower.contains("sonnet-4").then(|| {
+                    Box::new(LongContextTier {
+                        threshold: LONG_CONTEXT_THRESHOLD,
+                        rates: ModelPricing {
+                            input: 6e-6,     // $6/M
+                            output: 22.5e-6, // $22.5/M
+                            reasoning_output: 22.5e-6,
+                            cache_create: 7.5e-6,
+                            cache_create_1h: 12e-6,
+                            cache_read: 0.6e-6,
+                            long_context: None,
+                            service_tiers: Vec::new(),
+                        },
+                    })
+                }),
+                service_tiers: Vec::new(),
             }
         } else if model_lower.contains("haiku") {
             ModelPricing {
@@ -71,6 +106,8 @@ pub(crate) fn fallback_pricing(model: &str) -> Option<ModelPricing> {
                 cache_create: 1e-6,
                 cache_create_1h: 1.6e-6,
                 cache_read: 0.08e-6,
+                long_context: None,
+                service_tiers: Vec::new(),
             }
         } else if model_lower.contains("grok-build") {
             xai_pricing(1e-6, 2e-6, 0.2e-6)
@@ -131,6 +168,11 @@ mod tests {
         let p = fallback_pricing("claude-sonnet-4-20250514").unwrap();
         assert_eq!(p.input, 3e-6);
         assert_eq!(p.output, 15e-6);
+        let tier = p.long_context.as_deref().unwrap();
+        assert_eq!(tier.threshold, 200_000);
+        assert_eq!(tier.rates.input, 6e-6);
+        let legacy = fallback_pricing("claude-3-5-sonnet-20241022").unwrap();
+        assert!(legacy.long_context.is_none());
     }
 
     #[test]
@@ -3,5 +3,7 @@ mod parse;
 mod resolve;
 
 pub(crate) use fallback::fallback_pricing;
-pub(crate) use parse::parse_litellm_data;
-pub(crate) use resolve::{resolve_pricing_known, resolve_pricing_known_with_key};
+pub(crate) use parse::{parse_catalog_data, parse_litellm_data};
+pub(crate) use resolve::{
+    resolve_pricing_exact, resolve_pricing_known, resolve_pricing_known_with_key,
+};
@@ -1,9 +1,130 @@
 use std::collections::HashMap;
 
-use super::super::types::{ModelPricing, dot_version_variant};
+use super::super::types::{
+    LongContextTier, ModelPricing, dot_version_variant, service_tier_multiplier,
+};
+use crate::core::ServiceTier;
+
+/// Prompt-size threshold of a `LiteLLM` tiered rate key such as
+/// `input_cost_per_token_above_200k_tokens` (200,000).
+fn long_context_threshold(key: &str) -> Option<i64> {
+    let thousands = key
+        .strip_prefix("input_cost_per_token_above_")
+        .or_else(|| key.strip_prefix("output_cost_per_token_above_"))?
+        .strip_suffix("k_tokens")?;
+    thousands
+        .parse::<i64>()
+        .ok()
+        .filter(|thousands| *thousands > 0)
+        .map(|thousands| thousands * 1000)
+}
+
+/// Long-context tier of a `LiteLLM` entry, from its `*_above_<N>k_tokens`
+/// rates. When several tiers are published the lowest threshold is used.
+/// Rates the tier does not publish keep their base value; an entry without
+/// tiered input or output rates has no tier.
+fn parse_long_context_tier(
+    value: &serde_json::Value,
+    base: &ModelPricing,
+) -> Option<LongContextTier> {
+    let threshold = value
+        .as_object()?
+        .keys()
+        .filter_map(|key| long_context_threshold(key))
+        .min()?;
+    let suffix = format!("_above_{}k_tokens", threshold / 1000);
+    let rate = |field: &str| {
+        value
+            .get(format!("{field}{suffix}"))
+            .and_then(serde_json::Value::as_f64)
+    };
+    let input = rate("input_cost_per_token");
+    let output = rate("output_cost_per_token");
+    if input.is_none() && output.is_none() {
+        return None;
+    }
+    let output = output.unwrap_or(base.output);
+    let cache_create = rate("cache_creation_input_token_cost").unwrap_or(base.cache_create);
+    Some(LongContextTier {
+        threshold,
+        rates: ModelPricing {
+            input: input.unwrap_or(base.input),
+            output,
+            reasoning_output: rate("reasoning_output_cost_per_token").unwrap_or(output),
+            cache_read: rate("cache_read_input_token_cost").unwrap_or(base.cache_read),
+            cache_create,
+            // The 1-hour write rate is never below the 5-minute one.
+            cache_create_1h: rate("cache_creation_input_token_cost_above_1hr")
+                .unwrap_or(base.cache_create_1h.max(cache_create)),
+            long_context: None,
+            service_tiers: Vec::new(),
+        },
+    })
+}
+
+/// `LiteLLM` suffixes for service-tier-specific rates.
+const SERVICE_TIER_SUFFIXES: [(ServiceTier, &str); 3] = [
+    (ServiceTier::Batch, "_batches"),
+    (ServiceTier::Flex, "_flex"),
+    (ServiceTier::Priority, "_priority"),
+];
+
+/// Published service-tier rates of a `LiteLLM` entry. Rates a tier does not
+/// publish are the base rates scaled by the tier's default multiplier.
+fn parse_service_tiers(
+    value: &serde_json::Value,
+    base: &ModelPricing,
+) -> Vec<(ServiceTier, ModelPricing)> {
+    SERVICE_TIER_SUFFIXES
+        .into_iter()
+        .filter_map(|(tier, suffix)| {
+            let rate = |field: &str| {
+                value
+                    .get(format!("{field}{suffix}"))
+                    .and_then(serde_json::Value::as_f64)
+            };
+            let input = rate("input_cost_per_token");
+            let output = rate("output_cost_per_token");
+            if input.is_none() && output.is_none() {
+                return None;
+            }
+            let mut pricing = base.scaled(service_tier_multiplier(tier));
+            if let Some(input) = input {
+                pricing.input = input;
+            }
+            if let Some(output) = output {
+                pricing.output = output;
+                pricing.reasoning_output = output;
+            }
+            if let Some(cache_read) = rate("cache_read_input_token_cost") {
+                pricing.cache_read = cache_read;
+            }
+            if let Some(cache_create) = rate("cache_creation_input_token_cost") {
+                pricing.cache_create = cache_create;
+                pricing.cache_create_1h = pricing.cache_create_1h.max(cache_create);
+            }
+            Some((tier, pricing))
+        })
+        .collect()
+}
 
 pub(crate) fn parse_litellm_data(
     data: HashMap<String, serde_json::Value>,
+) -> HashMap<String, ModelPricing> {
+    parse_price_list(data, false)
+}
+
+/// Parse a configured price catalog in the `LiteLLM` layout. Unlike the
+/// public `LiteLLM` list, every priced model is kept, whatever its vendor.
+pub(crate) fn parse_catalog_data(
+    data: HashMap<String, serde_json::Value>,
+) -> HashMap<String, ModelPricing> {
+    parse_price_list(data, true)
+}
+
+fn parse_price_list(
+    data: HashMap<String, serde_json::Value>,
+    all_models: bool,
 ) -> HashMap<String, ModelPricing> {
     let mut models = HashMap::new();
 
@@ -20,7 +141,7 @@ pub(crate) fn parse_litellm_data(
             || name.starts_with("moonshot/")
             || name.contains("kimi");
 
-        if !is_claude && !is_openai && !is_xai && !is_cn {
+        if !all_models && !is_claude && !is_openai && !is_xai && !is_cn {
             continue;
         }
 
@@ -47,7 +168,7 @@ pub(crate) fn parse_litellm_data(
             .and_then(serde_json::Value::as_f64)
             .unwrap_or(0.0);
 
-        let pricing = ModelPricing {
+        let mut pricing = ModelPricing {
             input,
             output,
             reasoning_output,
@@ -60,7 +181,11 @@ pub(crate) fn parse_litellm_data(
                 .get("cache_creation_input_token_cost_above_1hr")
                 .and_then(serde_json::Value::as_f64)
                 .unwrap_or(cache_create),
+            long_context: None,
+            service_tiers: Vec::new(),
         };
+        pricing.long_context = parse_long_context_tier(&value, &pricing).map(Box::new);
+        pricing.service_tiers = parse_service_tiers(&value, &pricing);
 
         // Store with multiple key variations for matching
         models.insert(name.clone(), pricing.clone());
@@ -109,6 +234,7 @@ pub(crate) fn parse_litellm_data(
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod tests {
+    use super::super::super::provider::{CatalogSchema, load_catalog_file};
     use super::*;
     use serde_json::json;
 
@@ -129,6 +255,17 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn test_parse_catalog_keeps_every_vendor() {
+        let mut data = HashMap::new();
+        data.insert("internal-llm".to_string(), make_litellm_entry(1e-6, 2e-6));
+        data.insert("google/gemini".to_string(), make_litellm_entry(1e-6, 2e-6));
+
+        let result = parse_catalog_data(data);
+        assert!(result.contains_key("internal-llm"));
+        assert!(result.contains_key("google/gemini"));
+    }
+
     #[test]
     fn test_parse_cache_create_1h_rate() {
         let mut data = HashMap::new();
@@ -165,6 +302,141 @@ mod tests {
         assert_eq!(pricing.cache_create_1h, 3.75e-6);
     }
 
+    #[test]
+    fn test_parse_long_context_tier() {
+        let mut data = HashMap::new();
+        data.insert(
+            "claude-sonnet-4-5".to_string(),
+            json!({
+                "input_cost_per_token": 3e-6,
+                "output_cost_per_token": 15e-6,
+                "cache_read_input_token_cost": 0.3e-6,
+                "cache_creation_input_token_cost": 3.75e-6,
+                "input_cost_per_token_above_200k_tokens": 6e-6,
+                "output_cost_per_token_above_200k_tokens": 22.5e-6,
+                "cache_read_input_token_cost_above_200k_tokens": 0.6e-6,
+                "cache_creation_input_token_cost_above_200k_tokens": 7.5e-6,
+            }),
+        );
+        data.insert(
+            "claude-haiku-4-5".to_string(),
+            make_litellm_entry(1e-6, 5e-6),
+        );
+
+        data.insert(
+            "gpt-5.4".to_string(),
+            json!({
+                "input_cost_per_token": 2.5e-6,
+                "output_cost_per_token": 15e-6,
+                "input_cost_per_token_above_272k_tokens": 5e-6,
+                "output_cost_per_token_above_272k_tokens": 22.5e-6,
+            }),
+        );
+
+        let result = parse_litellm_data(data);
+        let tier = result["sonnet-4-5"].long_context.as_deref().unwrap();
+        assert_eq!(tier.threshold, 200_000);
+        assert_eq!(tier.rates.input, 6e-6);
+        assert_eq!(tier.rates.output, 22.5e-6);
+        assert_eq!(tier.rates.reasoning_output, 22.5e-6);
+        assert_eq!(tier.rates.cache_read, 0.6e-6);
+        assert_eq!(tier.rates.cache_create_1h, 7.5e-6);
+        let gpt = result["gpt-5.4"].long_context.as_deref().unwrap();
+        assert_eq!(gpt.threshold, 272_000);
+        assert_eq!(gpt.rates.input, 5e-6);
+        assert!(result["claude-haiku-4-5"].long_context.is_none());
+    }
+
+    #[test]
+    fn test_parse_service_tier_rates() {
+        let mut data = HashMap::new();
+        data.insert(
+            "gpt-5".to_string(),
+            json!({
+                "input_cost_per_token": 1.25e-6,
+                "output_cost_per_token": 10e-6,
+                "cache_read_input_token_cost": 0.125e-6,
+                "input_cost_per_token_flex": 0.625e-6,
+                "output_cost_per_token_flex": 5e-6,
+                "input_cost_per_token_priority": 2.5e-6,
+                "output_cost_per_token_priority": 20e-6,
+                "cache_read_input_token_cost_priority": 0.25e-6,
+            }),
+        );
+
+        let result = parse_litellm_data(data);
+        let pricing = &result["gpt-5"];
+        let flex = pricing.for_service_tier(ServiceTier::Flex);
+        assert_eq!(flex.output, 5e-6);
+        // Unpublished flex cache-read rate: half the standard rate.
+        assert_eq!(flex.cache_read, 0.0625e-6);
+        assert_eq!(
+            pricing.for_service_tier(ServiceTier::Priority).cache_read,
+            0.25e-6
+        );
+        // No published batch rates: the default batch discount.
+        assert_eq!(pricing.for_service_tier(ServiceTier::Batch).input, 0.625e-6);
+    }
+
+    #[test]
+    fn test_parse_ccstats_catalog_tiers_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("prices.toml");
+        std::fs::write(
+            &path,
+            r"
+[models.internal-llm]
+input = 2.0
+output = 8.0
+cache_read = 0.5
+
+[models.internal-llm.long_context]
+threshold = 128000
+input = 4.0
+output = 12.0
+
+[models.internal-llm.service_tiers.batch]
+input = 1.0
+output = 4.0
+
+[models.internal-llm.service_tiers.priority]
+input = 3.5
+output = 14.0
+cache_read = 0.9
+",
+        )
+        .unwrap();
+
+        let raw = load_catalog_file(&path, CatalogSchema::Ccstats).unwrap();
+        let result = parse_catalog_data(raw);
+        let pricing = &result["internal-llm"];
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-15;
+        let tier = pricing.long_context.as_deref().unwrap();
+        assert_eq!(tier.threshold, 128_000);
+        assert!(close(tier.rates.input, 4e-6));
+        assert!(close(tier.rates.output, 12e-6));
+        // Unpublished tier rates keep the base rate.
+        assert!(close(tier.rates.cache_read, 0.5e-6));
+        let batch = pricing.for_service_tier(ServiceTier::Batch);
+        assert!(close(batch.input, 1e-6));
+        assert!(close(batch.output, 4e-6));
+        // Unpublished batch cache-read rate: the default batch discount.
+        assert!(close(batch.cache_read, 0.25e-6));
+        assert!(close(
+            pricing.for_service_tier(ServiceTier::Priority).cache_read,
+            0.9e-6
+        ));
+        assert_eq!(pricing.service_tiers.len(), 2);
+
+        std::fs::write(
+            &path,
+            "[models.m]\ninput = 1.0\noutput = 2.0\n[models.m.long_context]\nthreshold = 1500\ninput = 2.0\n",
+        )
+        .unwrap();
+        let error = load_catalog_file(&path, CatalogSchema::Ccstats).unwrap_err();
+        assert!(error.to_string().contains("multiple of 1000"), "{error}");
+    }
+
     #[test]
     fn test_parse_claude_model() {
         let mut data = HashMap::new();
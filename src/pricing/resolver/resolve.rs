@@ -15,6 +15,18 @@ pub(crate) fn resolve_pricing_known(
     resolve_pricing_known_with_key(model, models).map(|resolved| resolved.pricing)
 }
 
+/// Price of `model` only when a catalog key matches it exactly (ignoring
+/// case), before any normalization or date handling.
+pub(crate) fn resolve_pricing_exact(
+    model: &str,
+    models: &HashMap<String, ModelPricing>,
+) -> Option<ModelPricing> {
+    match resolve_direct_exact(model, &model.to_lowercase(), models) {
+        PricingResolution::Resolved(resolved) => Some(resolved.pricing),
+        PricingResolution::Ambiguous | PricingResolution::NoMatch => None,
+    }
+}
+
 pub(crate) fn resolve_pricing_known_with_key(
     model: &str,
     models: &HashMap<String, ModelPricing>,
@@ -6,6 +6,8 @@ pub(crate) enum PricingSource {
     Cache,
     CacheStale,
     Fallback,
+    /// Configured local price catalog.
+    Catalog,
     Recorded,
     Unknown,
     Mixed,
@@ -18,6 +20,7 @@ impl PricingSource {
             PricingSource::Cache => "cache",
             PricingSource::CacheStale => "cache_stale",
             PricingSource::Fallback => "fallback",
+            PricingSource::Catalog => "catalog",
             PricingSource::Recorded => "recorded",
             PricingSource::Unknown => "unknown",
             PricingSource::Mixed => "mixed",
@@ -1,3 +1,5 @@
+use crate::core::ServiceTier;
+
 /// Model pricing info (per token, not per million)
 #[derive(Debug, Clone, Default)]
 pub(super) struct ModelPricing {
@@ -9,6 +11,62 @@ pub(super) struct ModelPricing {
     /// Cache creation with 1-hour TTL (falls back to `cache_create` when the
     /// provider does not publish a separate 1h rate).
     pub(super) cache_create_1h: f64,
+    /// Rates for requests above the model's long-context threshold, when the
+    /// model is priced in tiers.
+    pub(super) long_context: Option<Box<LongContextTier>>,
+    /// Published rates for non-standard service tiers. Tiers without an entry
+    /// are priced with [`service_tier_multiplier`].
+    pub(super) service_tiers: Vec<(ServiceTier, ModelPricing)>,
+}
+
+/// Rates billed for every token of a request whose prompt (input plus cache
+/// writes and reads) exceeds `threshold` tokens.
+#[derive(Debug, Clone)]
+pub(super) struct LongContextTier {
+    pub(super) threshold: i64,
+    pub(super) rates: ModelPricing,
+}
+
+/// Discount or premium applied to the standard rates for a service tier the
+/// price list does not cover: batch and flex requests cost half, priority
+/// requests are billed at list price unless the model publishes a premium.
+pub(super) fn service_tier_multiplier(tier: ServiceTier) -> f64 {
+    match tier {
+        ServiceTier::Batch | ServiceTier::Flex => 0.5,
+        ServiceTier::Standard | ServiceTier::Priority => 1.0,
+    }
+}
+
+impl ModelPricing {
+    /// Every rate (including the long-context tier) multiplied by `factor`.
+    pub(super) fn scaled(&self, factor: f64) -> ModelPricing {
+        ModelPricing {
+            input: self.input * factor,
+            output: self.output * factor,
+            reasoning_output: self.reasoning_output * factor,
+            cache_read: self.cache_read * factor,
+            cache_create: self.cache_create * factor,
+            cache_create_1h: self.cache_create_1h * factor,
+            long_context: self.long_context.as_ref().map(|tier| {
+                Box::new(LongContextTier {
+                    threshold: tier.threshold,
+                    rates: tier.rates.scaled(factor),
+                })
+            }),
+            service_tiers: Vec::new(),
+        }
+    }
+
+    /// Rates for requests billed under `tier`.
+    pub(super) fn for_service_tier(&self, tier: ServiceTier) -> ModelPricing {
+        self.service_tiers
+            .iter()
+            .find(|(published, _)| *published == tier)
+            .map_or_else(
+                || self.scaled(service_tier_multiplier(tier)),
+                |(_, pricing)| pricing.clone(),
+            )
+    }
 }
 
 /// Normalize version separators: some hosters spell version dots as `p`
@@ -0,0 +1,494 @@
+//! Handler and renderers for the `pricing` subcommand (catalog inspection).
+//!
+//! Lives in its own module to keep `app.rs` under the module size limit.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use crate::app::{CommandContext, print_json_report, print_json_rows};
+use crate::cli::PricingCommands;
+use crate::output::{
+    CatalogEntryJson, OutputFormat, PriceChangeJson, PriceQuoteJson, PricingDiffJson,
+    PricingRefreshJson, RatesJson, SCHEMA_VERSION, format_rate, print_catalog_table,
+};
+use crate::pricing::{
+    CatalogDiff, CatalogEntry, PriceChange, PriceQuote, PriceRates, PricingSource, RefreshReport,
+    diff_litellm, litellm_providers, refresh_litellm,
+};
+use crate::source::{Source, all_sources, load_daily};
+
+pub(crate) fn handle_pricing(source: Option<&dyn Source>, ctx: &CommandContext<'_>) {
+    let Some(command) = ctx.cli.pricing_command() else {
+        return;
+    };
+    match command {
+        PricingCommands::Show { model } => render_quote(&ctx.pricing_db.quote(model), ctx),
+        PricingCommands::List { pattern } => {
+            if ctx.pricing_db.source() == PricingSource::Fallback
+                && !matches!(
+                    ctx.cli.output_format(),
+                    OutputFormat::Json | OutputFormat::Csv
+                )
+            {
+                ctx.cli.output_format().markup().line(
+                    "No price catalog loaded; only built-in fallback prices apply.\nHint: run without --offline or `ccstats pricing refresh` to fetch the LiteLLM price list.",
+                );
+                return;
+            }
+            render_entries(&ctx.pricing_db.catalog_entries(pattern.as_deref()), ctx);
+        }
+        PricingCommands::Refresh => handle_refresh(ctx),
+        PricingCommands::Diff => handle_diff(source, ctx),
+    }
+}
+
+fn require_network(ctx: &CommandContext<'_>, view: &str) {
+    if ctx.cli.offline {
+        eprintln!("Error: `pricing {view}` downloads the price list; drop --offline");
+        std::process::exit(1);
+    }
+}
+
+/// `LiteLLM` providers of the chain, or `None` after telling the user the
+/// chain only has local catalogs.
+fn litellm_chain(ctx: &CommandContext<'_>) -> Option<Vec<(String, String)>> {
+    let providers = litellm_providers(&ctx.cli.pricing_providers);
+    if providers.is_empty() {
+        println!(
+            "The pricing chain has no LiteLLM provider; local catalogs are read on every run."
+        );
+        return None;
+    }
+    Some(providers)
+}
+
+fn handle_refresh(ctx: &CommandContext<'_>) {
+    require_network(ctx, "refresh");
+    let Some(providers) = litellm_chain(ctx) else {
+        return;
+    };
+    let mut reports = Vec::new();
+    let mut failed = false;
+    for (label, url) in &providers {
+        match refresh_litellm(label, url) {
+            Ok(report) => reports.push(report),
+            Err(error) => {
+                eprintln!("Error: {error}");
+                failed = true;
+            }
+        }
+    }
+    render_refresh(&reports, ctx);
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn handle_diff(source: Option<&dyn Source>, ctx: &CommandContext<'_>) {
+    require_network(ctx, "diff");
+    let Some(providers) = litellm_chain(ctx) else {
+        return;
+    };
+    let models = used_models(source, ctx);
+    let mut diffs = Vec::new();
+    let mut failed = false;
+    for (label, url) in &providers {
+        match diff_litellm(label, url, &models) {
+            Ok(diff) => diffs.push(diff),
+            Err(error) => {
+                eprintln!("Error: {error}");
+                failed = true;
+            }
+        }
+    }
+    render_diffs(&diffs, models.len(), ctx);
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// Model names seen in the selected date range of one source, or of every
+/// source for `--source all`.
+fn used_models(source: Option<&dyn Source>, ctx: &CommandContext<'_>) -> Vec<String> {
+    let sources: Vec<&dyn Source> = match source {
+        Some(source) => vec![source],
+        None => all_sources().collect(),
+    };
+    let mut models = BTreeSet::new();
+    for source in sources {
+        let result = load_daily(source, ctx.filter, ctx.timezone, true, ctx.cli.debug);
+        for day in result.day_stats.values() {
+            models.extend(day.models.keys().cloned());
+        }
+    }
+    models.into_iter().collect()
+}
+
+fn format_age(seconds: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let hours = seconds as f64 / 3600.0;
+    format!("{hours:.1}h old")
+}
+
+fn rates_csv(rates: Option<&PriceRates>) -> String {
+    rates.map_or_else(
+        || ",,,,,".to_string(),
+        |rates| {
+            rates
+                .fields()
+                .iter()
+                .map(|(_, value)| value.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        },
+    )
+}
+
+const RATES_CSV_HEADER: &str =
+    "input,output,reasoning_output,cache_read,cache_write,cache_write_1h";
+
+fn render_quote(quote: &PriceQuote, ctx: &CommandContext<'_>) {
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => {
+            println!("model,matched_key,pricing_source,pricing_catalog,{RATES_CSV_HEADER}");
+            println!(
+                "{},{},{},{},{}",
+                quote.model,
+                quote.matched_key.as_deref().unwrap_or_default(),
+                quote.source.as_str(),
+                quote.catalog.as_deref().unwrap_or_default(),
+                rates_csv(quote.rates.as_ref())
+            );
+        }
+        OutputFormat::Json => {
+            let json = PriceQuoteJson {
+                schema_version: SCHEMA_VERSION,
+                model: quote.model.clone(),
+                matched_key: quote.matched_key.clone(),
+                pricing_source: quote.source.as_str(),
+                pricing_catalog: quote.catalog.clone(),
+                rates_usd_per_million: quote.rates.as_ref().map(RatesJson::from),
+                long_context_threshold_tokens: quote.long_context.map(|tier| tier.threshold),
+                long_context_rates_usd_per_million: quote
+                    .long_context
+                    .as_ref()
+                    .map(|tier| RatesJson::from(&tier.rates)),
+            };
+            print_json_report(&json, false, ctx);
+        }
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            print!("{}", quote_text(quote));
+        }
+    }
+}
+
+fn quote_text(quote: &PriceQuote) -> String {
+    let mut out = String::new();
+    let Some(rates) = &quote.rates else {
+        let _ = writeln!(
+            out,
+            "{}: no price in any catalog ({})",
+            quote.model,
+            quote.source.as_str()
+        );
+        return out;
+    };
+    let _ = write!(
+        out,
+        "{} -> {} ({})",
+        quote.model,
+        quote.matched_key.as_deref().unwrap_or("no match"),
+        quote.source.as_str()
+    );
+    if let Some(catalog) = &quote.catalog {
+        let _ = write!(out, " [{catalog}]");
+    }
+    out.push('\n');
+    for (field, value) in rates.fields() {
+        let _ = writeln!(out, "  {field:<17} {}", format_rate(value));
+    }
+    if let Some(long_context) = &quote.long_context {
+        let _ = writeln!(
+            out,
+            "  Above {}k prompt tokens:",
+            long_context.threshold / 1000
+        );
+        for (field, value) in long_context.rates.fields() {
+            let _ = writeln!(out, "    {field:<17} {}", format_rate(value));
+        }
+    }
+    out
+}
+
+fn render_entries(entries: &[CatalogEntry], ctx: &CommandContext<'_>) {
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => {
+            println!("model,pricing_catalog,pricing_source,{RATES_CSV_HEADER}");
+            for entry in entries {
+                println!(
+                    "{},{},{},{}",
+                    entry.key,
+                    entry.catalog,
+                    entry.source.as_str(),
+                    rates_csv(Some(&entry.rates))
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let rows = entries.iter().map(|entry| CatalogEntryJson {
+                schema_version: SCHEMA_VERSION,
+                model: entry.key.clone(),
+                pricing_catalog: entry.catalog.clone(),
+                pricing_source: entry.source.as_str(),
+                rates_usd_per_million: RatesJson::from(&entry.rates),
+                long_context_threshold_tokens: entry.long_context.map(|tier| tier.threshold),
+                long_context_rates_usd_per_million: entry
+                    .long_context
+                    .as_ref()
+                    .map(|tier| RatesJson::from(&tier.rates)),
+            });
+            print_json_rows(rows, false, ctx);
+        }
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            print_catalog_table(
+                entries,
+                ctx.locale,
+                ctx.cli.use_color(),
+                ctx.cli.output_format().markup(),
+            );
+        }
+    }
+}
+
+fn render_refresh(reports: &[RefreshReport], ctx: &CommandContext<'_>) {
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => {
+            println!("pricing_catalog,models,previous_cache_age_seconds,cache_path");
+            for report in reports {
+                println!(
+                    "{},{},{},{}",
+                    report.label,
+                    report.models,
+                    report
+                        .previous_age_seconds
+                        .map(|age| age.to_string())
+                        .unwrap_or_default(),
+                    report.cache_path.as_deref().unwrap_or_default()
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let rows = reports.iter().map(|report| PricingRefreshJson {
+                schema_version: SCHEMA_VERSION,
+                pricing_catalog: report.label.clone(),
+                models: report.models,
+                previous_cache_age_seconds: report.previous_age_seconds,
+                cache_path: report.cache_path.clone(),
+                cache_error: report.save_error.clone(),
+            });
+            print_json_rows(rows, false, ctx);
+        }
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            for report in reports {
+                let previous = report.previous_age_seconds.map_or_else(
+                    || "no previous cache".to_string(),
+                    |age| format!("replaced cache {}", format_age(age)),
+                );
+                println!(
+                    "{}: fetched {} models; {previous}",
+                    report.label, report.models
+                );
+                match (&report.save_error, &report.cache_path) {
+                    (Some(error), _) => eprintln!("Warning: failed to save pricing cache: {error}"),
+                    (None, Some(path)) => println!("  cache: {path}"),
+                    (None, None) => {}
+                }
+            }
+        }
+    }
+}
+
+fn render_diffs(diffs: &[CatalogDiff], model_count: usize, ctx: &CommandContext<'_>) {
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => {
+            println!("pricing_catalog,model,field,cached,fresh");
+            for diff in diffs {
+                for change in &diff.changes {
+                    println!(
+                        "{},{},{},{},{}",
+                        diff.label,
+                        change.model,
+                        change.field,
+                        change
+                            .cached
+                            .map(|rate| rate.to_string())
+                            .unwrap_or_default(),
+                        change
+                            .fresh
+                            .map(|rate| rate.to_string())
+                            .unwrap_or_default()
+                    );
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let rows = diffs.iter().map(|diff| PricingDiffJson {
+                schema_version: SCHEMA_VERSION,
+                pricing_catalog: diff.label.clone(),
+                cache_age_seconds: diff.cache_age_seconds,
+                models_compared: model_count,
+                changes: diff.changes.iter().map(change_json).collect(),
+            });
+            print_json_rows(rows, false, ctx);
+        }
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            for diff in diffs {
+                print!("{}", diff_text(diff, model_count));
+            }
+        }
+    }
+}
+
+fn change_json(change: &PriceChange) -> PriceChangeJson {
+    PriceChangeJson {
+        model: change.model.clone(),
+        field: change.field,
+        cached_usd_per_million: change.cached,
+        fresh_usd_per_million: change.fresh,
+    }
+}
+
+fn diff_text(diff: &CatalogDiff, model_count: usize) -> String {
+    let cache = diff.cache_age_seconds.map_or_else(
+        || "no cache".to_string(),
+        |age| format!("cache {}", format_age(age)),
+    );
+    let mut out = String::new();
+    if diff.changes.is_empty() {
+        let _ = writeln!(
+            out,
+            "{}: no price changes for the {model_count} models used ({cache})",
+            diff.label
+        );
+        return out;
+    }
+    let _ = writeln!(
+        out,
+        "{}: {} changed prices across the {model_count} models used ({cache})",
+        diff.label,
+        diff.changes.len()
+    );
+    let width = diff
+        .changes
+        .iter()
+        .map(|change| change.model.len())
+        .max()
+        .unwrap_or_default();
+    for change in &diff.changes {
+        let rate = |rate: Option<f64>| rate.map_or_else(|| "-".to_string(), format_rate);
+        let _ = writeln!(
+            out,
+            "  {:<width$}  {:<16}  {} -> {}",
+            change.model,
+            change.field,
+            rate(change.cached),
+            rate(change.fresh)
+        );
+    }
+    out.push_str("Run `ccstats pricing refresh` to update the cache.\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rates(input: f64, output: f64) -> PriceRates {
+        PriceRates {
+            input,
+            output,
+            reasoning_output: output,
+            cache_read: input / 10.0,
+            cache_write: input * 1.25,
+            cache_write_1h: input * 2.0,
+        }
+    }
+
+    #[test]
+    fn quote_text_shows_match_source_catalog_and_rates() {
+        let quote = PriceQuote {
+            model: "sonnet-4".to_string(),
+            matched_key: Some("claude-sonnet-4-20250514".to_string()),
+            source: PricingSource::Cache,
+            catalog: Some("litellm".to_string()),
+            rates: Some(rates(3.0, 15.0)),
+            long_context: None,
+        };
+        let text = quote_text(&quote);
+        assert!(
+            text.starts_with("sonnet-4 -> claude-sonnet-4-20250514 (cache) [litellm]\n"),
+            "{text}"
+        );
+        assert!(text.contains("  input             $3.00/M\n"), "{text}");
+        assert!(text.contains("  cache_read        $0.30/M\n"), "{text}");
+
+        let unknown = PriceQuote {
+            model: "qwen-unknown".to_string(),
+            matched_key: None,
+            source: PricingSource::Unknown,
+            catalog: None,
+            rates: None,
+            long_context: None,
+        };
+        assert_eq!(
+            quote_text(&unknown),
+            "qwen-unknown: no price in any catalog (unknown)\n"
+        );
+    }
+
+    #[test]
+    fn diff_text_lists_changes_with_missing_sides_as_dashes() {
+        let diff = CatalogDiff {
+            label: "litellm".to_string(),
+            cache_age_seconds: Some(7200),
+            changes: vec![
+                PriceChange {
+                    model: "sonnet-4".to_string(),
+                    field: "output",
+                    cached: Some(15.0),
+                    fresh: Some(12.0),
+                },
+                PriceChange {
+                    model: "new".to_string(),
+                    field: "input",
+                    cached: None,
+                    fresh: Some(0.5),
+                },
+            ],
+        };
+        let text = diff_text(&diff, 3);
+        assert!(
+            text.starts_with(
+                "litellm: 2 changed prices across the 3 models used (cache 2.0h old)\n"
+            )
+        );
+        assert!(
+            text.contains("  sonnet-4  output            $15.00/M -> $12.00/M\n"),
+            "{text}"
+        );
+        assert!(
+            text.contains("  new       input             - -> $0.50/M\n"),
+            "{text}"
+        );
+
+        let unchanged = CatalogDiff {
+            changes: Vec::new(),
+            cache_age_seconds: None,
+            ..diff
+        };
+        assert_eq!(
+            diff_text(&unchanged, 3),
+            "litellm: no price changes for the 3 models used (no cache)\n"
+        );
+    }
+}
@@ -1,18 +1,30 @@
-use crate::app::{CommandContext, print_json};
+use crate::app::{CommandContext, print_json_report};
 use crate::output::{
-    OutputFormat, QuotaValueEstimate, output_quota_csv, output_quota_json, print_quota_table,
+    OutputFormat, QuotaValueEstimate, output_quota_csv, output_quota_history_csv,
+    print_quota_history_table, print_quota_table, quota_history_json_report, quota_json_report,
 };
 use crate::sdk::estimate_codex_weekly_value_with_pricing;
-use crate::source::load_weekly_quota;
+use crate::source::{
+    CodexQuotaError, CodexQuotaScan, CodexQuotaWindow, CodexWeeklyQuota, load_quota_history,
+    load_quota_scan,
+};
+
+pub(crate) fn handle_quota(ctx: &CommandContext<'_>, history: bool) {
+    if history {
+        return handle_quota_history(ctx);
+    }
 
-pub(crate) fn handle_quota(ctx: &CommandContext<'_>) {
-    let report = match load_weekly_quota() {
-        Ok(report) => report,
+    let CodexQuotaScan {
+        weekly: report,
+        windows,
+    } = match load_quota_scan() {
+        Ok(scan) => scan,
         Err(error) => {
             eprintln!("Error: {error}");
             std::process::exit(1);
         }
     };
+    let windows = current_windows(&report, windows);
     let value_estimate = ctx
         .cli
         .show_cost()
@@ -20,17 +32,59 @@ pub(crate) fn handle_quota(ctx: &CommandContext<'_>) {
     let rendered_estimate: QuotaValueEstimate<'_> = value_estimate.as_ref().map(Result::as_ref);
 
     match ctx.cli.output_format() {
-        OutputFormat::Json => print_json(
-            &output_quota_json(&report, rendered_estimate),
-            ctx.jq_filter,
+        OutputFormat::Json => print_json_report(
+            &quota_json_report(&report, &windows, rendered_estimate),
+            false,
+            ctx,
         ),
-        OutputFormat::Csv => print!("{}", output_quota_csv(&report, rendered_estimate)),
-        OutputFormat::Table => print_quota_table(
+        OutputFormat::Csv => print!("{}", output_quota_csv(&report, &windows, rendered_estimate)),
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => print_quota_table(
             &report,
+            &windows,
             rendered_estimate,
             ctx.timezone,
-            ctx.number_format,
+            ctx.locale,
             ctx.cli.use_color(),
+            ctx.cli.output_format().markup(),
         ),
     }
 }
+
+/// Every current window, with the weekly entry taken from the weekly report so
+/// both views agree even when the newest event lacks a weekly window.
+fn current_windows(
+    report: &CodexWeeklyQuota,
+    windows: Result<Vec<CodexQuotaWindow>, CodexQuotaError>,
+) -> Vec<CodexQuotaWindow> {
+    let mut windows = match windows {
+        Ok(windows) => windows,
+        Err(error) => {
+            eprintln!("Warning: showing the weekly window only: {error}");
+            Vec::new()
+        }
+    };
+    windows.retain(|window| window.window_minutes != report.window_minutes);
+    windows.push(report.clone());
+    windows.sort_by_key(|window| window.window_minutes);
+    windows
+}
+
+fn handle_quota_history(ctx: &CommandContext<'_>) {
+    let history = match load_quota_history(ctx.filter, ctx.timezone) {
+        Ok(history) => history,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    match ctx.cli.output_format() {
+        OutputFormat::Json => {
+            print_json_report(&quota_history_json_report(&history), false, ctx);
+        }
+        OutputFormat::Csv => print!("{}", output_quota_history_csv(&history)),
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            print_quota_history_table(&history, ctx.timezone, ctx.locale, ctx.cli.use_color());
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Handler for the `report` subcommand (offline HTML dashboard).
+//!
+//! Lives in its own module to keep `app.rs` under the module size limit.
+
+use chrono::Utc;
+
+use crate::app::{CommandContext, print_no_data_hint};
+use crate::output::{DashboardOptions, DashboardSource, DataKind, render_dashboard};
+use crate::source::{
+    Capabilities, Source, all_sources, load_blocks, load_daily, load_projects, load_tool_calls,
+};
+
+/// Build the dashboard from the same loaders as the `daily`, `project`,
+/// `blocks` and `tools` commands and write it to the `--html` file.
+pub(crate) fn handle_report(source: Option<&dyn Source>, ctx: &CommandContext<'_>) {
+    let Some(path) = ctx.cli.report_html_path() else {
+        return;
+    };
+    let title = source.map_or("All Sources", |source| source.display_name());
+    let selected: Vec<&dyn Source> = match source {
+        Some(source) => vec![source],
+        None => all_sources().collect(),
+    };
+
+    let mut with_data: Vec<&dyn Source> = Vec::new();
+    let mut sources = Vec::new();
+    let mut tool_calls = Vec::new();
+    for source in selected {
+        let result = load_daily(source, ctx.filter, ctx.timezone, false, ctx.cli.debug);
+        if result.day_stats.is_empty() {
+            continue;
+        }
+        let caps = source.capabilities();
+        let projects = if caps.has_projects {
+            load_projects(source, ctx.filter, ctx.timezone, false, ctx.daily_rates()).0
+        } else {
+            Vec::new()
+        };
+        let blocks = if caps.has_billing_blocks {
+            load_blocks(source, ctx.filter, ctx.timezone, false, ctx.daily_rates()).0
+        } else {
+            Vec::new()
+        };
+        tool_calls.extend(load_tool_calls(source, ctx.filter, ctx.timezone));
+        sources.push(DashboardSource {
+            label: source.display_name(),
+            day_stats: result.day_stats,
+            projects,
+            blocks,
+        });
+        with_data.push(source);
+    }
+
+    if sources.is_empty() {
+        print_no_data_hint(ctx.locale, title, DataKind::Usage);
+        return;
+    }
+
+    let generated = ctx
+        .timezone
+        .to_fixed_offset(Utc::now())
+        .format("%Y-%m-%d %H:%M")
+        .to_string();
+    let page = render_dashboard(
+        &sources,
+        &tool_calls,
+        ctx.pricing_db,
+        DashboardOptions {
+            title,
+            generated: &generated,
+            show_cost: ctx.cli.show_cost(),
+            supports_cache_read: Capabilities::combine(with_data).has_cache_read,
+            monthly_budget: ctx.cli.monthly_budget,
+            as_of: ctx.budget_as_of,
+            currency: ctx.currency,
+        },
+    );
+    if let Err(e) = std::fs::write(path, page) {
+        eprintln!("Error: failed to write {}: {e}", path.display());
+        std::process::exit(1);
+    }
+    println!("Wrote dashboard to {}", path.display());
+}
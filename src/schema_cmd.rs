@@ -0,0 +1,10 @@
+//! Handler for the `schema` subcommand (JSON Schema of typed reports).
+//!
+//! Lives in its own module to keep `app.rs` under the module size limit.
+
+use crate::app::{CommandContext, print_json_report};
+use crate::output::SchemaReport;
+
+pub(crate) fn handle_schema(report: SchemaReport, ctx: &CommandContext<'_>) {
+    print_json_report(&report.json_schema(), true, ctx);
+}
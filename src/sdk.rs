@@ -2,6 +2,7 @@
 
 mod batch;
 
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::path::Path;
@@ -12,17 +13,20 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::config::Config;
+use crate::consts::DATE_FORMAT;
 use crate::core::{DateFilter, DayStats, LoadResult, Stats};
+use crate::output::{blended_currency, span_currency};
 use crate::pricing::{
-    CurrencyConverter, PricingDb, calculate_cost, calculate_estimated_proxy_cost, model_cost_kind,
-    sum_estimated_proxy_model_costs, sum_model_costs,
+    CostDisplayMode, CurrencyConverter, PricingDb, calculate_cost, calculate_estimated_proxy_cost,
+    model_cost_kind, sum_estimated_proxy_model_costs, sum_model_costs,
 };
 use crate::source::{
-    Source, get_source, load_daily, load_weekly_quota_from_home, load_weekly_window_usage_from_home,
+    Source, get_source, load_daily, load_quota_windows_from_home, load_weekly_quota_from_home,
+    load_weekly_window_usage_from_home,
 };
 use crate::utils::Timezone;
 
-pub use crate::source::{CodexQuotaError, CodexQuotaStatus, CodexWeeklyQuota};
+pub use crate::source::{CodexQuotaError, CodexQuotaStatus, CodexQuotaWindow, CodexWeeklyQuota};
 
 pub use batch::{
     MultiCostSummary, MultiSummaryOptions, summarize_cost_ranges,
@@ -300,6 +304,24 @@ pub fn load_codex_weekly_quota(
     load_weekly_quota_from_home(codex_home)
 }
 
+/// Load every current Codex rate-limit window from the newest snapshot.
+///
+/// This is the companion of [`load_codex_weekly_quota`] for the shorter
+/// primary window (typically 5 hours) and any other window Codex reports.
+/// Windows are sorted by duration, shortest first; windows that have already
+/// reset are omitted. Codex home discovery matches [`load_codex_weekly_quota`].
+///
+/// # Errors
+///
+/// Returns an error when the sessions directory or a rate-limit snapshot
+/// cannot be found, a session file cannot be inspected or read, the newest
+/// snapshot is malformed, or every window in it has already reset.
+pub fn load_codex_quota_windows(
+    codex_home: Option<&Path>,
+) -> Result<Vec<CodexQuotaWindow>, CodexQuotaError> {
+    load_quota_windows_from_home(codex_home)
+}
+
 /// Estimate the API-equivalent dollar value represented by the active Codex
 /// weekly quota.
 ///
@@ -319,7 +341,7 @@ pub fn estimate_codex_weekly_value(
     strict_pricing: bool,
 ) -> Result<CodexWeeklyValueEstimate, CodexWeeklyValueError> {
     let quota = load_weekly_quota_from_home(codex_home)?;
-    let pricing_db = PricingDb::try_load_quiet(offline, strict_pricing).map_err(|error| {
+    let pricing_db = PricingDb::try_load_quiet(offline, strict_pricing, &[]).map_err(|error| {
         CodexWeeklyValueError::Pricing {
             message: error.to_string(),
         }
@@ -397,7 +419,7 @@ pub fn summarize_cost(options: SummaryOptions) -> Result<CostSummary, SdkError>
     let source = get_source(options.source.as_str()).ok_or_else(|| SdkError::InvalidSource {
         name: options.source.as_str().to_string(),
     })?;
-    let pricing_db = PricingDb::try_load_quiet(options.offline, options.strict_pricing)
+    let pricing_db = PricingDb::try_load_quiet(options.offline, options.strict_pricing, &[])
         .map_err(|err| SdkError::Configuration(err.to_string()))?;
     let currency = load_requested_currency(options.currency.as_deref(), options.offline)?;
     let currency_code = currency.as_ref().map_or_else(
@@ -483,6 +505,15 @@ pub(in crate::sdk) fn build_cost_summary(
     currency_code: &str,
 ) -> CostSummary {
     let (stats, models) = merge_days(&result.day_stats);
+    let total_currency = span_currency(
+        currency,
+        result
+            .day_stats
+            .iter()
+            .map(|(date, day)| (date, &day.models)),
+        pricing_db,
+        CostDisplayMode::Total,
+    );
     let cost_usd = finite_cost(sum_model_costs(&models, pricing_db));
     let estimated_cost_usd =
         finite_positive_cost(sum_estimated_proxy_model_costs(&models, pricing_db));
@@ -496,13 +527,19 @@ pub(in crate::sdk) fn build_cost_summary(
         since,
         until,
         currency: currency_code.to_string(),
-        cost: convert_cost(cost_usd, currency),
+        cost: convert_cost(cost_usd, total_currency.as_deref()),
         cost_usd,
-        estimated_cost: convert_cost(estimated_cost_usd, currency),
+        estimated_cost: convert_cost(estimated_cost_usd, total_currency.as_deref()),
         estimated_cost_usd,
         cost_kind: model_cost_kind(&models).as_str().to_string(),
         tokens: TokenBreakdown::from_stats(&stats, supports_cache_read),
-        models: summarize_models(&models, pricing_db, currency, supports_cache_read),
+        models: summarize_models(
+            &models,
+            &result.day_stats,
+            pricing_db,
+            currency,
+            supports_cache_read,
+        ),
         valid_entries: result.valid,
         skipped_entries: result.skipped,
         parse_error_entries: result.parse_errors,
@@ -557,8 +594,29 @@ pub(in crate::sdk) fn load_requested_currency(
     })
 }
 
+/// Converter for one model's cost at the rates of the days it was used.
+fn model_currency<'a>(
+    model: &str,
+    day_stats: &HashMap<String, DayStats>,
+    pricing_db: &PricingDb,
+    currency: Option<&'a CurrencyConverter>,
+) -> Option<Cow<'a, CurrencyConverter>> {
+    let day_costs: Vec<(NaiveDate, f64)> = day_stats
+        .iter()
+        .filter_map(|(date, day)| {
+            let date = NaiveDate::parse_from_str(date, DATE_FORMAT).ok()?;
+            Some((
+                date,
+                calculate_cost(day.models.get(model)?, model, pricing_db),
+            ))
+        })
+        .collect();
+    blended_currency(currency, &day_costs)
+}
+
 fn summarize_models(
     models: &HashMap<String, Stats>,
+    day_stats: &HashMap<String, DayStats>,
     pricing_db: &PricingDb,
     currency: Option<&CurrencyConverter>,
     supports_cache_read: bool,
@@ -566,6 +624,8 @@ fn summarize_models(
     let mut rows: Vec<_> = models
         .iter()
         .map(|(model, stats)| {
+            let model_currency = model_currency(model, day_stats, pricing_db, currency);
+            let currency = model_currency.as_deref();
             let cost_usd = finite_cost(calculate_cost(stats, model, pricing_db));
             let estimated_cost_usd =
                 finite_positive_cost(calculate_estimated_proxy_cost(stats, model, pricing_db));
@@ -715,7 +775,7 @@ mod tests {
             },
         );
 
-        let rows = summarize_models(&models, &pricing_db, None, true);
+        let rows = summarize_models(&models, &HashMap::new(), &pricing_db, None, true);
 
         assert_eq!(rows[0].model, "gpt-5-alpha");
         assert_eq!(rows[1].model, "gpt-5-zeta");
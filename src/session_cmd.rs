@@ -0,0 +1,157 @@
+//! Handlers for `session show` (turn-by-turn timeline of one session) and
+//! `session --context` (context-window growth per session).
+//!
+//! Lives in its own module to keep `app.rs` under the module size limit.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::app::{CommandContext, print_json_report, print_json_rows};
+use crate::core::{RawEntry, SessionStats, build_session_timeline, source_wide_message_id};
+use crate::output::{
+    OutputFormat, SessionContextTableOptions, SessionTimelineOptions, output_session_context_csv,
+    output_session_timeline_csv, print_ndjson, print_session_context_table, print_session_timeline,
+    session_context_json_rows, session_entry_json_rows, session_timeline_json_report,
+};
+use crate::source::{Source, load_session_entries, load_tool_calls};
+
+/// Most candidate ids listed when a prefix matches several sessions.
+const MAX_AMBIGUOUS_IDS: usize = 10;
+
+/// Tools invoked per response message of one session, keyed like
+/// `RawEntry::message_id`.
+fn tools_by_message(
+    source: &dyn Source,
+    session_key: &str,
+    ctx: &CommandContext<'_>,
+) -> HashMap<String, Vec<String>> {
+    let mut tools: HashMap<String, Vec<String>> = HashMap::new();
+    if !source.capabilities().has_tool_calls {
+        return tools;
+    }
+    let mut seen = HashSet::new();
+    for call in load_tool_calls(source, ctx.filter, ctx.timezone) {
+        let Some(identity) = call.identity else {
+            continue;
+        };
+        if call.session_key != session_key
+            || identity.message_id.is_empty()
+            || !seen.insert(identity.clone())
+        {
+            continue;
+        }
+        tools
+            .entry(source_wide_message_id(source.name(), &identity.message_id))
+            .or_default()
+            .push(call.name);
+    }
+    tools
+}
+
+pub(crate) fn handle_session_show(source: &dyn Source, ctx: &CommandContext<'_>) {
+    let Some(prefix) = ctx.cli.session_show_id() else {
+        return;
+    };
+    let (entries, data_quality) = load_session_entries(source, ctx.filter, ctx.timezone, prefix);
+    let mut sessions: BTreeMap<String, Vec<RawEntry>> = BTreeMap::new();
+    for entry in entries {
+        let key = if entry.session_key.is_empty() {
+            entry.session_id.clone()
+        } else {
+            entry.session_key.clone()
+        };
+        sessions.entry(key).or_default().push(entry);
+    }
+
+    if sessions.len() > 1 {
+        let mut ids: Vec<&str> = sessions
+            .values()
+            .map(|entries| entries[0].session_id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        let more = ids.len().saturating_sub(MAX_AMBIGUOUS_IDS);
+        ids.truncate(MAX_AMBIGUOUS_IDS);
+        let suffix = if more > 0 {
+            format!(" (and {more} more)")
+        } else {
+            String::new()
+        };
+        eprintln!(
+            "Error: session prefix '{prefix}' matches {} sessions: {}{suffix}",
+            sessions.len(),
+            ids.join(", ")
+        );
+        std::process::exit(1);
+    }
+    let Some((session_key, entries)) = sessions.pop_first() else {
+        eprintln!(
+            "Error: no {} session matches '{prefix}' in the selected date range.\nHint: run `ccstats session` to list session ids.",
+            source.display_name()
+        );
+        std::process::exit(1);
+    };
+
+    let tools = tools_by_message(source, &session_key, ctx);
+    let timeline = build_session_timeline(entries, &tools);
+    let show_cost = ctx.cli.show_cost();
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => print!(
+            "{}",
+            output_session_timeline_csv(&timeline, ctx.pricing_db, show_cost, ctx.currency)
+        ),
+        OutputFormat::Json if ctx.cli.ndjson => print_ndjson(None, |out| {
+            out.rows(session_entry_json_rows(
+                &timeline,
+                ctx.pricing_db,
+                show_cost,
+                ctx.currency,
+            ))?;
+            out.data_quality(data_quality)
+        }),
+        OutputFormat::Json => print_json_report(
+            &session_timeline_json_report(&timeline, ctx.pricing_db, show_cost, ctx.currency),
+            false,
+            ctx,
+        ),
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            print_session_timeline(
+                &timeline,
+                ctx.pricing_db,
+                &SessionTimelineOptions {
+                    use_color: ctx.cli.use_color(),
+                    show_cost,
+                    locale: ctx.locale,
+                    timezone: ctx.timezone,
+                    currency: ctx.currency,
+                    markup: ctx.cli.output_format().markup(),
+                },
+            );
+        }
+    }
+}
+
+pub(crate) fn render_session_context(
+    sessions: &[SessionStats],
+    threshold: i64,
+    source_label: &str,
+    ctx: &CommandContext<'_>,
+) {
+    match ctx.cli.output_format() {
+        OutputFormat::Csv => print!("{}", output_session_context_csv(sessions, threshold)),
+        OutputFormat::Json => {
+            print_json_rows(session_context_json_rows(sessions, threshold), false, ctx);
+        }
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            print_session_context_table(
+                sessions,
+                SessionContextTableOptions {
+                    threshold,
+                    source_label,
+                    locale: ctx.locale,
+                    use_color: ctx.cli.use_color(),
+                    markup: ctx.cli.output_format().markup(),
+                },
+            );
+        }
+    }
+}
@@ -48,6 +48,7 @@ impl Source for ClaudeSource {
             needs_dedup: true,
             has_tool_calls: true,
             has_endpoints: true,
+            has_subagents: true,
         }
     }
 
@@ -4,6 +4,7 @@
 
 mod config;
 mod parser;
+mod subagents;
 pub(crate) mod tool_parser;
 
 pub(crate) use config::ClaudeSource;
@@ -10,10 +10,14 @@ use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 use crate::consts::{DATE_FORMAT, UNKNOWN};
-use crate::core::{Endpoint, RawEntry, source_wide_message_id};
+use crate::core::{Endpoint, RawEntry, ServiceTier, source_wide_message_id};
 use crate::source::ParseOutput;
 use crate::utils::Timezone;
 
+use super::subagents::{
+    ContentLink, SubagentLinks, agent_file_id, content_links, launched_agent_id, parent_transcript,
+};
+
 // ============================================================================
 // Internal types for JSONL parsing
 // ============================================================================
@@ -21,6 +25,21 @@ use crate::utils::Timezone;
 #[derive(Debug, Deserialize)]
 struct UsageEntry {
     timestamp: Option<String>,
+    #[serde(rename = "isSidechain", default)]
+    is_sidechain: bool,
+    /// Owning session; in a subagent transcript, the session that launched it.
+    #[serde(rename = "sessionId")]
+    session_id: Option<String>,
+    /// Subagent that made a sidechain request.
+    #[serde(rename = "agentId")]
+    agent_id: Option<String>,
+    /// Agent started by the `Task` call whose result this line carries.
+    #[serde(
+        rename = "toolUseResult",
+        default,
+        deserialize_with = "launched_agent_id"
+    )]
+    launched_agent_id: Option<String>,
     message: Option<Message>,
 }
 
@@ -30,6 +49,8 @@ struct Message {
     model: Option<String>,
     stop_reason: Option<String>,
     usage: Option<Usage>,
+    #[serde(default, deserialize_with = "content_links")]
+    content: Vec<ContentLink>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -46,6 +67,8 @@ struct Usage {
     /// third-party proxies/gateways (`""`). Used only to classify `Endpoint`;
     /// values may change across versions/proxies.
     inference_geo: Option<String>,
+    /// Billing tier (`"standard"`, `"priority"`, `"batch"`).
+    service_tier: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -102,7 +125,7 @@ fn estimate_entry_capacity(file: &File, approx_bytes_per_entry: u64) -> usize {
 }
 
 /// Normalize model name by removing prefixes and date suffixes
-fn normalize_model_name(model: &str) -> String {
+pub(super) fn normalize_model_name(model: &str) -> String {
     let mut name = model;
     if let Some(stripped) = name.strip_prefix("anthropic.") {
         name = stripped;
@@ -122,7 +145,7 @@ fn normalize_model_name(model: &str) -> String {
     name.to_string()
 }
 
-fn derive_project_path(path: &Path) -> String {
+pub(super) fn derive_project_path(path: &Path) -> String {
     let mut components = path.parent().into_iter().flat_map(Path::components);
     while let Some(component) = components.next() {
         if component.as_os_str() == "projects" {
@@ -177,6 +200,8 @@ pub(super) fn parse_claude_file_with_debug(
     let mut parse_errors = 0usize;
     let mut line = String::new();
     let mut line_no = 0usize;
+    let file_agent_id = agent_file_id(path);
+    let mut sidechains = SidechainTypes::default();
     let mut reader = reader;
     loop {
         line.clear();
@@ -206,7 +231,7 @@ pub(super) fn parse_claude_file_with_debug(
             continue;
         }
 
-        let entry: UsageEntry = match serde_json::from_str(line) {
+        let mut entry: UsageEntry = match serde_json::from_str(line) {
             Ok(entry) => entry,
             Err(err) => {
                 if debug {
@@ -217,7 +242,17 @@ pub(super) fn parse_claude_file_with_debug(
             }
         };
 
-        if let Some(entry) = parse_entry_with_debug(
+        sidechains.observe(&mut entry);
+        let parent_session_id = entry
+            .session_id
+            .clone()
+            .filter(|id| file_agent_id.is_some() && *id != session_id);
+        let agent_id = entry
+            .agent_id
+            .take()
+            .or_else(|| file_agent_id.map(str::to_string));
+
+        if let Some(mut raw) = parse_entry_with_debug(
             entry,
             path,
             &session_key,
@@ -228,9 +263,15 @@ pub(super) fn parse_claude_file_with_debug(
             debug,
             &mut parse_errors,
         ) {
-            entries.push(entry);
+            if raw.is_sidechain {
+                raw.parent_session_id = parent_session_id;
+                sidechains.record(entries.len(), agent_id);
+            }
+            entries.push(raw);
         }
     }
+
+    sidechains.apply(path, &mut entries);
     ParseOutput {
         entries,
         errors: parse_errors,
@@ -337,9 +378,92 @@ fn parse_entry_with_debug(
         endpoint,
         call_count: 1,
         recorded_cost_usd: None,
+        is_sidechain: entry.is_sidechain,
+        service_tier: ServiceTier::from_label(usage.service_tier.as_deref()),
+        subagent_type: None,
+        parent_session_id: None,
     })
 }
 
+/// Types sidechain requests once the whole file is read: by their agent's
+/// `Task` launch, or without an agent id by the latest main-thread launch
+/// before them.
+#[derive(Default)]
+struct SidechainTypes {
+    links: SubagentLinks,
+    last_launched: Option<String>,
+    /// Sidechain entries by index, with their agent id and the latest
+    /// main-thread launch before them.
+    pending: Vec<(usize, Option<String>, Option<String>)>,
+}
+
+impl SidechainTypes {
+    /// Record the launches and results of a line; its content is not needed
+    /// afterwards.
+    fn observe(&mut self, entry: &mut UsageEntry) {
+        let content = entry
+            .message
+            .as_mut()
+            .map(|message| std::mem::take(&mut message.content))
+            .unwrap_or_default();
+        self.links
+            .observe(&content, entry.launched_agent_id.as_deref());
+        if !entry.is_sidechain
+            && let Some(ContentLink::Launch { subagent_type, .. }) = content
+                .iter()
+                .rfind(|link| matches!(link, ContentLink::Launch { .. }))
+        {
+            self.last_launched = Some(subagent_type.clone());
+        }
+    }
+
+    fn record(&mut self, index: usize, agent_id: Option<String>) {
+        self.pending
+            .push((index, agent_id, self.last_launched.clone()));
+    }
+
+    /// Agent files hold no launches of their own, so their `Task` call is
+    /// looked up in the parent session transcript.
+    fn apply(mut self, path: &Path, entries: &mut [RawEntry]) {
+        if let Some(agent_id) = agent_file_id(path)
+            && !self.pending.is_empty()
+            && self.links.agent_type(agent_id).is_none()
+            && let Some(parent) = parent_transcript(path)
+        {
+            self.links.extend(load_subagent_links(&parent));
+        }
+        for (index, agent_id, launched_before) in self.pending {
+            entries[index].subagent_type = agent_id
+                .as_deref()
+                .and_then(|agent_id| self.links.agent_type(agent_id))
+                .map(str::to_string)
+                .or(launched_before);
+        }
+    }
+}
+
+/// `Task` launches of a session transcript, for typing its agent files.
+/// Only lines that can take part in a launch are deserialized.
+fn load_subagent_links(path: &Path) -> SubagentLinks {
+    let mut links = SubagentLinks::default();
+    let Ok(file) = File::open(path) else {
+        return links;
+    };
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        if !line.contains("\"subagent_type\"") && !line.contains("\"agentId\"") {
+            continue;
+        }
+        if let Ok(entry) = serde_json::from_str::<UsageEntry>(&line) {
+            let content = entry.message.map(|message| message.content);
+            links.observe(
+                content.as_deref().unwrap_or_default(),
+                entry.launched_agent_id.as_deref(),
+            );
+        }
+    }
+    links
+}
+
 /// Classify the serving endpoint from the non-standard `inference_geo` field.
 /// See the `Usage.inference_geo` doc comment for the caveats.
 fn classify_endpoint(inference_geo: Option<&str>) -> Endpoint {
@@ -452,6 +576,10 @@ mod tests {
     ) -> UsageEntry {
         UsageEntry {
             timestamp: Some(timestamp.to_string()),
+            is_sidechain: false,
+            session_id: None,
+            agent_id: None,
+            launched_agent_id: None,
             message: Some(Message {
                 id: Some("msg_001".to_string()),
                 model: model.map(ToString::to_string),
@@ -463,7 +591,9 @@ mod tests {
                     cache_read_input_tokens: None,
                     cache_creation: None,
                     inference_geo: None,
+                    service_tier: None,
                 }),
+                content: Vec::new(),
             }),
         }
     }
@@ -499,11 +629,16 @@ mod tests {
     fn test_parse_entry_no_timestamp_returns_none() {
         let entry = UsageEntry {
             timestamp: None,
+            is_sidechain: false,
+            session_id: None,
+            agent_id: None,
+            launched_agent_id: None,
             message: Some(Message {
                 id: Some("msg_001".to_string()),
                 model: Some("claude-3-5-sonnet-20241022".to_string()),
                 stop_reason: None,
                 usage: Some(Usage::default()),
+                content: Vec::new(),
             }),
         };
         let tz = make_timezone();
@@ -514,6 +649,10 @@ mod tests {
     fn test_parse_entry_no_message_returns_none() {
         let entry = UsageEntry {
             timestamp: Some("2025-01-15T10:00:00Z".to_string()),
+            is_sidechain: false,
+            session_id: None,
+            agent_id: None,
+            launched_agent_id: None,
             message: None,
         };
         let tz = make_timezone();
@@ -524,11 +663,16 @@ mod tests {
     fn test_parse_entry_no_usage_returns_none() {
         let entry = UsageEntry {
             timestamp: Some("2025-01-15T10:00:00Z".to_string()),
+            is_sidechain: false,
+            session_id: None,
+            agent_id: None,
+            launched_agent_id: None,
             message: Some(Message {
                 id: Some("msg_001".to_string()),
                 model: Some("claude-3-5-sonnet-20241022".to_string()),
                 stop_reason: None,
                 usage: None,
+                content: Vec::new(),
             }),
         };
         let tz = make_timezone();
@@ -574,6 +718,10 @@ mod tests {
     fn test_parse_entry_cache_tokens() {
         let entry = UsageEntry {
             timestamp: Some("2025-01-15T10:00:00Z".to_string()),
+            is_sidechain: false,
+            session_id: None,
+            agent_id: None,
+            launched_agent_id: None,
             message: Some(Message {
                 id: Some("msg_002".to_string()),
                 model: Some("claude-3-5-sonnet-20241022".to_string()),
@@ -585,7 +733,9 @@ mod tests {
                     cache_read_input_tokens: Some(20),
                     cache_creation: None,
                     inference_geo: None,
+                    service_tier: None,
                 }),
+                content: Vec::new(),
             }),
         };
         let tz = make_timezone();
@@ -598,6 +748,10 @@ mod tests {
     fn test_parse_entry_none_tokens_default_to_zero() {
         let entry = UsageEntry {
             timestamp: Some("2025-01-15T10:00:00Z".to_string()),
+            is_sidechain: false,
+            session_id: None,
+            agent_id: None,
+            launched_agent_id: None,
             message: Some(Message {
                 id: Some("msg_003".to_string()),
                 model: Some("claude-3-5-sonnet-20241022".to_string()),
@@ -609,7 +763,9 @@ mod tests {
                     cache_read_input_tokens: None,
                     cache_creation: None,
                     inference_geo: None,
+                    service_tier: None,
                 }),
+                content: Vec::new(),
             }),
         };
         let tz = make_timezone();
@@ -624,6 +780,10 @@ mod tests {
     fn test_parse_entry_cache_creation_1h_breakdown() {
         let entry = UsageEntry {
             timestamp: Some("2025-01-15T10:00:00Z".to_string()),
+            is_sidechain: false,
+            session_id: None,
+            agent_id: None,
+            launched_agent_id: None,
             message: Some(Message {
                 id: Some("msg_1h".to_string()),
                 model: Some("claude-fable-5".to_string()),
@@ -637,7 +797,9 @@ mod tests {
                         ephemeral_1h_input_tokens: Some(25),
                     }),
                     inference_geo: None,
+                    service_tier: None,
                 }),
+                content: Vec::new(),
             }),
         };
         let tz = make_timezone();
@@ -651,6 +813,10 @@ mod tests {
         // Defensive: a malformed breakdown larger than the scalar total is clamped
         let entry = UsageEntry {
             timestamp: Some("2025-01-15T10:00:00Z".to_string()),
+            is_sidechain: false,
+            session_id: None,
+            agent_id: None,
+            launched_agent_id: None,
             message: Some(Message {
                 id: Some("msg_1h_clamp".to_string()),
                 model: Some("claude-fable-5".to_string()),
@@ -664,7 +830,9 @@ mod tests {
                         ephemeral_1h_input_tokens: Some(99),
                     }),
                     inference_geo: None,
+                    service_tier: None,
                 }),
+                content: Vec::new(),
             }),
         };
         let tz = make_timezone();
@@ -681,12 +849,65 @@ mod tests {
         let raw = parse_entry(entry, Path::new("t.jsonl"), "scope/t", "s", "p", tz, 1).unwrap();
         assert_eq!(raw.input_tokens, 10);
         assert_eq!(raw.output_tokens, 5);
+        assert!(raw.is_sidechain);
+    }
+
+    #[test]
+    fn test_malformed_content_does_not_drop_the_usage_line() {
+        let usage = r#""id":"msg_1","model":"claude-fable-5","usage":{"input_tokens":10,"output_tokens":5}"#;
+        for content in [
+            r#"{"type":"text","text":"an object, not an array"}"#,
+            r#"[{"type":"tool_use","id":"t1","name":"Task","input":"not an object"}]"#,
+            r#"[{"type":"tool_use","id":"t1","name":"Task","input":{"subagent_type":7}},42]"#,
+        ] {
+            let json = format!(
+                r#"{{"timestamp":"2025-01-15T10:00:00Z","message":{{{usage},"content":{content}}}}}"#
+            );
+            let entry: UsageEntry = serde_json::from_str(&json).unwrap();
+            assert!(
+                entry.message.as_ref().unwrap().content.is_empty(),
+                "{content}"
+            );
+            let raw = parse_entry(
+                entry,
+                Path::new("t.jsonl"),
+                "scope/t",
+                "s",
+                "p",
+                make_timezone(),
+                1,
+            )
+            .unwrap();
+            assert_eq!(raw.input_tokens, 10);
+        }
+    }
+
+    #[test]
+    fn test_parse_entry_reads_service_tier() {
+        let json = r#"{"timestamp":"2025-01-15T10:00:00Z","message":{"id":"msg_batch","model":"claude-fable-5","usage":{"input_tokens":10,"output_tokens":5,"service_tier":"batch"}}}"#;
+        let entry: UsageEntry = serde_json::from_str(json).unwrap();
+        let raw = parse_entry(
+            entry,
+            Path::new("t.jsonl"),
+            "scope/t",
+            "s",
+            "p",
+            make_timezone(),
+            1,
+        )
+        .unwrap();
+        assert_eq!(raw.service_tier, ServiceTier::Batch);
+        assert_eq!(raw.to_stats().service_tiers.batch.input_tokens, 10);
     }
 
     #[test]
     fn test_parse_entry_clamps_negative_tokens_to_zero() {
         let entry = UsageEntry {
             timestamp: Some("2025-01-15T10:00:00Z".to_string()),
+            is_sidechain: false,
+            session_id: None,
+            agent_id: None,
+            launched_agent_id: None,
             message: Some(Message {
                 id: Some("msg_004".to_string()),
                 model: Some("claude-3-5-sonnet-20241022".to_string()),
@@ -698,7 +919,9 @@ mod tests {
                     cache_read_input_tokens: Some(-20),
                     cache_creation: None,
                     inference_geo: None,
+                    service_tier: None,
                 }),
+                content: Vec::new(),
             }),
         };
         let tz = make_timezone();
@@ -0,0 +1,278 @@
+//! Links subagent requests to the `Task` call that launched them.
+//!
+//! A `Task` `tool_use` block carries the `subagent_type`, and the line with its
+//! `tool_result` carries the launched agent's id in `toolUseResult.agentId`.
+//! Subagent requests carry the same id in `agentId`, whether they are inline
+//! sidechain lines or live in `<session>/subagents/agent-<id>.jsonl`, so
+//! parallel launches each get their own type.
+
+use serde::Deserialize;
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Directory holding subagent transcripts (`agent-*.jsonl`) of a session.
+const SUBAGENTS_DIR: &str = "subagents";
+const AGENT_FILE_PREFIX: &str = "agent-";
+
+/// A content block that takes part in a subagent launch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ContentLink {
+    /// `Task` call launching a subagent of `subagent_type`.
+    Launch {
+        tool_use_id: String,
+        subagent_type: String,
+    },
+    /// Result of the tool call `tool_use_id`.
+    Result { tool_use_id: String },
+}
+
+/// A content element, or anything else that is not a well-formed block.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ContentElement {
+    Block(ContentBlock),
+    Other(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    id: Option<String>,
+    tool_use_id: Option<String>,
+    input: Option<TaskInput>,
+}
+
+#[derive(Deserialize)]
+struct TaskInput {
+    subagent_type: Option<String>,
+}
+
+impl ContentBlock {
+    fn into_link(self) -> Option<ContentLink> {
+        if let Some(tool_use_id) = self.tool_use_id {
+            return Some(ContentLink::Result { tool_use_id });
+        }
+        Some(ContentLink::Launch {
+            tool_use_id: self.id?,
+            subagent_type: self.input?.subagent_type?,
+        })
+    }
+}
+
+/// `message.content` reduced to its launch links. Plain-text content has none,
+/// and malformed content or blocks are skipped rather than failing the line.
+pub(super) fn content_links<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<ContentLink>, D::Error> {
+    struct Links;
+
+    impl<'de> Visitor<'de> for Links {
+        type Value = Vec<ContentLink>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("message content")
+        }
+
+        fn visit_str<E: de::Error>(self, _: &str) -> Result<Self::Value, E> {
+            Ok(Vec::new())
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(Vec::new())
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            Ok(Vec::new())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut links = Vec::new();
+            while let Some(element) = seq.next_element::<ContentElement>()? {
+                if let ContentElement::Block(block) = element {
+                    links.extend(block.into_link());
+                }
+            }
+            Ok(links)
+        }
+    }
+
+    deserializer.deserialize_any(Links)
+}
+
+/// `toolUseResult.agentId`. The result is a string or array for most tools,
+/// which carry no agent.
+pub(super) fn launched_agent_id<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    struct AgentId;
+
+    impl<'de> Visitor<'de> for AgentId {
+        type Value = Option<String>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a tool result")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut agent_id = None;
+            while let Some(key) = map.next_key::<String>()? {
+                if key == "agentId" {
+                    agent_id = map.next_value()?;
+                } else {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+            Ok(agent_id)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+            Ok(None)
+        }
+
+        fn visit_str<E: de::Error>(self, _: &str) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_bool<E: de::Error>(self, _: bool) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_i64<E: de::Error>(self, _: i64) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_u64<E: de::Error>(self, _: u64) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_f64<E: de::Error>(self, _: f64) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+    }
+
+    deserializer.deserialize_any(AgentId)
+}
+
+/// Subagent types of a transcript's `Task` launches, by tool call and by the
+/// agent each call started.
+#[derive(Debug, Default)]
+pub(super) struct SubagentLinks {
+    by_tool_use: HashMap<String, String>,
+    by_agent: HashMap<String, String>,
+}
+
+impl SubagentLinks {
+    /// Record the launches and results of one line. `agent_id` is the line's
+    /// `toolUseResult.agentId`.
+    pub(super) fn observe(&mut self, content: &[ContentLink], agent_id: Option<&str>) {
+        for link in content {
+            match link {
+                ContentLink::Launch {
+                    tool_use_id,
+                    subagent_type,
+                } => {
+                    self.by_tool_use
+                        .insert(tool_use_id.clone(), subagent_type.clone());
+                }
+                ContentLink::Result { tool_use_id } => {
+                    if let Some(agent_id) = agent_id
+                        && let Some(subagent_type) = self.by_tool_use.get(tool_use_id)
+                    {
+                        self.by_agent
+                            .insert(agent_id.to_string(), subagent_type.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    pub(super) fn agent_type(&self, agent_id: &str) -> Option<&str> {
+        self.by_agent.get(agent_id).map(String::as_str)
+    }
+
+    pub(super) fn extend(&mut self, other: SubagentLinks) {
+        self.by_tool_use.extend(other.by_tool_use);
+        self.by_agent.extend(other.by_agent);
+    }
+}
+
+/// Agent id of a `<session>/subagents/agent-<id>.jsonl` transcript.
+pub(super) fn agent_file_id(path: &Path) -> Option<&str> {
+    if path.parent()?.file_name()? != SUBAGENTS_DIR {
+        return None;
+    }
+    path.file_stem()?.to_str()?.strip_prefix(AGENT_FILE_PREFIX)
+}
+
+/// Transcript of the session that owns a `subagents` directory file:
+/// `<project>/<session>/subagents/agent-<id>.jsonl` belongs to
+/// `<project>/<session>.jsonl`.
+pub(super) fn parent_transcript(path: &Path) -> Option<PathBuf> {
+    let session_dir = path.parent()?.parent()?;
+    Some(session_dir.with_extension("jsonl"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Line {
+        #[serde(default, deserialize_with = "content_links")]
+        content: Vec<ContentLink>,
+        #[serde(
+            rename = "toolUseResult",
+            default,
+            deserialize_with = "launched_agent_id"
+        )]
+        agent_id: Option<String>,
+    }
+
+    fn line(json: &str) -> Line {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn parallel_launches_link_each_agent_to_its_own_type() {
+        let mut links = SubagentLinks::default();
+        let launch = line(
+            r#"{"content":[{"type":"text","text":"subagent_type"},{"type":"tool_use","id":"t1","name":"Task","input":{"subagent_type":"Explore"}},{"type":"tool_use","id":"t2","name":"Task","input":{"subagent_type":"code-reviewer"}},{"type":"tool_use","id":"t3","name":"Bash","input":{"command":"ls"}}]}"#,
+        );
+        links.observe(&launch.content, launch.agent_id.as_deref());
+        // Results arrive in completion order, not launch order.
+        for json in [
+            r#"{"content":[{"type":"tool_result","tool_use_id":"t2","content":"done"}],"toolUseResult":{"agentId":"b2","status":"completed"}}"#,
+            r#"{"content":[{"type":"tool_result","tool_use_id":"t1","content":[{"type":"text","text":"ok"}]}],"toolUseResult":{"agentId":"a1"}}"#,
+            r#"{"content":[{"type":"tool_result","tool_use_id":"t3"}],"toolUseResult":"plain output"}"#,
+        ] {
+            let result = line(json);
+            links.observe(&result.content, result.agent_id.as_deref());
+        }
+
+        assert_eq!(links.agent_type("a1"), Some("Explore"));
+        assert_eq!(links.agent_type("b2"), Some("code-reviewer"));
+        assert_eq!(links.agent_type("c3"), None);
+        assert!(line(r#"{"content":"plain text"}"#).content.is_empty());
+    }
+
+    #[test]
+    fn agent_files_resolve_to_their_parent_transcript() {
+        let path = Path::new("/p/.claude/projects/-work-app/sess-1/subagents/agent-a1.jsonl");
+        assert_eq!(agent_file_id(path), Some("a1"));
+        assert_eq!(
+            parent_transcript(path).unwrap(),
+            Path::new("/p/.claude/projects/-work-app/sess-1.jsonl")
+        );
+        assert_eq!(
+            agent_file_id(Path::new("/p/projects/app/agent-a1.jsonl")),
+            None
+        );
+    }
+}
@@ -1,26 +1,344 @@
 //! Parser for `tool_use` blocks in Claude Code JSONL logs
 //!
-//! Extracts tool call names from assistant messages using partial
-//! `serde_json::Value` parsing to avoid full deserialization.
+//! Extracts tool calls from assistant messages using partial
+//! `serde_json::Value` parsing to avoid full deserialization, and attributes
+//! tokens to them: each assistant message's output is split evenly across the
+//! `tool_use` blocks it emitted, and the prompt growth of the next request is
+//! split across the preceding `tool_result` blocks by result size.
 
 use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
 use crate::consts::{DATE_FORMAT, UNKNOWN};
-use crate::core::{ToolCall, ToolCallIdentity};
+use crate::core::{
+    PromptSizeTokens, RequestTokens, Stats, ToolCall, ToolCallIdentity, relative_path,
+};
 use crate::utils::Timezone;
 
+use super::parser::{derive_project_path, normalize_model_name};
+
+/// Token usage of one assistant request, merged across its streamed records.
+#[derive(Debug, Clone, Copy, Default)]
+struct TurnUsage {
+    input_tokens: i64,
+    cache_creation: i64,
+    cache_creation_1h: i64,
+    cache_read: i64,
+    output_tokens: i64,
+}
+
+impl TurnUsage {
+    fn from_value(usage: &Value) -> Self {
+        let tokens = |key: &str| usage.get(key).and_then(Value::as_i64).unwrap_or(0).max(0);
+        let cache_creation = tokens("cache_creation_input_tokens");
+        Self {
+            input_tokens: tokens("input_tokens"),
+            cache_creation,
+            cache_creation_1h: usage
+                .pointer("/cache_creation/ephemeral_1h_input_tokens")
+                .and_then(Value::as_i64)
+                .unwrap_or(0)
+                .clamp(0, cache_creation),
+            cache_read: tokens("cache_read_input_tokens"),
+            output_tokens: tokens("output_tokens"),
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.input_tokens = self.input_tokens.max(other.input_tokens);
+        self.cache_creation = self.cache_creation.max(other.cache_creation);
+        self.cache_creation_1h = self.cache_creation_1h.max(other.cache_creation_1h);
+        self.cache_read = self.cache_read.max(other.cache_read);
+        self.output_tokens = self.output_tokens.max(other.output_tokens);
+    }
+
+    fn prompt_tokens(&self) -> i64 {
+        self.input_tokens + self.cache_creation + self.cache_read
+    }
+
+    /// Split `growth` prompt tokens into the classes this request billed them
+    /// as. The newest part of a prompt is what the request wrote to the cache
+    /// or sent uncached, so growth fills cache writes first, then fresh input,
+    /// then cache reads, never exceeding what the request reported.
+    fn split_growth(&self, growth: i64) -> RequestTokens {
+        let mut remaining = growth.max(0);
+        let mut take = |available: i64| {
+            let taken = remaining.min(available);
+            remaining -= taken;
+            taken
+        };
+        let cache_creation = take(self.cache_creation);
+        let input_tokens = take(self.input_tokens);
+        let cache_read = take(self.cache_read);
+        let cache_creation_1h = split_by_weight(
+            cache_creation,
+            &[
+                self.cache_creation_1h as u64,
+                (self.cache_creation - self.cache_creation_1h) as u64,
+            ],
+        )[0];
+        RequestTokens {
+            input_tokens,
+            cache_creation,
+            cache_creation_1h,
+            cache_read,
+            ..RequestTokens::default()
+        }
+    }
+}
+
+/// Attribution state for one conversation thread (main or sidechain) in a file.
+#[derive(Default)]
+struct Thread {
+    message_id: Option<String>,
+    model: String,
+    usage: Option<TurnUsage>,
+    /// Calls emitted by the current assistant message.
+    turn_calls: Vec<usize>,
+    /// Results logged since the current assistant message, with their sizes.
+    pending_results: Vec<(usize, u64)>,
+}
+
+impl Thread {
+    /// Whether a usage-only record can still change this thread's attribution:
+    /// it may continue a message that emitted calls, or start the request that
+    /// carries pending results.
+    fn needs_usage(&self) -> bool {
+        !self.turn_calls.is_empty() || !self.pending_results.is_empty()
+    }
+
+    /// Split the finished message's output tokens across the calls it emitted.
+    fn finish_turn(&mut self, calls: &mut [ToolCall]) {
+        let Some(usage) = self.usage else {
+            return;
+        };
+        if self.turn_calls.is_empty() || self.model.is_empty() {
+            return;
+        }
+        let shares = split_evenly(usage.output_tokens, self.turn_calls.len());
+        for (&index, share) in self.turn_calls.iter().zip(shares) {
+            let tokens = RequestTokens {
+                output_tokens: share,
+                ..RequestTokens::default()
+            };
+            attribute(
+                &mut calls[index],
+                &self.model,
+                usage.prompt_tokens(),
+                tokens,
+            );
+        }
+    }
+
+    /// Start a new assistant message, charging its prompt growth to the
+    /// results that arrived since the previous one.
+    fn start_turn(
+        &mut self,
+        message_id: Option<String>,
+        model: String,
+        usage: TurnUsage,
+        calls: &mut [ToolCall],
+    ) {
+        self.finish_turn(calls);
+        if let Some(previous) = self.usage
+            && !self.pending_results.is_empty()
+            && !model.is_empty()
+        {
+            let growth = usage.split_growth(
+                usage.prompt_tokens() - previous.prompt_tokens() - previous.output_tokens,
+            );
+            let weights: Vec<u64> = self
+                .pending_results
+                .iter()
+                .map(|(_, bytes)| *bytes)
+                .collect();
+            let split = |total: i64| split_by_weight(total, &weights);
+            let shares = split(growth.input_tokens)
+                .into_iter()
+                .zip(split(growth.cache_creation))
+                .zip(split(growth.cache_creation_1h))
+                .zip(split(growth.cache_read));
+            for ((index, _), (((input_tokens, cache_creation), cache_creation_1h), cache_read)) in
+                self.pending_results.iter().zip(shares)
+            {
+                let tokens = RequestTokens {
+                    input_tokens,
+                    cache_creation,
+                    cache_creation_1h,
+                    cache_read,
+                    ..RequestTokens::default()
+                };
+                attribute(&mut calls[*index], &model, usage.prompt_tokens(), tokens);
+            }
+        }
+        self.pending_results.clear();
+        self.turn_calls.clear();
+        self.message_id = message_id;
+        self.model = model;
+        self.usage = Some(usage);
+    }
+}
+
+/// Charge `tokens` of a request with a prompt of `prompt_tokens` to `call`.
+fn attribute(call: &mut ToolCall, model: &str, prompt_tokens: i64, tokens: RequestTokens) {
+    let stats = Stats {
+        input_tokens: tokens.input_tokens,
+        output_tokens: tokens.output_tokens,
+        cache_creation: tokens.cache_creation,
+        cache_creation_1h: tokens.cache_creation_1h,
+        cache_read: tokens.cache_read,
+        by_prompt_size: PromptSizeTokens::request(prompt_tokens, tokens),
+        ..Stats::default()
+    };
+    call.attributed
+        .entry(model.to_string())
+        .or_default()
+        .add(&stats);
+}
+
+fn split_evenly(total: i64, parts: usize) -> Vec<i64> {
+    split_by_weight(total, &vec![1; parts])
+}
+
+/// Split `total` proportionally to `weights` (evenly when all are zero); the
+/// rounding remainder goes to the first parts so shares always sum to `total`.
+fn split_by_weight(total: i64, weights: &[u64]) -> Vec<i64> {
+    if weights.is_empty() {
+        return Vec::new();
+    }
+    let weight_sum: u128 = weights.iter().map(|&w| u128::from(w)).sum();
+    let mut shares: Vec<i64> = weights
+        .iter()
+        .map(|&weight| {
+            if weight_sum == 0 {
+                total / weights.len() as i64
+            } else {
+                (i128::from(total) * i128::from(weight) / weight_sum as i128) as i64
+            }
+        })
+        .collect();
+    let mut remainder = total - shares.iter().sum::<i64>();
+    for share in &mut shares {
+        if remainder <= 0 {
+            break;
+        }
+        *share += 1;
+        remainder -= 1;
+    }
+    shares
+}
+
+/// Size of a `tool_result` block's content: text length, or the serialized
+/// size of non-text blocks such as images.
+fn result_size(item: &Value) -> u64 {
+    match item.get("content") {
+        Some(Value::String(text)) => text.len() as u64,
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .map(|block| match block.get("text").and_then(Value::as_str) {
+                Some(text) => text.len() as u64,
+                None => block.to_string().len() as u64,
+            })
+            .sum(),
+        Some(other) => other.to_string().len() as u64,
+        None => 0,
+    }
+}
+
+/// Calls collected from one file, with the index of each `tool_use` id.
+struct FileCalls {
+    session_key: String,
+    project_path: String,
+    /// Working directory of the latest record, for relativizing file paths.
+    cwd: Option<String>,
+    calls: Vec<ToolCall>,
+    call_index: HashMap<String, usize>,
+}
+
+impl FileCalls {
+    /// Fill in the file-level context of a freshly extracted call.
+    fn attach(&self, call: &mut ToolCall) {
+        call.project_path.clone_from(&self.project_path);
+        call.session_key.clone_from(&self.session_key);
+        for path in &mut call.file_paths {
+            *path = relative_path(path, self.cwd.as_deref());
+        }
+    }
+}
+
+/// Record a direct assistant or user message: start a new turn on fresh usage,
+/// then collect its `tool_use` blocks and size its `tool_result` blocks.
+fn record_message(message: &Value, date_str: &str, thread: &mut Thread, file: &mut FileCalls) {
+    let message_id = message.get("id").and_then(Value::as_str);
+    if let Some(usage) = message.get("usage") {
+        let usage = TurnUsage::from_value(usage);
+        let same_message = message_id.is_some() && thread.message_id.as_deref() == message_id;
+        if same_message {
+            // Streamed continuation: keep the most complete usage.
+            thread.usage.get_or_insert_default().merge(&usage);
+        } else {
+            let model = message
+                .get("model")
+                .and_then(Value::as_str)
+                .map(normalize_model_name)
+                .filter(|model| model != "<synthetic>")
+                .unwrap_or_default();
+            thread.start_turn(
+                message_id.map(str::to_string),
+                model,
+                usage,
+                &mut file.calls,
+            );
+        }
+    }
+
+    let Some(content) = message.get("content").and_then(Value::as_array) else {
+        return;
+    };
+    for item in content {
+        if let Some(mut call) = extract_tool_call(item, &file.session_key, message_id, date_str) {
+            file.attach(&mut call);
+            let index = file.calls.len();
+            // Repeated records of the same call carry no attribution.
+            if let Some(id) = item.get("id").and_then(Value::as_str)
+                && !file.call_index.contains_key(id)
+            {
+                file.call_index.insert(id.to_string(), index);
+                thread.turn_calls.push(index);
+            }
+            file.calls.push(call);
+        } else if item.get("type").and_then(Value::as_str) == Some("tool_result")
+            && let Some(&index) = item
+                .get("tool_use_id")
+                .and_then(Value::as_str)
+                .and_then(|id| file.call_index.get(id))
+            && file.calls[index].result_bytes.is_none()
+        {
+            let bytes = result_size(item);
+            file.calls[index].result_bytes = Some(bytes);
+            thread.pending_results.push((index, bytes));
+        }
+    }
+}
+
 /// Parse a single JSONL file and extract tool calls
 pub(crate) fn parse_tool_calls(path: &Path, timezone: Timezone) -> Vec<ToolCall> {
     let Ok(file) = File::open(path) else {
         return Vec::new();
     };
     let reader = BufReader::new(file);
-    let session_key = path.display().to_string();
-
-    let mut calls = Vec::new();
+    let mut file = FileCalls {
+        session_key: path.display().to_string(),
+        project_path: derive_project_path(path),
+        cwd: None,
+        calls: Vec::new(),
+        call_index: HashMap::new(),
+    };
+    let mut threads: [Thread; 2] = Default::default();
     for line in reader.lines() {
         let Ok(line) = line else {
             continue;
@@ -29,56 +347,64 @@ pub(crate) fn parse_tool_calls(path: &Path, timezone: Timezone) -> Vec<ToolCall>
             continue;
         }
 
-        // Quick pre-filter: skip lines that can't contain tool_use
-        if !line.contains("\"tool_use\"") {
+        // Quick pre-filter: only tool calls, tool results and the usage of
+        // threads still attributing tokens matter
+        let wanted_usage = line.contains("\"usage\"")
+            && threads[usize::from(line.contains("\"isSidechain\":true"))].needs_usage();
+        if !wanted_usage && !line.contains("\"tool_use\"") && !line.contains("\"tool_result\"") {
             continue;
         }
 
-        let val: serde_json::Value = match serde_json::from_str(&line) {
+        let val: Value = match serde_json::from_str(&line) {
             Ok(v) => v,
             Err(_) => continue,
         };
 
+        if let Some(cwd) = val.get("cwd").and_then(Value::as_str) {
+            file.cwd = Some(cwd.to_string());
+        }
+
         // Extract timestamp for date filtering
         let date_str = extract_date(&val, timezone);
+        let thread = &mut threads[usize::from(
+            val.get("isSidechain")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        )];
 
-        // Handle direct assistant messages: {"type":"assistant","message":{"content":[...]}}
-        if let Some(content) = val
-            .pointer("/message/content")
-            .and_then(serde_json::Value::as_array)
-        {
-            let message_id = val
-                .pointer("/message/id")
-                .and_then(serde_json::Value::as_str);
-            for item in content {
-                if let Some(call) = extract_tool_call(item, &session_key, message_id, &date_str) {
-                    calls.push(call);
-                }
-            }
+        // Handle direct messages: {"type":"assistant","message":{"content":[...]}}
+        if let Some(message) = val.get("message") {
+            record_message(message, &date_str, thread, &mut file);
         }
 
         // Handle progress messages (subagent): {"type":"progress","data":{"message":{"message":{"content":[...]}}}}
         if let Some(content) = val
             .pointer("/data/message/message/content")
-            .and_then(serde_json::Value::as_array)
+            .and_then(Value::as_array)
         {
             let message_id = val
                 .pointer("/data/message/message/id")
                 .or_else(|| val.pointer("/data/message/id"))
-                .and_then(serde_json::Value::as_str);
+                .and_then(Value::as_str);
             for item in content {
-                if let Some(call) = extract_tool_call(item, &session_key, message_id, &date_str) {
-                    calls.push(call);
+                if let Some(mut call) =
+                    extract_tool_call(item, &file.session_key, message_id, &date_str)
+                {
+                    file.attach(&mut call);
+                    file.calls.push(call);
                 }
             }
         }
     }
 
-    calls
+    for thread in &mut threads {
+        thread.finish_turn(&mut file.calls);
+    }
+    file.calls
 }
 
 fn extract_tool_call(
-    item: &serde_json::Value,
+    item: &Value,
     session_key: &str,
     message_id: Option<&str>,
     date_str: &str,
@@ -87,28 +413,48 @@ fn extract_tool_call(
         let name = item.get("name")?.as_str()?.to_string();
         let identity = message_id.and_then(|msg_id| {
             item.get("id")
-                .and_then(serde_json::Value::as_str)
+                .and_then(Value::as_str)
                 .map(|tool_id| ToolCallIdentity::new(session_key, msg_id, tool_id))
         });
+        let input_str = |key: &str| {
+            item.get("input")
+                .and_then(|input| input.get(key))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        let command = input_str("command").filter(|_| name == "Bash");
+        let subagent_type = input_str("subagent_type");
+        let file_paths = match name.as_str() {
+            "Grep" | "Glob" => input_str("path"),
+            "NotebookRead" | "NotebookEdit" => input_str("notebook_path"),
+            "Read" | "Edit" | "Write" | "MultiEdit" => input_str("file_path"),
+            _ => None,
+        }
+        .into_iter()
+        .collect();
         Some(ToolCall {
             name,
             date_str: date_str.to_string(),
             identity,
+            file_paths,
+            command,
+            subagent_type,
+            ..ToolCall::default()
         })
     } else {
         None
     }
 }
 
-fn extract_date(val: &serde_json::Value, timezone: Timezone) -> String {
+fn extract_date(val: &Value, timezone: Timezone) -> String {
     // Try direct timestamp field
     let ts = val
         .get("timestamp")
-        .and_then(serde_json::Value::as_str)
+        .and_then(Value::as_str)
         // Try nested in progress messages
         .or_else(|| {
             val.pointer("/data/message/timestamp")
-                .and_then(serde_json::Value::as_str)
+                .and_then(Value::as_str)
         });
 
     if let Some(ts) = ts
@@ -152,12 +498,13 @@ mod tests {
 
     #[test]
     fn parse_multiple_tools_in_one_message() {
-        let line = r#"{"type":"assistant","timestamp":"2025-03-01T10:00:00Z","message":{"content":[{"type":"tool_use","name":"Read","id":"t1","input":{}},{"type":"tool_use","name":"Bash","id":"t2","input":{}},{"type":"text","text":"hello"}]}}"#;
+        let line = r#"{"type":"assistant","timestamp":"2025-03-01T10:00:00Z","message":{"content":[{"type":"tool_use","name":"Read","id":"t1","input":{}},{"type":"tool_use","name":"Bash","id":"t2","input":{"command":"cargo test"}},{"type":"text","text":"hello"}]}}"#;
         let f = write_jsonl(&[line]);
         let calls = parse_tool_calls(f.path(), tz());
         assert_eq!(calls.len(), 2);
         assert_eq!(calls[0].name, "Read");
         assert_eq!(calls[1].name, "Bash");
+        assert_eq!(calls[1].command.as_deref(), Some("cargo test"));
     }
 
     #[test]
@@ -200,4 +547,107 @@ mod tests {
         let calls = parse_tool_calls(f.path(), tz());
         assert!(calls.is_empty());
     }
+
+    #[test]
+    fn attributes_output_and_result_growth_to_tools() {
+        let lines = &[
+            // Turn 1 emits Read and Bash over two streamed records.
+            r#"{"type":"assistant","timestamp":"2025-03-01T10:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","content":[{"type":"tool_use","name":"Read","id":"t1","input":{}}],"usage":{"input_tokens":100,"cache_read_input_tokens":900,"output_tokens":10}}}"#,
+            r#"{"type":"assistant","timestamp":"2025-03-01T10:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","content":[{"type":"tool_use","name":"Bash","id":"t2","input":{}}],"usage":{"input_tokens":100,"cache_read_input_tokens":900,"output_tokens":41}}}"#,
+            r#"{"type":"user","timestamp":"2025-03-01T10:00:01Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"}]}}"#,
+            r#"{"type":"user","timestamp":"2025-03-01T10:00:01Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t2","content":[{"type":"text","text":"0123456789012345678901234567890123456789012345"}]}]}}"#,
+            // Turn 2 prompt grew by 1041 - 41 = 1000 tokens of results.
+            r#"{"type":"assistant","timestamp":"2025-03-01T10:00:02Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","content":[{"type":"text","text":"done"}],"usage":{"input_tokens":41,"cache_read_input_tokens":2000,"output_tokens":5}}}"#,
+        ];
+        let f = write_jsonl(lines);
+        let calls = parse_tool_calls(f.path(), tz());
+
+        assert_eq!(calls.len(), 2);
+        let read = &calls[0].attributed["sonnet-4"];
+        let bash = &calls[1].attributed["sonnet-4"];
+        assert_eq!(read.output_tokens + bash.output_tokens, 41);
+        assert_eq!(read.output_tokens, 21);
+        assert_eq!(calls[0].result_bytes, Some(150));
+        assert_eq!(calls[1].result_bytes, Some(46));
+        // The growth is billed as the next request's 41 fresh input tokens
+        // and, for the rest, as cache reads.
+        assert_eq!(read.input_tokens + bash.input_tokens, 41);
+        assert_eq!(read.cache_read + bash.cache_read, 959);
+        assert_eq!(read.cache_creation + bash.cache_creation, 0);
+        assert!(read.cache_read > bash.cache_read * 3);
+        assert!(!calls[0].project_path.is_empty());
+    }
+
+    #[test]
+    fn results_without_a_following_request_only_count_size() {
+        let lines = &[
+            r#"{"type":"assistant","timestamp":"2025-03-01T10:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","content":[{"type":"tool_use","name":"Read","id":"t1","input":{}}],"usage":{"input_tokens":100,"output_tokens":10}}}"#,
+            r#"{"type":"user","timestamp":"2025-03-01T10:00:01Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"abc"}]}}"#,
+        ];
+        let f = write_jsonl(lines);
+        let calls = parse_tool_calls(f.path(), tz());
+
+        assert_eq!(calls[0].result_bytes, Some(3));
+        assert_eq!(calls[0].attributed["sonnet-4"].input_tokens, 0);
+        assert_eq!(calls[0].attributed["sonnet-4"].output_tokens, 10);
+    }
+
+    #[test]
+    fn file_paths_are_relative_to_the_session_cwd() {
+        let line = r#"{"type":"assistant","cwd":"/work/app","timestamp":"2025-03-01T10:00:00Z","message":{"content":[{"type":"tool_use","name":"Edit","id":"t1","input":{"file_path":"/work/app/src/main.rs"}},{"type":"tool_use","name":"Grep","id":"t2","input":{"pattern":"x","path":"/etc"}},{"type":"tool_use","name":"Bash","id":"t3","input":{"command":"ls"}}]}}"#;
+        let f = write_jsonl(&[line]);
+        let calls = parse_tool_calls(f.path(), tz());
+
+        assert_eq!(calls[0].file_paths, ["src/main.rs"]);
+        assert_eq!(calls[1].file_paths, ["/etc"]);
+        assert!(calls[2].file_paths.is_empty());
+        assert_eq!(calls[0].session_key, f.path().display().to_string());
+    }
+
+    #[test]
+    fn growth_fills_cache_writes_then_input_then_cache_reads() {
+        let usage = TurnUsage {
+            input_tokens: 10,
+            cache_creation: 100,
+            cache_creation_1h: 50,
+            cache_read: 1_000,
+            output_tokens: 0,
+        };
+        let growth = usage.split_growth(150);
+        assert_eq!(growth.cache_creation, 100);
+        assert_eq!(growth.cache_creation_1h, 50);
+        assert_eq!(growth.input_tokens, 10);
+        assert_eq!(growth.cache_read, 40);
+        // Growth never exceeds what the request reported.
+        let growth = usage.split_growth(5_000);
+        assert_eq!(growth.cache_read, 1_000);
+        assert_eq!(usage.split_growth(-5), RequestTokens::default());
+    }
+
+    #[test]
+    fn usage_is_only_read_while_a_thread_has_tool_calls_pending() {
+        let lines = &[
+            // No calls yet: this request's usage is skipped.
+            r#"{"type":"assistant","timestamp":"2025-03-01T10:00:00Z","message":{"id":"msg_0","model":"claude-sonnet-4-20250514","content":[{"type":"text","text":"hi"}],"usage":{"input_tokens":5,"output_tokens":99}}}"#,
+            r#"{"type":"assistant","timestamp":"2025-03-01T10:00:01Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","content":[{"type":"tool_use","name":"Read","id":"t1","input":{}}],"usage":{"input_tokens":100,"output_tokens":10}}}"#,
+            // A streamed continuation of the message that emitted the call.
+            r#"{"type":"assistant","timestamp":"2025-03-01T10:00:01Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","content":[{"type":"text","text":"reading"}],"usage":{"input_tokens":100,"output_tokens":30}}}"#,
+            r#"{"type":"user","timestamp":"2025-03-01T10:00:02Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"abc"}]}}"#,
+            r#"{"type":"assistant","timestamp":"2025-03-01T10:00:03Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","content":[{"type":"text","text":"done"}],"usage":{"input_tokens":200,"output_tokens":5}}}"#,
+        ];
+        let f = write_jsonl(lines);
+        let calls = parse_tool_calls(f.path(), tz());
+
+        let read = &calls[0].attributed["sonnet-4"];
+        assert_eq!(read.output_tokens, 30);
+        assert_eq!(read.input_tokens, 70);
+    }
+
+    #[test]
+    fn split_by_weight_preserves_the_total() {
+        assert_eq!(split_by_weight(10, &[1, 1, 1]), vec![4, 3, 3]);
+        assert_eq!(split_by_weight(100, &[3, 1]), vec![75, 25]);
+        assert_eq!(split_by_weight(7, &[0, 0]), vec![4, 3]);
+        assert!(split_by_weight(5, &[]).is_empty());
+    }
 }
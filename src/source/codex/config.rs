@@ -10,6 +10,7 @@ use crate::source::{Capabilities, ParseOutput, Source};
 use crate::utils::Timezone;
 
 use super::parser::{find_codex_files, parse_codex_file_with_scope};
+use super::tool_parser::parse_tool_calls;
 
 #[derive(Debug, Clone, Copy, Default, ValueEnum, PartialEq, Eq)]
 pub(crate) enum CodexScope {
@@ -86,8 +87,9 @@ impl Source for CodexSource {
             has_cache_creation: false,
             has_cache_read: true,
             needs_dedup: true,
-            has_tool_calls: false,
+            has_tool_calls: true,
             has_endpoints: false,
+            has_subagents: false,
         }
     }
 
@@ -98,4 +100,12 @@ impl Source for CodexSource {
     fn parse_file(&self, path: &Path, timezone: Timezone, debug: bool) -> ParseOutput {
         parse_codex_file_with_scope(path, timezone, debug, self.scope)
     }
+
+    fn find_tool_call_files(&self) -> Vec<PathBuf> {
+        find_codex_files()
+    }
+
+    fn parse_tool_call_file(&self, path: &Path, timezone: Timezone) -> Vec<crate::core::ToolCall> {
+        parse_tool_calls(path, timezone, self.scope)
+    }
 }
@@ -6,9 +6,17 @@
 mod config;
 mod parser;
 mod quota;
+mod quota_history;
 mod quota_value;
+mod tool_parser;
 
 pub(crate) use config::{CodexScope, CodexSource};
-pub use quota::{CodexQuotaError, CodexQuotaStatus, CodexWeeklyQuota};
-pub(crate) use quota::{load_weekly_quota, load_weekly_quota_from_home};
+pub use quota::{CodexQuotaError, CodexQuotaStatus, CodexQuotaWindow, CodexWeeklyQuota};
+pub(crate) use quota::{
+    CodexQuotaScan, load_quota_scan, load_quota_windows, load_quota_windows_from_home,
+    load_weekly_quota, load_weekly_quota_from_home,
+};
+#[cfg(test)]
+pub(crate) use quota_history::CodexQuotaSample;
+pub(crate) use quota_history::{CodexQuotaHistory, CodexQuotaWindowHistory, load_quota_history};
 pub(crate) use quota_value::load_weekly_window_usage_from_home;
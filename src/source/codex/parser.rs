@@ -11,7 +11,7 @@ use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 use crate::consts::{DATE_FORMAT, UNKNOWN};
-use crate::core::RawEntry;
+use crate::core::{RawEntry, ServiceTier};
 use crate::source::ParseOutput;
 use crate::utils::Timezone;
 
@@ -45,6 +45,8 @@ struct Payload<'a> {
     model: Option<&'a str>,
     source: Option<serde_json::Value>,
     thread_source: Option<&'a str>,
+    /// Billing tier of the turn (`"flex"`, `"priority"`, ...).
+    service_tier: Option<&'a str>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -280,6 +282,7 @@ struct CodexParseState {
     parse_errors: usize,
     previous_totals: Option<UsageTotals>,
     current_model: Option<String>,
+    current_service_tier: ServiceTier,
     logical_session_key: String,
     session_origin: CodexSessionOrigin,
 }
@@ -291,6 +294,7 @@ impl CodexParseState {
             parse_errors: 0,
             previous_totals: None,
             current_model: None,
+            current_service_tier: ServiceTier::Standard,
             logical_session_key: session_key,
             session_origin: CodexSessionOrigin::Unknown,
         }
@@ -419,7 +423,7 @@ fn process_codex_line(
 
     match raw_entry.entry_type {
         Some("session_meta") => update_session_metadata(raw_entry.payload.as_ref(), state),
-        Some("turn_context") => update_current_model(raw_entry.payload.as_ref(), state),
+        Some("turn_context") => update_turn_context(raw_entry.payload.as_ref(), state),
         Some("event_msg") => process_event_message(&raw_entry, line_no, context, state),
         _ => {}
     }
@@ -434,12 +438,14 @@ fn update_session_metadata(payload: Option<&Payload<'_>>, state: &mut CodexParse
     state.session_origin = session_origin_from_payload(payload);
 }
 
-fn update_current_model(payload: Option<&Payload<'_>>, state: &mut CodexParseState) {
-    if let Some(payload) = payload
-        && let Some(model) = extract_model_ref(payload)
-    {
+fn update_turn_context(payload: Option<&Payload<'_>>, state: &mut CodexParseState) {
+    let Some(payload) = payload else {
+        return;
+    };
+    if let Some(model) = extract_model_ref(payload) {
         state.current_model = Some(model.to_string());
     }
+    state.current_service_tier = ServiceTier::from_label(payload.service_tier);
 }
 
 fn process_event_message(
@@ -481,6 +487,15 @@ enum CodexSessionOrigin {
     Unknown,
 }
 
+/// Whether a `session_meta` line belongs to a session the scope includes.
+pub(super) fn session_meta_in_scope(line: &str, scope: CodexScope) -> bool {
+    let origin = serde_json::from_str::<RawJsonEntry<'_>>(line)
+        .map_or(CodexSessionOrigin::Unknown, |entry| {
+            session_origin_from_payload(entry.payload.as_ref())
+        });
+    scope_includes_origin(scope, origin)
+}
+
 fn scope_includes_origin(scope: CodexScope, origin: CodexSessionOrigin) -> bool {
     match scope {
         CodexScope::All => true,
@@ -633,6 +648,10 @@ fn push_codex_entry(
         endpoint: crate::core::Endpoint::Unknown,
         call_count: 1,
         recorded_cost_usd: None,
+        is_sidechain: false,
+        service_tier: state.current_service_tier,
+        subagent_type: None,
+        parent_session_id: None,
     });
 }
 
@@ -172,6 +172,7 @@ fn test_extract_model_from_info_model() {
         model: Some("fallback-model"),
         source: None,
         thread_source: None,
+        service_tier: None,
         info: Some(TokenInfo {
             total_token_usage: None,
             last_token_usage: None,
@@ -193,6 +194,7 @@ fn test_extract_model_falls_back_to_model_name() {
         model: Some("fallback"),
         source: None,
         thread_source: None,
+        service_tier: None,
         info: Some(TokenInfo {
             total_token_usage: None,
             last_token_usage: None,
@@ -212,6 +214,7 @@ fn test_extract_model_falls_back_to_metadata() {
         model: Some("fallback"),
         source: None,
         thread_source: None,
+        service_tier: None,
         info: Some(TokenInfo {
             total_token_usage: None,
             last_token_usage: None,
@@ -233,6 +236,7 @@ fn test_extract_model_falls_back_to_payload_model() {
         model: Some("payload-model"),
         source: None,
         thread_source: None,
+        service_tier: None,
         info: Some(TokenInfo {
             total_token_usage: None,
             last_token_usage: None,
@@ -252,6 +256,7 @@ fn test_extract_model_no_info_uses_payload() {
         model: Some("payload-only"),
         source: None,
         thread_source: None,
+        service_tier: None,
         info: None,
     };
     assert_eq!(extract_model(&payload), Some("payload-only".to_string()));
@@ -265,6 +270,7 @@ fn test_extract_model_all_none_returns_none() {
         model: None,
         source: None,
         thread_source: None,
+        service_tier: None,
         info: None,
     };
     assert_eq!(extract_model(&payload), None);
@@ -278,6 +284,7 @@ fn test_extract_model_empty_strings_skipped() {
         model: Some("real-model"),
         source: None,
         thread_source: None,
+        service_tier: None,
         info: Some(TokenInfo {
             total_token_usage: None,
             last_token_usage: None,
@@ -16,9 +16,9 @@ use thiserror::Error;
 
 use super::parser::codex_sessions_dir_candidate;
 
-const WEEKLY_WINDOW_MINUTES: i64 = 7 * 24 * 60;
+pub(super) const WEEKLY_WINDOW_MINUTES: i64 = 7 * 24 * 60;
 const DISCOVERY_MARGIN_MINUTES: i64 = 24 * 60;
-const MAX_CLOCK_SKEW_SECONDS: i64 = 5 * 60;
+pub(super) const MAX_CLOCK_SKEW_SECONDS: i64 = 5 * 60;
 const REVERSE_READ_CHUNK_SIZE: usize = 64 * 1024;
 
 /// Projected risk for the current Codex weekly quota window.
@@ -44,10 +44,15 @@ impl CodexQuotaStatus {
     }
 }
 
-/// Provider-authoritative Codex weekly quota usage with a pace projection.
+/// Provider-authoritative usage of one Codex rate-limit window with a pace
+/// projection.
+///
+/// `window_minutes` identifies the window: `10080` is the weekly limit and
+/// shorter values, such as `300`, are the primary limits that reset within a
+/// day.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
-pub struct CodexWeeklyQuota {
+pub struct CodexQuotaWindow {
     pub observed_at: DateTime<Utc>,
     pub resets_at: DateTime<Utc>,
     pub estimated_depletion_at: Option<DateTime<Utc>>,
@@ -58,6 +63,9 @@ pub struct CodexWeeklyQuota {
     pub status: CodexQuotaStatus,
 }
 
+/// Provider-authoritative Codex weekly quota usage with a pace projection.
+pub type CodexWeeklyQuota = CodexQuotaWindow;
+
 /// Errors returned while discovering or reading a Codex weekly quota snapshot.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -104,13 +112,15 @@ pub enum CodexQuotaError {
 }
 
 #[derive(Debug, Clone)]
-struct QuotaSnapshot {
-    observed_at: DateTime<Utc>,
-    resets_at: DateTime<Utc>,
-    window_minutes: i64,
-    used_pct: f64,
+pub(super) struct QuotaSnapshot {
+    pub(super) observed_at: DateTime<Utc>,
+    pub(super) resets_at: DateTime<Utc>,
+    pub(super) window_minutes: i64,
+    pub(super) used_pct: f64,
 }
 
+type LineParser<T> = fn(&str) -> serde_json::Result<Option<T>>;
+
 #[derive(Debug)]
 enum SnapshotLineError {
     Incomplete,
@@ -164,6 +174,39 @@ pub(crate) fn load_weekly_quota() -> Result<CodexWeeklyQuota, CodexQuotaError> {
 pub(crate) fn load_weekly_quota_from_home(
     codex_home: Option<&Path>,
 ) -> Result<CodexWeeklyQuota, CodexQuotaError> {
+    let files = quota_files_from_home(codex_home)?;
+    load_weekly_quota_from_files_at(files, Utc::now())
+}
+
+pub(crate) fn load_quota_windows() -> Result<Vec<CodexQuotaWindow>, CodexQuotaError> {
+    load_quota_windows_from_home(None)
+}
+
+pub(crate) fn load_quota_windows_from_home(
+    codex_home: Option<&Path>,
+) -> Result<Vec<CodexQuotaWindow>, CodexQuotaError> {
+    let files = quota_files_from_home(codex_home)?;
+    load_quota_windows_from_files_at(files, Utc::now())
+}
+
+/// The weekly report and every current window, read in one scan.
+///
+/// The weekly report may come from an older event than the other windows
+/// when the newest event does not carry the weekly window. Only the weekly
+/// report is required; a failure to build the other windows is kept in
+/// `windows` so callers can still show the weekly one.
+#[derive(Debug)]
+pub(crate) struct CodexQuotaScan {
+    pub(crate) weekly: CodexWeeklyQuota,
+    pub(crate) windows: Result<Vec<CodexQuotaWindow>, CodexQuotaError>,
+}
+
+pub(crate) fn load_quota_scan() -> Result<CodexQuotaScan, CodexQuotaError> {
+    let files = quota_files_from_home(None)?;
+    load_quota_scan_from_files_at(files, Utc::now())
+}
+
+fn quota_files_from_home(codex_home: Option<&Path>) -> Result<Vec<PathBuf>, CodexQuotaError> {
     let explicit_home = codex_home.is_some();
     let sessions_dir = if let Some(codex_home) = codex_home {
         codex_home.join("sessions")
@@ -178,8 +221,7 @@ pub(crate) fn load_weekly_quota_from_home(
             (_, error) => Err(error),
         };
     }
-    let files = discover_quota_files(&sessions_dir)?;
-    load_weekly_quota_from_files_at(files, Utc::now())
+    discover_quota_files(&sessions_dir)
 }
 
 pub(super) fn validate_sessions_dir(sessions_dir: &Path) -> Result<(), CodexQuotaError> {
@@ -250,11 +292,140 @@ fn load_weekly_quota_from_files_at(
     build_report(&latest, now)
 }
 
+/// Report every window from the newest rate-limit event.
+///
+/// Windows that already reset are left out because their usage is no longer
+/// known; the call fails only when no window in the event is still current.
+fn load_quota_windows_from_files_at(
+    files: Vec<PathBuf>,
+    now: DateTime<Utc>,
+) -> Result<Vec<CodexQuotaWindow>, CodexQuotaError> {
+    let files = recent_codex_files(files, now)?;
+    let snapshots = latest_event_in_files(files)?.ok_or(CodexQuotaError::SnapshotNotFound)?;
+    windows_from_event(&snapshots, now)
+}
+
+fn windows_from_event(
+    snapshots: &[QuotaSnapshot],
+    now: DateTime<Utc>,
+) -> Result<Vec<CodexQuotaWindow>, CodexQuotaError> {
+    let mut windows = Vec::with_capacity(snapshots.len());
+    let mut expired = None;
+    for snapshot in snapshots {
+        match build_report(snapshot, now) {
+            Ok(window) => windows.push(window),
+            Err(error @ CodexQuotaError::SnapshotExpired { .. }) => {
+                expired.get_or_insert(error);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+    if windows.is_empty() {
+        return Err(expired.unwrap_or(CodexQuotaError::SnapshotNotFound));
+    }
+    windows.sort_by_key(|window| window.window_minutes);
+    Ok(windows)
+}
+
+fn load_quota_scan_from_files_at(
+    files: Vec<PathBuf>,
+    now: DateTime<Utc>,
+) -> Result<CodexQuotaScan, CodexQuotaError> {
+    let files = recent_codex_files(files, now)?;
+    let mut weekly: Option<QuotaSnapshot> = None;
+    let mut event: Option<Vec<QuotaSnapshot>> = None;
+    let mut event_error = None;
+    for path in files {
+        let (file_event, file_weekly) = scan_quota_file(&path)?;
+        if let Some(candidate) = file_weekly
+            && weekly
+                .as_ref()
+                .is_none_or(|current| candidate.observed_at > current.observed_at)
+        {
+            weekly = Some(candidate);
+        }
+        match file_event {
+            Ok(Some(candidate))
+                if event
+                    .as_ref()
+                    .is_none_or(|current| candidate[0].observed_at > current[0].observed_at) =>
+            {
+                event = Some(candidate);
+            }
+            Ok(_) => {}
+            Err(error) => {
+                event_error.get_or_insert(error);
+            }
+        }
+    }
+
+    let weekly = build_report(&weekly.ok_or(CodexQuotaError::SnapshotNotFound)?, now)?;
+    let windows = match event_error {
+        Some(error) => Err(error),
+        None => event
+            .ok_or(CodexQuotaError::SnapshotNotFound)
+            .and_then(|snapshots| windows_from_event(&snapshots, now)),
+    };
+    Ok(CodexQuotaScan { weekly, windows })
+}
+
+type EventResult = Result<Option<Vec<QuotaSnapshot>>, CodexQuotaError>;
+
+/// Read one file from its end until the newest weekly snapshot. The newest
+/// event comes first on the way there, so it is picked up by the same pass.
+/// A malformed non-weekly window only fails the event, not the weekly report.
+fn scan_quota_file(path: &Path) -> Result<(EventResult, Option<QuotaSnapshot>), CodexQuotaError> {
+    let read_error = |source| CodexQuotaError::SessionFile {
+        action: "read",
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut file = File::open(path).map_err(|source| CodexQuotaError::SessionFile {
+        action: "open",
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let mut event = None;
+    let mut weekly = None;
+    scan_from_seekable(&mut file, rate_limit_line, |(timestamp, windows)| {
+        if event.is_none() {
+            event = Some(snapshots_from_windows(timestamp.as_deref(), &windows));
+        }
+        let Some(window) = windows
+            .iter()
+            .find(|window| window.window_minutes == WEEKLY_WINDOW_MINUTES)
+        else {
+            return Ok(false);
+        };
+        let snapshot = observed_at_from_timestamp(timestamp.as_deref())
+            .and_then(|observed_at| snapshot_from_window(observed_at, window))
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        weekly = Some(snapshot);
+        Ok(true)
+    })
+    .map_err(read_error)?;
+
+    let event = event
+        .transpose()
+        .map_err(|error| read_error(io::Error::new(io::ErrorKind::InvalidData, error)));
+    Ok((event, weekly))
+}
+
 pub(super) fn recent_codex_files(
     files: Vec<PathBuf>,
     now: DateTime<Utc>,
 ) -> Result<Vec<PathBuf>, CodexQuotaError> {
-    let cutoff = now - Duration::minutes(WEEKLY_WINDOW_MINUTES + DISCOVERY_MARGIN_MINUTES);
+    codex_files_since(files, now - Duration::minutes(WEEKLY_WINDOW_MINUTES))
+}
+
+/// Keep session files that may hold records observed at or after `since`,
+/// judged by their dated directory or modification time.
+pub(super) fn codex_files_since(
+    files: Vec<PathBuf>,
+    since: DateTime<Utc>,
+) -> Result<Vec<PathBuf>, CodexQuotaError> {
+    let cutoff = since - Duration::minutes(DISCOVERY_MARGIN_MINUTES);
     files
         .into_iter()
         .filter_map(|path| {
@@ -296,40 +467,80 @@ fn session_path_date(path: &Path) -> Option<NaiveDate> {
 }
 
 fn latest_snapshot_in_files(files: Vec<PathBuf>) -> Result<Option<QuotaSnapshot>, CodexQuotaError> {
-    let mut latest: Option<QuotaSnapshot> = None;
+    latest_in_files(files, snapshot_from_line, |snapshot| snapshot.observed_at)
+}
+
+fn latest_event_in_files(
+    files: Vec<PathBuf>,
+) -> Result<Option<Vec<QuotaSnapshot>>, CodexQuotaError> {
+    latest_in_files(files, event_snapshots_from_line, |snapshots| {
+        snapshots
+            .first()
+            .map_or(DateTime::<Utc>::MIN_UTC, |snapshot| snapshot.observed_at)
+    })
+}
+
+fn latest_in_files<T>(
+    files: Vec<PathBuf>,
+    parse: LineParser<T>,
+    observed_at: fn(&T) -> DateTime<Utc>,
+) -> Result<Option<T>, CodexQuotaError> {
+    let mut latest: Option<T> = None;
     for path in files {
-        if let Some(snapshot) = latest_snapshot_in_file(&path)?
+        if let Some(candidate) = latest_in_file(&path, parse)?
             && latest
                 .as_ref()
-                .is_none_or(|current| snapshot.observed_at > current.observed_at)
+                .is_none_or(|current| observed_at(&candidate) > observed_at(current))
         {
-            latest = Some(snapshot);
+            latest = Some(candidate);
         }
     }
     Ok(latest)
 }
 
-fn latest_snapshot_in_file(path: &Path) -> Result<Option<QuotaSnapshot>, CodexQuotaError> {
+fn latest_in_file<T>(path: &Path, parse: LineParser<T>) -> Result<Option<T>, CodexQuotaError> {
     let mut file = File::open(path).map_err(|source| CodexQuotaError::SessionFile {
         action: "open",
         path: path.to_path_buf(),
         source,
     })?;
-    latest_snapshot_from_seekable(&mut file).map_err(|source| CodexQuotaError::SessionFile {
+    latest_from_seekable(&mut file, parse).map_err(|source| CodexQuotaError::SessionFile {
         action: "read",
         path: path.to_path_buf(),
         source,
     })
 }
 
+#[cfg(test)]
 fn latest_snapshot_from_seekable<R: Read + Seek>(
     reader: &mut R,
 ) -> io::Result<Option<QuotaSnapshot>> {
+    latest_from_seekable(reader, snapshot_from_line)
+}
+
+fn latest_from_seekable<R: Read + Seek, T>(
+    reader: &mut R,
+    parse: LineParser<T>,
+) -> io::Result<Option<T>> {
+    let mut latest = None;
+    scan_from_seekable(reader, parse, |value| {
+        latest = Some(value);
+        Ok(true)
+    })?;
+    Ok(latest)
+}
+
+/// Feed parsed lines to `visit` from the end of the file backwards until it
+/// returns `true` or the file is exhausted.
+fn scan_from_seekable<R: Read + Seek, T>(
+    reader: &mut R,
+    parse: LineParser<T>,
+    mut visit: impl FnMut(T) -> io::Result<bool>,
+) -> io::Result<()> {
     let length = reader.seek(SeekFrom::End(0))?;
     if length == 0 {
-        return Ok(None);
+        return Ok(());
     }
-
     reader.seek(SeekFrom::Start(length - 1))?;
     let mut last_byte = [0_u8; 1];
     reader.read_exact(&mut last_byte)?;
@@ -352,8 +563,12 @@ fn latest_snapshot_from_seekable<R: Read + Seek>(
         while let Some(newline) = buffer[..line_end].iter().rposition(|byte| *byte == b'\n') {
             let line = &buffer[newline + 1..line_end];
             if !line.is_empty() {
-                match snapshot_from_bytes(line) {
-                    Ok(Some(snapshot)) => return Ok(Some(snapshot)),
+                match parse_line_bytes(line, parse) {
+                    Ok(Some(value)) => {
+                        if visit(value)? {
+                            return Ok(());
+                        }
+                    }
                     Ok(None) => {}
                     Err(SnapshotLineError::Incomplete)
                         if trailing_segment && !ends_with_newline => {}
@@ -367,16 +582,17 @@ fn latest_snapshot_from_seekable<R: Read + Seek>(
     }
 
     if suffix.is_empty() {
-        return Ok(None);
+        return Ok(());
     }
-    match snapshot_from_bytes(&suffix) {
-        Ok(snapshot) => Ok(snapshot),
-        Err(SnapshotLineError::Incomplete) if trailing_segment && !ends_with_newline => Ok(None),
+    match parse_line_bytes(&suffix, parse) {
+        Ok(Some(value)) => visit(value).map(|_| ()),
+        Ok(None) => Ok(()),
+        Err(SnapshotLineError::Incomplete) if trailing_segment && !ends_with_newline => Ok(()),
         Err(error) => Err(error.into_io_error()),
     }
 }
 
-fn snapshot_from_bytes(line: &[u8]) -> Result<Option<QuotaSnapshot>, SnapshotLineError> {
+fn parse_line_bytes<T>(line: &[u8], parse: LineParser<T>) -> Result<Option<T>, SnapshotLineError> {
     let line = std::str::from_utf8(line).map_err(|error| {
         if error.error_len().is_none() {
             SnapshotLineError::Incomplete
@@ -384,7 +600,7 @@ fn snapshot_from_bytes(line: &[u8]) -> Result<Option<QuotaSnapshot>, SnapshotLin
             SnapshotLineError::Invalid(io::Error::new(io::ErrorKind::InvalidData, error))
         }
     })?;
-    snapshot_from_line(line).map_err(|error| {
+    parse(line).map_err(|error| {
         if error.is_eof() {
             SnapshotLineError::Incomplete
         } else {
@@ -394,6 +610,61 @@ fn snapshot_from_bytes(line: &[u8]) -> Result<Option<QuotaSnapshot>, SnapshotLin
 }
 
 fn snapshot_from_line(line: &str) -> serde_json::Result<Option<QuotaSnapshot>> {
+    let Some((timestamp, windows)) = rate_limit_windows_from_line(line)? else {
+        return Ok(None);
+    };
+    let Some(window) = windows
+        .into_iter()
+        .find(|window| window.window_minutes == WEEKLY_WINDOW_MINUTES)
+    else {
+        return Ok(None);
+    };
+    let observed_at = observed_at_from_timestamp(timestamp)?;
+
+    snapshot_from_window(observed_at, &window).map(Some)
+}
+
+fn event_snapshots_from_line(line: &str) -> serde_json::Result<Option<Vec<QuotaSnapshot>>> {
+    snapshots_from_line(line).map(|snapshots| (!snapshots.is_empty()).then_some(snapshots))
+}
+
+/// Parse every rate-limit window reported on one `token_count` line.
+///
+/// Windows are returned unvalidated so callers decide which ones must be
+/// well-formed; the newest-snapshot reader only fails on the weekly window.
+pub(super) fn snapshots_from_line(line: &str) -> serde_json::Result<Vec<QuotaSnapshot>> {
+    let Some((timestamp, windows)) = rate_limit_windows_from_line(line)? else {
+        return Ok(Vec::new());
+    };
+    snapshots_from_windows(timestamp, &windows)
+}
+
+fn snapshots_from_windows(
+    timestamp: Option<&str>,
+    windows: &[RateLimitWindow],
+) -> serde_json::Result<Vec<QuotaSnapshot>> {
+    if windows.is_empty() {
+        return Ok(Vec::new());
+    }
+    let observed_at = observed_at_from_timestamp(timestamp)?;
+    windows
+        .iter()
+        .map(|window| snapshot_from_window(observed_at, window))
+        .collect()
+}
+
+/// Unvalidated windows of a `token_count` line that reports any.
+fn rate_limit_line(
+    line: &str,
+) -> serde_json::Result<Option<(Option<String>, Vec<RateLimitWindow>)>> {
+    Ok(rate_limit_windows_from_line(line)?
+        .filter(|(_, windows)| !windows.is_empty())
+        .map(|(timestamp, windows)| (timestamp.map(str::to_string), windows)))
+}
+
+fn rate_limit_windows_from_line(
+    line: &str,
+) -> serde_json::Result<Option<(Option<&str>, Vec<RateLimitWindow>)>> {
     if line.trim().is_empty() {
         return Ok(None);
     }
@@ -410,23 +681,19 @@ fn snapshot_from_line(line: &str) -> serde_json::Result<Option<QuotaSnapshot>> {
     let Some(limits) = payload.rate_limits else {
         return Ok(None);
     };
-    let Some(window) = [limits.primary, limits.secondary]
+    let windows = [limits.primary, limits.secondary]
         .into_iter()
         .flatten()
-        .find(|window| window.window_minutes == WEEKLY_WINDOW_MINUTES)
-    else {
-        return Ok(None);
-    };
-    let timestamp = entry
-        .timestamp
-        .ok_or_else(|| serde_json::Error::custom("weekly quota snapshot has no timestamp"))?;
-    let observed_at = timestamp.parse::<DateTime<Utc>>().map_err(|error| {
-        serde_json::Error::custom(format!(
-            "weekly quota snapshot has invalid timestamp: {error}"
-        ))
-    })?;
+        .collect();
+    Ok(Some((entry.timestamp, windows)))
+}
 
-    snapshot_from_window(observed_at, &window).map(Some)
+fn observed_at_from_timestamp(timestamp: Option<&str>) -> serde_json::Result<DateTime<Utc>> {
+    let timestamp =
+        timestamp.ok_or_else(|| serde_json::Error::custom("quota snapshot has no timestamp"))?;
+    timestamp.parse::<DateTime<Utc>>().map_err(|error| {
+        serde_json::Error::custom(format!("quota snapshot has invalid timestamp: {error}"))
+    })
 }
 
 fn snapshot_from_window(
@@ -435,12 +702,11 @@ fn snapshot_from_window(
 ) -> serde_json::Result<QuotaSnapshot> {
     if !window.used_percent.is_finite() || !(0.0..=100.0).contains(&window.used_percent) {
         return Err(serde_json::Error::custom(
-            "weekly quota snapshot has invalid used percentage",
+            "quota snapshot has invalid used percentage",
         ));
     }
-    let resets_at = DateTime::from_timestamp(window.resets_at, 0).ok_or_else(|| {
-        serde_json::Error::custom("weekly quota snapshot has invalid reset timestamp")
-    })?;
+    let resets_at = DateTime::from_timestamp(window.resets_at, 0)
+        .ok_or_else(|| serde_json::Error::custom("quota snapshot has invalid reset timestamp"))?;
     Ok(QuotaSnapshot {
         observed_at,
         resets_at,
@@ -640,6 +906,54 @@ mod tests {
         assert_close(snapshot.used_pct, 30.0);
     }
 
+    #[test]
+    fn windows_report_every_current_window_and_skip_reset_ones() {
+        let root = tempfile::tempdir().unwrap();
+        let session = root.path().join("sessions/2026/08/22/windows.jsonl");
+        fs::create_dir_all(session.parent().unwrap()).unwrap();
+        fs::write(
+            &session,
+            r#"{"timestamp":"2026-08-22T00:00:00Z","type":"event_msg","payload":{"type":"token_count","rate_limits":{"primary":{"used_percent":50.0,"window_minutes":300,"resets_at":1787367600},"secondary":{"used_percent":25.0,"window_minutes":10080,"resets_at":1787875200}}}}"#,
+        )
+        .unwrap();
+
+        let current =
+            load_quota_windows_from_files_at(vec![session.clone()], utc("2026-08-22T01:00:00Z"))
+                .unwrap();
+        let minutes: Vec<_> = current.iter().map(|window| window.window_minutes).collect();
+        assert_eq!(minutes, [300, WEEKLY_WINDOW_MINUTES]);
+        assert_eq!(current[0].status, CodexQuotaStatus::LikelyExhausted);
+
+        let after_short_reset =
+            load_quota_windows_from_files_at(vec![session], utc("2026-08-22T04:00:00Z")).unwrap();
+        assert_eq!(after_short_reset.len(), 1);
+        assert_eq!(after_short_reset[0].window_minutes, WEEKLY_WINDOW_MINUTES);
+    }
+
+    #[test]
+    fn scan_takes_weekly_from_an_older_event_in_the_same_pass() {
+        let root = tempfile::tempdir().unwrap();
+        let session = root.path().join("sessions/2026/08/22/scan.jsonl");
+        fs::create_dir_all(session.parent().unwrap()).unwrap();
+        fs::write(
+            &session,
+            r#"{"timestamp":"2026-08-22T00:00:00Z","type":"event_msg","payload":{"type":"token_count","rate_limits":{"primary":{"used_percent":40.0,"window_minutes":300,"resets_at":1787367600},"secondary":{"used_percent":25.0,"window_minutes":10080,"resets_at":1787875200}}}}
+{"timestamp":"2026-08-22T00:30:00Z","type":"event_msg","payload":{"type":"token_count","rate_limits":{"primary":{"used_percent":50.0,"window_minutes":300,"resets_at":1787367600}}}}
+"#,
+        )
+        .unwrap();
+
+        let scan =
+            load_quota_scan_from_files_at(vec![session], utc("2026-08-22T01:00:00Z")).unwrap();
+
+        assert_eq!(scan.weekly.window_minutes, WEEKLY_WINDOW_MINUTES);
+        assert_eq!(scan.weekly.observed_at, utc("2026-08-22T00:00:00Z"));
+        let windows = scan.windows.unwrap();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].window_minutes, 300);
+        assert_close(windows[0].used_pct, 50.0);
+    }
+
     #[test]
     fn projection_uses_provider_window_boundaries() {
         let snapshot = QuotaSnapshot {
@@ -0,0 +1,421 @@
+//! Codex rate-limit history reconstructed from every logged snapshot.
+//!
+//! Each `token_count` event carries the provider's current used percentage for
+//! every active rate-limit window. Replaying all of them yields the usage curve
+//! of the current and past windows, including when a window reached 100%.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use rayon::prelude::*;
+
+use crate::core::DateFilter;
+use crate::utils::Timezone;
+
+use super::parser::codex_sessions_dir_candidate;
+use super::quota::{
+    CodexQuotaError, MAX_CLOCK_SKEW_SECONDS, QuotaSnapshot, WEEKLY_WINDOW_MINUTES,
+    codex_files_since, discover_quota_files, snapshots_from_line, validate_sessions_dir,
+};
+
+/// One provider-reported used percentage inside a rate-limit window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CodexQuotaSample {
+    pub(crate) observed_at: DateTime<Utc>,
+    pub(crate) used_pct: f64,
+}
+
+/// Reconstructed usage curve for one rate-limit window instance.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CodexQuotaWindowHistory {
+    pub(crate) window_minutes: i64,
+    pub(crate) started_at: DateTime<Utc>,
+    pub(crate) resets_at: DateTime<Utc>,
+    /// Samples where the used percentage changed, plus the last observation.
+    pub(crate) samples: Vec<CodexQuotaSample>,
+    pub(crate) peak_pct: f64,
+    /// First observation at or above 100%, if the window was exhausted.
+    pub(crate) exhausted_at: Option<DateTime<Utc>>,
+    pub(crate) active: bool,
+}
+
+impl CodexQuotaWindowHistory {
+    pub(crate) fn last_pct(&self) -> f64 {
+        self.samples.last().map_or(0.0, |sample| sample.used_pct)
+    }
+}
+
+/// All reconstructed windows, newest window last within each duration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct CodexQuotaHistory {
+    pub(crate) windows: Vec<CodexQuotaWindowHistory>,
+    /// Rate-limit records that could not be parsed and were left out.
+    pub(crate) invalid_records: usize,
+}
+
+pub(crate) fn load_quota_history(
+    filter: &DateFilter,
+    timezone: Timezone,
+) -> Result<CodexQuotaHistory, CodexQuotaError> {
+    let sessions_dir = codex_sessions_dir_candidate().ok_or(CodexQuotaError::SnapshotNotFound)?;
+    if let Err(error) = validate_sessions_dir(&sessions_dir) {
+        return match error {
+            CodexQuotaError::SessionsDirectoryNotFound { .. } => {
+                Err(CodexQuotaError::SnapshotNotFound)
+            }
+            error => Err(error),
+        };
+    }
+    let mut files = discover_quota_files(&sessions_dir)?;
+    if let Some(since) = filter.since {
+        let since = timezone
+            .date_start_utc_millis(since)
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap_or_else(|| since.and_time(NaiveTime::MIN).and_utc());
+        files = codex_files_since(files, since - Duration::minutes(WEEKLY_WINDOW_MINUTES))?;
+    }
+    let history = load_quota_history_from_files(&files, filter, timezone, Utc::now())?;
+    if history.windows.is_empty() {
+        return Err(CodexQuotaError::SnapshotNotFound);
+    }
+    Ok(history)
+}
+
+fn load_quota_history_from_files(
+    files: &[PathBuf],
+    filter: &DateFilter,
+    timezone: Timezone,
+    now: DateTime<Utc>,
+) -> Result<CodexQuotaHistory, CodexQuotaError> {
+    let parsed = files
+        .par_iter()
+        .map(|path| snapshots_in_file(path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut snapshots = Vec::new();
+    let mut invalid_records = 0usize;
+    for (file_snapshots, file_invalid) in parsed {
+        snapshots.extend(file_snapshots);
+        invalid_records = invalid_records.saturating_add(file_invalid);
+    }
+
+    let windows = group_windows(snapshots, now)
+        .into_iter()
+        .filter(|window| window_overlaps_filter(window, filter, timezone))
+        .collect();
+    Ok(CodexQuotaHistory {
+        windows,
+        invalid_records,
+    })
+}
+
+fn snapshots_in_file(path: &Path) -> Result<(Vec<QuotaSnapshot>, usize), CodexQuotaError> {
+    let file = File::open(path).map_err(|source| CodexQuotaError::SessionFile {
+        action: "open",
+        path: path.to_path_buf(),
+        source,
+    })?;
+    snapshots_from_reader(BufReader::new(file)).map_err(|source| CodexQuotaError::SessionFile {
+        action: "read",
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn snapshots_from_reader<R: BufRead>(mut reader: R) -> io::Result<(Vec<QuotaSnapshot>, usize)> {
+    let mut snapshots = Vec::new();
+    let mut invalid = 0usize;
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        // Cheap pre-filter: most records are messages without rate limits.
+        if !line
+            .windows(b"rate_limits".len())
+            .any(|bytes| bytes == b"rate_limits")
+        {
+            continue;
+        }
+        let complete = line.last() == Some(&b'\n');
+        let parsed = std::str::from_utf8(&line)
+            .map_err(|_| ())
+            .and_then(|text| snapshots_from_line(text).map_err(|_| ()));
+        match parsed {
+            Ok(line_snapshots) => snapshots.extend(line_snapshots),
+            // A partially written trailing record is not corruption.
+            Err(()) if !complete => {}
+            Err(()) => invalid += 1,
+        }
+    }
+    Ok((snapshots, invalid))
+}
+
+fn group_windows(
+    mut snapshots: Vec<QuotaSnapshot>,
+    now: DateTime<Utc>,
+) -> Vec<CodexQuotaWindowHistory> {
+    snapshots.sort_by(|a, b| {
+        a.window_minutes
+            .cmp(&b.window_minutes)
+            .reverse()
+            .then(a.observed_at.cmp(&b.observed_at))
+    });
+
+    let tolerance = Duration::seconds(MAX_CLOCK_SKEW_SECONDS);
+    let mut windows: Vec<CodexQuotaWindowHistory> = Vec::new();
+    for snapshot in snapshots {
+        let same_window = windows.last().is_some_and(|window| {
+            window.window_minutes == snapshot.window_minutes
+                && (window.resets_at - snapshot.resets_at).abs() <= tolerance
+        });
+        if !same_window {
+            windows.push(CodexQuotaWindowHistory {
+                window_minutes: snapshot.window_minutes,
+                started_at: snapshot.resets_at - Duration::minutes(snapshot.window_minutes),
+                resets_at: snapshot.resets_at,
+                samples: Vec::new(),
+                peak_pct: 0.0,
+                exhausted_at: None,
+                active: false,
+            });
+        }
+        let Some(window) = windows.last_mut() else {
+            continue;
+        };
+        window.peak_pct = window.peak_pct.max(snapshot.used_pct);
+        if snapshot.used_pct >= 100.0 && window.exhausted_at.is_none() {
+            window.exhausted_at = Some(snapshot.observed_at);
+        }
+        push_sample(
+            &mut window.samples,
+            CodexQuotaSample {
+                observed_at: snapshot.observed_at,
+                used_pct: snapshot.used_pct,
+            },
+        );
+    }
+
+    for window in &mut windows {
+        window.active = now < window.resets_at;
+    }
+    windows
+}
+
+/// Append a sample, collapsing runs of unchanged percentages to their first
+/// and latest observation.
+fn push_sample(samples: &mut Vec<CodexQuotaSample>, sample: CodexQuotaSample) {
+    if let [.., before_last, last] = samples.as_mut_slice()
+        && (before_last.used_pct - sample.used_pct).abs() < f64::EPSILON
+        && (last.used_pct - sample.used_pct).abs() < f64::EPSILON
+    {
+        *last = sample;
+        return;
+    }
+    if samples.last() == Some(&sample) {
+        return;
+    }
+    samples.push(sample);
+}
+
+fn window_overlaps_filter(
+    window: &CodexQuotaWindowHistory,
+    filter: &DateFilter,
+    timezone: Timezone,
+) -> bool {
+    let first_day = timezone.to_fixed_offset(window.started_at).date_naive();
+    let last_day = timezone.to_fixed_offset(window.resets_at).date_naive();
+    filter.since.is_none_or(|since| last_day >= since)
+        && filter.until.is_none_or(|until| first_day <= until)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn event(observed_at: &str, windows: &[(f64, i64, &str)]) -> String {
+        let slots: Vec<_> = windows
+            .iter()
+            .map(|(used, minutes, resets)| {
+                serde_json::json!({
+                    "used_percent": used,
+                    "window_minutes": minutes,
+                    "resets_at": utc(resets).timestamp(),
+                })
+            })
+            .collect();
+        format!(
+            "{}\n",
+            serde_json::json!({
+                "timestamp": observed_at,
+                "type": "event_msg",
+                "payload": {
+                    "type": "token_count",
+                    "rate_limits": {"primary": slots.first(), "secondary": slots.get(1)},
+                },
+            })
+        )
+    }
+
+    fn history_from(input: &str, now: &str) -> CodexQuotaHistory {
+        let (snapshots, invalid_records) = snapshots_from_reader(Cursor::new(input)).unwrap();
+        CodexQuotaHistory {
+            windows: group_windows(snapshots, utc(now)),
+            invalid_records,
+        }
+    }
+
+    #[test]
+    fn groups_samples_into_consecutive_weekly_windows() {
+        let input = [
+            event(
+                "2026-08-02T00:00:00Z",
+                &[(40.0, 10_080, "2026-08-07T00:00:00Z")],
+            ),
+            event(
+                "2026-08-05T00:00:00Z",
+                &[(100.0, 10_080, "2026-08-07T00:00:00Z")],
+            ),
+            event(
+                "2026-08-08T00:00:00Z",
+                &[(5.0, 10_080, "2026-08-14T00:00:00Z")],
+            ),
+        ]
+        .concat();
+
+        let history = history_from(&input, "2026-08-09T00:00:00Z");
+
+        assert_eq!(history.windows.len(), 2);
+        let past = &history.windows[0];
+        assert_eq!(past.started_at, utc("2026-07-31T00:00:00Z"));
+        assert!((past.peak_pct - 100.0).abs() < f64::EPSILON);
+        assert_eq!(past.exhausted_at, Some(utc("2026-08-05T00:00:00Z")));
+        assert!(!past.active);
+        let current = &history.windows[1];
+        assert!((current.last_pct() - 5.0).abs() < f64::EPSILON);
+        assert_eq!(current.exhausted_at, None);
+        assert!(current.active);
+    }
+
+    #[test]
+    fn tracks_short_windows_alongside_weekly_window() {
+        let input = [
+            event(
+                "2026-08-08T10:00:00Z",
+                &[
+                    (30.0, 300, "2026-08-08T12:00:00Z"),
+                    (5.0, 10_080, "2026-08-14T00:00:00Z"),
+                ],
+            ),
+            event(
+                "2026-08-08T13:00:00Z",
+                &[
+                    (10.0, 300, "2026-08-08T17:00:00Z"),
+                    (6.0, 10_080, "2026-08-14T00:00:00Z"),
+                ],
+            ),
+        ]
+        .concat();
+
+        let history = history_from(&input, "2026-08-08T14:00:00Z");
+        let minutes: Vec<_> = history.windows.iter().map(|w| w.window_minutes).collect();
+
+        assert_eq!(minutes, [10_080, 300, 300]);
+        assert_eq!(history.windows[0].samples.len(), 2);
+    }
+
+    #[test]
+    fn unchanged_percentages_collapse_to_first_and_latest_sample() {
+        let input = [
+            event(
+                "2026-08-08T01:00:00Z",
+                &[(10.0, 10_080, "2026-08-14T00:00:00Z")],
+            ),
+            event(
+                "2026-08-08T02:00:00Z",
+                &[(10.0, 10_080, "2026-08-14T00:00:00Z")],
+            ),
+            event(
+                "2026-08-08T03:00:00Z",
+                &[(10.0, 10_080, "2026-08-14T00:00:00Z")],
+            ),
+            event(
+                "2026-08-08T04:00:00Z",
+                &[(12.0, 10_080, "2026-08-14T00:00:00Z")],
+            ),
+        ]
+        .concat();
+
+        let history = history_from(&input, "2026-08-09T00:00:00Z");
+        let times: Vec<_> = history.windows[0]
+            .samples
+            .iter()
+            .map(|sample| sample.observed_at)
+            .collect();
+
+        assert_eq!(
+            times,
+            [
+                utc("2026-08-08T01:00:00Z"),
+                utc("2026-08-08T03:00:00Z"),
+                utc("2026-08-08T04:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_records_are_counted_but_incomplete_tail_is_not() {
+        let input = format!(
+            "{}{}\n{}",
+            event(
+                "2026-08-08T01:00:00Z",
+                &[(10.0, 10_080, "2026-08-14T00:00:00Z")]
+            ),
+            r#"{"type":"event_msg","payload":{"type":"token_count","rate_limits":{"primary":{"used_percent":999.0,"window_minutes":10080,"resets_at":1787801336}}},"timestamp":"2026-08-08T02:00:00Z"}"#,
+            r#"{"timestamp":"2026-08-08T03:00:00Z","type":"event_msg","payload":{"rate_limits":"#,
+        );
+
+        let history = history_from(&input, "2026-08-09T00:00:00Z");
+
+        assert_eq!(history.invalid_records, 1);
+        assert_eq!(history.windows.len(), 1);
+    }
+
+    #[test]
+    fn date_filter_keeps_windows_overlapping_the_range() {
+        let input = [
+            event(
+                "2026-08-02T00:00:00Z",
+                &[(40.0, 10_080, "2026-08-07T00:00:00Z")],
+            ),
+            event(
+                "2026-08-08T00:00:00Z",
+                &[(5.0, 10_080, "2026-08-14T00:00:00Z")],
+            ),
+        ]
+        .concat();
+        let history = history_from(&input, "2026-08-09T00:00:00Z");
+        let filter = DateFilter::new(NaiveDate::from_ymd_opt(2026, 8, 10), None);
+        let utc_tz = Timezone::Named(chrono_tz::UTC);
+
+        let kept: Vec<_> = history
+            .windows
+            .iter()
+            .filter(|window| window_overlaps_filter(window, &filter, utc_tz))
+            .collect();
+
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].resets_at, utc("2026-08-14T00:00:00Z"));
+    }
+}
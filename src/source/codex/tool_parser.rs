@@ -0,0 +1,263 @@
+//! Parser for tool calls in Codex rollout logs
+//!
+//! Codex records each model-issued tool call as a `response_item` whose
+//! payload is a `function_call` (`shell`, `exec_command`, `update_plan`, MCP
+//! tools, ...), a `custom_tool_call` (`apply_patch`), a `local_shell_call`, or
+//! a `web_search_call`. Outputs follow as `function_call_output` /
+//! `custom_tool_call_output` items keyed by `call_id`.
+//!
+//! ```json
+//! {"timestamp":"2026-02-06T12:00:00Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{}","call_id":"call_1"}}
+//! ```
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::consts::{DATE_FORMAT, UNKNOWN};
+use crate::core::{ToolCall, ToolCallIdentity, relative_path};
+use crate::utils::Timezone;
+
+use super::config::CodexScope;
+use super::parser::session_meta_in_scope;
+
+/// Tool name of a `response_item` payload that is a tool call.
+fn tool_name(payload: &Value) -> Option<&str> {
+    match payload.get("type")?.as_str()? {
+        "function_call" | "custom_tool_call" => payload.get("name")?.as_str(),
+        "local_shell_call" => Some("local_shell"),
+        "web_search_call" => Some("web_search"),
+        _ => None,
+    }
+}
+
+/// Command line of a shell call: `local_shell_call` actions, and the
+/// `command` (string or argv) or `cmd` argument of `shell`/`exec_command`.
+fn shell_command(payload: &Value, name: &str) -> Option<String> {
+    let input = if name == "local_shell" {
+        payload.get("action")?.clone()
+    } else if matches!(
+        name,
+        "shell" | "shell_command" | "exec_command" | "container.exec"
+    ) {
+        serde_json::from_str(payload.get("arguments")?.as_str()?).ok()?
+    } else {
+        return None;
+    };
+    match input.get("command").or_else(|| input.get("cmd"))? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(argv) => Some(
+            argv.iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
+/// Files targeted by an `apply_patch` call, from the `*** Add File:`,
+/// `*** Update File:` and `*** Delete File:` headers of its patch.
+fn patch_targets(payload: &Value, name: &str, cwd: &str) -> Vec<String> {
+    if name != "apply_patch" {
+        return Vec::new();
+    }
+    let patch = match payload.get("input").and_then(Value::as_str) {
+        Some(input) => input.to_string(),
+        None => payload
+            .get("arguments")
+            .and_then(Value::as_str)
+            .and_then(|arguments| serde_json::from_str::<Value>(arguments).ok())
+            .and_then(|arguments| arguments.get("input")?.as_str().map(str::to_string))
+            .unwrap_or_default(),
+    };
+    patch
+        .lines()
+        .filter_map(|line| {
+            ["*** Add File: ", "*** Update File: ", "*** Delete File: "]
+                .iter()
+                .find_map(|header| line.strip_prefix(header))
+        })
+        .map(|target| relative_path(target.trim(), Some(cwd)))
+        .collect()
+}
+
+fn is_tool_output(payload: &Value) -> bool {
+    matches!(
+        payload.get("type").and_then(Value::as_str),
+        Some("function_call_output" | "custom_tool_call_output")
+    )
+}
+
+/// Size of a tool output: text length, or the serialized size of structured output.
+fn output_size(payload: &Value) -> u64 {
+    match payload.get("output") {
+        Some(Value::String(text)) => text.len() as u64,
+        Some(other) => other.to_string().len() as u64,
+        None => 0,
+    }
+}
+
+fn entry_date(val: &Value, timezone: Timezone) -> String {
+    val.get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(|ts| ts.parse::<DateTime<Utc>>().ok())
+        .map_or_else(
+            || UNKNOWN.to_string(),
+            |utc_dt| {
+                timezone
+                    .to_fixed_offset(utc_dt)
+                    .date_naive()
+                    .format(DATE_FORMAT)
+                    .to_string()
+            },
+        )
+}
+
+/// Parse a single rollout file and extract tool calls. Forked and resumed
+/// sessions replay earlier items, so calls are identified by the logical
+/// session id and `call_id` and deduplicated at aggregation.
+pub(super) fn parse_tool_calls(
+    path: &Path,
+    timezone: Timezone,
+    scope: CodexScope,
+) -> Vec<ToolCall> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    let mut session_key = path.display().to_string();
+    let mut project_path = String::new();
+    let mut in_scope = scope == CodexScope::All;
+    let mut calls: Vec<ToolCall> = Vec::new();
+    let mut call_index: HashMap<String, usize> = HashMap::new();
+
+    for line in BufReader::new(file).lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        if !line.contains("\"session_meta\"") && !line.contains("\"response_item\"") {
+            continue;
+        }
+        let Ok(val) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let Some(payload) = val.get("payload") else {
+            continue;
+        };
+        if val.get("type").and_then(Value::as_str) == Some("session_meta") {
+            in_scope = session_meta_in_scope(&line, scope);
+            if let Some(id) = payload.get("id").and_then(Value::as_str) {
+                session_key = format!("codex-session:{id}");
+            }
+            if let Some(cwd) = payload.get("cwd").and_then(Value::as_str) {
+                project_path = cwd.to_string();
+            }
+            continue;
+        }
+        if !in_scope {
+            continue;
+        }
+
+        let call_id = payload.get("call_id").and_then(Value::as_str);
+        if let Some(name) = tool_name(payload) {
+            if let Some(id) = call_id {
+                call_index.insert(id.to_string(), calls.len());
+            }
+            calls.push(ToolCall {
+                name: name.to_string(),
+                date_str: entry_date(&val, timezone),
+                identity: call_id.map(|id| ToolCallIdentity::new(&session_key, "", id)),
+                project_path: project_path.clone(),
+                session_key: session_key.clone(),
+                file_paths: patch_targets(payload, name, &project_path),
+                command: shell_command(payload, name),
+                ..ToolCall::default()
+            });
+        } else if is_tool_output(payload)
+            && let Some(&index) = call_id.and_then(|id| call_index.get(id))
+        {
+            calls[index].result_bytes = Some(output_size(payload));
+        }
+    }
+    calls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::aggregate_tools;
+    use std::io::Write;
+
+    fn write_rollout(content: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    fn tz() -> Timezone {
+        Timezone::parse(Some("UTC")).unwrap()
+    }
+
+    #[test]
+    fn parses_function_custom_and_builtin_tool_calls() {
+        let file = write_rollout(
+            r#"{"timestamp":"2026-02-06T12:00:00Z","type":"session_meta","payload":{"id":"s1","cwd":"/work/myapp","source":"cli"}}
+{"timestamp":"2026-02-06T12:00:01Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"ls\"]}","call_id":"call_1"}}
+{"timestamp":"2026-02-06T12:00:02Z","type":"response_item","payload":{"type":"function_call_output","call_id":"call_1","output":"a.rs\nb.rs"}}
+{"timestamp":"2026-02-06T12:00:03Z","type":"response_item","payload":{"type":"custom_tool_call","name":"apply_patch","input":"*** Begin Patch","call_id":"call_2"}}
+{"timestamp":"2026-02-06T12:00:04Z","type":"response_item","payload":{"type":"web_search_call","status":"completed"}}
+{"timestamp":"2026-02-06T12:00:05Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"function_call"}]}}
+"#,
+        );
+
+        let calls = parse_tool_calls(file.path(), tz(), CodexScope::All);
+        let names: Vec<&str> = calls.iter().map(|call| call.name.as_str()).collect();
+        assert_eq!(names, ["shell", "apply_patch", "web_search"]);
+        assert_eq!(calls[0].result_bytes, Some(9));
+        assert_eq!(calls[0].command.as_deref(), Some("ls"));
+        assert_eq!(calls[0].project_path, "/work/myapp");
+        assert_eq!(calls[0].date_str, "2026-02-06");
+        assert_eq!(
+            calls[0].identity,
+            Some(ToolCallIdentity::new("codex-session:s1", "", "call_1"))
+        );
+    }
+
+    #[test]
+    fn apply_patch_targets_are_file_paths() {
+        let file = write_rollout(
+            r#"{"timestamp":"2026-02-06T12:00:00Z","type":"session_meta","payload":{"id":"s1","cwd":"/work/myapp","source":"cli"}}
+{"timestamp":"2026-02-06T12:00:01Z","type":"response_item","payload":{"type":"custom_tool_call","name":"apply_patch","input":"*** Begin Patch\n*** Update File: /work/myapp/src/main.rs\n@@\n-a\n+b\n*** Add File: docs/notes.md\n+hi\n*** End Patch","call_id":"call_1"}}
+{"timestamp":"2026-02-06T12:00:02Z","type":"response_item","payload":{"type":"function_call","name":"apply_patch","arguments":"{\"input\":\"*** Begin Patch\\n*** Delete File: old.rs\\n*** End Patch\"}","call_id":"call_2"}}
+"#,
+        );
+
+        let calls = parse_tool_calls(file.path(), tz(), CodexScope::All);
+        assert_eq!(calls[0].file_paths, ["src/main.rs", "docs/notes.md"]);
+        assert_eq!(calls[1].file_paths, ["old.rs"]);
+        assert_eq!(calls[0].session_key, "codex-session:s1");
+    }
+
+    #[test]
+    fn replayed_calls_deduplicate_and_scope_filters_sessions() {
+        let content = r#"{"timestamp":"2026-02-06T12:00:00Z","type":"session_meta","payload":{"id":"s1","source":"exec"}}
+{"timestamp":"2026-02-06T12:00:01Z","type":"response_item","payload":{"type":"function_call","name":"exec_command","arguments":"{\"cmd\":\"cargo test\"}","call_id":"call_1"}}
+"#;
+        let first = write_rollout(content);
+        let forked = write_rollout(content);
+        let mut calls = parse_tool_calls(first.path(), tz(), CodexScope::All);
+        calls.extend(parse_tool_calls(forked.path(), tz(), CodexScope::All));
+        assert_eq!(aggregate_tools(&calls).total, 1);
+        assert_eq!(calls[0].command.as_deref(), Some("cargo test"));
+
+        assert_eq!(
+            parse_tool_calls(first.path(), tz(), CodexScope::Exec).len(),
+            1
+        );
+        assert!(parse_tool_calls(first.path(), tz(), CodexScope::Interactive).is_empty());
+    }
+}
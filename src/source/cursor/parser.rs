@@ -201,6 +201,10 @@ fn entry_from_event(event: &Value, ordinal: usize, timezone: Timezone) -> Option
         endpoint: crate::core::Endpoint::Unknown,
         call_count: 1,
         recorded_cost_usd,
+        is_sidechain: false,
+        service_tier: crate::core::ServiceTier::Standard,
+        subagent_type: None,
+        parent_session_id: None,
     })
 }
 
@@ -7,6 +7,8 @@ use std::path::{Path, PathBuf};
 use crate::source::{Capabilities, ParseOutput, Source};
 use crate::utils::Timezone;
 
+use super::parser::find_grok_update_files;
+use super::tool_parser::parse_tool_calls;
 use super::unified::{find_grok_files, parse_grok_file_with_debug};
 
 /// Grok data source.
@@ -45,8 +47,9 @@ impl Source for GrokSource {
             has_cache_creation: true,
             has_cache_read: true,
             needs_dedup: true,
-            has_tool_calls: false,
+            has_tool_calls: true,
             has_endpoints: false,
+            has_subagents: false,
         }
     }
 
@@ -57,4 +60,12 @@ impl Source for GrokSource {
     fn parse_file(&self, path: &Path, timezone: Timezone, debug: bool) -> ParseOutput {
         parse_grok_file_with_debug(path, timezone, debug)
     }
+
+    fn find_tool_call_files(&self) -> Vec<PathBuf> {
+        find_grok_update_files()
+    }
+
+    fn parse_tool_call_file(&self, path: &Path, timezone: Timezone) -> Vec<crate::core::ToolCall> {
+        parse_tool_calls(path, timezone)
+    }
 }
@@ -5,6 +5,7 @@
 
 mod config;
 mod parser;
+mod tool_parser;
 mod unified;
 mod usage;
 
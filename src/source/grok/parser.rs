@@ -109,6 +109,23 @@ pub(super) fn find_grok_files() -> Vec<PathBuf> {
     files
 }
 
+/// Session `updates.jsonl` files, which carry tool-call updates.
+pub(super) fn find_grok_update_files() -> Vec<PathBuf> {
+    let Some(sessions_dir) = get_grok_sessions_dir() else {
+        return Vec::new();
+    };
+    let mut by_session: HashMap<PathBuf, PathBuf> = HashMap::new();
+    collect_session_files(
+        &sessions_dir,
+        UPDATES_FILE,
+        &mut by_session,
+        FilePreference::Preferred,
+    );
+    let mut files: Vec<PathBuf> = by_session.into_values().collect();
+    files.sort();
+    files
+}
+
 #[derive(Clone, Copy)]
 enum FilePreference {
     Preferred,
@@ -276,6 +293,16 @@ fn project_path(path: &Path, summary: &Summary) -> String {
         .unwrap_or_default()
 }
 
+/// Project of the session owning a session file, from its `summary.json` when present.
+pub(super) fn session_project_path(path: &Path) -> String {
+    let summary = path
+        .parent()
+        .and_then(|dir| read_optional_json::<Summary>(&dir.join(SUMMARY_FILE), false).ok())
+        .flatten()
+        .unwrap_or_default();
+    project_path(path, &summary)
+}
+
 fn percent_decode_component(value: &str) -> String {
     let bytes = value.as_bytes();
     let mut output = Vec::with_capacity(bytes.len());
@@ -404,6 +431,10 @@ pub(super) fn parse_grok_session_file_with_debug(
             endpoint: crate::core::Endpoint::Unknown,
             call_count: 1,
             recorded_cost_usd: None,
+            is_sidechain: false,
+            service_tier: crate::core::ServiceTier::Standard,
+            subagent_type: None,
+            parent_session_id: None,
         }],
         errors,
     }
@@ -0,0 +1,160 @@
+//! Parser for tool calls in Grok session updates
+//!
+//! `updates.jsonl` carries Agent Client Protocol `session/update`
+//! notifications. A `tool_call` update announces a call with its id and
+//! `kind` (`read`, `edit`, `search`, `execute`, `fetch`, ...); later
+//! `tool_call_update` updates carry its output. ACP has no stable tool name,
+//! so calls are reported by kind.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::consts::{DATE_FORMAT, UNKNOWN};
+use crate::core::{ToolCall, ToolCallIdentity};
+use crate::utils::Timezone;
+
+use super::parser::session_project_path;
+use super::usage::update_time;
+
+const TOOL_CALL: &str = "tool_call";
+const TOOL_CALL_UPDATE: &str = "tool_call_update";
+const OTHER_KIND: &str = "other";
+
+fn update_kind(update: &Value) -> Option<&str> {
+    update
+        .get("sessionUpdate")
+        .or_else(|| update.get("session_update"))
+        .and_then(Value::as_str)
+}
+
+fn update_date(val: &Value, timezone: Timezone) -> String {
+    update_time(
+        val.get("timestamp").and_then(Value::as_i64),
+        val.pointer("/params/_meta/agentTimestampMs")
+            .and_then(Value::as_i64),
+    )
+    .map_or_else(
+        || UNKNOWN.to_string(),
+        |utc_dt| {
+            timezone
+                .to_fixed_offset(utc_dt)
+                .date_naive()
+                .format(DATE_FORMAT)
+                .to_string()
+        },
+    )
+}
+
+/// Size of a tool call's output: its `content` blocks, or `rawOutput`.
+fn output_size(update: &Value) -> Option<u64> {
+    update
+        .get("content")
+        .filter(|content| content.as_array().is_some_and(|blocks| !blocks.is_empty()))
+        .or_else(|| update.get("rawOutput"))
+        .map(|output| match output {
+            Value::String(text) => text.len() as u64,
+            other => other.to_string().len() as u64,
+        })
+}
+
+/// Parse a session's `updates.jsonl` and extract tool calls.
+pub(super) fn parse_tool_calls(path: &Path, timezone: Timezone) -> Vec<ToolCall> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    let session_key = path.parent().map_or_else(
+        || path.display().to_string(),
+        |dir| dir.display().to_string(),
+    );
+    let project_path = session_project_path(path);
+    let mut calls: Vec<ToolCall> = Vec::new();
+    let mut call_index: HashMap<String, usize> = HashMap::new();
+
+    for line in BufReader::new(file).lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        if !line.contains(TOOL_CALL) {
+            continue;
+        }
+        let Ok(val) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let Some(update) = val.pointer("/params/update") else {
+            continue;
+        };
+        let call_id = update.get("toolCallId").and_then(Value::as_str);
+        match update_kind(update) {
+            Some(TOOL_CALL) => {
+                // A repeated announcement of the same call only refreshes it.
+                if call_id.is_some_and(|id| call_index.contains_key(id)) {
+                    continue;
+                }
+                if let Some(id) = call_id {
+                    call_index.insert(id.to_string(), calls.len());
+                }
+                let kind = update
+                    .get("kind")
+                    .and_then(Value::as_str)
+                    .filter(|kind| !kind.is_empty())
+                    .unwrap_or(OTHER_KIND);
+                calls.push(ToolCall {
+                    name: kind.to_string(),
+                    date_str: update_date(&val, timezone),
+                    identity: call_id.map(|id| ToolCallIdentity::new(&session_key, "", id)),
+                    project_path: project_path.clone(),
+                    result_bytes: output_size(update),
+                    ..ToolCall::default()
+                });
+            }
+            Some(TOOL_CALL_UPDATE) => {
+                if let Some(&index) = call_id.and_then(|id| call_index.get(id))
+                    && let Some(bytes) = output_size(update)
+                {
+                    calls[index].result_bytes = Some(bytes);
+                }
+            }
+            _ => {}
+        }
+    }
+    calls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn tz() -> Timezone {
+        Timezone::parse(Some("UTC")).unwrap()
+    }
+
+    #[test]
+    fn parses_tool_call_updates_by_kind() {
+        let root = tempfile::tempdir().unwrap();
+        let session_dir = root.path().join("sessions/%2Fwork%2Fmyapp/session-1");
+        fs::create_dir_all(&session_dir).unwrap();
+        let path = session_dir.join("updates.jsonl");
+        fs::write(
+            &path,
+            r#"{"timestamp":1776374400,"method":"session/update","params":{"sessionId":"session-1","update":{"sessionUpdate":"tool_call","toolCallId":"c1","title":"Run ls","kind":"execute","status":"pending"}}}
+{"timestamp":1776374401,"method":"session/update","params":{"sessionId":"session-1","update":{"sessionUpdate":"tool_call_update","toolCallId":"c1","status":"completed","rawOutput":"a.rs"}}}
+{"timestamp":1776374402,"method":"session/update","params":{"sessionId":"session-1","update":{"sessionUpdate":"tool_call","toolCallId":"c2","title":"Edit main.rs","kind":"edit"}}}
+{"timestamp":1776374402,"method":"session/update","params":{"sessionId":"session-1","update":{"sessionUpdate":"tool_call","toolCallId":"c2","title":"Edit main.rs","kind":"edit"}}}
+{"timestamp":1776374403,"method":"session/update","params":{"sessionId":"session-1","update":{"sessionUpdate":"agent_message_chunk","content":{"type":"text","text":"tool_call"}}}}
+"#,
+        )
+        .unwrap();
+
+        let calls = parse_tool_calls(&path, tz());
+        let names: Vec<&str> = calls.iter().map(|call| call.name.as_str()).collect();
+        assert_eq!(names, ["execute", "edit"]);
+        assert_eq!(calls[0].result_bytes, Some(4));
+        assert_eq!(calls[0].date_str, "2026-04-16");
+        assert_eq!(calls[0].project_path, "/work/myapp");
+    }
+}
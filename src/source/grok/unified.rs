@@ -16,7 +16,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::consts::{DATE_FORMAT, UNKNOWN};
-use crate::core::{CostKind, RawEntry};
+use crate::core::{CostKind, LONG_CONTEXT_THRESHOLD, RawEntry};
 use crate::source::ParseOutput;
 use crate::utils::Timezone;
 
@@ -27,7 +27,6 @@ const DEFAULT_GROK_DIR: &str = ".grok";
 const UNIFIED_LOG: &str = "unified.jsonl";
 const LEDGER_FILE: &str = "inference-v1.jsonl";
 const INFERENCE_DONE: &str = "shell.turn.inference_done";
-const LONG_CONTEXT_THRESHOLD: i64 = 200_000;
 
 #[derive(Debug, Deserialize, Default)]
 #[serde(default)]
@@ -521,6 +520,10 @@ fn records_to_parse_output(
                 endpoint: crate::core::Endpoint::Unknown,
                 call_count: 1,
                 recorded_cost_usd,
+                is_sidechain: false,
+                service_tier: crate::core::ServiceTier::Standard,
+                subagent_type: None,
+                parent_session_id: None,
             })
         })
         .collect();
@@ -284,6 +284,10 @@ fn parse_turn_line(
             endpoint: crate::core::Endpoint::Unknown,
             call_count: normalized.call_count,
             recorded_cost_usd: normalized.recorded_cost_usd,
+            is_sidechain: false,
+            service_tier: crate::core::ServiceTier::Standard,
+            subagent_type: None,
+            parent_session_id: None,
         });
     }
     Ok(Some(entries).filter(|entries| !entries.is_empty()))
@@ -294,13 +298,24 @@ fn event_time(envelope: &UpdateEnvelope) -> Option<DateTime<Utc>> {
         .params
         .as_ref()
         .and_then(|params| params.meta.as_ref())
-        .and_then(|meta| meta.agent_timestamp_ms)
-        .filter(|ms| *ms > 0);
-    if let Some(timestamp) = meta_ms.and_then(DateTime::<Utc>::from_timestamp_millis) {
+        .and_then(|meta| meta.agent_timestamp_ms);
+    update_time(envelope.timestamp, meta_ms)
+}
+
+/// Event time of an update: the agent's millisecond timestamp when valid,
+/// else the envelope timestamp in seconds (or milliseconds).
+pub(super) fn update_time(
+    timestamp: Option<i64>,
+    agent_timestamp_ms: Option<i64>,
+) -> Option<DateTime<Utc>> {
+    if let Some(timestamp) = agent_timestamp_ms
+        .filter(|ms| *ms > 0)
+        .and_then(DateTime::<Utc>::from_timestamp_millis)
+    {
         return Some(timestamp);
     }
 
-    let timestamp = envelope.timestamp.filter(|timestamp| *timestamp > 0)?;
+    let timestamp = timestamp.filter(|timestamp| *timestamp > 0)?;
     if timestamp > 1_000_000_000_000 {
         DateTime::<Utc>::from_timestamp_millis(timestamp)
     } else {
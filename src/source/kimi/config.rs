@@ -8,6 +8,7 @@ use crate::source::{Capabilities, ParseOutput, Source};
 use crate::utils::Timezone;
 
 use super::parser::{find_kimi_files, parse_kimi_wire_file_with_debug};
+use super::tool_parser::parse_tool_calls;
 
 /// Kimi Code CLI data source.
 pub(crate) struct KimiSource;
@@ -45,8 +46,9 @@ impl Source for KimiSource {
             has_cache_creation: true,
             has_cache_read: true,
             needs_dedup: false,
-            has_tool_calls: false,
+            has_tool_calls: true,
             has_endpoints: false,
+            has_subagents: false,
         }
     }
 
@@ -57,4 +59,12 @@ impl Source for KimiSource {
     fn parse_file(&self, path: &Path, timezone: Timezone, debug: bool) -> ParseOutput {
         parse_kimi_wire_file_with_debug(path, timezone, debug)
     }
+
+    fn find_tool_call_files(&self) -> Vec<PathBuf> {
+        find_kimi_files()
+    }
+
+    fn parse_tool_call_file(&self, path: &Path, timezone: Timezone) -> Vec<crate::core::ToolCall> {
+        parse_tool_calls(path, timezone)
+    }
 }
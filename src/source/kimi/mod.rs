@@ -4,5 +4,6 @@
 
 mod config;
 mod parser;
+mod tool_parser;
 
 pub(crate) use config::KimiSource;
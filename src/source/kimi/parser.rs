@@ -168,13 +168,13 @@ fn non_empty_model(model: Option<&str>) -> String {
 }
 
 /// Per-file session identity shared by every entry parsed from one wire file.
-struct SessionContext {
-    session_id: String,
-    session_key: String,
-    project_path: String,
+pub(super) struct SessionContext {
+    pub(super) session_id: String,
+    pub(super) session_key: String,
+    pub(super) project_path: String,
 }
 
-fn session_context(path: &Path, debug: bool) -> SessionContext {
+pub(super) fn session_context(path: &Path, debug: bool) -> SessionContext {
     let session_dir = session_dir_of(path);
     let session_id = session_dir
         .and_then(|dir| dir.file_name())
@@ -285,6 +285,10 @@ fn parse_usage_line(
         endpoint: crate::core::Endpoint::Unknown,
         call_count: 1,
         recorded_cost_usd: None,
+        is_sidechain: false,
+        service_tier: crate::core::ServiceTier::Standard,
+        subagent_type: None,
+        parent_session_id: None,
     })
 }
 
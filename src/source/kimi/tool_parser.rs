@@ -0,0 +1,159 @@
+//! Parser for tool calls in Kimi Code wire logs
+//!
+//! Each tool invocation is a typed `tool.call` record, and its output a
+//! `tool.result` record keyed by the call id:
+//!
+//! ```json
+//! {"type":"tool.call","time":1784247402000,"id":"tc_1","name":"Shell","arguments":"{\"command\":\"ls\"}"}
+//! {"type":"tool.result","time":1784247403000,"toolCallId":"tc_1","output":"Cargo.toml\nsrc"}
+//! ```
+//!
+//! Sub-agent wire files share the session, so call ids are scoped to it.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::consts::{DATE_FORMAT, UNKNOWN};
+use crate::core::{ToolCall, ToolCallIdentity};
+use crate::utils::Timezone;
+
+use super::parser::session_context;
+
+const TOOL_CALL_TYPE: &str = "tool.call";
+const TOOL_RESULT_TYPE: &str = "tool.result";
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ToolRecord {
+    #[serde(rename = "type")]
+    kind: String,
+    time: Option<i64>,
+    id: Option<String>,
+    name: Option<String>,
+    tool_call_id: Option<String>,
+    output: Option<Value>,
+}
+
+fn record_date(time_ms: Option<i64>, timezone: Timezone) -> String {
+    time_ms
+        .and_then(DateTime::<Utc>::from_timestamp_millis)
+        .map_or_else(
+            || UNKNOWN.to_string(),
+            |utc_dt| {
+                timezone
+                    .to_fixed_offset(utc_dt)
+                    .date_naive()
+                    .format(DATE_FORMAT)
+                    .to_string()
+            },
+        )
+}
+
+fn output_size(output: Option<&Value>) -> u64 {
+    match output {
+        Some(Value::String(text)) => text.len() as u64,
+        Some(other) => other.to_string().len() as u64,
+        None => 0,
+    }
+}
+
+/// Parse a single wire file and extract tool calls.
+pub(super) fn parse_tool_calls(path: &Path, timezone: Timezone) -> Vec<ToolCall> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    let ctx = session_context(path, false);
+    let mut calls: Vec<ToolCall> = Vec::new();
+    let mut call_index: HashMap<String, usize> = HashMap::new();
+
+    for line in BufReader::new(file).lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        // Cheap pre-filter: wire files carry large conversation payloads.
+        if !line.contains(TOOL_CALL_TYPE) && !line.contains(TOOL_RESULT_TYPE) {
+            continue;
+        }
+        let Ok(record) = serde_json::from_str::<ToolRecord>(&line) else {
+            continue;
+        };
+        match record.kind.as_str() {
+            TOOL_CALL_TYPE => {
+                let Some(name) = record.name.filter(|name| !name.trim().is_empty()) else {
+                    continue;
+                };
+                if let Some(id) = &record.id {
+                    call_index.insert(id.clone(), calls.len());
+                }
+                calls.push(ToolCall {
+                    name,
+                    date_str: record_date(record.time, timezone),
+                    identity: record
+                        .id
+                        .as_deref()
+                        .map(|id| ToolCallIdentity::new(&ctx.session_key, "", id)),
+                    project_path: ctx.project_path.clone(),
+                    ..ToolCall::default()
+                });
+            }
+            TOOL_RESULT_TYPE => {
+                if let Some(&index) = record
+                    .tool_call_id
+                    .as_deref()
+                    .and_then(|id| call_index.get(id))
+                {
+                    calls[index].result_bytes = Some(output_size(record.output.as_ref()));
+                }
+            }
+            _ => {}
+        }
+    }
+    calls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn tz() -> Timezone {
+        Timezone::parse(Some("UTC")).unwrap()
+    }
+
+    #[test]
+    fn parses_tool_calls_and_results() {
+        let root = tempfile::tempdir().unwrap();
+        let wire = root
+            .path()
+            .join("sessions/wd_myapp_1a2b3c4d5e6f/session-1/agents/main/wire.jsonl");
+        fs::create_dir_all(wire.parent().unwrap()).unwrap();
+        fs::write(
+            &wire,
+            r#"{"type":"turn.prompt","time":1784247400000,"input":"run tool.call please"}
+{"type":"tool.call","time":1784247402000,"id":"tc_1","name":"Shell","arguments":"{\"command\":\"ls\"}"}
+{"type":"tool.result","time":1784247403000,"toolCallId":"tc_1","output":"Cargo.toml"}
+{"type":"tool.call","time":1784247404000,"id":"tc_2","name":"ReadFile","arguments":"{}"}
+{"type":"context.append_message","time":1784247405000,"message":"tool.result lines are JSON"}
+"#,
+        )
+        .unwrap();
+
+        let calls = parse_tool_calls(&wire, tz());
+        let names: Vec<&str> = calls.iter().map(|call| call.name.as_str()).collect();
+        assert_eq!(names, ["Shell", "ReadFile"]);
+        assert_eq!(calls[0].result_bytes, Some(10));
+        assert_eq!(calls[1].result_bytes, None);
+        assert_eq!(calls[0].date_str, "2026-07-17");
+        assert_eq!(calls[0].project_path, "myapp");
+        assert_eq!(
+            calls[0].identity.as_ref().map(|id| id.tool_use_id.as_str()),
+            Some("tc_1")
+        );
+    }
+}
@@ -6,9 +6,10 @@ use std::time::Instant;
 
 use crate::consts::DATE_FORMAT;
 use crate::core::{
-    BlockStats, DateFilter, DedupAccumulator, EndpointStats, LoadResult, ProjectStats, RawEntry,
-    SessionStats, aggregate_blocks, aggregate_by_endpoint, aggregate_daily, aggregate_projects,
-    aggregate_sessions, aggregate_sessions_map, merge_day_stats,
+    BlockStats, DataQuality, DateFilter, DedupAccumulator, EndpointStats, LoadResult, ProjectStats,
+    RawEntry, SessionAgentStats, SessionStats, aggregate_agents, aggregate_blocks,
+    aggregate_by_endpoint, aggregate_daily, aggregate_projects, aggregate_sessions,
+    aggregate_sessions_map, merge_day_stats,
 };
 use crate::source::Source;
 use crate::utils::Timezone;
@@ -21,6 +22,8 @@ pub(super) struct DataLoader<'a> {
     source: &'a dyn Source,
     quiet: bool,
     debug: bool,
+    /// Keep per-day model stats on sessions, projects and blocks.
+    track_days: bool,
 }
 
 impl<'a> DataLoader<'a> {
@@ -29,9 +32,16 @@ impl<'a> DataLoader<'a> {
             source,
             quiet,
             debug,
+            track_days: false,
         }
     }
 
+    /// Keep per-day model stats, for converting costs at daily exchange rates.
+    pub(super) fn with_days(mut self, track_days: bool) -> Self {
+        self.track_days = track_days;
+        self
+    }
+
     #[cfg(test)]
     fn parse_date_fast(date_str: &str) -> Option<NaiveDate> {
         let (year, month, day) = Self::parse_date_parts_fast(date_str)?;
@@ -263,6 +273,7 @@ impl<'a> DataLoader<'a> {
             last_timestamp,
             stats,
             models,
+            context_samples,
             ..
         } = incoming;
 
@@ -276,6 +287,7 @@ impl<'a> DataLoader<'a> {
             into.last_timestamp = last_timestamp;
         }
         into.stats.add(&stats);
+        into.context_samples.extend(context_samples);
         for (model, stats) in models {
             into.models.entry(model).or_default().add(&stats);
         }
@@ -288,11 +300,11 @@ impl<'a> DataLoader<'a> {
         &self,
         filter: &DateFilter,
         timezone: Timezone,
-    ) -> Vec<SessionStats> {
+    ) -> (Vec<SessionStats>, DataQuality) {
         let result = self.par_process(
             filter,
             timezone,
-            aggregate_sessions_map,
+            |entries| aggregate_sessions_map(entries, self.track_days),
             HashMap::<String, SessionStats>::new,
             |mut acc, partial| {
                 for (session_key, session) in partial {
@@ -306,10 +318,16 @@ impl<'a> DataLoader<'a> {
             },
         );
 
-        match result {
-            Some((merged, _)) => merged.into_values().collect(),
-            None => Vec::new(),
-        }
+        let Some((merged, parse_errors)) = result else {
+            return (Vec::new(), DataQuality::default());
+        };
+        let sessions: Vec<SessionStats> = merged.into_values().collect();
+        let data_quality = DataQuality {
+            valid_entries: sessions.iter().map(|session| session.stats.records).sum(),
+            dedup_skipped_entries: 0,
+            parse_errors,
+        };
+        (sessions, data_quality)
     }
 
     /// Load and aggregate daily stats
@@ -357,17 +375,27 @@ impl<'a> DataLoader<'a> {
     }
 
     /// Load session stats
-    fn load_sessions(&self, filter: &DateFilter, timezone: Timezone) -> Vec<SessionStats> {
+    fn load_sessions(
+        &self,
+        filter: &DateFilter,
+        timezone: Timezone,
+    ) -> (Vec<SessionStats>, DataQuality) {
         if !self.source.capabilities().needs_dedup {
             return self.load_sessions_incremental(filter, timezone);
         }
 
-        let (final_entries, skipped, _) = self.load_deduped_entries_incremental(filter, timezone);
+        let (final_entries, skipped, parse_errors) =
+            self.load_deduped_entries_incremental(filter, timezone);
+        let data_quality = DataQuality {
+            valid_entries: final_entries.len() as i64,
+            dedup_skipped_entries: skipped,
+            parse_errors,
+        };
         if final_entries.is_empty() {
-            return Vec::new();
+            return (Vec::new(), data_quality);
         }
 
-        let sessions = aggregate_sessions(final_entries);
+        let sessions = aggregate_sessions(final_entries, self.track_days);
 
         if !self.quiet {
             if skipped > 0 {
@@ -381,23 +409,78 @@ impl<'a> DataLoader<'a> {
             }
         }
 
-        sessions
+        (sessions, data_quality)
     }
 
     /// Load project stats (only for sources that support it)
-    fn load_projects(&self, filter: &DateFilter, timezone: Timezone) -> Vec<ProjectStats> {
+    fn load_projects(
+        &self,
+        filter: &DateFilter,
+        timezone: Timezone,
+    ) -> (Vec<ProjectStats>, DataQuality) {
         if !self.source.capabilities().has_projects {
-            return Vec::new();
+            return (Vec::new(), DataQuality::default());
         }
 
-        let sessions = self.load_sessions(filter, timezone);
+        let (sessions, data_quality) = self.load_sessions(filter, timezone);
         let projects = aggregate_projects(sessions);
 
         if !self.quiet {
             eprintln!("Aggregated into {} projects", projects.len());
         }
 
-        projects
+        (projects, data_quality)
+    }
+
+    /// Load the entries of sessions whose id starts with `id_prefix`,
+    /// deduplicated for sources that need it.
+    pub(super) fn load_session_entries(
+        &self,
+        filter: &DateFilter,
+        timezone: Timezone,
+        id_prefix: &str,
+    ) -> (Vec<RawEntry>, DataQuality) {
+        let matches = |entry: &RawEntry| entry.session_id.starts_with(id_prefix);
+        if self.source.capabilities().needs_dedup {
+            let result = self.par_process(
+                filter,
+                timezone,
+                |filtered| {
+                    let mut partial = DedupAccumulator::new();
+                    partial.extend(filtered.into_iter().filter(|entry| matches(entry)));
+                    partial
+                },
+                DedupAccumulator::new,
+                |mut acc, partial| {
+                    acc.merge(partial);
+                    acc
+                },
+            );
+            let Some((acc, parse_errors)) = result else {
+                return (Vec::new(), DataQuality::default());
+            };
+            let (entries, skipped) = acc.finalize();
+            return entries_with_quality(entries, skipped, parse_errors);
+        }
+        self.par_process(
+            filter,
+            timezone,
+            |filtered| {
+                filtered
+                    .into_iter()
+                    .filter(|entry| matches(entry))
+                    .collect()
+            },
+            Vec::new,
+            |mut acc, partial: Vec<RawEntry>| {
+                acc.extend(partial);
+                acc
+            },
+        )
+        .map_or_else(
+            || (Vec::new(), DataQuality::default()),
+            |(entries, parse_errors)| entries_with_quality(entries, 0, parse_errors),
+        )
     }
 
     /// Load per-endpoint stats (native vs proxy). Only for sources that
@@ -417,44 +500,64 @@ impl<'a> DataLoader<'a> {
         aggregate_by_endpoint(final_entries)
     }
 
-    /// Load block stats (only for sources that support it)
-    fn load_blocks(&self, filter: &DateFilter, timezone: Timezone) -> Vec<BlockStats> {
-        if !self.source.capabilities().has_billing_blocks {
+    /// Load the main-thread vs subagent split of each session. Only for
+    /// sources that mark subagent requests (Claude); others return empty.
+    pub(super) fn load_agents(
+        &self,
+        filter: &DateFilter,
+        timezone: Timezone,
+    ) -> Vec<SessionAgentStats> {
+        if !self.source.capabilities().has_subagents {
             return Vec::new();
         }
+        let (final_entries, _skipped, _) = self.load_deduped_entries_incremental(filter, timezone);
+        aggregate_agents(final_entries)
+    }
 
-        let (final_entries, skipped) = if self.source.capabilities().needs_dedup {
-            let (entries, skipped, _) = self.load_deduped_entries_incremental(filter, timezone);
-            (entries, skipped)
-        } else {
-            match self.par_process(
-                filter,
-                timezone,
-                |filtered| filtered,
-                Vec::new,
-                |mut acc, partial| {
-                    acc.extend(partial);
-                    acc
-                },
-            ) {
-                Some((entries, _)) => (entries, 0),
-                None => return Vec::new(),
-            }
-        };
-        if final_entries.is_empty() {
-            return Vec::new();
+    /// Load every entry in the filter, deduplicated for sources that need it.
+    pub(super) fn load_entries(
+        &self,
+        filter: &DateFilter,
+        timezone: Timezone,
+    ) -> (Vec<RawEntry>, DataQuality) {
+        if self.source.capabilities().needs_dedup {
+            let (entries, skipped, parse_errors) =
+                self.load_deduped_entries_incremental(filter, timezone);
+            return entries_with_quality(entries, skipped, parse_errors);
+        }
+        self.par_process(
+            filter,
+            timezone,
+            |filtered| filtered,
+            Vec::new,
+            |mut acc, partial| {
+                acc.extend(partial);
+                acc
+            },
+        )
+        .map_or_else(
+            || (Vec::new(), DataQuality::default()),
+            |(entries, parse_errors)| entries_with_quality(entries, 0, parse_errors),
+        )
+    }
+
+    /// Load block stats (only for sources that support it)
+    fn load_blocks(
+        &self,
+        filter: &DateFilter,
+        timezone: Timezone,
+    ) -> (Vec<BlockStats>, DataQuality) {
+        if !self.source.capabilities().has_billing_blocks {
+            return (Vec::new(), DataQuality::default());
         }
 
-        // Build local time map for block calculation
-        let mut local_times: HashMap<i64, DateTime<FixedOffset>> = HashMap::new();
-        for entry in &final_entries {
-            if let Some(utc_dt) = DateTime::<Utc>::from_timestamp_millis(entry.timestamp_ms) {
-                let local_dt = timezone.to_fixed_offset(utc_dt);
-                local_times.insert(entry.timestamp_ms, local_dt);
-            }
+        let (final_entries, data_quality) = self.load_entries(filter, timezone);
+        if final_entries.is_empty() {
+            return (Vec::new(), data_quality);
         }
+        let skipped = data_quality.dedup_skipped_entries;
 
-        let blocks = aggregate_blocks(final_entries, &local_times);
+        let blocks = blocks_from_entries(final_entries, timezone, self.track_days);
 
         if !self.quiet {
             if skipped > 0 {
@@ -468,10 +571,41 @@ impl<'a> DataLoader<'a> {
             }
         }
 
-        blocks
+        (blocks, data_quality)
     }
 }
 
+/// Entries with the record counts behind them.
+fn entries_with_quality(
+    entries: Vec<RawEntry>,
+    skipped: i64,
+    parse_errors: usize,
+) -> (Vec<RawEntry>, DataQuality) {
+    let data_quality = DataQuality {
+        valid_entries: entries.len() as i64,
+        dedup_skipped_entries: skipped,
+        parse_errors,
+    };
+    (entries, data_quality)
+}
+
+/// Group entries into 5-hour billing blocks in local time, with per-day model
+/// stats when `track_days` is set.
+pub(crate) fn blocks_from_entries(
+    entries: Vec<RawEntry>,
+    timezone: Timezone,
+    track_days: bool,
+) -> Vec<BlockStats> {
+    let mut local_times: HashMap<i64, DateTime<FixedOffset>> = HashMap::new();
+    for entry in &entries {
+        if let Some(utc_dt) = DateTime::<Utc>::from_timestamp_millis(entry.timestamp_ms) {
+            let local_dt = timezone.to_fixed_offset(utc_dt);
+            local_times.insert(entry.timestamp_ms, local_dt);
+        }
+    }
+    aggregate_blocks(entries, &local_times, track_days)
+}
+
 /// Convenience function to load daily stats for a source
 pub(crate) fn load_daily(
     source: &dyn Source,
@@ -484,36 +618,42 @@ pub(crate) fn load_daily(
     loader.load_daily(filter, timezone)
 }
 
-/// Convenience function to load sessions for a source
+/// Convenience function to load sessions for a source, with the record counts
+/// behind them
 pub(crate) fn load_sessions(
     source: &dyn Source,
     filter: &DateFilter,
     timezone: Timezone,
     quiet: bool,
-) -> Vec<SessionStats> {
-    let loader = DataLoader::new(source, quiet, false);
+    track_days: bool,
+) -> (Vec<SessionStats>, DataQuality) {
+    let loader = DataLoader::new(source, quiet, false).with_days(track_days);
     loader.load_sessions(filter, timezone)
 }
 
-/// Convenience function to load projects for a source
+/// Convenience function to load projects for a source, with the record counts
+/// behind them
 pub(crate) fn load_projects(
     source: &dyn Source,
     filter: &DateFilter,
     timezone: Timezone,
     quiet: bool,
-) -> Vec<ProjectStats> {
-    let loader = DataLoader::new(source, quiet, false);
+    track_days: bool,
+) -> (Vec<ProjectStats>, DataQuality) {
+    let loader = DataLoader::new(source, quiet, false).with_days(track_days);
     loader.load_projects(filter, timezone)
 }
 
-/// Convenience function to load blocks for a source
+/// Convenience function to load blocks for a source, with the record counts
+/// behind them
 pub(crate) fn load_blocks(
     source: &dyn Source,
     filter: &DateFilter,
     timezone: Timezone,
     quiet: bool,
-) -> Vec<BlockStats> {
-    let loader = DataLoader::new(source, quiet, false);
+    track_days: bool,
+) -> (Vec<BlockStats>, DataQuality) {
+    let loader = DataLoader::new(source, quiet, false).with_days(track_days);
     loader.load_blocks(filter, timezone)
 }
 
@@ -544,6 +684,10 @@ pub(crate) fn load_tool_calls(
                     chrono::NaiveDate::parse_from_str(&c.date_str, crate::consts::DATE_FORMAT)
                         .is_ok_and(|d| filter.contains(d))
                 })
+                .map(|mut c| {
+                    c.source = source.name();
+                    c
+                })
                 .collect::<Vec<_>>()
         })
         .collect();
@@ -579,6 +723,10 @@ mod tests {
             endpoint: crate::core::Endpoint::Unknown,
             call_count: 1,
             recorded_cost_usd: None,
+            is_sidechain: false,
+            service_tier: crate::core::ServiceTier::Standard,
+            subagent_type: None,
+            parent_session_id: None,
         }
     }
 
@@ -690,6 +838,7 @@ mod tests {
                 );
                 m
             },
+            ..Default::default()
         }
     }
 
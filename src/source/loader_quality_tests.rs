@@ -63,6 +63,10 @@ fn entry(id: &str, input_tokens: i64) -> RawEntry {
         endpoint: crate::core::Endpoint::Unknown,
         call_count: 1,
         recorded_cost_usd: None,
+        is_sidechain: false,
+        service_tier: crate::core::ServiceTier::Standard,
+        subagent_type: None,
+        parent_session_id: None,
     }
 }
 
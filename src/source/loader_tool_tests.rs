@@ -41,6 +41,7 @@ impl Source for TestToolSource {
             name: "Read".to_string(),
             date_str: "2026-02-06".to_string(),
             identity: None,
+            ..Default::default()
         }]
     }
 }
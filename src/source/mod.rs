@@ -43,6 +43,8 @@ pub(crate) struct Capabilities {
     pub(crate) has_tool_calls: bool,
     /// Populates the serving-endpoint field (native vs proxy classification)
     pub(crate) has_endpoints: bool,
+    /// Marks subagent (sidechain) requests, enabling the main/subagent split
+    pub(crate) has_subagents: bool,
 }
 
 impl Capabilities {
@@ -63,6 +65,7 @@ impl Capabilities {
             combined.needs_dedup |= caps.needs_dedup;
             combined.has_tool_calls |= caps.has_tool_calls;
             combined.has_endpoints |= caps.has_endpoints;
+            combined.has_subagents |= caps.has_subagents;
         }
         combined.has_cache_read &= has_sources;
         combined
@@ -113,9 +116,15 @@ pub(crate) trait Source: Send + Sync {
 /// Box type for dynamic dispatch
 pub(crate) type BoxedSource = Box<dyn Source>;
 
+#[cfg(test)]
+pub(crate) use codex::CodexQuotaSample;
 pub(crate) use codex::load_weekly_window_usage_from_home;
-pub use codex::{CodexQuotaError, CodexQuotaStatus, CodexWeeklyQuota};
-pub(crate) use codex::{CodexScope, CodexSource, load_weekly_quota, load_weekly_quota_from_home};
+pub use codex::{CodexQuotaError, CodexQuotaStatus, CodexQuotaWindow, CodexWeeklyQuota};
+pub(crate) use codex::{
+    CodexQuotaHistory, CodexQuotaScan, CodexQuotaWindowHistory, CodexScope, CodexSource,
+    load_quota_history, load_quota_scan, load_quota_windows, load_quota_windows_from_home,
+    load_weekly_quota, load_weekly_quota_from_home,
+};
 
 // Re-export registry functions
 pub(crate) use registry::{ALL_SOURCES, all_sources, get_source, source_choices, suggest_source};
@@ -125,7 +134,9 @@ pub(crate) fn all_capabilities() -> Capabilities {
 }
 
 // Re-export loader functions
-pub(crate) use loader::{load_blocks, load_daily, load_projects, load_sessions, load_tool_calls};
+pub(crate) use loader::{
+    blocks_from_entries, load_blocks, load_daily, load_projects, load_sessions, load_tool_calls,
+};
 
 /// Load per-endpoint stats (native vs proxy) for a source. Claude-only; other
 /// sources return empty. Lives here (not in `loader.rs`) to keep that file
@@ -137,3 +148,56 @@ pub(crate) fn load_endpoints(
 ) -> Vec<crate::core::EndpointStats> {
     loader::DataLoader::new(source, false, false).load_endpoints(filter, timezone)
 }
+
+/// Load the main-thread vs subagent split of each session. Claude-only;
+/// other sources return empty. Lives here for the same reason as
+/// [`load_endpoints`].
+pub(crate) fn load_agents(
+    source: &dyn Source,
+    filter: &crate::core::DateFilter,
+    timezone: Timezone,
+) -> Vec<crate::core::SessionAgentStats> {
+    loader::DataLoader::new(source, false, false).load_agents(filter, timezone)
+}
+
+/// Load every entry in `filter`, deduplicated for sources that need it, so a
+/// command can build several views from one scan. Lives here for the same
+/// reason as [`load_endpoints`].
+pub(crate) fn load_entries(
+    source: &dyn Source,
+    filter: &crate::core::DateFilter,
+    timezone: Timezone,
+) -> Vec<crate::core::RawEntry> {
+    loader::DataLoader::new(source, true, false)
+        .load_entries(filter, timezone)
+        .0
+}
+
+/// Load the entries of sessions whose id starts with `id_prefix`, with the
+/// record counts behind them, for `session show`. Lives here for the same
+/// reason as [`load_endpoints`].
+pub(crate) fn load_session_entries(
+    source: &dyn Source,
+    filter: &crate::core::DateFilter,
+    timezone: Timezone,
+    id_prefix: &str,
+) -> (Vec<crate::core::RawEntry>, crate::core::DataQuality) {
+    loader::DataLoader::new(source, false, false).load_session_entries(filter, timezone, id_prefix)
+}
+
+/// Load every entry of one log file across all dates, deduplicated for sources
+/// that need it, for the statusLine hook's session transcript. Lives here for
+/// the same reason as [`load_endpoints`].
+pub(crate) fn load_file_entries(
+    source: &dyn Source,
+    path: &Path,
+    timezone: Timezone,
+) -> Vec<RawEntry> {
+    let entries = source.parse_file(path, timezone, false).entries;
+    if !source.capabilities().needs_dedup {
+        return entries;
+    }
+    let mut acc = crate::core::DedupAccumulator::new();
+    acc.extend(entries);
+    acc.finalize().0
+}
@@ -203,6 +203,7 @@ mod tests {
         assert!(caps.has_cache_read);
         assert!(caps.needs_dedup);
         assert!(caps.has_tool_calls);
+        assert!(caps.has_subagents);
         assert!(!caps.has_reasoning_tokens);
     }
 
@@ -216,7 +217,8 @@ mod tests {
         assert!(caps.has_cache_read);
         assert!(caps.needs_dedup);
         assert!(caps.has_reasoning_tokens);
-        assert!(!caps.has_tool_calls);
+        assert!(caps.has_tool_calls);
+        assert!(!caps.has_subagents);
     }
 
     #[test]
@@ -242,7 +244,7 @@ mod tests {
         assert!(caps.has_cache_read);
         assert!(caps.needs_dedup);
         assert!(caps.has_reasoning_tokens);
-        assert!(!caps.has_tool_calls);
+        assert!(caps.has_tool_calls);
     }
 
     #[test]
@@ -254,7 +256,7 @@ mod tests {
         assert!(caps.has_cache_creation);
         assert!(!caps.needs_dedup);
         assert!(!caps.has_reasoning_tokens);
-        assert!(!caps.has_tool_calls);
+        assert!(caps.has_tool_calls);
     }
 
     #[test]
@@ -2,10 +2,9 @@
 //!
 //! Lives in its own module to keep `app.rs` under the module size limit.
 
-use crate::app::{CommandContext, print_json};
-use crate::output::OutputFormat;
+use crate::app::{CommandContext, print_json_rows};
+use crate::output::{CapabilitiesJson, OutputFormat, SCHEMA_VERSION, SourceJson};
 use crate::source::{ALL_SOURCES, Capabilities, Source, all_capabilities, all_sources};
-use serde_json::json;
 
 pub(crate) fn handle_sources(ctx: &CommandContext<'_>) {
     let sources: Vec<&dyn Source> = all_sources().collect();
@@ -20,7 +19,9 @@ fn render_sources(sources: &[&dyn Source], all_caps: &Capabilities, ctx: &Comman
     match ctx.cli.output_format() {
         OutputFormat::Csv => render_sources_csv(sources, all_caps),
         OutputFormat::Json => render_sources_json(sources, all_caps, ctx),
-        OutputFormat::Table => print_sources_table(sources, all_caps),
+        OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html => {
+            print_sources_table(sources, all_caps);
+        }
     }
 }
 
@@ -56,38 +57,33 @@ fn render_sources_csv(sources: &[&dyn Source], all_caps: &Capabilities) {
     }
 }
 
+fn capabilities_json(caps: &Capabilities) -> CapabilitiesJson {
+    CapabilitiesJson {
+        has_projects: caps.has_projects,
+        has_billing_blocks: caps.has_billing_blocks,
+        has_reasoning_tokens: caps.has_reasoning_tokens,
+        has_cache_creation: caps.has_cache_creation,
+        has_cache_read: caps.has_cache_read,
+        needs_dedup: caps.needs_dedup,
+    }
+}
+
 fn render_sources_json(sources: &[&dyn Source], all_caps: &Capabilities, ctx: &CommandContext<'_>) {
-    let mut payload = vec![json!({
-        "name": ALL_SOURCES,
-        "display_name": "All Sources",
-        "aliases": [],
-        "capabilities": {
-            "has_projects": all_caps.has_projects,
-            "has_billing_blocks": all_caps.has_billing_blocks,
-            "has_reasoning_tokens": all_caps.has_reasoning_tokens,
-            "has_cache_creation": all_caps.has_cache_creation,
-            "has_cache_read": all_caps.has_cache_read,
-            "needs_dedup": all_caps.needs_dedup
-        }
-    })];
-    payload.extend(sources.iter().map(|source| {
-        let caps = source.capabilities();
-        json!({
-            "name": source.name(),
-            "display_name": source.display_name(),
-            "aliases": source.aliases(),
-            "capabilities": {
-                "has_projects": caps.has_projects,
-                "has_billing_blocks": caps.has_billing_blocks,
-                "has_reasoning_tokens": caps.has_reasoning_tokens,
-                "has_cache_creation": caps.has_cache_creation,
-                "has_cache_read": caps.has_cache_read,
-                "needs_dedup": caps.needs_dedup
-            }
-        })
+    let mut payload = vec![SourceJson {
+        schema_version: SCHEMA_VERSION,
+        name: ALL_SOURCES,
+        display_name: "All Sources",
+        aliases: &[],
+        capabilities: capabilities_json(all_caps),
+    }];
+    payload.extend(sources.iter().map(|source| SourceJson {
+        schema_version: SCHEMA_VERSION,
+        name: source.name(),
+        display_name: source.display_name(),
+        aliases: source.aliases(),
+        capabilities: capabilities_json(&source.capabilities()),
     }));
-    let json = serde_json::to_string(&payload).unwrap_or_else(|_| "[]".to_string());
-    print_json(&json, ctx.jq_filter);
+    print_json_rows(payload, false, ctx);
 }
 
 fn print_sources_table(sources: &[&dyn Source], all_caps: &Capabilities) {
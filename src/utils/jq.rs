@@ -1,10 +1,145 @@
+//! `--jq` filtering. Filters run on a built-in jq-compatible engine, so no
+//! `jq` binary is needed; the external binary is only used with
+//! `--jq-fallback` for expressions the built-in engine cannot compile.
+
+use std::fmt::Write as _;
 use std::io::Write;
 use std::process::{Command, Stdio};
 
+use hifijson::token::Lex;
+use jaq_core::load::{Arena, File, Loader};
+use jaq_core::{Compiler, Ctx, RcIter, compile, load};
+use jaq_json::Val;
+
 use crate::error::JqError;
 
-/// Pipe JSON through jq with the given filter expression
-pub(crate) fn filter_json(json: &str, filter: &str) -> Result<String, JqError> {
+/// Filter JSON with a jq expression. With `fallback`, expressions the
+/// built-in engine does not support are piped through the `jq` binary.
+pub(crate) fn filter_json(json: &str, filter: &str, fallback: bool) -> Result<String, JqError> {
+    match filter_builtin(json, filter) {
+        Err(JqError::Unsupported(_)) if fallback => filter_external(json, filter),
+        result => result,
+    }
+}
+
+/// Run the filter on the built-in engine and print each result like
+/// `jq` does: pretty-printed with two-space indents, one value per line.
+fn filter_builtin(json: &str, filter: &str) -> Result<String, JqError> {
+    let arena = Arena::default();
+    let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+    let modules = loader
+        .load(
+            &arena,
+            File {
+                code: filter,
+                path: (),
+            },
+        )
+        .map_err(|errs| JqError::Unsupported(load_error_message(errs)))?;
+    let filter = Compiler::default()
+        .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+        .compile(modules)
+        .map_err(|errs| JqError::Unsupported(compile_error_message(errs)))?;
+
+    let mut lexer = hifijson::SliceLexer::new(json.as_bytes());
+    let input = lexer
+        .exactly_one(Val::parse)
+        .map_err(|e| JqError::Filter(format!("cannot parse input: {e}")))?;
+
+    let inputs = RcIter::new(core::iter::empty());
+    let mut output = String::new();
+    for value in filter.run((Ctx::new([], &inputs), input)) {
+        let value = value.map_err(|e| JqError::Filter(e.to_string()))?;
+        write_pretty(&mut output, &value, 0);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+fn load_error_message(errs: load::Errors<&str, ()>) -> String {
+    let mut messages = Vec::new();
+    for (_, err) in errs {
+        match err {
+            load::Error::Io(errs) => {
+                messages.extend(errs.into_iter().map(|(path, e)| format!("{path}: {e}")));
+            }
+            load::Error::Lex(errs) => messages.extend(
+                errs.into_iter()
+                    .map(|(expect, found)| syntax_error(expect.as_str(), found)),
+            ),
+            load::Error::Parse(errs) => messages.extend(
+                errs.into_iter()
+                    .map(|(expect, found)| syntax_error(expect.as_str(), found)),
+            ),
+        }
+    }
+    messages.join("; ")
+}
+
+fn syntax_error(expected: &str, found: &str) -> String {
+    match found.split_whitespace().next() {
+        Some(token) => format!("syntax error: expected {expected} before `{token}`"),
+        None => format!("syntax error: expected {expected} at end of filter"),
+    }
+}
+
+fn compile_error_message(errs: compile::Errors<&str, ()>) -> String {
+    errs.into_iter()
+        .flat_map(|(_, errs)| errs)
+        .map(|(name, undefined)| match undefined {
+            compile::Undefined::Filter(arity) => format!("undefined function `{name}/{arity}`"),
+            compile::Undefined::Var => format!("undefined variable `${name}`"),
+            _ => format!("undefined `{name}`"),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Pretty-print a value the way `jq` does. Strings and other scalars reuse
+/// the compact `Display` form, which already escapes like JSON; integral
+/// floats print without a fraction (`10`, not `10.0`), as in `jq`.
+fn write_pretty(out: &mut String, value: &Val, indent: usize) {
+    let pad = |out: &mut String, level: usize| {
+        out.push('\n');
+        out.extend(std::iter::repeat_n(' ', level * 2));
+    };
+    match value {
+        Val::Arr(items) if !items.is_empty() => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                pad(out, indent + 1);
+                write_pretty(out, item, indent + 1);
+            }
+            pad(out, indent);
+            out.push(']');
+        }
+        Val::Obj(fields) if !fields.is_empty() => {
+            out.push('{');
+            for (i, (key, field)) in fields.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                pad(out, indent + 1);
+                let _ = write!(out, "{}: ", Val::Str(key.clone()));
+                write_pretty(out, field, indent + 1);
+            }
+            pad(out, indent);
+            out.push('}');
+        }
+        Val::Float(x) if x.is_finite() && x.fract() == 0.0 => {
+            let _ = write!(out, "{x:.0}");
+        }
+        _ => {
+            let _ = write!(out, "{value}");
+        }
+    }
+}
+
+/// Pipe JSON through the external `jq` binary.
+fn filter_external(json: &str, filter: &str) -> Result<String, JqError> {
     let mut child = Command::new("jq")
         .arg(filter)
         .stdin(Stdio::piped())
@@ -32,3 +167,66 @@ pub(crate) fn filter_json(json: &str, filter: &str) -> Result<String, JqError> {
         Err(JqError::Filter(stderr.trim().to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROWS: &str = r#"[
+        {"date": "2026-01-02", "model": "opus", "cost": 3.5, "total_tokens": 300},
+        {"date": "2026-01-01", "model": "sonnet", "cost": 0.25, "total_tokens": 100},
+        {"date": "2026-01-03", "model": "opus", "cost": 1.25, "total_tokens": 200}
+    ]"#;
+
+    fn run(filter: &str) -> String {
+        filter_json(ROWS, filter, false).unwrap()
+    }
+
+    #[test]
+    fn paths_and_select() {
+        assert_eq!(run(".[0].date"), "\"2026-01-02\"\n");
+        assert_eq!(
+            run(".[] | select(.cost > 1) | .date"),
+            "\"2026-01-02\"\n\"2026-01-03\"\n"
+        );
+        assert_eq!(run(".[5]"), "null\n");
+    }
+
+    #[test]
+    fn map_arithmetic_and_aggregation() {
+        assert_eq!(run("map(.total_tokens) | add"), "600\n");
+        assert_eq!(run("map(.cost * 2) | add"), "10\n");
+        assert_eq!(run("map(.cost * 4)"), "[\n  14,\n  1,\n  5\n]\n");
+        assert_eq!(run(".[0].cost"), "3.5\n");
+        assert_eq!(run("[.[] | .total_tokens / 100] | length"), "3\n");
+        assert_eq!(
+            run("sort_by(.date) | map(.date) | first"),
+            "\"2026-01-01\"\n"
+        );
+        assert_eq!(
+            run("group_by(.model) | map({model: .[0].model, n: length})"),
+            "[\n  {\n    \"model\": \"opus\",\n    \"n\": 2\n  },\n  {\n    \"model\": \"sonnet\",\n    \"n\": 1\n  }\n]\n"
+        );
+    }
+
+    #[test]
+    fn output_keeps_input_key_order() {
+        assert_eq!(
+            filter_json(r#"{"z": 1, "a": [], "m": {}}"#, ".", false).unwrap(),
+            "{\n  \"z\": 1,\n  \"a\": [],\n  \"m\": {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn unsupported_and_runtime_errors() {
+        let err = filter_json(ROWS, "no_such_fn", false).unwrap_err();
+        assert!(matches!(&err, JqError::Unsupported(msg) if msg.contains("no_such_fn/0")));
+        assert!(err.to_string().contains("--jq-fallback"));
+
+        let err = filter_json(ROWS, ".[", false).unwrap_err();
+        assert!(matches!(err, JqError::Unsupported(msg) if msg.contains("syntax error")));
+
+        let err = filter_json(ROWS, ".[0].date + 1", false).unwrap_err();
+        assert!(matches!(err, JqError::Filter(_)));
+    }
+}
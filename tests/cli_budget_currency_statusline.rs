@@ -687,3 +687,76 @@ fn statusline_json_includes_data_quality_metadata() {
 
     let _ = fs::remove_dir_all(root);
 }
+
+#[test]
+fn statusline_template_renders_active_block_and_json_exposes_it() {
+    let root = unique_temp_dir("statusline-template");
+    let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    write_file(
+        &root.join(".claude/projects/myproject/session-a.jsonl"),
+        &format!(
+            r#"{{"timestamp":"{now}","message":{{"id":"msg_1","model":"claude-3-5-sonnet-20241022","stop_reason":"end_turn","usage":{{"input_tokens":1000000,"output_tokens":100000}}}}}}
+"#
+        ),
+    );
+    let template = "{source}|{cost}|{in}/{out}|{block_tokens}|{quota_pct}";
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &[
+            "statusline",
+            "-O",
+            "--timezone",
+            "UTC",
+            "--template",
+            template,
+        ],
+        &[("HOME", &root)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    assert_eq!(
+        String::from_utf8(stdout).unwrap().trim_end(),
+        "Claude Code|$4.50|1.0M/100.0K|1.1M|"
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &[
+            "statusline",
+            "-j",
+            "-O",
+            "--timezone",
+            "UTC",
+            "--template",
+            template,
+        ],
+        &[("HOME", &root)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    assert_eq!(json["line"], "Claude Code|$4.50|1.0M/100.0K|1.1M|");
+    assert_eq!(json["block"]["total_tokens"], 1_100_000);
+    assert!(json["block"]["remaining_minutes"].as_i64().unwrap() <= 300);
+    assert!(json["quota"].is_null());
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn statusline_template_is_validated_and_statusline_only() {
+    let root = unique_temp_dir("statusline-template-errors");
+
+    let (ok, _, stderr) = run_ccstats(
+        &["statusline", "-O", "--template", "{cost} {tokens}"],
+        &[("HOME", &root)],
+    );
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("unknown statusline placeholder `{tokens}`"));
+
+    let (ok, _, stderr) = run_ccstats(&["daily", "-O", "--template", "{cost}"], &[("HOME", &root)]);
+    assert!(!ok);
+    assert!(
+        String::from_utf8_lossy(&stderr)
+            .contains("--template only supports the statusline command")
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
@@ -702,3 +702,241 @@ fn claude_blocks_csv_outputs_correct_format() {
 
     let _ = fs::remove_dir_all(root);
 }
+
+#[test]
+fn claude_session_show_prints_turn_timeline() {
+    let root = unique_temp_dir("claude-session-show");
+    write_file(
+        &root.join(".claude/projects/-work-myapp/abc123-main.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","stop_reason":"tool_use","content":[{"type":"tool_use","name":"Task","id":"t1","input":{"subagent_type":"explore"}}],"usage":{"input_tokens":100,"output_tokens":10,"cache_read_input_tokens":900}}}
+{"isSidechain":true,"timestamp":"2026-02-06T10:01:00Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","stop_reason":"end_turn","usage":{"input_tokens":50,"output_tokens":5}}}
+{"timestamp":"2026-02-06T10:30:00Z","message":{"id":"msg_3","model":"claude-sonnet-4-20250514","stop_reason":"end_turn","usage":{"input_tokens":100,"output_tokens":20,"cache_read_input_tokens":2000}}}
+"#,
+    );
+    write_file(
+        &root.join(".claude/projects/-work-myapp/abd999-other.jsonl"),
+        r#"{"timestamp":"2026-02-06T11:00:00Z","message":{"id":"msg_9","model":"claude-sonnet-4-20250514","stop_reason":"end_turn","usage":{"input_tokens":1,"output_tokens":1}}}
+"#,
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &[
+            "session",
+            "show",
+            "abc",
+            "-j",
+            "--offline",
+            "--timezone",
+            "UTC",
+        ],
+        &[("HOME", &root)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    assert_eq!(json["session_id"].as_str(), Some("abc123-main"));
+    let turns = json["turns"].as_array().expect("turns");
+    assert_eq!(turns.len(), 3);
+    assert_eq!(turns[0]["tools"][0].as_str(), Some("Task"));
+    assert_eq!(turns[1]["thread"].as_str(), Some("subagent"));
+    assert_eq!(turns[2]["context_tokens"].as_i64(), Some(2100));
+    assert_eq!(json["peak_context_tokens"].as_i64(), Some(2100));
+    assert_eq!(json["segments"].as_array().map(Vec::len), Some(3));
+    assert_eq!(json["idle_gaps"][0]["minutes"].as_i64(), Some(29));
+
+    let (ok, _, stderr) = run_ccstats(
+        &["session", "show", "ab", "--offline", "--timezone", "UTC"],
+        &[("HOME", &root)],
+    );
+    assert!(!ok);
+    let stderr = String::from_utf8_lossy(&stderr);
+    assert!(stderr.contains("matches 2 sessions"), "{stderr}");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn claude_session_show_dates_rows_after_midnight() {
+    let root = unique_temp_dir("claude-session-show-days");
+    write_file(
+        &root.join(".claude/projects/-work-myapp/late-session.jsonl"),
+        r#"{"timestamp":"2026-02-06T23:50:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":10}}}
+{"timestamp":"2026-02-06T23:55:00Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":10}}}
+{"timestamp":"2026-02-07T00:10:00Z","message":{"id":"msg_3","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":10}}}
+"#,
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &["session", "show", "late", "--offline", "--timezone", "UTC"],
+        &[("HOME", &root)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let stdout = String::from_utf8_lossy(&stdout);
+    assert!(
+        stdout.contains("2026-02-06 23:50 → 2026-02-07 00:10"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("23:55:00"), "{stdout}");
+    assert!(!stdout.contains("2026-02-06 23:55:00"), "{stdout}");
+    assert!(stdout.contains("2026-02-07 00:10:00"), "{stdout}");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn claude_session_context_reports_peak_and_compactions() {
+    let root = unique_temp_dir("claude-session-context");
+    write_file(
+        &root.join(".claude/projects/-work-myapp/ctx-session.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":10,"cache_read_input_tokens":99900}}}
+{"timestamp":"2026-02-06T10:01:00Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":10,"cache_read_input_tokens":159900}}}
+{"isSidechain":true,"timestamp":"2026-02-06T10:01:30Z","message":{"id":"msg_3","model":"claude-sonnet-4-20250514","usage":{"input_tokens":500,"output_tokens":10}}}
+{"timestamp":"2026-02-06T10:02:00Z","message":{"id":"msg_4","model":"claude-sonnet-4-20250514","usage":{"input_tokens":20000,"output_tokens":10}}}
+"#,
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &[
+            "session",
+            "--context",
+            "--context-threshold",
+            "120000",
+            "-j",
+            "--offline",
+            "--timezone",
+            "UTC",
+        ],
+        &[("HOME", &root)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    let context = &json[0]["context"];
+    assert_eq!(json[0]["session_id"].as_str(), Some("ctx-session"));
+    assert_eq!(context["turns"].as_i64(), Some(3));
+    assert_eq!(context["peak_tokens"].as_i64(), Some(160_000));
+    assert_eq!(context["average_tokens"].as_i64(), Some(93_333));
+    assert_eq!(context["compactions"].as_i64(), Some(1));
+    assert_eq!(context["turns_above_threshold"].as_i64(), Some(1));
+    assert_eq!(context["threshold"].as_i64(), Some(120_000));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn claude_session_context_table_follows_locale() {
+    let root = unique_temp_dir("claude-session-context-locale");
+    write_file(
+        &root.join(".claude/projects/-work-myapp/ctx-session.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":10,"cache_read_input_tokens":99900}}}
+{"timestamp":"2026-02-06T10:01:00Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":10,"cache_read_input_tokens":159900}}}
+"#,
+    );
+
+    let markdown = |locale: &str| {
+        let (ok, stdout, stderr) = run_ccstats(
+            &[
+                "session",
+                "--context",
+                "--context-threshold",
+                "120000",
+                "--format",
+                "markdown",
+                "--locale",
+                locale,
+                "--offline",
+                "--timezone",
+                "UTC",
+            ],
+            &[("HOME", &root)],
+        );
+        assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+        String::from_utf8(stdout).expect("utf-8")
+    };
+
+    assert_eq!(
+        markdown("ja"),
+        "## Claude Code セッションコンテキスト\n\n\
+         | セッション | プロジェクト | ターン | 最大 | 平均 | 圧縮 | ≥120.0K ターン | ターン比率 |\n\
+         | --- | --- | ---: | ---: | ---: | ---: | ---: | ---: |\n\
+         | ctx-session | work-myapp | 2 | 160.0K | 130.0K | 0 | 1 | 50.0% |\n\n"
+    );
+    assert_eq!(
+        markdown("ko"),
+        "## Claude Code 세션 컨텍스트\n\n\
+         | 세션 | 프로젝트 | 턴 | 최대 | 평균 | 압축 | ≥120.0K 턴 | 턴 비율 |\n\
+         | --- | --- | ---: | ---: | ---: | ---: | ---: | ---: |\n\
+         | ctx-session | work-myapp | 2 | 160.0K | 130.0K | 0 | 1 | 50.0% |\n\n"
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn claude_long_context_requests_use_tier_pricing() {
+    let root = unique_temp_dir("claude-long-context");
+    write_file(
+        &root.join(".claude/projects/-work-myapp/long.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100000,"output_tokens":10000}}}
+{"timestamp":"2026-02-06T10:01:00Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","usage":{"input_tokens":50000,"output_tokens":10000,"cache_read_input_tokens":200000}}}
+"#,
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &["daily", "-j", "--offline", "--timezone", "UTC"],
+        &[("HOME", &root)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    // Short request: 100K * $3/M + 10K * $15/M = $0.45
+    // Long request: 50K * $6/M + 200K * $0.6/M + 10K * $22.5/M = $0.645
+    let cost = json[0]["cost"].as_f64().expect("cost");
+    assert!((cost - 1.095).abs() < 1e-9, "{cost}");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn claude_agents_splits_session_cost_between_main_and_subagents() {
+    let root = unique_temp_dir("claude-agents");
+    let project = root.join(".claude/projects/-work-myapp");
+    write_file(
+        &project.join("sess-1.jsonl"),
+        r#"{"sessionId":"sess-1","timestamp":"2026-02-06T10:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":0,"output_tokens":200000}}}
+{"sessionId":"sess-1","timestamp":"2026-02-06T10:01:00Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","content":[{"type":"tool_use","id":"toolu_2","name":"Task","input":{"subagent_type":"Plan","prompt":"plan it"}},{"type":"tool_use","id":"toolu_1","name":"Task","input":{"subagent_type":"Explore","prompt":"look around"}}],"usage":{"input_tokens":0,"output_tokens":0}}}
+{"isSidechain":true,"agentId":"b2","sessionId":"sess-1","timestamp":"2026-02-06T10:02:00Z","message":{"id":"msg_3","model":"claude-sonnet-4-20250514","usage":{"input_tokens":0,"output_tokens":200000}}}
+{"sessionId":"sess-1","timestamp":"2026-02-06T10:04:00Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":[{"type":"text","text":"found it"}]}]},"toolUseResult":{"agentId":"a1","status":"completed"}}
+{"sessionId":"sess-1","timestamp":"2026-02-06T10:05:00Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_2","content":"planned"}]},"toolUseResult":{"agentId":"b2","status":"completed"}}
+"#,
+    );
+    write_file(
+        &project.join("sess-1/subagents/agent-a1.jsonl"),
+        r#"{"isSidechain":true,"agentId":"a1","sessionId":"sess-1","timestamp":"2026-02-06T10:03:00Z","message":{"id":"msg_4","model":"claude-sonnet-4-20250514","usage":{"input_tokens":0,"output_tokens":400000}}}
+"#,
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &["agents", "-j", "--offline", "--timezone", "UTC"],
+        &[("HOME", &root)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    let sessions = json.as_array().expect("array");
+    assert_eq!(sessions.len(), 1, "{json}");
+    let session = &sessions[0];
+    assert_eq!(session["session_id"].as_str(), Some("sess-1"));
+    // 200K output at $15/M on the main thread; 600K across subagents.
+    assert_eq!(session["main"]["calls"].as_i64(), Some(2));
+    assert!((session["main"]["cost"].as_f64().unwrap() - 3.0).abs() < 1e-9);
+    assert_eq!(session["subagents"]["calls"].as_i64(), Some(2));
+    assert!((session["subagents"]["cost"].as_f64().unwrap() - 9.0).abs() < 1e-9);
+    assert_eq!(session["subagent_share_pct"].as_f64(), Some(75.0));
+    // Both Tasks launched in one message; each agent is typed by its own
+    // launch, including the one in its own agent file.
+    let types = session["subagent_types"].as_array().expect("types");
+    assert_eq!(types[0]["subagent_type"].as_str(), Some("Explore"));
+    assert_eq!(types[0]["calls"].as_i64(), Some(1));
+    assert!((types[0]["cost"].as_f64().unwrap() - 6.0).abs() < 1e-9);
+    assert_eq!(types[1]["subagent_type"].as_str(), Some("Plan"));
+    assert!((types[1]["cost"].as_f64().unwrap() - 3.0).abs() < 1e-9);
+
+    let _ = fs::remove_dir_all(root);
+}
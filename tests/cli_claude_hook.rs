@@ -0,0 +1,146 @@
+mod common;
+
+use std::path::Path;
+
+use chrono::{Duration, SecondsFormat, Utc};
+use common::{run_ccstats, run_ccstats_with_stdin, unique_temp_dir, write_file};
+use serde_json::{Value, json};
+
+fn usage_line(id: &str, minutes_ago: i64, input_tokens: i64) -> String {
+    let timestamp =
+        (Utc::now() - Duration::minutes(minutes_ago)).to_rfc3339_opts(SecondsFormat::Secs, true);
+    json!({
+        "timestamp": timestamp,
+        "type": "assistant",
+        "message": {
+            "id": id,
+            "model": "claude-sonnet-4-20250514",
+            "stop_reason": "end_turn",
+            "usage": {
+                "input_tokens": input_tokens,
+                "output_tokens": 10,
+                "cache_read_input_tokens": 40_000,
+            },
+        },
+    })
+    .to_string()
+}
+
+fn run_hook(home: &Path, args: &[&str], stdin: &str) -> (bool, String, String) {
+    let (ok, stdout, stderr) = run_ccstats_with_stdin(args, &[("HOME", home)], stdin);
+    (
+        ok,
+        String::from_utf8_lossy(&stdout).into_owned(),
+        String::from_utf8_lossy(&stderr).into_owned(),
+    )
+}
+
+#[test]
+fn claude_hook_scopes_statusline_to_the_session_and_project() {
+    let root = unique_temp_dir("claude-hook");
+    let projects = root.join(".claude/projects");
+    let transcript = projects.join("-work-myapp/current.jsonl");
+    // The current session started three days ago and is still running.
+    write_file(
+        &transcript,
+        &format!(
+            "{}\n{}\n",
+            usage_line("msg_old", 3 * 24 * 60, 1_000),
+            usage_line("msg_now", 1, 2_000)
+        ),
+    );
+    write_file(
+        &projects.join("-work-myapp/other.jsonl"),
+        &format!("{}\n", usage_line("msg_sibling", 2, 500)),
+    );
+    write_file(
+        &projects.join("-work-elsewhere/else.jsonl"),
+        &format!("{}\n", usage_line("msg_else", 2, 9_000)),
+    );
+    let payload = json!({
+        "hook_event_name": "Status",
+        "session_id": "current",
+        "transcript_path": transcript,
+        "cwd": "/work/myapp",
+        "model": {"id": "claude-sonnet-4-20250514", "display_name": "Sonnet 4"},
+        "workspace": {"current_dir": "/work/myapp", "project_dir": "/work/myapp"},
+    })
+    .to_string();
+
+    let (ok, stdout, stderr) = run_hook(
+        &root,
+        &["statusline", "--claude-hook", "--offline", "--json"],
+        &payload,
+    );
+    assert!(ok, "stderr: {stderr}");
+    let value: Value = serde_json::from_str(&stdout).expect("json");
+    let (ok, schema, _) = run_hook(&root, &["schema", "claude-hook"], "");
+    assert!(ok);
+    let schema: Value = serde_json::from_str(&schema).expect("schema json");
+    assert!(jsonschema::is_valid(&schema, &value), "{value}");
+    assert_eq!(value["schema_version"], 1);
+    assert_eq!(value["session_id"], "current");
+    assert_eq!(value["project"], "myapp");
+    // Session covers the whole transcript, including its three-day-old turn.
+    assert_eq!(value["session"]["total_tokens"], 83_020);
+    // Today covers both sessions of this project, not other projects.
+    assert_eq!(value["today"]["total_tokens"], 82_520);
+    assert_eq!(value["context"]["used_tokens"], 42_000);
+    assert_eq!(value["context"]["limit_tokens"], 200_000);
+
+    let (ok, stdout, _) = run_hook(
+        &root,
+        &["statusline", "--claude-hook", "--offline"],
+        &payload,
+    );
+    assert!(ok);
+    let line = stdout.trim_end();
+    assert!(line.starts_with("Sonnet 4 | myapp | Session: $"), "{line}");
+    assert!(line.contains(" | Today: $"), "{line}");
+    assert!(line.ends_with("Ctx: 21% (42.0K/200.0K)"), "{line}");
+
+    std::fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn claude_hook_reports_a_bad_payload_on_the_status_line() {
+    let root = unique_temp_dir("claude-hook-bad");
+
+    let (ok, stdout, _) = run_ccstats(
+        &["statusline", "--claude-hook", "--offline"],
+        &[("HOME", &root)],
+    );
+    assert!(ok);
+    assert_eq!(
+        String::from_utf8_lossy(&stdout).trim_end(),
+        "ccstats: no Claude Code session JSON on stdin"
+    );
+
+    std::fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn claude_hook_is_limited_to_the_claude_statusline() {
+    let root = unique_temp_dir("claude-hook-invalid");
+
+    for (args, message) in [
+        (
+            &["daily", "--claude-hook"][..],
+            "--claude-hook only supports the statusline command",
+        ),
+        (
+            &["statusline", "--claude-hook", "--source", "codex"][..],
+            "--claude-hook only supports the Claude source",
+        ),
+        (
+            &["statusline", "--claude-hook", "--template", "{cost}"][..],
+            "--template cannot be combined with --claude-hook",
+        ),
+    ] {
+        let (ok, _, stderr) = run_hook(&root, args, "{}");
+        assert!(!ok, "{args:?} should fail");
+        assert!(stderr.contains(message), "stderr: {stderr}");
+    }
+
+    std::fs::remove_dir_all(root).ok();
+}
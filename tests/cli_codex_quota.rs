@@ -126,6 +126,12 @@ fn quota_and_codex_quota_return_the_same_json() {
         (resets_at - Duration::minutes(10_080)).to_rfc3339_opts(SecondsFormat::Secs, true)
     );
     assert!(value["value_estimate_error"].is_null());
+    let windows = value["windows"].as_array().unwrap();
+    assert_eq!(windows.len(), 2);
+    assert_eq!(windows[0]["window"], "5h");
+    assert_eq!(windows[0]["used_pct"], 10.0);
+    assert_eq!(windows[1]["window"], "weekly");
+    assert_eq!(windows[1]["status"], "likely_exhausted");
 
     let _ = fs::remove_dir_all(root);
 }
@@ -274,3 +280,54 @@ fn quota_rejects_non_codex_source_override() {
 
     let _ = fs::remove_dir_all(root);
 }
+
+#[test]
+fn quota_history_reports_expired_and_active_windows() {
+    let root = unique_temp_dir("codex-quota-history");
+    let codex_home = root.join("codex-home");
+    let now = Utc::now().with_nanosecond(0).unwrap();
+    let past_reset = now - Duration::days(2);
+    write_file(
+        &codex_home.join("sessions/past.jsonl"),
+        &[
+            quota_event(past_reset - Duration::days(5), 40.0, past_reset, false),
+            quota_event(past_reset - Duration::days(3), 100.0, past_reset, false),
+        ]
+        .concat(),
+    );
+    let resets_at = write_current_quota_fixture(&codex_home);
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &["quota", "--history", "--json"],
+        &[("CODEX_HOME", &codex_home)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let value: Value = serde_json::from_slice(&stdout).unwrap();
+    let windows = value["windows"].as_array().unwrap();
+    let weekly: Vec<_> = windows
+        .iter()
+        .filter(|window| window["window"] == "weekly")
+        .collect();
+    assert_eq!(weekly.len(), 2);
+    assert_eq!(weekly[0]["active"], false);
+    assert_eq!(weekly[0]["peak_pct"], 100.0);
+    assert_eq!(weekly[0]["exhausted"], true);
+    assert_eq!(weekly[1]["active"], true);
+    assert_eq!(weekly[1]["last_pct"], 25.0);
+    assert_eq!(
+        weekly[1]["resets_at"],
+        resets_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+    assert_eq!(weekly[1]["series"].as_array().unwrap().len(), 2);
+    assert!(windows.iter().any(|window| window["window"] == "5h"));
+
+    let (csv_ok, csv_stdout, _) = run_ccstats(
+        &["codex", "quota", "--history", "--csv"],
+        &[("CODEX_HOME", &codex_home)],
+    );
+    assert!(csv_ok);
+    let csv = String::from_utf8(csv_stdout).unwrap();
+    assert!(csv.starts_with("source,window,window_minutes,window_started_at,resets_at,observed_at,used_pct,peak_pct,exhausted_at\n"));
+
+    let _ = fs::remove_dir_all(root);
+}
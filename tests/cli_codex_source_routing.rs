@@ -854,3 +854,104 @@ fn codex_session_csv_includes_reasoning_and_cache_tokens() {
 
     let _ = fs::remove_dir_all(root);
 }
+
+#[test]
+fn codex_flex_turns_are_priced_and_broken_down_by_service_tier() {
+    let root = unique_temp_dir("codex-service-tier");
+    let codex_home = root.join("codex-home");
+    write_file(
+        &codex_home.join("sessions").join("flex.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","type":"turn_context","payload":{"model":"gpt-5","service_tier":"flex"}}
+{"timestamp":"2026-02-06T10:00:00Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1000000,"cached_input_tokens":0,"output_tokens":0,"reasoning_output_tokens":0,"total_tokens":1000000},"last_token_usage":{"input_tokens":1000000,"cached_input_tokens":0,"output_tokens":0,"reasoning_output_tokens":0,"total_tokens":1000000},"model":"gpt-5"}}}
+{"timestamp":"2026-02-06T10:05:00Z","type":"turn_context","payload":{"model":"gpt-5"}}
+{"timestamp":"2026-02-06T10:05:00Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":2000000,"cached_input_tokens":0,"output_tokens":0,"reasoning_output_tokens":0,"total_tokens":2000000},"last_token_usage":{"input_tokens":1000000,"cached_input_tokens":0,"output_tokens":0,"reasoning_output_tokens":0,"total_tokens":1000000},"model":"gpt-5"}}}
+"#,
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &["codex", "daily", "-j", "-b", "-O", "--timezone", "UTC"],
+        &[("CODEX_HOME", &codex_home)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    // Flex 1M * $0.625/M + standard 1M * $1.25/M
+    let cost = json[0]["cost"].as_f64().expect("cost");
+    assert!((cost - 1.875).abs() < 1e-9, "{cost}");
+    let tiers = json[0]["breakdown"][0]["service_tiers"]
+        .as_array()
+        .expect("service tiers");
+    assert_eq!(tiers.len(), 1);
+    assert_eq!(tiers[0]["tier"].as_str(), Some("flex"));
+    assert_eq!(tiers[0]["calls"].as_i64(), Some(1));
+    assert_eq!(tiers[0]["input_tokens"].as_i64(), Some(1_000_000));
+    assert!((tiers[0]["cost"].as_f64().unwrap() - 0.625).abs() < 1e-9);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn audit_pricing_reports_unpriced_share_and_fails_above_threshold() {
+    let root = unique_temp_dir("codex-audit-pricing");
+    let codex_home = root.join("codex-home");
+    write_file(
+        &codex_home.join("sessions").join("mixed.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","type":"turn_context","payload":{"model":"gpt-5"}}
+{"timestamp":"2026-02-06T10:00:00Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":300,"cached_input_tokens":0,"output_tokens":0,"reasoning_output_tokens":0,"total_tokens":300},"last_token_usage":{"input_tokens":300,"cached_input_tokens":0,"output_tokens":0,"reasoning_output_tokens":0,"total_tokens":300},"model":"gpt-5"}}}
+{"timestamp":"2026-02-06T11:00:00Z","type":"turn_context","payload":{"model":"mystery-llm"}}
+{"timestamp":"2026-02-06T11:00:00Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":400,"cached_input_tokens":0,"output_tokens":0,"reasoning_output_tokens":0,"total_tokens":400},"last_token_usage":{"input_tokens":100,"cached_input_tokens":0,"output_tokens":0,"reasoning_output_tokens":0,"total_tokens":100},"model":"mystery-llm"}}}
+"#,
+    );
+    let envs = [
+        ("CODEX_HOME", codex_home.as_path()),
+        ("HOME", root.as_path()),
+    ];
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &[
+            "audit",
+            "pricing",
+            "--source",
+            "codex",
+            "-j",
+            "-O",
+            "--fail-above",
+            "30",
+        ],
+        &envs,
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    assert_eq!(json["total_tokens"].as_i64(), Some(400));
+    assert_eq!(json["unpriced_tokens"].as_i64(), Some(100));
+    assert_eq!(json["unpriced_pct"].as_f64(), Some(25.0));
+    let models = json["models"].as_array().expect("models");
+    assert_eq!(models[0]["model"].as_str(), Some("gpt-5"));
+    assert_eq!(models[0]["resolution"].as_str(), Some("fallback"));
+    assert_eq!(models[0]["cost_share_pct"].as_f64(), Some(100.0));
+    assert_eq!(models[1]["resolution"].as_str(), Some("unknown"));
+    assert_eq!(models[1]["unpriced"].as_bool(), Some(true));
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &[
+            "audit",
+            "pricing",
+            "--source",
+            "codex",
+            "-O",
+            "--include-fallback",
+            "--fail-above",
+            "30",
+        ],
+        &envs,
+    );
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stdout).contains("Pricing Audit"));
+    assert!(
+        String::from_utf8_lossy(&stderr).contains("unpriced usage is 100.0% of tokens"),
+        "{}",
+        String::from_utf8_lossy(&stderr)
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
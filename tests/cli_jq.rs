@@ -0,0 +1,82 @@
+mod common;
+
+use common::{run_ccstats, unique_temp_dir, write_file};
+use std::fs;
+use std::path::Path;
+
+fn write_claude_session(root: &Path) {
+    write_file(
+        &root.join(".claude/projects/myapp/session-a.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","sessionId":"session-a","message":{"id":"msg_1","model":"claude-3-5-sonnet-20241022","stop_reason":"end_turn","usage":{"input_tokens":100,"output_tokens":50}}}
+{"timestamp":"2026-02-07T10:00:00Z","sessionId":"session-a","message":{"id":"msg_2","model":"claude-4-opus-20250514","stop_reason":"end_turn","usage":{"input_tokens":200,"output_tokens":80}}}
+"#,
+    );
+}
+
+/// Run with a `PATH` that contains no `jq` binary.
+fn run_without_jq(args: &[&str], root: &Path) -> (bool, String, String) {
+    let empty_path = root.join("empty-path");
+    fs::create_dir_all(&empty_path).unwrap();
+    let mut full_args = args.to_vec();
+    full_args.extend(["-j", "-O", "--timezone", "UTC", "--since", "2026-02-01"]);
+    let (ok, stdout, stderr) = run_ccstats(&full_args, &[("HOME", root), ("PATH", &empty_path)]);
+    (
+        ok,
+        String::from_utf8(stdout).unwrap(),
+        String::from_utf8_lossy(&stderr).into_owned(),
+    )
+}
+
+#[test]
+fn jq_filter_runs_without_jq_binary() {
+    let root = unique_temp_dir("jq-builtin");
+    write_claude_session(&root);
+
+    let (ok, stdout, stderr) = run_without_jq(
+        &[
+            "daily",
+            "--jq",
+            "map(select(.total_tokens > 200)) | map(.date)",
+        ],
+        &root,
+    );
+    assert!(ok, "stderr: {stderr}");
+    assert_eq!(stdout, "[\n  \"2026-02-07\"\n]\n");
+
+    let (ok, stdout, stderr) = run_without_jq(
+        &["daily", "--jq", "sort_by(.date) | .[] | .total_tokens * 2"],
+        &root,
+    );
+    assert!(ok, "stderr: {stderr}");
+    assert_eq!(stdout, "300\n560\n");
+
+    let (ok, stdout, _) = run_without_jq(&["daily", "--jq", ".[0] | keys_unsorted | .[0]"], &root);
+    assert!(ok);
+    assert_eq!(stdout, "\"schema_version\"\n");
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn unsupported_filter_suggests_fallback() {
+    let root = unique_temp_dir("jq-fallback");
+    write_claude_session(&root);
+
+    let (ok, _, stderr) = run_without_jq(&["daily", "--jq", "no_such_function"], &root);
+    assert!(!ok);
+    assert!(stderr.contains("no_such_function/0"), "stderr: {stderr}");
+    assert!(stderr.contains("--jq-fallback"), "stderr: {stderr}");
+
+    let (ok, _, stderr) = run_without_jq(
+        &["daily", "--jq", "no_such_function", "--jq-fallback"],
+        &root,
+    );
+    assert!(!ok);
+    assert!(stderr.contains("jq not found"), "stderr: {stderr}");
+
+    let (ok, _, stderr) = run_ccstats(&["daily", "-j", "--jq-fallback"], &[("HOME", &root)]);
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("--jq <FILTER>"));
+
+    fs::remove_dir_all(root).ok();
+}
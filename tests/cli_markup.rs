@@ -0,0 +1,128 @@
+mod common;
+
+use common::{run_ccstats, unique_temp_dir, write_file};
+use std::fs;
+use std::path::Path;
+
+fn write_claude_session(root: &Path) {
+    write_file(
+        &root.join(".claude/projects/myapp/session-a.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","sessionId":"session-a","cwd":"/work/myapp","message":{"id":"msg_1","model":"claude-3-5-sonnet-20241022","stop_reason":"end_turn","usage":{"input_tokens":1100,"output_tokens":50}}}
+{"timestamp":"2026-02-07T10:00:00Z","sessionId":"session-a","cwd":"/work/myapp","message":{"id":"msg_2","model":"claude-4-opus-20250514","stop_reason":"end_turn","usage":{"input_tokens":200,"output_tokens":80}}}
+"#,
+    );
+}
+
+fn run_format(args: &[&str], home: &Path) -> String {
+    let mut full_args = args.to_vec();
+    full_args.extend([
+        "-O",
+        "--timezone",
+        "UTC",
+        "--since",
+        "2026-02-01",
+        "--until",
+        "2026-02-28",
+    ]);
+    let (ok, stdout, stderr) = run_ccstats(&full_args, &[("HOME", home)]);
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    String::from_utf8(stdout).expect("utf8 output")
+}
+
+#[test]
+fn markdown_keeps_table_columns_and_breakdown_rows() {
+    let root = unique_temp_dir("markup-markdown");
+    write_claude_session(&root);
+
+    let out = run_format(&["daily", "--breakdown", "--format", "markdown"], &root);
+    assert!(out.starts_with("## Token Usage\n\n| Date | Model | Calls |"));
+    assert!(out.contains("| --- | --- | ---: |"));
+    assert!(out.contains("| 2026-02-06 | 3-5-sonnet | 1 | 1,100 |"));
+    assert!(out.contains("| TOTAL |"));
+    assert!(out.contains("2 usage records"));
+    assert!(!out.contains('│'), "no box drawing: {out}");
+    assert!(!out.contains('\x1b'), "no ANSI colors: {out}");
+
+    let out = run_format(&["session", "--format", "markdown"], &root);
+    assert!(out.starts_with("## Claude Code Session Usage\n\n| Session | Project |"));
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn html_is_one_standalone_page_with_sortable_tables() {
+    let root = unique_temp_dir("markup-html");
+    write_claude_session(&root);
+
+    let out = run_format(
+        &["monthly", "--monthly-budget", "50", "--format", "html"],
+        &root,
+    );
+    assert!(out.starts_with("<!DOCTYPE html>"));
+    assert!(out.trim_end().ends_with("</html>"));
+    assert_eq!(out.matches("<html").count(), 1);
+    assert!(out.contains("<style>"));
+    assert!(out.contains("<h2>Monthly Token Usage</h2>"));
+    assert!(out.contains("<h2>Monthly Budget Forecast</h2>"));
+    assert_eq!(out.matches("<table class=\"sortable\">").count(), 2);
+    assert!(out.contains("<td class=\"num\" data-value=\"1300\">1,300</td>"));
+    assert!(out.contains("<tfoot>\n<tr><td>TOTAL</td>"));
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn session_context_and_show_render_as_markup() {
+    let root = unique_temp_dir("markup-session-views");
+    write_claude_session(&root);
+
+    let out = run_format(&["session", "--context", "--format", "markdown"], &root);
+    assert!(
+        out.starts_with("## Claude Code Session Context\n\n| Session | Project | Turns |"),
+        "{out}"
+    );
+    assert!(out.contains("| session-a | myapp | 2 |"), "{out}");
+    assert!(!out.contains('│'), "no box drawing: {out}");
+
+    let out = run_format(&["session", "--context", "--format", "html"], &root);
+    assert!(out.starts_with("<!DOCTYPE html>"));
+    assert!(out.contains("<h2>Claude Code Session Context</h2>"));
+    assert_eq!(out.matches("<table class=\"sortable\">").count(), 1);
+    assert!(!out.contains('│'), "no box drawing: {out}");
+
+    let out = run_format(
+        &["session", "show", "session-a", "--format", "markdown"],
+        &root,
+    );
+    assert!(out.starts_with("Session session-a (myapp)"), "{out}");
+    assert!(out.contains("| Time | Thread | Model |"), "{out}");
+    assert!(out.contains("Peak context: "), "{out}");
+    assert!(!out.contains('│'), "no box drawing: {out}");
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn markup_formats_reject_unsupported_commands_and_flags() {
+    let root = unique_temp_dir("markup-errors");
+    write_claude_session(&root);
+
+    let (ok, stdout, stderr) = run_ccstats(&["files", "--format", "html"], &[("HOME", &root)]);
+    assert!(!ok);
+    assert!(stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&stderr);
+    assert!(stderr.contains("--format html supports daily"), "{stderr}");
+    assert!(
+        stderr.contains("session show, session --context,"),
+        "{stderr}"
+    );
+
+    let (ok, _, stderr) = run_ccstats(
+        &["daily", "--format", "markdown", "--json"],
+        &[("HOME", &root)],
+    );
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("cannot be used with"));
+
+    fs::remove_dir_all(root).ok();
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::{run_ccstats, unique_temp_dir, write_file};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+fn write_claude_session(root: &Path) {
+    write_file(
+        &root.join(".claude/projects/myapp/session-a.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","sessionId":"session-a","message":{"id":"msg_1","model":"claude-3-5-sonnet-20241022","stop_reason":"end_turn","usage":{"input_tokens":100,"output_tokens":50}}}
+{"timestamp":"2026-02-07T10:00:00Z","sessionId":"session-a","message":{"id":"msg_2","model":"claude-3-5-sonnet-20241022","stop_reason":"end_turn","usage":{"input_tokens":200,"output_tokens":80}}}
+{"timestamp":"2026-02-07T10:00:01Z","sessionId":"session-a","message":{"id":"msg_2","model":"claude-3-5-sonnet-20241022","stop_reason":"end_turn","usage":{"input_tokens":200,"output_tokens":80}}}
+"#,
+    );
+}
+
+fn run_ndjson(args: &[&str], home: &Path) -> Vec<Value> {
+    let mut full_args = args.to_vec();
+    full_args.extend([
+        "--ndjson",
+        "-O",
+        "--timezone",
+        "UTC",
+        "--since",
+        "2026-02-01",
+        "--until",
+        "2026-02-28",
+    ]);
+    let (ok, stdout, stderr) = run_ccstats(&full_args, &[("HOME", home)]);
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let stdout = String::from_utf8(stdout).expect("utf8 output");
+    assert!(stdout.ends_with('\n'));
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("one JSON object per line"))
+        .collect()
+}
+
+#[test]
+fn daily_ndjson_prints_one_row_per_line_then_data_quality() {
+    let root = unique_temp_dir("ndjson-daily");
+    write_claude_session(&root);
+
+    let lines = run_ndjson(&["daily"], &root);
+
+    assert_eq!(lines.len(), 3, "two days and the data quality line");
+    assert_eq!(lines[0]["date"], "2026-02-06");
+    assert_eq!(lines[1]["date"], "2026-02-07");
+    assert!(
+        lines[..2]
+            .iter()
+            .all(|line| line.get("data_quality").is_none())
+    );
+    let trailer = &lines[2];
+    assert_eq!(trailer["schema_version"], 1);
+    assert_eq!(trailer["data_quality"]["valid_entries"], 2);
+    assert_eq!(trailer["data_quality"]["dedup_skipped_entries"], 1);
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn session_project_and_blocks_end_with_data_quality() {
+    let root = unique_temp_dir("ndjson-session");
+    write_claude_session(&root);
+
+    for (command, key) in [
+        ("session", "session_id"),
+        ("project", "project"),
+        ("blocks", "block_start"),
+    ] {
+        let lines = run_ndjson(&[command], &root);
+        let (trailer, rows) = lines.split_last().expect("rows and trailer");
+        assert!(!rows.is_empty(), "{command} printed no rows");
+        assert!(rows.iter().all(|row| row.get(key).is_some()), "{command}");
+        assert_eq!(trailer["schema_version"], 1);
+        assert_eq!(trailer["data_quality"]["valid_entries"], 2, "{command}");
+        assert_eq!(trailer["data_quality"]["dedup_skipped_entries"], 1);
+    }
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn session_show_streams_one_line_per_entry() {
+    let root = unique_temp_dir("ndjson-entries");
+    write_claude_session(&root);
+
+    let lines = run_ndjson(&["session", "show", "session-a"], &root);
+
+    assert_eq!(lines.len(), 3, "two entries and the data quality line");
+    assert!(
+        lines[..2]
+            .iter()
+            .all(|line| line["session_id"] == "session-a")
+    );
+    assert_eq!(lines[0]["input_tokens"], 100);
+    assert_eq!(lines[1]["input_tokens"], 200);
+    assert_eq!(lines[2]["data_quality"]["dedup_skipped_entries"], 1);
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn other_reports_print_rows_or_one_object() {
+    let root = unique_temp_dir("ndjson-reports");
+    write_claude_session(&root);
+
+    let sources = run_ndjson(&["sources"], &root);
+    assert!(sources.len() > 1);
+    assert!(sources.iter().any(|line| line["name"] == "claude"));
+
+    let top = run_ndjson(&["top"], &root);
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0]["dimension"], "model");
+
+    let (ok, _, stderr) = run_ccstats(&["daily", "--ndjson", "--jq", "."], &[("HOME", &root)]);
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("cannot be used with"));
+
+    fs::remove_dir_all(root).ok();
+}
@@ -0,0 +1,213 @@
+mod common;
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+
+use chrono::{Duration, SecondsFormat, Timelike, Utc};
+use common::{run_ccstats, unique_temp_dir, write_file};
+use serde_json::{Value, json};
+
+/// One Codex event: the weekly window is 25% used a day in (likely exhausted)
+/// and the 5-hour window is 10% used an hour in (on track).
+fn write_quota_fixture(codex_home: &Path) {
+    let observed_at = Utc::now().with_nanosecond(0).unwrap();
+    let event = json!({
+        "timestamp": observed_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        "type": "event_msg",
+        "payload": {
+            "type": "token_count",
+            "rate_limits": {
+                "primary": {
+                    "used_percent": 10.0,
+                    "window_minutes": 300,
+                    "resets_at": (observed_at + Duration::hours(4)).timestamp(),
+                },
+                "secondary": {
+                    "used_percent": 25.0,
+                    "window_minutes": 10_080,
+                    "resets_at": (observed_at + Duration::days(6)).timestamp(),
+                },
+            },
+        },
+    });
+    write_file(
+        &codex_home.join("sessions/rollout.jsonl"),
+        &format!("{event}\n"),
+    );
+}
+
+fn notify(root: &Path, extra: &[&str]) -> (bool, String, String) {
+    let codex_home = root.join("codex-home");
+    let state_file = root.join("state.json");
+    let mut args = vec![
+        "notify",
+        "--offline",
+        "--state-file",
+        state_file.to_str().unwrap(),
+    ];
+    args.extend_from_slice(extra);
+    let (ok, stdout, stderr) = run_ccstats(&args, &[("CODEX_HOME", &codex_home), ("HOME", root)]);
+    (
+        ok,
+        String::from_utf8_lossy(&stdout).into_owned(),
+        String::from_utf8_lossy(&stderr).into_owned(),
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn notify_runs_the_command_hook_once_per_transition() {
+    let root = unique_temp_dir("notify-command");
+    write_quota_fixture(&root.join("codex-home"));
+    let out = root.join("payload.json");
+    let hook = format!("cat > '{}'", out.display());
+
+    let (ok, stdout, stderr) = notify(&root, &["--command", &hook]);
+    assert!(ok, "stderr: {stderr}");
+    assert!(stdout.contains("codex_quota:weekly: new → likely_exhausted"));
+
+    let payload: Value = serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+    assert_eq!(payload["event"], "status_changed");
+    let changes = payload["changes"].as_array().unwrap();
+    assert_eq!(changes.len(), 1, "the on-track 5h window is not reported");
+    assert_eq!(changes[0]["kind"], "codex_quota");
+    assert_eq!(changes[0]["status"], "likely_exhausted");
+    assert!(changes[0]["previous_status"].is_null());
+    assert_eq!(changes[0]["details"]["used_pct"], 25.0);
+
+    let state: Value =
+        serde_json::from_str(&fs::read_to_string(root.join("state.json")).unwrap()).unwrap();
+    assert_eq!(state["statuses"]["codex_quota:5h"]["status"], "on_track");
+
+    fs::remove_file(&out).unwrap();
+    let (ok, stdout, stderr) = notify(&root, &["--command", &hook]);
+    assert!(ok, "stderr: {stderr}");
+    assert!(stdout.contains("No status changes (2 checked)."));
+    assert!(
+        !out.exists(),
+        "an unchanged status must not re-run the hook"
+    );
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[cfg(unix)]
+#[test]
+fn notify_keeps_state_when_the_hook_fails() {
+    let root = unique_temp_dir("notify-hook-failure");
+    write_quota_fixture(&root.join("codex-home"));
+
+    let (ok, _, stderr) = notify(&root, &["--command", "exit 7"]);
+    assert!(!ok);
+    assert!(stderr.contains("notify command exited"), "stderr: {stderr}");
+    assert!(!root.join("state.json").exists());
+
+    let (ok, stdout, stderr) = notify(&root, &["--command", "true", "--json"]);
+    assert!(ok, "stderr: {stderr}");
+    let payload: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(payload["changes"][0]["status"], "likely_exhausted");
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn notify_posts_the_payload_to_a_webhook() {
+    let root = unique_temp_dir("notify-webhook");
+    write_quota_fixture(&root.join("codex-home"));
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        stream
+            .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+            .unwrap();
+        (request_line, body)
+    });
+
+    let (ok, _, stderr) = notify(&root, &["--webhook", &url]);
+    assert!(ok, "stderr: {stderr}");
+
+    let (request_line, body) = server.join().unwrap();
+    assert!(request_line.starts_with("POST /hook "));
+    let payload: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(payload["changes"][0]["key"], "codex_quota:weekly");
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn notify_without_anything_to_watch_says_so() {
+    let root = unique_temp_dir("notify-empty");
+    fs::create_dir_all(root.join("codex-home/sessions")).unwrap();
+
+    let (ok, stdout, stderr) = notify(&root, &[]);
+    assert!(ok, "stderr: {stderr}");
+    assert!(stdout.contains("Nothing to watch"));
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn notify_reports_monthly_budget_status_for_the_current_month() {
+    let root = unique_temp_dir("notify-budget");
+    fs::create_dir_all(root.join("codex-home/sessions")).unwrap();
+    let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    write_file(
+        &root.join(".claude/projects/myapp/session.jsonl"),
+        &format!(
+            "{}\n",
+            json!({
+                "timestamp": timestamp,
+                "message": {
+                    "id": "msg_1",
+                    "model": "claude-3-5-sonnet-20241022",
+                    "stop_reason": "end_turn",
+                    "usage": {"input_tokens": 1_000_000, "output_tokens": 100_000},
+                },
+            })
+        ),
+    );
+
+    let (ok, stdout, stderr) = notify(
+        &root,
+        &["--monthly-budget", "1", "--timezone", "UTC", "--json"],
+    );
+    assert!(ok, "stderr: {stderr}");
+    let payload: Value = serde_json::from_str(&stdout).unwrap();
+    let change = &payload["changes"][0];
+    assert_eq!(change["key"], "monthly_budget:claude");
+    assert_eq!(change["status"], "over_budget");
+    assert_eq!(change["period"], Utc::now().format("%Y-%m").to_string());
+    assert_eq!(change["details"]["currency"], "USD");
+
+    let (ok, stdout, _) = notify(
+        &root,
+        &["--monthly-budget", "1000000", "--timezone", "UTC", "--json"],
+    );
+    assert!(ok);
+    let payload: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(payload["changes"][0]["previous_status"], "over_budget");
+    assert_eq!(payload["changes"][0]["status"], "on_track");
+
+    fs::remove_dir_all(root).ok();
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::{run_ccstats, unique_temp_dir, write_file};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+fn write_sessions(root: &Path) {
+    write_file(
+        &root.join(".claude/projects/myapp/session-a.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","sessionId":"session-a","cwd":"/work/myapp","message":{"id":"msg_1","model":"claude-3-5-sonnet-20241022","stop_reason":"tool_use","content":[{"type":"tool_use","id":"toolu_1","name":"Bash","input":{"command":"ls"}}],"usage":{"input_tokens":1100,"output_tokens":50,"cache_read_input_tokens":500}}}
+{"timestamp":"2026-02-07T10:00:00Z","sessionId":"session-a","cwd":"/work/myapp","message":{"id":"msg_2","model":"claude-4-opus-20250514","stop_reason":"end_turn","usage":{"input_tokens":200,"output_tokens":80}}}
+"#,
+    );
+    write_file(
+        &root.join("codex-home/sessions/codex-session.jsonl"),
+        r#"{"timestamp":"2026-02-06T12:00:00Z","type":"turn_context","payload":{"model":"gpt-5"}}
+{"timestamp":"2026-02-06T12:00:00Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":100,"cached_input_tokens":20,"output_tokens":30,"reasoning_output_tokens":10,"total_tokens":140},"last_token_usage":{"input_tokens":100,"cached_input_tokens":20,"output_tokens":30,"reasoning_output_tokens":10,"total_tokens":140},"model":"gpt-5"}}}
+"#,
+    );
+}
+
+/// Run `report` and return the page with its embedded data.
+fn run_report(args: &[&str], root: &Path) -> (String, Value) {
+    let out = root.join("out.html");
+    let mut full_args = vec!["report", "--html", out.to_str().unwrap()];
+    full_args.extend(args);
+    full_args.extend([
+        "-O",
+        "--timezone",
+        "UTC",
+        "--since",
+        "2026-02-01",
+        "--until",
+        "2026-02-10",
+    ]);
+    let (ok, stdout, stderr) = run_ccstats(
+        &full_args,
+        &[("HOME", root), ("CODEX_HOME", &root.join("codex-home"))],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    assert!(String::from_utf8_lossy(&stdout).contains("Wrote dashboard to"));
+
+    let page = fs::read_to_string(out).expect("dashboard written");
+    let start = page
+        .find(r#"<script type="application/json" id="ccstats-data">"#)
+        .expect("embedded data");
+    let json = &page[start..];
+    let json = &json[json.find('>').unwrap() + 1..json.find("</script>").unwrap()];
+    let data = serde_json::from_str(json).expect("valid JSON");
+    (page, data)
+}
+
+#[test]
+fn report_writes_offline_dashboard_for_all_sources() {
+    let root = unique_temp_dir("report-all");
+    write_sessions(&root);
+
+    let (page, data) = run_report(&["--monthly-budget", "5"], &root);
+
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    assert!(page.contains("<h2>Cost month to date vs budget</h2>"));
+    assert!(!page.contains("<script src"), "no external scripts");
+    assert!(!page.contains("<link"), "no external stylesheets");
+
+    assert_eq!(data["title"], "All Sources");
+    assert_eq!(data["metric"], "cost");
+    let sources: Vec<&str> = data["sources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["label"].as_str().unwrap())
+        .collect();
+    assert_eq!(sources.len(), 2);
+    assert!(sources.contains(&"Claude Code") && sources.contains(&"OpenAI Codex"));
+
+    let days = data["days"].as_array().unwrap();
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[0]["date"], "2026-02-06");
+    assert_eq!(days[0]["models"].as_array().unwrap().len(), 2);
+    assert_eq!(days[1]["tokens"], 280);
+
+    assert_eq!(data["month"]["month"], "2026-02");
+    assert_eq!(data["month"]["budget"], 5.0);
+    assert_eq!(data["month"]["cumulative"].as_array().unwrap().len(), 10);
+
+    assert_eq!(data["projects"][0]["label"], "myapp");
+    assert_eq!(data["blocks"][0]["start"], "2026-02-06 10:00");
+    assert_eq!(data["blocks"][0]["end"], "2026-02-06 15:00");
+    assert_eq!(data["tools"][0]["label"], "Bash");
+    assert_eq!(data["tools"][0]["calls"], 1);
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn report_respects_source_and_cost_flags() {
+    let root = unique_temp_dir("report-source");
+    write_sessions(&root);
+
+    let (page, data) = run_report(&["--source", "claude", "--no-cost"], &root);
+    assert!(page.contains("<h2>Daily tokens by model</h2>"));
+    assert_eq!(data["title"], "Claude Code");
+    assert_eq!(data["metric"], "tokens");
+    assert_eq!(data["sources"].as_array().unwrap().len(), 1);
+    assert_eq!(data["days"][0]["cache_hit_rate"], 31.25);
+
+    let (ok, _, stderr) = run_ccstats(&["daily", "--monthly-budget", "5"], &[("HOME", &root)]);
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("monthly, notify and report"));
+
+    let (ok, _, stderr) = run_ccstats(&["report"], &[("HOME", &root)]);
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("--html <FILE>"));
+
+    fs::remove_dir_all(root).ok();
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use common::{run_ccstats, unique_temp_dir, write_file};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+fn write_claude_sessions(root: &Path) {
+    write_file(
+        &root.join(".claude/projects/myapp/session-a.jsonl"),
+        r#"{"timestamp":"2026-02-06T10:00:00Z","sessionId":"session-a","cwd":"/work/myapp","message":{"id":"msg_1","model":"claude-3-5-sonnet-20241022","stop_reason":"end_turn","usage":{"input_tokens":100,"output_tokens":50,"cache_creation_input_tokens":10,"cache_read_input_tokens":20}}}
+{"timestamp":"2026-02-07T11:00:00Z","sessionId":"session-a","cwd":"/work/myapp","message":{"id":"msg_2","model":"claude-4-opus-20250514","stop_reason":"end_turn","usage":{"input_tokens":200,"output_tokens":80,"cache_creation_input_tokens":0,"cache_read_input_tokens":0}}}
+"#,
+    );
+}
+
+fn run_json(args: &[&str], home: &Path) -> Value {
+    let (ok, stdout, stderr) = run_ccstats(args, &[("HOME", home)]);
+    assert!(ok, "{args:?} failed: {}", String::from_utf8_lossy(&stderr));
+    serde_json::from_slice(&stdout).expect("json output")
+}
+
+fn assert_valid(schema: &Value, instance: &Value, label: &str) {
+    let validator = jsonschema::validator_for(schema).expect("valid JSON Schema");
+    let errors: Vec<String> = validator
+        .iter_errors(instance)
+        .map(|error| format!("{} at {}", error, error.instance_path()))
+        .collect();
+    assert!(errors.is_empty(), "{label}: {errors:#?}");
+}
+
+#[test]
+fn report_json_validates_against_published_schema() {
+    let root = unique_temp_dir("schema-reports");
+    write_claude_sessions(&root);
+    let range = [
+        "-O",
+        "--timezone",
+        "UTC",
+        "--since",
+        "2026-02-01",
+        "--until",
+        "2026-02-28",
+    ];
+
+    let cases: [(&str, &[&str]); 7] = [
+        ("daily", &["daily", "-j"]),
+        ("daily", &["daily", "-j", "--breakdown"]),
+        ("weekly", &["weekly", "-j", "--no-cost"]),
+        ("monthly", &["monthly", "-j", "--monthly-budget", "50"]),
+        ("session", &["session", "-j"]),
+        ("project", &["project", "-j"]),
+        ("blocks", &["blocks", "-j"]),
+    ];
+    for (report, args) in cases {
+        let schema = run_json(&["schema", report], &root);
+        let mut full_args = args.to_vec();
+        full_args.extend(range);
+        let output = run_json(&full_args, &root);
+
+        let rows = output.as_array().expect("array output");
+        assert!(!rows.is_empty(), "{args:?} printed no rows");
+        assert!(rows.iter().all(|row| row["schema_version"] == 1));
+        assert_valid(&schema, &output, &args.join(" "));
+    }
+
+    let schema = run_json(&["schema", "daily"], &root);
+    let mut output = run_json(&[&["daily", "-j"][..], &range[..]].concat(), &root);
+    output[0]["input_tokens"] = Value::from("100");
+    let validator = jsonschema::validator_for(&schema).expect("valid JSON Schema");
+    assert!(
+        !validator.is_valid(&output),
+        "type change must not validate"
+    );
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn command_json_validates_against_published_schema() {
+    let root = unique_temp_dir("schema-commands");
+    write_claude_sessions(&root);
+    write_file(
+        &root.join(".claude/projects/myapp/session-b.jsonl"),
+        r#"{"timestamp":"2026-02-08T09:00:00Z","sessionId":"session-b","cwd":"/work/myapp","message":{"id":"msg_3","model":"claude-sonnet-4-20250514","stop_reason":"tool_use","content":[{"type":"tool_use","id":"toolu_1","name":"Read","input":{"file_path":"/work/myapp/src/main.rs"}},{"type":"tool_use","id":"toolu_2","name":"Bash","input":{"command":"cargo test"}}],"usage":{"input_tokens":40,"output_tokens":12}}}
+"#,
+    );
+    let range = [
+        "-O",
+        "--timezone",
+        "UTC",
+        "--since",
+        "2026-02-01",
+        "--until",
+        "2026-02-28",
+    ];
+
+    let cases: [(&str, &[&str]); 13] = [
+        ("session-show", &["session", "show", "session-a", "-j"]),
+        ("session-context", &["session", "--context", "-j"]),
+        ("tools", &["tools", "-j"]),
+        ("tools", &["tools", "-j", "--group-by", "project"]),
+        ("tools-commands", &["tools", "commands", "-j"]),
+        ("top", &["top", "-j"]),
+        ("endpoints", &["endpoints", "-j"]),
+        ("agents", &["agents", "-j"]),
+        ("files", &["files", "-j"]),
+        ("audit-pricing", &["audit", "pricing", "-j"]),
+        ("statusline", &["statusline", "-j"]),
+        ("sources", &["sources", "-j"]),
+        (
+            "pricing-show",
+            &["pricing", "show", "claude-sonnet-4-20250514", "-j"],
+        ),
+    ];
+    for (report, args) in cases {
+        let schema = run_json(&["schema", report], &root);
+        let mut full_args = args.to_vec();
+        full_args.extend(range);
+        let output = run_json(&full_args, &root);
+
+        let reports = output
+            .as_array()
+            .cloned()
+            .unwrap_or_else(|| vec![output.clone()]);
+        assert!(!reports.is_empty(), "{args:?} printed nothing");
+        assert!(reports.iter().all(|report| report["schema_version"] == 1));
+        assert_valid(&schema, &output, &args.join(" "));
+    }
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn schema_command_identifies_report_and_version() {
+    let root = unique_temp_dir("schema-id");
+    let schema = run_json(&["schema", "session"], &root);
+
+    assert_eq!(
+        schema["$id"],
+        "https://github.com/majiayu000/ccstats/schema/v1/session.json"
+    );
+    assert_eq!(schema["type"], "array");
+    let session = schema["$defs"]["SessionJson"]["properties"]
+        .as_object()
+        .expect("session row properties");
+    for field in ["currency", "exchange_rate", "exchange_rate_source"] {
+        assert!(session.contains_key(field), "missing {field}");
+    }
+    assert!(
+        schema["$schema"]
+            .as_str()
+            .is_some_and(|uri| uri.contains("2020-12"))
+    );
+
+    let schema = run_json(&["schema", "tools-commands"], &root);
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["title"], "ccstats tools commands --json");
+
+    let (ok, _, stderr) = run_ccstats(&["schema", "toolz"], &[("HOME", &root)]);
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("invalid value 'toolz'"));
+
+    fs::remove_dir_all(root).ok();
+}
@@ -1,6 +1,7 @@
 use std::fs;
+use std::io::Write as _;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const SOURCE_ENV_VARS: &[&str] = &[
@@ -72,6 +73,15 @@ fn resolve_ccstats_binary() -> PathBuf {
 }
 
 pub(crate) fn run_ccstats(args: &[&str], envs: &[(&str, &Path)]) -> (bool, Vec<u8>, Vec<u8>) {
+    run_ccstats_with_stdin(args, envs, "")
+}
+
+/// Run ccstats with `stdin` piped in, as hooks invoked by other tools do.
+pub(crate) fn run_ccstats_with_stdin(
+    args: &[&str],
+    envs: &[(&str, &Path)],
+    stdin: &str,
+) -> (bool, Vec<u8>, Vec<u8>) {
     let mut cmd = Command::new(resolve_ccstats_binary());
     cmd.args(args);
     for key in SOURCE_ENV_VARS {
@@ -80,6 +90,18 @@ pub(crate) fn run_ccstats(args: &[&str], envs: &[(&str, &Path)]) -> (bool, Vec<u
     for (k, v) in envs {
         cmd.env(k, v);
     }
-    let output = cmd.output().expect("run ccstats");
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run ccstats");
+    child
+        .stdin
+        .take()
+        .expect("piped stdin")
+        .write_all(stdin.as_bytes())
+        .expect("write stdin");
+    let output = child.wait_with_output().expect("wait for ccstats");
     (output.status.success(), output.stdout, output.stderr)
 }
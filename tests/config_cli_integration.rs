@@ -173,3 +173,171 @@ fn sdk_cli_config_helpers_fail_on_invalid_config() {
     }
     let _ = fs::remove_dir_all(root);
 }
+
+#[test]
+fn configured_price_catalog_prices_models_and_reports_provenance() {
+    let root = unique_temp_dir("price-catalog");
+    let codex_home = root.join("codex-home");
+    write_codex_session(&codex_home);
+    let catalog = root.join("prices.toml");
+    write_file(
+        &catalog,
+        "[models.gpt-5]\ninput = 1.0\noutput = 10.0\ncache_read = 0.1\n",
+    );
+    write_config(
+        &root,
+        &format!(
+            "[pricing]\nproviders = [{{ kind = \"file\", path = {:?}, schema = \"ccstats\", name = \"internal\" }}]\n",
+            catalog.display().to_string()
+        ),
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &[
+            "codex",
+            "daily",
+            "-j",
+            "-O",
+            "--breakdown",
+            "--timezone",
+            "UTC",
+        ],
+        &[("HOME", &root), ("CODEX_HOME", &codex_home)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("valid json");
+    let day = &json[0];
+    assert_eq!(day["pricing_source"], "catalog");
+    // 80 input at $1/M, 20 cache reads at $0.10/M, 30 output at $10/M.
+    let cost = day["cost"].as_f64().expect("cost");
+    assert!((cost - 0.000_382).abs() < 1e-9, "cost {cost}");
+    assert_eq!(day["breakdown"][0]["pricing_catalog"], "internal");
+
+    fs::remove_file(&catalog).expect("remove catalog");
+    let (ok, _, stderr) = run_ccstats(
+        &["codex", "daily", "-O", "--timezone", "UTC"],
+        &[("HOME", &root), ("CODEX_HOME", &codex_home)],
+    );
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("prices.toml"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn pricing_show_and_list_inspect_the_configured_catalog() {
+    let root = unique_temp_dir("pricing-inspect");
+    let catalog = root.join("prices.toml");
+    write_file(
+        &catalog,
+        "[models.gpt-5]\ninput = 1.0\noutput = 10.0\ncache_read = 0.1\n\n[models.internal-llm]\ninput = 2.0\noutput = 8.0\n",
+    );
+    write_config(
+        &root,
+        &format!(
+            "[pricing]\nproviders = [{{ kind = \"file\", path = {:?}, schema = \"ccstats\", name = \"internal\" }}]\n",
+            catalog.display().to_string()
+        ),
+    );
+    let envs = [("HOME", root.as_path())];
+
+    let (ok, stdout, stderr) = run_ccstats(&["pricing", "show", "gpt-5", "-j", "-O"], &envs);
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let quote: Value = serde_json::from_slice(&stdout).expect("valid json");
+    assert_eq!(quote["matched_key"], "gpt-5");
+    assert_eq!(quote["pricing_source"], "catalog");
+    assert_eq!(quote["pricing_catalog"], "internal");
+    assert_eq!(quote["rates_usd_per_million"]["output"], 10.0);
+
+    let (ok, stdout, _) = run_ccstats(&["pricing", "list", "--match", "LLM", "-O"], &envs);
+    assert!(ok);
+    let stdout = String::from_utf8_lossy(&stdout);
+    assert!(stdout.contains("│ internal-llm"), "{stdout}");
+    assert!(!stdout.contains("gpt-5"), "{stdout}");
+    assert!(stdout.contains("1 models"), "{stdout}");
+
+    let (ok, _, stderr) = run_ccstats(&["pricing", "refresh", "-O"], &envs);
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("drop --offline"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn exchange_rates_config_converts_days_at_their_own_rate() {
+    let root = unique_temp_dir("exchange-rates");
+    let codex_home = root.join("codex-home");
+    write_codex_session(&codex_home);
+    let catalog = root.join("prices.toml");
+    write_file(
+        &catalog,
+        "[models.gpt-5]\ninput = 1.0\noutput = 10.0\ncache_read = 0.1\n",
+    );
+    let rates = root.join("rates.csv");
+    write_file(
+        &rates,
+        "date,currency,rate\n2026-02-01,EUR,0.5\n2026-02-10,EUR,2.0\n",
+    );
+    let providers = format!(
+        "[pricing]\nproviders = [{{ kind = \"file\", path = {:?}, schema = \"ccstats\" }}]\n",
+        catalog.display().to_string()
+    );
+    write_config(
+        &root,
+        &format!(
+            "currency = \"EUR\"\n{providers}\n[exchange_rates]\nrates_file = {:?}\n",
+            rates.display().to_string()
+        ),
+    );
+    let envs = [
+        ("HOME", root.as_path()),
+        ("CODEX_HOME", codex_home.as_path()),
+    ];
+    let args = ["codex", "daily", "-j", "-O", "--timezone", "UTC"];
+
+    let (ok, stdout, stderr) = run_ccstats(&args, &envs);
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("valid json");
+    let day = &json[0];
+    assert_eq!(day["currency"], "EUR");
+    assert_eq!(day["exchange_rate_source"], "file");
+    // 2026-02-06 converts at the 2026-02-01 rate, not the latest one.
+    assert_eq!(day["exchange_rate"], 0.5);
+    let cost = day["cost"].as_f64().expect("cost");
+    assert!((cost - 0.000_191).abs() < 1e-9, "cost {cost}");
+
+    let session_args = ["codex", "session", "-j", "-O", "--timezone", "UTC"];
+    let (ok, stdout, stderr) = run_ccstats(&session_args, &envs);
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("valid json");
+    let session = &json[0];
+    assert_eq!(session["currency"], "EUR");
+    assert_eq!(session["exchange_rate_source"], "file");
+    assert_eq!(session["exchange_rate"], 0.5);
+    assert_eq!(session["cost"], day["cost"]);
+
+    write_config(
+        &root,
+        &format!(
+            "currency = \"EUR\"\n{providers}\n[exchange_rates]\nfixed_rates = {{ EUR = 0.9 }}\n"
+        ),
+    );
+    let (ok, stdout, stderr) = run_ccstats(&args, &envs);
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("valid json");
+    assert_eq!(json[0]["exchange_rate_source"], "fixed");
+    assert_eq!(json[0]["exchange_rate"], 0.9);
+
+    write_config(
+        &root,
+        &format!(
+            "currency = \"JPY\"\n{providers}\n[exchange_rates]\nrates_file = {:?}\n",
+            rates.display().to_string()
+        ),
+    );
+    let (ok, _, stderr) = run_ccstats(&args, &envs);
+    assert!(!ok);
+    assert!(String::from_utf8_lossy(&stderr).contains("no rates for 'JPY'"));
+
+    let _ = fs::remove_dir_all(root);
+}
@@ -4,8 +4,8 @@ use std::sync::Mutex;
 
 use ccstats::{
     CodexQuotaError, CodexQuotaStatus, CostSummary, MultiSummaryOptions, SummaryOptions,
-    UsageRange, UsageSource, estimate_codex_weekly_value, load_codex_weekly_quota, summarize_cost,
-    summarize_cost_ranges,
+    UsageRange, UsageSource, estimate_codex_weekly_value, load_codex_quota_windows,
+    load_codex_weekly_quota, summarize_cost, summarize_cost_ranges,
 };
 use chrono::{Datelike, Days, Duration, NaiveDate, Timelike, Utc};
 
@@ -53,6 +53,50 @@ fn sdk_loads_codex_weekly_quota_from_explicit_home() {
     );
 }
 
+#[test]
+fn sdk_loads_every_codex_quota_window() {
+    let root = tempfile::tempdir().expect("temp dir");
+    let codex_home = root.path().join("codex-home");
+    let now = Utc::now();
+    let observed_at = now - Duration::hours(1);
+    let event = serde_json::json!({
+        "timestamp": observed_at.to_rfc3339(),
+        "type": "event_msg",
+        "payload": {
+            "type": "token_count",
+            "rate_limits": {
+                "primary": {
+                    "used_percent": 60.0,
+                    "window_minutes": 300,
+                    "resets_at": (now + Duration::hours(2)).timestamp(),
+                },
+                "secondary": {
+                    "used_percent": 25.0,
+                    "window_minutes": 10_080,
+                    "resets_at": (now + Duration::days(6)).timestamp(),
+                }
+            }
+        }
+    });
+    write_file(
+        &codex_home.join("sessions").join("quota.jsonl"),
+        &format!("{event}\n"),
+    );
+
+    let windows = load_codex_quota_windows(Some(&codex_home)).expect("load quota windows");
+
+    assert_eq!(windows.len(), 2);
+    assert_eq!(windows[0].window_minutes, 300);
+    assert_eq!(windows[0].used_pct, 60.0);
+    assert_eq!(windows[0].status, CodexQuotaStatus::LikelyExhausted);
+    assert!(windows[0].estimated_depletion_at.is_some());
+    assert_eq!(windows[1].window_minutes, 10_080);
+    assert_eq!(
+        windows[1],
+        load_codex_weekly_quota(Some(&codex_home)).expect("load weekly quota")
+    );
+}
+
 #[test]
 fn sdk_estimates_codex_weekly_api_equivalent_value() {
     let root = tempfile::tempdir().expect("temp dir");
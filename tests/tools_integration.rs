@@ -101,19 +101,300 @@ fn tools_command_reads_claude_config_dir_tool_calls() {
 #[test]
 fn tools_command_rejects_source_without_tool_call_capability() {
     let root = unique_temp_dir("tools-capability-gate");
-    let codex_home = root.join("codex-home");
 
-    let (ok, stdout, stderr) = run_ccstats(
-        &["tools", "--source", "codex"],
-        &[("HOME", &root), ("CODEX_HOME", &codex_home)],
-    );
+    let (ok, stdout, stderr) = run_ccstats(&["tools", "--source", "cursor"], &[("HOME", &root)]);
     assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
     assert!(
-        String::from_utf8_lossy(&stdout)
-            .contains("Tool usage analysis is only supported for Claude source."),
+        String::from_utf8_lossy(&stdout).contains("Cursor does not record tool calls."),
         "stdout: {}",
         String::from_utf8_lossy(&stdout)
     );
 
     let _ = fs::remove_dir_all(root);
 }
+
+#[test]
+fn tools_command_reports_tool_calls_across_agents() {
+    let root = unique_temp_dir("tools-all-sources");
+    write_file(
+        &root.join(".claude/projects/-work-myapp/session-a.jsonl"),
+        r#"{"type":"assistant","timestamp":"2026-02-06T12:00:00Z","message":{"id":"msg_1","content":[{"type":"tool_use","name":"Bash","id":"t1","input":{}},{"type":"tool_use","name":"Grep","id":"t2","input":{}}]}}
+"#,
+    );
+    write_file(
+        &root.join(".codex/sessions/2026/02/06/rollout-a.jsonl"),
+        r#"{"timestamp":"2026-02-06T12:00:00Z","type":"session_meta","payload":{"id":"s1","cwd":"/work/myapp"}}
+{"timestamp":"2026-02-06T12:00:01Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{}","call_id":"call_1"}}
+{"timestamp":"2026-02-06T12:00:02Z","type":"response_item","payload":{"type":"custom_tool_call","name":"apply_patch","input":"","call_id":"call_2"}}
+"#,
+    );
+    let codex_home = root.join(".codex");
+    let kimi_home = root.join(".kimi-code");
+    let grok_home = root.join(".grok");
+    let envs = [
+        ("HOME", root.as_path()),
+        ("CODEX_HOME", codex_home.as_path()),
+        ("KIMI_CODE_HOME", kimi_home.as_path()),
+        ("GROK_HOME", grok_home.as_path()),
+    ];
+    let args = [
+        "tools",
+        "--source",
+        "all",
+        "-j",
+        "--offline",
+        "--timezone",
+        "UTC",
+    ];
+
+    let (ok, stdout, stderr) = run_ccstats(&args, &envs);
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    assert_eq!(json["group_by"].as_str(), Some("source"));
+    assert_eq!(json["total"].as_u64(), Some(4));
+    let rows: Vec<(&str, &str)> = json["tools"]
+        .as_array()
+        .expect("tools")
+        .iter()
+        .map(|row| {
+            (
+                row["source"].as_str().unwrap(),
+                row["name"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert!(rows.contains(&("claude", "Bash")), "{rows:?}");
+    assert!(rows.contains(&("codex", "shell")), "{rows:?}");
+
+    let (ok, stdout, _) = run_ccstats(&[&args[..], &["--group-by", "category"]].concat(), &envs);
+    assert!(ok);
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    let shell_calls: u64 = json["tools"]
+        .as_array()
+        .expect("tools")
+        .iter()
+        .filter(|row| row["category"] == "shell")
+        .map(|row| row["calls"].as_u64().unwrap())
+        .sum();
+    assert_eq!(shell_calls, 2);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn tools_command_attributes_tokens_per_project_and_day() {
+    let root = unique_temp_dir("tools-attribution");
+    let session = root.join(".claude/projects/-work-myapp/session-a.jsonl");
+    write_file(
+        &session,
+        r#"{"type":"assistant","timestamp":"2026-02-06T12:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":100,"output_tokens":20},"content":[{"type":"tool_use","name":"Read","id":"tool_read","input":{}}]}}
+{"type":"user","timestamp":"2026-02-06T12:00:01Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"tool_read","content":"0123456789"}]}}
+{"type":"assistant","timestamp":"2026-02-06T12:00:02Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","usage":{"input_tokens":400,"output_tokens":10},"content":[{"type":"text","text":"done"}]}}
+"#,
+    );
+
+    for (group_by, key, value) in [
+        ("project", "project", "work-myapp"),
+        ("day", "date", "2026-02-06"),
+    ] {
+        let (ok, stdout, stderr) = run_ccstats(
+            &[
+                "tools",
+                "-j",
+                "--offline",
+                "--timezone",
+                "UTC",
+                "--group-by",
+                group_by,
+            ],
+            &[("HOME", &root)],
+        );
+        assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+
+        let json: Value = serde_json::from_slice(&stdout).expect("json");
+        assert_eq!(json["group_by"].as_str(), Some(key));
+        let tools = json["tools"].as_array().expect("tools");
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0][key].as_str(), Some(value));
+        assert_eq!(tools[0]["name"].as_str(), Some("Read"));
+        assert_eq!(tools[0]["avg_result_bytes"].as_u64(), Some(10));
+        // The call gets its message's output and the prompt growth its result caused.
+        assert_eq!(tools[0]["output_tokens"].as_i64(), Some(20));
+        assert_eq!(tools[0]["input_tokens"].as_i64(), Some(280));
+        assert!(tools[0]["cost"].as_f64().expect("cost") > 0.0);
+    }
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn tools_command_attributed_cost_stays_within_the_session_cost() {
+    let root = unique_temp_dir("tools-attribution-cost");
+    let session = root.join(".claude/projects/-work-myapp/session-a.jsonl");
+    // The second request reads most of its prompt from a cache warmed
+    // elsewhere, so its growth is billed as cheap cache reads, not fresh input.
+    write_file(
+        &session,
+        r#"{"type":"assistant","timestamp":"2026-02-06T12:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":1000,"output_tokens":100},"content":[{"type":"tool_use","name":"Bash","id":"tool_bash","input":{"command":"ls"}},{"type":"tool_use","name":"Read","id":"tool_read","input":{}}]}}
+{"type":"user","timestamp":"2026-02-06T12:00:01Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"tool_bash","content":"0123456789"},{"type":"tool_result","tool_use_id":"tool_read","content":"01234567890123456789"}]}}
+{"type":"assistant","timestamp":"2026-02-06T12:00:02Z","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","usage":{"input_tokens":3,"cache_creation_input_tokens":2000,"cache_read_input_tokens":50000,"output_tokens":10},"content":[{"type":"tool_use","name":"Bash","id":"tool_bash_2","input":{"command":"pwd"}}]}}
+{"type":"user","timestamp":"2026-02-06T12:00:03Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"tool_bash_2","content":"/work/myapp"}]}}
+{"type":"assistant","timestamp":"2026-02-06T12:00:04Z","message":{"id":"msg_3","model":"claude-sonnet-4-20250514","usage":{"input_tokens":3,"cache_creation_input_tokens":500,"cache_read_input_tokens":52003,"output_tokens":20},"content":[{"type":"text","text":"done"}]}}
+"#,
+    );
+    let cost_of = |command: &str| {
+        let (ok, stdout, stderr) = run_ccstats(
+            &[command, "-j", "--offline", "--timezone", "UTC"],
+            &[("HOME", &root)],
+        );
+        assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+        serde_json::from_slice::<Value>(&stdout).expect("json")
+    };
+
+    let tools = cost_of("tools");
+    let session_cost = cost_of("daily")[0]["cost"].as_f64().expect("session cost");
+    let tools_cost = tools["cost"].as_f64().expect("tools cost");
+    assert!(tools_cost > 0.0);
+    assert!(
+        tools_cost <= session_cost,
+        "tools cost {tools_cost} exceeds session cost {session_cost}"
+    );
+    for tool in tools["tools"].as_array().expect("tools") {
+        assert!(tool["cost"].as_f64().expect("cost") <= session_cost);
+    }
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn tools_command_groups_mcp_tools_by_server() {
+    let root = unique_temp_dir("tools-mcp-server");
+    write_file(
+        &root.join(".claude/projects/-work-myapp/session-a.jsonl"),
+        r#"{"type":"assistant","timestamp":"2026-02-05T12:00:00Z","message":{"id":"msg_1","content":[{"type":"tool_use","name":"mcp__github__create_issue","id":"t1","input":{}},{"type":"tool_use","name":"Task","id":"t2","input":{"subagent_type":"code-reviewer"}}]}}
+{"type":"assistant","timestamp":"2026-02-06T12:00:00Z","message":{"id":"msg_2","content":[{"type":"tool_use","name":"mcp__github__create_issue","id":"t3","input":{}},{"type":"tool_use","name":"Read","id":"t4","input":{}}]}}
+"#,
+    );
+    let args = [
+        "tools",
+        "--offline",
+        "--timezone",
+        "UTC",
+        "--group-by",
+        "server",
+    ];
+
+    let (ok, stdout, stderr) = run_ccstats(&[&args[..], &["-j"]].concat(), &[("HOME", &root)]);
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    let rows: Vec<(&str, &str, u64)> = json["tools"]
+        .as_array()
+        .expect("tools")
+        .iter()
+        .map(|row| {
+            (
+                row["server"].as_str().unwrap(),
+                row["name"].as_str().unwrap(),
+                row["calls"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            ("built-in", "Read", 1),
+            ("github", "create_issue", 2),
+            ("subagents", "code-reviewer", 1)
+        ]
+    );
+    assert_eq!(json["tools"][1]["last_used"].as_str(), Some("2026-02-06"));
+    assert_eq!(json["tools"][1]["daily"].as_array().map(Vec::len), Some(2));
+
+    let (ok, stdout, _) = run_ccstats(&[&args[..], &["--csv"]].concat(), &[("HOME", &root)]);
+    assert!(ok);
+    let csv = String::from_utf8_lossy(&stdout);
+    assert!(csv.contains("server,tool,date,calls\n"), "csv: {csv}");
+    assert!(
+        csv.contains("github,create_issue,2026-02-05,1\n"),
+        "csv: {csv}"
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn tools_commands_ranks_shell_commands_and_flags_risky_ones() {
+    let root = unique_temp_dir("tools-commands");
+    write_file(
+        &root.join(".claude/projects/-work-myapp/session-a.jsonl"),
+        r#"{"type":"assistant","timestamp":"2026-02-06T12:00:00Z","message":{"id":"msg_1","content":[{"type":"tool_use","name":"Bash","id":"t1","input":{"command":"cargo test --workspace"}},{"type":"tool_use","name":"Bash","id":"t2","input":{"command":"cargo test -p core && git push --force"}}]}}
+"#,
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &["tools", "commands", "-j", "--offline", "--timezone", "UTC"],
+        &[("HOME", &root)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    assert_eq!(json["total"].as_u64(), Some(2));
+    assert_eq!(json["commands"][0]["project"].as_str(), Some("work-myapp"));
+    assert_eq!(json["commands"][0]["command"].as_str(), Some("cargo test"));
+    assert_eq!(json["commands"][0]["runs"].as_u64(), Some(2));
+    assert_eq!(json["commands"][1]["command"].as_str(), Some("git push"));
+    assert_eq!(
+        json["risky"][0]["pattern"].as_str(),
+        Some("git push --force")
+    );
+
+    let (ok, stdout, _) = run_ccstats(
+        &["tools", "commands", "--offline", "--timezone", "UTC"],
+        &[("HOME", &root)],
+    );
+    assert!(ok);
+    let table = String::from_utf8_lossy(&stdout);
+    assert!(table.contains("Risky commands"), "{table}");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn files_command_reports_reads_writes_and_hot_directories() {
+    let root = unique_temp_dir("files");
+    write_file(
+        &root.join(".claude/projects/-work-myapp/session-a.jsonl"),
+        r#"{"type":"assistant","cwd":"/work/myapp","timestamp":"2026-02-06T12:00:00Z","message":{"id":"msg_1","content":[{"type":"tool_use","name":"Read","id":"t1","input":{"file_path":"/work/myapp/src/main.rs"}},{"type":"tool_use","name":"Edit","id":"t2","input":{"file_path":"/work/myapp/src/main.rs","old_string":"a","new_string":"b"}}]}}
+"#,
+    );
+    write_file(
+        &root.join(".claude/projects/-work-myapp/session-b.jsonl"),
+        r#"{"type":"assistant","cwd":"/work/myapp","timestamp":"2026-02-06T13:00:00Z","message":{"id":"msg_2","content":[{"type":"tool_use","name":"Read","id":"t3","input":{"file_path":"/work/myapp/src/main.rs"}},{"type":"tool_use","name":"Write","id":"t4","input":{"file_path":"/work/myapp/src/lib.rs","content":""}}]}}
+"#,
+    );
+
+    let (ok, stdout, stderr) = run_ccstats(
+        &["files", "-j", "--offline", "--timezone", "UTC"],
+        &[("HOME", &root)],
+    );
+    assert!(ok, "stderr: {}", String::from_utf8_lossy(&stderr));
+    let json: Value = serde_json::from_slice(&stdout).expect("json");
+    assert_eq!(json["total"].as_u64(), Some(4));
+    let main = &json["files"][0];
+    assert_eq!(main["project"].as_str(), Some("work-myapp"));
+    assert_eq!(main["path"].as_str(), Some("src/main.rs"));
+    assert_eq!(main["reads"].as_u64(), Some(2));
+    assert_eq!(main["writes"].as_u64(), Some(1));
+    assert_eq!(main["sessions"].as_u64(), Some(2));
+    assert_eq!(json["directories"][0]["directory"].as_str(), Some("src"));
+    assert_eq!(json["directories"][0]["files"].as_u64(), Some(2));
+
+    let (ok, stdout, _) = run_ccstats(
+        &["files", "--offline", "--timezone", "UTC"],
+        &[("HOME", &root)],
+    );
+    assert!(ok);
+    let table = String::from_utf8_lossy(&stdout);
+    assert!(table.contains("Hot directories"), "{table}");
+
+    let _ = fs::remove_dir_all(root);
+}